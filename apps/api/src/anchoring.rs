@@ -0,0 +1,86 @@
+//! Inline (synchronous) anchoring for evidence submissions
+//!
+//! By default evidence is queued in the outbox and anchored asynchronously by
+//! the keeper. Callers that need a transaction reference in the response can
+//! request `anchor_mode: "sync"`, which anchors inline through the provider
+//! configured here. Because this ties up the request for the duration of the
+//! chain round-trip, it is disabled unless explicitly enabled.
+//!
+//! # Configuration
+//!
+//! | Variable | Default | Description |
+//! |----------|---------|-------------|
+//! | `API_SYNC_ANCHOR_ENABLED` | `false` | Capability flag for `anchor_mode: "sync"` |
+//! | `API_SYNC_ANCHOR_PROVIDER` | `etherlink-stub` | Provider spec, as for `KEEPER_ANCHOR_PROVIDER` |
+//! | `API_SYNC_ANCHOR_TIMEOUT_MS` | `10000` | How long a request waits for the inline anchor |
+//!
+//! The provider is built by the `anchor-providers` factory the keeper uses, so
+//! it reads the same `ETHERLINK_*` / `SOLANA_*` settings. An unknown spec is
+//! a startup error rather than a silent fallback to the stub.
+
+use anchor_providers::{ProviderError, ProviderKind};
+use phoenix_evidence::{
+    anchor::{AnchorError, AnchorProvider},
+    model::{ChainTxRef, EvidenceRecord},
+};
+use std::{sync::Arc, time::Duration};
+
+/// Default timeout for inline anchoring
+pub const DEFAULT_SYNC_ANCHOR_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Provider and timeout used for `anchor_mode: "sync"` submissions
+#[derive(Clone)]
+pub struct SyncAnchor {
+    provider: Arc<dyn AnchorProvider + Send + Sync>,
+    timeout: Duration,
+}
+
+impl SyncAnchor {
+    pub fn new(provider: Arc<dyn AnchorProvider + Send + Sync>, timeout: Duration) -> Self {
+        Self { provider, timeout }
+    }
+
+    /// Build from environment, returning `None` unless the capability is enabled
+    pub fn from_env() -> Result<Option<Self>, ProviderError> {
+        let enabled = std::env::var("API_SYNC_ANCHOR_ENABLED")
+            .map(|v| {
+                matches!(
+                    v.trim().to_lowercase().as_str(),
+                    "true" | "1" | "yes" | "on"
+                )
+            })
+            .unwrap_or(false);
+        if !enabled {
+            return Ok(None);
+        }
+
+        let timeout = std::env::var("API_SYNC_ANCHOR_TIMEOUT_MS")
+            .ok()
+            .and_then(|ms| ms.parse::<u64>().ok())
+            .map(Duration::from_millis)
+            .unwrap_or(DEFAULT_SYNC_ANCHOR_TIMEOUT);
+
+        let kind = match std::env::var("API_SYNC_ANCHOR_PROVIDER") {
+            // `stub` is the spelling this variable accepted before specs
+            Ok(spec) if spec.trim() != "stub" => spec.parse()?,
+            _ => ProviderKind::EtherlinkStub,
+        };
+        let provider = kind.build()?;
+
+        Ok(Some(Self::new(Arc::from(provider), timeout)))
+    }
+
+    /// How long a request waits for [`anchor`](Self::anchor)
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    /// Anchor a record inline
+    ///
+    /// Not bounded by [`timeout`](Self::timeout): cancelling a send midway
+    /// could leave a transaction on chain with no record of it, so callers
+    /// stop waiting instead of dropping the call.
+    pub async fn anchor(&self, record: &EvidenceRecord) -> Result<ChainTxRef, AnchorError> {
+        self.provider.anchor(record).await
+    }
+}
@@ -0,0 +1,92 @@
+//! Cross-origin access for browser clients
+//!
+//! Strict by default: with `API_CORS_ALLOWED_ORIGINS` unset no CORS headers
+//! are sent, so browsers refuse cross-origin calls. Setting it to a comma
+//! list of origins (`http://localhost:3000,https://app.example.com`) lets
+//! exactly those origins call the API from a browser.
+//!
+//! The layer never covers `POST /api/v1/evidence/verify-premium` (or its
+//! `-bulk` variant): premium verification is machine-to-machine, so it stays
+//! unreachable from browsers whatever is configured here (see
+//! [`crate::router_with_cors`]).
+
+use axum::http::{header, HeaderName, HeaderValue, Method};
+use tower_http::cors::{AllowOrigin, CorsLayer};
+
+/// Comma-separated list of origins allowed to call the API from a browser
+pub const ALLOWED_ORIGINS_ENV: &str = "API_CORS_ALLOWED_ORIGINS";
+
+/// Parse a comma-separated origin list, skipping (and logging) entries that
+/// are not valid header values
+pub fn parse_origins(list: &str) -> Vec<HeaderValue> {
+    list.split(',')
+        .map(|origin| origin.trim().trim_end_matches('/'))
+        .filter(|origin| !origin.is_empty())
+        .filter_map(|origin| match HeaderValue::from_str(origin) {
+            Ok(value) => Some(value),
+            Err(_) => {
+                tracing::warn!(%origin, "ignoring invalid CORS origin");
+                None
+            }
+        })
+        .collect()
+}
+
+/// Layer allowing `origins`, or `None` when the list is empty
+pub fn cors_layer(origins: Vec<HeaderValue>) -> Option<CorsLayer> {
+    if origins.is_empty() {
+        return None;
+    }
+    Some(
+        CorsLayer::new()
+            .allow_origin(AllowOrigin::list(origins))
+            .allow_methods([Method::GET, Method::POST, Method::PUT])
+            .allow_headers([
+                header::AUTHORIZATION,
+                header::CONTENT_TYPE,
+                HeaderName::from_static("idempotency-key"),
+                HeaderName::from_static(crate::request_id::REQUEST_ID_HEADER),
+            ])
+            .expose_headers([HeaderName::from_static(
+                crate::request_id::REQUEST_ID_HEADER,
+            )]),
+    )
+}
+
+/// Layer from `API_CORS_ALLOWED_ORIGINS` (`None` when unset or empty)
+pub fn cors_layer_from_env() -> Option<CorsLayer> {
+    let origins = std::env::var(ALLOWED_ORIGINS_ENV)
+        .map(|list| parse_origins(&list))
+        .unwrap_or_default();
+    if !origins.is_empty() {
+        tracing::info!(
+            origins = origins.len(),
+            "CORS enabled for configured origins"
+        );
+    }
+    cors_layer(origins)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_origins() {
+        let origins =
+            parse_origins(" http://localhost:3000/ ,,https://app.example.com,bad\norigin");
+        assert_eq!(
+            origins,
+            [
+                HeaderValue::from_static("http://localhost:3000"),
+                HeaderValue::from_static("https://app.example.com"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_empty_list_is_strict() {
+        assert!(cors_layer(parse_origins("")).is_none());
+        assert!(cors_layer(parse_origins(" , ")).is_none());
+    }
+}
@@ -1,11 +1,74 @@
-use crate::models::{EvidenceIn, EvidenceOut};
-use chrono::Utc;
-use sqlx::{Pool, Row, Sqlite};
+use crate::models::{
+    EvidenceCursor, EvidenceFilter, EvidenceIn, EvidenceOut, EvidenceStatsOut,
+    PaymentReceiptFilter, PaymentReceiptOut, TxRefOut, REFUNDED, REFUND_ELIGIBLE,
+};
+use chrono::{DateTime, Utc};
+use phoenix_evidence::canonical::canonicalize_json;
+use phoenix_evidence::explorer::NetworkInfo;
+use phoenix_evidence::merkle::{MerkleProof, ProofBundle, PROOF_BUNDLE_VERSION};
+use phoenix_evidence::model::ChainTxRef;
+use phoenix_x402::PaymentReceipt;
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Pool, QueryBuilder, Row, Sqlite};
 use uuid::Uuid;
 
 pub async fn create_evidence_job(
     pool: &Pool<Sqlite>,
     body: &EvidenceIn,
+    source: &str,
+) -> Result<(String, u64), sqlx::Error> {
+    insert_evidence_job(pool, body, source, "queued").await
+}
+
+/// Like [`create_evidence_job`], but the job is inserted already claimed
+/// (`in_progress`) for the API to anchor inline, so the keeper can't anchor
+/// it concurrently. Hand it back with [`release_inline_job`] if inline
+/// anchoring fails; if the API dies first, the keeper's reaper requeues it
+/// once it goes stale.
+pub async fn create_inline_evidence_job(
+    pool: &Pool<Sqlite>,
+    body: &EvidenceIn,
+    source: &str,
+) -> Result<(String, u64), sqlx::Error> {
+    insert_evidence_job(pool, body, source, "in_progress").await
+}
+
+/// Queue a job from [`create_inline_evidence_job`] for the keeper
+pub async fn release_inline_job(pool: &Pool<Sqlite>, job_id: &str) -> Result<(), sqlx::Error> {
+    let now = Utc::now().timestamp_millis();
+    sqlx::query(
+        "UPDATE outbox_jobs SET status='queued', updated_ms=?1, next_attempt_ms=?1 WHERE id=?2 AND status='in_progress'",
+    )
+    .bind(now)
+    .bind(job_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Fail a job from [`create_inline_evidence_job`] that the keeper must not
+/// retry, keeping `reason` as its `last_error`
+pub async fn fail_inline_job(
+    pool: &Pool<Sqlite>,
+    job_id: &str,
+    reason: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE outbox_jobs SET status='failed', attempts=attempts+1, last_error=?1, updated_ms=?2 WHERE id=?3 AND status='in_progress'",
+    )
+    .bind(reason)
+    .bind(Utc::now().timestamp_millis())
+    .bind(job_id)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+async fn insert_evidence_job(
+    pool: &Pool<Sqlite>,
+    body: &EvidenceIn,
+    source: &str,
+    status: &str,
 ) -> Result<(String, u64), sqlx::Error> {
     let id = body
         .id
@@ -13,22 +76,90 @@ pub async fn create_evidence_job(
         .unwrap_or_else(|| Uuid::new_v4().to_string());
     let current_timestamp_ms = Utc::now().timestamp_millis();
     let result = sqlx::query(
-        "INSERT OR IGNORE INTO outbox_jobs (id, payload_sha256, status, attempts, created_ms, updated_ms) VALUES (?1, ?2, 'queued', 0, ?3, ?3)"
+        "INSERT OR IGNORE INTO outbox_jobs (id, payload_sha256, status, attempts, created_ms, updated_ms, source, priority, metadata, payload_mime) VALUES (?1, ?2, ?8, 0, ?3, ?3, ?4, ?5, ?6, ?7)"
     )
     .bind(&id)
     .bind(&body.digest_hex)
     .bind(current_timestamp_ms)
+    .bind(source)
+    .bind(body.priority.unwrap_or(0))
+    .bind(body.metadata.as_ref().map(canonicalize_json))
+    .bind(&body.payload_mime)
+    .bind(status)
     .execute(pool)
     .await?;
     Ok((id, result.rows_affected()))
 }
 
+/// Outcome of claiming an `Idempotency-Key`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IdempotencyClaim {
+    /// The key is now bound to the given job id
+    Claimed,
+    /// An unexpired earlier request already holds the key
+    Existing { job_id: String, digest_hex: String },
+}
+
+/// Bind `key` to `job_id` for `ttl_ms`, unless an unexpired binding exists.
+///
+/// The claim is taken before the job is created (keyed on the table's primary
+/// key), so two concurrent requests with the same key cannot both create a
+/// job. Expired keys are purged on the way.
+pub async fn claim_idempotency_key(
+    pool: &Pool<Sqlite>,
+    key: &str,
+    job_id: &str,
+    digest_hex: &str,
+    ttl_ms: i64,
+) -> Result<IdempotencyClaim, sqlx::Error> {
+    let now = Utc::now().timestamp_millis();
+    let mut tx = pool.begin().await?;
+    sqlx::query("DELETE FROM idempotency_keys WHERE expires_ms <= ?1")
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+    let inserted = sqlx::query(
+        "INSERT OR IGNORE INTO idempotency_keys (key, job_id, digest_hex, created_ms, expires_ms) VALUES (?1, ?2, ?3, ?4, ?5)",
+    )
+    .bind(key)
+    .bind(job_id)
+    .bind(digest_hex)
+    .bind(now)
+    .bind(now.saturating_add(ttl_ms))
+    .execute(&mut *tx)
+    .await?
+    .rows_affected();
+    let claim = if inserted > 0 {
+        IdempotencyClaim::Claimed
+    } else {
+        let row = sqlx::query("SELECT job_id, digest_hex FROM idempotency_keys WHERE key = ?1")
+            .bind(key)
+            .fetch_one(&mut *tx)
+            .await?;
+        IdempotencyClaim::Existing {
+            job_id: row.get(0),
+            digest_hex: row.get(1),
+        }
+    };
+    tx.commit().await?;
+    Ok(claim)
+}
+
+/// Drop a claimed key whose request failed, so a retry can use it again
+pub async fn release_idempotency_key(pool: &Pool<Sqlite>, key: &str) -> Result<(), sqlx::Error> {
+    sqlx::query("DELETE FROM idempotency_keys WHERE key = ?1")
+        .bind(key)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
 pub async fn get_evidence_by_id(
     pool: &Pool<Sqlite>,
     id: &str,
 ) -> Result<Option<EvidenceOut>, sqlx::Error> {
     let row = sqlx::query(
-        "SELECT id, payload_sha256, status, attempts, last_error, created_ms, updated_ms FROM outbox_jobs WHERE id=?1"
+        "SELECT id, payload_sha256, status, attempts, last_error, created_ms, updated_ms, source FROM outbox_jobs WHERE id=?1"
     )
     .bind(id)
     .fetch_optional(pool)
@@ -42,43 +173,366 @@ pub async fn get_evidence_by_id(
         last_error: row.get::<Option<String>, _>(4),
         created_ms: row.get::<i64, _>(5),
         updated_ms: row.get::<i64, _>(6),
+        source: row.get::<String, _>(7),
     }))
 }
 
+/// Digest and submitted MIME type of an evidence job, for serving its payload
+pub async fn get_evidence_payload_ref(
+    pool: &Pool<Sqlite>,
+    id: &str,
+) -> Result<Option<(String, Option<String>)>, sqlx::Error> {
+    let row = sqlx::query("SELECT payload_sha256, payload_mime FROM outbox_jobs WHERE id=?1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+    Ok(row.map(|row| (row.get::<String, _>(0), row.get::<Option<String>, _>(1))))
+}
+
+const EVIDENCE_OUT_COLUMNS: &str =
+    "SELECT id, payload_sha256, status, attempts, last_error, created_ms, updated_ms, source FROM outbox_jobs";
+
+/// Append `WHERE` clauses for the filters that are set.
+///
+/// Only set filters are emitted (rather than `?1 IS NULL OR ...`) so SQLite
+/// can pick `idx_outbox_jobs_status` / `idx_outbox_jobs_source` /
+/// `idx_outbox_jobs_event_type`.
+fn push_evidence_filter<'a>(query: &mut QueryBuilder<'a, Sqlite>, filter: &'a EvidenceFilter) {
+    query.push(" WHERE 1 = 1");
+    if let Some(source) = &filter.source {
+        query.push(" AND source = ").push_bind(source.as_str());
+    }
+    if let Some(status) = &filter.status {
+        query.push(" AND status = ").push_bind(status.as_str());
+    }
+    if let Some(since_ms) = filter.since_ms {
+        query.push(" AND created_ms >= ").push_bind(since_ms);
+    }
+    if let Some(event_type) = &filter.event_type {
+        query
+            .push(" AND event_type = ")
+            .push_bind(event_type.as_str());
+    }
+}
+
+fn evidence_out_from_row(row: &SqliteRow) -> EvidenceOut {
+    EvidenceOut {
+        id: row.get::<String, _>(0),
+        digest_hex: row.get::<String, _>(1),
+        status: row.get::<String, _>(2),
+        attempts: row.get::<i64, _>(3),
+        last_error: row.get::<Option<String>, _>(4),
+        created_ms: row.get::<i64, _>(5),
+        updated_ms: row.get::<i64, _>(6),
+        source: row.get::<String, _>(7),
+    }
+}
+
+/// List evidence jobs, newest first, restricted by `filter`
+#[deprecated(note = "OFFSET pages shift under concurrent inserts; use list_evidence_jobs_after")]
 pub async fn list_evidence_jobs(
     pool: &Pool<Sqlite>,
     limit: i64,
     offset: i64,
+    filter: &EvidenceFilter,
 ) -> Result<(Vec<EvidenceOut>, i64), sqlx::Error> {
-    // First, get the total count of jobs
-    let count_row = sqlx::query("SELECT COUNT(*) FROM outbox_jobs")
-        .fetch_one(pool)
-        .await?;
-    let total_count: i64 = count_row.get(0);
+    // First, get the total count of matching jobs
+    let mut count_query = QueryBuilder::new("SELECT COUNT(*) FROM outbox_jobs");
+    push_evidence_filter(&mut count_query, filter);
+    let total_count: i64 = count_query.build().fetch_one(pool).await?.get(0);
 
     // Then, get the paginated list of jobs
+    let mut query = QueryBuilder::new(EVIDENCE_OUT_COLUMNS);
+    push_evidence_filter(&mut query, filter);
+    query
+        .push(" ORDER BY created_ms DESC LIMIT ")
+        .push_bind(limit)
+        .push(" OFFSET ")
+        .push_bind(offset);
+    let rows = query.build().fetch_all(pool).await?;
+
+    Ok((
+        rows.iter().map(evidence_out_from_row).collect(),
+        total_count,
+    ))
+}
+
+/// List up to `limit` evidence jobs after `cursor`, newest first.
+///
+/// Returns the page plus the cursor for the next one (None once exhausted).
+/// One extra row is fetched to tell whether more remain.
+pub async fn list_evidence_jobs_after(
+    pool: &Pool<Sqlite>,
+    cursor: Option<&EvidenceCursor>,
+    limit: i64,
+    filter: &EvidenceFilter,
+) -> Result<(Vec<EvidenceOut>, Option<EvidenceCursor>), sqlx::Error> {
+    let mut query = QueryBuilder::new(EVIDENCE_OUT_COLUMNS);
+    push_evidence_filter(&mut query, filter);
+    if let Some(cursor) = cursor {
+        query
+            .push(" AND (created_ms < ")
+            .push_bind(cursor.created_ms)
+            .push(" OR (created_ms = ")
+            .push_bind(cursor.created_ms)
+            .push(" AND id < ")
+            .push_bind(cursor.id.as_str())
+            .push("))");
+    }
+    query
+        .push(" ORDER BY created_ms DESC, id DESC LIMIT ")
+        .push_bind(limit + 1);
+    let rows = query.build().fetch_all(pool).await?;
+
+    let mut evidence_jobs: Vec<EvidenceOut> = rows.iter().map(evidence_out_from_row).collect();
+    let next_cursor = if evidence_jobs.len() as i64 > limit {
+        evidence_jobs.truncate(limit as usize);
+        evidence_jobs
+            .last()
+            .map(|last| EvidenceCursor::new(last.created_ms, last.id.clone()))
+    } else {
+        None
+    };
+
+    Ok((evidence_jobs, next_cursor))
+}
+
+/// Jobs the keeper dead-lettered after exhausting their attempts, most
+/// recently failed first
+pub async fn list_dead_letter_jobs(
+    pool: &Pool<Sqlite>,
+    limit: i64,
+) -> Result<Vec<EvidenceOut>, sqlx::Error> {
+    let mut query = QueryBuilder::new(EVIDENCE_OUT_COLUMNS);
+    query
+        .push(" WHERE status = 'dead_letter' ORDER BY updated_ms DESC, id DESC LIMIT ")
+        .push_bind(limit);
+    let rows = query.build().fetch_all(pool).await?;
+    Ok(rows.iter().map(evidence_out_from_row).collect())
+}
+
+/// Outbox job counts by status, oldest queued job age and unconfirmed tx refs
+pub async fn get_outbox_stats(
+    pool: &Pool<Sqlite>,
+    now_ms: i64,
+) -> Result<EvidenceStatsOut, sqlx::Error> {
+    let row = sqlx::query(
+        "SELECT
+            COUNT(*),
+            COALESCE(SUM(status = 'queued'), 0),
+            COALESCE(SUM(status = 'in_progress'), 0),
+            COALESCE(SUM(status = 'done'), 0),
+            COALESCE(SUM(status = 'failed'), 0),
+            COALESCE(SUM(status = 'dead_letter'), 0),
+            MIN(CASE WHEN status = 'queued' THEN created_ms END),
+            (SELECT COUNT(*) FROM outbox_tx_refs WHERE confirmed = 0)
+        FROM outbox_jobs",
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok(EvidenceStatsOut {
+        total: row.get(0),
+        queued: row.get(1),
+        in_progress: row.get(2),
+        done: row.get(3),
+        failed: row.get(4),
+        dead_letter: row.get(5),
+        oldest_queued_age_ms: row
+            .get::<Option<i64>, _>(6)
+            .map(|created_ms| (now_ms - created_ms).max(0)),
+        unconfirmed_tx_refs: row.get(7),
+    })
+}
+
+/// Requeue a dead-lettered job with a fresh attempt budget.
+///
+/// Returns false if the job does not exist or is not dead-lettered.
+pub async fn replay_dead_letter_job(pool: &Pool<Sqlite>, id: &str) -> Result<bool, sqlx::Error> {
+    let now_ms = Utc::now().timestamp_millis();
+    let result = sqlx::query(
+        "UPDATE outbox_jobs SET status='queued', attempts=0, next_attempt_ms=0, updated_ms=?1 WHERE id=?2 AND status='dead_letter'",
+    )
+    .bind(now_ms)
+    .bind(id)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Outcome of a manual job retry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JobRetry {
+    /// The job is queued again and due immediately
+    Requeued,
+    NotFound,
+    /// The job exists but is in this status, which is not retryable
+    NotRetryable(String),
+}
+
+/// Requeue a `failed` or dead-lettered job, due immediately.
+///
+/// The attempt counter is kept (so a job that keeps failing still
+/// dead-letters) unless `reset_attempts` is set.
+pub async fn retry_job(
+    pool: &Pool<Sqlite>,
+    id: &str,
+    reset_attempts: bool,
+) -> Result<JobRetry, sqlx::Error> {
+    let now_ms = Utc::now().timestamp_millis();
+    let result = sqlx::query(
+        "UPDATE outbox_jobs SET status='queued', next_attempt_ms=0, updated_ms=?1,
+            attempts=CASE WHEN ?2 THEN 0 ELSE attempts END
+         WHERE id=?3 AND status IN ('failed', 'dead_letter')",
+    )
+    .bind(now_ms)
+    .bind(reset_attempts)
+    .bind(id)
+    .execute(pool)
+    .await?;
+    if result.rows_affected() > 0 {
+        return Ok(JobRetry::Requeued);
+    }
+
+    let status: Option<String> = sqlx::query_scalar("SELECT status FROM outbox_jobs WHERE id=?1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+    Ok(status.map_or(JobRetry::NotFound, JobRetry::NotRetryable))
+}
+
+/// Record an inline anchor result: store the tx ref and mark the job done.
+pub async fn record_tx_ref_and_done(
+    pool: &Pool<Sqlite>,
+    job_id: &str,
+    tx_ref: &phoenix_evidence::model::ChainTxRef,
+) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    sqlx::query(
+        "INSERT OR REPLACE INTO outbox_tx_refs (job_id, network, chain, tx_id, confirmed, timestamp) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+    )
+    .bind(job_id)
+    .bind(&tx_ref.network)
+    .bind(&tx_ref.chain)
+    .bind(&tx_ref.tx_id)
+    .bind(if tx_ref.confirmed { 1 } else { 0 })
+    .bind(tx_ref.timestamp.map(|dt| dt.timestamp()))
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query(
+        "UPDATE outbox_jobs SET status='done', attempts=attempts+1, updated_ms=?1 WHERE id=?2",
+    )
+    .bind(Utc::now().timestamp_millis())
+    .bind(job_id)
+    .execute(&mut *tx)
+    .await?;
+    tx.commit().await?;
+    Ok(())
+}
+
+/// List the chain transaction references for a job.
+///
+/// Results are sorted by network, then chain, then tx_id so multi-chain
+/// responses are deterministic regardless of insertion order.
+pub async fn list_tx_refs_for_job(
+    pool: &Pool<Sqlite>,
+    job_id: &str,
+) -> Result<Vec<TxRefOut>, sqlx::Error> {
     let rows = sqlx::query(
-        "SELECT id, payload_sha256, status, attempts, last_error, created_ms, updated_ms FROM outbox_jobs ORDER BY created_ms DESC LIMIT ?1 OFFSET ?2"
+        "SELECT network, chain, tx_id, confirmed, timestamp FROM outbox_tx_refs WHERE job_id = ?1 ORDER BY network ASC, chain ASC, tx_id ASC"
     )
-    .bind(limit)
-    .bind(offset)
+    .bind(job_id)
     .fetch_all(pool)
     .await?;
 
-    let evidence_jobs = rows
+    Ok(rows
         .into_iter()
-        .map(|row| EvidenceOut {
-            id: row.get::<String, _>(0),
-            digest_hex: row.get::<String, _>(1),
-            status: row.get::<String, _>(2),
-            attempts: row.get::<i64, _>(3),
-            last_error: row.get::<Option<String>, _>(4),
-            created_ms: row.get::<i64, _>(5),
-            updated_ms: row.get::<i64, _>(6),
+        .map(|row| {
+            let network = row.get::<String, _>(0);
+            let chain = row.get::<String, _>(1);
+            let tx_id = row.get::<String, _>(2);
+            let explorer_url =
+                NetworkInfo::new(network.as_str(), chain.as_str()).explorer_url(&tx_id);
+            TxRefOut {
+                network,
+                chain,
+                tx_id,
+                confirmed: row.get::<i64, _>(3) != 0,
+                timestamp: row.get::<Option<i64>, _>(4),
+                explorer_url,
+            }
         })
-        .collect();
+        .collect())
+}
+
+/// Merkle proof state for an evidence job
+#[derive(Debug)]
+pub enum EvidenceProof {
+    /// The job's batch was anchored; the bundle verifies offline
+    Anchored(Box<ProofBundle>),
+    /// The job exists but is not yet batched, or its batch has no transaction
+    Pending,
+    /// The batch was anchored but its transaction is not yet confirmed
+    PendingConfirmation(ChainTxRef),
+}
+
+/// Load the Merkle proof for a job from the batch anchoring tables.
+///
+/// Returns `None` if neither a proof nor an outbox job exists for `job_id`.
+/// With `require_confirmed`, proofs for unconfirmed batches are withheld.
+pub async fn get_evidence_proof_by_job(
+    pool: &Pool<Sqlite>,
+    job_id: &str,
+    require_confirmed: bool,
+) -> Result<Option<EvidenceProof>, sqlx::Error> {
+    let row = sqlx::query(
+        "SELECT p.proof_json, b.merkle_root, b.tx_network, b.tx_chain, b.tx_id, b.tx_confirmed, b.anchored_at, b.construction_version FROM merkle_proofs p JOIN merkle_batches b ON p.batch_id = b.id WHERE p.job_id = ?1"
+    )
+    .bind(job_id)
+    .fetch_optional(pool)
+    .await?;
 
-    Ok((evidence_jobs, total_count))
+    let Some(row) = row else {
+        let job = sqlx::query("SELECT 1 FROM outbox_jobs WHERE id = ?1")
+            .bind(job_id)
+            .fetch_optional(pool)
+            .await?;
+        return Ok(job.map(|_| EvidenceProof::Pending));
+    };
+
+    let tx_network = row.get::<Option<String>, _>(2);
+    let tx_chain = row.get::<Option<String>, _>(3);
+    let tx_id = row.get::<Option<String>, _>(4);
+    let (Some(network), Some(chain), Some(tx_id)) = (tx_network, tx_chain, tx_id) else {
+        return Ok(Some(EvidenceProof::Pending));
+    };
+
+    let tx_ref = ChainTxRef {
+        network: network.clone(),
+        chain: chain.clone(),
+        tx_id,
+        confirmed: row.get::<Option<i64>, _>(5).unwrap_or(0) != 0,
+        timestamp: row
+            .get::<Option<i64>, _>(6)
+            .and_then(DateTime::<Utc>::from_timestamp_millis),
+    };
+    if require_confirmed && !tx_ref.confirmed {
+        return Ok(Some(EvidenceProof::PendingConfirmation(tx_ref)));
+    }
+
+    let proof: MerkleProof = serde_json::from_str(&row.get::<String, _>(0))
+        .map_err(|e| sqlx::Error::Decode(Box::new(e)))?;
+
+    Ok(Some(EvidenceProof::Anchored(Box::new(ProofBundle {
+        version: PROOF_BUNDLE_VERSION,
+        job_id: job_id.to_string(),
+        network,
+        chain,
+        merkle_root: row.get::<String, _>(1),
+        construction_version: row.get::<i64, _>(7) as u8,
+        proof,
+        tx_ref,
+    }))))
 }
 
 // Countermeasure Deployment functions
@@ -364,47 +818,41 @@ pub async fn is_payment_signature_used(
 }
 
 /// Store a payment receipt for audit trail
+///
+/// `amount_usdc` is the exact amount paid; `overpaid_usdc` is the part of it
+/// beyond the price, if any.
 pub async fn create_payment_receipt(
     pool: &Pool<Sqlite>,
-    evidence_id: &str,
-    tx_signature: &str,
-    amount_usdc: &str,
-    tier: &str,
-    sender_wallet: Option<&str>,
+    receipt: &PaymentReceipt,
 ) -> Result<String, sqlx::Error> {
     let id = Uuid::new_v4().to_string();
     let current_timestamp_ms = Utc::now().timestamp_millis();
+    let tier = format!("{:?}", receipt.tier).to_lowercase();
 
     sqlx::query(
-        "INSERT INTO payment_receipts (id, evidence_id, tx_signature, amount_usdc, tier, sender_wallet, verified_at, created_ms) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)"
+        "INSERT INTO payment_receipts (id, evidence_id, tx_signature, amount_usdc, tier, sender_wallet, verified_at, created_ms, overpaid_usdc, token, token_amount) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)"
     )
     .bind(&id)
-    .bind(evidence_id)
-    .bind(tx_signature)
-    .bind(amount_usdc)
+    .bind(&receipt.evidence_id)
+    .bind(&receipt.tx_signature)
+    .bind(&receipt.amount_usdc)
     .bind(tier)
-    .bind(sender_wallet)
+    .bind(&receipt.sender_wallet)
     .bind(current_timestamp_ms)
     .bind(current_timestamp_ms)
+    .bind(&receipt.overpaid_usdc)
+    .bind(&receipt.token)
+    .bind(&receipt.token_amount)
     .execute(pool)
     .await?;
 
     Ok(id)
 }
 
-/// Get payment receipt by transaction signature
-pub async fn get_payment_receipt_by_signature(
-    pool: &Pool<Sqlite>,
-    tx_signature: &str,
-) -> Result<Option<crate::models::PaymentReceiptOut>, sqlx::Error> {
-    let row = sqlx::query(
-        "SELECT id, evidence_id, tx_signature, amount_usdc, tier, sender_wallet, verified_at, created_ms FROM payment_receipts WHERE tx_signature = ?1"
-    )
-    .bind(tx_signature)
-    .fetch_optional(pool)
-    .await?;
+const PAYMENT_RECEIPT_COLUMNS: &str = "SELECT id, evidence_id, tx_signature, amount_usdc, tier, sender_wallet, verified_at, created_ms, overpaid_usdc, refund_status, token, token_amount FROM payment_receipts";
 
-    Ok(row.map(|row| crate::models::PaymentReceiptOut {
+fn payment_receipt_from_row(row: &SqliteRow) -> PaymentReceiptOut {
+    PaymentReceiptOut {
         id: row.get::<String, _>(0),
         evidence_id: row.get::<String, _>(1),
         tx_signature: row.get::<String, _>(2),
@@ -413,7 +861,188 @@ pub async fn get_payment_receipt_by_signature(
         sender_wallet: row.get::<Option<String>, _>(5),
         verified_at: row.get::<i64, _>(6),
         created_ms: row.get::<i64, _>(7),
-    }))
+        overpaid_usdc: row.get::<Option<String>, _>(8),
+        refund_status: row.get::<Option<String>, _>(9),
+        token: row.get::<String, _>(10),
+        token_amount: row.get::<Option<String>, _>(11),
+    }
+}
+
+/// Get payment receipt by transaction signature
+pub async fn get_payment_receipt_by_signature(
+    pool: &Pool<Sqlite>,
+    tx_signature: &str,
+) -> Result<Option<PaymentReceiptOut>, sqlx::Error> {
+    let row = sqlx::query(&format!(
+        "{} WHERE tx_signature = ?1",
+        PAYMENT_RECEIPT_COLUMNS
+    ))
+    .bind(tx_signature)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.as_ref().map(payment_receipt_from_row))
+}
+
+/// List payment receipts verified within `[from, to]` (ms, both optional), oldest first
+pub async fn list_payment_receipts_in_range(
+    pool: &Pool<Sqlite>,
+    from: Option<i64>,
+    to: Option<i64>,
+) -> Result<Vec<PaymentReceiptOut>, sqlx::Error> {
+    let rows = sqlx::query(&format!(
+        "{} WHERE (?1 IS NULL OR verified_at >= ?1) AND (?2 IS NULL OR verified_at <= ?2) ORDER BY verified_at ASC, id ASC",
+        PAYMENT_RECEIPT_COLUMNS
+    ))
+    .bind(from)
+    .bind(to)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.iter().map(payment_receipt_from_row).collect())
+}
+
+/// Flag a paid request that was not served as owed a refund.
+///
+/// Returns false if the receipt is unknown or already has a refund status.
+pub async fn mark_refund_eligible(
+    pool: &Pool<Sqlite>,
+    tx_signature: &str,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "UPDATE payment_receipts SET refund_status = ?1 WHERE tx_signature = ?2 AND refund_status IS NULL",
+    )
+    .bind(REFUND_ELIGIBLE)
+    .bind(tx_signature)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Record that an eligible refund was paid out.
+///
+/// Returns false unless the receipt was refund-eligible.
+pub async fn mark_refunded(pool: &Pool<Sqlite>, tx_signature: &str) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "UPDATE payment_receipts SET refund_status = ?1 WHERE tx_signature = ?2 AND refund_status = ?3",
+    )
+    .bind(REFUNDED)
+    .bind(tx_signature)
+    .bind(REFUND_ELIGIBLE)
+    .execute(pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// List receipts with the given refund status, oldest first
+pub async fn list_payment_receipts_by_refund_status(
+    pool: &Pool<Sqlite>,
+    refund_status: &str,
+) -> Result<Vec<PaymentReceiptOut>, sqlx::Error> {
+    let rows = sqlx::query(&format!(
+        "{} WHERE refund_status = ?1 ORDER BY verified_at ASC, id ASC",
+        PAYMENT_RECEIPT_COLUMNS
+    ))
+    .bind(refund_status)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.iter().map(payment_receipt_from_row).collect())
+}
+
+/// List up to `limit` payment receipts matching `filter` after `cursor`,
+/// newest first (by `created_ms`, then id).
+///
+/// Returns the page plus the cursor for the next one (None once exhausted).
+pub async fn list_payment_receipts_after(
+    pool: &Pool<Sqlite>,
+    filter: &PaymentReceiptFilter,
+    cursor: Option<&EvidenceCursor>,
+    limit: i64,
+) -> Result<(Vec<PaymentReceiptOut>, Option<EvidenceCursor>), sqlx::Error> {
+    let mut query = QueryBuilder::new(PAYMENT_RECEIPT_COLUMNS);
+    query.push(" WHERE 1 = 1");
+    if let Some(evidence_id) = &filter.evidence_id {
+        query
+            .push(" AND evidence_id = ")
+            .push_bind(evidence_id.as_str());
+    }
+    if let Some(tier) = &filter.tier {
+        query.push(" AND tier = ").push_bind(tier.as_str());
+    }
+    if let Some(since_ms) = filter.since_ms {
+        query.push(" AND verified_at >= ").push_bind(since_ms);
+    }
+    if let Some(cursor) = cursor {
+        query
+            .push(" AND (created_ms < ")
+            .push_bind(cursor.created_ms)
+            .push(" OR (created_ms = ")
+            .push_bind(cursor.created_ms)
+            .push(" AND id < ")
+            .push_bind(cursor.id.as_str())
+            .push("))");
+    }
+    query
+        .push(" ORDER BY created_ms DESC, id DESC LIMIT ")
+        .push_bind(limit + 1);
+    let rows = query.build().fetch_all(pool).await?;
+
+    let mut receipts: Vec<PaymentReceiptOut> = rows.iter().map(payment_receipt_from_row).collect();
+    let next_cursor = if receipts.len() as i64 > limit {
+        receipts.truncate(limit as usize);
+        receipts
+            .last()
+            .map(|last| EvidenceCursor::new(last.created_ms, last.id.clone()))
+    } else {
+        None
+    };
+    Ok((receipts, next_cursor))
+}
+
+/// Store a paid verification result until `expires_ms`, clearing out
+/// results that have already expired.
+pub async fn store_verification_result(
+    pool: &Pool<Sqlite>,
+    id: &str,
+    evidence_id: &str,
+    result: &serde_json::Value,
+    expires_ms: i64,
+) -> Result<(), sqlx::Error> {
+    let now = Utc::now().timestamp_millis();
+    sqlx::query("DELETE FROM verification_results WHERE expires_ms <= ?1")
+        .bind(now)
+        .execute(pool)
+        .await?;
+    sqlx::query(
+        "INSERT INTO verification_results (id, evidence_id, result, created_ms, expires_ms) VALUES (?1, ?2, ?3, ?4, ?5)",
+    )
+    .bind(id)
+    .bind(evidence_id)
+    .bind(result.to_string())
+    .bind(now)
+    .bind(expires_ms)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+/// Remove and return a stored verification result, unless it has expired.
+///
+/// Each result is handed out at most once.
+pub async fn take_verification_result(
+    pool: &Pool<Sqlite>,
+    id: &str,
+    now_ms: i64,
+) -> Result<Option<serde_json::Value>, sqlx::Error> {
+    let row = sqlx::query(
+        "DELETE FROM verification_results WHERE id = ?1 AND expires_ms > ?2 RETURNING result",
+    )
+    .bind(id)
+    .bind(now_ms)
+    .fetch_optional(pool)
+    .await?;
+    Ok(row.and_then(|row| serde_json::from_str(&row.get::<String, _>("result")).ok()))
 }
 
 // User Management functions
@@ -553,6 +1182,17 @@ pub async fn create_session(
     Ok(session_id)
 }
 
+/// Whether `session_id` names an unexpired session
+pub async fn is_active_session(pool: &Pool<Sqlite>, session_id: &str) -> Result<bool, sqlx::Error> {
+    let found =
+        sqlx::query_scalar::<_, i64>("SELECT 1 FROM sessions WHERE id = ?1 AND expires_at > ?2")
+            .bind(session_id)
+            .bind(Utc::now().timestamp_millis())
+            .fetch_optional(pool)
+            .await?;
+    Ok(found.is_some())
+}
+
 /// Validate session and return user if valid
 pub async fn get_user_by_session(
     pool: &Pool<Sqlite>,
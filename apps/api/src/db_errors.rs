@@ -11,10 +11,26 @@
 //! | SQLite     | 19                     | code() + message validation |
 //! | PostgreSQL | 23505                  | code() |
 //! | MySQL      | 1062                   | code() |
+//! | Cosmos DB  | HTTP 409               | [`is_conflict_status`], surfaced as `ProviderError::Conflict` |
 //!
+//! Callers that don't care which backend produced an error use the
+//! [`UniqueViolation`] trait, implemented for `sqlx::Error` and the provider
+//! layer's `ProviderError`.
 //! # Usage
 //!
 //! ```rust,ignore
+//! use crate::db_errors::UniqueViolation;
+//!
+//! if let Err(e) = create_record(&pool, &data).await {
+//!     if e.is_unique_violation() {
+//!         // Handle duplicate key
+//!     }
+//! }
+//! ```
+//!
+//! or, for a raw driver error:
+//!
+//! ```rust,ignore
 //! use crate::db_errors::is_unique_constraint_violation;
 //!
 //! match create_record(&pool, &data).await {
@@ -40,6 +56,7 @@
 //! generic constraint code 19. The connection setup in `lib.rs` enables this
 //! automatically.
 
+use crate::providers::ProviderError;
 use sqlx::error::DatabaseError;
 
 /// SQLite error codes for unique constraint violations
@@ -64,6 +81,40 @@ mod mysql_codes {
     pub const DUP_ENTRY: &str = "1062";
 }
 
+/// Cosmos DB status codes
+mod cosmos_codes {
+    /// 409 Conflict: an item with the same id (or unique key) exists
+    pub const CONFLICT: u16 = 409;
+}
+
+/// Whether an error is the store rejecting a duplicate key, whichever backend
+/// raised it
+pub trait UniqueViolation {
+    fn is_unique_violation(&self) -> bool;
+}
+
+impl UniqueViolation for sqlx::Error {
+    fn is_unique_violation(&self) -> bool {
+        match self {
+            sqlx::Error::Database(db_err) => is_unique_constraint_violation(db_err.as_ref()),
+            _ => false,
+        }
+    }
+}
+
+impl UniqueViolation for ProviderError {
+    /// Providers map their backend's duplicate signal (SQLSTATE 23505, Cosmos
+    /// 409) to `Conflict`
+    fn is_unique_violation(&self) -> bool {
+        matches!(self, ProviderError::Conflict(_))
+    }
+}
+
+/// Whether a Cosmos DB HTTP status reports a conflicting (duplicate) item
+pub fn is_conflict_status(status: u16) -> bool {
+    status == cosmos_codes::CONFLICT
+}
+
 /// Detects if a database error represents a unique constraint violation.
 ///
 /// This function provides database-agnostic detection of unique constraint
@@ -387,4 +438,34 @@ mod tests {
         };
         assert!(!is_unique_constraint_violation(&err));
     }
+
+    fn sqlx_error(code: &str, message: &str) -> sqlx::Error {
+        sqlx::Error::Database(Box::new(MockDatabaseError {
+            code: Some(code.to_string()),
+            message: message.to_string(),
+        }))
+    }
+
+    #[test]
+    fn test_sqlx_error_unique_violation_per_backend() {
+        assert!(sqlx_error("2067", "UNIQUE constraint failed: t.c").is_unique_violation());
+        assert!(sqlx_error("23505", "duplicate key value").is_unique_violation());
+        assert!(sqlx_error("1062", "Duplicate entry").is_unique_violation());
+        assert!(!sqlx_error("23503", "foreign key constraint violated").is_unique_violation());
+        assert!(!sqlx::Error::RowNotFound.is_unique_violation());
+    }
+
+    #[test]
+    fn test_cosmos_conflict_status() {
+        assert!(is_conflict_status(409));
+        assert!(!is_conflict_status(404));
+        assert!(!is_conflict_status(412));
+    }
+
+    #[test]
+    fn test_provider_error_unique_violation() {
+        assert!(ProviderError::Conflict("create users/u1: 409".to_string()).is_unique_violation());
+        assert!(!ProviderError::NotFound("u1".to_string()).is_unique_violation());
+        assert!(!ProviderError::Database("disk I/O error".to_string()).is_unique_violation());
+    }
 }
@@ -0,0 +1,212 @@
+//! Detector events: evidence ingestion and live streaming
+//!
+//! Detectors POST their webhook events to `/detections`. Each event is
+//! recorded as an outbox evidence job whose digest is the SHA-256 of the
+//! canonical JSON of the body as received, so anyone holding the event can
+//! recompute it. The event is then published on a broadcast channel and
+//! streamed as JSON to the clients connected to `GET /ws/detections`.
+//!
+//! Producers never wait for subscribers. Each subscriber has a bounded
+//! backlog (`CHANNEL_CAPACITY` events); a client that falls further behind
+//! loses the oldest events and keeps receiving from there.
+
+use crate::{
+    error::ApiError,
+    models::{DetectionEvent, EvidenceIn},
+    repository::EvidenceRepository,
+    AppState,
+};
+use axum::{
+    extract::{
+        ws::{Message, WebSocket, WebSocketUpgrade},
+        State,
+    },
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use phoenix_evidence::hash::sha256_canonical_json;
+use serde_json::json;
+use tokio::sync::broadcast;
+
+/// Events buffered per subscriber before the slowest start losing events
+pub const CHANNEL_CAPACITY: usize = 256;
+
+/// Fan-out of detection events to WebSocket subscribers
+#[derive(Clone)]
+pub struct DetectionBroadcaster {
+    sender: broadcast::Sender<DetectionEvent>,
+}
+
+impl DetectionBroadcaster {
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity.max(1));
+        Self { sender }
+    }
+
+    /// Publish an event, returning how many subscribers will see it
+    pub fn publish(&self, event: DetectionEvent) -> usize {
+        // Sending only fails when nobody is listening
+        self.sender.send(event).unwrap_or(0)
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<DetectionEvent> {
+        self.sender.subscribe()
+    }
+
+    pub fn subscriber_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+}
+
+impl Default for DetectionBroadcaster {
+    fn default() -> Self {
+        Self::new(CHANNEL_CAPACITY)
+    }
+}
+
+/// Evidence source recorded for detector events
+pub const DETECTION_SOURCE: &str = "detector";
+
+/// Record a detector webhook event as evidence and publish it to live subscribers
+///
+/// POST /detections
+#[utoipa::path(
+    post,
+    path = "/detections",
+    tag = "detections",
+    request_body = DetectionEvent,
+    responses(
+        (status = 202, description = "Queued as evidence and published to subscribers", body = serde_json::Value),
+        (status = 400, description = "Not a detection event", body = crate::openapi::ErrorResponse),
+    )
+)]
+pub async fn post_detection(
+    State(state): State<AppState>,
+    Json(body): Json<serde_json::Value>,
+) -> Result<Response, ApiError> {
+    let event: DetectionEvent = serde_json::from_value(body.clone())
+        .map_err(|e| ApiError::validation(format!("invalid detection event: {}", e)))?;
+    let digest_hex = sha256_canonical_json(&body);
+
+    let evidence = EvidenceIn {
+        id: None,
+        digest_hex: digest_hex.clone(),
+        payload_mime: Some("application/json".to_string()),
+        metadata: Some(json!({
+            "event": event.event,
+            "source_id": event.source_id,
+            "frame_number": event.frame_number,
+            "class_id": event.detection.class_id,
+            "class_name": event.detection.class_name,
+            "confidence": event.detection.confidence,
+        })),
+        anchor_mode: None,
+        source: Some(DETECTION_SOURCE.to_string()),
+        priority: None,
+        payload_base64: None,
+    };
+    let id = EvidenceRepository::new(state.pool.clone())
+        .create_evidence_job(&evidence)
+        .await?;
+
+    tracing::debug!(
+        id = %id,
+        source_id = %event.source_id,
+        class = %event.detection.class_name,
+        confidence = event.detection.confidence,
+        "recorded detection event"
+    );
+    let subscribers = state.detections.publish(event);
+    Ok((
+        StatusCode::ACCEPTED,
+        Json(json!({
+            "id": id,
+            "digest_hex": digest_hex,
+            "status": "queued",
+            "subscribers": subscribers,
+        })),
+    )
+        .into_response())
+}
+
+/// Stream detection events to a WebSocket client
+///
+/// GET /ws/detections
+pub async fn detections_ws(State(state): State<AppState>, ws: WebSocketUpgrade) -> Response {
+    // Subscribe before the upgrade completes so nothing published after the
+    // handshake response is missed
+    let events = state.detections.subscribe();
+    ws.on_upgrade(move |socket| stream_detections(socket, events))
+}
+
+async fn stream_detections(mut socket: WebSocket, mut events: broadcast::Receiver<DetectionEvent>) {
+    loop {
+        tokio::select! {
+            event = events.recv() => match event {
+                Ok(event) => {
+                    let text = match serde_json::to_string(&event) {
+                        Ok(text) => text,
+                        Err(e) => {
+                            tracing::error!("failed to serialize detection event: {}", e);
+                            continue;
+                        }
+                    };
+                    if socket.send(Message::Text(text.into())).await.is_err() {
+                        break;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::warn!(skipped, "detection subscriber lagging, events dropped");
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            },
+            incoming = socket.recv() => match incoming {
+                // Clients only listen; anything but a close is ignored
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Detection;
+
+    fn event(frame_number: i32) -> DetectionEvent {
+        DetectionEvent {
+            event: "drone_detected".to_string(),
+            timestamp: "2026-01-01T00:00:00Z".to_string(),
+            frame_number,
+            source_id: "test".to_string(),
+            detection: Detection {
+                class_id: 0,
+                class_name: "drone".to_string(),
+                confidence: 0.9,
+                bbox: vec![0.0, 0.0, 1.0, 1.0],
+                drone_score: 0.9,
+                track_id: None,
+                is_drone: true,
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_slow_subscriber_drops_oldest_without_blocking_publisher() {
+        let broadcaster = DetectionBroadcaster::new(2);
+        let mut slow = broadcaster.subscribe();
+
+        for frame in 0..5 {
+            assert_eq!(broadcaster.publish(event(frame)), 1);
+        }
+
+        assert!(matches!(
+            slow.recv().await,
+            Err(broadcast::error::RecvError::Lagged(3))
+        ));
+        assert_eq!(slow.recv().await.unwrap().frame_number, 3);
+        assert_eq!(slow.recv().await.unwrap().frame_number, 4);
+    }
+}
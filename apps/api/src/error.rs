@@ -0,0 +1,136 @@
+//! Typed API errors with stable HTTP status codes
+//!
+//! Handlers return `Result<_, ApiError>` so every failure maps to a fixed
+//! status and a consistent `{ "error", "details" }` JSON body:
+//!
+//! | Variant      | Status |
+//! |--------------|--------|
+//! | `NotFound`   | 404    |
+//! | `Conflict`   | 409    |
+//! | `Validation` | 400    |
+//! | `Internal`   | 500    |
+//!
+//! Not-found and conflict bodies also carry the resource `id` at the top level
+//! (and `"status": "not_found"`), matching the responses clients already parse.
+
+use crate::{
+    db_errors::is_unique_constraint_violation, providers::ProviderError,
+    repository::RepositoryError,
+};
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde_json::json;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ApiError {
+    #[error("{resource} not found")]
+    NotFound { resource: &'static str, id: String },
+    #[error("{message}")]
+    Conflict { message: String, id: Option<String> },
+    #[error("{0}")]
+    Validation(String),
+    #[error("internal server error")]
+    Internal(String),
+}
+
+impl ApiError {
+    pub fn not_found(resource: &'static str, id: impl Into<String>) -> Self {
+        ApiError::NotFound {
+            resource,
+            id: id.into(),
+        }
+    }
+
+    pub fn conflict(message: impl Into<String>, id: Option<String>) -> Self {
+        ApiError::Conflict {
+            message: message.into(),
+            id,
+        }
+    }
+
+    pub fn validation(message: impl Into<String>) -> Self {
+        ApiError::Validation(message.into())
+    }
+
+    pub fn internal(error: impl std::fmt::Display) -> Self {
+        ApiError::Internal(error.to_string())
+    }
+
+    pub fn status(&self) -> StatusCode {
+        match self {
+            ApiError::NotFound { .. } => StatusCode::NOT_FOUND,
+            ApiError::Conflict { .. } => StatusCode::CONFLICT,
+            ApiError::Validation(_) => StatusCode::BAD_REQUEST,
+            ApiError::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = self.status();
+        let error = self.to_string();
+        let body = match self {
+            ApiError::NotFound { resource, id } => json!({
+                "error": error,
+                "details": { "resource": resource, "id": id },
+                "id": id,
+                "status": "not_found",
+            }),
+            ApiError::Conflict { id: Some(id), .. } => json!({
+                "error": error,
+                "details": { "id": id },
+                "id": id,
+            }),
+            ApiError::Conflict { id: None, .. } | ApiError::Validation(_) => json!({
+                "error": error,
+                "details": null,
+            }),
+            ApiError::Internal(details) => {
+                tracing::error!("internal error: {}", details);
+                json!({ "error": error, "details": details })
+            }
+        };
+        (status, Json(body)).into_response()
+    }
+}
+
+impl From<sqlx::Error> for ApiError {
+    fn from(error: sqlx::Error) -> Self {
+        match &error {
+            sqlx::Error::RowNotFound => ApiError::not_found("resource", ""),
+            sqlx::Error::Database(db_err) if is_unique_constraint_violation(db_err.as_ref()) => {
+                ApiError::conflict(db_err.message().to_string(), None)
+            }
+            _ => ApiError::internal(error),
+        }
+    }
+}
+
+impl From<RepositoryError> for ApiError {
+    fn from(error: RepositoryError) -> Self {
+        match error {
+            RepositoryError::Database(e) => e.into(),
+            RepositoryError::Validation(message) => ApiError::Validation(message),
+            RepositoryError::NotFound(id) => ApiError::not_found("resource", id),
+            RepositoryError::Conflict(message) => ApiError::conflict(message, None),
+        }
+    }
+}
+
+impl From<ProviderError> for ApiError {
+    fn from(error: ProviderError) -> Self {
+        match error {
+            ProviderError::Validation(message) => ApiError::Validation(message),
+            ProviderError::NotFound(id) => ApiError::not_found("resource", id),
+            ProviderError::Conflict(message) => ApiError::conflict(message, None),
+            e @ (ProviderError::Database(_) | ProviderError::Connection(_)) => {
+                ApiError::internal(e)
+            }
+        }
+    }
+}
@@ -1,22 +1,42 @@
 use crate::{
+    anchoring::SyncAnchor,
+    connection::HealthChecker,
     db::{
-        create_countermeasure_deployment, create_evidence_job, create_jamming_operation,
-        create_signal_disruption_audit, get_countermeasure_deployment_by_id, get_evidence_by_id,
-        get_jamming_operation_by_id, get_signal_disruption_audit_by_id,
-        list_countermeasure_deployments, list_evidence_jobs, list_signal_disruption_audits,
+        claim_idempotency_key, create_countermeasure_deployment, create_evidence_job,
+        create_inline_evidence_job, create_jamming_operation, create_signal_disruption_audit,
+        fail_inline_job, get_countermeasure_deployment_by_id, get_evidence_by_id,
+        get_evidence_payload_ref, get_evidence_proof_by_job, get_jamming_operation_by_id,
+        get_outbox_stats, get_signal_disruption_audit_by_id, list_countermeasure_deployments,
+        list_dead_letter_jobs, list_evidence_jobs_after, list_signal_disruption_audits,
+        list_tx_refs_for_job, record_tx_ref_and_done, release_idempotency_key, release_inline_job,
+        replay_dead_letter_job, retry_job, EvidenceProof, IdempotencyClaim, JobRetry,
     },
+    error::ApiError,
+    handlers_x402::require_admin,
+    migrations::MigrationManager,
     models::{
-        CountermeasureDeploymentIn, EvidenceIn, JammingOperationIn, Pagination,
-        SignalDisruptionAuditIn,
+        check_digest_algo, normalize_digest_hex, AnchorMode, CountermeasureDeploymentIn,
+        CursorPagination, EvidenceCursor, EvidenceDetailOut, EvidenceFilter, EvidenceIn,
+        EvidenceStatsOut, JammingOperationIn, Pagination, RetryJobParams, SignalDisruptionAuditIn,
+        EVIDENCE_STATUSES,
     },
+    openapi::ErrorResponse,
+    payload_store::PayloadStoreError,
+    webhooks::{WebhookEvent, WebhookEventType},
     AppState,
 };
 use axum::{
     extract::{Path, Query, State},
-    http::StatusCode,
+    http::{header, HeaderMap, StatusCode},
     response::IntoResponse,
     Json,
 };
+use base64::Engine;
+use phoenix_evidence::{
+    anchor::AnchorError,
+    explorer::explorer_url,
+    model::{ChainTxRef, DigestAlgo, EvidenceDigest, EvidenceRecord},
+};
 use serde::Serialize;
 
 /// Parse pagination parameters and calculate offset
@@ -28,6 +48,99 @@ fn parse_pagination(pagination: Pagination) -> (i64, i64, i64) {
     (page, per_page, offset)
 }
 
+/// Header clients can set to attribute evidence to its originating system
+pub const EVIDENCE_SOURCE_HEADER: &str = "x-evidence-source";
+
+/// Source recorded when neither the body nor the header names one
+pub const DEFAULT_EVIDENCE_SOURCE: &str = "api";
+
+/// Header that makes `POST /evidence` safe to retry
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+
+/// Response header set when a request was answered from an earlier one
+pub const IDEMPOTENT_REPLAY_HEADER: &str = "idempotent-replayed";
+
+/// Longest accepted `Idempotency-Key`
+const MAX_IDEMPOTENCY_KEY_LEN: usize = 255;
+
+/// Writes of an inline anchor's tx ref before the job is failed instead
+const INLINE_RECORD_ATTEMPTS: u32 = 3;
+
+/// The trimmed `Idempotency-Key` header, if one was sent
+fn idempotency_key(headers: &HeaderMap) -> Result<Option<String>, String> {
+    let Some(value) = headers.get(IDEMPOTENCY_KEY_HEADER) else {
+        return Ok(None);
+    };
+    let key = value
+        .to_str()
+        .map_err(|_| "Idempotency-Key must be valid ASCII".to_string())?
+        .trim();
+    if key.is_empty() || key.len() > MAX_IDEMPOTENCY_KEY_LEN {
+        return Err(format!(
+            "Idempotency-Key must be 1-{} characters",
+            MAX_IDEMPOTENCY_KEY_LEN
+        ));
+    }
+    Ok(Some(key.to_string()))
+}
+
+/// Normalise an evidence source label (lowercase, `[a-z0-9._:-]`, max 64 chars)
+fn normalize_evidence_source(raw: &str) -> Result<String, String> {
+    let source = raw.trim().to_ascii_lowercase();
+    let valid_chars = source
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | ':' | '-'));
+    if source.is_empty() || source.len() > 64 || !valid_chars {
+        return Err(format!(
+            "invalid evidence source '{}': expected 1-64 characters of [a-z0-9._:-]",
+            raw
+        ));
+    }
+    Ok(source)
+}
+
+/// Normalise the `source` filter and check `status` is a known job status
+fn validate_evidence_filter(filter: EvidenceFilter) -> Result<EvidenceFilter, String> {
+    let source = filter
+        .source
+        .as_deref()
+        .map(normalize_evidence_source)
+        .transpose()?;
+    let status = match filter.status {
+        Some(status) if EVIDENCE_STATUSES.contains(&status.as_str()) => Some(status),
+        Some(status) => {
+            return Err(format!(
+                "invalid status '{}': expected one of {}",
+                status,
+                EVIDENCE_STATUSES.join(", ")
+            ))
+        }
+        None => None,
+    };
+    Ok(EvidenceFilter {
+        source,
+        status,
+        since_ms: filter.since_ms,
+        event_type: filter.event_type,
+    })
+}
+
+/// Resolve the source for a submission: body field, then header, then "api"
+fn resolve_evidence_source(body: &EvidenceIn, headers: &HeaderMap) -> Result<String, String> {
+    let header = headers
+        .get(EVIDENCE_SOURCE_HEADER)
+        .map(|value| {
+            value
+                .to_str()
+                .map_err(|_| format!("{} must be valid ASCII", EVIDENCE_SOURCE_HEADER))
+        })
+        .transpose()?;
+    match body.source.as_deref().or(header) {
+        Some(raw) => normalize_evidence_source(raw),
+        None => Ok(DEFAULT_EVIDENCE_SOURCE.to_string()),
+    }
+}
+
 /// Create an error response with a given status code and error message
 fn error_response(status: StatusCode, error: impl std::fmt::Display) -> axum::response::Response {
     (
@@ -74,50 +187,558 @@ fn create_paginated_response<T: Serialize>(
     (StatusCode::OK, Json(response)).into_response()
 }
 
+/// Liveness probe: the process is up and serving requests
+#[utoipa::path(
+    get,
+    path = "/health",
+    tag = "health",
+    responses((status = 200, description = "The process is up", body = String, content_type = "text/plain"))
+)]
 pub async fn health() -> &'static str {
     "OK"
 }
 
+/// Readiness probe: the database answers and its schema is current
+///
+/// Returns 200 with each component's status, or 503 naming the component that
+/// is not ready. x402 is reported but never fails readiness, since running
+/// without it is a supported configuration.
+#[utoipa::path(
+    get,
+    path = "/health/ready",
+    tag = "health",
+    responses(
+        (status = 200, description = "Database reachable and schema current", body = serde_json::Value),
+        (status = 503, description = "A component is not ready; `failing` names it", body = serde_json::Value),
+    )
+)]
+pub async fn health_ready(State(state): State<AppState>) -> impl IntoResponse {
+    let mut failing = Vec::new();
+
+    let database = match HealthChecker::check_health(&state.pool).await {
+        Ok(health) => serde_json::json!({
+            "status": "ok",
+            "response_time_ms": health.response_time.as_millis() as u64,
+        }),
+        Err(e) => {
+            failing.push("database");
+            serde_json::json!({ "status": "error", "error": e.to_string() })
+        }
+    };
+
+    let migrations = if failing.is_empty() {
+        match MigrationManager::new(state.pool.clone()).get_status().await {
+            Ok(status) => {
+                if !status.is_up_to_date {
+                    failing.push("migrations");
+                }
+                serde_json::json!({
+                    "status": if status.is_up_to_date { "ok" } else { "pending" },
+                    "current_version": status.current_version,
+                    "latest_version": status.latest_version,
+                })
+            }
+            Err(e) => {
+                failing.push("migrations");
+                serde_json::json!({ "status": "error", "error": e.to_string() })
+            }
+        }
+    } else {
+        serde_json::json!({ "status": "unknown" })
+    };
+
+    let ready = failing.is_empty();
+    let body = serde_json::json!({
+        "status": if ready { "ready" } else { "unavailable" },
+        "failing": failing,
+        "components": {
+            "database": database,
+            "migrations": migrations,
+            "x402": { "enabled": state.x402.is_some() },
+        },
+    });
+    let status = if ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(body))
+}
+
+#[utoipa::path(
+    get,
+    path = "/evidence",
+    tag = "evidence",
+    params(Pagination, CursorPagination, EvidenceFilter),
+    responses(
+        (status = 200, description = "A page of evidence jobs (`next_cursor` with cursor paging)", body = serde_json::Value),
+        (status = 400, description = "Invalid filter or cursor", body = ErrorResponse),
+    )
+)]
 pub async fn list_evidence(
     State(state): State<AppState>,
     Query(pagination): Query<Pagination>,
-) -> impl IntoResponse {
-    let (page, items_per_page, offset) = parse_pagination(pagination);
+    Query(cursor_pagination): Query<CursorPagination>,
+    Query(filter): Query<EvidenceFilter>,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let filter = validate_evidence_filter(filter).map_err(ApiError::Validation)?;
 
-    match list_evidence_jobs(&state.pool, items_per_page, offset).await {
-        Ok((evidence_jobs, total_count)) => {
-            create_paginated_response(evidence_jobs, page, items_per_page, total_count)
-        }
-        Err(db_error) => error_response(StatusCode::INTERNAL_SERVER_ERROR, db_error),
+    // `cursor` or `limit` selects keyset pagination; `page`/`per_page` keep
+    // working for existing clients
+    if cursor_pagination.cursor.is_some() || cursor_pagination.limit.is_some() {
+        return list_evidence_by_cursor(&state, cursor_pagination, &filter).await;
     }
+
+    let (page, items_per_page, offset) = parse_pagination(pagination);
+    #[allow(deprecated)]
+    let (evidence_jobs, total_count) =
+        crate::db::list_evidence_jobs(&state.pool, items_per_page, offset, &filter).await?;
+    Ok(Json(serde_json::json!({
+        "data": evidence_jobs,
+        "page": page,
+        "per_page": items_per_page,
+        "total": total_count,
+    })))
 }
 
+async fn list_evidence_by_cursor(
+    state: &AppState,
+    pagination: CursorPagination,
+    filter: &EvidenceFilter,
+) -> Result<Json<serde_json::Value>, ApiError> {
+    let limit = pagination.limit.unwrap_or(10).clamp(1, 100);
+    let cursor = pagination
+        .cursor
+        .as_deref()
+        .map(EvidenceCursor::decode)
+        .transpose()
+        .map_err(ApiError::validation)?;
+
+    let (evidence_jobs, next_cursor) =
+        list_evidence_jobs_after(&state.pool, cursor.as_ref(), limit, filter).await?;
+    Ok(Json(serde_json::json!({
+        "data": evidence_jobs,
+        "limit": limit,
+        "next_cursor": next_cursor.map(|c| c.encode()),
+    })))
+}
+
+#[utoipa::path(
+    post,
+    path = "/evidence",
+    tag = "evidence",
+    request_body = EvidenceIn,
+    params(
+        ("Idempotency-Key" = Option<String>, Header, description = "Replays within the TTL return the original job"),
+        ("X-Evidence-Source" = Option<String>, Header, description = "Source label when the body has none"),
+    ),
+    responses(
+        (status = 200, description = "Job queued (`status: queued`), anchored inline (`status: anchored`), or an idempotent replay", body = serde_json::Value),
+        (status = 400, description = "Invalid digest or digest algorithm, source, anchor mode or inline payload", body = ErrorResponse),
+        (status = 409, description = "Evidence id already exists, or Idempotency-Key reused for another digest", body = ErrorResponse),
+        (status = 413, description = "Sync anchoring rejected the payload as too large for the chain; the job is failed", body = serde_json::Value),
+        (status = 504, description = "Sync anchoring is still running past the timeout; the job stays `in_progress` until it settles", body = serde_json::Value),
+    )
+)]
 pub async fn post_evidence(
     State(state): State<AppState>,
-    Json(body): Json<EvidenceIn>,
-) -> impl IntoResponse {
-    match create_evidence_job(&state.pool, &body).await {
-        Ok((id, rows_affected)) => {
-            if rows_affected > 0 {
-                (
-                    StatusCode::OK,
-                    Json(serde_json::json!({ "id": id, "status": "queued" })),
+    headers: HeaderMap,
+    Json(mut body): Json<EvidenceIn>,
+) -> Result<axum::response::Response, ApiError> {
+    body.digest_hex = normalize_digest_hex(&body.digest_hex).map_err(ApiError::Validation)?;
+    check_digest_algo(body.metadata.as_ref()).map_err(ApiError::Validation)?;
+    let source = resolve_evidence_source(&body, &headers).map_err(ApiError::Validation)?;
+    let payload = decode_inline_payload(&state, &body)?;
+
+    // Sync anchoring is a capability the server must opt into
+    let sync_anchor = match body.anchor_mode.unwrap_or_default() {
+        AnchorMode::Async => None,
+        AnchorMode::Sync => Some(state.sync_anchor.as_ref().ok_or_else(|| {
+            ApiError::validation("synchronous anchoring is not enabled on this server")
+        })?),
+    };
+
+    // Content-addressed, so storing before the job exists is harmless and a
+    // failed store leaves nothing behind to conflict with a retry
+    if let (Some(store), Some(payload)) = (&state.payloads, payload) {
+        store
+            .put(&body.digest_hex, &payload)
+            .await
+            .map_err(ApiError::internal)?;
+    }
+
+    // Claim the idempotency key (binding it to the job id) before creating
+    // the job, so a concurrent retry cannot create a second one
+    let idempotency_key = idempotency_key(&headers).map_err(ApiError::Validation)?;
+    if let Some(key) = &idempotency_key {
+        let job_id = body
+            .id
+            .get_or_insert_with(|| uuid::Uuid::new_v4().to_string())
+            .clone();
+        let ttl_ms = state.idempotency_ttl.as_millis().min(i64::MAX as u128) as i64;
+        match claim_idempotency_key(&state.pool, key, &job_id, &body.digest_hex, ttl_ms).await? {
+            IdempotencyClaim::Claimed => {}
+            IdempotencyClaim::Existing { job_id, digest_hex } => {
+                if digest_hex != body.digest_hex {
+                    return Err(ApiError::conflict(
+                        "Idempotency-Key was already used for a different digest",
+                        Some(job_id),
+                    ));
+                }
+                return replay_evidence_response(&state, job_id).await;
+            }
+        }
+    }
+
+    // A job anchored inline is inserted already claimed, so the keeper
+    // can't pick it up while the request is anchoring it
+    let created = match sync_anchor {
+        Some(_) => create_inline_evidence_job(&state.pool, &body, &source).await,
+        None => create_evidence_job(&state.pool, &body, &source).await,
+    };
+    let (id, rows_affected) = match created {
+        Ok(created) => created,
+        Err(e) => {
+            if let Some(key) = &idempotency_key {
+                release_idempotency_key(&state.pool, key).await?;
+            }
+            return Err(e.into());
+        }
+    };
+    if rows_affected == 0 {
+        if let Some(key) = &idempotency_key {
+            release_idempotency_key(&state.pool, key).await?;
+        }
+        return Err(ApiError::conflict(
+            "evidence with this ID already exists",
+            Some(id),
+        ));
+    }
+
+    match sync_anchor {
+        Some(sync_anchor) => Ok(anchor_evidence_inline(&state, sync_anchor, id, &body).await),
+        None => Ok(Json(serde_json::json!({ "id": id, "status": "queued" })).into_response()),
+    }
+}
+
+/// Decode and check `payload_base64`, if given, before anything is written
+fn decode_inline_payload(state: &AppState, body: &EvidenceIn) -> Result<Option<Vec<u8>>, ApiError> {
+    let Some(encoded) = &body.payload_base64 else {
+        return Ok(None);
+    };
+    if state.payloads.is_none() {
+        return Err(ApiError::validation(
+            "payload storage is not enabled on this server",
+        ));
+    }
+    let payload = base64::engine::general_purpose::STANDARD
+        .decode(encoded.trim())
+        .map_err(|e| ApiError::validation(format!("invalid payload_base64: {}", e)))?;
+    crate::payload_store::verify_payload(&body.digest_hex, &payload)
+        .map_err(|e| ApiError::validation(e.to_string()))?;
+    Ok(Some(payload))
+}
+
+/// Answer a replayed `Idempotency-Key` with the job the first request created
+async fn replay_evidence_response(
+    state: &AppState,
+    job_id: String,
+) -> Result<axum::response::Response, ApiError> {
+    // The first request may still be inserting the job it claimed the key for
+    let status = get_evidence_by_id(&state.pool, &job_id)
+        .await?
+        .map(|job| job.status)
+        .unwrap_or_else(|| "queued".to_string());
+    Ok((
+        StatusCode::OK,
+        [(IDEMPOTENT_REPLAY_HEADER, "true")],
+        Json(serde_json::json!({ "id": job_id, "status": status })),
+    )
+        .into_response())
+}
+
+/// Anchor a job claimed by [`create_inline_evidence_job`] inline for
+/// `anchor_mode: "sync"`.
+///
+/// Anchoring and settling the job run in a task of their own, which the
+/// request waits on for up to the sync timeout. Past that the job stays
+/// claimed and the task carries on, so a transaction already broadcast is
+/// still recorded rather than anchored a second time by the keeper.
+async fn anchor_evidence_inline(
+    state: &AppState,
+    sync_anchor: &SyncAnchor,
+    id: String,
+    body: &EvidenceIn,
+) -> axum::response::Response {
+    let record = EvidenceRecord {
+        id: id.clone(),
+        created_at: chrono::Utc::now(),
+        digest: EvidenceDigest {
+            algo: DigestAlgo::Sha256,
+            hex: body.digest_hex.clone(),
+        },
+        payload_mime: body.payload_mime.clone(),
+        metadata: body.metadata.clone().unwrap_or(serde_json::Value::Null),
+    };
+
+    let timeout = sync_anchor.timeout();
+    let settle = tokio::spawn(settle_inline_anchor(
+        state.clone(),
+        sync_anchor.clone(),
+        record,
+    ));
+    match tokio::time::timeout(timeout, settle).await {
+        Ok(Ok(response)) => response,
+        Ok(Err(join_error)) => error_response(StatusCode::INTERNAL_SERVER_ERROR, join_error),
+        Err(_) => (
+            StatusCode::GATEWAY_TIMEOUT,
+            Json(serde_json::json!({
+                "error": format!(
+                    "anchoring did not finish within {}ms; it continues in the background",
+                    timeout.as_millis()
+                ),
+                "id": id,
+                "status": "in_progress",
+            })),
+        )
+            .into_response(),
+    }
+}
+
+/// Anchor `record` and settle its claimed job: done with the tx ref on
+/// success, failed if the payload is too large for the chain, otherwise
+/// released to the queue for the keeper.
+async fn settle_inline_anchor(
+    state: AppState,
+    sync_anchor: SyncAnchor,
+    record: EvidenceRecord,
+) -> axum::response::Response {
+    let id = record.id.clone();
+    let anchor_error = match sync_anchor.anchor(&record).await {
+        Ok(tx_ref) => {
+            return match record_inline_anchor(&state, &id, &tx_ref).await {
+                Ok(()) => {
+                    if let Some(webhooks) = &state.webhooks {
+                        webhooks.dispatch(WebhookEvent::new(
+                            WebhookEventType::EvidenceAnchored,
+                            serde_json::json!({
+                                "evidence_id": id,
+                                "digest_hex": record.digest.hex,
+                                "tx_ref": tx_ref,
+                            }),
+                        ));
+                    }
+                    (
+                        StatusCode::OK,
+                        Json(serde_json::json!({
+                            "id": id,
+                            "status": "anchored",
+                            "explorer_url": explorer_url(&tx_ref),
+                            "tx_ref": tx_ref,
+                        })),
+                    )
+                        .into_response()
+                }
+                // The transaction exists on chain; hand its ref to the caller
+                Err(db_error) => (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(serde_json::json!({
+                        "error": format!("anchored but recording the tx ref failed: {}", db_error),
+                        "id": id,
+                        "status": "failed",
+                        "explorer_url": explorer_url(&tx_ref),
+                        "tx_ref": tx_ref,
+                    })),
                 )
-                    .into_response()
-            } else {
-                (StatusCode::CONFLICT, Json(serde_json::json!({ "error": "evidence with this ID already exists", "id": id }))).into_response()
+                    .into_response(),
+            };
+        }
+        Err(anchor_error) => anchor_error,
+    };
+
+    // The chain will never accept it, so the keeper won't either
+    if let AnchorError::PayloadTooLarge { .. } = &anchor_error {
+        let reason = anchor_error.to_string();
+        if let Err(db_error) = fail_inline_job(&state.pool, &id, &reason).await {
+            return error_response(StatusCode::INTERNAL_SERVER_ERROR, db_error);
+        }
+        return (
+            StatusCode::PAYLOAD_TOO_LARGE,
+            Json(serde_json::json!({
+                "error": reason,
+                "id": id,
+                "status": "failed",
+            })),
+        )
+            .into_response();
+    }
+
+    if let Err(db_error) = release_inline_job(&state.pool, &id).await {
+        return error_response(StatusCode::INTERNAL_SERVER_ERROR, db_error);
+    }
+    (
+        StatusCode::BAD_GATEWAY,
+        Json(serde_json::json!({
+            "error": format!("anchoring failed: {}", anchor_error),
+            "id": id,
+            "status": "queued",
+        })),
+    )
+        .into_response()
+}
+
+/// Store an inline anchor's tx ref and mark its job done, retrying briefly.
+///
+/// If the write keeps failing the job is failed with the tx id in
+/// `last_error`: left `in_progress`, the reaper would requeue it and the
+/// keeper would anchor the same digest again.
+async fn record_inline_anchor(
+    state: &AppState,
+    id: &str,
+    tx_ref: &ChainTxRef,
+) -> Result<(), sqlx::Error> {
+    let mut attempt = 1;
+    loop {
+        match record_tx_ref_and_done(&state.pool, id, tx_ref).await {
+            Ok(()) => return Ok(()),
+            Err(e) if attempt < INLINE_RECORD_ATTEMPTS => {
+                tracing::warn!(evidence_id = %id, attempt, error = %e, "Recording inline anchor failed; retrying");
+                tokio::time::sleep(std::time::Duration::from_millis(50 * u64::from(attempt))).await;
+                attempt += 1;
+            }
+            Err(e) => {
+                let reason = format!(
+                    "anchored on {}/{} as {} but recording the tx ref failed: {}",
+                    tx_ref.network, tx_ref.chain, tx_ref.tx_id, e
+                );
+                if let Err(fail_error) = fail_inline_job(&state.pool, id, &reason).await {
+                    tracing::error!(
+                        evidence_id = %id,
+                        tx_id = %tx_ref.tx_id,
+                        error = %fail_error,
+                        "Could not fail an anchored job; it may be anchored again"
+                    );
+                }
+                return Err(e);
             }
         }
-        Err(db_error) => error_response(StatusCode::INTERNAL_SERVER_ERROR, db_error),
     }
 }
 
+/// Outbox health: what an operator checks first when anchoring stalls.
+#[utoipa::path(
+    get,
+    path = "/evidence/stats",
+    tag = "evidence",
+    responses(
+        (status = 200, description = "Job counts by status and anchoring backlog", body = EvidenceStatsOut),
+    )
+)]
+pub async fn get_evidence_stats(
+    State(state): State<AppState>,
+) -> Result<Json<EvidenceStatsOut>, ApiError> {
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    Ok(Json(get_outbox_stats(&state.pool, now_ms).await?))
+}
+
+#[utoipa::path(
+    get,
+    path = "/evidence/{id}",
+    tag = "evidence",
+    params(("id" = String, Path, description = "Evidence id")),
+    responses(
+        (status = 200, description = "Evidence job and its chain transactions", body = EvidenceDetailOut),
+        (status = 404, description = "No such evidence", body = ErrorResponse),
+    )
+)]
 pub async fn get_evidence(
     State(state): State<AppState>,
     Path(id): Path<String>,
-) -> impl IntoResponse {
-    let result = get_evidence_by_id(&state.pool, &id).await;
-    handle_get_by_id_response(result, id)
+) -> Result<Json<EvidenceDetailOut>, ApiError> {
+    let evidence = get_evidence_by_id(&state.pool, &id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("evidence", &id))?;
+    let tx_refs = list_tx_refs_for_job(&state.pool, &id).await?;
+    Ok(Json(EvidenceDetailOut { evidence, tx_refs }))
+}
+
+/// The stored payload of an evidence job, served with its submitted MIME type.
+///
+/// The bytes are re-hashed on the way out; a payload that no longer matches
+/// its digest is reported as a conflict rather than served.
+#[utoipa::path(
+    get,
+    path = "/evidence/{id}/payload",
+    tag = "evidence",
+    params(("id" = String, Path, description = "Evidence id")),
+    responses(
+        (status = 200, description = "Payload bytes, with the MIME type given at submission"),
+        (status = 404, description = "No such evidence, or no payload stored for it", body = ErrorResponse),
+        (status = 409, description = "Stored payload no longer matches its digest", body = ErrorResponse),
+    )
+)]
+pub async fn get_evidence_payload(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<axum::response::Response, ApiError> {
+    let (digest_hex, mime) = get_evidence_payload_ref(&state.pool, &id)
+        .await?
+        .ok_or_else(|| ApiError::not_found("evidence", &id))?;
+    let store = state
+        .payloads
+        .as_ref()
+        .ok_or_else(|| ApiError::not_found("payload", &id))?;
+    let payload = match store.get(&digest_hex).await {
+        Ok(Some(payload)) => payload,
+        Ok(None) => return Err(ApiError::not_found("payload", &id)),
+        Err(e @ PayloadStoreError::DigestMismatch { .. }) => {
+            tracing::error!(evidence_id = %id, error = %e, "Stored evidence payload failed verification");
+            return Err(ApiError::conflict(e.to_string(), Some(id)));
+        }
+        Err(e) => return Err(ApiError::internal(e)),
+    };
+    let mime = mime.unwrap_or_else(|| "application/octet-stream".to_string());
+    Ok(([(header::CONTENT_TYPE, mime)], payload).into_response())
+}
+
+/// Merkle proof bundle for a batch-anchored evidence job.
+///
+/// 202 with `{"status":"pending"}` until the job's batch has been anchored.
+#[utoipa::path(
+    get,
+    path = "/evidence/{id}/proof",
+    tag = "evidence",
+    params(("id" = String, Path, description = "Evidence id")),
+    responses(
+        (status = 200, description = "Merkle proof bundle", body = serde_json::Value),
+        (status = 202, description = "Not anchored (or confirmed) yet", body = serde_json::Value),
+        (status = 404, description = "No such evidence", body = ErrorResponse),
+    )
+)]
+pub async fn get_evidence_proof(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<axum::response::Response, ApiError> {
+    let proof = get_evidence_proof_by_job(&state.pool, &id, state.require_confirmed_proofs)
+        .await?
+        .ok_or_else(|| ApiError::not_found("evidence", &id))?;
+    Ok(match proof {
+        EvidenceProof::Anchored(bundle) => (StatusCode::OK, Json(*bundle)).into_response(),
+        EvidenceProof::Pending => (
+            StatusCode::ACCEPTED,
+            Json(serde_json::json!({ "id": id, "status": "pending" })),
+        )
+            .into_response(),
+        EvidenceProof::PendingConfirmation(tx_ref) => (
+            StatusCode::ACCEPTED,
+            Json(serde_json::json!({
+                "id": id,
+                "status": "pending_confirmation",
+                "tx_ref": tx_ref,
+            })),
+        )
+            .into_response(),
+    })
 }
 
 // Countermeasure Deployment handlers
@@ -393,6 +1014,78 @@ pub async fn post_career_application(
     }
 }
 
+/// List dead-lettered evidence jobs (admin, `?limit=` defaults to 50)
+pub async fn list_dead_letters(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(pagination): Query<CursorPagination>,
+) -> impl IntoResponse {
+    if let Err(response) = require_admin(&state, &headers) {
+        return response;
+    }
+
+    let limit = pagination.limit.unwrap_or(50).clamp(1, 500);
+    match list_dead_letter_jobs(&state.pool, limit).await {
+        Ok(jobs) => (
+            StatusCode::OK,
+            Json(serde_json::json!({ "data": jobs, "limit": limit })),
+        )
+            .into_response(),
+        Err(db_error) => error_response(StatusCode::INTERNAL_SERVER_ERROR, db_error),
+    }
+}
+
+/// Requeue a dead-lettered job for the keeper to retry (admin)
+pub async fn post_replay_dead_letter(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+) -> impl IntoResponse {
+    if let Err(response) = require_admin(&state, &headers) {
+        return response;
+    }
+
+    match replay_dead_letter_job(&state.pool, &id).await {
+        Ok(true) => (
+            StatusCode::OK,
+            Json(serde_json::json!({ "id": id, "status": "queued" })),
+        )
+            .into_response(),
+        Ok(false) => error_response(StatusCode::NOT_FOUND, "no dead-lettered job with this id"),
+        Err(db_error) => error_response(StatusCode::INTERNAL_SERVER_ERROR, db_error),
+    }
+}
+
+/// Requeue a `failed` or dead-lettered job (admin, `?reset_attempts=true` to
+/// also start its attempt budget over)
+pub async fn post_retry_job(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(id): Path<String>,
+    Query(params): Query<RetryJobParams>,
+) -> impl IntoResponse {
+    if let Err(response) = require_admin(&state, &headers) {
+        return response;
+    }
+
+    match retry_job(&state.pool, &id, params.reset_attempts).await {
+        Ok(JobRetry::Requeued) => (
+            StatusCode::OK,
+            Json(serde_json::json!({ "id": id, "status": "queued" })),
+        )
+            .into_response(),
+        Ok(JobRetry::NotFound) => error_response(StatusCode::NOT_FOUND, "no job with this id"),
+        Ok(JobRetry::NotRetryable(status)) => error_response(
+            StatusCode::CONFLICT,
+            format!(
+                "job is {}; only failed or dead-lettered jobs can be retried",
+                status
+            ),
+        ),
+        Err(db_error) => error_response(StatusCode::INTERNAL_SERVER_ERROR, db_error),
+    }
+}
+
 /// Seed team members (admin endpoint - should be protected in production)
 pub async fn post_seed_team_members(State(state): State<AppState>) -> impl IntoResponse {
     match crate::db::seed_team_members(&state.pool).await {
@@ -4,21 +4,37 @@
 //! monetizing evidence verification API access.
 
 use crate::{
-    db::{create_payment_receipt, get_evidence_by_id, is_payment_signature_used},
-    db_errors::is_unique_constraint_violation,
+    db::{
+        get_evidence_by_id, list_payment_receipts_after, list_payment_receipts_by_refund_status,
+        list_payment_receipts_in_range, list_tx_refs_for_job, mark_refund_eligible, mark_refunded,
+        store_verification_result, take_verification_result,
+    },
+    models::{
+        CursorPagination, EvidenceCursor, PaymentReceiptFilter, ReconciliationQuery, RefundQuery,
+        TxRefOut, REFUND_ELIGIBLE, REFUND_STATUSES,
+    },
+    rate_limit::ClientKey,
+    reconciliation::reconcile_receipts,
+    replay_store::SqliteReplayStore,
+    request_id::RequestId,
+    result_tokens::{ResultTokenSigner, RESULT_TOKEN_TTL},
+    webhooks::{WebhookEvent, WebhookEventType},
     AppState,
 };
 use axum::{
-    extract::State,
+    extract::{Path, Query, State},
     http::{HeaderMap, StatusCode},
     response::{IntoResponse, Response},
-    Json,
+    Extension, Json,
 };
 use phoenix_x402::{
-    middleware::extract_payment_proof, PaymentDetails, PaymentProof, PaymentVerification,
-    PriceTier, VerifyEvidenceRequest, VerifyEvidenceResponse, X402Config, X402Facilitator,
+    middleware::extract_payment_proof, BulkEvidenceResult, PaymentDetails, PaymentProof,
+    PaymentReceipt, PaymentVerification, PriceTier, ReplayError, VerifyEvidenceBulkRequest,
+    VerifyEvidenceRequest, VerifyEvidenceResponse, X402Config, X402Facilitator,
 };
 use serde_json::json;
+use sqlx::{Pool, Sqlite};
+use std::sync::Arc;
 
 /// State extension for x402 configuration
 #[derive(Clone)]
@@ -27,19 +43,24 @@ pub struct X402State {
     pub config: X402Config,
     /// Ed25519 attestation signer for legal tier (None if key not configured)
     pub attestation_signer: Option<phoenix_x402::AttestationSigner>,
+    /// Signs the one-time tokens that share a paid verification result
+    pub result_tokens: ResultTokenSigner,
 }
 
 impl X402State {
-    /// Create x402 state from environment configuration
-    pub fn from_env() -> Option<Self> {
+    /// Create x402 state from environment configuration, redeeming payments
+    /// into `pool`'s `payment_receipts`
+    pub fn from_env(pool: &Pool<Sqlite>) -> Option<Self> {
         match X402Config::from_env() {
             Ok(config) if config.enabled => {
-                let facilitator = X402Facilitator::new(config.clone());
+                let facilitator = X402Facilitator::new(config.clone())
+                    .with_replay_store(Arc::new(SqliteReplayStore::new(pool.clone())));
                 let attestation_signer = phoenix_x402::AttestationSigner::from_env();
                 Some(Self {
                     facilitator,
                     config,
                     attestation_signer,
+                    result_tokens: ResultTokenSigner::from_env(),
                 })
             }
             Ok(_) => {
@@ -54,13 +75,15 @@ impl X402State {
     }
 
     /// Create x402 state for devnet testing
-    pub fn devnet(wallet_address: &str) -> Self {
+    pub fn devnet(wallet_address: &str, pool: &Pool<Sqlite>) -> Self {
         let config = X402Config::devnet(wallet_address);
-        let facilitator = X402Facilitator::new(config.clone());
+        let facilitator = X402Facilitator::new(config.clone())
+            .with_replay_store(Arc::new(SqliteReplayStore::new(pool.clone())));
         Self {
             facilitator,
             config,
             attestation_signer: Some(phoenix_x402::AttestationSigner::ephemeral()),
+            result_tokens: ResultTokenSigner::ephemeral(),
         }
     }
 }
@@ -77,8 +100,8 @@ impl X402State {
 /// - **Bearer token authentication required**: All requests must include a valid
 ///   `Authorization: Bearer <token>` header. Cookie-based authentication is not
 ///   accepted, preventing browser-originated CSRF attacks.
-/// - **Strict CORS policy**: The API should be configured to reject cross-origin
-///   requests or only allow trusted origins via infrastructure.
+/// - **No CORS**: This route is outside the `API_CORS_ALLOWED_ORIGINS` layer
+///   (see [`crate::cors`]), so browsers never get cross-origin access to it.
 /// - **Browser origin detection**: Requests with browser-specific headers like
 ///   `Cookie` or `Sec-Fetch-*` without proper Bearer auth will be rejected.
 ///
@@ -87,81 +110,235 @@ impl X402State {
 /// Operators must configure edge proxies/load balancers to:
 /// - Set and normalize X-Forwarded-For/X-Real-IP headers
 /// - Strip client-supplied values of these headers before forwarding
-/// - Enforce strict CORS policies at the infrastructure level
+/// - Enforce strict CORS policies at the edge as well as in-app
 ///
 /// Without X-PAYMENT header: Returns 402 Payment Required with payment details
 /// With X-PAYMENT header: Verifies payment and returns premium evidence verification
+#[utoipa::path(
+    post,
+    path = "/api/v1/evidence/verify-premium",
+    tag = "x402",
+    request_body = VerifyEvidenceRequest,
+    params(("X-PAYMENT" = Option<String>, Header, description = "Base64 JSON payment proof; omit to get a quote")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Payment verified; premium verification result", body = VerifyEvidenceResponse),
+        (status = 402, description = "Payment required: quote to pay, or a new quote after an expired or short payment", body = PaymentDetails),
+        (status = 400, description = "Malformed payment proof or request", body = serde_json::Value),
+        (status = 403, description = "Browser request without M2M authentication", body = serde_json::Value),
+        (status = 409, description = "Payment signature already redeemed", body = serde_json::Value),
+        (status = 429, description = "Rate limited; see `Retry-After`", body = serde_json::Value),
+        (status = 503, description = "x402 is not configured", body = serde_json::Value),
+    )
+)]
 pub async fn verify_evidence_premium(
     State(state): State<AppState>,
+    Extension(request_id): Extension<RequestId>,
     headers: HeaderMap,
     Json(req): Json<VerifyEvidenceRequest>,
 ) -> Response {
+    let x402_state = match premium_preamble(&state, &headers, req.tier).await {
+        Ok(x402_state) => x402_state,
+        Err(response) => return response,
+    };
+
+    // Refuse malformed requests before quoting or taking payment for them
+    if let Err(e) = req.validate() {
+        return invalid_request(&e);
+    }
+
+    let charge = Charge::from(&req);
+    match take_payment(&state, &x402_state, &charge, &headers, &request_id).await {
+        Ok(payment) => perform_premium_verification(state, req, payment).await,
+        Err(response) => response,
+    }
+}
+
+/// Bulk premium evidence verification with x402 payment
+///
+/// POST /api/v1/evidence/verify-premium-bulk
+///
+/// One bulk-tier payment covers every listed evidence id (up to
+/// `MAX_BULK_EVIDENCE_IDS`, no duplicates), priced by the bulk formula for
+/// their count. The payment memo is `evidence:bulk-<sha256>` over the ids,
+/// as quoted in the 402. Results come back per id, in request order; ids that
+/// are not found are reported as unverified. Only a batch with no evidence
+/// found at all is flagged for refund.
+///
+/// Same M2M, rate limit and payment rules as
+/// `POST /api/v1/evidence/verify-premium`.
+#[utoipa::path(
+    post,
+    path = "/api/v1/evidence/verify-premium-bulk",
+    tag = "x402",
+    request_body = VerifyEvidenceBulkRequest,
+    params(("X-PAYMENT" = Option<String>, Header, description = "Base64 JSON payment proof; omit to get a quote")),
+    security(("bearer_auth" = [])),
+    responses(
+        (status = 200, description = "Payment verified; per-evidence results", body = serde_json::Value),
+        (status = 402, description = "Payment required: quote to pay, or a new quote after an expired or short payment", body = PaymentDetails),
+        (status = 400, description = "Malformed payment proof or request, or too many evidence ids", body = serde_json::Value),
+        (status = 403, description = "Browser request without M2M authentication", body = serde_json::Value),
+        (status = 404, description = "None of the evidence ids exist; payment flagged for refund", body = serde_json::Value),
+        (status = 409, description = "Payment signature already redeemed", body = serde_json::Value),
+        (status = 429, description = "Rate limited; see `Retry-After`", body = serde_json::Value),
+        (status = 503, description = "x402 is not configured", body = serde_json::Value),
+    )
+)]
+pub async fn verify_evidence_premium_bulk(
+    State(state): State<AppState>,
+    Extension(request_id): Extension<RequestId>,
+    headers: HeaderMap,
+    Json(req): Json<VerifyEvidenceBulkRequest>,
+) -> Response {
+    let x402_state = match premium_preamble(&state, &headers, PriceTier::Bulk).await {
+        Ok(x402_state) => x402_state,
+        Err(response) => return response,
+    };
+
+    if let Err(e) = req.validate() {
+        return invalid_request(&e);
+    }
+
+    let charge = Charge {
+        evidence_id: bulk_batch_id(&req.evidence_ids),
+        tier: PriceTier::Bulk,
+        count: req.count(),
+    };
+    match take_payment(&state, &x402_state, &charge, &headers, &request_id).await {
+        Ok(payment) => perform_bulk_verification(state, req, payment).await,
+        Err(response) => response,
+    }
+}
+
+/// Id a bulk batch is quoted, paid and receipted under: `bulk-` and the
+/// SHA-256 of its evidence ids, one per line, in request order
+fn bulk_batch_id(evidence_ids: &[String]) -> String {
+    format!(
+        "bulk-{}",
+        phoenix_evidence::hash::sha256_hex(evidence_ids.join("\n").as_bytes())
+    )
+}
+
+/// Checks shared by the premium endpoints before the body is validated:
+/// M2M access, rate limits for `tier`, and x402 being configured
+#[allow(clippy::result_large_err)]
+async fn premium_preamble(
+    state: &AppState,
+    headers: &HeaderMap,
+    tier: PriceTier,
+) -> Result<X402State, Response> {
     // Enforce machine-to-machine access only - reject browser-originated requests
     // without proper API authentication to prevent CSRF attacks
-    if let Err(response) = enforce_m2m_access(&headers) {
-        return response;
-    }
+    enforce_m2m_access(headers)?;
 
-    // Extract client IP for rate limiting
-    let client_ip = extract_client_ip_from_headers(&headers);
+    // Limit per API key when a valid bearer token is present, otherwise per IP
+    let client_ip = extract_client_ip_from_headers(headers);
+    let client = ClientKey::from_headers(&state.pool, headers, &client_ip).await;
 
     // Check rate limit for premium verification endpoint
-    if let Err(response) = state.rate_limiter.check_verify(&client_ip) {
-        return response;
-    }
+    state.rate_limiter.check_verify(client.clone())?;
+
+    // Tier-specific limit, now that the body (and so the tier) is known
+    state.rate_limiter.check_verify_tier(client, tier)?;
 
     // Get x402 configuration from AppState (initialized once at startup)
-    let x402_state = match &state.x402 {
-        Some(s) => s.clone(),
+    match &state.x402 {
+        Some(s) => Ok(s.clone()),
         None => {
             // x402 not configured - return 503 Service Unavailable
-            return (
+            Err((
                 StatusCode::SERVICE_UNAVAILABLE,
                 Json(json!({
                     "error": "Premium verification service not configured",
                     "hint": "Set X402_ENABLED=true and X402_WALLET_ADDRESS to enable"
                 })),
             )
-                .into_response();
+                .into_response())
         }
-    };
+    }
+}
+
+fn invalid_request(e: &phoenix_x402::X402Error) -> Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(json!({
+            "error": "Invalid verification request",
+            "details": e.to_string(),
+        })),
+    )
+        .into_response()
+}
 
-    // Check for X-PAYMENT header
-    match extract_payment_proof(&headers) {
+/// What a premium request is billed as: the id its payment memo and receipt
+/// name, its tier, and how many evidence items it covers
+struct Charge {
+    evidence_id: String,
+    tier: PriceTier,
+    count: u32,
+}
+
+impl From<&VerifyEvidenceRequest> for Charge {
+    fn from(req: &VerifyEvidenceRequest) -> Self {
+        Self {
+            evidence_id: req.evidence_id.clone(),
+            tier: req.tier,
+            count: req.count.unwrap_or(1),
+        }
+    }
+}
+
+/// Quote `charge` when no `X-PAYMENT` header was sent, otherwise verify and
+/// redeem the payment it carries
+async fn take_payment(
+    state: &AppState,
+    x402_state: &X402State,
+    charge: &Charge,
+    headers: &HeaderMap,
+    request_id: &RequestId,
+) -> Result<PaymentVerification, Response> {
+    match extract_payment_proof(headers) {
         Ok(Some(proof)) => {
-            // Payment provided - verify and process
-            handle_paid_verification(state, x402_state, req, proof).await
+            // Payment provided - verify and redeem
+            redeem_payment(state, x402_state, charge, &proof, request_id).await
         }
         Ok(None) => {
             // No payment - return 402 with payment details
-            create_payment_required_response(&req.evidence_id, req.tier, &x402_state)
+            Err(create_payment_required_response(charge, x402_state).await)
         }
         Err(e) => {
             // Invalid payment proof format
-            (
+            Err((
                 StatusCode::BAD_REQUEST,
                 Json(json!({
                     "error": "Invalid payment proof",
                     "details": e.to_string()
                 })),
             )
-                .into_response()
+                .into_response())
         }
     }
 }
 
-/// Create 402 Payment Required response
-fn create_payment_required_response(
-    evidence_id: &str,
-    tier: PriceTier,
-    x402_state: &X402State,
-) -> Response {
-    let details = PaymentDetails::for_evidence(
-        evidence_id,
-        tier,
+/// Payment details for a request, priced by item count for the bulk tier
+/// and quoted in SOL at the price oracle's current rate
+async fn payment_details_for(charge: &Charge, x402_state: &X402State) -> PaymentDetails {
+    let mut details = PaymentDetails::for_evidence(
+        &charge.evidence_id,
+        charge.tier,
         &x402_state.config.wallet_address,
         &x402_state.config.facilitator_url,
     );
+    details.price = x402_state.config.price_usdc(charge.tier, charge.count);
+    let details = details
+        .with_token_quotes(&x402_state.facilitator.pricing_config().await)
+        .with_expiry(x402_state.config.quote_ttl_secs);
+    x402_state.facilitator.sign_quote(details)
+}
+
+/// Create 402 Payment Required response
+async fn create_payment_required_response(charge: &Charge, x402_state: &X402State) -> Response {
+    let details = payment_details_for(charge, x402_state).await;
 
     // Add custom headers for x402 protocol
     let mut response = Json(details).into_response();
@@ -170,17 +347,22 @@ fn create_payment_required_response(
     response
 }
 
-/// Handle verification request with valid payment
-async fn handle_paid_verification(
-    state: AppState,
-    x402_state: X402State,
-    req: VerifyEvidenceRequest,
-    proof: PaymentProof,
-) -> Response {
+/// Verify a payment for `charge` and redeem it, recording its receipt
+async fn redeem_payment(
+    state: &AppState,
+    x402_state: &X402State,
+    charge: &Charge,
+    proof: &PaymentProof,
+    request_id: &RequestId,
+) -> Result<PaymentVerification, Response> {
     // Check for payment replay attack
-    match is_payment_signature_used(&state.pool, &proof.signature).await {
+    match x402_state
+        .facilitator
+        .is_payment_used(&proof.signature)
+        .await
+    {
         Ok(true) => {
-            return (
+            return Err((
                 StatusCode::CONFLICT,
                 Json(json!({
                     "error": "Payment already used",
@@ -188,111 +370,191 @@ async fn handle_paid_verification(
                     "hint": "This payment signature has already been redeemed"
                 })),
             )
-                .into_response();
+                .into_response());
         }
         Ok(false) => {} // Payment not used yet, continue
         Err(e) => {
-            tracing::error!("Failed to check payment signature: {}", e);
-            return (
+            tracing::error!(%request_id, "Failed to check payment signature: {}", e);
+            return Err((
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(json!({
                     "error": "Failed to verify payment uniqueness",
                     "details": e.to_string()
                 })),
             )
-                .into_response();
+                .into_response());
         }
     }
 
-    let expected_memo = format!("evidence:{}", req.evidence_id);
-    let min_amount = req.tier.price_usdc();
+    let expected_memo = format!("evidence:{}", charge.evidence_id);
+    let min_amount = x402_state.config.price_usdc(charge.tier, charge.count);
 
     // Verify payment with facilitator
     let verification = match x402_state
         .facilitator
-        .verify_payment(&proof, &expected_memo, min_amount)
+        .verify_payment(proof, &expected_memo, &min_amount)
         .await
     {
         Ok(v) => v,
+        // Paid after the quote expired: quote again at the current price
+        Err(e @ phoenix_x402::X402Error::PaymentExpired(_)) => {
+            return Err((
+                StatusCode::PAYMENT_REQUIRED,
+                Json(json!({
+                    "error": "Payment quote expired",
+                    "details": e.to_string(),
+                    "payment_details": payment_details_for(charge, x402_state).await
+                })),
+            )
+                .into_response());
+        }
+        // Unsupported token, wrong mint or malformed amount: the client must
+        // pay differently
+        Err(e) if e.is_client_error() => {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(json!({
+                    "error": "Payment not accepted",
+                    "details": e.to_string(),
+                    "payment_details": payment_details_for(charge, x402_state).await
+                })),
+            )
+                .into_response());
+        }
+        // Facilitator unreachable (502) or too slow (504)
         Err(e) => {
-            return (
-                StatusCode::BAD_GATEWAY,
+            let status = if e.is_timeout() {
+                StatusCode::GATEWAY_TIMEOUT
+            } else {
+                StatusCode::BAD_GATEWAY
+            };
+            return Err((
+                status,
                 Json(json!({
                     "error": "Payment verification failed",
                     "details": e.to_string()
                 })),
             )
-                .into_response();
+                .into_response());
         }
     };
 
+    if let Some(shortfall) = &verification.underpaid {
+        // Short payment - state exactly how much is still owed
+        let mut response = Json(json!({
+            "error": "Payment insufficient",
+            "message": format!(
+                "Paid {} USDC of {} USDC; {} USDC short",
+                verification.amount_usdc, min_amount, shortfall
+            ),
+            "shortfall_usdc": shortfall,
+            "verification": verification,
+            "payment_details": payment_details_for(charge, x402_state).await
+        }))
+        .into_response();
+        *response.status_mut() = StatusCode::PAYMENT_REQUIRED;
+        return Err(response);
+    }
+
     if !verification.valid {
         // Payment verification failed - return 402 with details
         let mut response = Json(json!({
             "error": "Payment verification failed",
             "verification": verification,
-            "payment_details": PaymentDetails::for_evidence(
-                &req.evidence_id,
-                req.tier,
-                &x402_state.config.wallet_address,
-                &x402_state.config.facilitator_url,
-            )
+            "payment_details": payment_details_for(charge, x402_state).await
         }))
         .into_response();
         *response.status_mut() = StatusCode::PAYMENT_REQUIRED;
-        return response;
+        return Err(response);
     }
 
     // Store payment receipt for audit trail and replay protection
-    // Uses UNIQUE constraint on tx_signature to prevent race conditions
-    let tier_str = format!("{:?}", req.tier).to_lowercase();
-    match create_payment_receipt(
-        &state.pool,
-        &req.evidence_id,
-        &proof.signature,
-        &verification.amount_usdc,
-        &tier_str,
-        Some(&proof.sender),
-    )
-    .await
-    {
-        Ok(_) => {
+    // The store records atomically, so concurrent redemptions can't both pass
+    let tier_str = format!("{:?}", charge.tier).to_lowercase();
+    let receipt = PaymentReceipt {
+        tx_signature: proof.signature.clone(),
+        evidence_id: charge.evidence_id.clone(),
+        tier: charge.tier,
+        token: proof.token.trim().to_ascii_uppercase(),
+        token_amount: proof.amount.clone(),
+        amount_usdc: verification.amount_usdc.to_string(),
+        overpaid_usdc: verification.overpaid_usdc.clone(),
+        sender_wallet: Some(proof.sender.clone()),
+    };
+    match x402_state.facilitator.record_payment(&receipt).await {
+        Ok(()) => {
             // Receipt stored successfully, payment is unique
         }
+        Err(ReplayError::AlreadyUsed(_)) => {
+            return Err((
+                StatusCode::CONFLICT,
+                Json(json!({
+                    "error": "Payment already used",
+                    "tx_signature": proof.signature,
+                    "hint": "This payment signature has already been redeemed"
+                })),
+            )
+                .into_response());
+        }
         Err(e) => {
-            // Check if this is a UNIQUE constraint violation (payment replay)
-            // Uses database-agnostic helper for portable detection across backends
-            let is_replay = match &e {
-                sqlx::Error::Database(db_err) => is_unique_constraint_violation(db_err.as_ref()),
-                _ => false,
-            };
-
-            if is_replay {
-                return (
-                    StatusCode::CONFLICT,
-                    Json(json!({
-                        "error": "Payment already used",
-                        "tx_signature": proof.signature,
-                        "hint": "This payment signature has already been redeemed"
-                    })),
-                )
-                    .into_response();
-            }
-            // Any other DB error is fatal - do not proceed without audit trail
-            tracing::error!("Failed to store payment receipt: {}", e);
-            return (
+            // Any other store error is fatal - do not proceed without audit trail
+            tracing::error!(%request_id, "Failed to store payment receipt: {}", e);
+            return Err((
                 StatusCode::INTERNAL_SERVER_ERROR,
                 Json(json!({
                     "error": "Failed to record payment receipt",
                     "details": "Database error during payment processing"
                 })),
             )
-                .into_response();
+                .into_response());
         }
     }
 
-    // Payment verified and receipt stored - perform premium evidence verification
-    perform_premium_verification(state, req, verification).await
+    if let Some(webhooks) = &state.webhooks {
+        webhooks.dispatch(WebhookEvent::new(
+            WebhookEventType::PaymentVerified,
+            json!({
+                "evidence_id": charge.evidence_id,
+                "tier": tier_str,
+                "tx_signature": verification.tx_signature,
+                "amount_usdc": verification.amount_usdc,
+                "overpaid_usdc": verification.overpaid_usdc,
+                "sender": proof.sender,
+            }),
+        ));
+    }
+
+    // Payment verified and receipt stored - the request can be served
+    Ok(verification)
+}
+
+/// Record that a verified payment is owed a refund because its request could
+/// not be served. Failures are logged: the response already tells the payer.
+async fn flag_refund_eligible(state: &AppState, tx_signature: &str) {
+    match mark_refund_eligible(&state.pool, tx_signature).await {
+        Ok(true) => tracing::info!(%tx_signature, "payment flagged refund-eligible"),
+        Ok(false) => tracing::warn!(%tx_signature, "no receipt to flag refund-eligible"),
+        Err(e) => tracing::error!(%tx_signature, "failed to flag payment for refund: {}", e),
+    }
+}
+
+/// 500 for a lookup that failed after the payment was redeemed: the payment
+/// is flagged for refund and the database error stays in the server log
+async fn paid_lookup_failed(state: &AppState, tx_signature: &str, error: &sqlx::Error) -> Response {
+    tracing::error!(%tx_signature, "evidence lookup failed after payment: {}", error);
+    flag_refund_eligible(state, tx_signature).await;
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(json!({
+            "error": "Database error",
+            "payment": {
+                "verified": true,
+                "tx_signature": tx_signature,
+                "refund_eligible": true
+            }
+        })),
+    )
+        .into_response()
 }
 
 /// Perform the actual premium evidence verification
@@ -308,6 +570,7 @@ async fn perform_premium_verification(
         .and_then(|x| x.attestation_signer.as_ref());
 
     if req.tier == PriceTier::LegalAttestation && attestation_signer.is_none() {
+        flag_refund_eligible(&state, &payment.tx_signature).await;
         return (
             StatusCode::SERVICE_UNAVAILABLE,
             Json(json!({
@@ -329,6 +592,7 @@ async fn perform_premium_verification(
     let evidence = match get_evidence_by_id(&state.pool, &req.evidence_id).await {
         Ok(Some(e)) => e,
         Ok(None) => {
+            flag_refund_eligible(&state, &payment.tx_signature).await;
             return (
                 StatusCode::NOT_FOUND,
                 Json(json!({
@@ -344,24 +608,27 @@ async fn perform_premium_verification(
                 .into_response();
         }
         Err(e) => {
-            return (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                Json(json!({
-                    "error": "Database error",
-                    "details": e.to_string()
-                })),
-            )
-                .into_response();
+            return paid_lookup_failed(&state, &payment.tx_signature, &e).await;
+        }
+    };
+
+    // Recorded tx refs come back sorted by network, then chain
+    let tx_refs = match list_tx_refs_for_job(&state.pool, &evidence.id).await {
+        Ok(refs) => refs,
+        Err(e) => {
+            return paid_lookup_failed(&state, &payment.tx_signature, &e).await;
         }
     };
 
     // Build chain confirmations based on tier
-    let chain_confirmations = build_chain_confirmations(&evidence, &req);
+    let chain_confirmations = build_chain_confirmations(&evidence, &tx_refs, &req);
 
     // Build attestation for legal tier using Ed25519 signing
     let attestation = if req.tier == PriceTier::LegalAttestation {
-        attestation_signer
-            .map(|signer| signer.sign_attestation(&evidence.id, &evidence.digest_hex, 365))
+        attestation_signer.map(|signer| {
+            let valid_until = chrono::Utc::now() + chrono::Duration::days(365);
+            signer.sign_attestation(&evidence.id, &evidence.digest_hex, valid_until)
+        })
     } else {
         None
     };
@@ -377,55 +644,173 @@ async fn perform_premium_verification(
         attestation,
     };
 
+    let mut body = json!({
+        "verification": response,
+        "payment": {
+            "verified": true,
+            "tx_signature": payment.tx_signature,
+            "amount_usdc": payment.amount_usdc,
+            "overpaid_usdc": payment.overpaid_usdc,
+            "block": payment.block
+        }
+    });
+    if let Some((token, expires_at)) = share_verification_result(&state, &evidence.id, &body).await
+    {
+        body["result_token"] = json!(token);
+        body["result_token_expires_at"] = json!(expires_at);
+    }
+
+    (StatusCode::OK, Json(body)).into_response()
+}
+
+/// Store a paid verification result and mint the token that fetches it once.
+///
+/// Returns the token and its expiry (RFC 3339). Storage failures are logged
+/// and leave the response without a token: the payer still has the result.
+async fn share_verification_result(
+    state: &AppState,
+    evidence_id: &str,
+    result: &serde_json::Value,
+) -> Option<(String, String)> {
+    let x402 = state.x402.as_ref()?;
+    let id = uuid::Uuid::new_v4().to_string();
+    let expires_at = chrono::Utc::now() + RESULT_TOKEN_TTL;
+    let expires_ms = expires_at.timestamp_millis();
+    if let Err(e) =
+        store_verification_result(&state.pool, &id, evidence_id, result, expires_ms).await
+    {
+        tracing::warn!(%evidence_id, "failed to store verification result: {}", e);
+        return None;
+    }
+    Some((
+        x402.result_tokens.mint(&id, expires_ms),
+        expires_at.to_rfc3339(),
+    ))
+}
+
+/// Verify each evidence id of a paid bulk request
+async fn perform_bulk_verification(
+    state: AppState,
+    req: VerifyEvidenceBulkRequest,
+    payment: PaymentVerification,
+) -> Response {
+    let mut results = Vec::with_capacity(req.evidence_ids.len());
+    for evidence_id in &req.evidence_ids {
+        let lookup = match get_evidence_by_id(&state.pool, evidence_id).await {
+            Ok(Some(evidence)) => list_tx_refs_for_job(&state.pool, &evidence.id)
+                .await
+                .map(|tx_refs| Some((evidence, tx_refs))),
+            Ok(None) => Ok(None),
+            Err(e) => Err(e),
+        };
+        let result = match lookup {
+            Ok(Some((evidence, tx_refs))) => {
+                let single = VerifyEvidenceRequest {
+                    evidence_id: evidence_id.clone(),
+                    chain: req.chain.clone(),
+                    tier: PriceTier::Bulk,
+                    count: None,
+                };
+                BulkEvidenceResult {
+                    evidence_id: evidence_id.clone(),
+                    verified: true,
+                    verification: Some(VerifyEvidenceResponse {
+                        verified: true,
+                        evidence_id: evidence.id.clone(),
+                        chain_confirmations: build_chain_confirmations(
+                            &evidence, &tx_refs, &single,
+                        ),
+                        digest: phoenix_x402::EvidenceDigestInfo {
+                            algo: "sha256".to_string(),
+                            hex: evidence.digest_hex.clone(),
+                        },
+                        attestation: None,
+                    }),
+                    error: None,
+                }
+            }
+            Ok(None) => BulkEvidenceResult {
+                evidence_id: evidence_id.clone(),
+                verified: false,
+                verification: None,
+                error: Some("Evidence not found".to_string()),
+            },
+            Err(e) => return paid_lookup_failed(&state, &payment.tx_signature, &e).await,
+        };
+        results.push(result);
+    }
+
+    let verified = results.iter().filter(|r| r.verified).count();
+    let mut payment_info = json!({
+        "verified": true,
+        "tx_signature": payment.tx_signature,
+        "amount_usdc": payment.amount_usdc,
+        "overpaid_usdc": payment.overpaid_usdc,
+        "block": payment.block
+    });
+    let status = if verified == 0 {
+        flag_refund_eligible(&state, &payment.tx_signature).await;
+        payment_info["refund_eligible"] = json!(true);
+        StatusCode::NOT_FOUND
+    } else {
+        StatusCode::OK
+    };
+
     (
-        StatusCode::OK,
+        status,
         Json(json!({
-            "verification": response,
-            "payment": {
-                "verified": true,
-                "tx_signature": payment.tx_signature,
-                "amount_usdc": payment.amount_usdc,
-                "block": payment.block
-            }
+            "results": results,
+            "verified_count": verified,
+            "not_found_count": results.len() - verified,
+            "payment": payment_info
         })),
     )
         .into_response()
 }
 
 /// Build chain confirmation details based on evidence and tier
+///
+/// Chains are emitted in a fixed order. Anchor providers record the chain
+/// family (`solana`, `etherlink`) as the tx ref's `network` and the cluster
+/// (`devnet`, `testnet`) as its `chain`. When a family has several recorded
+/// tx refs, the first one in (network, chain, tx_id) order is reported, so
+/// `tx_refs` must already be sorted (as returned by `list_tx_refs_for_job`).
+/// Chains without a recorded tx ref get a `pending:` placeholder.
 fn build_chain_confirmations(
     evidence: &crate::models::EvidenceOut,
+    tx_refs: &[TxRefOut],
     req: &VerifyEvidenceRequest,
 ) -> serde_json::Value {
     let chain = req.chain.as_deref().unwrap_or("solana");
 
-    match req.tier {
+    // (chain, placeholder network) pairs to report for this tier
+    let chains: Vec<(&str, &str)> = match req.tier {
         PriceTier::MultiChain | PriceTier::LegalAttestation => {
             // Multi-chain verification
-            json!({
-                "solana": {
-                    "tx_id": format!("pending:{}", evidence.id),
-                    "confirmed": evidence.status == "done",
-                    "network": "devnet"
-                },
-                "etherlink": {
-                    "tx_id": format!("pending:{}", evidence.id),
-                    "confirmed": evidence.status == "done",
-                    "network": "testnet"
-                }
-            })
-        }
-        _ => {
-            // Single-chain verification
-            json!({
-                chain: {
-                    "tx_id": format!("pending:{}", evidence.id),
-                    "confirmed": evidence.status == "done",
-                    "network": "devnet"
-                }
-            })
+            vec![("etherlink", "testnet"), ("solana", "devnet")]
         }
+        // Single-chain verification
+        _ => vec![(chain, "devnet")],
+    };
+
+    let mut confirmations = serde_json::Map::new();
+    for (chain, placeholder_network) in chains {
+        let entry = match tx_refs.iter().find(|tx_ref| tx_ref.network == chain) {
+            Some(tx_ref) => json!({
+                "tx_id": tx_ref.tx_id,
+                "confirmed": tx_ref.confirmed,
+                "network": tx_ref.chain
+            }),
+            None => json!({
+                "tx_id": format!("pending:{}", evidence.id),
+                "confirmed": evidence.status == "done",
+                "network": placeholder_network
+            }),
+        };
+        confirmations.insert(chain.to_string(), entry);
     }
+
+    serde_json::Value::Object(confirmations)
 }
 
 /// Enforce machine-to-machine (M2M) access only
@@ -547,12 +932,22 @@ fn extract_client_ip_from_headers(headers: &HeaderMap) -> String {
 /// Get x402 payment status and configuration
 ///
 /// GET /api/v1/x402/status
+#[utoipa::path(
+    get,
+    path = "/api/v1/x402/status",
+    tag = "x402",
+    responses(
+        (status = 200, description = "Whether x402 is enabled, network, price tiers and tokens", body = serde_json::Value),
+        (status = 429, description = "Rate limited; see `Retry-After`", body = serde_json::Value),
+    )
+)]
 pub async fn x402_status(State(state): State<AppState>, headers: HeaderMap) -> Response {
-    // Extract client IP for rate limiting
+    // Limit per API key when a valid bearer token is present, otherwise per IP
     let client_ip = extract_client_ip_from_headers(&headers);
+    let client = ClientKey::from_headers(&state.pool, &headers, &client_ip).await;
 
     // Check rate limit for status endpoint
-    if let Err(response) = state.rate_limiter.check_status(&client_ip) {
+    if let Err(response) = state.rate_limiter.check_status(client) {
         return response;
     }
     match &state.x402 {
@@ -563,7 +958,8 @@ pub async fn x402_status(State(state): State<AppState>, headers: HeaderMap) -> R
                 "network": x402.config.network,
                 "wallet_address": x402.config.wallet_address,
                 "facilitator_url": x402.config.facilitator_url,
-                "supported_tokens": ["USDC", "USDT", "SOL"],
+                "supported_tokens": x402.config.supported_tokens(),
+                "sol_price_usdc": x402.config.sol_price_usdc,
                 "price_tiers": {
                     "basic": {
                         "price": PriceTier::Basic.price_usdc(),
@@ -583,7 +979,14 @@ pub async fn x402_status(State(state): State<AppState>, headers: HeaderMap) -> R
                     "bulk": {
                         "price": PriceTier::Bulk.price_usdc(),
                         "currency": "USDC",
-                        "description": PriceTier::Bulk.description()
+                        "description": PriceTier::Bulk.description(),
+                        "pricing": {
+                            "formula": "base + per_item * count * (10000 - discount_bps) / 10000",
+                            "unit": "micro_usdc",
+                            "base": x402.config.bulk_pricing.base_micro_usdc,
+                            "per_item": x402.config.bulk_pricing.per_item_micro_usdc,
+                            "discounts": x402.config.bulk_pricing.discounts,
+                        }
                     }
                 }
             })),
@@ -600,13 +1003,301 @@ pub async fn x402_status(State(state): State<AppState>, headers: HeaderMap) -> R
     }
 }
 
+/// Fetch a shared premium verification result
+///
+/// GET /api/v1/verifications/{token}
+///
+/// The token comes from a paid `verify-premium` response and works once,
+/// until it expires.
+#[utoipa::path(
+    get,
+    path = "/api/v1/verifications/{token}",
+    tag = "x402",
+    params(("token" = String, Path, description = "`result_token` from a paid verification")),
+    responses(
+        (status = 200, description = "The stored verification result", body = serde_json::Value),
+        (status = 401, description = "Forged, malformed or expired token", body = serde_json::Value),
+        (status = 404, description = "Result already fetched or no longer stored", body = serde_json::Value),
+    )
+)]
+pub async fn get_shared_verification(
+    State(state): State<AppState>,
+    Path(token): Path<String>,
+) -> Response {
+    let not_found = || {
+        (
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": "Verification result not found" })),
+        )
+            .into_response()
+    };
+    let Some(x402) = &state.x402 else {
+        return not_found();
+    };
+
+    let now_ms = chrono::Utc::now().timestamp_millis();
+    let id = match x402.result_tokens.verify(&token, now_ms) {
+        Ok(id) => id,
+        Err(e) => {
+            return (
+                StatusCode::UNAUTHORIZED,
+                Json(json!({ "error": e.to_string() })),
+            )
+                .into_response();
+        }
+    };
+
+    match take_verification_result(&state.pool, &id, now_ms).await {
+        Ok(Some(result)) => (StatusCode::OK, Json(result)).into_response(),
+        Ok(None) => not_found(),
+        Err(e) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({
+                "error": "Database error",
+                "details": e.to_string()
+            })),
+        )
+            .into_response(),
+    }
+}
+
+/// Check the `Authorization: Bearer` header against the configured admin token
+#[allow(clippy::result_large_err)]
+pub(crate) fn require_admin(state: &AppState, headers: &HeaderMap) -> Result<(), Response> {
+    let Some(expected) = state.admin_token.as_deref() else {
+        return Err((
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({
+                "error": "Admin endpoints not configured",
+                "hint": "Set API_ADMIN_TOKEN to enable"
+            })),
+        )
+            .into_response());
+    };
+
+    let provided = headers
+        .get("authorization")
+        .and_then(|h| h.to_str().ok())
+        .and_then(|auth| {
+            auth.get(..7)
+                .filter(|scheme| scheme.eq_ignore_ascii_case("bearer "))
+                .map(|_| auth[7..].trim())
+        });
+
+    // Constant-time comparison so the token can't be recovered byte by byte
+    let authorized = provided.is_some_and(|token| {
+        token.len() == expected.len()
+            && token
+                .bytes()
+                .zip(expected.bytes())
+                .fold(0u8, |acc, (a, b)| acc | (a ^ b))
+                == 0
+    });
+
+    if authorized {
+        Ok(())
+    } else {
+        Err((
+            StatusCode::UNAUTHORIZED,
+            Json(json!({ "error": "Invalid or missing admin token" })),
+        )
+            .into_response())
+    }
+}
+
+/// Reconcile recorded payment receipts against the chain
+///
+/// GET /admin/payments/reconcile?from=&to=
+///
+/// `from`/`to` bound the receipts' `verified_at` (Unix ms, inclusive). Each
+/// receipt is re-checked for amount, recipient and confirmation; the report
+/// lists per-receipt discrepancies plus totals.
+pub async fn get_payments_reconcile(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<ReconciliationQuery>,
+) -> Response {
+    if let Err(response) = require_admin(&state, &headers) {
+        return response;
+    }
+
+    if let (Some(from), Some(to)) = (query.from, query.to) {
+        if from > to {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(json!({ "error": "'from' must not be after 'to'" })),
+            )
+                .into_response();
+        }
+    }
+
+    let (Some(x402), Some(verifier)) = (&state.x402, &state.payment_verifier) else {
+        return (
+            StatusCode::SERVICE_UNAVAILABLE,
+            Json(json!({
+                "error": "Payment reconciliation not available",
+                "hint": "Set X402_ENABLED=true and X402_WALLET_ADDRESS to enable"
+            })),
+        )
+            .into_response();
+    };
+
+    let receipts = match list_payment_receipts_in_range(&state.pool, query.from, query.to).await {
+        Ok(receipts) => receipts,
+        Err(e) => {
+            tracing::error!("Failed to list payment receipts: {}", e);
+            return (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Failed to list payment receipts" })),
+            )
+                .into_response();
+        }
+    };
+
+    let report = reconcile_receipts(
+        receipts,
+        verifier.as_ref(),
+        &x402.config.wallet_address,
+        query.from,
+        query.to,
+    )
+    .await;
+
+    (StatusCode::OK, Json(report)).into_response()
+}
+
+/// List recorded payment receipts for audit and finance reporting
+///
+/// GET /api/v1/payments/receipts?evidence_id=&tier=&since_ms=&cursor=&limit=
+///
+/// Receipts identify payers, so unlike the other M2M endpoints a bearer token
+/// alone is not enough: it must be the admin token. Newest first, keyset
+/// paginated like `GET /evidence` (`limit` defaults to 50, at most 500).
+pub async fn get_payment_receipts(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(filter): Query<PaymentReceiptFilter>,
+    Query(pagination): Query<CursorPagination>,
+) -> Response {
+    if let Err(response) = require_admin(&state, &headers) {
+        return response;
+    }
+
+    let limit = pagination.limit.unwrap_or(50).clamp(1, 500);
+    let cursor = match pagination.cursor.as_deref().map(EvidenceCursor::decode) {
+        None => None,
+        Some(Ok(cursor)) => Some(cursor),
+        Some(Err(e)) => {
+            return (StatusCode::BAD_REQUEST, Json(json!({ "error": e }))).into_response();
+        }
+    };
+
+    match list_payment_receipts_after(&state.pool, &filter, cursor.as_ref(), limit).await {
+        Ok((receipts, next_cursor)) => (
+            StatusCode::OK,
+            Json(json!({
+                "data": receipts,
+                "limit": limit,
+                "next_cursor": next_cursor.map(|c| c.encode()),
+            })),
+        )
+            .into_response(),
+        Err(e) => {
+            tracing::error!("Failed to list payment receipts: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Failed to list payment receipts" })),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// List payments by refund status, the operators' refund queue
+///
+/// GET /api/v1/payments/refunds?status=eligible|refunded (default `eligible`),
+/// oldest first. Requires the admin token.
+pub async fn get_payment_refunds(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(query): Query<RefundQuery>,
+) -> Response {
+    if let Err(response) = require_admin(&state, &headers) {
+        return response;
+    }
+
+    let status = query.status.as_deref().unwrap_or(REFUND_ELIGIBLE);
+    if !REFUND_STATUSES.contains(&status) {
+        return (
+            StatusCode::BAD_REQUEST,
+            Json(json!({
+                "error": format!("unknown refund status '{}'", status),
+                "expected": REFUND_STATUSES,
+            })),
+        )
+            .into_response();
+    }
+
+    match list_payment_receipts_by_refund_status(&state.pool, status).await {
+        Ok(receipts) => (
+            StatusCode::OK,
+            Json(json!({ "status": status, "data": receipts })),
+        )
+            .into_response(),
+        Err(e) => {
+            tracing::error!("Failed to list refunds: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Failed to list refunds" })),
+            )
+                .into_response()
+        }
+    }
+}
+
+/// Record that an eligible refund was paid out
+///
+/// POST /api/v1/payments/refunds/{tx_signature}/refunded. Requires the admin
+/// token; 404 unless the payment is currently refund-eligible.
+pub async fn post_payment_refunded(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Path(tx_signature): Path<String>,
+) -> Response {
+    if let Err(response) = require_admin(&state, &headers) {
+        return response;
+    }
+
+    match mark_refunded(&state.pool, &tx_signature).await {
+        Ok(true) => (
+            StatusCode::OK,
+            Json(json!({ "tx_signature": tx_signature, "refund_status": "refunded" })),
+        )
+            .into_response(),
+        Ok(false) => (
+            StatusCode::NOT_FOUND,
+            Json(json!({ "error": "no refund-eligible payment with this signature" })),
+        )
+            .into_response(),
+        Err(e) => {
+            tracing::error!("Failed to mark refund: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(json!({ "error": "Failed to mark refund" })),
+            )
+                .into_response()
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_x402_state_devnet() {
-        let state = X402State::devnet("PhxRvk123");
+    #[tokio::test]
+    async fn test_x402_state_devnet() {
+        let pool = sqlx::SqlitePool::connect_lazy("sqlite::memory:").unwrap();
+        let state = X402State::devnet("PhxRvk123", &pool);
         assert!(state.facilitator.is_enabled());
         assert_eq!(state.config.wallet_address, "PhxRvk123");
         assert_eq!(state.config.network, "devnet");
@@ -619,4 +1310,51 @@ mod tests {
         assert!(!PriceTier::LegalAttestation.description().is_empty());
         assert!(!PriceTier::Bulk.description().is_empty());
     }
+
+    fn tx_ref(network: &str, chain: &str, tx_id: &str) -> TxRefOut {
+        TxRefOut {
+            network: network.to_string(),
+            chain: chain.to_string(),
+            tx_id: tx_id.to_string(),
+            confirmed: true,
+            timestamp: None,
+            explorer_url: None,
+        }
+    }
+
+    #[test]
+    fn test_chain_confirmations_independent_of_tx_ref_order() {
+        let evidence = crate::models::EvidenceOut {
+            id: "ev-1".to_string(),
+            digest_hex: "ab".repeat(32),
+            status: "done".to_string(),
+            attempts: 1,
+            last_error: None,
+            created_ms: 0,
+            updated_ms: 0,
+            source: "api".to_string(),
+        };
+        let req = VerifyEvidenceRequest {
+            evidence_id: "ev-1".to_string(),
+            chain: None,
+            tier: PriceTier::MultiChain,
+            count: None,
+        };
+
+        let mut refs = vec![
+            tx_ref("solana", "devnet", "sig123"),
+            tx_ref("etherlink", "testnet", "0xabc"),
+        ];
+        let forward = build_chain_confirmations(&evidence, &refs, &req);
+        refs.reverse();
+        let reversed = build_chain_confirmations(&evidence, &refs, &req);
+
+        assert_eq!(
+            serde_json::to_string(&forward).unwrap(),
+            serde_json::to_string(&reversed).unwrap()
+        );
+        assert_eq!(forward["solana"]["tx_id"], "sig123");
+        assert_eq!(forward["solana"]["network"], "devnet");
+        assert_eq!(forward["etherlink"]["tx_id"], "0xabc");
+    }
 }
@@ -7,17 +7,29 @@ use sqlx::{
     Pool, Sqlite,
 };
 
+pub mod anchoring;
 pub mod connection;
+pub mod cors;
 pub mod db;
 pub mod db_errors;
+pub mod detections;
 pub mod entities;
+pub mod error;
 pub mod handlers;
 pub mod handlers_x402;
+pub mod limits;
 pub mod migrations;
 pub mod models;
+pub mod openapi;
+pub mod payload_store;
 pub mod providers;
 pub mod rate_limit;
+pub mod reconciliation;
+pub mod replay_store;
 pub mod repository;
+pub mod request_id;
+pub mod result_tokens;
+pub mod webhooks;
 
 /// Application state shared across all handlers
 #[derive(Clone)]
@@ -28,9 +40,36 @@ pub struct AppState {
     pub x402: Option<handlers_x402::X402State>,
     /// Rate limiter for x402 endpoints
     pub rate_limiter: rate_limit::X402RateLimiter,
+    /// Inline anchoring for `anchor_mode: "sync"` (None if the capability is disabled)
+    pub sync_anchor: Option<anchoring::SyncAnchor>,
+    /// On-chain lookup for payment reconciliation (None if x402 is not configured)
+    pub payment_verifier: Option<std::sync::Arc<dyn reconciliation::PaymentVerifier + Send + Sync>>,
+    /// Bearer token for `/admin/*` reporting endpoints (None disables them)
+    pub admin_token: Option<String>,
+    /// Withhold Merkle proofs until their batch transaction is confirmed
+    pub require_confirmed_proofs: bool,
+    /// Payment and anchoring event notifications (None if no webhook URL is set)
+    pub webhooks: Option<webhooks::WebhookDispatcher>,
+    /// Live detection events for `/ws/detections` subscribers
+    pub detections: detections::DetectionBroadcaster,
+    /// How long an `Idempotency-Key` on `POST /evidence` stays bound to its job
+    pub idempotency_ttl: std::time::Duration,
+    /// Inline evidence payloads by digest (None unless `API_STORE_PAYLOADS` is set)
+    pub payloads: Option<std::sync::Arc<dyn payload_store::PayloadStore>>,
 }
 
+/// Default `Idempotency-Key` lifetime (24 hours)
+pub const DEFAULT_IDEMPOTENCY_TTL: std::time::Duration =
+    std::time::Duration::from_secs(24 * 60 * 60);
+
 pub async fn build_app() -> anyhow::Result<(Router, Pool<Sqlite>)> {
+    build_app_with_sync_anchor(anchoring::SyncAnchor::from_env()?).await
+}
+
+/// Build the app with an explicit sync-anchoring provider (or none)
+pub async fn build_app_with_sync_anchor(
+    sync_anchor: Option<anchoring::SyncAnchor>,
+) -> anyhow::Result<(Router, Pool<Sqlite>)> {
     // DB pool (use API_DB_URL, fallback to KEEPER_DB_URL, then sqlite file)
     let db_url = std::env::var("API_DB_URL")
         .ok()
@@ -62,9 +101,10 @@ pub async fn build_app() -> anyhow::Result<(Router, Pool<Sqlite>)> {
     // Run full migrations (includes outbox + countermeasures + audits + jamming)
     let migration_manager = crate::migrations::MigrationManager::new(pool.clone());
     migration_manager.migrate().await?;
+    check_migration_integrity(&migration_manager).await?;
 
     // Initialize x402 payment protocol (once at startup, not per-request)
-    let x402 = handlers_x402::X402State::from_env();
+    let x402 = handlers_x402::X402State::from_env(&pool);
     if x402.is_some() {
         tracing::info!("x402 payment protocol enabled");
     } else {
@@ -72,22 +112,154 @@ pub async fn build_app() -> anyhow::Result<(Router, Pool<Sqlite>)> {
     }
 
     // Initialize rate limiter for x402 endpoints
-    let rate_limiter = rate_limit::X402RateLimiter::new();
+    let rate_limits = x402
+        .as_ref()
+        .map(|x| x.config.rate_limits)
+        .unwrap_or_else(phoenix_x402::RateLimits::from_env);
+    let strategy = match std::env::var("X402_RATE_LIMIT_STRATEGY") {
+        Ok(value) => value.parse().unwrap_or_else(|e| {
+            tracing::warn!("{}, using token_bucket", e);
+            rate_limit::RateLimitStrategy::default()
+        }),
+        Err(_) => rate_limit::RateLimitStrategy::default(),
+    };
+    let rate_limiter = rate_limit::X402RateLimiter::from_limits(&rate_limits)
+        .with_tier_quotas(rate_limit::TierQuotas::from_env())
+        .with_strategy(strategy);
     tracing::debug!("x402 rate limiter initialized");
 
+    let payment_verifier = x402.as_ref().map(|x| {
+        std::sync::Arc::new(reconciliation::FacilitatorPaymentVerifier::new(
+            x.facilitator.clone(),
+        )) as std::sync::Arc<dyn reconciliation::PaymentVerifier + Send + Sync>
+    });
+    let admin_token = std::env::var("API_ADMIN_TOKEN")
+        .ok()
+        .filter(|t| !t.trim().is_empty());
+    let require_confirmed_proofs = std::env::var("PROOF_REQUIRE_CONFIRMED")
+        .map(|v| v == "true" || v == "1")
+        .unwrap_or(false);
+    let idempotency_ttl = std::env::var("EVIDENCE_IDEMPOTENCY_TTL_SECS")
+        .ok()
+        .and_then(|v| v.trim().parse::<u64>().ok())
+        .map(std::time::Duration::from_secs)
+        .unwrap_or(DEFAULT_IDEMPOTENCY_TTL);
+    let payloads = std::env::var("API_STORE_PAYLOADS")
+        .map(|v| v == "true" || v == "1")
+        .unwrap_or(false)
+        .then(|| {
+            std::sync::Arc::new(payload_store::SqlitePayloadStore::new(pool.clone()))
+                as std::sync::Arc<dyn payload_store::PayloadStore>
+        });
+    let webhooks = webhooks::WebhookDispatcher::from_env();
+    if let Some(dispatcher) = &webhooks {
+        tracing::info!(url = dispatcher.url(), "webhook notifications enabled");
+    }
+
     let state = AppState {
         pool: pool.clone(),
         x402,
         rate_limiter,
+        sync_anchor,
+        payment_verifier,
+        admin_token,
+        require_confirmed_proofs,
+        webhooks,
+        detections: detections::DetectionBroadcaster::default(),
+        idempotency_ttl,
+        payloads,
     };
-    let app = Router::new()
+    let app = router_with_cors(state, cors::cors_layer_from_env());
+    Ok((limits::RequestLimits::from_env().apply(app), pool))
+}
+
+/// Refuse to start when an applied migration's SQL has changed since it was
+/// applied, unless `API_ALLOW_MIGRATION_DRIFT` is set (then only warn)
+async fn check_migration_integrity(
+    migration_manager: &migrations::MigrationManager,
+) -> anyhow::Result<()> {
+    let drift = migration_manager.verify_integrity().await?;
+    if drift.is_empty() {
+        return Ok(());
+    }
+    for mismatch in &drift {
+        tracing::error!(
+            version = mismatch.version,
+            name = %mismatch.name,
+            recorded = %mismatch.recorded,
+            expected = ?mismatch.expected,
+            "applied migration does not match its source"
+        );
+    }
+    let allow_drift = std::env::var("API_ALLOW_MIGRATION_DRIFT")
+        .map(|v| v == "true" || v == "1")
+        .unwrap_or(false);
+    if allow_drift {
+        tracing::warn!(
+            "{} migration(s) drifted, starting anyway (API_ALLOW_MIGRATION_DRIFT)",
+            drift.len()
+        );
+        return Ok(());
+    }
+    anyhow::bail!(
+        "{} applied migration(s) do not match their source (first: version {}); set API_ALLOW_MIGRATION_DRIFT=true to start anyway",
+        drift.len(),
+        drift[0].version
+    )
+}
+
+/// Build the router for a fully constructed state, without CORS
+pub fn router(state: AppState) -> Router {
+    router_with_cors(state, None)
+}
+
+/// Build the router, applying `cors` to every route except the premium
+/// verification endpoints, which only machine clients may call
+pub fn router_with_cors(state: AppState, cors: Option<tower_http::cors::CorsLayer>) -> Router {
+    let mut routes = browser_routes();
+    if let Some(cors) = cors {
+        routes = routes.layer(cors);
+    }
+    routes
+        .route(
+            "/api/v1/evidence/verify-premium",
+            post(handlers_x402::verify_evidence_premium).layer(
+                tower_http::limit::RequestBodyLimitLayer::new(limits::PREMIUM_MAX_BODY_BYTES),
+            ),
+        )
+        .route(
+            "/api/v1/evidence/verify-premium-bulk",
+            post(handlers_x402::verify_evidence_premium_bulk).layer(
+                tower_http::limit::RequestBodyLimitLayer::new(limits::PREMIUM_BULK_MAX_BODY_BYTES),
+            ),
+        )
+        .layer(axum::middleware::from_fn(request_id::request_id_middleware))
+        .with_state(state)
+}
+
+/// Routes browser clients may reach when CORS allows their origin
+fn browser_routes() -> Router<AppState> {
+    Router::new()
         .route("/health", get(handlers::health))
+        .route("/health/ready", get(handlers::health_ready))
+        // API description
+        .route("/openapi.json", get(openapi::openapi_json))
+        .route("/docs", get(openapi::swagger_ui))
         // Evidence
         .route(
             "/evidence",
             post(handlers::post_evidence).get(handlers::list_evidence),
         )
+        .route("/evidence/stats", get(handlers::get_evidence_stats))
         .route("/evidence/{id}", get(handlers::get_evidence))
+        .route("/evidence/{id}/proof", get(handlers::get_evidence_proof))
+        .route(
+            "/evidence/{id}/payload",
+            get(handlers::get_evidence_payload),
+        )
+        // Detector events
+        .route("/detections", post(detections::post_detection))
+        .route("/ws/detections", get(detections::detections_ws))
         // Countermeasures
         .route(
             "/countermeasures",
@@ -123,18 +295,38 @@ pub async fn build_app() -> anyhow::Result<(Router, Pool<Sqlite>)> {
             "/admin/seed-team-members",
             post(handlers::post_seed_team_members),
         )
+        .route(
+            "/admin/payments/reconcile",
+            get(handlers_x402::get_payments_reconcile),
+        )
+        .route("/admin/dead-letters", get(handlers::list_dead_letters))
+        .route(
+            "/admin/dead-letters/{id}/replay",
+            post(handlers::post_replay_dead_letter),
+        )
+        .route("/admin/jobs/{id}/retry", post(handlers::post_retry_job))
         // Preorders
         .route(
             "/preorders",
             post(handlers::post_preorder).get(handlers::list_preorders),
         )
         .route("/preorders/{id}", get(handlers::get_preorder))
-        // x402 Premium Evidence Verification
+        // x402 status and payment records
+        .route("/api/v1/x402/status", get(handlers_x402::x402_status))
         .route(
-            "/api/v1/evidence/verify-premium",
-            post(handlers_x402::verify_evidence_premium),
+            "/api/v1/payments/receipts",
+            get(handlers_x402::get_payment_receipts),
+        )
+        .route(
+            "/api/v1/payments/refunds",
+            get(handlers_x402::get_payment_refunds),
+        )
+        .route(
+            "/api/v1/payments/refunds/{tx_signature}/refunded",
+            post(handlers_x402::post_payment_refunded),
+        )
+        .route(
+            "/api/v1/verifications/{token}",
+            get(handlers_x402::get_shared_verification),
         )
-        .route("/api/v1/x402/status", get(handlers_x402::x402_status))
-        .with_state(state);
-    Ok((app, pool))
 }
@@ -0,0 +1,86 @@
+//! Request body size and duration limits
+//!
+//! `build_app` wraps every route so a client can't make the API buffer,
+//! hash and store an arbitrarily large payload, or hold a connection open on
+//! a slow request. Oversized bodies get 413 Payload Too Large (up front when
+//! `Content-Length` says so, otherwise once the body is read past the limit);
+//! requests still running at the deadline get 408 Request Timeout.
+//!
+//! # Configuration
+//!
+//! | Variable | Default | Description |
+//! |----------|---------|-------------|
+//! | `API_MAX_BODY_BYTES` | `262144` | Largest request body accepted |
+//! | `API_REQUEST_TIMEOUT_SECS` | `30` | Upper bound on handling one request |
+//!
+//! Keep the timeout above `API_SYNC_ANCHOR_TIMEOUT_MS`, or inline anchoring
+//! is cut off before it can report its own timeout. Premium verification
+//! always has the tighter [`PREMIUM_MAX_BODY_BYTES`] cap on its own route
+//! ([`PREMIUM_BULK_MAX_BODY_BYTES`] for the bulk one).
+
+use axum::{http::StatusCode, Router};
+use std::time::Duration;
+use tower_http::{limit::RequestBodyLimitLayer, timeout::TimeoutLayer};
+
+/// Default largest request body (256 KiB)
+pub const DEFAULT_MAX_BODY_BYTES: usize = 256 * 1024;
+
+/// Default upper bound on handling one request
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Body cap for `POST /api/v1/evidence/verify-premium`, whose request is an
+/// evidence id and a tier
+pub const PREMIUM_MAX_BODY_BYTES: usize = 16 * 1024;
+
+/// Body cap for `POST /api/v1/evidence/verify-premium-bulk`, room for
+/// `MAX_BULK_EVIDENCE_IDS` ids of up to 100 bytes each
+pub const PREMIUM_BULK_MAX_BODY_BYTES: usize = 64 * 1024;
+
+/// Body size and timeout applied to every route
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestLimits {
+    pub max_body_bytes: usize,
+    pub timeout: Duration,
+}
+
+impl Default for RequestLimits {
+    fn default() -> Self {
+        Self {
+            max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+            timeout: DEFAULT_REQUEST_TIMEOUT,
+        }
+    }
+}
+
+impl RequestLimits {
+    /// Limits from `API_MAX_BODY_BYTES` / `API_REQUEST_TIMEOUT_SECS`, falling
+    /// back to the defaults for unset, unparsable or zero values
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let max_body_bytes = std::env::var("API_MAX_BODY_BYTES")
+            .ok()
+            .and_then(|v| v.trim().parse::<usize>().ok())
+            .filter(|bytes| *bytes > 0)
+            .unwrap_or(defaults.max_body_bytes);
+        let timeout = std::env::var("API_REQUEST_TIMEOUT_SECS")
+            .ok()
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .filter(|secs| *secs > 0)
+            .map(Duration::from_secs)
+            .unwrap_or(defaults.timeout);
+        Self {
+            max_body_bytes,
+            timeout,
+        }
+    }
+
+    /// Wrap every route of `router` in these limits
+    pub fn apply(self, router: Router) -> Router {
+        router
+            .layer(RequestBodyLimitLayer::new(self.max_body_bytes))
+            .layer(TimeoutLayer::with_status_code(
+                StatusCode::REQUEST_TIMEOUT,
+                self.timeout,
+            ))
+    }
+}
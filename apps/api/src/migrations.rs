@@ -1,3 +1,4 @@
+use sha2::{Digest, Sha256};
 use sqlx::{Pool, Row, Sqlite};
 use thiserror::Error;
 
@@ -46,6 +47,11 @@ impl MigrationManager {
                     next_attempt_ms INTEGER NOT NULL DEFAULT 0
                 );
                 "#,
+                down_sql: Some(
+                    r#"
+                DROP TABLE IF EXISTS outbox_jobs;
+                "#,
+                ),
             },
             Migration {
                 version: 2,
@@ -61,6 +67,11 @@ impl MigrationManager {
                     PRIMARY KEY (job_id, network, chain, tx_id)
                 );
                 "#,
+                down_sql: Some(
+                    r#"
+                DROP TABLE IF EXISTS outbox_tx_refs;
+                "#,
+                ),
             },
             Migration {
                 version: 3,
@@ -70,6 +81,13 @@ impl MigrationManager {
                 CREATE INDEX IF NOT EXISTS idx_outbox_jobs_created_ms ON outbox_jobs(created_ms);
                 CREATE INDEX IF NOT EXISTS idx_outbox_jobs_next_attempt ON outbox_jobs(next_attempt_ms);
                 "#,
+                down_sql: Some(
+                    r#"
+                DROP INDEX IF EXISTS idx_outbox_jobs_status;
+                DROP INDEX IF EXISTS idx_outbox_jobs_created_ms;
+                DROP INDEX IF EXISTS idx_outbox_jobs_next_attempt;
+                "#,
+                ),
             },
             Migration {
                 version: 4,
@@ -78,6 +96,12 @@ impl MigrationManager {
                 CREATE INDEX IF NOT EXISTS idx_outbox_tx_refs_job_id ON outbox_tx_refs(job_id);
                 CREATE INDEX IF NOT EXISTS idx_outbox_tx_refs_confirmed ON outbox_tx_refs(confirmed);
                 "#,
+                down_sql: Some(
+                    r#"
+                DROP INDEX IF EXISTS idx_outbox_tx_refs_job_id;
+                DROP INDEX IF EXISTS idx_outbox_tx_refs_confirmed;
+                "#,
+                ),
             },
             Migration {
                 version: 5,
@@ -99,6 +123,11 @@ impl MigrationManager {
                 CREATE INDEX IF NOT EXISTS idx_countermeasure_deployments_deployed_at ON countermeasure_deployments(deployed_at);
                 CREATE INDEX IF NOT EXISTS idx_countermeasure_deployments_type ON countermeasure_deployments(countermeasure_type);
                 "#,
+                down_sql: Some(
+                    r#"
+                DROP TABLE IF EXISTS countermeasure_deployments;
+                "#,
+                ),
             },
             Migration {
                 version: 6,
@@ -121,6 +150,11 @@ impl MigrationManager {
                 CREATE INDEX IF NOT EXISTS idx_signal_disruption_audit_event_type ON signal_disruption_audit(event_type);
                 CREATE INDEX IF NOT EXISTS idx_signal_disruption_audit_severity ON signal_disruption_audit(severity);
                 "#,
+                down_sql: Some(
+                    r#"
+                DROP TABLE IF EXISTS signal_disruption_audit;
+                "#,
+                ),
             },
             Migration {
                 version: 7,
@@ -146,6 +180,11 @@ impl MigrationManager {
                 CREATE INDEX IF NOT EXISTS idx_jamming_operations_operation_id ON jamming_operations(operation_id);
                 CREATE INDEX IF NOT EXISTS idx_jamming_operations_target_frequency ON jamming_operations(target_frequency_range);
                 "#,
+                down_sql: Some(
+                    r#"
+                DROP TABLE IF EXISTS jamming_operations;
+                "#,
+                ),
             },
             Migration {
                 version: 8,
@@ -173,6 +212,8 @@ impl MigrationManager {
                 CREATE INDEX IF NOT EXISTS idx_outbox_tx_refs_job_id ON outbox_tx_refs(job_id);
                 CREATE INDEX IF NOT EXISTS idx_outbox_tx_refs_confirmed ON outbox_tx_refs(confirmed);
                 "#,
+                // Irreversible: duplicate rows were dropped on copy
+                down_sql: None,
             },
             Migration {
                 version: 9,
@@ -198,6 +239,11 @@ impl MigrationManager {
                 -- Index for analytics by tier
                 CREATE INDEX IF NOT EXISTS idx_payment_receipts_tier ON payment_receipts(tier);
                 "#,
+                down_sql: Some(
+                    r#"
+                DROP TABLE IF EXISTS payment_receipts;
+                "#,
+                ),
             },
             Migration {
                 version: 10,
@@ -243,6 +289,13 @@ impl MigrationManager {
                 CREATE INDEX IF NOT EXISTS idx_career_applications_user_id ON career_applications(user_id);
                 CREATE INDEX IF NOT EXISTS idx_career_applications_status ON career_applications(status);
                 "#,
+                down_sql: Some(
+                    r#"
+                DROP TABLE IF EXISTS career_applications;
+                DROP TABLE IF EXISTS sessions;
+                DROP TABLE IF EXISTS users;
+                "#,
+                ),
             },
             Migration {
                 version: 11,
@@ -279,6 +332,250 @@ impl MigrationManager {
                 );
                 CREATE INDEX IF NOT EXISTS idx_preorder_items_preorder_id ON preorder_items(preorder_id);
                 "#,
+                down_sql: Some(
+                    r#"
+                DROP TABLE IF EXISTS preorder_items;
+                DROP TABLE IF EXISTS preorders;
+                "#,
+                ),
+            },
+            Migration {
+                version: 12,
+                name: "add_merkle_batch_tables",
+                sql: r#"
+                -- Batch anchoring tables, shared with the keeper's BatchAnchor
+                CREATE TABLE IF NOT EXISTS merkle_batches (
+                    id TEXT PRIMARY KEY,
+                    merkle_root TEXT NOT NULL,
+                    item_count INTEGER NOT NULL,
+                    created_at INTEGER NOT NULL,
+                    anchored_at INTEGER,
+                    tx_network TEXT,
+                    tx_chain TEXT,
+                    tx_id TEXT,
+                    tx_confirmed INTEGER DEFAULT 0
+                );
+                CREATE TABLE IF NOT EXISTS merkle_proofs (
+                    job_id TEXT PRIMARY KEY,
+                    batch_id TEXT NOT NULL,
+                    leaf_index INTEGER NOT NULL,
+                    proof_json TEXT NOT NULL,
+                    FOREIGN KEY (batch_id) REFERENCES merkle_batches(id)
+                );
+                CREATE INDEX IF NOT EXISTS idx_proofs_batch_id ON merkle_proofs(batch_id);
+                "#,
+                down_sql: Some(
+                    r#"
+                DROP TABLE IF EXISTS merkle_proofs;
+                DROP TABLE IF EXISTS merkle_batches;
+                "#,
+                ),
+            },
+            Migration {
+                version: 13,
+                name: "add_evidence_source",
+                sql: r#"
+                -- Originating system for each evidence job (cli, simulator, edge nodes, api)
+                ALTER TABLE outbox_jobs ADD COLUMN source TEXT NOT NULL DEFAULT 'api';
+                CREATE INDEX IF NOT EXISTS idx_outbox_jobs_source ON outbox_jobs(source);
+                "#,
+                down_sql: Some(
+                    r#"
+                DROP INDEX IF EXISTS idx_outbox_jobs_source;
+                ALTER TABLE outbox_jobs DROP COLUMN source;
+                "#,
+                ),
+            },
+            Migration {
+                version: 14,
+                name: "add_evidence_cursor_index",
+                sql: r#"
+                -- Keyset pagination over GET /evidence orders by (created_ms, id)
+                CREATE INDEX IF NOT EXISTS idx_outbox_jobs_created_id ON outbox_jobs(created_ms, id);
+                "#,
+                down_sql: Some(
+                    r#"
+                DROP INDEX IF EXISTS idx_outbox_jobs_created_id;
+                "#,
+                ),
+            },
+            Migration {
+                version: 15,
+                name: "add_job_priority",
+                sql: r#"
+                -- Higher-priority jobs are fetched by the keeper ahead of older routine ones
+                ALTER TABLE outbox_jobs ADD COLUMN priority INTEGER NOT NULL DEFAULT 0;
+                CREATE INDEX IF NOT EXISTS idx_outbox_jobs_queue ON outbox_jobs(status, priority DESC, created_ms);
+                "#,
+                down_sql: Some(
+                    r#"
+                DROP INDEX IF EXISTS idx_outbox_jobs_queue;
+                ALTER TABLE outbox_jobs DROP COLUMN priority;
+                "#,
+                ),
+            },
+            Migration {
+                version: 16,
+                name: "add_payment_overpayment",
+                sql: r#"
+                -- USDC paid beyond the price, NULL for exact payments
+                ALTER TABLE payment_receipts ADD COLUMN overpaid_usdc TEXT;
+                "#,
+                down_sql: Some(
+                    r#"
+                ALTER TABLE payment_receipts DROP COLUMN overpaid_usdc;
+                "#,
+                ),
+            },
+            Migration {
+                version: 17,
+                name: "add_job_metadata",
+                sql: r#"
+                -- Canonical JSON of the submitted metadata, NULL when none was given
+                ALTER TABLE outbox_jobs ADD COLUMN metadata TEXT;
+                "#,
+                down_sql: Some(
+                    r#"
+                ALTER TABLE outbox_jobs DROP COLUMN metadata;
+                "#,
+                ),
+            },
+            Migration {
+                version: 18,
+                name: "create_idempotency_keys",
+                sql: r#"
+                -- Idempotency-Key header to the job it created. No foreign key:
+                -- the key is claimed before the job row is inserted
+                CREATE TABLE IF NOT EXISTS idempotency_keys (
+                    key TEXT PRIMARY KEY,
+                    job_id TEXT NOT NULL,
+                    digest_hex TEXT NOT NULL,
+                    created_ms INTEGER NOT NULL,
+                    expires_ms INTEGER NOT NULL
+                );
+                CREATE INDEX IF NOT EXISTS idx_idempotency_keys_expires ON idempotency_keys(expires_ms);
+                "#,
+                down_sql: Some(
+                    r#"
+                DROP TABLE IF EXISTS idempotency_keys;
+                "#,
+                ),
+            },
+            Migration {
+                version: 19,
+                name: "add_tx_refs_unconfirmed_index",
+                sql: r#"
+                -- The keeper pages through unconfirmed tx refs oldest first
+                CREATE INDEX IF NOT EXISTS idx_outbox_tx_refs_unconfirmed ON outbox_tx_refs(confirmed, timestamp);
+                "#,
+                down_sql: Some(
+                    r#"
+                DROP INDEX IF EXISTS idx_outbox_tx_refs_unconfirmed;
+                "#,
+                ),
+            },
+            Migration {
+                version: 20,
+                name: "add_payment_refund_status",
+                sql: r#"
+                -- 'eligible' when a paid request could not be served, 'refunded' once paid back
+                ALTER TABLE payment_receipts ADD COLUMN refund_status TEXT;
+                CREATE INDEX IF NOT EXISTS idx_payment_receipts_refund_status ON payment_receipts(refund_status);
+                "#,
+                down_sql: Some(
+                    r#"
+                DROP INDEX IF EXISTS idx_payment_receipts_refund_status;
+                ALTER TABLE payment_receipts DROP COLUMN refund_status;
+                "#,
+                ),
+            },
+            Migration {
+                version: 21,
+                name: "add_job_event_type",
+                sql: r#"
+                -- metadata.event_type, indexed so GET /evidence?metadata.event_type= avoids a JSON scan
+                ALTER TABLE outbox_jobs ADD COLUMN event_type TEXT GENERATED ALWAYS AS (json_extract(metadata, '$.event_type')) VIRTUAL;
+                CREATE INDEX IF NOT EXISTS idx_outbox_jobs_event_type ON outbox_jobs(event_type);
+                "#,
+                down_sql: Some(
+                    r#"
+                DROP INDEX IF EXISTS idx_outbox_jobs_event_type;
+                ALTER TABLE outbox_jobs DROP COLUMN event_type;
+                "#,
+                ),
+            },
+            Migration {
+                version: 22,
+                name: "create_verification_results",
+                sql: r#"
+                -- Paid premium verification results, fetched once via a signed result token
+                CREATE TABLE IF NOT EXISTS verification_results (
+                    id TEXT PRIMARY KEY,
+                    evidence_id TEXT NOT NULL,
+                    result TEXT NOT NULL,
+                    created_ms INTEGER NOT NULL,
+                    expires_ms INTEGER NOT NULL
+                );
+                CREATE INDEX IF NOT EXISTS idx_verification_results_expires ON verification_results(expires_ms);
+                "#,
+                down_sql: Some(
+                    r#"
+                DROP INDEX IF EXISTS idx_verification_results_expires;
+                DROP TABLE IF EXISTS verification_results;
+                "#,
+                ),
+            },
+            Migration {
+                version: 23,
+                name: "create_evidence_payloads",
+                sql: r#"
+                -- Submitted evidence payloads, content-addressed by their SHA-256 digest
+                CREATE TABLE IF NOT EXISTS evidence_payloads (
+                    digest_hex TEXT PRIMARY KEY,
+                    bytes BLOB NOT NULL,
+                    size INTEGER NOT NULL,
+                    created_ms INTEGER NOT NULL
+                );
+                -- MIME type given at submission, served back with the payload
+                ALTER TABLE outbox_jobs ADD COLUMN payload_mime TEXT;
+                "#,
+                down_sql: Some(
+                    r#"
+                ALTER TABLE outbox_jobs DROP COLUMN payload_mime;
+                DROP TABLE IF EXISTS evidence_payloads;
+                "#,
+                ),
+            },
+            Migration {
+                version: 24,
+                name: "add_payment_token",
+                sql: r#"
+                -- Token the payment was made in and the amount in that token.
+                -- Older receipts keep USDC and NULL: amount_usdc is what was paid
+                ALTER TABLE payment_receipts ADD COLUMN token TEXT NOT NULL DEFAULT 'USDC';
+                ALTER TABLE payment_receipts ADD COLUMN token_amount TEXT;
+                "#,
+                down_sql: Some(
+                    r#"
+                ALTER TABLE payment_receipts DROP COLUMN token_amount;
+                ALTER TABLE payment_receipts DROP COLUMN token;
+                "#,
+                ),
+            },
+            Migration {
+                version: 25,
+                name: "add_merkle_batch_construction_version",
+                sql: r#"
+                -- How each batch's Merkle nodes were hashed. Proofs are verified
+                -- with this rather than a version the proof claims. Existing
+                -- batches predate domain separation (0, legacy)
+                ALTER TABLE merkle_batches ADD COLUMN construction_version INTEGER NOT NULL DEFAULT 0;
+                "#,
+                down_sql: Some(
+                    r#"
+                ALTER TABLE merkle_batches DROP COLUMN construction_version;
+                "#,
+                ),
             },
         ]
     }
@@ -290,13 +587,28 @@ impl MigrationManager {
             CREATE TABLE IF NOT EXISTS schema_migrations (
                 version INTEGER PRIMARY KEY,
                 name TEXT NOT NULL,
-                applied_at INTEGER NOT NULL
+                applied_at INTEGER NOT NULL,
+                checksum TEXT
             );
             "#,
         )
         .execute(&self.pool)
         .await?;
 
+        // Tables created before checksums were tracked (or by
+        // SqliteProvider::initialize) lack the column
+        let has_checksum = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM pragma_table_info('schema_migrations') WHERE name = 'checksum'",
+        )
+        .fetch_one(&self.pool)
+        .await?
+            > 0;
+        if !has_checksum {
+            sqlx::query("ALTER TABLE schema_migrations ADD COLUMN checksum TEXT")
+                .execute(&self.pool)
+                .await?;
+        }
+
         Ok(())
     }
 
@@ -309,7 +621,11 @@ impl MigrationManager {
         Ok(result.unwrap_or(0))
     }
 
-    /// Apply a migration
+    /// Apply a migration.
+    ///
+    /// All of its statements and its `schema_migrations` row share one
+    /// transaction (SQLite DDL is transactional), so a failing statement
+    /// leaves neither a half-applied schema nor a version row behind.
     async fn apply_migration(&self, version: i32, name: &str, sql: &str) -> Result<()> {
         let mut tx = self.pool.begin().await?;
 
@@ -321,17 +637,26 @@ impl MigrationManager {
             .collect();
 
         for statement in statements {
-            sqlx::query(statement).execute(&mut *tx).await?;
+            if let Err(e) = sqlx::query(statement).execute(&mut *tx).await {
+                // The keeper's ensure_schema may already have added a column to a
+                // shared table; treat that ADD COLUMN as applied
+                if is_add_column(statement) && e.to_string().contains("duplicate column name") {
+                    tracing::debug!("Column already present, skipping: {}", statement);
+                    continue;
+                }
+                return Err(e.into());
+            }
         }
 
         // Record the migration
         let now = chrono::Utc::now().timestamp_millis();
         sqlx::query(
-            "INSERT OR IGNORE INTO schema_migrations (version, name, applied_at) VALUES (?1, ?2, ?3)"
+            "INSERT OR IGNORE INTO schema_migrations (version, name, applied_at, checksum) VALUES (?1, ?2, ?3, ?4)"
         )
         .bind(version)
         .bind(name)
         .bind(now)
+        .bind(checksum(sql))
         .execute(&mut *tx)
         .await?;
 
@@ -341,15 +666,30 @@ impl MigrationManager {
 
     /// Run all pending migrations
     pub async fn migrate(&self) -> Result<()> {
+        self.migrate_to(Self::get_migrations().len() as i32).await
+    }
+
+    /// Apply pending migrations up to and including `target_version`.
+    ///
+    /// A target at or below the current version does nothing (see
+    /// [`rollback`](Self::rollback)), and one past the latest migration is an
+    /// error.
+    pub async fn migrate_to(&self, target_version: i32) -> Result<()> {
+        let latest_version = Self::get_migrations().len() as i32;
+        if target_version > latest_version {
+            return Err(MigrationError::Migration(format!(
+                "target version {} is past the latest migration ({})",
+                target_version, latest_version
+            )));
+        }
+
         self.init_migration_table().await?;
+        self.backfill_checksums().await?;
         let current_version = self.get_current_version().await?;
 
-        // Get migrations
-        let migrations = Self::get_migrations();
-
         // Apply pending migrations
-        for migration in migrations {
-            if migration.version > current_version {
+        for migration in Self::get_migrations() {
+            if migration.version > current_version && migration.version <= target_version {
                 tracing::info!(
                     "Applying migration {}: {}",
                     migration.version,
@@ -363,6 +703,139 @@ impl MigrationManager {
         Ok(())
     }
 
+    /// Record checksums for migrations applied before checksums were tracked,
+    /// trusting that their SQL has not changed since
+    async fn backfill_checksums(&self) -> Result<()> {
+        for migration in Self::get_migrations() {
+            sqlx::query(
+                "UPDATE schema_migrations SET checksum = ?1 WHERE version = ?2 AND checksum IS NULL",
+            )
+            .bind(checksum(migration.sql))
+            .bind(migration.version)
+            .execute(&self.pool)
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Compare the checksum recorded for each applied migration with the
+    /// checksum of its SQL in this build. An empty result means no drift.
+    pub async fn verify_integrity(&self) -> Result<Vec<ChecksumMismatch>> {
+        self.init_migration_table().await?;
+        let recorded = sqlx::query(
+            "SELECT version, name, checksum FROM schema_migrations WHERE checksum IS NOT NULL ORDER BY version",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let migrations = Self::get_migrations();
+        Ok(recorded
+            .into_iter()
+            .filter_map(|row| {
+                let version = row.get::<i32, _>(0);
+                let recorded = row.get::<String, _>(2);
+                let expected = migrations
+                    .iter()
+                    .find(|m| m.version == version)
+                    .map(|m| checksum(m.sql));
+                (expected.as_ref() != Some(&recorded)).then(|| ChecksumMismatch {
+                    version,
+                    name: row.get::<String, _>(1),
+                    recorded,
+                    expected,
+                })
+            })
+            .collect())
+    }
+
+    /// Revert applied migrations newer than `target_version`, newest first.
+    ///
+    /// Every migration to revert must have a down script; if any does not,
+    /// nothing is reverted. Each migration is reverted in its own transaction
+    /// together with removing its `schema_migrations` row.
+    pub async fn rollback(&self, target_version: i32) -> Result<()> {
+        if target_version < 0 {
+            return Err(MigrationError::Migration(format!(
+                "invalid rollback target version {}",
+                target_version
+            )));
+        }
+
+        self.init_migration_table().await?;
+        let current_version = self.get_current_version().await?;
+
+        let to_revert: Vec<Migration> = Self::get_migrations()
+            .into_iter()
+            .rev()
+            .filter(|m| m.version > target_version && m.version <= current_version)
+            .collect();
+
+        if let Some(m) = to_revert.iter().find(|m| m.down_sql.is_none()) {
+            return Err(MigrationError::Migration(format!(
+                "migration {} ({}) has no down script, cannot roll back to version {}",
+                m.version, m.name, target_version
+            )));
+        }
+
+        for migration in to_revert {
+            tracing::info!(
+                "Reverting migration {}: {}",
+                migration.version,
+                migration.name
+            );
+            let down_sql = migration.down_sql.unwrap_or_default();
+            self.revert_migration(migration.version, down_sql).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Run a migration's down script and forget it was applied
+    async fn revert_migration(&self, version: i32, down_sql: &str) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        for statement in down_sql
+            .split(';')
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+        {
+            sqlx::query(statement).execute(&mut *tx).await?;
+        }
+
+        sqlx::query("DELETE FROM schema_migrations WHERE version = ?1")
+            .bind(version)
+            .execute(&mut *tx)
+            .await?;
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Migrations `migrate` would apply, in order, without touching the
+    /// database (not even to create the tracking table)
+    pub async fn plan(&self) -> Result<Vec<PendingMigration>> {
+        let tracked = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = 'schema_migrations'",
+        )
+        .fetch_one(&self.pool)
+        .await?
+            > 0;
+        let current_version = if tracked {
+            self.get_current_version().await?
+        } else {
+            0
+        };
+
+        Ok(Self::get_migrations()
+            .into_iter()
+            .filter(|migration| migration.version > current_version)
+            .map(|migration| PendingMigration {
+                version: migration.version,
+                name: migration.name.to_string(),
+            })
+            .collect())
+    }
+
     /// Check if migrations are up to date
     pub async fn is_up_to_date(&self) -> Result<bool> {
         self.init_migration_table().await?;
@@ -404,6 +877,28 @@ struct Migration {
     version: i32,
     name: &'static str,
     sql: &'static str,
+    /// Reverts `sql`, or `None` when the migration cannot be undone
+    down_sql: Option<&'static str>,
+}
+
+/// A migration not yet applied, as listed by [`MigrationManager::plan`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingMigration {
+    pub version: i32,
+    pub name: String,
+}
+
+/// An applied migration whose SQL no longer matches what was applied,
+/// as reported by [`MigrationManager::verify_integrity`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChecksumMismatch {
+    pub version: i32,
+    pub name: String,
+    /// Checksum stored when the migration was applied
+    pub recorded: String,
+    /// Checksum of the migration's current SQL, `None` if this build no
+    /// longer has a migration with that version
+    pub expected: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -413,6 +908,18 @@ pub struct AppliedMigration {
     pub applied_at: i64,
 }
 
+/// SHA-256 of a migration's SQL, hex-encoded
+fn checksum(sql: &str) -> String {
+    hex::encode(Sha256::digest(sql.as_bytes()))
+}
+
+fn is_add_column(statement: &str) -> bool {
+    statement
+        .lines()
+        .filter(|line| !line.trim_start().starts_with("--"))
+        .any(|line| line.to_ascii_uppercase().contains("ADD COLUMN"))
+}
+
 #[derive(Debug, Clone)]
 pub struct MigrationStatus {
     pub current_version: i32,
@@ -456,8 +963,8 @@ mod tests {
         // Check status
         let status = migration_manager.get_status().await.unwrap();
         assert!(status.is_up_to_date);
-        assert_eq!(status.current_version, 11);
-        assert_eq!(status.applied_migrations.len(), 11);
+        assert_eq!(status.current_version, 25);
+        assert_eq!(status.applied_migrations.len(), 25);
 
         // Verify tables exist
         let tables = sqlx::query("SELECT name FROM sqlite_master WHERE type='table'")
@@ -487,4 +994,253 @@ mod tests {
         // Should still be up to date
         assert!(migration_manager.is_up_to_date().await.unwrap());
     }
+
+    async fn named_pool(name: &str) -> Pool<Sqlite> {
+        SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect(&format!("sqlite:file:{}?mode=memory&cache=shared", name))
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_plan_lists_pending_migrations_without_applying() {
+        let migration_manager = MigrationManager::new(named_pool("migration_plan").await);
+
+        let plan = migration_manager.plan().await.unwrap();
+        assert_eq!(plan.len(), MigrationManager::get_migrations().len());
+        assert_eq!(
+            plan[0],
+            PendingMigration {
+                version: 1,
+                name: "initial_schema".to_string(),
+            }
+        );
+        assert!(plan.windows(2).all(|w| w[0].version < w[1].version));
+
+        // Planning is read-only
+        let tables: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM sqlite_master")
+            .fetch_one(&migration_manager.pool)
+            .await
+            .unwrap();
+        assert_eq!(tables, 0);
+
+        migration_manager.migrate().await.unwrap();
+        assert!(migration_manager.plan().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_migrate_to_applies_up_to_target() {
+        let migration_manager = MigrationManager::new(named_pool("migration_to_target").await);
+
+        migration_manager.migrate_to(3).await.unwrap();
+        let status = migration_manager.get_status().await.unwrap();
+        assert_eq!(status.current_version, 3);
+        assert_eq!(status.applied_migrations.len(), 3);
+        assert!(!status.is_up_to_date);
+        assert_eq!(migration_manager.plan().await.unwrap()[0].version, 4);
+
+        // migrate_to never reverts; unknown versions are rejected
+        migration_manager.migrate_to(1).await.unwrap();
+        assert_eq!(
+            migration_manager
+                .get_status()
+                .await
+                .unwrap()
+                .current_version,
+            3
+        );
+        assert!(matches!(
+            migration_manager.migrate_to(i32::MAX).await,
+            Err(MigrationError::Migration(_))
+        ));
+
+        migration_manager.migrate().await.unwrap();
+        assert!(migration_manager.is_up_to_date().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_migration_tolerates_columns_added_by_keeper() {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite:file:keeper_first_migration?mode=memory&cache=shared")
+            .await
+            .unwrap();
+        // The keeper created the shared table first, priority column included
+        sqlx::query(
+            "CREATE TABLE outbox_jobs (id TEXT PRIMARY KEY, payload_sha256 TEXT NOT NULL, status TEXT NOT NULL DEFAULT 'queued', attempts INTEGER NOT NULL DEFAULT 0, last_error TEXT, created_ms INTEGER NOT NULL, updated_ms INTEGER NOT NULL, next_attempt_ms INTEGER NOT NULL DEFAULT 0, priority INTEGER NOT NULL DEFAULT 0)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let migration_manager = MigrationManager::new(pool);
+        migration_manager.migrate().await.unwrap();
+        assert!(migration_manager.is_up_to_date().await.unwrap());
+    }
+
+    async fn table_exists(pool: &Pool<Sqlite>, name: &str) -> bool {
+        sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name = ?1",
+        )
+        .bind(name)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+            > 0
+    }
+
+    #[tokio::test]
+    async fn test_rollback_reverts_to_target_version() {
+        let migration_manager = MigrationManager::new(named_pool("migration_rollback").await);
+        migration_manager.migrate_to(5).await.unwrap();
+        assert!(table_exists(&migration_manager.pool, "countermeasure_deployments").await);
+
+        migration_manager.rollback(4).await.unwrap();
+
+        assert!(!table_exists(&migration_manager.pool, "countermeasure_deployments").await);
+        assert!(table_exists(&migration_manager.pool, "outbox_jobs").await);
+        let status = migration_manager.get_status().await.unwrap();
+        assert_eq!(status.current_version, 4);
+        assert_eq!(status.applied_migrations.len(), 4);
+
+        // Reverted migrations apply again
+        migration_manager.migrate().await.unwrap();
+        assert!(table_exists(&migration_manager.pool, "countermeasure_deployments").await);
+        assert!(migration_manager.is_up_to_date().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_rollback_reverts_column_migrations() {
+        let migration_manager =
+            MigrationManager::new(named_pool("migration_rollback_columns").await);
+        migration_manager.migrate().await.unwrap();
+
+        migration_manager.rollback(8).await.unwrap();
+
+        assert_eq!(
+            migration_manager
+                .get_status()
+                .await
+                .unwrap()
+                .current_version,
+            8
+        );
+        let columns: Vec<String> =
+            sqlx::query_scalar("SELECT name FROM pragma_table_xinfo('outbox_jobs')")
+                .fetch_all(&migration_manager.pool)
+                .await
+                .unwrap();
+        assert!(!columns.contains(&"source".to_string()));
+        assert!(!columns.contains(&"priority".to_string()));
+        assert!(!columns.contains(&"metadata".to_string()));
+        assert!(!columns.contains(&"event_type".to_string()));
+        assert!(!table_exists(&migration_manager.pool, "payment_receipts").await);
+
+        migration_manager.migrate().await.unwrap();
+        assert!(migration_manager.is_up_to_date().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_rollback_refuses_migration_without_down_script() {
+        let migration_manager =
+            MigrationManager::new(named_pool("migration_rollback_refused").await);
+        migration_manager.migrate_to(9).await.unwrap();
+
+        // Migration 8 cannot be undone, so nothing is reverted
+        let err = migration_manager.rollback(7).await.unwrap_err();
+        assert!(err.to_string().contains("migration 8"), "{}", err);
+        assert_eq!(
+            migration_manager
+                .get_status()
+                .await
+                .unwrap()
+                .current_version,
+            9
+        );
+        assert!(table_exists(&migration_manager.pool, "payment_receipts").await);
+    }
+
+    #[tokio::test]
+    async fn test_verify_integrity_flags_changed_checksum() {
+        let migration_manager = MigrationManager::new(named_pool("migration_checksum").await);
+        migration_manager.migrate_to(6).await.unwrap();
+        assert!(migration_manager
+            .verify_integrity()
+            .await
+            .unwrap()
+            .is_empty());
+
+        sqlx::query("UPDATE schema_migrations SET checksum = 'edited' WHERE version = 5")
+            .execute(&migration_manager.pool)
+            .await
+            .unwrap();
+
+        let drift = migration_manager.verify_integrity().await.unwrap();
+        assert_eq!(drift.len(), 1);
+        assert_eq!(drift[0].version, 5);
+        assert_eq!(drift[0].name, "add_countermeasure_deployments_table");
+        assert_eq!(drift[0].recorded, "edited");
+        assert_eq!(
+            drift[0].expected.as_deref(),
+            Some(checksum(MigrationManager::get_migrations()[4].sql).as_str())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_checksums_backfilled_for_untracked_migrations() {
+        let pool = named_pool("migration_checksum_backfill").await;
+        // Tracking table as created before checksums existed
+        sqlx::query(
+            "CREATE TABLE schema_migrations (version INTEGER PRIMARY KEY, name TEXT NOT NULL, applied_at INTEGER NOT NULL)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(MigrationManager::get_migrations()[0].sql)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO schema_migrations (version, name, applied_at) VALUES (1, 'initial_schema', 0)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let migration_manager = MigrationManager::new(pool);
+
+        migration_manager.migrate().await.unwrap();
+
+        let missing: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM schema_migrations WHERE checksum IS NULL")
+                .fetch_one(&migration_manager.pool)
+                .await
+                .unwrap();
+        assert_eq!(missing, 0);
+        assert!(migration_manager
+            .verify_integrity()
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_failed_statement_rolls_back_whole_migration() {
+        let migration_manager = MigrationManager::new(named_pool("migration_atomic").await);
+        migration_manager.migrate_to(1).await.unwrap();
+
+        let result = migration_manager
+            .apply_migration(
+                2,
+                "broken",
+                "CREATE TABLE half_applied (id TEXT PRIMARY KEY); CREATE TABLEX broken (id TEXT)",
+            )
+            .await;
+
+        assert!(matches!(result, Err(MigrationError::Database(_))));
+        assert!(!table_exists(&migration_manager.pool, "half_applied").await);
+        let status = migration_manager.get_status().await.unwrap();
+        assert_eq!(status.current_version, 1);
+        assert_eq!(status.applied_migrations.len(), 1);
+    }
 }
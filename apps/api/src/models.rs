@@ -1,20 +1,150 @@
 use serde::{Deserialize, Serialize};
+use utoipa::{IntoParams, ToSchema};
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
 pub struct Pagination {
     pub page: Option<i64>,
     pub per_page: Option<i64>,
 }
 
-#[derive(Debug, Deserialize)]
+/// Keyset pagination for `GET /evidence?cursor=...&limit=...`
+#[derive(Debug, Default, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct CursorPagination {
+    pub cursor: Option<String>,
+    pub limit: Option<i64>,
+}
+
+/// Query for `POST /admin/jobs/{id}/retry`
+#[derive(Debug, Default, Deserialize)]
+pub struct RetryJobParams {
+    /// Start the job's attempt budget over
+    #[serde(default)]
+    pub reset_attempts: bool,
+}
+
+/// Position after the last row of a page, ordered by `(created_ms, id)` descending.
+///
+/// Rows inserted while a client walks the list sort ahead of the cursor, so
+/// pages never shift underneath it the way `OFFSET` pages do.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvidenceCursor {
+    pub created_ms: i64,
+    pub id: String,
+}
+
+impl EvidenceCursor {
+    pub fn new(created_ms: i64, id: impl Into<String>) -> Self {
+        Self {
+            created_ms,
+            id: id.into(),
+        }
+    }
+
+    /// Opaque, URL-safe form handed to clients as `next_cursor`
+    pub fn encode(&self) -> String {
+        hex::encode(format!("{}:{}", self.created_ms, self.id))
+    }
+
+    pub fn decode(cursor: &str) -> Result<Self, String> {
+        let invalid = || format!("invalid cursor '{}'", cursor);
+        let bytes = hex::decode(cursor).map_err(|_| invalid())?;
+        let text = String::from_utf8(bytes).map_err(|_| invalid())?;
+        let (created_ms, id) = text.split_once(':').ok_or_else(invalid)?;
+        let created_ms = created_ms.parse().map_err(|_| invalid())?;
+        if id.is_empty() {
+            return Err(invalid());
+        }
+        Ok(Self::new(created_ms, id))
+    }
+}
+
+/// How a submitted evidence record should be anchored
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AnchorMode {
+    /// Queue for the keeper to anchor asynchronously
+    #[default]
+    Async,
+    /// Anchor inline and return the tx ref (requires the sync capability)
+    Sync,
+}
+
+#[derive(Debug, Deserialize, ToSchema)]
 pub struct EvidenceIn {
     pub id: Option<String>,
     pub digest_hex: String,
     pub payload_mime: Option<String>,
     pub metadata: Option<serde_json::Value>,
+    pub anchor_mode: Option<AnchorMode>,
+    /// Originating system (e.g. "cli", "simulator", "edge-node-7"); falls
+    /// back to the `X-Evidence-Source` header, then "api"
+    #[serde(default)]
+    pub source: Option<String>,
+    /// Keeper fetch priority; higher anchors first, default 0
+    #[serde(default)]
+    pub priority: Option<i64>,
+    /// The payload itself, base64; kept by digest when the server stores
+    /// payloads (`API_STORE_PAYLOADS`) and served by `GET /evidence/{id}/payload`
+    #[serde(default)]
+    pub payload_base64: Option<String>,
 }
 
-#[derive(Debug, Serialize)]
+/// Hex characters in a SHA-256 evidence digest
+pub const DIGEST_HEX_LEN: usize = 64;
+
+/// Check a submitted digest is a SHA-256 hex string and lowercase it
+///
+/// Anything else cannot be anchored: the keeper feeds the digest straight
+/// into `MerkleTree::from_leaves`, which needs 32 bytes of valid hex.
+pub fn normalize_digest_hex(digest_hex: &str) -> Result<String, String> {
+    if digest_hex.len() != DIGEST_HEX_LEN || !digest_hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(format!(
+            "invalid digest_hex '{}': expected {} hex characters (SHA-256)",
+            digest_hex, DIGEST_HEX_LEN
+        ));
+    }
+    Ok(digest_hex.to_ascii_lowercase())
+}
+
+/// Reject a `metadata.digest_algo` other than SHA-256
+///
+/// BLAKE3 digests are also 64 hex characters, but the keeper anchors and
+/// proves every job as SHA-256, so one would be silently mislabelled.
+pub fn check_digest_algo(metadata: Option<&serde_json::Value>) -> Result<(), String> {
+    match metadata.and_then(|m| m.get("digest_algo")) {
+        None | Some(serde_json::Value::Null) => Ok(()),
+        Some(algo) if algo.as_str() == Some("sha256") => Ok(()),
+        Some(algo) => Err(format!(
+            "unsupported metadata.digest_algo {}: only sha256 digests can be anchored",
+            algo
+        )),
+    }
+}
+
+/// Job statuses accepted by `GET /evidence?status=`
+pub const EVIDENCE_STATUSES: &[&str] = &["queued", "in_progress", "done", "failed", "dead_letter"];
+
+/// Metadata keys backed by an indexed generated column on `outbox_jobs`; the
+/// only keys evidence can be searched by
+pub const INDEXED_METADATA_KEYS: &[&str] = &["event_type"];
+
+/// Filters for `GET /evidence`
+#[derive(Debug, Default, Clone, Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct EvidenceFilter {
+    pub source: Option<String>,
+    /// One of [`EVIDENCE_STATUSES`]
+    pub status: Option<String>,
+    /// Only jobs created at or after this time (Unix ms)
+    pub since_ms: Option<i64>,
+    /// Only jobs whose metadata `event_type` equals this
+    #[serde(rename = "metadata.event_type")]
+    pub event_type: Option<String>,
+}
+
+#[derive(Debug, Serialize, ToSchema)]
 pub struct EvidenceOut {
     pub id: String,
     pub digest_hex: String,
@@ -23,6 +153,46 @@ pub struct EvidenceOut {
     pub last_error: Option<String>,
     pub created_ms: i64,
     pub updated_ms: i64,
+    pub source: String,
+}
+
+/// Chain transaction reference recorded for an evidence job
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct TxRefOut {
+    pub network: String,
+    pub chain: String,
+    pub tx_id: String,
+    pub confirmed: bool,
+    pub timestamp: Option<i64>,
+    /// Block explorer link, omitted for networks without a known explorer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub explorer_url: Option<String>,
+}
+
+/// Evidence job together with its chain transaction references
+///
+/// `tx_refs` is always ordered by network, then chain, then tx_id so that
+/// responses are stable regardless of insertion order.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EvidenceDetailOut {
+    #[serde(flatten)]
+    pub evidence: EvidenceOut,
+    pub tx_refs: Vec<TxRefOut>,
+}
+
+/// Outbox health: job counts by status and how far anchoring is behind
+#[derive(Debug, Serialize, ToSchema)]
+pub struct EvidenceStatsOut {
+    pub total: i64,
+    pub queued: i64,
+    pub in_progress: i64,
+    pub done: i64,
+    pub failed: i64,
+    pub dead_letter: i64,
+    /// Age of the oldest queued job in ms, null when nothing is queued
+    pub oldest_queued_age_ms: Option<i64>,
+    /// Chain transactions recorded but not yet confirmed
+    pub unconfirmed_tx_refs: i64,
 }
 
 // Countermeasure Deployment models
@@ -100,7 +270,7 @@ pub struct JammingOperationOut {
 }
 
 // x402 Payment Receipt models
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct PaymentReceiptOut {
     pub id: String,
     pub evidence_id: String,
@@ -110,6 +280,85 @@ pub struct PaymentReceiptOut {
     pub sender_wallet: Option<String>,
     pub verified_at: i64,
     pub created_ms: i64,
+    /// USDC paid beyond the price; `None` for exact payments
+    pub overpaid_usdc: Option<String>,
+    /// [`REFUND_ELIGIBLE`] or [`REFUNDED`]; `None` when no refund is owed
+    pub refund_status: Option<String>,
+    /// Token paid in (`USDC`, `USDT` or `SOL`)
+    pub token: String,
+    /// Amount paid, in `token`; `None` on receipts recorded before it was kept
+    pub token_amount: Option<String>,
+}
+
+/// Refund status of a paid request that could not be served
+pub const REFUND_ELIGIBLE: &str = "eligible";
+
+/// Refund status once the refund has been paid out
+pub const REFUNDED: &str = "refunded";
+
+/// Every value of `payment_receipts.refund_status`
+pub const REFUND_STATUSES: [&str; 2] = [REFUND_ELIGIBLE, REFUNDED];
+
+/// Query for `GET /api/v1/payments/refunds` (defaults to `eligible`)
+#[derive(Debug, Default, Deserialize)]
+pub struct RefundQuery {
+    pub status: Option<String>,
+}
+
+/// Filters for `GET /api/v1/payments/receipts`
+#[derive(Debug, Default, Clone, Deserialize)]
+pub struct PaymentReceiptFilter {
+    pub evidence_id: Option<String>,
+    /// Stored tier name, e.g. `basic`, `multichain`
+    pub tier: Option<String>,
+    /// Only receipts verified at or after this time (Unix ms)
+    pub since_ms: Option<i64>,
+}
+
+/// Query for `GET /admin/payments/reconcile` (bounds on `verified_at`, ms)
+#[derive(Debug, Deserialize)]
+pub struct ReconciliationQuery {
+    pub from: Option<i64>,
+    pub to: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReceiptDiscrepancy {
+    pub field: String,
+    pub recorded: String,
+    pub on_chain: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReceiptReconciliation {
+    pub receipt_id: String,
+    pub evidence_id: String,
+    pub tx_signature: String,
+    pub amount_usdc: String,
+    pub verified_at: i64,
+    /// "matched", "mismatched" or "error"
+    pub status: String,
+    pub discrepancies: Vec<ReceiptDiscrepancy>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct ReconciliationTotals {
+    pub receipts: u64,
+    pub matched: u64,
+    pub mismatched: u64,
+    pub errors: u64,
+    pub recorded_amount_usdc: String,
+    pub on_chain_amount_usdc: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReconciliationReport {
+    pub from: Option<i64>,
+    pub to: Option<i64>,
+    pub totals: ReconciliationTotals,
+    pub receipts: Vec<ReceiptReconciliation>,
 }
 
 // User Authentication models
@@ -205,3 +454,29 @@ pub struct PreorderListItemOut {
     pub created_ms: i64,
     pub updated_ms: i64,
 }
+
+/// A single detection, as emitted by the Python detector
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Detection {
+    pub class_id: i32,
+    pub class_name: String,
+    pub confidence: f32,
+    /// `[x, y, width, height]`
+    pub bbox: Vec<f32>,
+    pub drone_score: f32,
+    pub track_id: Option<i32>,
+    pub is_drone: bool,
+}
+
+/// Detector webhook payload (same shape the desktop app's
+/// `receive_detection` command accepts)
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct DetectionEvent {
+    pub event: String,
+    pub timestamp: String,
+    pub frame_number: i32,
+    pub source_id: String,
+    pub detection: Detection,
+}
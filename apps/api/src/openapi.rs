@@ -0,0 +1,120 @@
+//! OpenAPI description of the public API
+//!
+//! Handlers carry `#[utoipa::path]` annotations and request/response types
+//! derive `ToSchema`; [`ApiDoc`] collects them into the spec served at
+//! `GET /openapi.json`. `GET /docs` is a Swagger UI page rendering that spec
+//! (assets load from a CDN, so nothing is bundled into the binary).
+//!
+//! Covered: health, evidence, detections and the x402 endpoints integrators
+//! call. Admin, auth, career and preorder routes are internal to the site and
+//! left out.
+
+use axum::{response::Html, Json};
+use serde::Serialize;
+use utoipa::{
+    openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme},
+    Modify, OpenApi, ToSchema,
+};
+
+/// Body of the evidence handlers' 4xx/5xx responses (`ApiError`)
+#[derive(Serialize, ToSchema)]
+pub struct ErrorResponse {
+    /// Human-readable message
+    pub error: String,
+    /// Structured context (e.g. `{ "id": ... }`), or null
+    pub details: Option<serde_json::Value>,
+    /// Correlates with the `X-Request-Id` header and server logs
+    pub request_id: Option<String>,
+}
+
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Phoenix Rooivalk API",
+        description = "Evidence anchoring, detection events and x402 premium verification"
+    ),
+    paths(
+        crate::handlers::health,
+        crate::handlers::health_ready,
+        crate::handlers::list_evidence,
+        crate::handlers::post_evidence,
+        crate::handlers::get_evidence_stats,
+        crate::handlers::get_evidence,
+        crate::handlers::get_evidence_proof,
+        crate::handlers::get_evidence_payload,
+        crate::detections::post_detection,
+        crate::handlers_x402::verify_evidence_premium,
+        crate::handlers_x402::verify_evidence_premium_bulk,
+        crate::handlers_x402::x402_status,
+        crate::handlers_x402::get_shared_verification,
+    ),
+    components(schemas(
+        ErrorResponse,
+        crate::models::AnchorMode,
+        crate::models::EvidenceIn,
+        crate::models::EvidenceOut,
+        crate::models::EvidenceDetailOut,
+        crate::models::EvidenceStatsOut,
+        crate::models::TxRefOut,
+        crate::models::Detection,
+        crate::models::DetectionEvent,
+        phoenix_x402::PriceTier,
+        phoenix_x402::PaymentDetails,
+        phoenix_x402::VerifyEvidenceRequest,
+        phoenix_x402::VerifyEvidenceResponse,
+        phoenix_x402::VerifyEvidenceBulkRequest,
+        phoenix_x402::BulkEvidenceResult,
+    )),
+    modifiers(&BearerAuth),
+    tags(
+        (name = "health", description = "Liveness and readiness probes"),
+        (name = "evidence", description = "Submit and look up evidence jobs"),
+        (name = "detections", description = "Detector events"),
+        (name = "x402", description = "Pay-per-request premium verification"),
+    )
+)]
+pub struct ApiDoc;
+
+/// Registers the bearer token scheme the x402 endpoints require
+struct BearerAuth;
+
+impl Modify for BearerAuth {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "bearer_auth",
+            SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+        );
+    }
+}
+
+/// GET /openapi.json
+pub async fn openapi_json() -> Json<utoipa::openapi::OpenApi> {
+    Json(ApiDoc::openapi())
+}
+
+/// Swagger UI assets, pinned to a major version
+const SWAGGER_UI_CDN: &str = "https://unpkg.com/swagger-ui-dist@5";
+
+/// GET /docs
+pub async fn swagger_ui() -> Html<String> {
+    Html(format!(
+        r##"<!doctype html>
+<html lang="en">
+<head>
+  <meta charset="utf-8">
+  <title>Phoenix Rooivalk API</title>
+  <link rel="stylesheet" href="{cdn}/swagger-ui.css">
+</head>
+<body>
+  <div id="swagger-ui"></div>
+  <script src="{cdn}/swagger-ui-bundle.js"></script>
+  <script>
+    window.ui = SwaggerUIBundle({{ url: "/openapi.json", dom_id: "#swagger-ui" }});
+  </script>
+</body>
+</html>
+"##,
+        cdn = SWAGGER_UI_CDN
+    ))
+}
@@ -0,0 +1,95 @@
+//! Content-addressed storage for evidence payloads
+//!
+//! Only the digest is anchored, so without the original bytes a proof
+//! attests to something nobody can re-present. When `API_STORE_PAYLOADS` is
+//! on, `POST /evidence` accepts the payload inline (`payload_base64`), checks
+//! it hashes to `digest_hex` and keeps it here keyed by that digest;
+//! `GET /evidence/{id}/payload` hands it back. Reads re-hash the stored bytes,
+//! so a row altered in the database is reported rather than served.
+
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+use sqlx::{Pool, Row, Sqlite};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum PayloadStoreError {
+    /// The bytes don't hash to the digest they were stored or fetched under
+    #[error("payload digest mismatch: expected {expected}, got {actual}")]
+    DigestMismatch { expected: String, actual: String },
+    #[error("payload store: {0}")]
+    Store(String),
+}
+
+impl From<sqlx::Error> for PayloadStoreError {
+    fn from(e: sqlx::Error) -> Self {
+        PayloadStoreError::Store(e.to_string())
+    }
+}
+
+/// Evidence payloads keyed by their lowercase SHA-256 hex digest
+#[async_trait]
+pub trait PayloadStore: Send + Sync {
+    /// Store `bytes` under `digest_hex`. Fails with
+    /// [`PayloadStoreError::DigestMismatch`] unless they hash to it; storing
+    /// the same payload twice is a no-op.
+    async fn put(&self, digest_hex: &str, bytes: &[u8]) -> Result<(), PayloadStoreError>;
+
+    /// The payload stored under `digest_hex`, checked against it
+    async fn get(&self, digest_hex: &str) -> Result<Option<Vec<u8>>, PayloadStoreError>;
+}
+
+/// Check `bytes` hash to `digest_hex`
+pub fn verify_payload(digest_hex: &str, bytes: &[u8]) -> Result<(), PayloadStoreError> {
+    let actual = hex::encode(Sha256::digest(bytes));
+    if actual.eq_ignore_ascii_case(digest_hex) {
+        Ok(())
+    } else {
+        Err(PayloadStoreError::DigestMismatch {
+            expected: digest_hex.to_string(),
+            actual,
+        })
+    }
+}
+
+/// [`PayloadStore`] over the API's `evidence_payloads` table
+#[derive(Debug, Clone)]
+pub struct SqlitePayloadStore {
+    pool: Pool<Sqlite>,
+}
+
+impl SqlitePayloadStore {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl PayloadStore for SqlitePayloadStore {
+    async fn put(&self, digest_hex: &str, bytes: &[u8]) -> Result<(), PayloadStoreError> {
+        verify_payload(digest_hex, bytes)?;
+        sqlx::query(
+            "INSERT OR IGNORE INTO evidence_payloads (digest_hex, bytes, size, created_ms) VALUES (?1, ?2, ?3, ?4)",
+        )
+        .bind(digest_hex.to_ascii_lowercase())
+        .bind(bytes)
+        .bind(bytes.len() as i64)
+        .bind(chrono::Utc::now().timestamp_millis())
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn get(&self, digest_hex: &str) -> Result<Option<Vec<u8>>, PayloadStoreError> {
+        let row = sqlx::query("SELECT bytes FROM evidence_payloads WHERE digest_hex = ?1")
+            .bind(digest_hex.to_ascii_lowercase())
+            .fetch_optional(&self.pool)
+            .await?;
+        let Some(row) = row else {
+            return Ok(None);
+        };
+        let bytes: Vec<u8> = row.get("bytes");
+        verify_payload(digest_hex, &bytes)?;
+        Ok(Some(bytes))
+    }
+}
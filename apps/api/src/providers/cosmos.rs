@@ -1,43 +1,116 @@
 /// Azure Cosmos DB provider implementation
 ///
 /// This module provides Cosmos DB integration when the "cosmos" feature is enabled.
+/// Each entity lives in its own container of the SQL API database, partitioned
+/// on `/id` so point reads, replaces and deletes by id are single-partition.
 ///
 /// # Configuration
 ///
 /// Required environment variables:
-/// - COSMOS_DB_ACCOUNT: Azure Cosmos DB account name
+/// - COSMOS_DB_ACCOUNT: Azure Cosmos DB account name (or COSMOS_DB_ENDPOINT)
 /// - COSMOS_DB_DATABASE: Database name
 /// - COSMOS_DB_KEY: Account key (or use Entra authentication)
 /// - AZURE_TENANT_ID: Azure AD tenant ID (for Entra auth)
 /// - AZURE_CLIENT_ID: Azure AD client ID (for Entra auth)
 /// - AZURE_CLIENT_SECRET: Azure AD client secret (for Entra auth)
+///
+/// Optional:
+/// - COSMOS_DB_ENDPOINT: Full account endpoint, e.g. the local emulator at
+///   `https://localhost:8081/` (overrides COSMOS_DB_ACCOUNT)
 use super::{
-    ApplicationRepository, DatabaseProvider, EvidenceRepository, Filter, ProviderError, Result,
-    SessionRepository, UserRepository,
+    decode_evidence_cursor, evidence_page, ApplicationRepository, DatabaseProvider,
+    EvidenceRepository, Filter, ProviderError, Result, SessionRepository, UserRepository,
 };
+use crate::db_errors::is_conflict_status;
 use crate::entities::{CareerApplication, Evidence, Session, User};
 use async_trait::async_trait;
-
-#[cfg(feature = "cosmos")]
 use azure_core::credentials::TokenCredential;
-#[cfg(feature = "cosmos")]
-use azure_data_cosmos::{CosmosAccountEndpoint, CosmosAccountReference, CosmosClient};
-#[cfg(feature = "cosmos")]
+use azure_data_cosmos::{
+    clients::ContainerClient,
+    models::{ContainerProperties, PartitionKeyDefinition},
+    CosmosAccountEndpoint, CosmosAccountReference, CosmosClient, PartitionKey, Query,
+};
 use azure_identity::AzureCliCredential;
-#[cfg(feature = "cosmos")]
+use futures::TryStreamExt;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::sync::Arc;
 
+/// Container holding `User` documents
+const USERS_CONTAINER: &str = "users";
+/// Container holding `Session` documents
+const SESSIONS_CONTAINER: &str = "sessions";
+/// Container holding `Evidence` (outbox job) documents
+const EVIDENCE_CONTAINER: &str = "evidence";
+/// Container holding `CareerApplication` documents
+const APPLICATIONS_CONTAINER: &str = "career_applications";
+/// Container holding provider metadata such as the schema version
+const METADATA_CONTAINER: &str = "metadata";
+
+/// Id of the metadata document recording the applied schema version
+const SCHEMA_VERSION_ID: &str = "schema_version";
+
+/// Schema version `migrate` brings the database up to
+const LATEST_SCHEMA_VERSION: u32 = 1;
+
+/// Every container, in creation order
+const CONTAINERS: [&str; 5] = [
+    USERS_CONTAINER,
+    SESSIONS_CONTAINER,
+    EVIDENCE_CONTAINER,
+    APPLICATIONS_CONTAINER,
+    METADATA_CONTAINER,
+];
+
+/// Schema version record stored in the metadata container
+#[derive(Debug, Serialize, Deserialize)]
+struct SchemaVersionDoc {
+    id: String,
+    version: u32,
+    applied_at: i64,
+}
+
+/// Translate a Cosmos HTTP status into the provider error taxonomy
+fn error_for_status(status: Option<u16>, message: String) -> ProviderError {
+    match status {
+        Some(status) if is_conflict_status(status) => ProviderError::Conflict(message),
+        Some(404) => ProviderError::NotFound(message),
+        Some(401) | Some(403) | Some(503) => ProviderError::Connection(message),
+        _ => ProviderError::Database(message),
+    }
+}
+
+/// Map an Azure SDK error, keeping 409 conflicts and 404s distinguishable
+fn map_cosmos_error(context: &str, error: azure_core::Error) -> ProviderError {
+    let status = error.http_status().map(u16::from);
+    error_for_status(status, format!("{}: {}", context, error))
+}
+
+/// Whether an SDK error is a 404 for the addressed resource
+fn is_not_found(error: &azure_core::Error) -> bool {
+    error.http_status().map(u16::from) == Some(404)
+}
+
+/// Sort newest first and apply `Filter` limit/offset, matching the SQLite provider.
+///
+/// Cross-partition `ORDER BY`/`OFFSET` needs a query plan the gateway does not
+/// provide, so ordering and paging happen client-side on the filtered result.
+fn paginate<T>(
+    mut items: Vec<T>,
+    filter: &Filter,
+    created_ms: impl Fn(&T) -> i64,
+) -> (Vec<T>, i64) {
+    let total = items.len() as i64;
+    items.sort_by_key(|item| std::cmp::Reverse(created_ms(item)));
+    let offset = filter.offset.unwrap_or(0).max(0) as usize;
+    let limit = filter.limit.unwrap_or(100).max(0) as usize;
+    (items.into_iter().skip(offset).take(limit).collect(), total)
+}
+
 /// Azure Cosmos DB provider
 #[derive(Debug)]
 pub struct CosmosProvider {
-    #[cfg(feature = "cosmos")]
-    #[allow(dead_code)]
     client: CosmosClient,
-    #[cfg(feature = "cosmos")]
-    #[allow(dead_code)]
     database: String,
-    #[cfg(not(feature = "cosmos"))]
-    _phantom: std::marker::PhantomData<()>,
 }
 
 impl CosmosProvider {
@@ -45,59 +118,165 @@ impl CosmosProvider {
     ///
     /// # Environment Variables
     ///
-    /// - COSMOS_DB_ACCOUNT: Azure Cosmos DB account name
+    /// - COSMOS_DB_ENDPOINT: Full account endpoint (optional, e.g. the emulator)
+    /// - COSMOS_DB_ACCOUNT: Azure Cosmos DB account name (if no endpoint is given)
     /// - COSMOS_DB_DATABASE: Database name
     /// - COSMOS_DB_KEY: Account key (optional, will use Entra if not provided)
     pub async fn from_env() -> Result<Self> {
-        #[cfg(feature = "cosmos")]
-        {
-            let account = std::env::var("COSMOS_DB_ACCOUNT")
-                .map_err(|_| ProviderError::Connection("COSMOS_DB_ACCOUNT not set".to_string()))?;
-            let database = std::env::var("COSMOS_DB_DATABASE")
-                .map_err(|_| ProviderError::Connection("COSMOS_DB_DATABASE not set".to_string()))?;
-
-            // Try key-based auth first, fall back to Entra
-            if std::env::var("COSMOS_DB_KEY").is_ok() {
-                // Note: This is a simplified example. In practice, you'd use:
-                // CosmosClient::new(endpoint, key, options)
-                // The actual implementation depends on the azure_data_cosmos version
-                return Err(ProviderError::Connection(
-                    "Key-based auth not fully implemented in stub. Use Entra auth or implement with proper azure_data_cosmos version.".to_string(),
-                ));
+        let endpoint = match std::env::var("COSMOS_DB_ENDPOINT") {
+            Ok(endpoint) => endpoint,
+            Err(_) => {
+                let account = std::env::var("COSMOS_DB_ACCOUNT").map_err(|_| {
+                    ProviderError::Connection(
+                        "COSMOS_DB_ACCOUNT or COSMOS_DB_ENDPOINT not set".to_string(),
+                    )
+                })?;
+                format!("https://{}.documents.azure.com:443/", account)
             }
+        };
+        let database = std::env::var("COSMOS_DB_DATABASE")
+            .map_err(|_| ProviderError::Connection("COSMOS_DB_DATABASE not set".to_string()))?;
+        let key = std::env::var("COSMOS_DB_KEY").ok();
 
-            // Use Entra authentication with AzureCliCredential
-            // In production, consider using ManagedIdentityCredential or a credential chain
-            // AzureCliCredential::new returns Arc<AzureCliCredential>, which coerces to Arc<dyn TokenCredential>
-            let credential: Arc<dyn TokenCredential> =
-                AzureCliCredential::new(None).map_err(|e| {
-                    ProviderError::Connection(format!("Failed to create Azure credential: {}", e))
-                })?;
+        Self::new(&endpoint, key, database).await
+    }
 
-            let endpoint: CosmosAccountEndpoint =
-                format!("https://{}.documents.azure.com:443/", account)
-                    .parse()
-                    .map_err(|e| {
-                        ProviderError::Connection(format!("Invalid Cosmos endpoint: {}", e))
+    /// Connect to `endpoint`, authenticating with the account key if given and
+    /// Entra (Azure CLI credential) otherwise
+    pub async fn new(endpoint: &str, key: Option<String>, database: String) -> Result<Self> {
+        let endpoint: CosmosAccountEndpoint = endpoint
+            .parse()
+            .map_err(|e| ProviderError::Connection(format!("Invalid Cosmos endpoint: {}", e)))?;
+
+        let account_ref = match key {
+            Some(key) => CosmosAccountReference::with_master_key(endpoint, key.into()),
+            None => {
+                // In production, consider using ManagedIdentityCredential or a credential chain
+                // AzureCliCredential::new returns Arc<AzureCliCredential>, which coerces to Arc<dyn TokenCredential>
+                let credential: Arc<dyn TokenCredential> =
+                    AzureCliCredential::new(None).map_err(|e| {
+                        ProviderError::Connection(format!(
+                            "Failed to create Azure credential: {}",
+                            e
+                        ))
                     })?;
-            let account_ref = CosmosAccountReference::with_credential(endpoint, credential);
-            let client = CosmosClient::builder()
-                .build(account_ref)
-                .await
-                .map_err(|e| {
-                    ProviderError::Connection(format!("Failed to create Cosmos client: {}", e))
-                })?;
+                CosmosAccountReference::with_credential(endpoint, credential)
+            }
+        };
 
-            Ok(Self { client, database })
-        }
+        let client = CosmosClient::builder()
+            .build(account_ref)
+            .await
+            .map_err(|e| {
+                ProviderError::Connection(format!("Failed to create Cosmos client: {}", e))
+            })?;
 
-        #[cfg(not(feature = "cosmos"))]
-        {
-            Err(ProviderError::Connection(
-                "Cosmos DB feature not enabled. Compile with --features cosmos".to_string(),
-            ))
+        Ok(Self { client, database })
+    }
+
+    fn container(&self, name: &str) -> ContainerClient {
+        self.client
+            .database_client(&self.database)
+            .container_client(name)
+    }
+
+    /// Insert a new document; an existing id surfaces as `ProviderError::Conflict`
+    async fn create_item<T: Serialize + Send + Sync>(
+        &self,
+        container: &str,
+        id: &str,
+        item: &T,
+    ) -> Result<()> {
+        self.container(container)
+            .create_item(PartitionKey::from(id.to_string()), item, None)
+            .await
+            .map(|_| ())
+            .map_err(|e| map_cosmos_error(&format!("create {}/{}", container, id), e))
+    }
+
+    /// Point read by id (`None` on 404)
+    async fn read_item<T: DeserializeOwned + Send>(
+        &self,
+        container: &str,
+        id: &str,
+    ) -> Result<Option<T>> {
+        let context = format!("read {}/{}", container, id);
+        match self
+            .container(container)
+            .read_item::<T>(PartitionKey::from(id.to_string()), id, None)
+            .await
+        {
+            Ok(response) => response
+                .into_model()
+                .map(Some)
+                .map_err(|e| map_cosmos_error(&context, e)),
+            Err(e) if is_not_found(&e) => Ok(None),
+            Err(e) => Err(map_cosmos_error(&context, e)),
         }
     }
+
+    /// Replace an existing document (`ProviderError::NotFound` if it is missing)
+    async fn replace_item<T: Serialize + Send + Sync>(
+        &self,
+        container: &str,
+        id: &str,
+        item: &T,
+    ) -> Result<()> {
+        self.container(container)
+            .replace_item(PartitionKey::from(id.to_string()), id, item, None)
+            .await
+            .map(|_| ())
+            .map_err(|e| map_cosmos_error(&format!("replace {}/{}", container, id), e))
+    }
+
+    /// Insert or overwrite a document
+    async fn upsert_item<T: Serialize + Send + Sync>(
+        &self,
+        container: &str,
+        id: &str,
+        item: &T,
+    ) -> Result<()> {
+        self.container(container)
+            .upsert_item(PartitionKey::from(id.to_string()), item, None)
+            .await
+            .map(|_| ())
+            .map_err(|e| map_cosmos_error(&format!("upsert {}/{}", container, id), e))
+    }
+
+    /// Delete by id (`ProviderError::NotFound` if it is missing)
+    async fn delete_item(&self, container: &str, id: &str) -> Result<()> {
+        self.container(container)
+            .delete_item(PartitionKey::from(id.to_string()), id, None)
+            .await
+            .map(|_| ())
+            .map_err(|e| map_cosmos_error(&format!("delete {}/{}", container, id), e))
+    }
+
+    /// Run a cross-partition SQL query and collect every result
+    async fn query_items<T: DeserializeOwned + Send + 'static>(
+        &self,
+        container: &str,
+        query: Query,
+    ) -> Result<Vec<T>> {
+        let context = format!("query {}", container);
+        self.container(container)
+            .query_items::<T>(query, PartitionKey::EMPTY, None)
+            .map_err(|e| map_cosmos_error(&context, e))?
+            .try_collect()
+            .await
+            .map_err(|e| map_cosmos_error(&context, e))
+    }
+
+    /// Build a parameterised query
+    fn query(sql: &str, params: &[(&str, serde_json::Value)]) -> Result<Query> {
+        params
+            .iter()
+            .try_fold(Query::from(sql), |query, (name, value)| {
+                query.with_parameter(*name, value.clone()).map_err(|e| {
+                    ProviderError::Validation(format!("query parameter {}: {}", name, e))
+                })
+            })
+    }
 }
 
 #[async_trait]
@@ -107,373 +286,344 @@ impl DatabaseProvider for CosmosProvider {
     }
 
     async fn health_check(&self) -> Result<()> {
-        #[cfg(feature = "cosmos")]
-        {
-            // Try to get database info to verify connection
-            // Note: The new API uses database_client().read() pattern
-            let _database = &self.database;
-            // Placeholder - actual implementation would call:
-            // self.client.database_client(&self.database).read().await
-            Ok(())
-        }
-
-        #[cfg(not(feature = "cosmos"))]
-        {
-            Err(ProviderError::Connection(
-                "Cosmos DB feature not enabled".to_string(),
-            ))
-        }
+        // Reading the database resource is the cheapest authenticated round trip
+        self.client
+            .database_client(&self.database)
+            .read(None)
+            .await
+            .map(|_| ())
+            .map_err(|e| ProviderError::Connection(format!("Cosmos health check failed: {}", e)))
     }
 
     async fn initialize(&self) -> Result<()> {
-        #[cfg(feature = "cosmos")]
-        {
-            // Create database if it doesn't exist
-            // Note: In production, database should be pre-provisioned
-            tracing::info!("Initializing Cosmos DB database: {}", self.database);
-            Ok(())
-        }
-
-        #[cfg(not(feature = "cosmos"))]
-        {
-            Err(ProviderError::Connection(
-                "Cosmos DB feature not enabled".to_string(),
-            ))
-        }
+        // The database itself should be pre-provisioned (throughput, region, etc.)
+        tracing::info!("Initializing Cosmos DB database: {}", self.database);
+        self.migrate(None).await
     }
 
     async fn get_version(&self) -> Result<u32> {
-        #[cfg(feature = "cosmos")]
-        {
-            // Store version in a special "migrations" container
-            // For now, return 0 as placeholder
-            Ok(0)
-        }
-
-        #[cfg(not(feature = "cosmos"))]
+        match self
+            .read_item::<SchemaVersionDoc>(METADATA_CONTAINER, SCHEMA_VERSION_ID)
+            .await
         {
-            Ok(0)
+            Ok(doc) => Ok(doc.map(|d| d.version).unwrap_or(0)),
+            // Metadata container not created yet
+            Err(ProviderError::NotFound(_)) => Ok(0),
+            Err(e) => Err(e),
         }
     }
 
-    async fn migrate(&self, _target_version: Option<u32>) -> Result<()> {
-        #[cfg(feature = "cosmos")]
-        {
-            // Cosmos DB migrations would involve:
-            // 1. Creating containers (collections)
-            // 2. Setting up partition keys
-            // 3. Creating indexes
-            // 4. Data transformations
-            tracing::info!("Running Cosmos DB migrations");
-            Ok(())
+    async fn migrate(&self, target_version: Option<u32>) -> Result<()> {
+        let target = target_version
+            .unwrap_or(LATEST_SCHEMA_VERSION)
+            .min(LATEST_SCHEMA_VERSION);
+        let current = self.get_version().await?;
+        if current >= target {
+            return Ok(());
+        }
+
+        tracing::info!("Running Cosmos DB migrations: v{} -> v{}", current, target);
+
+        // v1: one container per entity, partitioned on /id
+        let database = self.client.database_client(&self.database);
+        for name in CONTAINERS {
+            let properties = ContainerProperties {
+                id: name.to_string().into(),
+                partition_key: PartitionKeyDefinition::from("/id"),
+                ..Default::default()
+            };
+            match database.create_container(properties, None).await {
+                Ok(_) => tracing::info!("Created Cosmos container {}", name),
+                Err(e)
+                    if e.http_status()
+                        .map(u16::from)
+                        .is_some_and(is_conflict_status) => {}
+                Err(e) => return Err(map_cosmos_error(&format!("create container {}", name), e)),
+            }
         }
 
-        #[cfg(not(feature = "cosmos"))]
-        {
-            Ok(())
-        }
+        self.upsert_item(
+            METADATA_CONTAINER,
+            SCHEMA_VERSION_ID,
+            &SchemaVersionDoc {
+                id: SCHEMA_VERSION_ID.to_string(),
+                version: target,
+                applied_at: chrono::Utc::now().timestamp_millis(),
+            },
+        )
+        .await
     }
 }
 
-// Note: The repository implementations for Cosmos DB are stubs
-// A full implementation would require:
-// 1. Container management for each entity type
-// 2. Query composition using Cosmos DB SQL API
-// 3. Partition key strategy
-// 4. Consistency level configuration
-// 5. Request unit (RU) optimization
-
 #[async_trait]
 impl UserRepository for CosmosProvider {
-    async fn create(&self, _user: &User) -> Result<String> {
-        #[cfg(feature = "cosmos")]
-        {
-            // Implementation would:
-            // 1. Get users container
-            // 2. Create document with partition key
-            // 3. Handle conflicts
-            Err(ProviderError::Database("Not implemented".to_string()))
-        }
-
-        #[cfg(not(feature = "cosmos"))]
-        {
-            Err(ProviderError::Connection(
-                "Cosmos DB feature not enabled".to_string(),
-            ))
-        }
+    async fn create(&self, user: &User) -> Result<String> {
+        // Unique keys are per partition, so email uniqueness is checked here
+        if self.get_by_email(&user.email).await?.is_some() {
+            return Err(ProviderError::Conflict(format!(
+                "User with email '{}' already exists",
+                user.email
+            )));
+        }
+        self.create_item(USERS_CONTAINER, &user.id, user).await?;
+        Ok(user.id.clone())
     }
 
-    async fn get_by_id(&self, _id: &str) -> Result<Option<User>> {
-        #[cfg(feature = "cosmos")]
-        {
-            Err(ProviderError::Database("Not implemented".to_string()))
-        }
-
-        #[cfg(not(feature = "cosmos"))]
-        {
-            Err(ProviderError::Connection(
-                "Cosmos DB feature not enabled".to_string(),
-            ))
-        }
+    async fn get_by_id(&self, id: &str) -> Result<Option<User>> {
+        self.read_item(USERS_CONTAINER, id).await
     }
 
-    async fn get_by_email(&self, _email: &str) -> Result<Option<User>> {
-        #[cfg(feature = "cosmos")]
-        {
-            Err(ProviderError::Database("Not implemented".to_string()))
-        }
-
-        #[cfg(not(feature = "cosmos"))]
-        {
-            Err(ProviderError::Connection(
-                "Cosmos DB feature not enabled".to_string(),
-            ))
-        }
+    async fn get_by_email(&self, email: &str) -> Result<Option<User>> {
+        let query = Self::query(
+            "SELECT * FROM c WHERE c.email = @email",
+            &[("@email", email.into())],
+        )?;
+        let users: Vec<User> = self.query_items(USERS_CONTAINER, query).await?;
+        Ok(users.into_iter().next())
     }
 
-    async fn update(&self, _id: &str, _user: &User) -> Result<()> {
-        #[cfg(feature = "cosmos")]
-        {
-            Err(ProviderError::Database("Not implemented".to_string()))
-        }
-
-        #[cfg(not(feature = "cosmos"))]
-        {
-            Err(ProviderError::Connection(
-                "Cosmos DB feature not enabled".to_string(),
-            ))
-        }
+    async fn update(&self, id: &str, user: &User) -> Result<()> {
+        let Some(existing) = UserRepository::get_by_id(self, id).await? else {
+            return Err(ProviderError::NotFound(format!(
+                "User with id '{}' not found",
+                id
+            )));
+        };
+
+        // Same columns the SQLite provider updates; id and created_ms are kept
+        let updated = User {
+            id: existing.id,
+            created_ms: existing.created_ms,
+            ..user.clone()
+        };
+        self.replace_item(USERS_CONTAINER, id, &updated).await
     }
 
-    async fn delete(&self, _id: &str) -> Result<()> {
-        #[cfg(feature = "cosmos")]
-        {
-            Err(ProviderError::Database("Not implemented".to_string()))
-        }
-
-        #[cfg(not(feature = "cosmos"))]
-        {
-            Err(ProviderError::Connection(
-                "Cosmos DB feature not enabled".to_string(),
-            ))
-        }
+    async fn delete(&self, id: &str) -> Result<()> {
+        self.delete_item(USERS_CONTAINER, id)
+            .await
+            .map_err(|e| match e {
+                ProviderError::NotFound(_) => {
+                    ProviderError::NotFound(format!("User with id '{}' not found", id))
+                }
+                other => other,
+            })
     }
 
-    async fn list(&self, _filter: &Filter) -> Result<(Vec<User>, i64)> {
-        #[cfg(feature = "cosmos")]
-        {
-            Err(ProviderError::Database("Not implemented".to_string()))
-        }
-
-        #[cfg(not(feature = "cosmos"))]
-        {
-            Err(ProviderError::Connection(
-                "Cosmos DB feature not enabled".to_string(),
-            ))
-        }
+    async fn list(&self, filter: &Filter) -> Result<(Vec<User>, i64)> {
+        let query = Self::query("SELECT * FROM c", &[])?;
+        let users: Vec<User> = self.query_items(USERS_CONTAINER, query).await?;
+        Ok(paginate(users, filter, |u| u.created_ms))
     }
 }
 
 #[async_trait]
 impl SessionRepository for CosmosProvider {
-    async fn create(&self, _session: &Session) -> Result<String> {
-        #[cfg(not(feature = "cosmos"))]
-        {
-            Err(ProviderError::Connection(
-                "Cosmos DB feature not enabled".to_string(),
-            ))
-        }
-        #[cfg(feature = "cosmos")]
-        {
-            Err(ProviderError::Database("Not implemented".to_string()))
-        }
+    async fn create(&self, session: &Session) -> Result<String> {
+        self.create_item(SESSIONS_CONTAINER, &session.id, session)
+            .await?;
+        Ok(session.id.clone())
     }
 
-    async fn get_by_id(&self, _id: &str) -> Result<Option<Session>> {
-        #[cfg(not(feature = "cosmos"))]
-        {
-            Err(ProviderError::Connection(
-                "Cosmos DB feature not enabled".to_string(),
-            ))
-        }
-        #[cfg(feature = "cosmos")]
-        {
-            Err(ProviderError::Database("Not implemented".to_string()))
-        }
+    async fn get_by_id(&self, id: &str) -> Result<Option<Session>> {
+        self.read_item(SESSIONS_CONTAINER, id).await
     }
 
-    async fn get_by_user_id(&self, _user_id: &str) -> Result<Vec<Session>> {
-        #[cfg(not(feature = "cosmos"))]
-        {
-            Err(ProviderError::Connection(
-                "Cosmos DB feature not enabled".to_string(),
-            ))
-        }
-        #[cfg(feature = "cosmos")]
-        {
-            Err(ProviderError::Database("Not implemented".to_string()))
-        }
+    async fn get_by_user_id(&self, user_id: &str) -> Result<Vec<Session>> {
+        let query = Self::query(
+            "SELECT * FROM c WHERE c.user_id = @user_id",
+            &[("@user_id", user_id.into())],
+        )?;
+        self.query_items(SESSIONS_CONTAINER, query).await
     }
 
-    async fn delete(&self, _id: &str) -> Result<()> {
-        #[cfg(not(feature = "cosmos"))]
-        {
-            Err(ProviderError::Connection(
-                "Cosmos DB feature not enabled".to_string(),
-            ))
-        }
-        #[cfg(feature = "cosmos")]
-        {
-            Err(ProviderError::Database("Not implemented".to_string()))
-        }
+    async fn delete(&self, id: &str) -> Result<()> {
+        self.delete_item(SESSIONS_CONTAINER, id)
+            .await
+            .map_err(|e| match e {
+                ProviderError::NotFound(_) => {
+                    ProviderError::NotFound(format!("Session with id '{}' not found", id))
+                }
+                other => other,
+            })
     }
 
     async fn delete_expired(&self) -> Result<u64> {
-        #[cfg(not(feature = "cosmos"))]
-        {
-            Err(ProviderError::Connection(
-                "Cosmos DB feature not enabled".to_string(),
-            ))
-        }
-        #[cfg(feature = "cosmos")]
-        {
-            Err(ProviderError::Database("Not implemented".to_string()))
+        let now = chrono::Utc::now().timestamp_millis();
+        let query = Self::query(
+            "SELECT VALUE c.id FROM c WHERE c.expires_at < @now",
+            &[("@now", now.into())],
+        )?;
+        let expired: Vec<String> = self.query_items(SESSIONS_CONTAINER, query).await?;
+
+        let mut deleted = 0;
+        for id in expired {
+            match self.delete_item(SESSIONS_CONTAINER, &id).await {
+                Ok(()) => deleted += 1,
+                // Already removed by a concurrent sweep
+                Err(ProviderError::NotFound(_)) => {}
+                Err(e) => return Err(e),
+            }
         }
+        Ok(deleted)
     }
 }
 
 #[async_trait]
 impl EvidenceRepository for CosmosProvider {
-    async fn create(&self, _evidence: &Evidence) -> Result<String> {
-        #[cfg(not(feature = "cosmos"))]
-        {
-            Err(ProviderError::Connection(
-                "Cosmos DB feature not enabled".to_string(),
-            ))
-        }
-        #[cfg(feature = "cosmos")]
-        {
-            Err(ProviderError::Database("Not implemented".to_string()))
-        }
+    async fn create(&self, evidence: &Evidence) -> Result<String> {
+        self.create_item(EVIDENCE_CONTAINER, &evidence.id, evidence)
+            .await?;
+        Ok(evidence.id.clone())
     }
 
-    async fn get_by_id(&self, _id: &str) -> Result<Option<Evidence>> {
-        #[cfg(not(feature = "cosmos"))]
-        {
-            Err(ProviderError::Connection(
-                "Cosmos DB feature not enabled".to_string(),
-            ))
-        }
-        #[cfg(feature = "cosmos")]
-        {
-            Err(ProviderError::Database("Not implemented".to_string()))
-        }
+    async fn get_by_id(&self, id: &str) -> Result<Option<Evidence>> {
+        self.read_item(EVIDENCE_CONTAINER, id).await
     }
 
-    async fn update_status(&self, _id: &str, _status: &str, _error: Option<&str>) -> Result<()> {
-        #[cfg(not(feature = "cosmos"))]
-        {
-            Err(ProviderError::Connection(
-                "Cosmos DB feature not enabled".to_string(),
-            ))
-        }
-        #[cfg(feature = "cosmos")]
-        {
-            Err(ProviderError::Database("Not implemented".to_string()))
-        }
+    async fn update_status(&self, id: &str, status: &str, error: Option<&str>) -> Result<()> {
+        let Some(mut evidence) = EvidenceRepository::get_by_id(self, id).await? else {
+            return Err(ProviderError::NotFound(format!(
+                "Evidence with id '{}' not found",
+                id
+            )));
+        };
+
+        evidence.status = status.to_string();
+        evidence.last_error = error.map(str::to_string);
+        evidence.updated_ms = chrono::Utc::now().timestamp_millis();
+        self.replace_item(EVIDENCE_CONTAINER, id, &evidence).await
     }
 
-    async fn list(&self, _filter: &Filter) -> Result<(Vec<Evidence>, i64)> {
-        #[cfg(not(feature = "cosmos"))]
-        {
-            Err(ProviderError::Connection(
-                "Cosmos DB feature not enabled".to_string(),
-            ))
-        }
-        #[cfg(feature = "cosmos")]
-        {
-            Err(ProviderError::Database("Not implemented".to_string()))
-        }
+    async fn list(&self, filter: &Filter) -> Result<(Vec<Evidence>, i64)> {
+        let query = Self::query("SELECT * FROM c", &[])?;
+        let evidence: Vec<Evidence> = self.query_items(EVIDENCE_CONTAINER, query).await?;
+        Ok(paginate(evidence, filter, |e| e.created_ms))
     }
 
-    async fn get_ready_jobs(&self, _limit: i64) -> Result<Vec<Evidence>> {
-        #[cfg(not(feature = "cosmos"))]
-        {
-            Err(ProviderError::Connection(
-                "Cosmos DB feature not enabled".to_string(),
-            ))
-        }
-        #[cfg(feature = "cosmos")]
-        {
-            Err(ProviderError::Database("Not implemented".to_string()))
-        }
+    async fn list_after(
+        &self,
+        cursor: Option<String>,
+        limit: i64,
+    ) -> Result<(Vec<Evidence>, Option<String>)> {
+        let limit = limit.max(1);
+        let query = match decode_evidence_cursor(cursor)? {
+            Some(cursor) => Self::query(
+                "SELECT * FROM c WHERE c.created_ms < @created_ms OR (c.created_ms = @created_ms AND c.id < @id)",
+                &[
+                    ("@created_ms", cursor.created_ms.into()),
+                    ("@id", cursor.id.into()),
+                ],
+            )?,
+            None => Self::query("SELECT * FROM c", &[])?,
+        };
+        let mut evidence: Vec<Evidence> = self.query_items(EVIDENCE_CONTAINER, query).await?;
+
+        // Cross-partition ORDER BY needs a composite index, so sort here
+        evidence.sort_by(|a, b| (b.created_ms, &b.id).cmp(&(a.created_ms, &a.id)));
+        evidence.truncate(limit as usize + 1);
+        Ok(evidence_page(evidence, limit))
+    }
+
+    async fn get_ready_jobs(&self, limit: i64) -> Result<Vec<Evidence>> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let query = Self::query(
+            "SELECT * FROM c WHERE c.status = 'queued' AND c.next_attempt_ms <= @now",
+            &[("@now", now.into())],
+        )?;
+        let mut ready: Vec<Evidence> = self.query_items(EVIDENCE_CONTAINER, query).await?;
+
+        // Oldest first, as in the SQLite provider
+        ready.sort_by_key(|e| e.created_ms);
+        ready.truncate(limit.max(0) as usize);
+        Ok(ready)
     }
 }
 
 #[async_trait]
 impl ApplicationRepository for CosmosProvider {
-    async fn create(&self, _application: &CareerApplication) -> Result<String> {
-        #[cfg(not(feature = "cosmos"))]
-        {
-            Err(ProviderError::Connection(
-                "Cosmos DB feature not enabled".to_string(),
-            ))
-        }
-        #[cfg(feature = "cosmos")]
-        {
-            Err(ProviderError::Database("Not implemented".to_string()))
-        }
+    async fn create(&self, application: &CareerApplication) -> Result<String> {
+        self.create_item(APPLICATIONS_CONTAINER, &application.id, application)
+            .await?;
+        Ok(application.id.clone())
     }
 
-    async fn get_by_id(&self, _id: &str) -> Result<Option<CareerApplication>> {
-        #[cfg(not(feature = "cosmos"))]
-        {
-            Err(ProviderError::Connection(
-                "Cosmos DB feature not enabled".to_string(),
-            ))
-        }
-        #[cfg(feature = "cosmos")]
-        {
-            Err(ProviderError::Database("Not implemented".to_string()))
-        }
+    async fn get_by_id(&self, id: &str) -> Result<Option<CareerApplication>> {
+        self.read_item(APPLICATIONS_CONTAINER, id).await
     }
 
-    async fn get_by_user_id(&self, _user_id: &str) -> Result<Vec<CareerApplication>> {
-        #[cfg(not(feature = "cosmos"))]
-        {
-            Err(ProviderError::Connection(
-                "Cosmos DB feature not enabled".to_string(),
-            ))
-        }
-        #[cfg(feature = "cosmos")]
-        {
-            Err(ProviderError::Database("Not implemented".to_string()))
-        }
+    async fn get_by_user_id(&self, user_id: &str) -> Result<Vec<CareerApplication>> {
+        let query = Self::query(
+            "SELECT * FROM c WHERE c.user_id = @user_id",
+            &[("@user_id", user_id.into())],
+        )?;
+        let mut applications: Vec<CareerApplication> =
+            self.query_items(APPLICATIONS_CONTAINER, query).await?;
+        applications.sort_by_key(|a| std::cmp::Reverse(a.created_ms));
+        Ok(applications)
     }
 
-    async fn update_status(&self, _id: &str, _status: &str) -> Result<()> {
-        #[cfg(not(feature = "cosmos"))]
-        {
-            Err(ProviderError::Connection(
-                "Cosmos DB feature not enabled".to_string(),
-            ))
-        }
-        #[cfg(feature = "cosmos")]
-        {
-            Err(ProviderError::Database("Not implemented".to_string()))
-        }
+    async fn update_status(&self, id: &str, status: &str) -> Result<()> {
+        let Some(mut application) = ApplicationRepository::get_by_id(self, id).await? else {
+            return Err(ProviderError::NotFound(format!(
+                "Application with id '{}' not found",
+                id
+            )));
+        };
+
+        application.status = status.to_string();
+        application.updated_ms = chrono::Utc::now().timestamp_millis();
+        self.replace_item(APPLICATIONS_CONTAINER, id, &application)
+            .await
     }
 
-    async fn list(&self, _filter: &Filter) -> Result<(Vec<CareerApplication>, i64)> {
-        #[cfg(not(feature = "cosmos"))]
-        {
-            Err(ProviderError::Connection(
-                "Cosmos DB feature not enabled".to_string(),
-            ))
-        }
-        #[cfg(feature = "cosmos")]
-        {
-            Err(ProviderError::Database("Not implemented".to_string()))
-        }
+    async fn list(&self, filter: &Filter) -> Result<(Vec<CareerApplication>, i64)> {
+        let query = Self::query("SELECT * FROM c", &[])?;
+        let applications: Vec<CareerApplication> =
+            self.query_items(APPLICATIONS_CONTAINER, query).await?;
+        Ok(paginate(applications, filter, |a| a.created_ms))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conflict_status_maps_to_conflict() {
+        assert!(matches!(
+            error_for_status(Some(409), "dup".to_string()),
+            ProviderError::Conflict(_)
+        ));
+        assert!(matches!(
+            error_for_status(Some(404), "missing".to_string()),
+            ProviderError::NotFound(_)
+        ));
+        assert!(matches!(
+            error_for_status(Some(403), "denied".to_string()),
+            ProviderError::Connection(_)
+        ));
+        assert!(matches!(
+            error_for_status(Some(500), "boom".to_string()),
+            ProviderError::Database(_)
+        ));
+        assert!(matches!(
+            error_for_status(None, "io".to_string()),
+            ProviderError::Database(_)
+        ));
+    }
+
+    #[test]
+    fn test_paginate_newest_first() {
+        let items = vec![1_i64, 5, 3, 4, 2];
+        let filter = Filter {
+            limit: Some(2),
+            offset: Some(1),
+            ..Filter::default()
+        };
+
+        let (page, total) = paginate(items, &filter, |i| *i);
+        assert_eq!(total, 5);
+        assert_eq!(page, vec![4, 3]);
     }
 }
@@ -1,6 +1,7 @@
 /// Database provider abstraction layer
 /// Defines traits for database operations that can be implemented by different providers
 use crate::entities::{CareerApplication, Evidence, Session, User};
+use crate::models::EvidenceCursor;
 use async_trait::async_trait;
 use std::fmt::Debug;
 use thiserror::Error;
@@ -10,6 +11,9 @@ pub mod sqlite;
 #[cfg(feature = "cosmos")]
 pub mod cosmos;
 
+#[cfg(feature = "postgres")]
+pub mod postgres;
+
 #[derive(Error, Debug)]
 pub enum ProviderError {
     #[error("Database error: {0}")]
@@ -50,7 +54,7 @@ impl Default for Filter {
 /// All database implementations must implement this trait
 #[async_trait]
 pub trait DatabaseProvider: Send + Sync + Debug {
-    /// Get provider name (e.g., "sqlite", "cosmos")
+    /// Get provider name (e.g., "sqlite", "cosmos", "postgres")
     fn name(&self) -> &str;
 
     /// Check if the provider is healthy
@@ -120,12 +124,47 @@ pub trait EvidenceRepository: Send + Sync {
     async fn update_status(&self, id: &str, status: &str, error: Option<&str>) -> Result<()>;
 
     /// List evidence with filter
+    #[deprecated(note = "OFFSET pages shift under concurrent inserts; use list_after")]
     async fn list(&self, filter: &Filter) -> Result<(Vec<Evidence>, i64)>;
 
+    /// List up to `limit` evidence jobs after an opaque cursor, newest first.
+    ///
+    /// Returns the page and the cursor for the next one (None once exhausted).
+    async fn list_after(
+        &self,
+        cursor: Option<String>,
+        limit: i64,
+    ) -> Result<(Vec<Evidence>, Option<String>)>;
+
     /// Get ready jobs for processing
     async fn get_ready_jobs(&self, limit: i64) -> Result<Vec<Evidence>>;
 }
 
+/// Decode a `list_after` cursor, rejecting malformed input as a validation error
+pub(crate) fn decode_evidence_cursor(cursor: Option<String>) -> Result<Option<EvidenceCursor>> {
+    cursor
+        .as_deref()
+        .map(EvidenceCursor::decode)
+        .transpose()
+        .map_err(ProviderError::Validation)
+}
+
+/// Trim a `limit + 1` row fetch down to `limit` and derive the next cursor
+pub(crate) fn evidence_page(
+    mut evidence: Vec<Evidence>,
+    limit: i64,
+) -> (Vec<Evidence>, Option<String>) {
+    let limit = limit.max(0) as usize;
+    if evidence.len() <= limit {
+        return (evidence, None);
+    }
+    evidence.truncate(limit);
+    let next_cursor = evidence
+        .last()
+        .map(|last| EvidenceCursor::new(last.created_ms, last.id.clone()).encode());
+    (evidence, next_cursor)
+}
+
 /// Career application repository trait
 #[async_trait]
 pub trait ApplicationRepository: Send + Sync {
@@ -1,7 +1,7 @@
 /// SQLite database provider implementation
 use super::{
-    ApplicationRepository, DatabaseProvider, EvidenceRepository, Filter, ProviderError, Result,
-    SessionRepository, UserRepository,
+    decode_evidence_cursor, evidence_page, ApplicationRepository, DatabaseProvider,
+    EvidenceRepository, Filter, ProviderError, Result, SessionRepository, UserRepository,
 };
 use crate::entities::{CareerApplication, Evidence, Session, User};
 use async_trait::async_trait;
@@ -400,6 +400,40 @@ impl EvidenceRepository for SqliteProvider {
         Ok((evidence_list, total))
     }
 
+    async fn list_after(
+        &self,
+        cursor: Option<String>,
+        limit: i64,
+    ) -> Result<(Vec<Evidence>, Option<String>)> {
+        let cursor = decode_evidence_cursor(cursor)?;
+        let limit = limit.max(1);
+
+        let rows = sqlx::query(
+            "SELECT id, payload_sha256, status, attempts, last_error, created_ms, updated_ms, next_attempt_ms FROM outbox_jobs WHERE ?1 IS NULL OR created_ms < ?1 OR (created_ms = ?1 AND id < ?2) ORDER BY created_ms DESC, id DESC LIMIT ?3"
+        )
+        .bind(cursor.as_ref().map(|c| c.created_ms))
+        .bind(cursor.as_ref().map(|c| c.id.as_str()))
+        .bind(limit + 1)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let evidence_list = rows
+            .into_iter()
+            .map(|row| Evidence {
+                id: row.get(0),
+                payload_sha256: row.get(1),
+                status: row.get(2),
+                attempts: row.get(3),
+                last_error: row.get(4),
+                created_ms: row.get(5),
+                updated_ms: row.get(6),
+                next_attempt_ms: row.get(7),
+            })
+            .collect();
+
+        Ok(evidence_page(evidence_list, limit))
+    }
+
     async fn get_ready_jobs(&self, limit: i64) -> Result<Vec<Evidence>> {
         let now = chrono::Utc::now().timestamp_millis();
         let rows = sqlx::query(
@@ -675,4 +709,44 @@ mod tests {
         let deleted = SessionRepository::get_by_id(&provider, &id).await.unwrap();
         assert!(deleted.is_none());
     }
+
+    #[tokio::test]
+    async fn test_evidence_list_after_walks_all_pages() {
+        let provider = create_test_provider().await;
+
+        for i in 0..5 {
+            let mut evidence = Evidence::new(format!("evidence-{}", i), "ab".repeat(32));
+            // Two jobs share each timestamp so the id breaks ties
+            evidence.created_ms = 1_000 + i / 2;
+            EvidenceRepository::create(&provider, &evidence)
+                .await
+                .unwrap();
+        }
+
+        let mut seen = Vec::new();
+        let mut cursor = None;
+        loop {
+            let (page, next) = provider.list_after(cursor, 2).await.unwrap();
+            seen.extend(page.into_iter().map(|e| e.id));
+            match next {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+        assert_eq!(
+            seen,
+            vec![
+                "evidence-4",
+                "evidence-3",
+                "evidence-2",
+                "evidence-1",
+                "evidence-0"
+            ]
+        );
+
+        assert!(matches!(
+            provider.list_after(Some("zz".to_string()), 2).await,
+            Err(ProviderError::Validation(_))
+        ));
+    }
 }
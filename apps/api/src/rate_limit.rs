@@ -1,10 +1,28 @@
 //! Rate limiting middleware for x402 premium endpoints
 //!
 //! Provides per-IP rate limiting to prevent abuse of the payment endpoints.
+//! Requests with a valid bearer token (a live session) are limited per token
+//! instead, with the higher
+//! `api_key_*` quotas from `phoenix_x402::RateLimits`
+//! (`X402_RATE_LIMIT_{VERIFY,STATUS,API_KEY_VERIFY,API_KEY_STATUS}_PER_MIN`).
+//!
+//! Premium verification is additionally limited per price tier, so expensive
+//! tiers (`bulk`, `legal_attestation`) can be throttled harder than `basic`.
+//! Tier quotas are requests per minute per IP and can be overridden with:
+//!
+//! | Variable | Default |
+//! |----------|---------|
+//! | `X402_RATE_LIMIT_BASIC_PER_MIN` | `10` |
+//! | `X402_RATE_LIMIT_MULTI_CHAIN_PER_MIN` | `6` |
+//! | `X402_RATE_LIMIT_LEGAL_ATTESTATION_PER_MIN` | `2` |
+//! | `X402_RATE_LIMIT_BULK_PER_MIN` | `2` |
+//!
+//! `X402_RATE_LIMIT_STRATEGY` picks the algorithm (`token_bucket`, the default,
+//! `sliding_window` or `fixed_window`); see `RateLimitStrategy`.
 
 use axum::{
     extract::ConnectInfo,
-    http::{Request, StatusCode},
+    http::{HeaderMap, Request, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
@@ -13,141 +31,436 @@ use governor::{
     state::{InMemoryState, NotKeyed},
     Quota, RateLimiter,
 };
+use phoenix_x402::{PriceTier, RateLimits};
 use serde_json::json;
+use sha2::{Digest, Sha256};
+use sqlx::{Pool, Sqlite};
 use std::{
-    collections::HashMap,
+    collections::{HashMap, VecDeque},
     net::SocketAddr,
     num::NonZeroU32,
-    sync::{Arc, RwLock},
-    time::Duration,
+    sync::{Arc, Mutex, RwLock},
+    time::{Duration, Instant},
 };
 
 /// Type alias for rate limiter map to reduce complexity
-type RateLimiterMap =
-    Arc<RwLock<HashMap<String, Arc<RateLimiter<NotKeyed, InMemoryState, DefaultClock>>>>>;
+type RateLimiterMap = Arc<RwLock<HashMap<String, Arc<Limiter>>>>;
+
+/// Algorithm used to enforce each quota
+///
+/// A quota of `n` per minute means `n` requests per 60-second window:
+///
+/// - `TokenBucket` (default): governor's GCRA. Up to `n` requests may burst,
+///   then one more is allowed every `60/n` seconds. This is the limiter the
+///   x402 endpoints have always used.
+/// - `FixedWindow`: at most `n` requests per clock-aligned window. Cheap, but
+///   a client can send `n` at the end of one window and `n` more at the start
+///   of the next.
+/// - `SlidingWindow`: at most `n` requests in any 60-second span (a log of
+///   request times per client), which closes that boundary burst.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum RateLimitStrategy {
+    FixedWindow,
+    SlidingWindow,
+    #[default]
+    TokenBucket,
+}
+
+impl std::str::FromStr for RateLimitStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().replace('-', "_").as_str() {
+            "fixed_window" => Ok(RateLimitStrategy::FixedWindow),
+            "sliding_window" => Ok(RateLimitStrategy::SlidingWindow),
+            "token_bucket" => Ok(RateLimitStrategy::TokenBucket),
+            other => Err(format!("unknown rate limit strategy: {}", other)),
+        }
+    }
+}
+
+/// One client's limiter under the configured strategy
+enum Limiter {
+    TokenBucket(RateLimiter<NotKeyed, InMemoryState, DefaultClock>),
+    Window(WindowLimiter),
+}
+
+impl Limiter {
+    fn new(strategy: RateLimitStrategy, quota: Quota) -> Self {
+        match strategy {
+            RateLimitStrategy::TokenBucket => Limiter::TokenBucket(RateLimiter::direct(quota)),
+            RateLimitStrategy::FixedWindow => {
+                Limiter::Window(WindowLimiter::new(quota, false, Instant::now()))
+            }
+            RateLimitStrategy::SlidingWindow => {
+                Limiter::Window(WindowLimiter::new(quota, true, Instant::now()))
+            }
+        }
+    }
+
+    /// `Err` carries how long until a request would be allowed
+    fn check(&self) -> Result<(), Duration> {
+        match self {
+            Limiter::TokenBucket(limiter) => limiter.check().map_err(|not_until| {
+                not_until.wait_time_from(governor::clock::Clock::now(&DefaultClock::default()))
+            }),
+            Limiter::Window(limiter) => limiter.check_at(Instant::now()),
+        }
+    }
+}
+
+/// Fixed- or sliding-window counter for one client
+struct WindowLimiter {
+    limit: usize,
+    window: Duration,
+    sliding: bool,
+    state: Mutex<WindowState>,
+}
+
+struct WindowState {
+    /// Start of the current fixed window
+    window_start: Instant,
+    /// Requests counted in the current fixed window
+    count: usize,
+    /// Times of requests within the last window (sliding only)
+    log: VecDeque<Instant>,
+}
+
+impl WindowLimiter {
+    fn new(quota: Quota, sliding: bool, start: Instant) -> Self {
+        let limit = quota.burst_size().get();
+        Self {
+            limit: limit as usize,
+            window: quota.replenish_interval() * limit,
+            sliding,
+            state: Mutex::new(WindowState {
+                window_start: start,
+                count: 0,
+                log: VecDeque::new(),
+            }),
+        }
+    }
+
+    fn check_at(&self, now: Instant) -> Result<(), Duration> {
+        let mut state = self.state.lock().unwrap();
+        if self.sliding {
+            while state
+                .log
+                .front()
+                .is_some_and(|t| now.saturating_duration_since(*t) >= self.window)
+            {
+                state.log.pop_front();
+            }
+            if state.log.len() < self.limit {
+                state.log.push_back(now);
+                return Ok(());
+            }
+            let oldest = state.log[0];
+            return Err((oldest + self.window).saturating_duration_since(now));
+        }
+
+        // Advance to the window containing `now`, keeping windows aligned
+        let elapsed = now.saturating_duration_since(state.window_start);
+        if elapsed >= self.window {
+            let windows = elapsed.as_nanos() / self.window.as_nanos();
+            state.window_start += self.window * windows as u32;
+            state.count = 0;
+        }
+        if state.count < self.limit {
+            state.count += 1;
+            return Ok(());
+        }
+        Err((state.window_start + self.window).saturating_duration_since(now))
+    }
+}
+
+/// Per-tier quotas for premium verification
+#[derive(Clone, Copy, Debug)]
+pub struct TierQuotas {
+    pub basic: Quota,
+    pub multi_chain: Quota,
+    pub legal_attestation: Quota,
+    pub bulk: Quota,
+}
+
+impl TierQuotas {
+    /// Same quota for every tier
+    pub fn uniform(quota: Quota) -> Self {
+        Self {
+            basic: quota,
+            multi_chain: quota,
+            legal_attestation: quota,
+            bulk: quota,
+        }
+    }
+
+    /// Defaults overridden by `X402_RATE_LIMIT_<TIER>_PER_MIN`
+    pub fn from_env() -> Self {
+        Self::from_vars(|var| std::env::var(var).ok())
+    }
+
+    /// Like [`from_env`](Self::from_env), reading variables through `lookup`
+    pub fn from_vars(lookup: impl Fn(&str) -> Option<String>) -> Self {
+        let defaults = Self::default();
+        let per_minute = |var: &str, default: Quota| {
+            lookup(var)
+                .and_then(|v| v.trim().parse::<u32>().ok())
+                .and_then(NonZeroU32::new)
+                .map(Quota::per_minute)
+                .unwrap_or(default)
+        };
+        Self {
+            basic: per_minute("X402_RATE_LIMIT_BASIC_PER_MIN", defaults.basic),
+            multi_chain: per_minute("X402_RATE_LIMIT_MULTI_CHAIN_PER_MIN", defaults.multi_chain),
+            legal_attestation: per_minute(
+                "X402_RATE_LIMIT_LEGAL_ATTESTATION_PER_MIN",
+                defaults.legal_attestation,
+            ),
+            bulk: per_minute("X402_RATE_LIMIT_BULK_PER_MIN", defaults.bulk),
+        }
+    }
+
+    pub fn get(&self, tier: PriceTier) -> Quota {
+        match tier {
+            PriceTier::Basic => self.basic,
+            PriceTier::MultiChain => self.multi_chain,
+            PriceTier::LegalAttestation => self.legal_attestation,
+            PriceTier::Bulk => self.bulk,
+        }
+    }
+}
+
+impl Default for TierQuotas {
+    fn default() -> Self {
+        Self {
+            basic: Quota::per_minute(NonZeroU32::new(10).unwrap()),
+            multi_chain: Quota::per_minute(NonZeroU32::new(6).unwrap()),
+            legal_attestation: Quota::per_minute(NonZeroU32::new(2).unwrap()),
+            bulk: Quota::per_minute(NonZeroU32::new(2).unwrap()),
+        }
+    }
+}
+
+/// Stable tier name used in limiter keys and 429 bodies
+fn tier_name(tier: PriceTier) -> &'static str {
+    match tier {
+        PriceTier::Basic => "basic",
+        PriceTier::MultiChain => "multi_chain",
+        PriceTier::LegalAttestation => "legal_attestation",
+        PriceTier::Bulk => "bulk",
+    }
+}
+
+/// Who a request is limited as
+///
+/// Bearer-authenticated clients get their own bucket keyed by a SHA-256 of the
+/// token (the raw token is never stored), so paying agents are neither
+/// throttled by neighbours behind the same NAT nor able to dodge limits by
+/// spoofing `X-Forwarded-For`. Only tokens naming a live session count;
+/// anything else is limited per IP, so minting a fresh token per request
+/// doesn't buy a fresh bucket.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ClientKey {
+    Ip(String),
+    ApiKey(String),
+}
+
+impl ClientKey {
+    /// Key for a bearer token
+    pub fn api_key(token: &str) -> Self {
+        ClientKey::ApiKey(hex::encode(Sha256::digest(token.as_bytes())))
+    }
+
+    /// The request's bearer token if it is a live session, otherwise
+    /// `client_ip`. A failed session lookup also falls back to the IP.
+    pub async fn from_headers(pool: &Pool<Sqlite>, headers: &HeaderMap, client_ip: &str) -> Self {
+        if let Some(token) = bearer_token(headers) {
+            match crate::db::is_active_session(pool, token).await {
+                Ok(true) => return Self::api_key(token),
+                Ok(false) => {}
+                Err(e) => tracing::warn!(error = %e, "Bearer token lookup failed; limiting by IP"),
+            }
+        }
+        ClientKey::Ip(client_ip.to_string())
+    }
+
+    /// Limiter map key; the prefix keeps IPs and key hashes apart
+    fn limiter_key(&self) -> String {
+        match self {
+            ClientKey::Ip(ip) => format!("ip:{}", ip),
+            ClientKey::ApiKey(hash) => format!("key:{}", hash),
+        }
+    }
+}
+
+/// The non-empty token of an `Authorization: Bearer` header
+pub fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+    let auth = headers
+        .get(axum::http::header::AUTHORIZATION)?
+        .to_str()
+        .ok()?;
+    let (scheme, token) = auth.split_once(' ')?;
+    let token = token.trim();
+    (scheme.eq_ignore_ascii_case("bearer") && !token.is_empty()).then_some(token)
+}
+
+impl From<&str> for ClientKey {
+    fn from(ip: &str) -> Self {
+        ClientKey::Ip(ip.to_string())
+    }
+}
 
 /// Rate limiter configuration for x402 endpoints
 #[derive(Clone)]
 pub struct X402RateLimiter {
-    /// Per-IP rate limiters for premium verification
+    /// Per-client rate limiters for premium verification
     verify_limiters: RateLimiterMap,
-    /// Per-IP rate limiters for status checks
+    /// Per-client rate limiters for status checks
     status_limiters: RateLimiterMap,
-    /// Quota for premium verification (more restrictive)
+    /// Per-IP quota for premium verification (more restrictive)
     verify_quota: Quota,
-    /// Quota for status checks (less restrictive)
+    /// Per-IP quota for status checks (less restrictive)
     status_quota: Quota,
+    /// Per-API-key quota for premium verification
+    api_key_verify_quota: Quota,
+    /// Per-API-key quota for status checks
+    api_key_status_quota: Quota,
+    /// Per-(tier, client) rate limiters for premium verification
+    tier_limiters: RateLimiterMap,
+    /// Quotas applied per tier on top of `verify_quota`
+    tier_quotas: TierQuotas,
+    /// Algorithm new limiters are created with
+    strategy: RateLimitStrategy,
 }
 
 impl X402RateLimiter {
     /// Create a new rate limiter with default quotas
     ///
-    /// Default quotas:
-    /// - Premium verification: 10 requests per minute per IP
-    /// - Status checks: 60 requests per minute per IP
+    /// Default quotas (see `RateLimits::default`):
+    /// - Premium verification: 10 requests per minute per IP, 60 per API key
+    /// - Status checks: 60 requests per minute per IP, 300 per API key
     pub fn new() -> Self {
+        Self::from_limits(&RateLimits::default())
+    }
+
+    /// Create a rate limiter from configured per-minute limits
+    pub fn from_limits(limits: &RateLimits) -> Self {
         Self::with_quotas(
-            Quota::per_minute(NonZeroU32::new(10).unwrap()),
-            Quota::per_minute(NonZeroU32::new(60).unwrap()),
+            per_minute(limits.verify_per_min),
+            per_minute(limits.status_per_min),
+        )
+        .with_api_key_quotas(
+            per_minute(limits.api_key_verify_per_min),
+            per_minute(limits.api_key_status_per_min),
         )
     }
 
-    /// Create a new rate limiter with custom quotas
+    /// Create a new rate limiter with custom per-IP quotas
     pub fn with_quotas(verify_quota: Quota, status_quota: Quota) -> Self {
+        let defaults = RateLimits::default();
         Self {
             verify_limiters: Arc::new(RwLock::new(HashMap::new())),
             status_limiters: Arc::new(RwLock::new(HashMap::new())),
             verify_quota,
             status_quota,
+            api_key_verify_quota: per_minute(defaults.api_key_verify_per_min),
+            api_key_status_quota: per_minute(defaults.api_key_status_per_min),
+            tier_limiters: Arc::new(RwLock::new(HashMap::new())),
+            tier_quotas: TierQuotas::default(),
+            strategy: RateLimitStrategy::default(),
         }
     }
 
-    /// Create a rate limiter for testing with higher limits
-    pub fn for_testing() -> Self {
-        Self::with_quotas(
-            Quota::per_second(NonZeroU32::new(100).unwrap()),
-            Quota::per_second(NonZeroU32::new(100).unwrap()),
-        )
+    /// Replace the per-API-key quotas
+    pub fn with_api_key_quotas(mut self, verify_quota: Quota, status_quota: Quota) -> Self {
+        self.api_key_verify_quota = verify_quota;
+        self.api_key_status_quota = status_quota;
+        self
     }
 
-    /// Get or create a rate limiter for an IP address (verify endpoint)
-    fn get_verify_limiter(
-        &self,
-        ip: &str,
-    ) -> Arc<RateLimiter<NotKeyed, InMemoryState, DefaultClock>> {
-        // Try read lock first
-        {
-            let limiters = self.verify_limiters.read().unwrap();
-            if let Some(limiter) = limiters.get(ip) {
-                return limiter.clone();
-            }
-        }
+    /// Enforce quotas with `strategy` instead of the default token bucket
+    pub fn with_strategy(mut self, strategy: RateLimitStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
 
-        // Need to create new limiter
-        let mut limiters = self.verify_limiters.write().unwrap();
-        // Double-check after acquiring write lock
-        if let Some(limiter) = limiters.get(ip) {
-            return limiter.clone();
-        }
+    /// Replace the per-tier quotas
+    pub fn with_tier_quotas(mut self, tier_quotas: TierQuotas) -> Self {
+        self.tier_quotas = tier_quotas;
+        self
+    }
 
-        let limiter = Arc::new(RateLimiter::direct(self.verify_quota));
-        limiters.insert(ip.to_string(), limiter.clone());
-        limiter
+    /// Create a rate limiter for testing with higher limits
+    pub fn for_testing() -> Self {
+        let quota = Quota::per_second(NonZeroU32::new(100).unwrap());
+        Self::with_quotas(quota, quota)
+            .with_api_key_quotas(quota, quota)
+            .with_tier_quotas(TierQuotas::uniform(quota))
     }
 
-    /// Get or create a rate limiter for an IP address (status endpoint)
-    fn get_status_limiter(
-        &self,
-        ip: &str,
-    ) -> Arc<RateLimiter<NotKeyed, InMemoryState, DefaultClock>> {
+    /// Get or create the limiter stored under `key`
+    fn get_limiter(&self, limiters: &RateLimiterMap, key: String, quota: Quota) -> Arc<Limiter> {
         // Try read lock first
         {
-            let limiters = self.status_limiters.read().unwrap();
-            if let Some(limiter) = limiters.get(ip) {
+            let limiters = limiters.read().unwrap();
+            if let Some(limiter) = limiters.get(&key) {
                 return limiter.clone();
             }
         }
 
         // Need to create new limiter
-        let mut limiters = self.status_limiters.write().unwrap();
+        let mut limiters = limiters.write().unwrap();
         // Double-check after acquiring write lock
-        if let Some(limiter) = limiters.get(ip) {
+        if let Some(limiter) = limiters.get(&key) {
             return limiter.clone();
         }
 
-        let limiter = Arc::new(RateLimiter::direct(self.status_quota));
-        limiters.insert(ip.to_string(), limiter.clone());
+        let limiter = Arc::new(Limiter::new(self.strategy, quota));
+        limiters.insert(key, limiter.clone());
         limiter
     }
 
     /// Check rate limit for premium verification endpoint
     /// Returns Ok(()) if allowed, Err(Response) if rate limited
     #[allow(clippy::result_large_err)]
-    pub fn check_verify(&self, ip: &str) -> Result<(), Response> {
-        let limiter = self.get_verify_limiter(ip);
-        match limiter.check() {
-            Ok(_) => Ok(()),
-            Err(not_until) => {
-                let wait_time =
-                    not_until.wait_time_from(governor::clock::Clock::now(&DefaultClock::default()));
-                Err(rate_limit_response(wait_time))
-            }
-        }
+    pub fn check_verify(&self, client: impl Into<ClientKey>) -> Result<(), Response> {
+        let client = client.into();
+        let quota = match client {
+            ClientKey::Ip(_) => self.verify_quota,
+            ClientKey::ApiKey(_) => self.api_key_verify_quota,
+        };
+        let limiter = self.get_limiter(&self.verify_limiters, client.limiter_key(), quota);
+        limiter.check().map_err(rate_limit_response)
+    }
+
+    /// Check the tier-specific limit for premium verification
+    ///
+    /// Applied after the request body is parsed, in addition to `check_verify`.
+    /// Returns Ok(()) if allowed, Err(Response) if rate limited
+    #[allow(clippy::result_large_err)]
+    pub fn check_verify_tier(
+        &self,
+        client: impl Into<ClientKey>,
+        tier: PriceTier,
+    ) -> Result<(), Response> {
+        let key = format!("{}:{}", tier_name(tier), client.into().limiter_key());
+        let limiter = self.get_limiter(&self.tier_limiters, key, self.tier_quotas.get(tier));
+        limiter
+            .check()
+            .map_err(|wait_time| tier_rate_limit_response(wait_time, tier))
     }
 
     /// Check rate limit for status endpoint
     /// Returns Ok(()) if allowed, Err(Response) if rate limited
     #[allow(clippy::result_large_err)]
-    pub fn check_status(&self, ip: &str) -> Result<(), Response> {
-        let limiter = self.get_status_limiter(ip);
-        match limiter.check() {
-            Ok(_) => Ok(()),
-            Err(not_until) => {
-                let wait_time =
-                    not_until.wait_time_from(governor::clock::Clock::now(&DefaultClock::default()));
-                Err(rate_limit_response(wait_time))
-            }
-        }
+    pub fn check_status(&self, client: impl Into<ClientKey>) -> Result<(), Response> {
+        let client = client.into();
+        let quota = match client {
+            ClientKey::Ip(_) => self.status_quota,
+            ClientKey::ApiKey(_) => self.api_key_status_quota,
+        };
+        let limiter = self.get_limiter(&self.status_limiters, client.limiter_key(), quota);
+        limiter.check().map_err(rate_limit_response)
     }
 
     /// Clean up old rate limiters (call periodically)
@@ -157,6 +470,7 @@ impl X402RateLimiter {
         // A more sophisticated implementation would track last access time
         let mut verify_limiters = self.verify_limiters.write().unwrap();
         let mut status_limiters = self.status_limiters.write().unwrap();
+        let mut tier_limiters = self.tier_limiters.write().unwrap();
 
         // Only cleanup if we have more than 10000 entries
         if verify_limiters.len() > 10000 {
@@ -165,35 +479,63 @@ impl X402RateLimiter {
         if status_limiters.len() > 10000 {
             status_limiters.clear();
         }
+        if tier_limiters.len() > 10000 {
+            tier_limiters.clear();
+        }
     }
 }
 
+/// Quota of `n` requests per minute (at least one)
+fn per_minute(n: u32) -> Quota {
+    Quota::per_minute(NonZeroU32::new(n).unwrap_or(NonZeroU32::MIN))
+}
+
 impl Default for X402RateLimiter {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// Whole seconds until a request can succeed, rounded up (at least one)
+fn retry_after_secs(retry_after: Duration) -> u64 {
+    let secs = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+    secs.max(1)
+}
+
 /// Create a 429 Too Many Requests response
 fn rate_limit_response(retry_after: Duration) -> Response {
-    let retry_secs = retry_after.as_secs().max(1);
-
-    let mut response = (
-        StatusCode::TOO_MANY_REQUESTS,
-        Json(json!({
-            "error": "Rate limit exceeded",
-            "retry_after_seconds": retry_secs,
+    let retry_secs = retry_after_secs(retry_after);
+    with_retry_after(
+        json!({
+            "error": "rate_limited",
+            "retry_after_secs": retry_secs,
             "hint": "Please wait before making another request"
-        })),
+        }),
+        retry_secs,
     )
-        .into_response();
+}
+
+/// Create a 429 response naming the tier whose quota was exhausted
+fn tier_rate_limit_response(retry_after: Duration, tier: PriceTier) -> Response {
+    let retry_secs = retry_after_secs(retry_after);
+    with_retry_after(
+        json!({
+            "error": "rate_limited",
+            "tier": tier_name(tier),
+            "retry_after_secs": retry_secs,
+            "hint": "Please wait before making another request for this tier"
+        }),
+        retry_secs,
+    )
+}
 
-    // Add Retry-After header
+/// 429 with `body`, telling the client in `Retry-After` when the bucket refills
+fn with_retry_after(body: serde_json::Value, retry_secs: u64) -> Response {
+    let mut response = (StatusCode::TOO_MANY_REQUESTS, Json(body)).into_response();
     response.headers_mut().insert(
         axum::http::header::RETRY_AFTER,
-        axum::http::HeaderValue::from_str(&retry_secs.to_string()).unwrap(),
+        axum::http::HeaderValue::from(retry_secs),
     );
-
     response
 }
 
@@ -297,13 +639,300 @@ mod tests {
         assert!(limiter.check_status(ip).is_err());
     }
 
+    #[test]
+    fn test_low_limit_tier_throttled_before_high_limit_tier() {
+        let limiter = X402RateLimiter::for_testing().with_tier_quotas(TierQuotas {
+            basic: Quota::per_minute(NonZeroU32::new(5).unwrap()),
+            multi_chain: Quota::per_minute(NonZeroU32::new(5).unwrap()),
+            legal_attestation: Quota::per_minute(NonZeroU32::new(1).unwrap()),
+            bulk: Quota::per_minute(NonZeroU32::new(1).unwrap()),
+        });
+
+        let ip = "10.1.1.1";
+
+        // Bulk: 1 allowed, then throttled
+        assert!(limiter.check_verify_tier(ip, PriceTier::Bulk).is_ok());
+        let response = limiter
+            .check_verify_tier(ip, PriceTier::Bulk)
+            .expect_err("bulk should be throttled after one request");
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert!(response
+            .headers()
+            .contains_key(axum::http::header::RETRY_AFTER));
+
+        // Basic for the same client still has its own, larger budget
+        for _ in 0..5 {
+            assert!(limiter.check_verify_tier(ip, PriceTier::Basic).is_ok());
+        }
+        assert!(limiter.check_verify_tier(ip, PriceTier::Basic).is_err());
+    }
+
+    #[test]
+    fn test_tier_quotas_from_env() {
+        let vars = std::collections::HashMap::from([("X402_RATE_LIMIT_BULK_PER_MIN", "1")]);
+        let quotas = TierQuotas::from_vars(|var| vars.get(var).map(|v| v.to_string()));
+
+        assert_eq!(quotas.bulk, Quota::per_minute(NonZeroU32::new(1).unwrap()));
+        assert_eq!(quotas.basic, TierQuotas::default().basic);
+    }
+
+    #[test]
+    fn test_api_key_not_blocked_by_exhausted_ip_bucket() {
+        let limiter = X402RateLimiter::with_quotas(
+            Quota::per_minute(NonZeroU32::new(1).unwrap()),
+            Quota::per_minute(NonZeroU32::new(1).unwrap()),
+        )
+        .with_api_key_quotas(
+            Quota::per_minute(NonZeroU32::new(3).unwrap()),
+            Quota::per_minute(NonZeroU32::new(3).unwrap()),
+        );
+
+        let ip = "10.2.2.2";
+        assert!(limiter.check_verify(ip).is_ok());
+        assert!(limiter.check_verify(ip).is_err());
+
+        // Same origin, but authenticated: a separate, larger bucket
+        let key = ClientKey::api_key("agent-token-a");
+        for _ in 0..3 {
+            assert!(limiter.check_verify(key.clone()).is_ok());
+        }
+        assert!(limiter.check_verify(key).is_err());
+        assert!(limiter
+            .check_verify(ClientKey::api_key("agent-token-b"))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_tier_limit_keyed_per_api_key() {
+        let limiter = X402RateLimiter::for_testing().with_tier_quotas(TierQuotas::uniform(
+            Quota::per_minute(NonZeroU32::new(1).unwrap()),
+        ));
+
+        // Two agents behind one NAT: the shared IP's bucket is spent...
+        let ip = "10.6.6.6";
+        assert!(limiter.check_verify_tier(ip, PriceTier::Bulk).is_ok());
+        assert!(limiter.check_verify_tier(ip, PriceTier::Bulk).is_err());
+
+        // ...but each API key has its own tier budget
+        let first = ClientKey::api_key("agent-token-a");
+        let second = ClientKey::api_key("agent-token-b");
+        assert!(limiter
+            .check_verify_tier(first.clone(), PriceTier::Bulk)
+            .is_ok());
+        assert!(limiter
+            .check_verify_tier(second.clone(), PriceTier::Bulk)
+            .is_ok());
+        assert!(limiter.check_verify_tier(first, PriceTier::Bulk).is_err());
+        assert!(limiter.check_verify_tier(second, PriceTier::Bulk).is_err());
+    }
+
+    /// In-memory database with one live session, `session_id`
+    async fn pool_with_session(session_id: &str) -> Pool<Sqlite> {
+        let pool = sqlx::sqlite::SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        crate::migrations::MigrationManager::new(pool.clone())
+            .migrate()
+            .await
+            .unwrap();
+        let now = chrono::Utc::now().timestamp_millis();
+        sqlx::query("INSERT INTO users (id, email, created_ms, updated_ms) VALUES ('u1', 'agent@example.com', ?1, ?1)")
+            .bind(now)
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO sessions (id, user_id, expires_at, created_ms) VALUES (?1, 'u1', ?2, ?3)",
+        )
+        .bind(session_id)
+        .bind(now + 60_000)
+        .bind(now)
+        .execute(&pool)
+        .await
+        .unwrap();
+        pool
+    }
+
+    #[tokio::test]
+    async fn test_same_api_key_shares_bucket_across_ips() {
+        let limiter = X402RateLimiter::new().with_api_key_quotas(
+            Quota::per_minute(NonZeroU32::new(2).unwrap()),
+            Quota::per_minute(NonZeroU32::new(2).unwrap()),
+        );
+        let pool = pool_with_session("shared-token").await;
+
+        let mut headers = HeaderMap::new();
+        headers.insert("authorization", "Bearer shared-token".parse().unwrap());
+        let first = ClientKey::from_headers(&pool, &headers, "10.3.3.1").await;
+        let second = ClientKey::from_headers(&pool, &headers, "10.3.3.2").await;
+        assert_eq!(first, second);
+        assert!(matches!(&first, ClientKey::ApiKey(hash) if !hash.contains("shared-token")));
+
+        assert!(limiter.check_status(first.clone()).is_ok());
+        assert!(limiter.check_status(second).is_ok());
+        assert!(limiter.check_status(first).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_client_key_falls_back_to_ip() {
+        let pool = pool_with_session("live-session").await;
+        let ip = ClientKey::Ip("10.4.4.4".to_string());
+
+        let headers = HeaderMap::new();
+        assert_eq!(
+            ClientKey::from_headers(&pool, &headers, "10.4.4.4").await,
+            ip
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert("authorization", "Bearer ".parse().unwrap());
+        assert_eq!(
+            ClientKey::from_headers(&pool, &headers, "10.4.4.4").await,
+            ip
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unknown_bearer_token_gets_ip_bucket() {
+        let limiter = X402RateLimiter::with_quotas(
+            Quota::per_minute(NonZeroU32::new(1).unwrap()),
+            Quota::per_minute(NonZeroU32::new(1).unwrap()),
+        );
+        let pool = pool_with_session("live-session").await;
+
+        // A fresh made-up token per request still lands in the IP's bucket
+        let mut keys = Vec::new();
+        for token in ["made-up-1", "made-up-2"] {
+            let mut headers = HeaderMap::new();
+            headers.insert(
+                "authorization",
+                format!("Bearer {}", token).parse().unwrap(),
+            );
+            keys.push(ClientKey::from_headers(&pool, &headers, "10.5.5.5").await);
+        }
+        assert!(keys
+            .iter()
+            .all(|key| *key == ClientKey::Ip("10.5.5.5".to_string())));
+        assert!(limiter.check_verify(keys[0].clone()).is_ok());
+        assert!(limiter.check_verify(keys[1].clone()).is_err());
+
+        // Expired sessions don't count either
+        sqlx::query("UPDATE sessions SET expires_at = 0")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let mut headers = HeaderMap::new();
+        headers.insert("authorization", "Bearer live-session".parse().unwrap());
+        assert_eq!(
+            ClientKey::from_headers(&pool, &headers, "10.5.5.5").await,
+            ClientKey::Ip("10.5.5.5".to_string())
+        );
+    }
+
+    #[test]
+    fn test_retry_after_rounds_up() {
+        assert_eq!(retry_after_secs(Duration::ZERO), 1);
+        assert_eq!(retry_after_secs(Duration::from_millis(200)), 1);
+        assert_eq!(retry_after_secs(Duration::from_secs(6)), 6);
+        assert_eq!(retry_after_secs(Duration::from_millis(5_100)), 6);
+    }
+
+    /// `limit` requests just before and just after the boundary of the window
+    /// that starts at `start`; returns how many were allowed
+    fn boundary_burst(limiter: &WindowLimiter, start: Instant, limit: usize) -> usize {
+        let before = start + Duration::from_secs(59);
+        let after = start + Duration::from_secs(61);
+        (0..limit)
+            .map(|_| limiter.check_at(before))
+            .chain((0..limit).map(|_| limiter.check_at(after)))
+            .filter(Result::is_ok)
+            .count()
+    }
+
+    #[test]
+    fn test_fixed_window_allows_boundary_burst() {
+        let quota = Quota::per_minute(NonZeroU32::new(4).unwrap());
+        let start = Instant::now();
+        let limiter = WindowLimiter::new(quota, false, start);
+
+        // Twice the quota within two seconds
+        assert_eq!(boundary_burst(&limiter, start, 4), 8);
+        let wait = limiter
+            .check_at(start + Duration::from_secs(61))
+            .unwrap_err();
+        assert_eq!(wait, Duration::from_secs(59));
+    }
+
+    #[test]
+    fn test_sliding_window_rejects_boundary_burst() {
+        let quota = Quota::per_minute(NonZeroU32::new(4).unwrap());
+        let start = Instant::now();
+        let limiter = WindowLimiter::new(quota, true, start);
+
+        assert_eq!(boundary_burst(&limiter, start, 4), 4);
+        // Free again once the first requests are a full window old
+        let wait = limiter
+            .check_at(start + Duration::from_secs(61))
+            .unwrap_err();
+        assert_eq!(wait, Duration::from_secs(58));
+        assert!(limiter.check_at(start + Duration::from_secs(119)).is_ok());
+    }
+
+    #[test]
+    fn test_token_bucket_rejects_burst_beyond_quota() {
+        let limiter = X402RateLimiter::with_quotas(
+            Quota::per_minute(NonZeroU32::new(4).unwrap()),
+            Quota::per_minute(NonZeroU32::new(4).unwrap()),
+        );
+
+        // The default strategy only refills one request every 15 seconds,
+        // so a second burst right after the first is refused
+        let allowed = (0..8)
+            .filter(|_| limiter.check_verify("10.5.5.5").is_ok())
+            .count();
+        assert_eq!(allowed, 4);
+    }
+
+    #[test]
+    fn test_with_strategy_applies_to_endpoints() {
+        let limiter = X402RateLimiter::with_quotas(
+            Quota::per_minute(NonZeroU32::new(2).unwrap()),
+            Quota::per_minute(NonZeroU32::new(2).unwrap()),
+        )
+        .with_strategy(RateLimitStrategy::SlidingWindow);
+
+        assert!(limiter.check_status("10.6.6.6").is_ok());
+        assert!(limiter.check_status("10.6.6.6").is_ok());
+        let response = limiter.check_status("10.6.6.6").unwrap_err();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[axum::http::header::RETRY_AFTER], "60");
+    }
+
+    #[test]
+    fn test_strategy_from_str() {
+        assert_eq!(
+            "sliding-window".parse::<RateLimitStrategy>(),
+            Ok(RateLimitStrategy::SlidingWindow)
+        );
+        assert_eq!(
+            "FIXED_WINDOW".parse::<RateLimitStrategy>(),
+            Ok(RateLimitStrategy::FixedWindow)
+        );
+        assert!("leaky".parse::<RateLimitStrategy>().is_err());
+        assert_eq!(RateLimitStrategy::default(), RateLimitStrategy::TokenBucket);
+    }
+
     #[test]
     fn test_cleanup() {
         let limiter = X402RateLimiter::new();
 
         // Create many limiters
         for i in 0..100 {
-            limiter.check_verify(&format!("192.168.1.{}", i)).ok();
+            limiter
+                .check_verify(format!("192.168.1.{}", i).as_str())
+                .ok();
         }
 
         // Cleanup shouldn't panic
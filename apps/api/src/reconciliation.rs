@@ -0,0 +1,206 @@
+//! Payment receipt reconciliation
+//!
+//! Re-checks recorded x402 payment receipts against what the chain reports
+//! (amount, recipient, confirmation) so recording bugs and partial failures
+//! surface as explicit discrepancies instead of silently skewing revenue.
+
+use crate::models::{
+    PaymentReceiptOut, ReceiptDiscrepancy, ReceiptReconciliation, ReconciliationReport,
+    ReconciliationTotals,
+};
+use async_trait::async_trait;
+use phoenix_x402::{PaymentProof, UsdcAmount, X402Facilitator};
+
+/// What the chain reports for a payment transaction
+#[derive(Debug, Clone, PartialEq)]
+pub struct OnChainPayment {
+    /// Whether the transaction exists and is confirmed
+    pub confirmed: bool,
+    /// Amount transferred in USDC, if known
+    pub amount_usdc: Option<String>,
+    /// Recipient wallet, if known
+    pub recipient: Option<String>,
+}
+
+/// Looks up the on-chain state of a recorded payment
+#[async_trait]
+pub trait PaymentVerifier {
+    async fn lookup(&self, receipt: &PaymentReceiptOut) -> Result<OnChainPayment, String>;
+}
+
+/// Verifier backed by the x402 facilitator
+///
+/// The recipient is the transfer destination the facilitator reports, so a
+/// facilitator that omits it shows up as an unknown recipient rather than a
+/// silent match with our own wallet.
+pub struct FacilitatorPaymentVerifier {
+    facilitator: X402Facilitator,
+}
+
+impl FacilitatorPaymentVerifier {
+    pub fn new(facilitator: X402Facilitator) -> Self {
+        Self { facilitator }
+    }
+}
+
+#[async_trait]
+impl PaymentVerifier for FacilitatorPaymentVerifier {
+    async fn lookup(&self, receipt: &PaymentReceiptOut) -> Result<OnChainPayment, String> {
+        let memo = format!("evidence:{}", receipt.evidence_id);
+        // Receipts from before the token was recorded were paid in USDC
+        let proof = PaymentProof {
+            signature: receipt.tx_signature.clone(),
+            amount: receipt
+                .token_amount
+                .clone()
+                .unwrap_or_else(|| receipt.amount_usdc.clone()),
+            token: receipt.token.clone(),
+            mint: None,
+            sender: receipt.sender_wallet.clone().unwrap_or_default(),
+            memo: memo.clone(),
+            expires_at: None,
+            quote_signature: None,
+            timestamp: String::new(),
+        };
+
+        let amount = receipt
+            .amount_usdc
+            .parse()
+            .map_err(|e: phoenix_x402::X402Error| e.to_string())?;
+        let verification = self
+            .facilitator
+            .recheck_payment(&proof, &memo, &amount)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        Ok(OnChainPayment {
+            confirmed: verification.valid,
+            amount_usdc: Some(verification.amount_usdc.to_string()),
+            recipient: verification.recipient,
+        })
+    }
+}
+
+/// Amount in millionths of a USDC, or `None` if it isn't a USDC amount
+fn micro_usdc(amount: &str) -> Option<u128> {
+    amount.parse::<UsdcAmount>().ok().map(|a| a.to_micro())
+}
+
+/// Compare USDC amounts exactly in micro-units so "0.01" and "0.010" reconcile
+fn amounts_match(recorded: &str, on_chain: &str) -> bool {
+    match (micro_usdc(recorded), micro_usdc(on_chain)) {
+        (Some(a), Some(b)) => a == b,
+        _ => recorded.trim() == on_chain.trim(),
+    }
+}
+
+/// Micro-USDC total at a fixed six places, e.g. `"0.050000"`
+fn format_micro_total(micro: u128) -> String {
+    format!("{}.{:06}", micro / 1_000_000, micro % 1_000_000)
+}
+
+/// Compare one receipt with its on-chain counterpart
+pub fn compare_receipt(
+    receipt: &PaymentReceiptOut,
+    on_chain: &OnChainPayment,
+    expected_recipient: &str,
+) -> Vec<ReceiptDiscrepancy> {
+    let mut discrepancies = Vec::new();
+
+    if !on_chain.confirmed {
+        discrepancies.push(ReceiptDiscrepancy {
+            field: "confirmation".to_string(),
+            recorded: "confirmed".to_string(),
+            on_chain: "unconfirmed".to_string(),
+        });
+    }
+
+    match &on_chain.amount_usdc {
+        Some(amount) if amounts_match(&receipt.amount_usdc, amount) => {}
+        other => discrepancies.push(ReceiptDiscrepancy {
+            field: "amount_usdc".to_string(),
+            recorded: receipt.amount_usdc.clone(),
+            on_chain: other.clone().unwrap_or_else(|| "unknown".to_string()),
+        }),
+    }
+
+    match &on_chain.recipient {
+        Some(recipient) if recipient == expected_recipient => {}
+        other => discrepancies.push(ReceiptDiscrepancy {
+            field: "recipient".to_string(),
+            recorded: expected_recipient.to_string(),
+            on_chain: other.clone().unwrap_or_else(|| "unknown".to_string()),
+        }),
+    }
+
+    discrepancies
+}
+
+/// Reconcile a set of receipts and summarise the result
+pub async fn reconcile_receipts(
+    receipts: Vec<PaymentReceiptOut>,
+    verifier: &(dyn PaymentVerifier + Send + Sync),
+    expected_recipient: &str,
+    from: Option<i64>,
+    to: Option<i64>,
+) -> ReconciliationReport {
+    let mut totals = ReconciliationTotals::default();
+    let mut recorded_total: u128 = 0;
+    let mut on_chain_total: u128 = 0;
+    let mut results = Vec::with_capacity(receipts.len());
+
+    for receipt in receipts {
+        totals.receipts += 1;
+        recorded_total += micro_usdc(&receipt.amount_usdc).unwrap_or(0);
+
+        let (status, discrepancies, error) = match verifier.lookup(&receipt).await {
+            Ok(on_chain) => {
+                if on_chain.confirmed {
+                    on_chain_total += on_chain
+                        .amount_usdc
+                        .as_deref()
+                        .and_then(micro_usdc)
+                        .unwrap_or(0);
+                }
+                let discrepancies = compare_receipt(&receipt, &on_chain, expected_recipient);
+                if discrepancies.is_empty() {
+                    totals.matched += 1;
+                    ("matched", discrepancies, None)
+                } else {
+                    totals.mismatched += 1;
+                    ("mismatched", discrepancies, None)
+                }
+            }
+            Err(e) => {
+                tracing::warn!(
+                    tx_signature = %receipt.tx_signature,
+                    "payment reconciliation lookup failed: {}",
+                    e
+                );
+                totals.errors += 1;
+                ("error", Vec::new(), Some(e))
+            }
+        };
+
+        results.push(ReceiptReconciliation {
+            receipt_id: receipt.id,
+            evidence_id: receipt.evidence_id,
+            tx_signature: receipt.tx_signature,
+            amount_usdc: receipt.amount_usdc,
+            verified_at: receipt.verified_at,
+            status: status.to_string(),
+            discrepancies,
+            error,
+        });
+    }
+
+    totals.recorded_amount_usdc = format_micro_total(recorded_total);
+    totals.on_chain_amount_usdc = format_micro_total(on_chain_total);
+
+    ReconciliationReport {
+        from,
+        to,
+        totals,
+        receipts: results,
+    }
+}
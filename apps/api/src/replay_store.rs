@@ -0,0 +1,46 @@
+//! SQLite-backed x402 replay protection
+//!
+//! Redeemed payments are the rows of `payment_receipts`, so the receipt that
+//! serves as the audit trail is also what stops a signature being used twice.
+//! The UNIQUE `tx_signature` constraint makes [`ReplayStore::record`] atomic
+//! across concurrent requests and API instances sharing the database.
+
+use crate::db::{create_payment_receipt, is_payment_signature_used};
+use crate::db_errors::UniqueViolation;
+use async_trait::async_trait;
+use phoenix_x402::{PaymentReceipt, ReplayError, ReplayStore};
+use sqlx::{Pool, Sqlite};
+
+/// [`ReplayStore`] over the API's `payment_receipts` table
+#[derive(Debug, Clone)]
+pub struct SqliteReplayStore {
+    pool: Pool<Sqlite>,
+}
+
+impl SqliteReplayStore {
+    pub fn new(pool: Pool<Sqlite>) -> Self {
+        Self { pool }
+    }
+}
+
+#[async_trait]
+impl ReplayStore for SqliteReplayStore {
+    async fn is_used(&self, signature: &str) -> Result<bool, ReplayError> {
+        is_payment_signature_used(&self.pool, signature)
+            .await
+            .map_err(|e| ReplayError::Store(e.to_string()))
+    }
+
+    async fn record(&self, receipt: &PaymentReceipt) -> Result<(), ReplayError> {
+        create_payment_receipt(&self.pool, receipt)
+            .await
+            .map(|_| ())
+            .map_err(|e| {
+                if e.is_unique_violation() {
+                    ReplayError::AlreadyUsed(receipt.tx_signature.clone())
+                } else {
+                    ReplayError::Store(e.to_string())
+                }
+            })
+    }
+}
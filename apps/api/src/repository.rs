@@ -1,4 +1,7 @@
-use crate::models::{EvidenceIn, EvidenceOut};
+use crate::models::{
+    check_digest_algo, normalize_digest_hex, EvidenceIn, EvidenceOut, INDEXED_METADATA_KEYS,
+};
+use phoenix_evidence::canonical::canonicalize_json;
 use sqlx::{Pool, Row, Sqlite, Transaction};
 use thiserror::Error;
 
@@ -46,7 +49,10 @@ impl EvidenceRepository {
                 last_error TEXT,
                 created_ms INTEGER NOT NULL,
                 updated_ms INTEGER NOT NULL,
-                next_attempt_ms INTEGER NOT NULL DEFAULT 0
+                next_attempt_ms INTEGER NOT NULL DEFAULT 0,
+                source TEXT NOT NULL DEFAULT 'api',
+                priority INTEGER NOT NULL DEFAULT 0,
+                metadata TEXT
             );
             "#,
         )
@@ -77,6 +83,24 @@ impl EvidenceRepository {
         .execute(&self.pool)
         .await;
 
+        // Likewise for source attribution
+        let _ =
+            sqlx::query("ALTER TABLE outbox_jobs ADD COLUMN source TEXT NOT NULL DEFAULT 'api'")
+                .execute(&self.pool)
+                .await;
+
+        // And the searchable metadata columns
+        let _ = sqlx::query(
+            "ALTER TABLE outbox_jobs ADD COLUMN event_type TEXT GENERATED ALWAYS AS (json_extract(metadata, '$.event_type')) VIRTUAL",
+        )
+        .execute(&self.pool)
+        .await;
+        sqlx::query(
+            "CREATE INDEX IF NOT EXISTS idx_outbox_jobs_event_type ON outbox_jobs(event_type)",
+        )
+        .execute(&self.pool)
+        .await?;
+
         Ok(())
     }
 
@@ -86,15 +110,21 @@ impl EvidenceRepository {
             .id
             .clone()
             .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        let digest_hex =
+            normalize_digest_hex(&evidence.digest_hex).map_err(RepositoryError::Validation)?;
+        check_digest_algo(evidence.metadata.as_ref()).map_err(RepositoryError::Validation)?;
 
         let current_timestamp_ms = chrono::Utc::now().timestamp_millis();
 
         let result = sqlx::query(
-            "INSERT OR IGNORE INTO outbox_jobs (id, payload_sha256, status, attempts, created_ms, updated_ms, next_attempt_ms) VALUES (?1, ?2, 'queued', 0, ?3, ?3, 0)"
+            "INSERT OR IGNORE INTO outbox_jobs (id, payload_sha256, status, attempts, created_ms, updated_ms, next_attempt_ms, source, priority, metadata) VALUES (?1, ?2, 'queued', 0, ?3, ?3, 0, ?4, ?5, ?6)"
         )
         .bind(&id)
-        .bind(&evidence.digest_hex)
+        .bind(&digest_hex)
         .bind(current_timestamp_ms)
+        .bind(evidence.source.as_deref().unwrap_or("api"))
+        .bind(evidence.priority.unwrap_or(0))
+        .bind(evidence.metadata.as_ref().map(canonicalize_json))
         .execute(&self.pool)
         .await?;
 
@@ -111,7 +141,7 @@ impl EvidenceRepository {
     /// Get evidence job by ID
     pub async fn get_evidence_by_id(&self, id: &str) -> Result<Option<EvidenceOut>> {
         let row = sqlx::query(
-            "SELECT id, payload_sha256, status, attempts, last_error, created_ms, updated_ms FROM outbox_jobs WHERE id = ?1"
+            "SELECT id, payload_sha256, status, attempts, last_error, created_ms, updated_ms, source FROM outbox_jobs WHERE id = ?1"
         )
         .bind(id)
         .fetch_optional(&self.pool)
@@ -125,6 +155,7 @@ impl EvidenceRepository {
             last_error: row.get::<Option<String>, _>(4),
             created_ms: row.get::<i64, _>(5),
             updated_ms: row.get::<i64, _>(6),
+            source: row.get::<String, _>(7),
         }))
     }
 
@@ -142,7 +173,7 @@ impl EvidenceRepository {
 
         // Get paginated results
         let rows = sqlx::query(
-            "SELECT id, payload_sha256, status, attempts, last_error, created_ms, updated_ms FROM outbox_jobs ORDER BY created_ms DESC LIMIT ?1 OFFSET ?2"
+            "SELECT id, payload_sha256, status, attempts, last_error, created_ms, updated_ms, source FROM outbox_jobs ORDER BY created_ms DESC LIMIT ?1 OFFSET ?2"
         )
         .bind(limit)
         .bind(offset)
@@ -159,12 +190,56 @@ impl EvidenceRepository {
                 last_error: row.get::<Option<String>, _>(4),
                 created_ms: row.get::<i64, _>(5),
                 updated_ms: row.get::<i64, _>(6),
+                source: row.get::<String, _>(7),
             })
             .collect();
 
         Ok((evidence_jobs, total_count))
     }
 
+    /// Jobs whose metadata `key` equals `value`, newest first.
+    ///
+    /// Only keys in [`INDEXED_METADATA_KEYS`] can be searched, so a search is
+    /// an index lookup rather than a scan over every job's metadata JSON.
+    pub async fn search_by_metadata(
+        &self,
+        key: &str,
+        value: &str,
+        limit: i64,
+    ) -> Result<Vec<EvidenceOut>> {
+        let Some(column) = INDEXED_METADATA_KEYS.iter().find(|k| **k == key) else {
+            return Err(RepositoryError::Validation(format!(
+                "metadata key '{}' is not searchable: expected one of {}",
+                key,
+                INDEXED_METADATA_KEYS.join(", ")
+            )));
+        };
+
+        // `column` comes from INDEXED_METADATA_KEYS, never from the caller
+        let rows = sqlx::query(&format!(
+            "SELECT id, payload_sha256, status, attempts, last_error, created_ms, updated_ms, source FROM outbox_jobs WHERE {} = ?1 ORDER BY created_ms DESC, id DESC LIMIT ?2",
+            column
+        ))
+        .bind(value)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| EvidenceOut {
+                id: row.get::<String, _>(0),
+                digest_hex: row.get::<String, _>(1),
+                status: row.get::<String, _>(2),
+                attempts: row.get::<i64, _>(3),
+                last_error: row.get::<Option<String>, _>(4),
+                created_ms: row.get::<i64, _>(5),
+                updated_ms: row.get::<i64, _>(6),
+                source: row.get::<String, _>(7),
+            })
+            .collect())
+    }
+
     /// Update job status
     pub async fn update_job_status(
         &self,
@@ -244,7 +319,7 @@ impl EvidenceRepository {
         let current_timestamp_ms = chrono::Utc::now().timestamp_millis();
 
         let rows = sqlx::query(
-            "SELECT id, payload_sha256, status, attempts, last_error, created_ms, updated_ms FROM outbox_jobs WHERE status = 'queued' AND next_attempt_ms <= ?1 ORDER BY created_ms ASC LIMIT ?2"
+            "SELECT id, payload_sha256, status, attempts, last_error, created_ms, updated_ms, source FROM outbox_jobs WHERE status = 'queued' AND next_attempt_ms <= ?1 ORDER BY created_ms ASC LIMIT ?2"
         )
         .bind(current_timestamp_ms)
         .bind(limit)
@@ -261,6 +336,7 @@ impl EvidenceRepository {
                 last_error: row.get::<Option<String>, _>(4),
                 created_ms: row.get::<i64, _>(5),
                 updated_ms: row.get::<i64, _>(6),
+                source: row.get::<String, _>(7),
             })
             .collect();
 
@@ -346,15 +422,21 @@ impl EvidenceRepository {
             .id
             .clone()
             .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+        let digest_hex =
+            normalize_digest_hex(&evidence.digest_hex).map_err(RepositoryError::Validation)?;
+        check_digest_algo(evidence.metadata.as_ref()).map_err(RepositoryError::Validation)?;
 
         let current_timestamp_ms = chrono::Utc::now().timestamp_millis();
 
         let result = sqlx::query(
-            "INSERT OR IGNORE INTO outbox_jobs (id, payload_sha256, status, attempts, created_ms, updated_ms, next_attempt_ms) VALUES (?1, ?2, 'queued', 0, ?3, ?3, 0)"
+            "INSERT OR IGNORE INTO outbox_jobs (id, payload_sha256, status, attempts, created_ms, updated_ms, next_attempt_ms, source, priority, metadata) VALUES (?1, ?2, 'queued', 0, ?3, ?3, 0, ?4, ?5, ?6)"
         )
         .bind(&id)
-        .bind(&evidence.digest_hex)
+        .bind(&digest_hex)
         .bind(current_timestamp_ms)
+        .bind(evidence.source.as_deref().unwrap_or("api"))
+        .bind(evidence.priority.unwrap_or(0))
+        .bind(evidence.metadata.as_ref().map(canonicalize_json))
         .execute(&mut *tx)
         .await?;
 
@@ -402,9 +484,13 @@ mod tests {
 
         let evidence = EvidenceIn {
             id: Some("test-123".to_string()),
-            digest_hex: "abcd1234".to_string(),
+            digest_hex: "ab".repeat(32),
             payload_mime: Some("application/json".to_string()),
             metadata: Some(serde_json::json!({"key": "value"})),
+            anchor_mode: None,
+            source: None,
+            priority: None,
+            payload_base64: None,
         };
 
         let id = repo.create_evidence_job(&evidence).await.unwrap();
@@ -421,9 +507,13 @@ mod tests {
 
         let evidence = EvidenceIn {
             id: Some("test-123".to_string()),
-            digest_hex: "abcd1234".to_string(),
+            digest_hex: "ab".repeat(32),
             payload_mime: None,
             metadata: None,
+            anchor_mode: None,
+            source: None,
+            priority: None,
+            payload_base64: None,
         };
 
         // First creation should succeed
@@ -435,15 +525,43 @@ mod tests {
         assert!(matches!(result, Err(RepositoryError::Conflict(_))));
     }
 
+    #[tokio::test]
+    async fn test_digest_hex_is_validated_and_lowercased() {
+        let repo = create_test_repo().await;
+
+        let mut evidence = EvidenceIn {
+            id: Some("test-digest".to_string()),
+            digest_hex: "AB".repeat(32),
+            payload_mime: None,
+            metadata: None,
+            anchor_mode: None,
+            source: None,
+            priority: None,
+            payload_base64: None,
+        };
+        let id = repo.create_evidence_job(&evidence).await.unwrap();
+        let job = repo.get_evidence_by_id(&id).await.unwrap().unwrap();
+        assert_eq!(job.digest_hex, "ab".repeat(32));
+
+        evidence.id = Some("test-digest-short".to_string());
+        evidence.digest_hex = "abcd1234".to_string();
+        let result = repo.create_evidence_job(&evidence).await;
+        assert!(matches!(result, Err(RepositoryError::Validation(_))));
+    }
+
     #[tokio::test]
     async fn test_job_lifecycle() {
         let repo = create_test_repo().await;
 
         let evidence = EvidenceIn {
             id: Some("test-lifecycle".to_string()),
-            digest_hex: "abcd1234".to_string(),
+            digest_hex: "ab".repeat(32),
             payload_mime: None,
             metadata: None,
+            anchor_mode: None,
+            source: None,
+            priority: None,
+            payload_base64: None,
         };
 
         // Create job
@@ -470,9 +588,13 @@ mod tests {
         for i in 0..5 {
             let evidence = EvidenceIn {
                 id: Some(format!("test-{}", i)),
-                digest_hex: "abcd1234".to_string(),
+                digest_hex: "ab".repeat(32),
                 payload_mime: None,
                 metadata: None,
+                anchor_mode: None,
+                source: None,
+                priority: None,
+                payload_base64: None,
             };
             repo.create_evidence_job(&evidence).await.unwrap();
         }
@@ -482,4 +604,60 @@ mod tests {
         assert_eq!(stats.queued, 5);
         assert_eq!(stats.done, 0);
     }
+
+    #[tokio::test]
+    async fn test_search_by_metadata_event_type() {
+        let repo = create_test_repo().await;
+
+        for (id, metadata) in [
+            (
+                "meta-engagement-1",
+                Some(serde_json::json!({"event_type": "engagement_summary"})),
+            ),
+            (
+                "meta-engagement-2",
+                Some(serde_json::json!({"event_type": "engagement_summary", "priority": 1})),
+            ),
+            (
+                "meta-detection",
+                Some(serde_json::json!({"event_type": "detection"})),
+            ),
+            ("meta-none", None),
+        ] {
+            let evidence = EvidenceIn {
+                id: Some(id.to_string()),
+                digest_hex: "ab".repeat(32),
+                payload_mime: None,
+                metadata,
+                anchor_mode: None,
+                source: None,
+                priority: None,
+                payload_base64: None,
+            };
+            repo.create_evidence_job(&evidence).await.unwrap();
+        }
+
+        let mut ids: Vec<String> = repo
+            .search_by_metadata("event_type", "engagement_summary", 10)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|job| job.id)
+            .collect();
+        ids.sort();
+        assert_eq!(ids, vec!["meta-engagement-1", "meta-engagement-2"]);
+
+        let found = repo
+            .search_by_metadata("event_type", "detection", 10)
+            .await
+            .unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, "meta-detection");
+
+        // Keys without an index are refused rather than scanned
+        assert!(matches!(
+            repo.search_by_metadata("priority", "1", 10).await,
+            Err(RepositoryError::Validation(_))
+        ));
+    }
 }
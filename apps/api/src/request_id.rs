@@ -0,0 +1,124 @@
+//! Request ids for correlating logs across a request
+//!
+//! Every request gets an id, taken from an inbound `X-Request-Id` header when
+//! it is a sane token (so ids assigned by a gateway or calling service carry
+//! through) and otherwise a fresh UUID. The id is:
+//!
+//! - recorded on a `request` tracing span wrapping the handler, so every log
+//!   line emitted while serving the request carries `request_id`
+//! - available to handlers as `Extension<RequestId>`
+//! - echoed in the `X-Request-Id` response header
+//! - added as `request_id` to JSON error bodies (4xx/5xx, except the 402
+//!   payment quote, whose body is protocol-defined)
+
+use axum::{
+    body::Body,
+    extract::Request,
+    http::{header, HeaderValue, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use tracing::Instrument;
+
+/// Header carrying the request id in both directions
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Longest inbound id accepted as-is
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+/// Largest error body rewritten to include the request id
+const MAX_ERROR_BODY_BYTES: usize = 64 * 1024;
+
+/// The current request's id
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(pub String);
+
+impl std::fmt::Display for RequestId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Use the inbound id if it is short and made of visible ASCII
+fn inbound_request_id(request: &Request) -> Option<String> {
+    let value = request
+        .headers()
+        .get(REQUEST_ID_HEADER)?
+        .to_str()
+        .ok()?
+        .trim();
+    let valid = !value.is_empty()
+        && value.len() <= MAX_REQUEST_ID_LEN
+        && value.bytes().all(|b| b.is_ascii_graphic());
+    valid.then(|| value.to_string())
+}
+
+/// Middleware assigning and propagating the request id
+pub async fn request_id_middleware(mut request: Request, next: Next) -> Response {
+    let id = inbound_request_id(&request).unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
+    request.extensions_mut().insert(RequestId(id.clone()));
+
+    let span = tracing::info_span!(
+        "request",
+        request_id = %id,
+        method = %request.method(),
+        path = %request.uri().path(),
+    );
+    let response = next.run(request).instrument(span).await;
+
+    let mut response = if is_json_error(&response) {
+        with_request_id_in_body(response, &id).await
+    } else {
+        response
+    };
+    if let Ok(value) = HeaderValue::from_str(&id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+    response
+}
+
+fn is_json_error(response: &Response) -> bool {
+    let status = response.status();
+    (status.is_client_error() || status.is_server_error())
+        && status != StatusCode::PAYMENT_REQUIRED
+        && response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.starts_with("application/json"))
+}
+
+/// Add `request_id` to a JSON object body, leaving any other body untouched
+async fn with_request_id_in_body(response: Response, id: &str) -> Response {
+    use axum::body::HttpBody;
+
+    // Only buffer bodies of known, small size (handler JSON always is)
+    let small = response
+        .body()
+        .size_hint()
+        .upper()
+        .is_some_and(|len| len <= MAX_ERROR_BODY_BYTES as u64);
+    if !small {
+        return response;
+    }
+    let (mut parts, body) = response.into_parts();
+    let bytes = match axum::body::to_bytes(body, MAX_ERROR_BODY_BYTES).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::warn!("failed to read error body: {}", e);
+            parts.headers.remove(header::CONTENT_LENGTH);
+            return Response::from_parts(parts, Body::empty());
+        }
+    };
+    let body = match serde_json::from_slice::<serde_json::Value>(&bytes) {
+        Ok(serde_json::Value::Object(mut object)) => {
+            object.insert("request_id".to_string(), id.into());
+            serde_json::to_vec(&object)
+                .map(Body::from)
+                .unwrap_or(Body::from(bytes))
+        }
+        _ => Body::from(bytes),
+    };
+    parts.headers.remove(header::CONTENT_LENGTH);
+    Response::from_parts(parts, body)
+}
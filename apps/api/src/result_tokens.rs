@@ -0,0 +1,142 @@
+//! Shareable receipts of paid premium verifications
+//!
+//! A successful `POST /api/v1/evidence/verify-premium` stores its result and
+//! answers with a token `<id>.<expires_ms>.<mac>`, where `mac` is the
+//! HMAC-SHA256 of `<id>.<expires_ms>` under `X402_RESULT_TOKEN_SECRET`.
+//! `GET /api/v1/verifications/{token}` hands the stored result out once,
+//! before the expiry. The MAC makes tokens unguessable and stops anyone
+//! moving their expiry.
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+
+/// How long a result token can be redeemed for
+pub const RESULT_TOKEN_TTL: Duration = Duration::from_secs(15 * 60);
+
+/// Why a result token was refused
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ResultTokenError {
+    #[error("invalid result token")]
+    Invalid,
+    #[error("result token expired")]
+    Expired,
+}
+
+/// Mints and checks result tokens
+#[derive(Clone)]
+pub struct ResultTokenSigner {
+    secret: Arc<[u8]>,
+}
+
+impl ResultTokenSigner {
+    pub fn new(secret: impl AsRef<[u8]>) -> Self {
+        Self {
+            secret: Arc::from(secret.as_ref()),
+        }
+    }
+
+    /// Random secret: tokens stop working when the process restarts
+    pub fn ephemeral() -> Self {
+        let secret = [uuid::Uuid::new_v4(), uuid::Uuid::new_v4()]
+            .iter()
+            .flat_map(|u| *u.as_bytes())
+            .collect::<Vec<u8>>();
+        Self::new(secret)
+    }
+
+    /// Signer keyed by `X402_RESULT_TOKEN_SECRET`, or an ephemeral one if
+    /// that is unset (tokens then only work against this instance)
+    pub fn from_env() -> Self {
+        match std::env::var("X402_RESULT_TOKEN_SECRET") {
+            Ok(secret) if !secret.trim().is_empty() => Self::new(secret.trim()),
+            _ => {
+                tracing::warn!(
+                    "X402_RESULT_TOKEN_SECRET not set; result tokens will not survive a restart"
+                );
+                Self::ephemeral()
+            }
+        }
+    }
+
+    fn mac(&self, id: &str, expires_ms: i64) -> Hmac<Sha256> {
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(&self.secret).expect("HMAC accepts keys of any length");
+        mac.update(format!("{}.{}", id, expires_ms).as_bytes());
+        mac
+    }
+
+    /// Token for the stored result `id`, redeemable until `expires_ms`
+    pub fn mint(&self, id: &str, expires_ms: i64) -> String {
+        let mac = hex::encode(self.mac(id, expires_ms).finalize().into_bytes());
+        format!("{}.{}.{}", id, expires_ms, mac)
+    }
+
+    /// The result id a token was minted for, if its MAC checks out and it
+    /// has not expired at `now_ms`
+    pub fn verify(&self, token: &str, now_ms: i64) -> Result<String, ResultTokenError> {
+        let mut parts = token.split('.');
+        let (Some(id), Some(expires_ms), Some(mac), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err(ResultTokenError::Invalid);
+        };
+        let expires_ms: i64 = expires_ms.parse().map_err(|_| ResultTokenError::Invalid)?;
+        let mac = hex::decode(mac).map_err(|_| ResultTokenError::Invalid)?;
+
+        self.mac(id, expires_ms)
+            .verify_slice(&mac)
+            .map_err(|_| ResultTokenError::Invalid)?;
+        if expires_ms <= now_ms {
+            return Err(ResultTokenError::Expired);
+        }
+        Ok(id.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_round_trip_and_tampering() {
+        let signer = ResultTokenSigner::new("secret");
+        let token = signer.mint("result-1", 2_000);
+        assert_eq!(signer.verify(&token, 1_000).unwrap(), "result-1");
+
+        // Expired
+        assert_eq!(signer.verify(&token, 2_000), Err(ResultTokenError::Expired));
+
+        // Extended expiry, other id, other key, garbage
+        let extended = token.replacen(".2000.", ".9000.", 1);
+        assert_eq!(
+            signer.verify(&extended, 1_000),
+            Err(ResultTokenError::Invalid)
+        );
+        let other_id = token.replacen("result-1", "result-2", 1);
+        assert_eq!(
+            signer.verify(&other_id, 1_000),
+            Err(ResultTokenError::Invalid)
+        );
+        assert_eq!(
+            ResultTokenSigner::new("other").verify(&token, 1_000),
+            Err(ResultTokenError::Invalid)
+        );
+        for bad in [
+            "",
+            "result-1",
+            "result-1.2000",
+            "result-1.soon.abcd",
+            &format!("{}.x", token),
+        ] {
+            assert_eq!(
+                signer.verify(bad, 1_000),
+                Err(ResultTokenError::Invalid),
+                "{:?}",
+                bad
+            );
+        }
+    }
+}
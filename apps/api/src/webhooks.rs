@@ -0,0 +1,222 @@
+//! Webhook notifications for payment and anchoring events
+//!
+//! Integrators can register a URL to be told when an x402 payment is verified
+//! (`payment.verified`) and when evidence is anchored (`evidence.anchored`)
+//! instead of polling. Each event is POSTed as JSON with an HMAC-SHA256 of the
+//! exact body bytes in the `X-Phoenix-Signature` header (`sha256=<hex>`), so
+//! receivers can authenticate it with the shared secret.
+//!
+//! Delivery is fire-and-forget: it runs on a spawned task with bounded
+//! retries and never blocks or fails the request that triggered it.
+//!
+//! # Configuration
+//!
+//! | Variable | Default | Description |
+//! |----------|---------|-------------|
+//! | `X402_WEBHOOK_URL` | unset | Endpoint to POST events to (unset disables webhooks) |
+//! | `X402_WEBHOOK_SECRET` | empty | HMAC-SHA256 key for the signature header |
+//! | `X402_WEBHOOK_MAX_ATTEMPTS` | `3` | Delivery attempts per event |
+//! | `X402_WEBHOOK_TIMEOUT_MS` | `5000` | Per-attempt request timeout |
+
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use sha2::Sha256;
+use std::time::Duration;
+
+/// Header carrying `sha256=<hex HMAC of the body>`
+pub const SIGNATURE_HEADER: &str = "X-Phoenix-Signature";
+/// Header carrying the event type
+pub const EVENT_HEADER: &str = "X-Phoenix-Event";
+
+/// Default delivery attempts per event
+pub const DEFAULT_MAX_ATTEMPTS: u32 = 3;
+/// Default per-attempt request timeout
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+/// Delay before the first retry, doubled on each subsequent one
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// Event types delivered to the webhook
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum WebhookEventType {
+    #[serde(rename = "payment.verified")]
+    PaymentVerified,
+    #[serde(rename = "evidence.anchored")]
+    EvidenceAnchored,
+}
+
+impl WebhookEventType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WebhookEventType::PaymentVerified => "payment.verified",
+            WebhookEventType::EvidenceAnchored => "evidence.anchored",
+        }
+    }
+}
+
+/// A single webhook event body
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookEvent {
+    /// Unique event id, for receiver-side deduplication of retries
+    pub id: String,
+    #[serde(rename = "type")]
+    pub event_type: WebhookEventType,
+    /// RFC 3339 creation time
+    pub created_at: String,
+    pub data: serde_json::Value,
+}
+
+impl WebhookEvent {
+    pub fn new(event_type: WebhookEventType, data: serde_json::Value) -> Self {
+        Self {
+            id: uuid::Uuid::new_v4().to_string(),
+            event_type,
+            created_at: chrono::Utc::now().to_rfc3339(),
+            data,
+        }
+    }
+}
+
+/// HMAC-SHA256 of `body` under `secret`, hex-encoded
+pub fn sign_payload(secret: &[u8], body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC accepts keys of any length");
+    mac.update(body);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Posts signed events to the configured webhook URL
+#[derive(Clone)]
+pub struct WebhookDispatcher {
+    url: String,
+    secret: Vec<u8>,
+    client: reqwest::Client,
+    max_attempts: u32,
+    retry_delay: Duration,
+}
+
+impl WebhookDispatcher {
+    pub fn new(url: impl Into<String>, secret: impl Into<Vec<u8>>) -> Self {
+        Self {
+            url: url.into(),
+            secret: secret.into(),
+            client: reqwest::Client::builder()
+                .timeout(DEFAULT_TIMEOUT)
+                .build()
+                .unwrap_or_default(),
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            retry_delay: RETRY_BASE_DELAY,
+        }
+    }
+
+    /// Build from environment, returning `None` unless `X402_WEBHOOK_URL` is set
+    pub fn from_env() -> Option<Self> {
+        let url = std::env::var("X402_WEBHOOK_URL")
+            .ok()
+            .filter(|u| !u.trim().is_empty())?;
+        let secret = std::env::var("X402_WEBHOOK_SECRET").unwrap_or_default();
+        if secret.is_empty() {
+            tracing::warn!("X402_WEBHOOK_SECRET is not set, webhook signatures use an empty key");
+        }
+        let mut dispatcher = Self::new(url.trim(), secret);
+        if let Some(attempts) = std::env::var("X402_WEBHOOK_MAX_ATTEMPTS")
+            .ok()
+            .and_then(|v| v.trim().parse::<u32>().ok())
+        {
+            dispatcher = dispatcher.with_max_attempts(attempts);
+        }
+        if let Some(timeout) = std::env::var("X402_WEBHOOK_TIMEOUT_MS")
+            .ok()
+            .and_then(|v| v.trim().parse::<u64>().ok())
+            .map(Duration::from_millis)
+        {
+            dispatcher = dispatcher.with_timeout(timeout);
+        }
+        Some(dispatcher)
+    }
+
+    /// Delivery attempts per event (at least one)
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Delay before the first retry; later retries double it
+    pub fn with_retry_delay(mut self, delay: Duration) -> Self {
+        self.retry_delay = delay;
+        self
+    }
+
+    /// Per-attempt request timeout
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        if let Ok(client) = reqwest::Client::builder().timeout(timeout).build() {
+            self.client = client;
+        }
+        self
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Queue an event for delivery without waiting for it
+    ///
+    /// The returned handle resolves to whether the receiver accepted the event
+    /// and may simply be dropped.
+    pub fn dispatch(&self, event: WebhookEvent) -> tokio::task::JoinHandle<bool> {
+        let dispatcher = self.clone();
+        tokio::spawn(async move { dispatcher.deliver(&event).await })
+    }
+
+    /// Deliver an event, retrying on transport errors and non-2xx responses
+    pub async fn deliver(&self, event: &WebhookEvent) -> bool {
+        let body = match serde_json::to_vec(event) {
+            Ok(body) => body,
+            Err(e) => {
+                tracing::error!(event_id = %event.id, "failed to serialize webhook event: {}", e);
+                return false;
+            }
+        };
+        let signature = format!("sha256={}", sign_payload(&self.secret, &body));
+
+        let mut delay = self.retry_delay;
+        for attempt in 1..=self.max_attempts {
+            let result = self
+                .client
+                .post(&self.url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .header(EVENT_HEADER, event.event_type.as_str())
+                .header(SIGNATURE_HEADER, &signature)
+                .body(body.clone())
+                .send()
+                .await;
+            match result {
+                Ok(response) if response.status().is_success() => {
+                    tracing::debug!(event_id = %event.id, attempt, "webhook delivered");
+                    return true;
+                }
+                Ok(response) => tracing::warn!(
+                    event_id = %event.id,
+                    attempt,
+                    status = %response.status(),
+                    "webhook rejected"
+                ),
+                Err(e) => tracing::warn!(
+                    event_id = %event.id,
+                    attempt,
+                    "webhook delivery failed: {}",
+                    e
+                ),
+            }
+            if attempt < self.max_attempts {
+                tokio::time::sleep(delay).await;
+                delay = delay.saturating_mul(2);
+            }
+        }
+        tracing::error!(
+            event_id = %event.id,
+            event_type = event.event_type.as_str(),
+            "webhook dropped after {} attempts",
+            self.max_attempts
+        );
+        false
+    }
+}
@@ -104,3 +104,29 @@ async fn test_build_app_with_default_url() {
         None => std::env::remove_var("KEEPER_DB_URL"),
     }
 }
+
+#[tokio::test]
+async fn test_sync_anchor_provider_spec() {
+    let _guard = TEST_MUTEX.lock().await;
+    use phoenix_api::anchoring::SyncAnchor;
+
+    std::env::set_var("API_SYNC_ANCHOR_ENABLED", "true");
+
+    // Unset and the legacy `stub` spelling both select the Etherlink stub
+    std::env::remove_var("API_SYNC_ANCHOR_PROVIDER");
+    assert!(SyncAnchor::from_env().unwrap().is_some());
+    std::env::set_var("API_SYNC_ANCHOR_PROVIDER", "stub");
+    assert!(SyncAnchor::from_env().unwrap().is_some());
+    std::env::set_var("API_SYNC_ANCHOR_PROVIDER", "solana-stub");
+    assert!(SyncAnchor::from_env().unwrap().is_some());
+
+    // A typo fails startup instead of falling back to the stub
+    std::env::set_var("API_SYNC_ANCHOR_PROVIDER", "etherlnik");
+    assert!(SyncAnchor::from_env().is_err());
+    std::env::set_var("API_DB_URL", "sqlite::memory:");
+    assert!(build_app().await.is_err());
+
+    std::env::remove_var("API_SYNC_ANCHOR_PROVIDER");
+    std::env::remove_var("API_SYNC_ANCHOR_ENABLED");
+    std::env::remove_var("API_DB_URL");
+}
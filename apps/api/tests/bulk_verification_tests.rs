@@ -0,0 +1,152 @@
+//! Bulk premium verification: one payment for many evidence records
+
+mod common;
+
+use phoenix_api::{
+    db::create_evidence_job, handlers_x402::X402State, models::EvidenceIn, AppState,
+};
+use phoenix_x402::{PaymentProof, MAX_BULK_EVIDENCE_IDS};
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+const WALLET: &str = "PhxRvkTreasury111111111111111111111111111111";
+const TOKEN: &str = "test-api-token";
+const SIGNATURE: &str =
+    "4QiBAXV86WdR45GLrTqScdVE9ivjtGWPghWEboaN4B9kYgf5X8GNgJZkNaLNGoQ6s3z7bqtD3rrb95aCzWFLAXPq";
+
+async fn spawn_bulk_server() -> (tokio::task::JoinHandle<()>, u16, sqlx::Pool<sqlx::Sqlite>) {
+    let (_app, pool) = phoenix_api::build_app().await.unwrap();
+    let state = AppState {
+        x402: Some(X402State::devnet(WALLET, &pool)),
+        ..common::test_state(&pool)
+    };
+
+    let (listener, _) = common::create_test_listener();
+    let (server, port) = common::spawn_test_server(phoenix_api::router(state), listener).await;
+    (server, port, pool)
+}
+
+/// Each request comes from its own client IP, so the bulk tier's per-IP rate
+/// limit doesn't interfere
+async fn post_bulk(port: u16, body: &Value, payment: Option<&PaymentProof>) -> reqwest::Response {
+    static NEXT_IP: AtomicU32 = AtomicU32::new(1);
+    let ip = NEXT_IP.fetch_add(1, Ordering::Relaxed);
+    let mut request = reqwest::Client::new()
+        .post(format!(
+            "http://127.0.0.1:{}/api/v1/evidence/verify-premium-bulk",
+            port
+        ))
+        .bearer_auth(TOKEN)
+        .header("x-forwarded-for", format!("10.0.9.{}", ip))
+        .json(body);
+    if let Some(proof) = payment {
+        request = request.header("x-payment", proof.to_header().unwrap());
+    }
+    request.send().await.unwrap()
+}
+
+#[tokio::test]
+async fn test_one_payment_covers_every_evidence_id() {
+    common::with_api_db_env(|| async {
+        let (server, port, pool) = spawn_bulk_server().await;
+        let ids = ["bulk-ev-1", "bulk-ev-2", "bulk-ev-3"];
+        for (i, id) in ids.iter().enumerate() {
+            let evidence = EvidenceIn {
+                id: Some(id.to_string()),
+                digest_hex: format!("{:0>64}", i + 1),
+                payload_mime: None,
+                metadata: None,
+                anchor_mode: None,
+                source: None,
+                priority: None,
+                payload_base64: None,
+            };
+            create_evidence_job(&pool, &evidence, "test").await.unwrap();
+        }
+        let body =
+            json!({ "evidence_ids": ["bulk-ev-1", "bulk-ev-2", "bulk-missing", "bulk-ev-3"] });
+
+        // Quoted at the bulk price for four items: $0.05 base + 4 x $0.005
+        let response = post_bulk(port, &body, None).await;
+        assert_eq!(response.status(), StatusCode::PAYMENT_REQUIRED);
+        let quote: Value = response.json().await.unwrap();
+        assert_eq!(quote["price"], "0.07");
+        assert_eq!(quote["tier"], "bulk");
+        let memo = quote["memo"].as_str().unwrap().to_string();
+        assert!(memo.starts_with("evidence:bulk-"));
+
+        let proof = PaymentProof {
+            signature: SIGNATURE.to_string(),
+            amount: "0.07".to_string(),
+            token: "USDC".to_string(),
+            mint: None,
+            sender: "gsGBZpMXkp6VsXpe6t81fa2SAnKKkeVBZ8mucAAy7qb".to_string(),
+            memo,
+            expires_at: quote["expires_at"].as_str().map(str::to_string),
+            quote_signature: quote["quote_signature"].as_str().map(str::to_string),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        };
+        let response = post_bulk(port, &body, Some(&proof)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let result: Value = response.json().await.unwrap();
+        assert_eq!(result["verified_count"], 3);
+        assert_eq!(result["not_found_count"], 1);
+        let results = result["results"].as_array().unwrap();
+        let order: Vec<&str> = results
+            .iter()
+            .map(|r| r["evidence_id"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            order,
+            ["bulk-ev-1", "bulk-ev-2", "bulk-missing", "bulk-ev-3"]
+        );
+        assert_eq!(results[0]["verified"], true);
+        assert_eq!(
+            results[0]["verification"]["digest"]["hex"],
+            format!("{:0>64}", 1)
+        );
+        assert_eq!(results[2]["verified"], false);
+        assert_eq!(results[2]["error"], "Evidence not found");
+
+        // One receipt for the whole batch, not flagged for refund
+        let receipt = phoenix_api::db::get_payment_receipt_by_signature(&pool, SIGNATURE)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(receipt.tier, "bulk");
+        assert_eq!(receipt.amount_usdc, "0.07");
+        assert!(receipt.refund_status.is_none());
+
+        // The payment is redeemed once
+        let response = post_bulk(port, &body, Some(&proof)).await;
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_bulk_batch_over_cap_is_refused() {
+    common::with_api_db_env(|| async {
+        let (server, port, _pool) = spawn_bulk_server().await;
+
+        let ids: Vec<String> = (0..=MAX_BULK_EVIDENCE_IDS)
+            .map(|i| format!("bulk-over-{}", i))
+            .collect();
+        let response = post_bulk(port, &json!({ "evidence_ids": ids }), None).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body: Value = response.json().await.unwrap();
+        assert!(body["details"].as_str().unwrap().contains("limit of 500"));
+
+        // Duplicates and empty batches are refused too
+        for evidence_ids in [json!(["bulk-a", "bulk-a"]), json!([])] {
+            let response = post_bulk(port, &json!({ "evidence_ids": evidence_ids }), None).await;
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        }
+
+        server.abort();
+    })
+    .await;
+}
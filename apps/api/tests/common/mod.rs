@@ -62,6 +62,8 @@
 
 use axum::{serve, Router};
 use once_cell::sync::Lazy;
+use phoenix_api::{rate_limit::X402RateLimiter, AppState};
+use sqlx::{Pool, Sqlite};
 use std::net::TcpListener as StdTcpListener;
 use std::time::Duration;
 use tokio::net::TcpListener;
@@ -146,6 +148,27 @@ pub fn assert_json_response(json: &serde_json::Value, expected_values: &[(&str,
     }
 }
 
+/// App state over `pool` with every optional capability disabled
+///
+/// Override what a test needs with struct update syntax:
+/// `AppState { admin_token: Some(token), ..common::test_state(&pool) }`
+#[allow(dead_code)]
+pub fn test_state(pool: &Pool<Sqlite>) -> AppState {
+    AppState {
+        pool: pool.clone(),
+        x402: None,
+        rate_limiter: X402RateLimiter::new(),
+        sync_anchor: None,
+        payment_verifier: None,
+        admin_token: None,
+        require_confirmed_proofs: false,
+        webhooks: None,
+        detections: Default::default(),
+        idempotency_ttl: phoenix_api::DEFAULT_IDEMPOTENCY_TTL,
+        payloads: None,
+    }
+}
+
 /// Sets up environment for API database tests with in-memory SQLite
 #[allow(dead_code)]
 pub async fn with_api_db_env<F, Fut>(f: F)
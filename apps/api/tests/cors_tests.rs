@@ -0,0 +1,102 @@
+//! CORS headers for configured browser origins
+
+mod common;
+
+use phoenix_api::{build_app, cors::ALLOWED_ORIGINS_ENV};
+use reqwest::{Method, StatusCode};
+
+const ALLOWED: &str = "http://localhost:3000";
+
+#[tokio::test]
+async fn test_cors_allows_only_configured_origins() {
+    common::with_api_db_env(|| async {
+        // Still under the env lock held by with_api_db_env
+        std::env::set_var(
+            ALLOWED_ORIGINS_ENV,
+            format!("{},https://app.example.com", ALLOWED),
+        );
+        let built = build_app().await;
+        std::env::remove_var(ALLOWED_ORIGINS_ENV);
+        let (app, _pool) = built.unwrap();
+        let (listener, port) = common::create_test_listener();
+        let (server, _) = common::spawn_test_server(app, listener).await;
+        let client = reqwest::Client::new();
+        let url = |path: &str| format!("http://127.0.0.1:{}{}", port, path);
+
+        let response = client
+            .get(url("/health"))
+            .header("Origin", ALLOWED)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["access-control-allow-origin"], ALLOWED);
+
+        let response = client
+            .get(url("/health"))
+            .header("Origin", "https://evil.example")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(!response
+            .headers()
+            .contains_key("access-control-allow-origin"));
+
+        // Preflight for a browser POST with a bearer token
+        let response = client
+            .request(Method::OPTIONS, url("/evidence"))
+            .header("Origin", ALLOWED)
+            .header("Access-Control-Request-Method", "POST")
+            .header(
+                "Access-Control-Request-Headers",
+                "authorization,content-type",
+            )
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.headers()["access-control-allow-origin"], ALLOWED);
+        let allowed_headers = response.headers()["access-control-allow-headers"]
+            .to_str()
+            .unwrap()
+            .to_lowercase();
+        assert!(allowed_headers.contains("authorization"));
+
+        // Premium verification stays machine-to-machine even for allowed origins
+        let response = client
+            .request(Method::OPTIONS, url("/api/v1/evidence/verify-premium"))
+            .header("Origin", ALLOWED)
+            .header("Access-Control-Request-Method", "POST")
+            .send()
+            .await
+            .unwrap();
+        assert!(!response
+            .headers()
+            .contains_key("access-control-allow-origin"));
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_cors_disabled_by_default() {
+    common::with_api_db_env(|| async {
+        let (app, _pool) = build_app().await.unwrap();
+        let (listener, port) = common::create_test_listener();
+        let (server, _) = common::spawn_test_server(app, listener).await;
+
+        let response = reqwest::Client::new()
+            .get(format!("http://127.0.0.1:{}/health", port))
+            .header("Origin", ALLOWED)
+            .send()
+            .await
+            .unwrap();
+        assert!(!response
+            .headers()
+            .contains_key("access-control-allow-origin"));
+
+        server.abort();
+    })
+    .await;
+}
@@ -0,0 +1,216 @@
+//! Integration tests for the admin dead-letter and job retry endpoints
+
+mod common;
+
+use phoenix_api::AppState;
+use reqwest::StatusCode;
+use serde_json::Value;
+
+const ADMIN_TOKEN: &str = "test-admin-token";
+
+async fn spawn_admin_server() -> (tokio::task::JoinHandle<()>, u16, sqlx::Pool<sqlx::Sqlite>) {
+    let (_app, pool) = phoenix_api::build_app().await.unwrap();
+    let state = AppState {
+        admin_token: Some(ADMIN_TOKEN.to_string()),
+        ..common::test_state(&pool)
+    };
+
+    let (listener, _) = common::create_test_listener();
+    let (server, port) = common::spawn_test_server(phoenix_api::router(state), listener).await;
+    (server, port, pool)
+}
+
+async fn insert_job(pool: &sqlx::Pool<sqlx::Sqlite>, id: &str, status: &str) {
+    sqlx::query(
+        "INSERT INTO outbox_jobs (id, payload_sha256, status, attempts, last_error, created_ms, updated_ms, next_attempt_ms)
+         VALUES (?1, ?2, ?3, 10, 'rpc timeout', 1000, 2000, 5000)",
+    )
+    .bind(id)
+    .bind("ab".repeat(32))
+    .bind(status)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_list_and_replay_dead_letters() {
+    common::with_api_db_env(|| async {
+        let (server, port, pool) = spawn_admin_server().await;
+        insert_job(&pool, "dl-api-dead", "dead_letter").await;
+        insert_job(&pool, "dl-api-queued", "queued").await;
+        let client = reqwest::Client::new();
+
+        let body: Value = client
+            .get(format!("http://127.0.0.1:{}/admin/dead-letters", port))
+            .bearer_auth(ADMIN_TOKEN)
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        let ids: Vec<&str> = body["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|job| job["id"].as_str().unwrap())
+            .collect();
+        assert!(ids.contains(&"dl-api-dead"));
+        assert!(!ids.contains(&"dl-api-queued"));
+        let dead = body["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|job| job["id"] == "dl-api-dead")
+            .unwrap();
+        assert_eq!(dead["last_error"], "rpc timeout");
+        assert_eq!(dead["attempts"], 10);
+
+        let replay_url = format!(
+            "http://127.0.0.1:{}/admin/dead-letters/dl-api-dead/replay",
+            port
+        );
+        let res = client
+            .post(&replay_url)
+            .bearer_auth(ADMIN_TOKEN)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+
+        let (status, attempts, next_attempt_ms): (String, i64, i64) = sqlx::query_as(
+            "SELECT status, attempts, next_attempt_ms FROM outbox_jobs WHERE id = 'dl-api-dead'",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(status, "queued");
+        assert_eq!(attempts, 0);
+        assert_eq!(next_attempt_ms, 0);
+
+        // Replaying again, or replaying a live job, is a 404
+        let res = client
+            .post(&replay_url)
+            .bearer_auth(ADMIN_TOKEN)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+        let res = client
+            .post(format!(
+                "http://127.0.0.1:{}/admin/dead-letters/dl-api-queued/replay",
+                port
+            ))
+            .bearer_auth(ADMIN_TOKEN)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+        server.abort();
+    })
+    .await;
+}
+
+async fn job_state(pool: &sqlx::Pool<sqlx::Sqlite>, id: &str) -> (String, i64, i64) {
+    sqlx::query_as("SELECT status, attempts, next_attempt_ms FROM outbox_jobs WHERE id = ?1")
+        .bind(id)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_retry_failed_job() {
+    common::with_api_db_env(|| async {
+        let (server, port, pool) = spawn_admin_server().await;
+        insert_job(&pool, "retry-api-failed", "failed").await;
+        insert_job(&pool, "retry-api-dead", "dead_letter").await;
+        insert_job(&pool, "retry-api-done", "done").await;
+        let client = reqwest::Client::new();
+        let retry = |id: &str, query: &str| {
+            client
+                .post(format!(
+                    "http://127.0.0.1:{}/admin/jobs/{}/retry{}",
+                    port, id, query
+                ))
+                .bearer_auth(ADMIN_TOKEN)
+                .send()
+        };
+
+        // Failed job is due again with its attempts kept
+        let res = retry("retry-api-failed", "").await.unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        let body: Value = res.json().await.unwrap();
+        assert_eq!(body["status"], "queued");
+        assert_eq!(
+            job_state(&pool, "retry-api-failed").await,
+            ("queued".to_string(), 10, 0)
+        );
+
+        // Dead-lettered job with a fresh attempt budget
+        let res = retry("retry-api-dead", "?reset_attempts=true")
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::OK);
+        assert_eq!(
+            job_state(&pool, "retry-api-dead").await,
+            ("queued".to_string(), 0, 0)
+        );
+
+        // Done, or already requeued, is a conflict
+        let res = retry("retry-api-done", "").await.unwrap();
+        assert_eq!(res.status(), StatusCode::CONFLICT);
+        let body: Value = res.json().await.unwrap();
+        assert!(body["error"].as_str().unwrap().contains("job is done"));
+        assert_eq!(job_state(&pool, "retry-api-done").await.0, "done");
+        let res = retry("retry-api-failed", "").await.unwrap();
+        assert_eq!(res.status(), StatusCode::CONFLICT);
+
+        let res = retry("retry-api-missing", "").await.unwrap();
+        assert_eq!(res.status(), StatusCode::NOT_FOUND);
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_dead_letters_require_admin_token() {
+    common::with_api_db_env(|| async {
+        let (server, port, _pool) = spawn_admin_server().await;
+        let client = reqwest::Client::new();
+
+        let res = client
+            .get(format!("http://127.0.0.1:{}/admin/dead-letters", port))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+        let res = client
+            .post(format!(
+                "http://127.0.0.1:{}/admin/dead-letters/anything/replay",
+                port
+            ))
+            .bearer_auth("wrong-token")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+        let res = client
+            .post(format!(
+                "http://127.0.0.1:{}/admin/jobs/anything/retry",
+                port
+            ))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(res.status(), StatusCode::UNAUTHORIZED);
+
+        server.abort();
+    })
+    .await;
+}
@@ -0,0 +1,162 @@
+//! `POST /detections`: evidence jobs for detector events and their delivery
+//! to `/ws/detections` subscribers
+
+mod common;
+
+use futures_util::StreamExt;
+use phoenix_api::build_app;
+use phoenix_evidence::hash::sha256_canonical_json;
+use serde_json::{json, Value};
+use sqlx::Row;
+use std::time::Duration;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+fn detection(source_id: &str) -> Value {
+    json!({
+        "event": "drone_detected",
+        "timestamp": "2026-01-01T00:00:00Z",
+        "frameNumber": 42,
+        "sourceId": source_id,
+        "detection": {
+            "classId": 0,
+            "className": "drone",
+            "confidence": 0.91,
+            "bbox": [10.0, 20.0, 30.0, 40.0],
+            "droneScore": 0.88,
+            "trackId": 7,
+            "isDrone": true
+        }
+    })
+}
+
+#[tokio::test]
+async fn test_posted_detection_is_streamed_to_ws_client() {
+    common::with_api_db_env(|| async {
+        let (app, _pool) = build_app().await.unwrap();
+        let (listener, port) = common::create_test_listener();
+        let (server, _) = common::spawn_test_server(app, listener).await;
+
+        let (mut socket, _) = connect_async(format!("ws://127.0.0.1:{}/ws/detections", port))
+            .await
+            .unwrap();
+
+        let response = reqwest::Client::new()
+            .post(format!("http://127.0.0.1:{}/detections", port))
+            .json(&detection("edge-node-7"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 202);
+        let body: Value = response.json().await.unwrap();
+        assert_eq!(body["subscribers"], 1);
+
+        let message = tokio::time::timeout(Duration::from_secs(5), socket.next())
+            .await
+            .expect("no detection streamed")
+            .unwrap()
+            .unwrap();
+        let Message::Text(text) = message else {
+            panic!("expected a text frame, got {:?}", message);
+        };
+        let event: Value = serde_json::from_str(&text).unwrap();
+        assert_eq!(event["sourceId"], "edge-node-7");
+        assert_eq!(event["frameNumber"], 42);
+        assert_eq!(event["detection"]["className"], "drone");
+        assert_eq!(event["detection"]["isDrone"], true);
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_detection_without_subscribers_is_accepted() {
+    common::with_api_db_env(|| async {
+        let (app, _pool) = build_app().await.unwrap();
+        let (listener, port) = common::create_test_listener();
+        let (server, _) = common::spawn_test_server(app, listener).await;
+
+        let response = reqwest::Client::new()
+            .post(format!("http://127.0.0.1:{}/detections", port))
+            .json(&detection("edge-node-8"))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 202);
+        let body: Value = response.json().await.unwrap();
+        assert_eq!(body["subscribers"], 0);
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_posted_detection_queues_evidence_job() {
+    common::with_api_db_env(|| async {
+        let (app, pool) = build_app().await.unwrap();
+        let (listener, port) = common::create_test_listener();
+        let (server, _) = common::spawn_test_server(app, listener).await;
+
+        let event = detection("edge-node-9");
+        let response = reqwest::Client::new()
+            .post(format!("http://127.0.0.1:{}/detections", port))
+            .json(&event)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 202);
+        let body: Value = response.json().await.unwrap();
+        let id = body["id"].as_str().unwrap();
+
+        // Digest is over the canonical JSON of the event as sent
+        let expected_digest = sha256_canonical_json(&event);
+        assert_eq!(body["digest_hex"], expected_digest);
+
+        let row = sqlx::query(
+            "SELECT payload_sha256, status, source, metadata FROM outbox_jobs WHERE id = ?1",
+        )
+        .bind(id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(row.get::<String, _>("payload_sha256"), expected_digest);
+        assert_eq!(row.get::<String, _>("status"), "queued");
+        assert_eq!(row.get::<String, _>("source"), "detector");
+
+        let metadata: Value = serde_json::from_str(&row.get::<String, _>("metadata")).unwrap();
+        assert_eq!(metadata["class_name"], "drone");
+        assert_eq!(metadata["source_id"], "edge-node-9");
+        let confidence = metadata["confidence"].as_f64().unwrap();
+        assert!((confidence - 0.91).abs() < 1e-6);
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_malformed_detection_is_rejected() {
+    common::with_api_db_env(|| async {
+        let (app, pool) = build_app().await.unwrap();
+        let (listener, port) = common::create_test_listener();
+        let (server, _) = common::spawn_test_server(app, listener).await;
+
+        let response = reqwest::Client::new()
+            .post(format!("http://127.0.0.1:{}/detections", port))
+            .json(&json!({ "event": "drone_detected", "sourceId": "edge-node-10" }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 400);
+
+        let count: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM outbox_jobs")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(count, 0);
+
+        server.abort();
+    })
+    .await;
+}
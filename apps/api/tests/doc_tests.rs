@@ -125,12 +125,16 @@ async fn example_evidence_repository_operations() {
     // Create evidence job
     let evidence = EvidenceIn {
         id: Some("doc-test-123".to_string()),
-        digest_hex: "abcd1234efgh5678".to_string(),
+        digest_hex: "ab".repeat(32),
         payload_mime: Some("application/json".to_string()),
         metadata: Some(serde_json::json!({
             "source": "documentation_test",
             "priority": "high"
         })),
+        anchor_mode: None,
+        source: None,
+        priority: None,
+        payload_base64: None,
     };
 
     let job_id = repo.create_evidence_job(&evidence).await.unwrap();
@@ -175,9 +179,13 @@ async fn example_error_handling() {
     // Test duplicate job creation
     let evidence = EvidenceIn {
         id: Some("duplicate-test".to_string()),
-        digest_hex: "abcd1234".to_string(),
+        digest_hex: "cd".repeat(32),
         payload_mime: None,
         metadata: None,
+        anchor_mode: None,
+        source: None,
+        priority: None,
+        payload_base64: None,
     };
 
     // First creation should succeed
@@ -220,9 +228,13 @@ async fn example_pagination_and_statistics() {
     for i in 0..5 {
         let evidence = EvidenceIn {
             id: Some(format!("pagination-test-{}", i)),
-            digest_hex: format!("hash{}", i),
+            digest_hex: format!("{:064x}", i),
             payload_mime: None,
             metadata: None,
+            anchor_mode: None,
+            source: None,
+            priority: None,
+            payload_base64: None,
         };
         repo.create_evidence_job(&evidence).await.unwrap();
     }
@@ -267,9 +279,13 @@ async fn example_job_processing_workflow() {
     for i in 0..3 {
         let evidence = EvidenceIn {
             id: Some(format!("workflow-test-{}", i)),
-            digest_hex: format!("workflow-hash{}", i),
+            digest_hex: format!("{:064x}", i),
             payload_mime: None,
             metadata: None,
+            anchor_mode: None,
+            source: None,
+            priority: None,
+            payload_base64: None,
         };
         repo.create_evidence_job(&evidence).await.unwrap();
     }
@@ -1,9 +1,59 @@
 mod common;
 
-use phoenix_api::build_app;
+use async_trait::async_trait;
+use phoenix_api::db::{claim_idempotency_key, IdempotencyClaim};
+use phoenix_api::{anchoring::SyncAnchor, build_app, build_app_with_sync_anchor};
+use phoenix_evidence::anchor::{AnchorError, AnchorProvider};
+use phoenix_evidence::model::{ChainTxRef, EvidenceRecord};
+use phoenix_keeper::{ensure_schema, reclaim_stale_jobs, JobProvider, SqliteJobProvider};
 use reqwest::Client;
 use serde_json::json;
 use sqlx::Row;
+use std::{sync::Arc, time::Duration};
+
+/// Anchor provider that answers after a fixed delay
+struct DelayedAnchor {
+    delay: Duration,
+}
+
+#[async_trait]
+impl AnchorProvider for DelayedAnchor {
+    async fn anchor(&self, evidence: &EvidenceRecord) -> Result<ChainTxRef, AnchorError> {
+        tokio::time::sleep(self.delay).await;
+        Ok(ChainTxRef {
+            network: "etherlink".to_string(),
+            chain: "testnet".to_string(),
+            tx_id: format!("0x{}", evidence.digest.hex),
+            confirmed: false,
+            timestamp: Some(chrono::Utc::now()),
+        })
+    }
+
+    async fn confirm(&self, tx: &ChainTxRef) -> Result<ChainTxRef, AnchorError> {
+        Ok(tx.clone())
+    }
+}
+
+/// Anchor provider whose chain rejects every payload as too large
+struct OversizedAnchor;
+
+#[async_trait]
+impl AnchorProvider for OversizedAnchor {
+    async fn anchor(&self, _evidence: &EvidenceRecord) -> Result<ChainTxRef, AnchorError> {
+        Err(AnchorError::PayloadTooLarge {
+            size: 2048,
+            limit: 566,
+        })
+    }
+
+    async fn confirm(&self, tx: &ChainTxRef) -> Result<ChainTxRef, AnchorError> {
+        Ok(tx.clone())
+    }
+}
+
+fn sync_anchor(delay: Duration, timeout: Duration) -> SyncAnchor {
+    SyncAnchor::new(Arc::new(DelayedAnchor { delay }), timeout)
+}
 
 #[tokio::test]
 async fn test_health_endpoint() {
@@ -120,6 +170,278 @@ async fn test_post_evidence_with_custom_id() {
     .await;
 }
 
+#[tokio::test]
+async fn test_post_evidence_duplicate_id_returns_409() {
+    common::with_api_db_env(|| async {
+        let (app, _pool) = build_app().await.unwrap();
+        let (listener, port) = common::create_test_listener();
+        let (server, _) = common::spawn_test_server(app, listener).await;
+
+        let client = Client::new();
+        let evidence_payload = json!({
+            "id": "duplicate-evidence-409",
+            "digest_hex": "ab".repeat(32)
+        });
+        let post = || {
+            client
+                .post(format!("http://127.0.0.1:{}/evidence", port))
+                .json(&evidence_payload)
+                .send()
+        };
+
+        assert_eq!(post().await.unwrap().status(), 200);
+
+        let response = post().await.unwrap();
+        assert_eq!(response.status(), 409);
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(body["error"], "evidence with this ID already exists");
+        assert_eq!(body["details"]["id"], "duplicate-evidence-409");
+        assert_eq!(body["id"], "duplicate-evidence-409");
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_post_evidence_idempotency_key_replays_original_job() {
+    common::with_api_db_env(|| async {
+        let (app, pool) = build_app().await.unwrap();
+        let (listener, port) = common::create_test_listener();
+        let (server, _) = common::spawn_test_server(app, listener).await;
+
+        let client = Client::new();
+        let digest = "1d".repeat(32);
+        let post = |key: &'static str| {
+            client
+                .post(format!("http://127.0.0.1:{}/evidence", port))
+                .header("Idempotency-Key", key)
+                .json(&json!({ "digest_hex": digest }))
+                .send()
+        };
+
+        let first = post("retry-key-1").await.unwrap();
+        assert_eq!(first.status(), 200);
+        assert!(first.headers().get("idempotent-replayed").is_none());
+        let first: serde_json::Value = first.json().await.unwrap();
+
+        let replay = post("retry-key-1").await.unwrap();
+        assert_eq!(replay.status(), 200);
+        assert_eq!(replay.headers()["idempotent-replayed"], "true");
+        let replay: serde_json::Value = replay.json().await.unwrap();
+        assert_eq!(replay["id"], first["id"]);
+        assert_eq!(replay["status"], "queued");
+
+        let other: serde_json::Value = post("retry-key-2").await.unwrap().json().await.unwrap();
+        assert_ne!(other["id"], first["id"]);
+
+        let jobs: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM outbox_jobs WHERE payload_sha256 = ?1")
+                .bind(&digest)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(jobs, 2);
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_post_evidence_idempotency_key_rejects_different_digest() {
+    common::with_api_db_env(|| async {
+        let (app, _pool) = build_app().await.unwrap();
+        let (listener, port) = common::create_test_listener();
+        let (server, _) = common::spawn_test_server(app, listener).await;
+
+        let client = Client::new();
+        let post = |digest: String| {
+            client
+                .post(format!("http://127.0.0.1:{}/evidence", port))
+                .header("Idempotency-Key", "reused-key")
+                .json(&json!({ "digest_hex": digest }))
+                .send()
+        };
+
+        assert_eq!(post("2d".repeat(32)).await.unwrap().status(), 200);
+        assert_eq!(post("3d".repeat(32)).await.unwrap().status(), 409);
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_expired_idempotency_key_can_be_claimed_again() {
+    common::with_api_db_env(|| async {
+        let (_app, pool) = build_app().await.unwrap();
+        let digest = "4d".repeat(32);
+
+        let claim = |job_id: &'static str, ttl_ms: i64| {
+            claim_idempotency_key(&pool, "expiring-key", job_id, &digest, ttl_ms)
+        };
+        assert_eq!(claim("job-a", 0).await.unwrap(), IdempotencyClaim::Claimed);
+        assert_eq!(
+            claim("job-b", 60_000).await.unwrap(),
+            IdempotencyClaim::Claimed
+        );
+        assert_eq!(
+            claim("job-c", 60_000).await.unwrap(),
+            IdempotencyClaim::Existing {
+                job_id: "job-b".to_string(),
+                digest_hex: digest.clone(),
+            }
+        );
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_post_evidence_malformed_digest_returns_400() {
+    common::with_api_db_env(|| async {
+        let (app, pool) = build_app().await.unwrap();
+        let (listener, port) = common::create_test_listener();
+        let (server, _) = common::spawn_test_server(app, listener).await;
+
+        let response = Client::new()
+            .post(format!("http://127.0.0.1:{}/evidence", port))
+            .json(&json!({
+                "id": "malformed-digest-400",
+                "digest_hex": "not-a-hex-digest"
+            }))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), 400);
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert!(body["error"].as_str().unwrap().contains("digest_hex"));
+        assert!(body.get("details").is_some());
+
+        // Nothing reached the outbox
+        let row = sqlx::query("SELECT id FROM outbox_jobs WHERE id = ?")
+            .bind("malformed-digest-400")
+            .fetch_optional(&pool)
+            .await
+            .unwrap();
+        assert!(row.is_none());
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_post_evidence_uppercase_digest_is_lowercased() {
+    common::with_api_db_env(|| async {
+        let (app, pool) = build_app().await.unwrap();
+        let (listener, port) = common::create_test_listener();
+        let (server, _) = common::spawn_test_server(app, listener).await;
+
+        let response = Client::new()
+            .post(format!("http://127.0.0.1:{}/evidence", port))
+            .json(&json!({
+                "id": "uppercase-digest",
+                "digest_hex": "DEADBEEFCAFEBABE1234567890ABCDEF1234567890ABCDEF1234567890ABCDEF"
+            }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+
+        let row = sqlx::query("SELECT payload_sha256 FROM outbox_jobs WHERE id = ?")
+            .bind("uppercase-digest")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(
+            row.get::<String, _>("payload_sha256"),
+            "deadbeefcafebabe1234567890abcdef1234567890abcdef1234567890abcdef"
+        );
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_post_evidence_short_digest_returns_400() {
+    common::with_api_db_env(|| async {
+        let (app, _pool) = build_app().await.unwrap();
+        let (listener, port) = common::create_test_listener();
+        let (server, _) = common::spawn_test_server(app, listener).await;
+
+        // Valid hex, but only 4 bytes
+        let response = Client::new()
+            .post(format!("http://127.0.0.1:{}/evidence", port))
+            .json(&json!({
+                "id": "short-digest",
+                "digest_hex": "deadbeef"
+            }))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), 400);
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert!(body["error"]
+            .as_str()
+            .unwrap()
+            .contains("64 hex characters"));
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_post_evidence_non_sha256_digest_algo_returns_400() {
+    common::with_api_db_env(|| async {
+        let (app, pool) = build_app().await.unwrap();
+        let (listener, port) = common::create_test_listener();
+        let (server, _) = common::spawn_test_server(app, listener).await;
+
+        // A BLAKE3 digest is the right length, but would be anchored as SHA-256
+        let response = Client::new()
+            .post(format!("http://127.0.0.1:{}/evidence", port))
+            .json(&json!({
+                "id": "blake3-digest",
+                "digest_hex": "b3".repeat(32),
+                "metadata": { "digest_algo": "blake3" }
+            }))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), 400);
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert!(body["error"].as_str().unwrap().contains("digest_algo"));
+
+        let row = sqlx::query("SELECT id FROM outbox_jobs WHERE id = ?")
+            .bind("blake3-digest")
+            .fetch_optional(&pool)
+            .await
+            .unwrap();
+        assert!(row.is_none());
+
+        // Labelling a digest sha256 is accepted
+        let response = Client::new()
+            .post(format!("http://127.0.0.1:{}/evidence", port))
+            .json(&json!({
+                "digest_hex": "5a".repeat(32),
+                "metadata": { "digest_algo": "sha256" }
+            }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+
+        server.abort();
+    })
+    .await;
+}
+
 #[tokio::test]
 async fn test_post_evidence_with_metadata() {
     // Use specialized helper for API database environment setup
@@ -161,3 +483,629 @@ async fn test_post_evidence_with_metadata() {
     })
     .await;
 }
+
+#[tokio::test]
+async fn test_post_evidence_async_mode_returns_queued() {
+    common::with_api_db_env(|| async {
+        let (app, _pool) =
+            build_app_with_sync_anchor(Some(sync_anchor(Duration::ZERO, Duration::from_secs(5))))
+                .await
+                .unwrap();
+        let (listener, port) = common::create_test_listener();
+        let (server, _) = common::spawn_test_server(app, listener).await;
+
+        let response = Client::new()
+            .post(format!("http://127.0.0.1:{}/evidence", port))
+            .json(&json!({
+                "id": "anchor-mode-async",
+                "digest_hex": "aa".repeat(32),
+                "anchor_mode": "async"
+            }))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), 200);
+        let result: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(result["status"], "queued");
+        assert!(result.get("tx_ref").is_none());
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_post_evidence_sync_mode_returns_tx_ref() {
+    common::with_api_db_env(|| async {
+        let (app, pool) =
+            build_app_with_sync_anchor(Some(sync_anchor(Duration::ZERO, Duration::from_secs(5))))
+                .await
+                .unwrap();
+        let (listener, port) = common::create_test_listener();
+        let (server, _) = common::spawn_test_server(app, listener).await;
+
+        let digest = "bb".repeat(32);
+        let response = Client::new()
+            .post(format!("http://127.0.0.1:{}/evidence", port))
+            .json(&json!({
+                "id": "anchor-mode-sync",
+                "digest_hex": digest,
+                "anchor_mode": "sync"
+            }))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), 200);
+        let result: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(result["status"], "anchored");
+        assert_eq!(result["tx_ref"]["network"], "etherlink");
+        assert_eq!(result["tx_ref"]["tx_id"], format!("0x{}", digest));
+
+        // Job is done and the tx ref is persisted
+        let status: String = sqlx::query("SELECT status FROM outbox_jobs WHERE id = ?")
+            .bind("anchor-mode-sync")
+            .fetch_one(&pool)
+            .await
+            .unwrap()
+            .get(0);
+        assert_eq!(status, "done");
+        let refs: i64 = sqlx::query("SELECT COUNT(*) FROM outbox_tx_refs WHERE job_id = ?")
+            .bind("anchor-mode-sync")
+            .fetch_one(&pool)
+            .await
+            .unwrap()
+            .get(0);
+        assert_eq!(refs, 1);
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_post_evidence_sync_mode_timeout_keeps_job_claimed() {
+    common::with_api_db_env(|| async {
+        let (app, pool) = build_app_with_sync_anchor(Some(sync_anchor(
+            Duration::from_millis(300),
+            Duration::from_millis(50),
+        )))
+        .await
+        .unwrap();
+        ensure_schema(&pool).await.unwrap();
+        let (listener, port) = common::create_test_listener();
+        let (server, _) = common::spawn_test_server(app, listener).await;
+
+        let response = Client::new()
+            .post(format!("http://127.0.0.1:{}/evidence", port))
+            .json(&json!({
+                "id": "anchor-mode-timeout",
+                "digest_hex": "cc".repeat(32),
+                "anchor_mode": "sync"
+            }))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), 504);
+        let result: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(result["status"], "in_progress");
+
+        // The send may already be on chain, so the keeper must not anchor it
+        let status = |pool: sqlx::SqlitePool| async move {
+            sqlx::query("SELECT status FROM outbox_jobs WHERE id = ?")
+                .bind("anchor-mode-timeout")
+                .fetch_one(&pool)
+                .await
+                .unwrap()
+                .get::<String, _>(0)
+        };
+        assert_eq!(status(pool.clone()).await, "in_progress");
+        assert!(SqliteJobProvider::new(pool.clone())
+            .fetch_next()
+            .await
+            .unwrap()
+            .is_none());
+
+        // The anchor finishes in the background and is recorded
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+        while status(pool.clone()).await != "done" {
+            assert!(tokio::time::Instant::now() < deadline, "job never settled");
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        let refs: i64 = sqlx::query("SELECT COUNT(*) FROM outbox_tx_refs WHERE job_id = ?")
+            .bind("anchor-mode-timeout")
+            .fetch_one(&pool)
+            .await
+            .unwrap()
+            .get(0);
+        assert_eq!(refs, 1);
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_post_evidence_sync_mode_is_not_claimable_by_keeper() {
+    common::with_api_db_env(|| async {
+        let (app, pool) = build_app_with_sync_anchor(Some(sync_anchor(
+            Duration::from_millis(500),
+            Duration::from_secs(5),
+        )))
+        .await
+        .unwrap();
+        ensure_schema(&pool).await.unwrap();
+        let (listener, port) = common::create_test_listener();
+        let (server, _) = common::spawn_test_server(app, listener).await;
+
+        let request = tokio::spawn(
+            Client::new()
+                .post(format!("http://127.0.0.1:{}/evidence", port))
+                .json(&json!({
+                    "id": "anchor-mode-race",
+                    "digest_hex": "ee".repeat(32),
+                    "anchor_mode": "sync"
+                }))
+                .send(),
+        );
+
+        // Keeper ticks while the request is anchoring find nothing to claim
+        let mut keeper = SqliteJobProvider::new(pool.clone());
+        let mut saw_job_row = false;
+        while !request.is_finished() {
+            let claimed = keeper.fetch_next().await.unwrap();
+            assert!(claimed.is_none(), "keeper claimed an inline-anchored job");
+            saw_job_row |= sqlx::query("SELECT 1 FROM outbox_jobs WHERE id = ?")
+                .bind("anchor-mode-race")
+                .fetch_optional(&pool)
+                .await
+                .unwrap()
+                .is_some();
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert!(saw_job_row, "keeper never ran while the job was anchoring");
+
+        let response = request.await.unwrap().unwrap();
+        assert_eq!(response.status(), 200);
+        assert!(keeper.fetch_next().await.unwrap().is_none());
+
+        let status: String = sqlx::query("SELECT status FROM outbox_jobs WHERE id = ?")
+            .bind("anchor-mode-race")
+            .fetch_one(&pool)
+            .await
+            .unwrap()
+            .get(0);
+        assert_eq!(status, "done");
+        let refs: i64 = sqlx::query("SELECT COUNT(*) FROM outbox_tx_refs WHERE job_id = ?")
+            .bind("anchor-mode-race")
+            .fetch_one(&pool)
+            .await
+            .unwrap()
+            .get(0);
+        assert_eq!(refs, 1);
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_post_evidence_sync_mode_payload_too_large_fails_job() {
+    common::with_api_db_env(|| async {
+        let (app, pool) = build_app_with_sync_anchor(Some(SyncAnchor::new(
+            Arc::new(OversizedAnchor),
+            Duration::from_secs(5),
+        )))
+        .await
+        .unwrap();
+        ensure_schema(&pool).await.unwrap();
+        let (listener, port) = common::create_test_listener();
+        let (server, _) = common::spawn_test_server(app, listener).await;
+
+        let response = Client::new()
+            .post(format!("http://127.0.0.1:{}/evidence", port))
+            .json(&json!({
+                "id": "anchor-mode-oversized",
+                "digest_hex": "ab".repeat(32),
+                "anchor_mode": "sync"
+            }))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), 413);
+        let result: serde_json::Value = response.json().await.unwrap();
+        assert_eq!(result["status"], "failed");
+
+        // Failed for good: the keeper has nothing to retry
+        let (status, last_error): (String, String) =
+            sqlx::query_as("SELECT status, last_error FROM outbox_jobs WHERE id = ?")
+                .bind("anchor-mode-oversized")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(status, "failed");
+        assert!(last_error.contains("2048"));
+        assert!(SqliteJobProvider::new(pool.clone())
+            .fetch_next()
+            .await
+            .unwrap()
+            .is_none());
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_post_evidence_sync_mode_record_failure_keeps_tx_ref() {
+    common::with_api_db_env(|| async {
+        let (app, pool) =
+            build_app_with_sync_anchor(Some(sync_anchor(Duration::ZERO, Duration::from_secs(5))))
+                .await
+                .unwrap();
+        ensure_schema(&pool).await.unwrap();
+        // Every tx ref write fails after the chain accepted the anchor
+        sqlx::query(
+            "CREATE TRIGGER reject_tx_refs BEFORE INSERT ON outbox_tx_refs
+             BEGIN SELECT RAISE(ABORT, 'disk I/O error'); END",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        let (listener, port) = common::create_test_listener();
+        let (server, _) = common::spawn_test_server(app, listener).await;
+
+        let digest = "fa".repeat(32);
+        let response = Client::new()
+            .post(format!("http://127.0.0.1:{}/evidence", port))
+            .json(&json!({
+                "id": "anchor-mode-unrecorded",
+                "digest_hex": digest,
+                "anchor_mode": "sync"
+            }))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), 500);
+        let result: serde_json::Value = response.json().await.unwrap();
+        let tx_id = format!("0x{}", digest);
+        assert_eq!(result["status"], "failed");
+        assert_eq!(result["tx_ref"]["tx_id"], tx_id);
+
+        // The job is failed with the tx id, not left for the reaper to requeue
+        let (status, last_error): (String, String) =
+            sqlx::query_as("SELECT status, last_error FROM outbox_jobs WHERE id = ?")
+                .bind("anchor-mode-unrecorded")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(status, "failed");
+        assert!(last_error.contains(&tx_id));
+        assert_eq!(reclaim_stale_jobs(&pool, Duration::ZERO).await.unwrap(), 0);
+        assert!(SqliteJobProvider::new(pool.clone())
+            .fetch_next()
+            .await
+            .unwrap()
+            .is_none());
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_post_evidence_sync_mode_requires_capability() {
+    common::with_api_db_env(|| async {
+        let (app, _pool) = build_app_with_sync_anchor(None).await.unwrap();
+        let (listener, port) = common::create_test_listener();
+        let (server, _) = common::spawn_test_server(app, listener).await;
+
+        let response = Client::new()
+            .post(format!("http://127.0.0.1:{}/evidence", port))
+            .json(&json!({
+                "digest_hex": "dd".repeat(32),
+                "anchor_mode": "sync"
+            }))
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), 400);
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_post_evidence_records_source() {
+    common::with_api_db_env(|| async {
+        let (app, _pool) = build_app().await.unwrap();
+        let (listener, port) = common::create_test_listener();
+        let (server, _) = common::spawn_test_server(app, listener).await;
+        let client = Client::new();
+
+        // Body field wins over the header
+        let cases = [
+            ("source-body", Some("Simulator"), Some("cli"), "simulator"),
+            ("source-header", None, Some("edge-node-7"), "edge-node-7"),
+            ("source-default", None, None, "api"),
+        ];
+        for (id, body_source, header_source, expected) in cases {
+            let mut request = client
+                .post(format!("http://127.0.0.1:{}/evidence", port))
+                .json(&json!({
+                    "id": id,
+                    "digest_hex": "ab".repeat(32),
+                    "source": body_source,
+                }));
+            if let Some(header_source) = header_source {
+                request = request.header("X-Evidence-Source", header_source);
+            }
+            assert_eq!(request.send().await.unwrap().status(), 200);
+
+            let evidence: serde_json::Value = client
+                .get(format!("http://127.0.0.1:{}/evidence/{}", port, id))
+                .send()
+                .await
+                .unwrap()
+                .json()
+                .await
+                .unwrap();
+            assert_eq!(evidence["source"], expected, "case {}", id);
+        }
+
+        // Labels outside [a-z0-9._:-] are rejected
+        let response = client
+            .post(format!("http://127.0.0.1:{}/evidence", port))
+            .header("X-Evidence-Source", "edge node")
+            .json(&json!({ "digest_hex": "cd".repeat(32) }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 400);
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_post_evidence_records_priority() {
+    common::with_api_db_env(|| async {
+        let (app, pool) = build_app().await.unwrap();
+        let (listener, port) = common::create_test_listener();
+        let (server, _) = common::spawn_test_server(app, listener).await;
+        let client = Client::new();
+
+        for (id, priority, expected) in [
+            ("priority-legal", Some(10), 10i64),
+            ("priority-default", None, 0),
+        ] {
+            let response = client
+                .post(format!("http://127.0.0.1:{}/evidence", port))
+                .json(&json!({
+                    "id": id,
+                    "digest_hex": "ef".repeat(32),
+                    "priority": priority,
+                }))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), 200);
+
+            let stored: i64 = sqlx::query_scalar("SELECT priority FROM outbox_jobs WHERE id = ?1")
+                .bind(id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+            assert_eq!(stored, expected, "case {}", id);
+        }
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_list_evidence_filters_by_source() {
+    common::with_api_db_env(|| async {
+        let (app, _pool) = build_app().await.unwrap();
+        let (listener, port) = common::create_test_listener();
+        let (server, _) = common::spawn_test_server(app, listener).await;
+        let client = Client::new();
+
+        for (id, source) in [
+            ("filter-edge-1", "filter-edge"),
+            ("filter-edge-2", "filter-edge"),
+            ("filter-sim-1", "filter-sim"),
+        ] {
+            let response = client
+                .post(format!("http://127.0.0.1:{}/evidence", port))
+                .header("X-Evidence-Source", source)
+                .json(&json!({ "id": id, "digest_hex": "ef".repeat(32) }))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(response.status(), 200);
+        }
+
+        let listing: serde_json::Value = client
+            .get(format!(
+                "http://127.0.0.1:{}/evidence?source=filter-edge&per_page=100",
+                port
+            ))
+            .send()
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(listing["total"], 2);
+        let data = listing["data"].as_array().unwrap();
+        assert_eq!(data.len(), 2);
+        assert!(data.iter().all(|e| e["source"] == "filter-edge"));
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_list_evidence_filters_by_status_and_since() {
+    common::with_api_db_env(|| async {
+        let (app, pool) = build_app().await.unwrap();
+        let (listener, port) = common::create_test_listener();
+        let (server, _) = common::spawn_test_server(app, listener).await;
+        let client = Client::new();
+
+        for (id, status, created_ms) in [
+            ("status-queued-old", "queued", 1_000),
+            ("status-queued-new", "queued", 5_000),
+            ("status-failed-old", "failed", 1_000),
+            ("status-failed-new", "failed", 5_000),
+            ("status-done", "done", 5_000),
+        ] {
+            sqlx::query(
+                "INSERT INTO outbox_jobs (id, payload_sha256, status, attempts, created_ms, updated_ms, next_attempt_ms, source)
+                 VALUES (?1, ?2, ?3, 0, ?4, ?4, 0, 'status-filter')",
+            )
+            .bind(id)
+            .bind("ab".repeat(32))
+            .bind(status)
+            .bind(created_ms)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let list = |query: &'static str| {
+            let client = client.clone();
+            async move {
+                let response = client
+                    .get(format!(
+                        "http://127.0.0.1:{}/evidence?source=status-filter&per_page=100&{}",
+                        port, query
+                    ))
+                    .send()
+                    .await
+                    .unwrap();
+                let status = response.status();
+                let body: serde_json::Value = response.json().await.unwrap();
+                (status, body)
+            }
+        };
+        let ids = |body: &serde_json::Value| -> Vec<String> {
+            body["data"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|e| e["id"].as_str().unwrap().to_string())
+                .collect()
+        };
+
+        // Status alone
+        let (status, body) = list("status=failed").await;
+        assert_eq!(status, 200);
+        assert_eq!(body["total"], 2);
+        assert_eq!(ids(&body), vec!["status-failed-new", "status-failed-old"]);
+        assert!(body["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .all(|e| e["status"] == "failed"));
+
+        // Status combined with a created_ms lower bound
+        let (status, body) = list("status=queued&since_ms=5000").await;
+        assert_eq!(status, 200);
+        assert_eq!(body["total"], 1);
+        assert_eq!(ids(&body), vec!["status-queued-new"]);
+
+        // The same filters apply to cursor pages
+        let (status, body) = list("status=queued&since_ms=1000&limit=10").await;
+        assert_eq!(status, 200);
+        assert_eq!(ids(&body), vec!["status-queued-new", "status-queued-old"]);
+        assert!(body["next_cursor"].is_null());
+
+        // Unknown statuses are rejected
+        let (status, body) = list("status=exploded").await;
+        assert_eq!(status, 400);
+        assert!(body["error"].as_str().unwrap().contains("exploded"));
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_list_evidence_filters_by_metadata_event_type() {
+    common::with_api_db_env(|| async {
+        let (app, _pool) = build_app().await.unwrap();
+        let (listener, port) = common::create_test_listener();
+        let (server, _) = common::spawn_test_server(app, listener).await;
+        let client = Client::new();
+
+        for (id, event_type) in [
+            ("meta-filter-engagement-1", "meta_filter_engagement"),
+            ("meta-filter-engagement-2", "meta_filter_engagement"),
+            ("meta-filter-detection", "meta_filter_detection"),
+        ] {
+            let response = client
+                .post(format!("http://127.0.0.1:{}/evidence", port))
+                .json(&serde_json::json!({
+                    "id": id,
+                    "digest_hex": "cd".repeat(32),
+                    "metadata": { "event_type": event_type, "operator": "alpha" },
+                }))
+                .send()
+                .await
+                .unwrap();
+            assert!(response.status().is_success());
+        }
+
+        let list = |query: &'static str| {
+            let client = client.clone();
+            async move {
+                let body: serde_json::Value = client
+                    .get(format!(
+                        "http://127.0.0.1:{}/evidence?per_page=100&{}",
+                        port, query
+                    ))
+                    .send()
+                    .await
+                    .unwrap()
+                    .json()
+                    .await
+                    .unwrap();
+                let mut ids: Vec<String> = body["data"]
+                    .as_array()
+                    .unwrap()
+                    .iter()
+                    .map(|e| e["id"].as_str().unwrap().to_string())
+                    .collect();
+                ids.sort();
+                ids
+            }
+        };
+
+        assert_eq!(
+            list("metadata.event_type=meta_filter_engagement").await,
+            vec!["meta-filter-engagement-1", "meta-filter-engagement-2"]
+        );
+        // Cursor pages apply the same filter
+        assert_eq!(
+            list("metadata.event_type=meta_filter_detection&limit=10").await,
+            vec!["meta-filter-detection"]
+        );
+        assert!(list("metadata.event_type=meta_filter_unknown")
+            .await
+            .is_empty());
+
+        server.abort();
+    })
+    .await;
+}
@@ -0,0 +1,227 @@
+//! Content-addressed evidence payloads: stored on submission, served back
+//! with their MIME type, and refused once they no longer match the digest
+
+mod common;
+
+use base64::Engine;
+use phoenix_api::{
+    payload_store::{PayloadStore, PayloadStoreError, SqlitePayloadStore},
+    AppState,
+};
+use reqwest::StatusCode;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+
+async fn spawn_server(
+    store_payloads: bool,
+) -> (tokio::task::JoinHandle<()>, u16, sqlx::Pool<sqlx::Sqlite>) {
+    let (_app, pool) = phoenix_api::build_app().await.unwrap();
+    let state = AppState {
+        payloads: store_payloads
+            .then(|| Arc::new(SqlitePayloadStore::new(pool.clone())) as Arc<dyn PayloadStore>),
+        ..common::test_state(&pool)
+    };
+
+    let (listener, _) = common::create_test_listener();
+    let (server, port) = common::spawn_test_server(phoenix_api::router(state), listener).await;
+    (server, port, pool)
+}
+
+fn digest(payload: &[u8]) -> String {
+    hex::encode(Sha256::digest(payload))
+}
+
+async fn submit(port: u16, body: serde_json::Value) -> reqwest::Response {
+    reqwest::Client::new()
+        .post(format!("http://127.0.0.1:{}/evidence", port))
+        .json(&body)
+        .send()
+        .await
+        .unwrap()
+}
+
+async fn fetch_payload(port: u16, id: &str) -> reqwest::Response {
+    reqwest::get(format!("http://127.0.0.1:{}/evidence/{}/payload", port, id))
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_inline_payload_is_stored_and_served_with_its_mime_type() {
+    common::with_api_db_env(|| async {
+        let (server, port, _pool) = spawn_server(true).await;
+        let payload = br#"{"track":"drone-7","confidence":0.93}"#;
+
+        let response = submit(
+            port,
+            json!({
+                "id": "payload-roundtrip-1",
+                "digest_hex": digest(payload),
+                "payload_mime": "application/json",
+                "payload_base64": base64::engine::general_purpose::STANDARD.encode(payload),
+            }),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = fetch_payload(port, "payload-roundtrip-1").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()["content-type"], "application/json");
+        assert_eq!(response.bytes().await.unwrap().as_ref(), payload);
+
+        // Evidence submitted without a payload has nothing to serve
+        let response = submit(
+            port,
+            json!({ "id": "payload-roundtrip-2", "digest_hex": "ab".repeat(32) }),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = fetch_payload(port, "payload-roundtrip-2").await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        assert_eq!(
+            fetch_payload(port, "payload-no-such-evidence")
+                .await
+                .status(),
+            StatusCode::NOT_FOUND
+        );
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_payload_not_matching_digest_is_rejected() {
+    common::with_api_db_env(|| async {
+        let (server, port, _pool) = spawn_server(true).await;
+
+        let response = submit(
+            port,
+            json!({
+                "id": "payload-mismatch-1",
+                "digest_hex": digest(b"what was hashed"),
+                "payload_base64": base64::engine::general_purpose::STANDARD.encode(b"something else"),
+            }),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body: serde_json::Value = response.json().await.unwrap();
+        assert!(body["error"].as_str().unwrap().contains("digest mismatch"));
+
+        let response = submit(
+            port,
+            json!({
+                "id": "payload-mismatch-2",
+                "digest_hex": digest(b"x"),
+                "payload_base64": "not base64!",
+            }),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        // Neither submission created a job
+        let response = reqwest::get(format!(
+            "http://127.0.0.1:{}/evidence/payload-mismatch-1",
+            port
+        ))
+        .await
+        .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_inline_payload_requires_payload_storage() {
+    common::with_api_db_env(|| async {
+        let (server, port, _pool) = spawn_server(false).await;
+        let payload = b"frame-0042";
+
+        let response = submit(
+            port,
+            json!({
+                "id": "payload-disabled-1",
+                "digest_hex": digest(payload),
+                "payload_base64": base64::engine::general_purpose::STANDARD.encode(payload),
+            }),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_tampered_payload_is_detected_on_read() {
+    common::with_api_db_env(|| async {
+        let (server, port, pool) = spawn_server(true).await;
+        let payload = b"thermal-frame-payload-tamper-test";
+        let digest_hex = digest(payload);
+
+        let response = submit(
+            port,
+            json!({
+                "id": "payload-tamper-1",
+                "digest_hex": digest_hex,
+                "payload_mime": "application/octet-stream",
+                "payload_base64": base64::engine::general_purpose::STANDARD.encode(payload),
+            }),
+        )
+        .await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        sqlx::query("UPDATE evidence_payloads SET bytes = ?1 WHERE digest_hex = ?2")
+            .bind(b"thermal-frame-payload-TAMPERED".as_slice())
+            .bind(&digest_hex)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let response = fetch_payload(port, "payload-tamper-1").await;
+        assert_eq!(response.status(), StatusCode::CONFLICT);
+
+        let store = SqlitePayloadStore::new(pool.clone());
+        match store.get(&digest_hex).await {
+            Err(PayloadStoreError::DigestMismatch { expected, actual }) => {
+                assert_eq!(expected, digest_hex);
+                assert_ne!(actual, digest_hex);
+            }
+            other => panic!("expected a digest mismatch, got {:?}", other),
+        }
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_store_put_checks_digest_and_is_idempotent() {
+    common::with_api_db_env(|| async {
+        let (_app, pool) = phoenix_api::build_app().await.unwrap();
+        let store = SqlitePayloadStore::new(pool);
+        let payload = b"store-level-payload";
+        let digest_hex = digest(payload);
+
+        assert!(matches!(
+            store.put(&digest_hex, b"other bytes").await,
+            Err(PayloadStoreError::DigestMismatch { .. })
+        ));
+        assert_eq!(store.get(&digest_hex).await.unwrap(), None);
+
+        store.put(&digest_hex, payload).await.unwrap();
+        store
+            .put(&digest_hex.to_uppercase(), payload)
+            .await
+            .unwrap();
+        assert_eq!(
+            store.get(&digest_hex).await.unwrap().as_deref(),
+            Some(payload.as_slice())
+        );
+    })
+    .await;
+}
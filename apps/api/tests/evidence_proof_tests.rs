@@ -0,0 +1,202 @@
+//! Integration tests for GET /evidence/{id}/proof
+
+mod common;
+
+use phoenix_evidence::merkle::{verify_proof_bundle, MerkleTree, ProofBundle};
+use reqwest::StatusCode;
+use serde_json::Value;
+use sqlx::{Pool, Sqlite};
+
+/// 32-byte leaf digests, each a byte pair repeated
+fn leaves(pairs: &[&str]) -> Vec<String> {
+    pairs.iter().map(|pair| pair.repeat(32)).collect()
+}
+
+/// Insert a batch over `leaves` and a proof row for `job_id` at `index`
+async fn insert_batch(
+    pool: &Pool<Sqlite>,
+    batch_id: &str,
+    job_id: &str,
+    leaves: &[String],
+    index: usize,
+    tx_id: Option<&str>,
+) -> MerkleTree {
+    let tree = MerkleTree::from_leaves(leaves.to_vec()).unwrap();
+    let now = chrono::Utc::now().timestamp_millis();
+
+    sqlx::query(
+        "INSERT INTO merkle_batches (id, merkle_root, item_count, created_at, anchored_at, tx_network, tx_chain, tx_id, tx_confirmed, construction_version)
+        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+    )
+    .bind(batch_id)
+    .bind(tree.root())
+    .bind(leaves.len() as i64)
+    .bind(now)
+    .bind(tx_id.map(|_| now))
+    .bind(tx_id.map(|_| "solana"))
+    .bind(tx_id.map(|_| "devnet"))
+    .bind(tx_id)
+    .bind(tx_id.is_some() as i64)
+    .bind(tree.construction_version() as i64)
+    .execute(pool)
+    .await
+    .unwrap();
+
+    sqlx::query(
+        "INSERT INTO merkle_proofs (job_id, batch_id, leaf_index, proof_json) VALUES (?1, ?2, ?3, ?4)",
+    )
+    .bind(job_id)
+    .bind(batch_id)
+    .bind(index as i64)
+    .bind(serde_json::to_string(&tree.proof(index).unwrap()).unwrap())
+    .execute(pool)
+    .await
+    .unwrap();
+
+    tree
+}
+
+async fn get_proof(port: u16, job_id: &str) -> reqwest::Response {
+    reqwest::Client::new()
+        .get(format!(
+            "http://127.0.0.1:{}/evidence/{}/proof",
+            port, job_id
+        ))
+        .send()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_get_evidence_proof_anchored() {
+    common::with_api_db_env(|| async {
+        let (app, pool) = phoenix_api::build_app().await.unwrap();
+        let (listener, _) = common::create_test_listener();
+        let (server, port) = common::spawn_test_server(app, listener).await;
+
+        let tree = insert_batch(
+            &pool,
+            "batch-proof-anchored",
+            "job-proof-anchored",
+            &leaves(&["aa", "bb", "cc"]),
+            1,
+            Some("sig-proof-anchored"),
+        )
+        .await;
+
+        let response = get_proof(port, "job-proof-anchored").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let body: Value = response.json().await.unwrap();
+
+        assert_eq!(body["version"], 1);
+        assert_eq!(body["job_id"], "job-proof-anchored");
+        assert_eq!(body["network"], "solana");
+        assert_eq!(body["chain"], "devnet");
+        assert_eq!(body["merkle_root"], tree.root());
+        assert_eq!(body["proof"]["leaf_hash"], "bb".repeat(32));
+        assert_eq!(body["proof"]["leaf_index"], 1);
+        assert!(body["proof"]["siblings"].is_array());
+        assert_eq!(body["tx_ref"]["tx_id"], "sig-proof-anchored");
+        assert_eq!(body["tx_ref"]["confirmed"], true);
+
+        // The response is a portable bundle that verifies offline
+        let bundle: ProofBundle = serde_json::from_value(body).unwrap();
+        assert!(verify_proof_bundle(&bundle).unwrap());
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_get_evidence_proof_pending_until_anchored() {
+    common::with_api_db_env(|| async {
+        let (app, pool) = phoenix_api::build_app().await.unwrap();
+        let (listener, _) = common::create_test_listener();
+        let (server, port) = common::spawn_test_server(app, listener).await;
+
+        insert_batch(
+            &pool,
+            "batch-proof-pending",
+            "job-proof-pending",
+            &leaves(&["dd", "ee"]),
+            0,
+            None,
+        )
+        .await;
+
+        let response = get_proof(port, "job-proof-pending").await;
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        let body: Value = response.json().await.unwrap();
+        assert_eq!(body["status"], "pending");
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_get_evidence_proof_unknown_job() {
+    common::with_api_db_env(|| async {
+        let (app, _pool) = phoenix_api::build_app().await.unwrap();
+        let (listener, _) = common::create_test_listener();
+        let (server, port) = common::spawn_test_server(app, listener).await;
+
+        let response = get_proof(port, "job-proof-missing").await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_get_evidence_proof_withheld_until_confirmed() {
+    common::with_api_db_env(|| async {
+        let (_app, pool) = phoenix_api::build_app().await.unwrap();
+        let state = phoenix_api::AppState {
+            require_confirmed_proofs: true,
+            ..common::test_state(&pool)
+        };
+        let (listener, _) = common::create_test_listener();
+        let (server, port) = common::spawn_test_server(phoenix_api::router(state), listener).await;
+
+        insert_batch(
+            &pool,
+            "batch-proof-unconfirmed",
+            "job-proof-unconfirmed",
+            &leaves(&["ab", "cd"]),
+            1,
+            Some("sig-proof-unconfirmed"),
+        )
+        .await;
+        sqlx::query("UPDATE merkle_batches SET tx_confirmed = 0 WHERE id = ?1")
+            .bind("batch-proof-unconfirmed")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        // Anchored but not confirmed: no proof yet
+        let response = get_proof(port, "job-proof-unconfirmed").await;
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+        let body: Value = response.json().await.unwrap();
+        assert_eq!(body["status"], "pending_confirmation");
+        assert_eq!(body["tx_ref"]["tx_id"], "sig-proof-unconfirmed");
+        assert!(body.get("proof").is_none());
+
+        // Once the keeper marks the batch confirmed the bundle is served
+        sqlx::query("UPDATE merkle_batches SET tx_confirmed = 1 WHERE id = ?1")
+            .bind("batch-proof-unconfirmed")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let response = get_proof(port, "job-proof-unconfirmed").await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let bundle: ProofBundle = response.json().await.unwrap();
+        assert!(bundle.tx_ref.confirmed);
+        assert!(verify_proof_bundle(&bundle).unwrap());
+
+        server.abort();
+    })
+    .await;
+}
@@ -146,3 +146,90 @@ async fn test_get_evidence_not_found() {
     })
     .await;
 }
+
+#[tokio::test]
+async fn test_get_evidence_tx_refs_ordering_is_stable() {
+    let db_url = "sqlite::memory:?cache=shared";
+
+    common::with_env_var("API_DB_URL", db_url, || async {
+        let (app, pool) = build_app().await.unwrap();
+        let (listener, port) = common::create_test_listener();
+        let (server, _) = common::spawn_test_server(app, listener).await;
+
+        let now = chrono::Utc::now().timestamp_millis();
+        let solana = ("solana", "devnet", "5xSolanaSig");
+        let etherlink = ("etherlink", "testnet", "0xetherlinkhash");
+
+        // Same refs, opposite insertion order
+        let jobs = [
+            ("order-job-a", [solana, etherlink]),
+            ("order-job-b", [etherlink, solana]),
+        ];
+
+        for (job_id, refs) in &jobs {
+            sqlx::query(
+                "INSERT INTO outbox_jobs (id, payload_sha256, status, attempts, created_ms, updated_ms)
+                VALUES (?, ?, 'done', 1, ?, ?)",
+            )
+            .bind(job_id)
+            .bind("abcd1234")
+            .bind(now)
+            .bind(now)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+            for (network, chain, tx_id) in refs {
+                sqlx::query(
+                    "INSERT INTO outbox_tx_refs (job_id, network, chain, tx_id, confirmed, timestamp)
+                    VALUES (?, ?, ?, ?, 1, ?)",
+                )
+                .bind(job_id)
+                .bind(network)
+                .bind(chain)
+                .bind(tx_id)
+                .bind(now / 1000)
+                .execute(&pool)
+                .await
+                .unwrap();
+            }
+        }
+
+        let client = Client::new();
+        let mut orders = Vec::new();
+        for (job_id, _) in &jobs {
+            let result: serde_json::Value = client
+                .get(format!("http://127.0.0.1:{}/evidence/{}", port, job_id))
+                .send()
+                .await
+                .unwrap()
+                .json()
+                .await
+                .unwrap();
+
+            let order: Vec<(String, String)> = result["tx_refs"]
+                .as_array()
+                .expect("tx_refs should be an array")
+                .iter()
+                .map(|r| {
+                    (
+                        r["network"].as_str().unwrap().to_string(),
+                        r["chain"].as_str().unwrap().to_string(),
+                    )
+                })
+                .collect();
+            orders.push(order);
+        }
+
+        // Sorted by network, then chain, regardless of insertion order
+        let expected = vec![
+            ("etherlink".to_string(), "testnet".to_string()),
+            ("solana".to_string(), "devnet".to_string()),
+        ];
+        assert_eq!(orders[0], expected);
+        assert_eq!(orders[1], expected);
+
+        server.abort();
+    })
+    .await;
+}
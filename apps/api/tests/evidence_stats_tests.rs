@@ -0,0 +1,103 @@
+//! Integration tests for `GET /evidence/stats`
+
+mod common;
+
+use phoenix_api::build_app;
+use serde_json::Value;
+use tempfile::NamedTempFile;
+
+async fn insert_job(pool: &sqlx::Pool<sqlx::Sqlite>, id: &str, status: &str, created_ms: i64) {
+    sqlx::query(
+        "INSERT INTO outbox_jobs (id, payload_sha256, status, attempts, created_ms, updated_ms, next_attempt_ms)
+         VALUES (?1, ?2, ?3, 1, ?4, ?4, 0)",
+    )
+    .bind(id)
+    .bind("ab".repeat(32))
+    .bind(status)
+    .bind(created_ms)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+async fn insert_tx_ref(
+    pool: &sqlx::Pool<sqlx::Sqlite>,
+    job_id: &str,
+    tx_id: &str,
+    confirmed: bool,
+) {
+    sqlx::query(
+        "INSERT INTO outbox_tx_refs (job_id, network, chain, tx_id, confirmed, timestamp)
+         VALUES (?1, 'solana', 'devnet', ?2, ?3, NULL)",
+    )
+    .bind(job_id)
+    .bind(tx_id)
+    .bind(confirmed as i64)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_evidence_stats_counts_jobs_by_status() {
+    // Own database file: the shared in-memory one holds other tests' jobs
+    let db = NamedTempFile::new().unwrap();
+    let db_url = format!("sqlite://{}", db.path().display());
+
+    common::with_env_var("API_DB_URL", &db_url, || async {
+        let (app, pool) = build_app().await.unwrap();
+        let (listener, _) = common::create_test_listener();
+        let (server, port) = common::spawn_test_server(app, listener).await;
+
+        let now = chrono::Utc::now().timestamp_millis();
+        let oldest_queued = now - 120_000;
+        insert_job(&pool, "stats-queued-old", "queued", oldest_queued).await;
+        insert_job(&pool, "stats-queued-new", "queued", now - 1_000).await;
+        insert_job(&pool, "stats-in-progress", "in_progress", now).await;
+        insert_job(&pool, "stats-done-1", "done", now).await;
+        insert_job(&pool, "stats-done-2", "done", now).await;
+        insert_job(&pool, "stats-done-3", "done", now).await;
+        insert_job(&pool, "stats-failed", "failed", now).await;
+        insert_job(&pool, "stats-dead", "dead_letter", now).await;
+        insert_tx_ref(&pool, "stats-done-1", "5sig-confirmed", true).await;
+        insert_tx_ref(&pool, "stats-done-2", "5sig-pending", false).await;
+        insert_tx_ref(&pool, "stats-done-3", "5sig-pending-too", false).await;
+
+        let response = reqwest::get(format!("http://127.0.0.1:{}/evidence/stats", port))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), reqwest::StatusCode::OK);
+        let stats: Value = response.json().await.unwrap();
+
+        assert_eq!(stats["total"], 8);
+        assert_eq!(stats["queued"], 2);
+        assert_eq!(stats["in_progress"], 1);
+        assert_eq!(stats["done"], 3);
+        assert_eq!(stats["failed"], 1);
+        assert_eq!(stats["dead_letter"], 1);
+        assert_eq!(stats["unconfirmed_tx_refs"], 2);
+        let age = stats["oldest_queued_age_ms"].as_i64().unwrap();
+        assert!(
+            (120_000..180_000).contains(&age),
+            "oldest queued job age should be about 2 minutes, got {}ms",
+            age
+        );
+
+        // Nothing queued: no age to report
+        sqlx::query("UPDATE outbox_jobs SET status='done' WHERE status='queued'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let stats: Value = reqwest::get(format!("http://127.0.0.1:{}/evidence/stats", port))
+            .await
+            .unwrap()
+            .json()
+            .await
+            .unwrap();
+        assert_eq!(stats["queued"], 0);
+        assert!(stats["oldest_queued_age_ms"].is_null());
+
+        server.abort();
+    })
+    .await;
+}
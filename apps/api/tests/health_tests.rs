@@ -0,0 +1,66 @@
+//! Liveness and readiness probes
+
+mod common;
+
+use phoenix_api::build_app;
+use serde_json::Value;
+
+#[tokio::test]
+async fn test_ready_reports_components_when_healthy() {
+    common::with_api_db_env(|| async {
+        let (app, _pool) = build_app().await.unwrap();
+        let (listener, _) = common::create_test_listener();
+        let (server, port) = common::spawn_test_server(app, listener).await;
+        let client = reqwest::Client::new();
+
+        let live = client
+            .get(format!("http://127.0.0.1:{}/health", port))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(live.status(), 200);
+        assert_eq!(live.text().await.unwrap(), "OK");
+
+        let response = client
+            .get(format!("http://127.0.0.1:{}/health/ready", port))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+        let body: Value = response.json().await.unwrap();
+        assert_eq!(body["status"], "ready");
+        assert_eq!(body["failing"], serde_json::json!([]));
+        assert_eq!(body["components"]["database"]["status"], "ok");
+        let migrations = &body["components"]["migrations"];
+        assert_eq!(migrations["status"], "ok");
+        assert_eq!(migrations["current_version"], migrations["latest_version"]);
+        assert_eq!(body["components"]["x402"]["enabled"], false);
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_ready_returns_503_when_database_is_down() {
+    common::with_api_db_env(|| async {
+        let (app, pool) = build_app().await.unwrap();
+        let (listener, _) = common::create_test_listener();
+        let (server, port) = common::spawn_test_server(app, listener).await;
+
+        // Simulate the database going away under a running server
+        pool.close().await;
+
+        let response = reqwest::get(format!("http://127.0.0.1:{}/health/ready", port))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 503);
+        let body: Value = response.json().await.unwrap();
+        assert_eq!(body["status"], "unavailable");
+        assert_eq!(body["failing"], serde_json::json!(["database"]));
+        assert_eq!(body["components"]["database"]["status"], "error");
+
+        server.abort();
+    })
+    .await;
+}
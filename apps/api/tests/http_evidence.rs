@@ -1,7 +1,7 @@
 use anchor_etherlink::EtherlinkProviderStub;
 use axum::serve;
 use phoenix_api::build_app;
-use phoenix_keeper::{run_job_loop, SqliteJobProvider};
+use phoenix_keeper::{run_job_loop, BoxedAnchor, SqliteJobProvider};
 use reqwest::Client;
 use serde_json::json;
 use sqlx::Row;
@@ -9,6 +9,7 @@ use std::net::TcpListener;
 use std::time::Duration;
 use tempfile::NamedTempFile;
 use tokio::net::TcpListener as TokioTcpListener;
+use tokio::sync::watch;
 use tokio::time::timeout;
 
 #[tokio::test]
@@ -42,7 +43,13 @@ async fn test_http_evidence_flow() {
     let keeper = tokio::spawn(async move {
         let mut jp = SqliteJobProvider::new(keeper_pool);
         let anchor = EtherlinkProviderStub;
-        run_job_loop(&mut jp, &anchor, Duration::from_millis(100)).await;
+        run_job_loop(
+            &mut jp,
+            &[Box::new(anchor) as BoxedAnchor],
+            Duration::from_millis(100),
+            watch::channel(false).1,
+        )
+        .await;
     });
 
     let client = Client::new();
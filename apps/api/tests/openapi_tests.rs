@@ -0,0 +1,59 @@
+//! The served OpenAPI spec documents the integrator-facing endpoints
+
+mod common;
+
+use phoenix_api::build_app;
+use serde_json::Value;
+
+#[tokio::test]
+async fn test_openapi_documents_premium_endpoint_and_402() {
+    common::with_api_db_env(|| async {
+        let (app, _pool) = build_app().await.unwrap();
+        let (listener, port) = common::create_test_listener();
+        let (server, _) = common::spawn_test_server(app, listener).await;
+
+        let response = reqwest::get(format!("http://127.0.0.1:{}/openapi.json", port))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+        let spec: Value = response.json().await.unwrap();
+        assert!(spec["openapi"].as_str().unwrap().starts_with("3."));
+
+        let premium = &spec["paths"]["/api/v1/evidence/verify-premium"]["post"];
+        assert!(premium.is_object(), "premium endpoint missing");
+        assert_eq!(
+            premium["requestBody"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/VerifyEvidenceRequest"
+        );
+        assert_eq!(
+            premium["responses"]["402"]["content"]["application/json"]["schema"]["$ref"],
+            "#/components/schemas/PaymentDetails"
+        );
+        let payment_details = &spec["components"]["schemas"]["PaymentDetails"];
+        for field in ["price", "recipient", "memo", "expires_at", "tier"] {
+            assert!(
+                payment_details["properties"][field].is_object(),
+                "PaymentDetails.{} missing",
+                field
+            );
+        }
+
+        for path in [
+            "/evidence",
+            "/evidence/{id}",
+            "/evidence/{id}/proof",
+            "/detections",
+        ] {
+            assert!(spec["paths"][path].is_object(), "{} missing", path);
+        }
+
+        let docs = reqwest::get(format!("http://127.0.0.1:{}/docs", port))
+            .await
+            .unwrap();
+        assert_eq!(docs.status(), 200);
+        assert!(docs.text().await.unwrap().contains("/openapi.json"));
+
+        server.abort();
+    })
+    .await;
+}
@@ -57,3 +57,103 @@ async fn test_evidence_pagination_clamp() {
 
     server.abort();
 }
+
+#[tokio::test]
+async fn test_evidence_cursor_walk_is_stable() {
+    let db_url = "sqlite::memory:?cache=shared";
+    std::env::set_var("API_DB_URL", db_url);
+
+    let (app, pool) = build_app().await.unwrap();
+
+    // 30 jobs under their own source, three per timestamp so ties on
+    // created_ms are broken by id
+    let base = chrono::Utc::now().timestamp_millis() - 60_000;
+    let insert = |id: String, created_ms: i64| {
+        let pool = pool.clone();
+        async move {
+            sqlx::query(
+                "INSERT INTO outbox_jobs (id, payload_sha256, status, attempts, created_ms, updated_ms, next_attempt_ms, source)
+                 VALUES (?1, ?2, 'queued', 0, ?3, ?3, 0, 'cursor-walk')"
+            )
+            .bind(id)
+            .bind("seedhash")
+            .bind(created_ms)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+    };
+    for i in 0..30 {
+        insert(format!("cursor-job-{:02}", i), base + i / 3).await;
+    }
+
+    let std_listener = StdTcpListener::bind("127.0.0.1:0").unwrap();
+    std_listener.set_nonblocking(true).unwrap();
+    let port = std_listener.local_addr().unwrap().port();
+    let listener = TcpListener::from_std(std_listener).unwrap();
+    let server = tokio::spawn(async move {
+        serve(listener, app.into_make_service()).await.unwrap();
+    });
+
+    let client = Client::new();
+    let mut seen: Vec<String> = Vec::new();
+    let mut cursor: Option<String> = None;
+    let mut pages = 0;
+
+    loop {
+        let mut url = format!(
+            "http://127.0.0.1:{}/evidence?source=cursor-walk&limit=7",
+            port
+        );
+        if let Some(cursor) = &cursor {
+            url.push_str(&format!("&cursor={}", cursor));
+        }
+        let resp = client.get(&url).send().await.unwrap();
+        assert!(resp.status().is_success());
+        let body: Value = resp.json().await.unwrap();
+        assert_eq!(body["limit"], 7);
+
+        seen.extend(
+            body["data"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|e| e["id"].as_str().unwrap().to_string()),
+        );
+        pages += 1;
+
+        // A job arriving mid-walk sorts ahead of the cursor and must not
+        // shift later pages
+        if pages == 1 {
+            insert("cursor-job-late".to_string(), base + 1_000).await;
+        }
+
+        match body["next_cursor"].as_str() {
+            Some(next) => cursor = Some(next.to_string()),
+            None => break,
+        }
+    }
+
+    assert_eq!(pages, 5);
+    let expected: Vec<String> = (0..30)
+        .rev()
+        .map(|i| format!("cursor-job-{:02}", i))
+        .collect();
+    assert_eq!(
+        seen, expected,
+        "cursor walk must have no duplicates or gaps"
+    );
+
+    // Malformed cursors are rejected rather than restarting the walk
+    let resp = client
+        .get(format!(
+            "http://127.0.0.1:{}/evidence?cursor=not-a-cursor",
+            port
+        ))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), 400);
+
+    server.abort();
+}
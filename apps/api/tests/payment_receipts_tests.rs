@@ -0,0 +1,331 @@
+//! Integration tests for the payment receipts audit listing and refund queue
+
+mod common;
+
+use phoenix_api::{db::create_payment_receipt, handlers_x402::X402State, AppState};
+use phoenix_x402::{PaymentReceipt, PriceTier};
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+
+const WALLET: &str = "PhxRvkTreasury111111111111111111111111111111";
+const ADMIN_TOKEN: &str = "test-admin-token";
+const REFUND_SIGNATURE: &str =
+    "2htwqarwqYduxSL7NiuyLejNvQFGgms62UdPaaDTPEjZbcoh5pv8Xc4WxbeXTgMTx8B58Ugkw7aq6LBWxkDazuwh";
+
+async fn spawn_receipts_server() -> (tokio::task::JoinHandle<()>, u16, sqlx::Pool<sqlx::Sqlite>) {
+    let (_app, pool) = phoenix_api::build_app().await.unwrap();
+    let state = AppState {
+        x402: Some(X402State::devnet(WALLET, &pool)),
+        admin_token: Some(ADMIN_TOKEN.to_string()),
+        ..common::test_state(&pool)
+    };
+
+    let (listener, _) = common::create_test_listener();
+    let (server, port) = common::spawn_test_server(phoenix_api::router(state), listener).await;
+    (server, port, pool)
+}
+
+async fn get_receipts(port: u16, query: &str, token: Option<&str>) -> reqwest::Response {
+    let mut request = reqwest::Client::new().get(format!(
+        "http://127.0.0.1:{}/api/v1/payments/receipts?{}",
+        port, query
+    ));
+    if let Some(token) = token {
+        request = request.bearer_auth(token);
+    }
+    request.send().await.unwrap()
+}
+
+async fn signatures(port: u16, query: &str) -> Vec<String> {
+    let response = get_receipts(port, query, Some(ADMIN_TOKEN)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: Value = response.json().await.unwrap();
+    let mut signatures: Vec<String> = body["data"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|r| r["tx_signature"].as_str().unwrap().to_string())
+        .collect();
+    signatures.sort();
+    signatures
+}
+
+#[tokio::test]
+async fn test_receipts_filter_and_paginate() {
+    common::with_api_db_env(|| async {
+        let (server, port, pool) = spawn_receipts_server().await;
+        let since = chrono::Utc::now().timestamp_millis();
+        for (evidence_id, signature, tier) in [
+            ("evt-receipts-a", "receipts-sig-1", PriceTier::Basic),
+            ("evt-receipts-a", "receipts-sig-2", PriceTier::MultiChain),
+            ("evt-receipts-b", "receipts-sig-3", PriceTier::Basic),
+        ] {
+            let receipt = PaymentReceipt {
+                tx_signature: signature.to_string(),
+                evidence_id: evidence_id.to_string(),
+                tier,
+                token: "USDC".to_string(),
+                token_amount: "0.01".to_string(),
+                amount_usdc: "0.01".to_string(),
+                overpaid_usdc: None,
+                sender_wallet: Some("sender-wallet".to_string()),
+            };
+            create_payment_receipt(&pool, &receipt).await.unwrap();
+        }
+
+        assert_eq!(
+            signatures(port, "evidence_id=evt-receipts-a").await,
+            ["receipts-sig-1", "receipts-sig-2"]
+        );
+        assert_eq!(
+            signatures(port, &format!("tier=basic&since_ms={}", since)).await,
+            ["receipts-sig-1", "receipts-sig-3"]
+        );
+        assert!(signatures(port, &format!("since_ms={}", since + 60_000))
+            .await
+            .is_empty());
+
+        let response = get_receipts(
+            port,
+            "evidence_id=evt-receipts-a&tier=basic",
+            Some(ADMIN_TOKEN),
+        )
+        .await;
+        let body: Value = response.json().await.unwrap();
+        let receipt = &body["data"][0];
+        assert_eq!(receipt["tx_signature"], "receipts-sig-1");
+        assert_eq!(receipt["amount_usdc"], "0.01");
+        assert_eq!(receipt["sender_wallet"], "sender-wallet");
+        assert_eq!(receipt["tier"], "basic");
+
+        // Two pages of two cover all three receipts exactly once
+        let first: Value = get_receipts(
+            port,
+            &format!("since_ms={}&limit=2", since),
+            Some(ADMIN_TOKEN),
+        )
+        .await
+        .json()
+        .await
+        .unwrap();
+        assert_eq!(first["data"].as_array().unwrap().len(), 2);
+        let cursor = first["next_cursor"].as_str().unwrap();
+        let second: Value = get_receipts(
+            port,
+            &format!("since_ms={}&limit=2&cursor={}", since, cursor),
+            Some(ADMIN_TOKEN),
+        )
+        .await
+        .json()
+        .await
+        .unwrap();
+        assert_eq!(second["data"].as_array().unwrap().len(), 1);
+        assert!(second["next_cursor"].is_null());
+        let mut all: Vec<&str> = first["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .chain(second["data"].as_array().unwrap())
+            .map(|r| r["tx_signature"].as_str().unwrap())
+            .collect();
+        all.sort();
+        assert_eq!(all, ["receipts-sig-1", "receipts-sig-2", "receipts-sig-3"]);
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_receipts_require_admin_token() {
+    common::with_api_db_env(|| async {
+        let (server, port, _pool) = spawn_receipts_server().await;
+
+        let response = get_receipts(port, "", None).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let response = get_receipts(port, "", Some("some-other-token")).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let response = get_receipts(port, "cursor=zz", Some(ADMIN_TOKEN)).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_payment_is_refund_eligible_when_lookup_fails() {
+    common::with_api_db_env(|| async {
+        let (server, port, pool) = spawn_receipts_server().await;
+        let now = chrono::Utc::now().timestamp_millis();
+        sqlx::query(
+            "INSERT OR REPLACE INTO outbox_jobs (id, payload_sha256, status, attempts, created_ms, updated_ms, next_attempt_ms)
+             VALUES ('refund-dberr-001', 'abcd1234', 'done', 1, ?1, ?1, 0)",
+        )
+        .bind(now)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let client = reqwest::Client::new();
+        let verify = || {
+            client
+                .post(format!(
+                    "http://127.0.0.1:{}/api/v1/evidence/verify-premium",
+                    port
+                ))
+                .bearer_auth(ADMIN_TOKEN)
+                .json(&json!({ "evidence_id": "refund-dberr-001", "tier": "basic" }))
+        };
+        let response = verify().send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYMENT_REQUIRED);
+        let quote: phoenix_x402::PaymentDetails = response.json().await.unwrap();
+
+        // The tx ref lookup fails once the payment has been taken
+        sqlx::query("ALTER TABLE outbox_tx_refs RENAME TO outbox_tx_refs_hidden")
+            .execute(&pool)
+            .await
+            .unwrap();
+        let signature =
+            "3kuwqarwqYduxSL7NiuyLejNvQFGgms62UdPaaDTPEjZbcoh5pv8Xc4WxbeXTgMTx8B58Ugkw7aq6LBWxkDazuwh";
+        let proof = phoenix_x402::PaymentProof {
+            signature: signature.to_string(),
+            amount: "0.01".to_string(),
+            token: "USDC".to_string(),
+            mint: None,
+            sender: "gsGBZpMXkp6VsXpe6t81fa2SAnKKkeVBZ8mucAAy7qb".to_string(),
+            memo: "evidence:refund-dberr-001".to_string(),
+            expires_at: None,
+            quote_signature: None,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        }
+        .for_quote(&quote);
+        let response = verify()
+            .header("x-payment", proof.to_header().unwrap())
+            .send()
+            .await
+            .unwrap();
+        sqlx::query("ALTER TABLE outbox_tx_refs_hidden RENAME TO outbox_tx_refs")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+        let body: Value = response.json().await.unwrap();
+        assert_eq!(body["payment"]["refund_eligible"], true);
+        assert!(body.get("details").is_none(), "{}", body);
+
+        let receipt = phoenix_api::db::get_payment_receipt_by_signature(&pool, signature)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(receipt.refund_status.as_deref(), Some("eligible"));
+
+        server.abort();
+    })
+    .await;
+}
+
+async fn get_refunds(port: u16, query: &str) -> Value {
+    let response = reqwest::Client::new()
+        .get(format!(
+            "http://127.0.0.1:{}/api/v1/payments/refunds?{}",
+            port, query
+        ))
+        .bearer_auth(ADMIN_TOKEN)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    response.json().await.unwrap()
+}
+
+#[tokio::test]
+async fn test_unserved_payment_is_refund_eligible() {
+    common::with_api_db_env(|| async {
+        let (server, port, pool) = spawn_receipts_server().await;
+        let client = reqwest::Client::new();
+
+        // A valid payment for evidence that doesn't exist is taken, then refused
+        let verify = || {
+            client
+                .post(format!(
+                    "http://127.0.0.1:{}/api/v1/evidence/verify-premium",
+                    port
+                ))
+                .bearer_auth(ADMIN_TOKEN)
+                .json(&json!({ "evidence_id": "refund-missing-001", "tier": "basic" }))
+        };
+        let response = verify().send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYMENT_REQUIRED);
+        let quote: phoenix_x402::PaymentDetails = response.json().await.unwrap();
+
+        let proof = phoenix_x402::PaymentProof {
+            signature: REFUND_SIGNATURE.to_string(),
+            amount: "0.01".to_string(),
+            token: "USDC".to_string(),
+            mint: None,
+            sender: "gsGBZpMXkp6VsXpe6t81fa2SAnKKkeVBZ8mucAAy7qb".to_string(),
+            memo: "evidence:refund-missing-001".to_string(),
+            expires_at: None,
+            quote_signature: None,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        }
+        .for_quote(&quote);
+        let response = verify()
+            .header("x-payment", proof.to_header().unwrap())
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let body: Value = response.json().await.unwrap();
+        assert_eq!(body["payment"]["refund_eligible"], true);
+
+        let receipt = phoenix_api::db::get_payment_receipt_by_signature(&pool, REFUND_SIGNATURE)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(receipt.refund_status.as_deref(), Some("eligible"));
+
+        // Listed in the refund queue (the default status), not yet as refunded
+        let eligible = get_refunds(port, "").await;
+        assert_eq!(eligible["status"], "eligible");
+        assert!(eligible["data"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|r| r["tx_signature"] == REFUND_SIGNATURE));
+        let refunded = get_refunds(port, "status=refunded").await;
+        assert!(refunded["data"].as_array().unwrap().is_empty());
+
+        // Paying the refund moves it across, and only once
+        let mark = || {
+            client
+                .post(format!(
+                    "http://127.0.0.1:{}/api/v1/payments/refunds/{}/refunded",
+                    port, REFUND_SIGNATURE
+                ))
+                .bearer_auth(ADMIN_TOKEN)
+                .send()
+        };
+        assert_eq!(mark().await.unwrap().status(), StatusCode::OK);
+        assert_eq!(mark().await.unwrap().status(), StatusCode::NOT_FOUND);
+        let refunded = get_refunds(port, "status=refunded").await;
+        assert_eq!(refunded["data"][0]["tx_signature"], REFUND_SIGNATURE);
+        assert_eq!(refunded["data"][0]["refund_status"], "refunded");
+
+        let response = client
+            .get(format!(
+                "http://127.0.0.1:{}/api/v1/payments/refunds?status=pending",
+                port
+            ))
+            .bearer_auth(ADMIN_TOKEN)
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        server.abort();
+    })
+    .await;
+}
@@ -0,0 +1,318 @@
+//! Integration tests for the admin payment reconciliation report
+
+mod common;
+
+use async_trait::async_trait;
+use phoenix_api::{
+    db::create_payment_receipt,
+    handlers_x402::X402State,
+    models::PaymentReceiptOut,
+    reconciliation::{compare_receipt, OnChainPayment, PaymentVerifier},
+    AppState,
+};
+use phoenix_x402::{PaymentReceipt, PriceTier};
+use reqwest::StatusCode;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+const WALLET: &str = "PhxRvkTreasury111111111111111111111111111111";
+const ADMIN_TOKEN: &str = "test-admin-token";
+
+/// A USDC payment of `amount` as redeemed through the replay store
+fn receipt(
+    evidence_id: &str,
+    signature: &str,
+    amount: &str,
+    tier: PriceTier,
+    sender: &str,
+) -> PaymentReceipt {
+    PaymentReceipt {
+        tx_signature: signature.to_string(),
+        evidence_id: evidence_id.to_string(),
+        tier,
+        token: "USDC".to_string(),
+        token_amount: amount.to_string(),
+        amount_usdc: amount.to_string(),
+        overpaid_usdc: None,
+        sender_wallet: Some(sender.to_string()),
+    }
+}
+
+/// Verifier that answers from a fixed table keyed by transaction signature
+struct MockVerifier {
+    payments: HashMap<String, OnChainPayment>,
+}
+
+#[async_trait]
+impl PaymentVerifier for MockVerifier {
+    async fn lookup(&self, receipt: &PaymentReceiptOut) -> Result<OnChainPayment, String> {
+        self.payments
+            .get(&receipt.tx_signature)
+            .cloned()
+            .ok_or_else(|| format!("transaction {} not found", receipt.tx_signature))
+    }
+}
+
+/// Build a server over the migrated test DB with the given verifier
+async fn spawn_reconcile_server(
+    payments: HashMap<String, OnChainPayment>,
+) -> (tokio::task::JoinHandle<()>, u16, sqlx::Pool<sqlx::Sqlite>) {
+    let (_app, pool) = phoenix_api::build_app().await.unwrap();
+    let state = AppState {
+        x402: Some(X402State::devnet(WALLET, &pool)),
+        payment_verifier: Some(Arc::new(MockVerifier { payments })),
+        admin_token: Some(ADMIN_TOKEN.to_string()),
+        ..common::test_state(&pool)
+    };
+
+    let (listener, _) = common::create_test_listener();
+    let (server, port) = common::spawn_test_server(phoenix_api::router(state), listener).await;
+    (server, port, pool)
+}
+
+async fn get_report(port: u16, from: i64, token: Option<&str>) -> reqwest::Response {
+    let mut request = reqwest::Client::new().get(format!(
+        "http://127.0.0.1:{}/admin/payments/reconcile?from={}",
+        port, from
+    ));
+    if let Some(token) = token {
+        request = request.bearer_auth(token);
+    }
+    request.send().await.unwrap()
+}
+
+#[tokio::test]
+async fn test_reconcile_matching_receipt() {
+    common::with_api_db_env(|| async {
+        let payments = HashMap::from([(
+            "recon-match-sig".to_string(),
+            OnChainPayment {
+                confirmed: true,
+                amount_usdc: Some("0.050".to_string()),
+                recipient: Some(WALLET.to_string()),
+            },
+        )]);
+        let (server, port, pool) = spawn_reconcile_server(payments).await;
+
+        let from = chrono::Utc::now().timestamp_millis();
+        create_payment_receipt(
+            &pool,
+            &receipt(
+                "evt-recon-match",
+                "recon-match-sig",
+                "0.05",
+                PriceTier::MultiChain,
+                "sender1",
+            ),
+        )
+        .await
+        .unwrap();
+
+        let response = get_report(port, from, Some(ADMIN_TOKEN)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let report: Value = response.json().await.unwrap();
+
+        assert_eq!(report["totals"]["receipts"], 1);
+        assert_eq!(report["totals"]["matched"], 1);
+        assert_eq!(report["totals"]["mismatched"], 0);
+        assert_eq!(report["totals"]["recorded_amount_usdc"], "0.050000");
+        assert_eq!(report["totals"]["on_chain_amount_usdc"], "0.050000");
+        let receipt = &report["receipts"][0];
+        assert_eq!(receipt["tx_signature"], "recon-match-sig");
+        assert_eq!(receipt["status"], "matched");
+        assert_eq!(receipt["discrepancies"].as_array().unwrap().len(), 0);
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_reconcile_flags_mismatched_receipt() {
+    common::with_api_db_env(|| async {
+        let payments = HashMap::from([(
+            "recon-mismatch-sig".to_string(),
+            OnChainPayment {
+                confirmed: true,
+                amount_usdc: Some("0.01".to_string()),
+                recipient: Some("SomeoneElse1111111111111111111111111111111".to_string()),
+            },
+        )]);
+        let (server, port, pool) = spawn_reconcile_server(payments).await;
+
+        let from = chrono::Utc::now().timestamp_millis();
+        create_payment_receipt(
+            &pool,
+            &receipt(
+                "evt-recon-mismatch",
+                "recon-mismatch-sig",
+                "1.00",
+                PriceTier::LegalAttestation,
+                "sender2",
+            ),
+        )
+        .await
+        .unwrap();
+
+        let response = get_report(port, from, Some(ADMIN_TOKEN)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let report: Value = response.json().await.unwrap();
+
+        assert_eq!(report["totals"]["receipts"], 1);
+        assert_eq!(report["totals"]["matched"], 0);
+        assert_eq!(report["totals"]["mismatched"], 1);
+
+        let receipt = &report["receipts"][0];
+        assert_eq!(receipt["status"], "mismatched");
+        let discrepancies = receipt["discrepancies"].as_array().unwrap();
+        let amount = discrepancies
+            .iter()
+            .find(|d| d["field"] == "amount_usdc")
+            .expect("amount discrepancy reported");
+        assert_eq!(amount["recorded"], "1.00");
+        assert_eq!(amount["on_chain"], "0.01");
+        assert!(discrepancies.iter().any(|d| d["field"] == "recipient"));
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_reconcile_compares_amounts_exactly() {
+    common::with_api_db_env(|| async {
+        // Both amounts are the same f64; they differ by one micro-USDC
+        let payments = HashMap::from([(
+            "recon-exact-sig".to_string(),
+            OnChainPayment {
+                confirmed: true,
+                amount_usdc: Some("17179869184.000000".to_string()),
+                recipient: Some(WALLET.to_string()),
+            },
+        )]);
+        let (server, port, pool) = spawn_reconcile_server(payments).await;
+
+        let from = chrono::Utc::now().timestamp_millis();
+        create_payment_receipt(
+            &pool,
+            &receipt(
+                "evt-recon-exact",
+                "recon-exact-sig",
+                "17179869184.000001",
+                PriceTier::LegalAttestation,
+                "sender4",
+            ),
+        )
+        .await
+        .unwrap();
+
+        let response = get_report(port, from, Some(ADMIN_TOKEN)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let report: Value = response.json().await.unwrap();
+
+        assert_eq!(report["totals"]["mismatched"], 1);
+        assert_eq!(
+            report["totals"]["recorded_amount_usdc"],
+            "17179869184.000001"
+        );
+        assert_eq!(
+            report["totals"]["on_chain_amount_usdc"],
+            "17179869184.000000"
+        );
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_reconcile_requires_admin_token() {
+    common::with_api_db_env(|| async {
+        let (server, port, _pool) = spawn_reconcile_server(HashMap::new()).await;
+
+        let response = get_report(port, 0, None).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = get_report(port, 0, Some("wrong-token")).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_facilitator_lookup_rechecks_in_the_token_paid() {
+    common::with_api_db_env(|| async {
+        let (_app, pool) = phoenix_api::build_app().await.unwrap();
+        let sol_payment = PaymentReceipt {
+            token: "SOL".to_string(),
+            token_amount: "0.0004".to_string(),
+            ..receipt(
+                "evt-recon-sol",
+                "recon-sol-sig",
+                "0.05",
+                PriceTier::MultiChain,
+                "sender3",
+            )
+        };
+        create_payment_receipt(&pool, &sol_payment).await.unwrap();
+        let stored = phoenix_api::db::get_payment_receipt_by_signature(&pool, "recon-sol-sig")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.token, "SOL");
+        assert_eq!(stored.token_amount.as_deref(), Some("0.0004"));
+
+        // Facilitator confirming the SOL transfer, recording what it was asked
+        let received: Arc<std::sync::Mutex<Vec<Value>>> = Arc::default();
+        let app = axum::Router::new()
+            .route(
+                "/verify",
+                axum::routing::post(
+                    |axum::extract::State(received): axum::extract::State<
+                        Arc<std::sync::Mutex<Vec<Value>>>,
+                    >,
+                     axum::Json(body): axum::Json<Value>| async move {
+                        received.lock().unwrap().push(body);
+                        axum::Json(serde_json::json!({
+                            "valid": true,
+                            "amount": "0.0004",
+                            "recipient": "SomeoneElse1111111111111111111111111111111",
+                            "block": 123456,
+                            "confirmed_at": chrono::Utc::now().to_rfc3339()
+                        }))
+                    },
+                ),
+            )
+            .with_state(received.clone());
+        let (listener, port) = common::create_test_listener();
+        let (facilitator_server, _) = common::spawn_test_server(app, listener).await;
+
+        let mut config = phoenix_x402::X402Config::mainnet(WALLET);
+        config.facilitator_url = format!("http://127.0.0.1:{}", port);
+        config.sol_price_usdc = Some("125".to_string());
+        let verifier = phoenix_api::reconciliation::FacilitatorPaymentVerifier::new(
+            phoenix_x402::X402Facilitator::new(config),
+        );
+
+        let on_chain = verifier.lookup(&stored).await.unwrap();
+        assert!(on_chain.confirmed);
+        assert_eq!(on_chain.amount_usdc.as_deref(), Some("0.05"));
+        // The recipient is what the facilitator saw on chain, not our wallet
+        assert_eq!(
+            on_chain.recipient.as_deref(),
+            Some("SomeoneElse1111111111111111111111111111111")
+        );
+        assert!(compare_receipt(&stored, &on_chain, WALLET)
+            .iter()
+            .any(|d| d.field == "recipient"));
+        let sent = received.lock().unwrap()[0].clone();
+        assert_eq!(sent["token"], "SOL");
+        assert_eq!(sent["min_amount"], "0.0004");
+
+        facilitator_server.abort();
+    })
+    .await;
+}
@@ -0,0 +1,69 @@
+//! Request id assignment, propagation and echo in error bodies
+
+mod common;
+
+use phoenix_api::build_app;
+use serde_json::Value;
+
+#[tokio::test]
+async fn test_request_id_is_assigned_and_inbound_id_preserved() {
+    common::with_api_db_env(|| async {
+        let (app, _pool) = build_app().await.unwrap();
+        let (listener, port) = common::create_test_listener();
+        let (server, _) = common::spawn_test_server(app, listener).await;
+        let client = reqwest::Client::new();
+        let url = format!("http://127.0.0.1:{}/health", port);
+
+        let response = client.get(&url).send().await.unwrap();
+        let assigned = response.headers()["x-request-id"].to_str().unwrap();
+        assert!(uuid::Uuid::parse_str(assigned).is_ok(), "{}", assigned);
+
+        let response = client
+            .get(&url)
+            .header("X-Request-Id", "gateway-req-42")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.headers()["x-request-id"], "gateway-req-42");
+
+        // Ids that could not be echoed safely are replaced
+        let response = client
+            .get(&url)
+            .header("X-Request-Id", "x".repeat(500))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.headers()["x-request-id"].len(), 36);
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_error_body_carries_request_id() {
+    common::with_api_db_env(|| async {
+        let (app, _pool) = build_app().await.unwrap();
+        let (listener, port) = common::create_test_listener();
+        let (server, _) = common::spawn_test_server(app, listener).await;
+
+        let response = reqwest::Client::new()
+            .get(format!(
+                "http://127.0.0.1:{}/evidence/no-such-evidence",
+                port
+            ))
+            .header("X-Request-Id", "trace-404")
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 404);
+        assert_eq!(response.headers()["x-request-id"], "trace-404");
+        let body: Value = response.json().await.unwrap();
+        assert_eq!(body["request_id"], "trace-404");
+        // The handler's own fields are untouched
+        assert_eq!(body["id"], "no-such-evidence");
+
+        server.abort();
+    })
+    .await;
+}
@@ -0,0 +1,92 @@
+//! Request body size and timeout limits
+
+mod common;
+
+use axum::{routing::get, Router};
+use phoenix_api::{build_app, limits::RequestLimits};
+use reqwest::StatusCode;
+use serde_json::json;
+use std::time::Duration;
+
+#[tokio::test]
+async fn test_oversized_bodies_are_rejected() {
+    common::with_api_db_env(|| async {
+        let (app, _pool) = build_app().await.unwrap();
+        let (listener, port) = common::create_test_listener();
+        let (server, _) = common::spawn_test_server(app, listener).await;
+        let client = reqwest::Client::new();
+
+        let evidence = |notes: String| {
+            client
+                .post(format!("http://127.0.0.1:{}/evidence", port))
+                .json(&json!({
+                    "digest_hex": "ab".repeat(32),
+                    "metadata": { "notes": notes }
+                }))
+                .send()
+        };
+
+        let response = evidence("x".repeat(1024)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // Past the 256 KiB default
+        let response = evidence("x".repeat(300 * 1024)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        // Premium verification has its own, tighter cap
+        let response = client
+            .post(format!(
+                "http://127.0.0.1:{}/api/v1/evidence/verify-premium",
+                port
+            ))
+            .header("authorization", "Bearer test-api-token")
+            .json(&json!({
+                "evidence_id": "x".repeat(32 * 1024),
+                "tier": "basic"
+            }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_slow_requests_time_out() {
+    let app = Router::new()
+        .route("/fast", get(|| async { "done" }))
+        .route(
+            "/slow",
+            get(|| async {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                "done"
+            }),
+        );
+    let app = RequestLimits {
+        timeout: Duration::from_millis(100),
+        ..RequestLimits::default()
+    }
+    .apply(app);
+    let (listener, port) = common::create_test_listener();
+    let (server, _) = common::spawn_test_server(app, listener).await;
+    let client = reqwest::Client::new();
+
+    let response = client
+        .get(format!("http://127.0.0.1:{}/fast", port))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = client
+        .get(format!("http://127.0.0.1:{}/slow", port))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::REQUEST_TIMEOUT);
+
+    server.abort();
+}
@@ -0,0 +1,168 @@
+//! Sharing a paid premium verification through a one-time result token
+
+mod common;
+
+use phoenix_api::{
+    db::create_evidence_job, handlers_x402::X402State, models::EvidenceIn,
+    result_tokens::ResultTokenSigner, AppState,
+};
+use phoenix_x402::{PaymentDetails, PaymentProof};
+use reqwest::StatusCode;
+use serde_json::{json, Value};
+
+const WALLET: &str = "PhxRvkTreasury111111111111111111111111111111";
+const TOKEN: &str = "test-api-token";
+const SIGNATURE: &str =
+    "2EhMxpWxmAA62kaUxGmFSefCgTNbUuA94raiShAspopV8NK7DqE752Ww4iH4qSVUznvtVvmUhB7jDKj4HsQ3kfSF";
+const SECRET: &[u8] = b"test-result-token-secret";
+
+async fn spawn_server() -> (tokio::task::JoinHandle<()>, u16, sqlx::Pool<sqlx::Sqlite>) {
+    let (_app, pool) = phoenix_api::build_app().await.unwrap();
+    let mut x402 = X402State::devnet(WALLET, &pool);
+    x402.result_tokens = ResultTokenSigner::new(SECRET);
+    let state = AppState {
+        x402: Some(x402),
+        ..common::test_state(&pool)
+    };
+
+    let (listener, _) = common::create_test_listener();
+    let (server, port) = common::spawn_test_server(phoenix_api::router(state), listener).await;
+    (server, port, pool)
+}
+
+async fn get_shared(port: u16, token: &str) -> reqwest::Response {
+    reqwest::get(format!(
+        "http://127.0.0.1:{}/api/v1/verifications/{}",
+        port, token
+    ))
+    .await
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_result_token_fetches_paid_verification_once() {
+    common::with_api_db_env(|| async {
+        let (server, port, pool) = spawn_server().await;
+        let evidence = EvidenceIn {
+            id: Some("result-token-ev-1".to_string()),
+            digest_hex: format!("{:0>64}", "7e57"),
+            payload_mime: None,
+            metadata: None,
+            anchor_mode: None,
+            source: None,
+            priority: None,
+            payload_base64: None,
+        };
+        create_evidence_job(&pool, &evidence, "test").await.unwrap();
+
+        let verify = || {
+            reqwest::Client::new()
+                .post(format!(
+                    "http://127.0.0.1:{}/api/v1/evidence/verify-premium",
+                    port
+                ))
+                .bearer_auth(TOKEN)
+                .header("x-forwarded-for", "10.0.8.1")
+                .json(&json!({ "evidence_id": "result-token-ev-1", "tier": "basic" }))
+        };
+        let response = verify().send().await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYMENT_REQUIRED);
+        let quote: PaymentDetails = response.json().await.unwrap();
+
+        let proof = PaymentProof {
+            signature: SIGNATURE.to_string(),
+            amount: "0.01".to_string(),
+            token: "USDC".to_string(),
+            mint: None,
+            sender: "gsGBZpMXkp6VsXpe6t81fa2SAnKKkeVBZ8mucAAy7qb".to_string(),
+            memo: "evidence:result-token-ev-1".to_string(),
+            expires_at: None,
+            quote_signature: None,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        }
+        .for_quote(&quote);
+        let response = verify()
+            .header("x-payment", proof.to_header().unwrap())
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let paid: Value = response.json().await.unwrap();
+        let token = paid["result_token"].as_str().unwrap().to_string();
+        assert!(paid["result_token_expires_at"].is_string());
+
+        // A forged MAC or a token under another key is refused
+        let (unsigned, _mac) = token.rsplit_once('.').unwrap();
+        let forged = format!("{}.{}", unsigned, "00".repeat(32));
+        assert_eq!(
+            get_shared(port, &forged).await.status(),
+            StatusCode::UNAUTHORIZED
+        );
+        let other_key = ResultTokenSigner::new(b"other-secret").mint(
+            unsigned.split('.').next().unwrap(),
+            chrono::Utc::now().timestamp_millis() + 60_000,
+        );
+        assert_eq!(
+            get_shared(port, &other_key).await.status(),
+            StatusCode::UNAUTHORIZED
+        );
+
+        // The real token returns the paid result, once
+        let response = get_shared(port, &token).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let shared: Value = response.json().await.unwrap();
+        assert_eq!(shared["verification"], paid["verification"]);
+        assert_eq!(shared["verification"]["evidence_id"], "result-token-ev-1");
+        assert_eq!(shared["payment"]["tx_signature"], SIGNATURE);
+        assert_eq!(
+            get_shared(port, &token).await.status(),
+            StatusCode::NOT_FOUND
+        );
+
+        // A validly signed token for a result that was never stored
+        let unknown = ResultTokenSigner::new(SECRET).mint(
+            "result-token-unknown",
+            chrono::Utc::now().timestamp_millis() + 60_000,
+        );
+        assert_eq!(
+            get_shared(port, &unknown).await.status(),
+            StatusCode::NOT_FOUND
+        );
+
+        server.abort();
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_expired_result_token_is_refused() {
+    common::with_api_db_env(|| async {
+        let (server, port, pool) = spawn_server().await;
+        let expired_ms = chrono::Utc::now().timestamp_millis() - 1;
+        phoenix_api::db::store_verification_result(
+            &pool,
+            "result-token-expired",
+            "result-token-ev-2",
+            &json!({ "verification": { "verified": true } }),
+            chrono::Utc::now().timestamp_millis() + 60_000,
+        )
+        .await
+        .unwrap();
+
+        let expired = ResultTokenSigner::new(SECRET).mint("result-token-expired", expired_ms);
+        let response = get_shared(port, &expired).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let body: Value = response.json().await.unwrap();
+        assert_eq!(body["error"], "result token expired");
+
+        for garbage in ["not-a-token", "a.b.c", "a.1.zz"] {
+            assert_eq!(
+                get_shared(port, garbage).await.status(),
+                StatusCode::UNAUTHORIZED
+            );
+        }
+
+        server.abort();
+    })
+    .await;
+}
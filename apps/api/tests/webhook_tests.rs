@@ -0,0 +1,223 @@
+//! Webhook delivery against a mock receiver: body, signature header, retries
+//! and the `evidence.anchored` event from sync anchoring.
+
+mod common;
+
+use async_trait::async_trait;
+use axum::{body::Bytes, extract::State, http::HeaderMap, http::StatusCode, routing::post, Router};
+use phoenix_api::{
+    anchoring::SyncAnchor,
+    webhooks::{
+        sign_payload, WebhookDispatcher, WebhookEvent, WebhookEventType, EVENT_HEADER,
+        SIGNATURE_HEADER,
+    },
+    AppState,
+};
+use phoenix_evidence::anchor::{AnchorError, AnchorProvider};
+use phoenix_evidence::model::{ChainTxRef, EvidenceRecord};
+use serde_json::{json, Value};
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc, Mutex,
+};
+use std::time::Duration;
+
+const SECRET: &str = "whsec-test";
+
+/// A request seen by the mock receiver: (event header, signature header, raw body)
+type Delivery = (String, String, Vec<u8>);
+
+#[derive(Clone, Default)]
+struct Receiver {
+    deliveries: Arc<Mutex<Vec<Delivery>>>,
+    /// Number of initial requests to answer with 500
+    failures: Arc<AtomicUsize>,
+}
+
+/// Start a mock webhook receiver that fails the first `failures` requests
+async fn mock_receiver(failures: usize) -> (String, Receiver) {
+    let receiver = Receiver::default();
+    receiver.failures.store(failures, Ordering::SeqCst);
+    let app = Router::new()
+        .route(
+            "/hook",
+            post(
+                |State(receiver): State<Receiver>, headers: HeaderMap, body: Bytes| async move {
+                    let header = |name: &str| {
+                        headers
+                            .get(name)
+                            .and_then(|v| v.to_str().ok())
+                            .unwrap_or_default()
+                            .to_string()
+                    };
+                    receiver.deliveries.lock().unwrap().push((
+                        header(EVENT_HEADER),
+                        header(SIGNATURE_HEADER),
+                        body.to_vec(),
+                    ));
+                    let failing = receiver
+                        .failures
+                        .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                        .is_ok();
+                    if failing {
+                        StatusCode::INTERNAL_SERVER_ERROR
+                    } else {
+                        StatusCode::NO_CONTENT
+                    }
+                },
+            ),
+        )
+        .with_state(receiver.clone());
+
+    let (listener, port) = common::create_test_listener();
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    (format!("http://127.0.0.1:{}/hook", port), receiver)
+}
+
+fn dispatcher(url: &str) -> WebhookDispatcher {
+    WebhookDispatcher::new(url, SECRET).with_retry_delay(Duration::from_millis(10))
+}
+
+#[tokio::test]
+async fn test_event_is_posted_with_hmac_signature() {
+    let (url, receiver) = mock_receiver(0).await;
+
+    let event = WebhookEvent::new(
+        WebhookEventType::PaymentVerified,
+        json!({ "evidence_id": "evt-hook-1", "tx_signature": "sig-hook-1" }),
+    );
+    let delivered = dispatcher(&url).dispatch(event.clone()).await.unwrap();
+    assert!(delivered);
+
+    let deliveries = receiver.deliveries.lock().unwrap();
+    assert_eq!(deliveries.len(), 1);
+    let (event_header, signature, body) = &deliveries[0];
+    assert_eq!(event_header, "payment.verified");
+    assert_eq!(
+        signature,
+        &format!("sha256={}", sign_payload(SECRET.as_bytes(), body))
+    );
+
+    let body: Value = serde_json::from_slice(body).unwrap();
+    assert_eq!(body["id"], event.id);
+    assert_eq!(body["type"], "payment.verified");
+    assert_eq!(body["data"]["evidence_id"], "evt-hook-1");
+    assert_eq!(body["data"]["tx_signature"], "sig-hook-1");
+}
+
+#[test]
+fn test_signature_matches_known_hmac_vector() {
+    // RFC 4231 test case 2
+    assert_eq!(
+        sign_payload(b"Jefe", b"what do ya want for nothing?"),
+        "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+    );
+}
+
+#[tokio::test]
+async fn test_failed_delivery_is_retried() {
+    let (url, receiver) = mock_receiver(2).await;
+
+    let event = WebhookEvent::new(WebhookEventType::PaymentVerified, json!({}));
+    let delivered = dispatcher(&url)
+        .with_max_attempts(3)
+        .dispatch(event)
+        .await
+        .unwrap();
+
+    assert!(delivered);
+    let deliveries = receiver.deliveries.lock().unwrap();
+    assert_eq!(deliveries.len(), 3);
+    // Every retry carries the same body and signature
+    assert!(deliveries.iter().all(|d| d == &deliveries[0]));
+}
+
+#[tokio::test]
+async fn test_delivery_gives_up_after_max_attempts() {
+    let (url, receiver) = mock_receiver(usize::MAX).await;
+
+    let event = WebhookEvent::new(WebhookEventType::PaymentVerified, json!({}));
+    let delivered = dispatcher(&url)
+        .with_max_attempts(2)
+        .dispatch(event)
+        .await
+        .unwrap();
+
+    assert!(!delivered);
+    assert_eq!(receiver.deliveries.lock().unwrap().len(), 2);
+}
+
+/// Anchor provider that answers immediately
+struct InstantAnchor;
+
+#[async_trait]
+impl AnchorProvider for InstantAnchor {
+    async fn anchor(&self, evidence: &EvidenceRecord) -> Result<ChainTxRef, AnchorError> {
+        Ok(ChainTxRef {
+            network: "etherlink".to_string(),
+            chain: "testnet".to_string(),
+            tx_id: format!("0x{}", evidence.digest.hex),
+            confirmed: false,
+            timestamp: Some(chrono::Utc::now()),
+        })
+    }
+
+    async fn confirm(&self, tx: &ChainTxRef) -> Result<ChainTxRef, AnchorError> {
+        Ok(tx.clone())
+    }
+}
+
+#[tokio::test]
+async fn test_sync_anchoring_emits_evidence_anchored() {
+    common::with_api_db_env(|| async {
+        let (url, receiver) = mock_receiver(0).await;
+        let (_app, pool) = phoenix_api::build_app().await.unwrap();
+        let state = AppState {
+            sync_anchor: Some(SyncAnchor::new(
+                Arc::new(InstantAnchor),
+                Duration::from_secs(5),
+            )),
+            webhooks: Some(dispatcher(&url)),
+            ..common::test_state(&pool)
+        };
+        let (listener, _) = common::create_test_listener();
+        let (server, port) = common::spawn_test_server(phoenix_api::router(state), listener).await;
+
+        let digest = "cd".repeat(32);
+        let response = reqwest::Client::new()
+            .post(format!("http://127.0.0.1:{}/evidence", port))
+            .json(&json!({
+                "id": "webhook-anchored",
+                "digest_hex": digest,
+                "anchor_mode": "sync"
+            }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), 200);
+
+        // Delivery happens off the request path; wait for it
+        let mut delivery = None;
+        for _ in 0..50 {
+            if let Some(d) = receiver.deliveries.lock().unwrap().first().cloned() {
+                delivery = Some(d);
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        let (event_header, signature, body) = delivery.expect("webhook was not delivered");
+        assert_eq!(event_header, "evidence.anchored");
+        assert_eq!(
+            signature,
+            format!("sha256={}", sign_payload(SECRET.as_bytes(), &body))
+        );
+        let body: Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(body["data"]["evidence_id"], "webhook-anchored");
+        assert_eq!(body["data"]["tx_ref"]["tx_id"], format!("0x{}", digest));
+
+        server.abort();
+    })
+    .await;
+}
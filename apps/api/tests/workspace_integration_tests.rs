@@ -112,12 +112,16 @@ async fn test_api_workflow() {
     // Test evidence creation
     let evidence = EvidenceIn {
         id: Some("api-workflow-test".to_string()),
-        digest_hex: "api-test-hash".to_string(),
+        digest_hex: "ab".repeat(32),
         payload_mime: Some("application/json".to_string()),
         metadata: Some(json!({
             "test": "api_workflow",
             "timestamp": Utc::now().timestamp()
         })),
+        anchor_mode: None,
+        source: None,
+        priority: None,
+        payload_base64: None,
     };
 
     let job_id = repo.create_evidence_job(&evidence).await.unwrap();
@@ -224,9 +228,13 @@ async fn test_system_error_handling() {
     // Test duplicate job creation
     let evidence = EvidenceIn {
         id: Some("error-test".to_string()),
-        digest_hex: "test-hash".to_string(),
+        digest_hex: "cd".repeat(32),
         payload_mime: None,
         metadata: None,
+        anchor_mode: None,
+        source: None,
+        priority: None,
+        payload_base64: None,
     };
 
     // First creation should succeed
@@ -435,9 +443,13 @@ async fn test_pagination_and_statistics() {
     for i in 0..10 {
         let evidence = EvidenceIn {
             id: Some(format!("pagination-test-{}", i)),
-            digest_hex: format!("hash-{}", i),
+            digest_hex: format!("{:064x}", i),
             payload_mime: None,
             metadata: None,
+            anchor_mode: None,
+            source: None,
+            priority: None,
+            payload_base64: None,
         };
         repo.create_evidence_job(&evidence).await.unwrap();
     }
@@ -530,6 +542,10 @@ async fn test_api_to_keeper_cross_app_flow() {
         digest_hex: "a1b2c3d4e5f60718293a4b5c6d7e8f90a1b2c3d4e5f60718293a4b5c6d7e8f90".to_string(),
         payload_mime: Some("application/json".to_string()),
         metadata: Some(json!({ "source": "cross-app-test" })),
+        anchor_mode: None,
+        source: None,
+        priority: None,
+        payload_base64: None,
     };
     let job_id = repo.create_evidence_job(&evidence_in).await.unwrap();
     assert_eq!(job_id, "cross-app-e2e-001");
@@ -618,9 +634,13 @@ async fn test_api_to_keeper_failure_and_retry() {
     let repo = EvidenceRepository::new(pool.clone());
     let evidence_in = EvidenceIn {
         id: Some("retry-test-001".to_string()),
-        digest_hex: "retry-hash-001".to_string(),
+        digest_hex: "ef".repeat(32),
         payload_mime: None,
         metadata: None,
+        anchor_mode: None,
+        source: None,
+        priority: None,
+        payload_base64: None,
     };
     repo.create_evidence_job(&evidence_in).await.unwrap();
 
@@ -16,6 +16,9 @@ static TEST_MUTEX: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
 /// The x402 premium verification endpoint requires Bearer token auth
 const TEST_BEARER_TOKEN: &str = "Bearer test-api-token";
 
+/// Payer wallet for test payment proofs
+const SENDER: &str = "gsGBZpMXkp6VsXpe6t81fa2SAnKKkeVBZ8mucAAy7qb";
+
 /// Test context that properly cleans up resources when dropped
 struct TestContext {
     base_url: String,
@@ -23,6 +26,25 @@ struct TestContext {
     env_vars: Vec<(String, Option<String>)>, // Track original values for restoration
 }
 
+/// Quote secret the x402-enabled test servers sign with
+const TEST_QUOTE_SECRET: &str = "x402-tests-quote-secret";
+
+/// `proof` echoing a quote for its memo as the server would sign it,
+/// expiring in five minutes unless it already names an expiry
+fn signed(proof: phoenix_x402::PaymentProof) -> phoenix_x402::PaymentProof {
+    let expires_at = proof
+        .expires_at
+        .clone()
+        .unwrap_or_else(|| (chrono::Utc::now() + chrono::Duration::minutes(5)).to_rfc3339());
+    phoenix_x402::PaymentProof {
+        quote_signature: Some(
+            phoenix_x402::QuoteSigner::new(TEST_QUOTE_SECRET).sign(&proof.memo, &expires_at),
+        ),
+        expires_at: Some(expires_at),
+        ..proof
+    }
+}
+
 impl TestContext {
     /// Create a new test context with a running server
     async fn new() -> Self {
@@ -45,6 +67,7 @@ impl TestContext {
 
         if enabled {
             set_env("X402_ENABLED", "true");
+            set_env("X402_QUOTE_SECRET", TEST_QUOTE_SECRET);
 
             if let Some(addr) = wallet {
                 set_env("X402_WALLET_ADDRESS", addr);
@@ -252,6 +275,63 @@ async fn test_x402_price_tiers() {
     assert_eq!(body["price"], "1.00");
 }
 
+/// Test bulk tier charges by item count and publishes its pricing formula
+#[tokio::test]
+async fn test_x402_bulk_price_scales_with_count() {
+    let _guard = TEST_MUTEX.lock().await;
+    let ctx = TestContext::with_x402(true, Some("PhxRvkTestWalletBulk")).await;
+    let client = reqwest::Client::new();
+
+    // Distinct client IPs so the bulk tier's per-IP rate limit doesn't interfere
+    let mut prices = Vec::new();
+    for count in [10, 100, 1000] {
+        let response = client
+            .post(ctx.url("/api/v1/evidence/verify-premium"))
+            .header("authorization", TEST_BEARER_TOKEN)
+            .header("x-forwarded-for", format!("10.0.8.{}", count % 251))
+            .json(&json!({
+                "evidence_id": format!("test-bulk-{}", count),
+                "tier": "bulk",
+                "count": count
+            }))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::PAYMENT_REQUIRED);
+        let body: Value = response.json().await.unwrap();
+        prices.push(body["price"].as_str().unwrap().to_string());
+    }
+    // $0.05 base + $0.005/item, 10% off at 100+, 25% off at 1000+
+    assert_eq!(prices, ["0.10", "0.50", "3.80"]);
+
+    let response = client
+        .post(ctx.url("/api/v1/evidence/verify-premium"))
+        .header("authorization", TEST_BEARER_TOKEN)
+        .header("x-forwarded-for", "10.0.8.250")
+        .json(&json!({
+            "evidence_id": "test-bulk-0",
+            "tier": "bulk",
+            "count": 0
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = client
+        .get(ctx.url("/api/v1/x402/status"))
+        .send()
+        .await
+        .unwrap();
+    let body: Value = response.json().await.unwrap();
+    let pricing = &body["price_tiers"]["bulk"]["pricing"];
+    assert_eq!(pricing["base"], 50_000);
+    assert_eq!(pricing["per_item"], 5_000);
+    assert_eq!(pricing["discounts"][0]["min_items"], 100);
+    assert_eq!(pricing["discounts"][0]["discount_bps"], 1_000);
+    assert!(pricing["formula"].is_string());
+}
+
 /// Test rate limiting module unit tests
 #[tokio::test]
 async fn test_rate_limiter_unit() {
@@ -317,3 +397,328 @@ async fn test_x402_rate_limiting_headers() {
 
     assert_eq!(response.status(), StatusCode::OK);
 }
+
+/// Test that a 429 tells the client how long to back off, in header and body
+#[tokio::test]
+async fn test_x402_rate_limited_response_has_retry_after() {
+    let _guard = TEST_MUTEX.lock().await;
+    let original = std::env::var("X402_RATE_LIMIT_STATUS_PER_MIN").ok();
+    std::env::set_var("X402_RATE_LIMIT_STATUS_PER_MIN", "1");
+    let ctx = TestContext::with_x402(true, Some("PhxRvkTestWalletRetry")).await;
+    match original {
+        Some(val) => std::env::set_var("X402_RATE_LIMIT_STATUS_PER_MIN", val),
+        None => std::env::remove_var("X402_RATE_LIMIT_STATUS_PER_MIN"),
+    }
+
+    let client = reqwest::Client::new();
+    let status = || {
+        client
+            .get(ctx.url("/api/v1/x402/status"))
+            .header("x-forwarded-for", "10.0.10.1")
+            .send()
+    };
+
+    assert_eq!(status().await.unwrap().status(), StatusCode::OK);
+    let response = status().await.unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+    // One request per minute: the bucket refills within a minute
+    let header: u64 = response
+        .headers()
+        .get("retry-after")
+        .expect("Retry-After header")
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!((1..=60).contains(&header), "retry-after {}", header);
+
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["error"], "rate_limited");
+    assert_eq!(body["retry_after_secs"].as_u64(), Some(header));
+}
+
+/// Test that a tier with a low limit is throttled before a tier with a higher one
+#[tokio::test]
+async fn test_x402_per_tier_rate_limits() {
+    let _guard = TEST_MUTEX.lock().await;
+    let original = std::env::var("X402_RATE_LIMIT_BULK_PER_MIN").ok();
+    std::env::set_var("X402_RATE_LIMIT_BULK_PER_MIN", "1");
+    let ctx = TestContext::with_x402(true, Some("PhxRvkTestWalletTier")).await;
+    match original {
+        Some(val) => std::env::set_var("X402_RATE_LIMIT_BULK_PER_MIN", val),
+        None => std::env::remove_var("X402_RATE_LIMIT_BULK_PER_MIN"),
+    }
+
+    let client = reqwest::Client::new();
+    let verify = |tier: &'static str| {
+        client
+            .post(ctx.url("/api/v1/evidence/verify-premium"))
+            .header("x-forwarded-for", "10.0.9.1")
+            .header("authorization", TEST_BEARER_TOKEN)
+            .json(&json!({
+                "evidence_id": "tier-rate-test-001",
+                "tier": tier
+            }))
+            .send()
+    };
+
+    // Bulk allows a single request, then throttles with a tier-specific 429
+    assert_eq!(
+        verify("bulk").await.unwrap().status(),
+        StatusCode::PAYMENT_REQUIRED
+    );
+    let response = verify("bulk").await.unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert!(response.headers().contains_key("retry-after"));
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["tier"], "bulk");
+    assert_eq!(body["error"], "rate_limited");
+    assert!(body["retry_after_secs"].as_u64().unwrap() >= 1);
+
+    // Basic for the same client is unaffected
+    assert_eq!(
+        verify("basic").await.unwrap().status(),
+        StatusCode::PAYMENT_REQUIRED
+    );
+    assert_eq!(
+        verify("basic").await.unwrap().status(),
+        StatusCode::PAYMENT_REQUIRED
+    );
+}
+
+/// Test that an underpayment is refused with the shortfall stated
+#[tokio::test]
+async fn test_x402_underpayment_states_shortfall() {
+    let _guard = TEST_MUTEX.lock().await;
+    let ctx = TestContext::with_x402(true, Some("PhxRvkTestWalletShort")).await;
+    let client = reqwest::Client::new();
+
+    let proof = phoenix_x402::PaymentProof {
+        signature: "5ivC3aygpPjezqAEQ9VmCJnhv8J7nq9JMGd8xLKQvJn8pBvJy6wEToqcJTKwVFZuu35DsDJDVcLc2kR1jZkb93om".to_string(),
+        amount: "0.03".to_string(),
+        token: "USDC".to_string(),
+        mint: None,
+        sender: SENDER.to_string(),
+        memo: "evidence:short-001".to_string(),
+        expires_at: None,
+        quote_signature: None,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    };
+    let response = client
+        .post(ctx.url("/api/v1/evidence/verify-premium"))
+        .header("x-forwarded-for", "10.0.10.1")
+        .header("authorization", TEST_BEARER_TOKEN)
+        .header("x-payment", signed(proof).to_header().unwrap())
+        .json(&json!({
+            "evidence_id": "short-001",
+            "tier": "multi_chain"
+        }))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::PAYMENT_REQUIRED);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["shortfall_usdc"], "0.02");
+    assert_eq!(body["verification"]["underpaid"], "0.02");
+    assert_eq!(body["verification"]["valid"], false);
+    assert!(body["message"]
+        .as_str()
+        .unwrap()
+        .contains("0.02 USDC short"));
+    assert_eq!(body["payment_details"]["price"], "0.05");
+}
+
+/// Test that paying a quote after it expired is refused with a fresh quote
+#[tokio::test]
+async fn test_x402_expired_quote_is_refused() {
+    let _guard = TEST_MUTEX.lock().await;
+    let ctx = TestContext::with_x402(true, Some("PhxRvkTestWalletExpiry")).await;
+    let client = reqwest::Client::new();
+
+    let pay = |expires_at: chrono::DateTime<chrono::Utc>, signature: &str| {
+        let proof = phoenix_x402::PaymentProof {
+            signature: signature.to_string(),
+            amount: "0.01".to_string(),
+            token: "USDC".to_string(),
+            mint: None,
+            sender: SENDER.to_string(),
+            memo: "evidence:expiry-001".to_string(),
+            expires_at: Some(expires_at.to_rfc3339()),
+            quote_signature: None,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        };
+        client
+            .post(ctx.url("/api/v1/evidence/verify-premium"))
+            .header("x-forwarded-for", "10.0.11.1")
+            .header("authorization", TEST_BEARER_TOKEN)
+            .header("x-payment", signed(proof).to_header().unwrap())
+            .json(&json!({
+                "evidence_id": "expiry-001",
+                "tier": "basic"
+            }))
+            .send()
+    };
+
+    let response = pay(
+        chrono::Utc::now() - chrono::Duration::minutes(1),
+        "2M1ubg2tdjndN7LjgPKrBY3VJB1yrQ88cHcMW9CFLU4xxXaPKZcDYksZ4s7DoSH6ew9Q45azxqfgp9yanpj77WP1",
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::PAYMENT_REQUIRED);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["error"], "Payment quote expired");
+    assert!(body["details"].as_str().unwrap().contains("expired quote"));
+    assert!(body["payment_details"]["expires_at"].is_string());
+
+    // Within the window the payment gets past expiry (evidence is unknown here)
+    let response = pay(
+        chrono::Utc::now() + chrono::Duration::minutes(1),
+        "51w64FFxbJhqVjocXknFhNKvmbRUMJv2vbmpGtiz6VV3qdxaQWvdR5KfKbbGehKeEpodDfoXsFvJ3VnbqU6SGxR9",
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    // A proof with the quote's expiry removed is not accepted
+    let proof = phoenix_x402::PaymentProof {
+        signature: "3qWkWxfUpwJoSN5nV2XQDgtzgP3FuN1uYgGN5YkMtF5RjkHbE2Fh4Wcwk5SFgKFuUeHmT9J5hH5U6eNDZ5GoC3nC".to_string(),
+        amount: "0.01".to_string(),
+        token: "USDC".to_string(),
+        mint: None,
+        sender: SENDER.to_string(),
+        memo: "evidence:expiry-001".to_string(),
+        expires_at: None,
+        quote_signature: None,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    };
+    let response = client
+        .post(ctx.url("/api/v1/evidence/verify-premium"))
+        .header("x-forwarded-for", "10.0.11.1")
+        .header("authorization", TEST_BEARER_TOKEN)
+        .header("x-payment", proof.to_header().unwrap())
+        .json(&json!({
+            "evidence_id": "expiry-001",
+            "tier": "basic"
+        }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["error"], "Payment not accepted");
+    assert!(body["details"].as_str().unwrap().contains("expires_at"));
+}
+
+/// Test that malformed requests are refused before any payment is taken
+#[tokio::test]
+async fn test_x402_malformed_request_is_refused_before_payment() {
+    let _guard = TEST_MUTEX.lock().await;
+    let ctx = TestContext::with_x402(true, Some("PhxRvkTestWalletInvalid")).await;
+    let client = reqwest::Client::new();
+
+    let pay = |body: Value| {
+        let proof = phoenix_x402::PaymentProof {
+            signature: "4dittWHeWjDsBMhV2ryq6TyJzTTf5oDUz59tGf2xQs1GsRBHgLnh5hUhJ6k7ZZuKDyH1KhZB8FoELVczof26ZP3o".to_string(),
+            amount: "0.01".to_string(),
+            token: "USDC".to_string(),
+            mint: None,
+            sender: SENDER.to_string(),
+            memo: "evidence:invalid-001".to_string(),
+            expires_at: None,
+            quote_signature: None,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        };
+        client
+            .post(ctx.url("/api/v1/evidence/verify-premium"))
+            .header("x-forwarded-for", "10.0.12.1")
+            .header("authorization", TEST_BEARER_TOKEN)
+            .header("x-payment", signed(proof).to_header().unwrap())
+            .json(&body)
+            .send()
+    };
+
+    for (body, detail) in [
+        (json!({ "evidence_id": "", "tier": "basic" }), "evidence_id"),
+        (
+            json!({ "evidence_id": "invalid-001", "chain": "bitcoin", "tier": "basic" }),
+            "unknown chain 'bitcoin'",
+        ),
+    ] {
+        let response = pay(body).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body: Value = response.json().await.unwrap();
+        assert!(
+            body["details"].as_str().unwrap().contains(detail),
+            "{}",
+            body
+        );
+    }
+
+    // The signature was never redeemed, so a well-formed request can still use it
+    let response = pay(json!({ "evidence_id": "invalid-001", "tier": "basic" }))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+/// Test that a proof whose signature or sender can't exist on Solana is
+/// refused with 400 before any replay check or facilitator call
+#[tokio::test]
+async fn test_x402_malformed_signature_is_refused() {
+    let _guard = TEST_MUTEX.lock().await;
+    let ctx = TestContext::with_x402(true, Some("PhxRvkTestWalletSignature")).await;
+    let client = reqwest::Client::new();
+
+    let pay = |signature: &str, sender: &str| {
+        let proof = phoenix_x402::PaymentProof {
+            signature: signature.to_string(),
+            amount: "0.01".to_string(),
+            token: "USDC".to_string(),
+            mint: None,
+            sender: sender.to_string(),
+            memo: "evidence:signature-001".to_string(),
+            expires_at: None,
+            quote_signature: None,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        };
+        client
+            .post(ctx.url("/api/v1/evidence/verify-premium"))
+            .header("x-forwarded-for", "10.0.13.1")
+            .header("authorization", TEST_BEARER_TOKEN)
+            .header("x-payment", signed(proof).to_header().unwrap())
+            .json(&json!({ "evidence_id": "signature-001", "tier": "basic" }))
+            .send()
+    };
+
+    for (signature, sender, detail) in [
+        ("not-a-signature", SENDER, "signature"),
+        ("3vQB7B6MrGQZaxCuFg4oh", SENDER, "signature must be 64 bytes"),
+        (
+            "3qWkWxfUpwJoSN5nV2XQDgtzgP3FuN1uYgGN5YkMtF5RjkHbE2Fh4Wcwk5SFgKFuUeHmT9J5hH5U6eNDZ5GoC3nC",
+            "sender-wallet",
+            "sender",
+        ),
+    ] {
+        let response = pay(signature, sender).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let body: Value = response.json().await.unwrap();
+        assert_eq!(body["error"], "Invalid payment proof");
+        assert!(
+            body["details"].as_str().unwrap().contains(detail),
+            "{}",
+            body
+        );
+    }
+
+    // A well-formed proof gets past validation (the evidence is unknown here)
+    let response = pay(
+        "3qWkWxfUpwJoSN5nV2XQDgtzgP3FuN1uYgGN5YkMtF5RjkHbE2Fh4Wcwk5SFgKFuUeHmT9J5hH5U6eNDZ5GoC3nC",
+        SENDER,
+    )
+    .await
+    .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
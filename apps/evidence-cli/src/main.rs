@@ -1,6 +1,12 @@
 use anyhow::{Context, Result};
 use clap::{Arg, Command};
-use phoenix_evidence::hash::sha256_hex;
+use phoenix_evidence::{
+    canonical::canonicalize_json,
+    explorer::{explorer_url, NetworkInfo},
+    hash::digest_hex,
+    merkle::{verify_proof_bundle, ProofBundle},
+    model::DigestAlgo,
+};
 use reqwest::Client;
 use serde_json::{json, Value};
 use std::fs;
@@ -43,6 +49,117 @@ fn build_cli() -> Command {
                 .help("Output format: json, digest-only")
                 .default_value("json"),
         )
+        .arg(
+            Arg::new("algo")
+                .long("algo")
+                .help("Digest algorithm for the payload (only sha256 can be submitted; the API anchors SHA-256 digests)")
+                .value_parser(["sha256", "sha512", "blake3"])
+                .default_value("sha256"),
+        )
+        .arg(
+            Arg::new("batch")
+                .long("batch")
+                .help(
+                    "Treat the payload as a path to an NDJSON file and submit one record per line",
+                )
+                .action(clap::ArgAction::SetTrue)
+                .requires("submit"),
+        )
+        .arg(
+            Arg::new("continue-on-error")
+                .long("continue-on-error")
+                .help("With --batch, record bad lines as failures instead of aborting")
+                .action(clap::ArgAction::SetTrue)
+                .requires("batch"),
+        )
+        .arg(
+            Arg::new("verify-after-submit")
+                .long("verify-after-submit")
+                .help("After --submit, wait for the evidence job to finish anchoring")
+                .action(clap::ArgAction::SetTrue)
+                .requires("submit")
+                .conflicts_with("batch"),
+        )
+        .arg(
+            Arg::new("verify-timeout")
+                .long("verify-timeout")
+                .help("Seconds to wait with --verify-after-submit")
+                .value_parser(clap::value_parser!(u64))
+                .default_value("120"),
+        )
+        .arg(
+            Arg::new("verify-interval")
+                .long("verify-interval")
+                .help("Seconds between status polls with --verify-after-submit")
+                .value_parser(clap::value_parser!(u64).range(1..))
+                .default_value("5"),
+        )
+        .after_help(format!(
+            "With --verify-after-submit, exits with code 1 if anchoring failed and {} on timeout.",
+            EXIT_VERIFY_TIMEOUT
+        ))
+        .subcommand_negates_reqs(true)
+        .args_conflicts_with_subcommands(true)
+        .subcommand(build_verify_cli())
+        .subcommand(build_proof_cli())
+}
+
+/// `proof` subcommand: fetch the stored Merkle proof and tx ref for a job.
+fn build_proof_cli() -> Command {
+    Command::new("proof")
+        .about("Fetch the Merkle proof and chain transaction for a submitted job")
+        .after_help(format!(
+            "Exits with code {} if the job exists but has not been anchored yet.",
+            EXIT_PENDING_ANCHOR
+        ))
+        .arg(
+            Arg::new("job_id")
+                .help("Evidence job id returned by --submit")
+                .required(true)
+                .index(1),
+        )
+        .arg(
+            Arg::new("api-url")
+                .long("api-url")
+                .help("Phoenix API URL")
+                .default_value("http://localhost:8080"),
+        )
+        .arg(
+            Arg::new("output-format")
+                .long("output-format")
+                .help("Output format: json, digest-only")
+                .default_value("json"),
+        )
+}
+
+/// `verify` subcommand: check a Merkle proof bundle offline.
+fn build_verify_cli() -> Command {
+    Command::new("verify")
+        .about("Verify a Merkle proof bundle against its embedded root")
+        .arg(
+            Arg::new("bundle")
+                .help("Path to a proof bundle JSON file")
+                .index(1)
+                .required_unless_present("job-id"),
+        )
+        .arg(
+            Arg::new("job-id")
+                .long("job-id")
+                .help("Fetch the proof bundle for this job from --api-url instead of a file")
+                .conflicts_with("bundle"),
+        )
+        .arg(
+            Arg::new("api-url")
+                .long("api-url")
+                .help("Phoenix API URL used with --job-id")
+                .default_value("http://localhost:8080"),
+        )
+        .arg(
+            Arg::new("output-format")
+                .long("output-format")
+                .help("Output format: text, json")
+                .default_value("text"),
+        )
 }
 
 /// Resolve the payload argument: inline JSON string or `@/path/to/file.json`.
@@ -57,74 +174,544 @@ fn resolve_payload(payload_arg: &str) -> Result<Value> {
     }
 }
 
-#[tokio::main]
-async fn main() -> Result<()> {
-    let matches = build_cli().get_matches();
+/// Map an `--algo` value to its digest algorithm.
+fn parse_algo(name: &str) -> Result<DigestAlgo> {
+    match name {
+        "sha256" => Ok(DigestAlgo::Sha256),
+        "sha512" => Ok(DigestAlgo::Sha512),
+        "blake3" => Ok(DigestAlgo::Blake3),
+        _ => anyhow::bail!("Unknown digest algorithm: {}", name),
+    }
+}
 
-    let event_type = matches.get_one::<String>("event_type").unwrap();
-    let payload_arg = matches.get_one::<String>("payload").unwrap();
-    let api_url = matches.get_one::<String>("api-url").unwrap();
-    let submit = matches.get_flag("submit");
-    let output_format = matches.get_one::<String>("output-format").unwrap();
+/// Refuse `--submit` with any algorithm but SHA-256, before anything is sent.
+///
+/// The API anchors and proves every digest as SHA-256, so a BLAKE3 digest of
+/// the same length would be accepted and then mislabelled.
+fn ensure_submittable(algo: DigestAlgo, algo_name: &str) -> Result<()> {
+    if algo != DigestAlgo::Sha256 {
+        anyhow::bail!(
+            "--algo {} cannot be submitted: the API only anchors SHA-256 digests; use --algo sha256 with --submit",
+            algo_name
+        );
+    }
+    Ok(())
+}
 
-    // Load payload
-    let payload = resolve_payload(payload_arg)?;
+/// Digest of the canonical (RFC 8785 style) form of a payload, so anyone can
+/// reproduce it.
+fn payload_digest(algo: DigestAlgo, payload: &Value) -> String {
+    digest_hex(algo, canonicalize_json(payload).as_bytes())
+}
 
-    // Compute digest
-    let canonical_json = serde_json::to_string(&payload)?;
-    let digest = sha256_hex(canonical_json.as_bytes());
+/// HTTP client for Phoenix API calls.
+fn api_client() -> Result<Client> {
+    Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .build()
+        .context("Failed to build HTTP client")
+}
 
-    if submit {
-        // Submit to API
-        let client = Client::builder()
-            .timeout(std::time::Duration::from_secs(30))
-            .build()
-            .context("Failed to build HTTP client")?;
-        let submit_payload = json!({
-            "digest_hex": digest,
-            "payload_mime": "application/json",
-            "metadata": {
-                "event_type": event_type,
-                "timestamp": chrono::Utc::now().to_rfc3339()
+/// Where and how digests are submitted for anchoring.
+struct SubmitTarget<'a> {
+    client: Client,
+    api_url: &'a str,
+    event_type: &'a str,
+    algo: DigestAlgo,
+    algo_name: &'a str,
+}
+
+/// POST one digest to `/evidence`, returning the API response with explorer
+/// links added.
+async fn submit_evidence(target: &SubmitTarget<'_>, digest: &str) -> Result<Value> {
+    let submit_payload = json!({
+        "digest_hex": digest,
+        "payload_mime": "application/json",
+        "metadata": {
+            "event_type": target.event_type,
+            "digest_algo": target.algo_name,
+            "timestamp": chrono::Utc::now().to_rfc3339()
+        }
+    });
+
+    let response = target
+        .client
+        .post(format!("{}/evidence", target.api_url))
+        .header("X-Evidence-Source", "cli")
+        .json(&submit_payload)
+        .send()
+        .await
+        .context("Failed to submit evidence to API")?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        anyhow::bail!("API request failed with status {}: {}", status, error_text);
+    }
+
+    let mut api_response: Value = response
+        .json()
+        .await
+        .context("Failed to parse API response")?;
+    add_explorer_links(&mut api_response);
+    Ok(api_response)
+}
+
+/// Outcome of a `--batch` run.
+#[derive(Debug, Default)]
+struct BatchSummary {
+    submitted: usize,
+    failed: usize,
+    /// One entry per non-blank line: `digest` and `api_response`, or `error`
+    results: Vec<Value>,
+}
+
+/// Digest and submit one NDJSON line.
+async fn submit_batch_line(target: &SubmitTarget<'_>, line: &str) -> Result<Value> {
+    let payload: Value = serde_json::from_str(line).context("Failed to parse JSON")?;
+    let digest = payload_digest(target.algo, &payload);
+    let api_response = submit_evidence(target, &digest).await?;
+    Ok(json!({ "digest": digest, "api_response": api_response }))
+}
+
+/// Submit every line of an NDJSON file as its own evidence record.
+///
+/// Blank lines are skipped. A bad line (invalid JSON or a rejected
+/// submission) aborts the run unless `continue_on_error` is set, in which
+/// case it is counted as failed and the next line is processed.
+async fn run_batch(
+    target: &SubmitTarget<'_>,
+    path: &str,
+    continue_on_error: bool,
+) -> Result<BatchSummary> {
+    let path = path.strip_prefix('@').unwrap_or(path);
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read batch file: {}", path))?;
+
+    let mut summary = BatchSummary::default();
+    for (index, line) in content.lines().enumerate() {
+        let line_no = index + 1;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        match submit_batch_line(target, line).await {
+            Ok(mut result) => {
+                result["line"] = json!(line_no);
+                summary.submitted += 1;
+                summary.results.push(result);
             }
-        });
+            Err(e) if continue_on_error => {
+                summary.failed += 1;
+                summary
+                    .results
+                    .push(json!({ "line": line_no, "error": format!("{:#}", e) }));
+            }
+            Err(e) => {
+                return Err(e.context(format!(
+                    "line {} of {} ({} record(s) submitted before aborting)",
+                    line_no, path, summary.submitted
+                )));
+            }
+        }
+    }
+    Ok(summary)
+}
+
+/// Explorer link for a tx ref object (`network`, `chain`, `tx_id`), if known.
+fn tx_ref_explorer_url(tx_ref: &Value) -> Option<String> {
+    let network = tx_ref.get("network")?.as_str()?;
+    let chain = tx_ref.get("chain")?.as_str()?;
+    let tx_id = tx_ref.get("tx_id")?.as_str()?;
+    NetworkInfo::new(network, chain).explorer_url(tx_id)
+}
 
+/// Add `explorer_url` to every tx ref (`tx_ref` / `tx_refs[]`) in an API response.
+///
+/// Refs on unknown networks are left without a link rather than a broken URL.
+fn add_explorer_links(response: &mut Value) {
+    let annotate = |tx_ref: &mut Value| {
+        if let Some(url) = tx_ref_explorer_url(tx_ref) {
+            if let Some(obj) = tx_ref.as_object_mut() {
+                obj.insert("explorer_url".to_string(), Value::String(url));
+            }
+        }
+    };
+
+    if let Some(tx_ref) = response.get_mut("tx_ref") {
+        annotate(tx_ref);
+    }
+    if let Some(tx_refs) = response.get_mut("tx_refs").and_then(Value::as_array_mut) {
+        tx_refs.iter_mut().for_each(annotate);
+    }
+}
+
+/// Load a proof bundle from a JSON file.
+fn load_bundle(path: &str) -> Result<ProofBundle> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read proof bundle: {}", path))?;
+    serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse proof bundle: {}", path))
+}
+
+/// Exit code for `proof` when the job exists but its batch isn't anchored yet.
+const EXIT_PENDING_ANCHOR: i32 = 3;
+
+/// Exit code for `--verify-after-submit` when the job is still unfinished at
+/// the deadline.
+const EXIT_VERIFY_TIMEOUT: i32 = 4;
+
+/// Job statuses after which the outbox never touches a job again.
+const FINAL_STATUSES: &[&str] = &["done", "failed", "dead_letter"];
+
+/// Result of waiting for a submitted job to finish anchoring.
+#[derive(Debug)]
+enum VerifyOutcome {
+    /// `GET /evidence/{id}` body once the job reached a final status
+    Finished(Value),
+    /// Last body seen before the deadline
+    TimedOut(Value),
+}
+
+impl VerifyOutcome {
+    fn evidence(&self) -> &Value {
+        match self {
+            VerifyOutcome::Finished(evidence) | VerifyOutcome::TimedOut(evidence) => evidence,
+        }
+    }
+
+    fn exit_code(&self) -> i32 {
+        match self {
+            VerifyOutcome::Finished(evidence) if evidence["status"] == "done" => 0,
+            VerifyOutcome::Finished(_) => 1,
+            VerifyOutcome::TimedOut(_) => EXIT_VERIFY_TIMEOUT,
+        }
+    }
+}
+
+/// Poll `GET /evidence/{id}` every `interval` until the job reaches a final
+/// status or `timeout` runs out.
+async fn wait_for_anchor(
+    client: &Client,
+    api_url: &str,
+    id: &str,
+    timeout: std::time::Duration,
+    interval: std::time::Duration,
+) -> Result<VerifyOutcome> {
+    let deadline = tokio::time::Instant::now() + timeout;
+    loop {
         let response = client
-            .post(format!("{}/evidence", api_url))
-            .json(&submit_payload)
+            .get(format!("{}/evidence/{}", api_url, id))
             .send()
             .await
-            .context("Failed to submit evidence to API")?;
-
+            .context("Failed to fetch evidence status from API")?;
         if !response.status().is_success() {
             let status = response.status();
             let error_text = response.text().await.unwrap_or_default();
             anyhow::bail!("API request failed with status {}: {}", status, error_text);
         }
-
-        let api_response: Value = response
+        let mut evidence: Value = response
             .json()
             .await
-            .context("Failed to parse API response")?;
+            .context("Failed to parse evidence status from API")?;
+        add_explorer_links(&mut evidence);
+
+        let status = evidence["status"].as_str().unwrap_or_default();
+        if FINAL_STATUSES.contains(&status) {
+            return Ok(VerifyOutcome::Finished(evidence));
+        }
+        if tokio::time::Instant::now() + interval > deadline {
+            return Ok(VerifyOutcome::TimedOut(evidence));
+        }
+        tokio::time::sleep(interval).await;
+    }
+}
+
+/// One-line description of a verify outcome, with the tx if there is one.
+fn format_verify_outcome(outcome: &VerifyOutcome) -> String {
+    let evidence = outcome.evidence();
+    let status = evidence["status"].as_str().unwrap_or("unknown");
+    let mut out = match outcome {
+        VerifyOutcome::Finished(_) => format!("final status: {}", status),
+        VerifyOutcome::TimedOut(_) => {
+            format!("timed out waiting for anchor, last status: {}", status)
+        }
+    };
+    if let Some(tx_ref) = evidence["tx_refs"].as_array().and_then(|refs| refs.first()) {
+        out.push_str(&format!(
+            ", tx: {}",
+            tx_ref["tx_id"].as_str().unwrap_or("-")
+        ));
+        if let Some(url) = tx_ref["explorer_url"].as_str() {
+            out.push_str(&format!(" ({})", url));
+        }
+    }
+    if let Some(error) = evidence["last_error"].as_str() {
+        out.push_str(&format!(", error: {}", error));
+    }
+    out
+}
+
+/// Result of asking the API for a job's proof.
+#[derive(Debug)]
+enum ProofFetch {
+    Anchored(Box<ProofBundle>),
+    /// Job is known but its batch has no transaction yet
+    Pending,
+}
+
+/// Fetch the proof bundle for a job from `GET /evidence/{id}/proof`.
+async fn fetch_proof(api_url: &str, job_id: &str) -> Result<ProofFetch> {
+    let response = api_client()?
+        .get(format!("{}/evidence/{}/proof", api_url, job_id))
+        .send()
+        .await
+        .context("Failed to fetch proof bundle from API")?;
+
+    if response.status() == reqwest::StatusCode::ACCEPTED {
+        return Ok(ProofFetch::Pending);
+    }
+    if !response.status().is_success() {
+        let status = response.status();
+        let error_text = response.text().await.unwrap_or_default();
+        anyhow::bail!("API request failed with status {}: {}", status, error_text);
+    }
+
+    let bundle = response
+        .json()
+        .await
+        .context("Failed to parse proof bundle from API")?;
+    Ok(ProofFetch::Anchored(Box::new(bundle)))
+}
+
+/// Render a fetched proof in the requested output format.
+fn format_proof(bundle: &ProofBundle, output_format: &str) -> Result<String> {
+    match output_format {
+        "digest-only" => Ok(bundle.proof.leaf_hash.clone()),
+        "json" => {
+            let output = json!({
+                "job_id": bundle.job_id,
+                "merkle_proof": bundle.proof,
+                "tx_ref": bundle.tx_ref,
+                "explorer_url": explorer_url(&bundle.tx_ref),
+            });
+            Ok(serde_json::to_string_pretty(&output)?)
+        }
+        _ => anyhow::bail!("Invalid output format: {}", output_format),
+    }
+}
+
+/// Run `proof`, returning `false` if the job is still pending anchor.
+async fn run_proof(matches: &clap::ArgMatches) -> Result<bool> {
+    let job_id = matches.get_one::<String>("job_id").unwrap();
+    let api_url = matches.get_one::<String>("api-url").unwrap();
+    let output_format = matches.get_one::<String>("output-format").unwrap();
+
+    match fetch_proof(api_url, job_id).await? {
+        ProofFetch::Anchored(bundle) => {
+            println!("{}", format_proof(&bundle, output_format)?);
+            Ok(true)
+        }
+        ProofFetch::Pending => {
+            eprintln!("pending anchor: job {} has not been anchored yet", job_id);
+            Ok(false)
+        }
+    }
+}
+
+/// Verify a bundle and describe the outcome as JSON.
+fn verify_report(bundle: &ProofBundle) -> Result<Value> {
+    let verified = verify_proof_bundle(bundle).context("Malformed proof bundle")?;
+    let computed_root = bundle.proof.compute_root(bundle.construction_version)?;
+
+    Ok(json!({
+        "result": if verified { "PASS" } else { "FAIL" },
+        "verified": verified,
+        "job_id": bundle.job_id,
+        "leaf_hash": bundle.proof.leaf_hash,
+        "merkle_root": bundle.merkle_root,
+        "computed_root": computed_root,
+        "network": bundle.network,
+        "chain": bundle.chain,
+        "tx_id": bundle.tx_ref.tx_id,
+        "explorer_url": explorer_url(&bundle.tx_ref),
+    }))
+}
+
+/// Human-readable rendering of `verify_report` output.
+fn format_verify_text(report: &Value) -> String {
+    let field = |key: &str| report[key].as_str().unwrap_or("-").to_string();
+    let mut out = format!(
+        "{}\n  job:           {}\n  leaf hash:     {}\n  merkle root:   {}\n  computed root: {}\n  tx:            {} ({}/{})",
+        field("result"),
+        field("job_id"),
+        field("leaf_hash"),
+        field("merkle_root"),
+        field("computed_root"),
+        field("tx_id"),
+        field("network"),
+        field("chain"),
+    );
+    if let Some(url) = report["explorer_url"].as_str() {
+        out.push_str(&format!("\n  explorer:      {}", url));
+    }
+    out
+}
+
+/// Run `verify`, returning whether the proof checked out.
+async fn run_verify(matches: &clap::ArgMatches) -> Result<bool> {
+    let output_format = matches.get_one::<String>("output-format").unwrap();
+    let bundle = match matches.get_one::<String>("job-id") {
+        Some(job_id) => {
+            let api_url = matches.get_one::<String>("api-url").unwrap();
+            match fetch_proof(api_url, job_id).await? {
+                ProofFetch::Anchored(bundle) => *bundle,
+                ProofFetch::Pending => {
+                    anyhow::bail!("pending anchor: job {} has not been anchored yet", job_id)
+                }
+            }
+        }
+        None => load_bundle(matches.get_one::<String>("bundle").unwrap())?,
+    };
+
+    let report = verify_report(&bundle)?;
+    match output_format.as_str() {
+        "json" => println!("{}", serde_json::to_string_pretty(&report)?),
+        "text" => println!("{}", format_verify_text(&report)),
+        _ => anyhow::bail!("Invalid output format: {}", output_format),
+    }
+
+    Ok(report["verified"].as_bool().unwrap_or(false))
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let matches = build_cli().get_matches();
+
+    match matches.subcommand() {
+        Some(("verify", verify_matches)) => {
+            // Non-zero exit on FAIL so CI pipelines can gate on it
+            if !run_verify(verify_matches).await? {
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        Some(("proof", proof_matches)) => {
+            // Distinct exit code so scripts can poll until anchored
+            if !run_proof(proof_matches).await? {
+                std::process::exit(EXIT_PENDING_ANCHOR);
+            }
+            return Ok(());
+        }
+        _ => {}
+    }
+
+    let event_type = matches.get_one::<String>("event_type").unwrap();
+    let payload_arg = matches.get_one::<String>("payload").unwrap();
+    let api_url = matches.get_one::<String>("api-url").unwrap();
+    let submit = matches.get_flag("submit");
+    let output_format = matches.get_one::<String>("output-format").unwrap();
+    let algo_name = matches.get_one::<String>("algo").unwrap();
+    let algo = parse_algo(algo_name)?;
+    if submit {
+        ensure_submittable(algo, algo_name)?;
+    }
+
+    if matches.get_flag("batch") {
+        let target = SubmitTarget {
+            client: api_client()?,
+            api_url,
+            event_type,
+            algo,
+            algo_name,
+        };
+        let summary =
+            run_batch(&target, payload_arg, matches.get_flag("continue-on-error")).await?;
+
+        match output_format.as_str() {
+            "digest-only" => summary
+                .results
+                .iter()
+                .filter_map(|r| r["digest"].as_str())
+                .for_each(|d| println!("{}", d)),
+            "json" => println!("{}", serde_json::to_string_pretty(&summary.results)?),
+            _ => anyhow::bail!("Invalid output format: {}", output_format),
+        }
+        eprintln!(
+            "batch: {} submitted, {} failed",
+            summary.submitted, summary.failed
+        );
+        if summary.failed > 0 {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    // Load payload
+    let payload = resolve_payload(payload_arg)?;
+
+    let digest = payload_digest(algo, &payload);
+
+    if submit {
+        let target = SubmitTarget {
+            client: api_client()?,
+            api_url,
+            event_type,
+            algo,
+            algo_name,
+        };
+        let api_response = submit_evidence(&target, &digest).await?;
+
+        let outcome = if matches.get_flag("verify-after-submit") {
+            let id = api_response["id"]
+                .as_str()
+                .context("API response has no evidence id to verify")?;
+            let secs =
+                |name: &str| std::time::Duration::from_secs(*matches.get_one::<u64>(name).unwrap());
+            Some(
+                wait_for_anchor(
+                    &target.client,
+                    api_url,
+                    id,
+                    secs("verify-timeout"),
+                    secs("verify-interval"),
+                )
+                .await?,
+            )
+        } else {
+            None
+        };
 
         match output_format.as_str() {
             "digest-only" => println!("{}", digest),
             "json" => {
-                let output = json!({
+                let mut output = json!({
                     "digest": digest,
+                    "algo": algo_name,
                     "event_type": event_type,
                     "api_response": api_response,
                     "submitted": true
                 });
+                if let Some(outcome) = &outcome {
+                    output["final_status"] = outcome.evidence().clone();
+                }
                 println!("{}", serde_json::to_string_pretty(&output)?);
             }
             _ => anyhow::bail!("Invalid output format: {}", output_format),
         }
+
+        if let Some(outcome) = outcome {
+            eprintln!("{}", format_verify_outcome(&outcome));
+            if outcome.exit_code() != 0 {
+                std::process::exit(outcome.exit_code());
+            }
+        }
     } else {
         // Local processing only
         let evidence_record = json!({
             "event_type": event_type,
             "digest": digest,
+            "algo": algo_name,
             "payload": payload,
             "timestamp": chrono::Utc::now().to_rfc3339()
         });
@@ -134,6 +721,7 @@ async fn main() -> Result<()> {
             "json" => {
                 let output = json!({
                     "digest": digest,
+                    "algo": algo_name,
                     "event_type": event_type,
                     "evidence_record": evidence_record,
                     "submitted": false
@@ -150,7 +738,11 @@ async fn main() -> Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use phoenix_evidence::hash::{sha256_canonical_json, sha256_hex};
     use std::io::Write;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::Duration;
     use tempfile::NamedTempFile;
 
     // ---------------------------------------------------------------------------
@@ -185,6 +777,7 @@ mod tests {
         );
         assert!(!m.get_flag("submit"));
         assert_eq!(m.get_one::<String>("output-format").unwrap(), "json");
+        assert_eq!(m.get_one::<String>("algo").unwrap(), "sha256");
     }
 
     #[test]
@@ -296,10 +889,9 @@ mod tests {
     #[test]
     fn test_digest_is_deterministic_for_same_payload() {
         let payload: Value = serde_json::from_str(r#"{"key":"value"}"#).unwrap();
-        let canonical = serde_json::to_string(&payload).unwrap();
 
-        let digest_a = sha256_hex(canonical.as_bytes());
-        let digest_b = sha256_hex(canonical.as_bytes());
+        let digest_a = sha256_canonical_json(&payload);
+        let digest_b = sha256_canonical_json(&payload);
 
         assert_eq!(digest_a, digest_b);
         assert_eq!(digest_a.len(), 64, "SHA-256 hex digest must be 64 chars");
@@ -314,9 +906,463 @@ mod tests {
         let a: Value = serde_json::from_str(r#"{"x":1}"#).unwrap();
         let b: Value = serde_json::from_str(r#"{"x":2}"#).unwrap();
 
-        let digest_a = sha256_hex(serde_json::to_string(&a).unwrap().as_bytes());
-        let digest_b = sha256_hex(serde_json::to_string(&b).unwrap().as_bytes());
+        assert_ne!(sha256_canonical_json(&a), sha256_canonical_json(&b));
+    }
+
+    #[test]
+    fn test_digest_ignores_key_order_and_number_form() {
+        let a: Value =
+            serde_json::from_str(r#"{"target":{"lat":-25.7,"lon":28.2},"count":3}"#).unwrap();
+        let b: Value =
+            serde_json::from_str(r#"{ "count": 3.0, "target": { "lon": 28.2, "lat": -25.7 } }"#)
+                .unwrap();
+
+        assert_eq!(sha256_canonical_json(&a), sha256_canonical_json(&b));
+        assert_eq!(
+            sha256_canonical_json(&a),
+            sha256_hex(br#"{"count":3,"target":{"lat":-25.7,"lon":28.2}}"#)
+        );
+    }
+
+    #[test]
+    fn test_cli_parses_algo_flag() {
+        let m = build_cli()
+            .try_get_matches_from(["record-evidence", "test_event", "{}", "--algo", "blake3"])
+            .expect("known algorithm should parse");
+        assert_eq!(m.get_one::<String>("algo").unwrap(), "blake3");
+
+        assert!(build_cli()
+            .try_get_matches_from(["record-evidence", "test_event", "{}", "--algo", "md5"])
+            .is_err());
+        assert!(parse_algo("md5").is_err());
+    }
+
+    #[test]
+    fn test_payload_digest_follows_algo() {
+        let payload: Value = serde_json::from_str(r#"{"key":"value"}"#).unwrap();
+
+        let sha256 = payload_digest(DigestAlgo::Sha256, &payload);
+        let blake3 = payload_digest(parse_algo("blake3").unwrap(), &payload);
+        let sha512 = payload_digest(parse_algo("sha512").unwrap(), &payload);
+
+        // The default stays the plain canonical SHA-256
+        assert_eq!(sha256, sha256_canonical_json(&payload));
+        assert_ne!(blake3, sha256);
+        assert_eq!(blake3.len(), 64);
+        assert_eq!(sha512.len(), 128);
+    }
+
+    #[test]
+    fn test_only_sha256_can_be_submitted() {
+        assert!(ensure_submittable(DigestAlgo::Sha256, "sha256").is_ok());
+        let err = ensure_submittable(DigestAlgo::Sha512, "sha512").unwrap_err();
+        assert!(err.to_string().contains("--algo sha512"));
+
+        // Same length as SHA-256, but the API would anchor it as SHA-256
+        let err = ensure_submittable(DigestAlgo::Blake3, "blake3").unwrap_err();
+        assert!(err.to_string().contains("--algo blake3"));
+    }
+
+    // ---------------------------------------------------------------------------
+    // Batch mode
+    // ---------------------------------------------------------------------------
+
+    /// Start a mock `POST /evidence` that accepts everything, counting calls
+    async fn mock_evidence_api() -> (String, Arc<AtomicUsize>) {
+        use axum::{extract::State, routing::post, Json, Router};
+
+        let hits = Arc::new(AtomicUsize::new(0));
+        let app = Router::new()
+            .route(
+                "/evidence",
+                post(
+                    |State(hits): State<Arc<AtomicUsize>>, Json(body): Json<Value>| async move {
+                        let n = hits.fetch_add(1, Ordering::SeqCst) + 1;
+                        assert_eq!(body["metadata"]["digest_algo"], "sha256");
+                        Json(json!({ "id": format!("ev-{}", n), "status": "queued" }))
+                    },
+                ),
+            )
+            .with_state(hits.clone());
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        (url, hits)
+    }
+
+    /// Three records, the middle one malformed
+    fn batch_file() -> NamedTempFile {
+        let mut tmp = NamedTempFile::new().unwrap();
+        writeln!(tmp, r#"{{"event":"a","seq":1}}"#).unwrap();
+        writeln!(tmp, r#"{{"event":"b","seq":"#).unwrap();
+        writeln!(tmp, r#"{{"event":"c","seq":3}}"#).unwrap();
+        tmp
+    }
+
+    fn target(api_url: &str) -> SubmitTarget<'_> {
+        SubmitTarget {
+            client: api_client().unwrap(),
+            api_url,
+            event_type: "historical_import",
+            algo: DigestAlgo::Sha256,
+            algo_name: "sha256",
+        }
+    }
+
+    #[test]
+    fn test_cli_parses_batch_flags() {
+        let m = build_cli()
+            .try_get_matches_from([
+                "record-evidence",
+                "historical_import",
+                "events.ndjson",
+                "--submit",
+                "--batch",
+                "--continue-on-error",
+            ])
+            .expect("batch flags should parse");
+        assert!(m.get_flag("batch"));
+        assert!(m.get_flag("continue-on-error"));
+
+        // --batch submits, --continue-on-error only means something in batch mode
+        assert!(build_cli()
+            .try_get_matches_from(["record-evidence", "e", "events.ndjson", "--batch"])
+            .is_err());
+        assert!(build_cli()
+            .try_get_matches_from(["record-evidence", "e", "{}", "--continue-on-error"])
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_batch_continue_on_error_skips_bad_line() {
+        let (url, hits) = mock_evidence_api().await;
+        let file = batch_file();
+
+        let summary = run_batch(&target(&url), file.path().to_str().unwrap(), true)
+            .await
+            .unwrap();
+
+        assert_eq!(summary.submitted, 2);
+        assert_eq!(summary.failed, 1);
+        assert_eq!(hits.load(Ordering::SeqCst), 2);
+
+        let lines: Vec<_> = summary.results.iter().map(|r| r["line"].clone()).collect();
+        assert_eq!(lines, [json!(1), json!(2), json!(3)]);
+        assert!(summary.results[1]["error"]
+            .as_str()
+            .unwrap()
+            .contains("Failed to parse JSON"));
+        assert_eq!(summary.results[2]["api_response"]["id"], "ev-2");
+        assert_eq!(
+            summary.results[0]["digest"],
+            sha256_canonical_json(&json!({"event": "a", "seq": 1}))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_batch_aborts_on_bad_line_by_default() {
+        let (url, hits) = mock_evidence_api().await;
+        let file = batch_file();
+
+        let err = run_batch(&target(&url), file.path().to_str().unwrap(), false)
+            .await
+            .unwrap_err();
+
+        let msg = format!("{:#}", err);
+        assert!(msg.contains("line 2"), "got: {}", msg);
+        assert!(msg.contains("1 record(s) submitted"), "got: {}", msg);
+        // Nothing after the bad line was sent
+        assert_eq!(hits.load(Ordering::SeqCst), 1);
+    }
+
+    // ---------------------------------------------------------------------------
+    // --verify-after-submit
+    // ---------------------------------------------------------------------------
+
+    /// Start a mock `GET /evidence/{id}` that reports `queued` for the first
+    /// `queued_polls` calls and `done` with a Solana tx after that
+    async fn mock_status_api(queued_polls: usize) -> (String, Arc<AtomicUsize>) {
+        use axum::{
+            extract::{Path, State},
+            routing::get,
+            Json, Router,
+        };
+
+        let polls = Arc::new(AtomicUsize::new(0));
+        let app = Router::new()
+            .route(
+                "/evidence/{id}",
+                get(
+                    move |State(polls): State<Arc<AtomicUsize>>, Path(id): Path<String>| async move {
+                        if polls.fetch_add(1, Ordering::SeqCst) < queued_polls {
+                            return Json(json!({ "id": id, "status": "queued", "tx_refs": [] }));
+                        }
+                        Json(json!({
+                            "id": id,
+                            "status": "done",
+                            "tx_refs": [{
+                                "network": "solana",
+                                "chain": "devnet",
+                                "tx_id": "5sig",
+                                "confirmed": true
+                            }]
+                        }))
+                    },
+                ),
+            )
+            .with_state(polls.clone());
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+        (url, polls)
+    }
+
+    #[test]
+    fn test_cli_parses_verify_after_submit() {
+        let m = build_cli()
+            .try_get_matches_from([
+                "record-evidence",
+                "e",
+                "{}",
+                "--submit",
+                "--verify-after-submit",
+                "--verify-timeout",
+                "30",
+            ])
+            .expect("verify flags should parse");
+        assert!(m.get_flag("verify-after-submit"));
+        assert_eq!(*m.get_one::<u64>("verify-timeout").unwrap(), 30);
+        assert_eq!(*m.get_one::<u64>("verify-interval").unwrap(), 5);
+
+        // Nothing to verify without a submission
+        assert!(build_cli()
+            .try_get_matches_from(["record-evidence", "e", "{}", "--verify-after-submit"])
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_anchor_reports_done() {
+        let (url, polls) = mock_status_api(1).await;
+        let client = api_client().unwrap();
+
+        let outcome = wait_for_anchor(
+            &client,
+            &url,
+            "ev-1",
+            Duration::from_secs(5),
+            Duration::from_millis(10),
+        )
+        .await
+        .unwrap();
+
+        assert!(
+            matches!(outcome, VerifyOutcome::Finished(_)),
+            "{:?}",
+            outcome
+        );
+        assert_eq!(outcome.exit_code(), 0);
+        assert_eq!(polls.load(Ordering::SeqCst), 2);
+        assert_eq!(
+            format_verify_outcome(&outcome),
+            "final status: done, tx: 5sig (https://explorer.solana.com/tx/5sig?cluster=devnet)"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_anchor_times_out_with_last_status() {
+        let (url, _) = mock_status_api(usize::MAX).await;
+        let client = api_client().unwrap();
+
+        let outcome = wait_for_anchor(
+            &client,
+            &url,
+            "ev-1",
+            Duration::from_millis(50),
+            Duration::from_millis(10),
+        )
+        .await
+        .unwrap();
+
+        assert!(
+            matches!(outcome, VerifyOutcome::TimedOut(_)),
+            "{:?}",
+            outcome
+        );
+        assert_eq!(outcome.exit_code(), EXIT_VERIFY_TIMEOUT);
+        assert_eq!(
+            format_verify_outcome(&outcome),
+            "timed out waiting for anchor, last status: queued"
+        );
+    }
+
+    // ---------------------------------------------------------------------------
+    // Explorer links
+    // ---------------------------------------------------------------------------
+
+    #[test]
+    fn test_add_explorer_links_to_tx_refs() {
+        let mut response = json!({
+            "id": "ev-1",
+            "tx_ref": {"network": "solana", "chain": "devnet", "tx_id": "5sig"},
+            "tx_refs": [
+                {"network": "etherlink", "chain": "testnet", "tx_id": "0xabc"},
+                {"network": "unknown", "chain": "x", "tx_id": "0xdef"}
+            ]
+        });
+
+        add_explorer_links(&mut response);
+
+        assert_eq!(
+            response["tx_ref"]["explorer_url"],
+            "https://explorer.solana.com/tx/5sig?cluster=devnet"
+        );
+        assert_eq!(
+            response["tx_refs"][0]["explorer_url"],
+            "https://testnet.explorer.etherlink.com/tx/0xabc"
+        );
+        assert!(
+            response["tx_refs"][1].get("explorer_url").is_none(),
+            "unknown networks must not get a link"
+        );
+    }
+
+    // ---------------------------------------------------------------------------
+    // verify subcommand
+    // ---------------------------------------------------------------------------
+
+    fn sample_bundle() -> ProofBundle {
+        use phoenix_evidence::merkle::{MerkleTree, PROOF_BUNDLE_VERSION};
+        use phoenix_evidence::model::ChainTxRef;
+
+        let tree = MerkleTree::from_leaves(vec!["aa".repeat(32), "bb".repeat(32)]).unwrap();
+        ProofBundle {
+            version: PROOF_BUNDLE_VERSION,
+            job_id: "job-1".to_string(),
+            network: "solana".to_string(),
+            chain: "devnet".to_string(),
+            merkle_root: tree.root(),
+            construction_version: tree.construction_version(),
+            proof: tree.proof(1).unwrap(),
+            tx_ref: ChainTxRef {
+                network: "solana".to_string(),
+                chain: "devnet".to_string(),
+                tx_id: "5sig".to_string(),
+                confirmed: true,
+                timestamp: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_cli_parses_verify_subcommand() {
+        let m = build_cli()
+            .try_get_matches_from([
+                "record-evidence",
+                "verify",
+                "bundle.json",
+                "--output-format",
+                "json",
+            ])
+            .expect("verify with a bundle path should parse");
+
+        let (name, sub) = m.subcommand().unwrap();
+        assert_eq!(name, "verify");
+        assert_eq!(sub.get_one::<String>("bundle").unwrap(), "bundle.json");
+        assert_eq!(sub.get_one::<String>("output-format").unwrap(), "json");
+
+        let m = build_cli()
+            .try_get_matches_from(["record-evidence", "verify", "--job-id", "job-1"])
+            .expect("verify by job id should parse");
+        let (_, sub) = m.subcommand().unwrap();
+        assert_eq!(sub.get_one::<String>("job-id").unwrap(), "job-1");
+
+        // Needs either a bundle path or --job-id
+        assert!(build_cli()
+            .try_get_matches_from(["record-evidence", "verify"])
+            .is_err());
+    }
+
+    #[test]
+    fn test_verify_report_pass() {
+        let mut tmp = NamedTempFile::new().unwrap();
+        write!(tmp, "{}", serde_json::to_string(&sample_bundle()).unwrap()).unwrap();
+
+        let bundle = load_bundle(tmp.path().to_str().unwrap()).unwrap();
+        let report = verify_report(&bundle).unwrap();
+
+        assert_eq!(report["result"], "PASS");
+        assert_eq!(report["verified"], true);
+        assert_eq!(report["merkle_root"], report["computed_root"]);
+        assert_eq!(report["tx_id"], "5sig");
+        assert_eq!(
+            report["explorer_url"],
+            "https://explorer.solana.com/tx/5sig?cluster=devnet"
+        );
+        assert!(format_verify_text(&report).starts_with("PASS"));
+    }
+
+    #[test]
+    fn test_verify_report_fail_on_tampered_root() {
+        let mut bundle = sample_bundle();
+        bundle.merkle_root = "cc".repeat(32);
+
+        let report = verify_report(&bundle).unwrap();
+        assert_eq!(report["result"], "FAIL");
+        assert_eq!(report["verified"], false);
+        assert_ne!(report["merkle_root"], report["computed_root"]);
+        assert!(format_verify_text(&report).starts_with("FAIL"));
+    }
+
+    // ---------------------------------------------------------------------------
+    // proof subcommand
+    // ---------------------------------------------------------------------------
+
+    #[test]
+    fn test_cli_parses_proof_subcommand() {
+        let m = build_cli()
+            .try_get_matches_from([
+                "record-evidence",
+                "proof",
+                "job-42",
+                "--output-format",
+                "digest-only",
+            ])
+            .expect("proof with a job id should parse");
+
+        let (name, sub) = m.subcommand().unwrap();
+        assert_eq!(name, "proof");
+        assert_eq!(sub.get_one::<String>("job_id").unwrap(), "job-42");
+        assert_eq!(
+            sub.get_one::<String>("output-format").unwrap(),
+            "digest-only"
+        );
+
+        assert!(build_cli()
+            .try_get_matches_from(["record-evidence", "proof"])
+            .is_err());
+    }
+
+    #[test]
+    fn test_format_proof_outputs() {
+        let bundle = sample_bundle();
+
+        assert_eq!(
+            format_proof(&bundle, "digest-only").unwrap(),
+            bundle.proof.leaf_hash
+        );
+
+        let json_out: Value =
+            serde_json::from_str(&format_proof(&bundle, "json").unwrap()).unwrap();
+        assert_eq!(json_out["job_id"], "job-1");
+        assert_eq!(json_out["merkle_proof"]["leaf_index"], 1);
+        assert_eq!(json_out["tx_ref"]["tx_id"], "5sig");
 
-        assert_ne!(digest_a, digest_b);
+        assert!(format_proof(&bundle, "xml").is_err());
     }
 }
@@ -0,0 +1,156 @@
+//! Anchoring wallet balance checks.
+//!
+//! A wallet that runs out of SOL or XTZ makes every anchor fail with an
+//! opaque provider error. [`BalanceGuard`] wraps a provider, checks its
+//! wallet against a minimum at startup and every `KEEPER_BALANCE_CHECK_MS`,
+//! and warns (log plus `keeper_low_balance_warnings_total`) while it is
+//! short. With `KEEPER_PAUSE_ON_LOW_BALANCE=true` a short wallet also makes
+//! [`AnchorProvider::is_available`] false, so the job loops stop claiming
+//! jobs until it is topped up instead of burning their retries.
+
+use crate::metrics;
+use async_trait::async_trait;
+use phoenix_evidence::anchor::{AnchorError, AnchorProvider, BalanceCheck};
+use phoenix_evidence::model::{ChainTxRef, EvidenceRecord};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+
+/// How often the monitor re-checks wallet balances
+pub const DEFAULT_BALANCE_CHECK_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Default minimum on Solana: 0.01 SOL, about a thousand memo transactions
+pub const DEFAULT_SOLANA_MIN_BALANCE_LAMPORTS: u128 = 10_000_000;
+
+/// Default minimum on Etherlink: 0.01 XTZ
+pub const DEFAULT_ETHERLINK_MIN_BALANCE_WEI: u128 = 10_000_000_000_000_000;
+
+/// Minimum balance for a provider's network, from `SOLANA_MIN_BALANCE_LAMPORTS`
+/// or `ETHERLINK_MIN_BALANCE_WEI`; 0 (never short) for other networks
+pub fn min_balance_from_env(network: Option<&str>) -> Result<u128, String> {
+    let (name, default) = match network {
+        Some("solana") => (
+            "SOLANA_MIN_BALANCE_LAMPORTS",
+            DEFAULT_SOLANA_MIN_BALANCE_LAMPORTS,
+        ),
+        Some("etherlink") => (
+            "ETHERLINK_MIN_BALANCE_WEI",
+            DEFAULT_ETHERLINK_MIN_BALANCE_WEI,
+        ),
+        _ => return Ok(0),
+    };
+    match std::env::var(name) {
+        Ok(value) if !value.trim().is_empty() => value
+            .trim()
+            .parse::<u128>()
+            .map_err(|e| format!("invalid {} '{}': {}", name, value, e)),
+        _ => Ok(default),
+    }
+}
+
+/// Provider wrapper tracking whether its wallet holds at least `minimum`
+pub struct BalanceGuard {
+    inner: Arc<dyn AnchorProvider + Send + Sync>,
+    minimum: u128,
+    pause_when_short: bool,
+    short: AtomicBool,
+}
+
+impl BalanceGuard {
+    pub fn new(inner: Arc<dyn AnchorProvider + Send + Sync>, minimum: u128) -> Self {
+        Self {
+            inner,
+            minimum,
+            pause_when_short: false,
+            short: AtomicBool::new(false),
+        }
+    }
+
+    /// Report the provider unavailable while its wallet is short
+    pub fn with_pause_when_short(mut self, pause: bool) -> Self {
+        self.pause_when_short = pause;
+        self
+    }
+
+    /// Whether the last check found the wallet below the minimum
+    pub fn is_short(&self) -> bool {
+        self.short.load(Ordering::Relaxed)
+    }
+
+    /// Check the wallet now, warning if it is short. A failed check is
+    /// logged and leaves the previous verdict in place.
+    pub async fn check(&self) -> Option<BalanceCheck> {
+        let network = self.inner.network().unwrap_or("anchor");
+        match self.inner.check_sufficient_balance(self.minimum).await {
+            Ok(Some(check)) => {
+                let was_short = self.short.swap(!check.is_sufficient(), Ordering::Relaxed);
+                if !check.is_sufficient() {
+                    metrics::global().record_low_balance();
+                    tracing::warn!(
+                        network,
+                        balance = %check.balance,
+                        minimum = %check.minimum,
+                        paused = self.pause_when_short,
+                        "Anchoring wallet balance below minimum"
+                    );
+                } else if was_short {
+                    tracing::info!(network, balance = %check.balance, "Anchoring wallet topped up");
+                }
+                Some(check)
+            }
+            Ok(None) => None,
+            Err(e) => {
+                tracing::warn!(network, error = %e, "Failed to check anchoring wallet balance");
+                None
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl AnchorProvider for BalanceGuard {
+    async fn anchor(&self, evidence: &EvidenceRecord) -> Result<ChainTxRef, AnchorError> {
+        self.inner.anchor(evidence).await
+    }
+
+    async fn confirm(&self, tx: &ChainTxRef) -> Result<ChainTxRef, AnchorError> {
+        self.inner.confirm(tx).await
+    }
+
+    async fn confirm_many(&self, txs: &[ChainTxRef]) -> Vec<Result<ChainTxRef, AnchorError>> {
+        self.inner.confirm_many(txs).await
+    }
+
+    fn is_available(&self) -> bool {
+        !(self.pause_when_short && self.is_short()) && self.inner.is_available()
+    }
+
+    fn network(&self) -> Option<&str> {
+        self.inner.network()
+    }
+
+    async fn validate_network(&self) -> Result<(), AnchorError> {
+        self.inner.validate_network().await
+    }
+
+    async fn check_sufficient_balance(
+        &self,
+        min: u128,
+    ) -> Result<Option<BalanceCheck>, AnchorError> {
+        self.inner.check_sufficient_balance(min).await
+    }
+}
+
+/// Re-check every guard each `interval` until `shutdown` is set to true
+pub async fn run_balance_monitor(
+    guards: Vec<Arc<BalanceGuard>>,
+    interval: Duration,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    while !crate::idle(interval, &mut shutdown).await {
+        for guard in &guards {
+            guard.check().await;
+        }
+    }
+}
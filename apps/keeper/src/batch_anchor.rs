@@ -15,22 +15,18 @@
 use chrono::{DateTime, Utc};
 use phoenix_evidence::anchor::AnchorProvider;
 use phoenix_evidence::model::{ChainTxRef, DigestAlgo, EvidenceDigest, EvidenceRecord};
-use serde::{Deserialize, Serialize};
-use sha2::{Digest, Sha256};
 use sqlx::{Pool, Row, Sqlite};
 use std::sync::Arc;
 use std::time::Duration;
 use thiserror::Error;
-use tokio::sync::Mutex;
+use tokio::sync::{watch, Mutex};
 
-/// Errors that can occur during Merkle tree operations
-#[derive(Debug, Error)]
-pub enum MerkleError {
-    #[error("Invalid hex encoding: {0}")]
-    HexDecode(#[from] hex::FromHexError),
-    #[error("JSON serialization error: {0}")]
-    Json(#[from] serde_json::Error),
-}
+use crate::idle;
+
+pub use phoenix_evidence::merkle::{
+    verify_proof_bundle, MerkleError, MerkleProof, MerkleProofSibling, MerkleTree, ProofBundle,
+    MERKLE_CONSTRUCTION_VERSION, PROOF_BUNDLE_VERSION,
+};
 
 /// Errors that can occur during batch anchoring operations
 #[derive(Debug, Error)]
@@ -39,6 +35,10 @@ pub enum BatchError {
     Database(#[from] sqlx::Error),
     #[error("Merkle tree error: {0}")]
     Merkle(#[from] MerkleError),
+    #[error("No anchored proof for job {0}")]
+    ProofNotFound(String),
+    #[error("Batch transaction for job {0} is not yet confirmed")]
+    PendingConfirmation(String),
 }
 
 /// Configuration for batch anchoring
@@ -50,6 +50,8 @@ pub struct BatchConfig {
     pub max_batch_age_seconds: u64,
     /// Minimum batch size before anchoring (unless timeout)
     pub min_batch_size: usize,
+    /// Withhold proofs until the batch transaction is confirmed on chain
+    pub require_confirmed: bool,
 }
 
 impl Default for BatchConfig {
@@ -58,57 +60,11 @@ impl Default for BatchConfig {
             max_batch_size: 100,
             max_batch_age_seconds: 60,
             min_batch_size: 1,
+            require_confirmed: false,
         }
     }
 }
 
-/// Merkle proof for a single evidence item
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct MerkleProof {
-    /// The evidence hash being proven
-    pub leaf_hash: String,
-    /// Index of the leaf in the original batch
-    pub leaf_index: usize,
-    /// Sibling hashes from leaf to root
-    pub siblings: Vec<MerkleProofSibling>,
-    /// The computed Merkle root
-    pub root: String,
-}
-
-/// A sibling node in the Merkle proof
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct MerkleProofSibling {
-    /// The hash of the sibling node
-    pub hash: String,
-    /// Whether the sibling is on the left (true) or right (false)
-    pub is_left: bool,
-}
-
-impl MerkleProof {
-    /// Verify this proof against a given root hash.
-    ///
-    /// Returns an error if any hex string in the proof is malformed.
-    pub fn verify(&self, expected_root: &str) -> Result<bool, MerkleError> {
-        let mut current_hash = hex::decode(&self.leaf_hash)?;
-
-        for sibling in &self.siblings {
-            let sibling_hash = hex::decode(&sibling.hash)?;
-
-            let mut hasher = Sha256::new();
-            if sibling.is_left {
-                hasher.update(&sibling_hash);
-                hasher.update(&current_hash);
-            } else {
-                hasher.update(&current_hash);
-                hasher.update(&sibling_hash);
-            }
-            current_hash = hasher.finalize().to_vec();
-        }
-
-        Ok(hex::encode(current_hash) == expected_root)
-    }
-}
-
 /// A batch of evidence awaiting anchoring
 #[derive(Debug)]
 struct EvidenceBatch {
@@ -124,102 +80,6 @@ struct BatchItem {
     payload_sha256: String,
 }
 
-/// Merkle tree for batch anchoring
-#[derive(Debug)]
-pub struct MerkleTree {
-    /// Leaf hashes (bottom level)
-    leaves: Vec<Vec<u8>>,
-    /// All levels of the tree (leaves at 0, root at end)
-    levels: Vec<Vec<Vec<u8>>>,
-}
-
-impl MerkleTree {
-    /// Build a Merkle tree from leaf hashes.
-    ///
-    /// Returns an error if any input hash is not valid hex.
-    pub fn from_leaves(leaf_hashes: Vec<String>) -> Result<Self, MerkleError> {
-        let leaves: Vec<Vec<u8>> = leaf_hashes
-            .iter()
-            .map(hex::decode)
-            .collect::<Result<Vec<_>, _>>()?;
-
-        let mut levels = vec![leaves.clone()];
-        let mut current_level = leaves.clone();
-
-        // Build tree bottom-up
-        while current_level.len() > 1 {
-            let mut next_level = Vec::new();
-
-            for chunk in current_level.chunks(2) {
-                let mut hasher = Sha256::new();
-                hasher.update(&chunk[0]);
-                if chunk.len() > 1 {
-                    hasher.update(&chunk[1]);
-                } else {
-                    // Odd number of nodes - duplicate the last one
-                    hasher.update(&chunk[0]);
-                }
-                next_level.push(hasher.finalize().to_vec());
-            }
-
-            levels.push(next_level.clone());
-            current_level = next_level;
-        }
-
-        Ok(Self { leaves, levels })
-    }
-
-    /// Get the Merkle root hash
-    pub fn root(&self) -> String {
-        if let Some(top_level) = self.levels.last() {
-            if let Some(root) = top_level.first() {
-                return hex::encode(root);
-            }
-        }
-        String::new()
-    }
-
-    /// Generate a proof for a specific leaf index
-    pub fn proof(&self, index: usize) -> Option<MerkleProof> {
-        if index >= self.leaves.len() {
-            return None;
-        }
-
-        let mut siblings = Vec::new();
-        let mut current_index = index;
-
-        for level in &self.levels[..self.levels.len().saturating_sub(1)] {
-            let sibling_index = if current_index.is_multiple_of(2) {
-                current_index + 1
-            } else {
-                current_index - 1
-            };
-
-            if sibling_index < level.len() {
-                siblings.push(MerkleProofSibling {
-                    hash: hex::encode(&level[sibling_index]),
-                    is_left: current_index % 2 == 1,
-                });
-            } else {
-                // Odd number of nodes - sibling is self
-                siblings.push(MerkleProofSibling {
-                    hash: hex::encode(&level[current_index]),
-                    is_left: current_index % 2 == 1,
-                });
-            }
-
-            current_index /= 2;
-        }
-
-        Some(MerkleProof {
-            leaf_hash: hex::encode(&self.leaves[index]),
-            leaf_index: index,
-            siblings,
-            root: self.root(),
-        })
-    }
-}
-
 /// Batch anchoring job processor
 pub struct BatchAnchor {
     pool: Pool<Sqlite>,
@@ -257,13 +117,22 @@ impl BatchAnchor {
                 tx_network TEXT,
                 tx_chain TEXT,
                 tx_id TEXT,
-                tx_confirmed INTEGER DEFAULT 0
+                tx_confirmed INTEGER DEFAULT 0,
+                construction_version INTEGER NOT NULL DEFAULT 0
             )
             "#,
         )
         .execute(pool)
         .await?;
 
+        // Batches from before domain separation were built with the legacy
+        // construction, which the column's default records
+        crate::add_column(
+            pool,
+            "ALTER TABLE merkle_batches ADD COLUMN construction_version INTEGER NOT NULL DEFAULT 0",
+        )
+        .await?;
+
         // Individual proofs table
         sqlx::query(
             r#"
@@ -365,7 +234,7 @@ impl BatchAnchor {
             return Ok(());
         }
 
-        // Build Merkle tree
+        // Build Merkle tree; outbox digests are always SHA-256
         let leaf_hashes: Vec<String> = items.iter().map(|i| i.payload_sha256.clone()).collect();
         let tree = MerkleTree::from_leaves(leaf_hashes)?;
         let merkle_root = tree.root();
@@ -376,12 +245,13 @@ impl BatchAnchor {
 
         // Store batch metadata
         sqlx::query(
-            "INSERT INTO merkle_batches (id, merkle_root, item_count, created_at) VALUES (?1, ?2, ?3, ?4)",
+            "INSERT INTO merkle_batches (id, merkle_root, item_count, created_at, construction_version) VALUES (?1, ?2, ?3, ?4, ?5)",
         )
         .bind(&batch_id)
         .bind(&merkle_root)
         .bind(items.len() as i64)
         .bind(now_ms)
+        .bind(tree.construction_version() as i64)
         .execute(&self.pool)
         .await?;
 
@@ -469,6 +339,9 @@ impl BatchAnchor {
     }
 
     /// Get proof for a specific job
+    ///
+    /// With `require_confirmed`, returns `BatchError::PendingConfirmation`
+    /// until the batch transaction is confirmed.
     pub async fn get_proof(
         &self,
         job_id: &str,
@@ -496,6 +369,9 @@ impl BatchAnchor {
                 serde_json::from_str(&proof_json).map_err(MerkleError::from)?;
 
             if let (Some(network), Some(chain), Some(tx_id)) = (tx_network, tx_chain, tx_id) {
+                if self.config.require_confirmed && tx_confirmed == 0 {
+                    return Err(BatchError::PendingConfirmation(job_id.to_string()));
+                }
                 return Ok(Some((
                     proof,
                     ChainTxRef {
@@ -512,6 +388,117 @@ impl BatchAnchor {
         Ok(None)
     }
 
+    /// Export a portable proof bundle for an anchored job
+    pub async fn export_proof_bundle(&self, job_id: &str) -> Result<ProofBundle, BatchError> {
+        let row = sqlx::query(
+            r#"
+            SELECT p.proof_json, b.merkle_root, b.construction_version, b.tx_network,
+                   b.tx_chain, b.tx_id, b.tx_confirmed, b.anchored_at
+            FROM merkle_proofs p
+            JOIN merkle_batches b ON p.batch_id = b.id
+            WHERE p.job_id = ?1
+            "#,
+        )
+        .bind(job_id)
+        .fetch_optional(&self.pool)
+        .await?
+        .ok_or_else(|| BatchError::ProofNotFound(job_id.to_string()))?;
+
+        let proof_json: String = row.get("proof_json");
+        let merkle_root: String = row.get("merkle_root");
+        let construction_version: i64 = row.get("construction_version");
+        let tx_network: Option<String> = row.get("tx_network");
+        let tx_chain: Option<String> = row.get("tx_chain");
+        let tx_id: Option<String> = row.get("tx_id");
+        let tx_confirmed: i32 = row.get("tx_confirmed");
+        let anchored_at: Option<i64> = row.get("anchored_at");
+
+        // A batch that failed to anchor has no transaction to point at
+        let (Some(network), Some(chain), Some(tx_id)) = (tx_network, tx_chain, tx_id) else {
+            return Err(BatchError::ProofNotFound(job_id.to_string()));
+        };
+        if self.config.require_confirmed && tx_confirmed == 0 {
+            return Err(BatchError::PendingConfirmation(job_id.to_string()));
+        }
+
+        let proof: MerkleProof = serde_json::from_str(&proof_json).map_err(MerkleError::from)?;
+
+        Ok(ProofBundle {
+            version: PROOF_BUNDLE_VERSION,
+            job_id: job_id.to_string(),
+            network: network.clone(),
+            chain: chain.clone(),
+            merkle_root,
+            construction_version: construction_version as u8,
+            proof,
+            tx_ref: ChainTxRef {
+                network,
+                chain,
+                tx_id,
+                confirmed: tx_confirmed != 0,
+                timestamp: anchored_at.and_then(DateTime::<Utc>::from_timestamp_millis),
+            },
+        })
+    }
+
+    /// Re-check anchored but unconfirmed batches with the provider, marking
+    /// those it now reports confirmed. Returns how many were newly confirmed.
+    ///
+    /// Provider errors are logged and the batch is retried on the next pass.
+    pub async fn confirm_pending_batches(&self) -> Result<usize, BatchError> {
+        let rows = sqlx::query(
+            r#"
+            SELECT id, tx_network, tx_chain, tx_id, anchored_at
+            FROM merkle_batches
+            WHERE tx_id IS NOT NULL AND tx_confirmed = 0
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut confirmed = 0;
+        for row in rows {
+            let batch_id: String = row.get("id");
+            let tx_ref = ChainTxRef {
+                network: row
+                    .get::<Option<String>, _>("tx_network")
+                    .unwrap_or_default(),
+                chain: row.get::<Option<String>, _>("tx_chain").unwrap_or_default(),
+                tx_id: row.get("tx_id"),
+                confirmed: false,
+                timestamp: row
+                    .get::<Option<i64>, _>("anchored_at")
+                    .and_then(DateTime::<Utc>::from_timestamp_millis),
+            };
+
+            match self.anchor.confirm(&tx_ref).await {
+                Ok(checked) if checked.confirmed => {
+                    sqlx::query("UPDATE merkle_batches SET tx_confirmed = 1 WHERE id = ?1")
+                        .bind(&batch_id)
+                        .execute(&self.pool)
+                        .await?;
+                    confirmed += 1;
+                    tracing::info!(
+                        batch_id = %batch_id,
+                        tx_id = %tx_ref.tx_id,
+                        "Batch transaction confirmed"
+                    );
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::warn!(
+                        batch_id = %batch_id,
+                        tx_id = %tx_ref.tx_id,
+                        error = %e,
+                        "Batch confirmation check failed"
+                    );
+                }
+            }
+        }
+
+        Ok(confirmed)
+    }
+
     /// Get batch statistics
     pub async fn get_stats(&self) -> Result<BatchStats, sqlx::Error> {
         let batch = self.current_batch.lock().await;
@@ -543,82 +530,25 @@ pub struct BatchStats {
     pub total_items: usize,
 }
 
-/// Run the batch anchoring loop
-pub async fn run_batch_loop(batch_anchor: Arc<BatchAnchor>, poll_interval: Duration) {
+/// Run the batch anchoring loop: flush timed-out batches and confirm
+/// anchored ones every `poll_interval` until `shutdown` is set
+pub async fn run_batch_loop(
+    batch_anchor: Arc<BatchAnchor>,
+    poll_interval: Duration,
+    mut shutdown: watch::Receiver<bool>,
+) {
     loop {
+        if *shutdown.borrow() {
+            break;
+        }
         if let Err(e) = batch_anchor.check_timeout().await {
             tracing::error!(error = %e, "Batch timeout check failed");
         }
-        tokio::time::sleep(poll_interval).await;
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_merkle_tree_single_leaf() {
-        // Use valid hex strings for testing
-        let tree = MerkleTree::from_leaves(vec!["abc123".to_string()]).unwrap();
-        assert!(!tree.root().is_empty());
-    }
-
-    #[test]
-    fn test_merkle_tree_multiple_leaves() {
-        // Use valid hex strings for testing
-        let leaves = vec![
-            "abcd".to_string(),
-            "1234".to_string(),
-            "5678".to_string(),
-            "9abc".to_string(),
-        ];
-        let tree = MerkleTree::from_leaves(leaves).unwrap();
-
-        // Verify each proof
-        for i in 0..4 {
-            let proof = tree.proof(i).unwrap();
-            assert!(proof.verify(&tree.root()).unwrap());
+        if let Err(e) = batch_anchor.confirm_pending_batches().await {
+            tracing::error!(error = %e, "Batch confirmation check failed");
+        }
+        if idle(poll_interval, &mut shutdown).await {
+            break;
         }
-    }
-
-    #[test]
-    fn test_merkle_proof_verification() {
-        // Use valid hex strings for testing
-        let leaves = vec!["aa".to_string(), "bb".to_string()];
-        let tree = MerkleTree::from_leaves(leaves).unwrap();
-
-        let proof0 = tree.proof(0).unwrap();
-        let proof1 = tree.proof(1).unwrap();
-
-        assert!(proof0.verify(&tree.root()).unwrap());
-        assert!(proof1.verify(&tree.root()).unwrap());
-
-        // Wrong root should fail (but return Ok(false), not an error for valid hex)
-        assert!(!proof0.verify(&tree.root().replace("a", "b")).unwrap());
-    }
-
-    #[test]
-    fn test_merkle_tree_invalid_hex() {
-        // Invalid hex should return an error
-        let result = MerkleTree::from_leaves(vec!["not_valid_hex!".to_string()]);
-        assert!(result.is_err());
-    }
-
-    #[test]
-    fn test_merkle_proof_verify_invalid_hex() {
-        let leaves = vec!["aa".to_string(), "bb".to_string()];
-        let tree = MerkleTree::from_leaves(leaves).unwrap();
-        let proof = tree.proof(0).unwrap();
-
-        // Invalid hex in expected_root should return an error
-        // Note: The expected_root is compared as hex string, so this tests
-        // that invalid sibling hashes would be caught
-        let mut bad_proof = proof.clone();
-        bad_proof.siblings = vec![MerkleProofSibling {
-            hash: "not_valid_hex!".to_string(),
-            is_left: false,
-        }];
-        assert!(bad_proof.verify(&tree.root()).is_err());
     }
 }
@@ -0,0 +1,288 @@
+//! Circuit breaker around the anchoring provider.
+//!
+//! After `failure_threshold` consecutive temporary failures (network or
+//! provider errors) the breaker opens. While open, jobs go to the fallback
+//! provider if one is configured; otherwise [`AnchorProvider::is_available`]
+//! reports false and the job loops stop claiming jobs instead of burning
+//! their retry budget. Once `probe_interval` has passed, the next job probes
+//! the primary: success closes the breaker, failure reopens it.
+
+use crate::metrics;
+use async_trait::async_trait;
+use phoenix_evidence::anchor::{AnchorError, AnchorProvider, BalanceCheck};
+use phoenix_evidence::model::{ChainTxRef, EvidenceRecord};
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// Consecutive temporary failures before the breaker opens
+pub const DEFAULT_FAILURE_THRESHOLD: u32 = 5;
+
+/// How long the breaker stays open before probing the primary
+pub const DEFAULT_PROBE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Breaker state, as exported by the `keeper_circuit_breaker_state` gauge
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakerState {
+    /// Primary healthy; counting consecutive failures
+    Closed,
+    /// Primary failing; waiting out the probe interval
+    Open,
+    /// A probe request to the primary is in flight
+    HalfOpen,
+}
+
+impl BreakerState {
+    fn gauge_value(self) -> u64 {
+        match self {
+            BreakerState::Closed => 0,
+            BreakerState::Open => 1,
+            BreakerState::HalfOpen => 2,
+        }
+    }
+}
+
+#[derive(Debug)]
+enum State {
+    Closed { failures: u32 },
+    Open { until: Instant },
+    HalfOpen,
+}
+
+/// Which provider a single anchor call goes to
+enum Route {
+    Primary { probe: bool },
+    Fallback,
+    Rejected,
+}
+
+pub struct CircuitBreakerProvider {
+    primary: Arc<dyn AnchorProvider + Send + Sync>,
+    fallback: Option<Arc<dyn AnchorProvider + Send + Sync>>,
+    failure_threshold: u32,
+    probe_interval: Duration,
+    state: Mutex<State>,
+    /// Networks of transactions the fallback anchored, so their
+    /// confirmations are checked against the fallback
+    fallback_networks: Mutex<HashSet<String>>,
+}
+
+impl CircuitBreakerProvider {
+    pub fn new(primary: Arc<dyn AnchorProvider + Send + Sync>) -> Self {
+        Self {
+            primary,
+            fallback: None,
+            failure_threshold: DEFAULT_FAILURE_THRESHOLD,
+            probe_interval: DEFAULT_PROBE_INTERVAL,
+            state: Mutex::new(State::Closed { failures: 0 }),
+            fallback_networks: Mutex::new(HashSet::new()),
+        }
+    }
+
+    /// Anchor through `fallback` while the breaker is open
+    pub fn with_fallback(mut self, fallback: Arc<dyn AnchorProvider + Send + Sync>) -> Self {
+        self.fallback = Some(fallback);
+        self
+    }
+
+    pub fn with_failure_threshold(mut self, failure_threshold: u32) -> Self {
+        self.failure_threshold = failure_threshold.max(1);
+        self
+    }
+
+    pub fn with_probe_interval(mut self, probe_interval: Duration) -> Self {
+        self.probe_interval = probe_interval;
+        self
+    }
+
+    pub fn state(&self) -> BreakerState {
+        match *self.state.lock().unwrap_or_else(|e| e.into_inner()) {
+            State::Closed { .. } => BreakerState::Closed,
+            State::Open { .. } => BreakerState::Open,
+            State::HalfOpen => BreakerState::HalfOpen,
+        }
+    }
+
+    fn set_state(&self, state: &mut State, next: State) {
+        *state = next;
+        let public = match state {
+            State::Closed { .. } => BreakerState::Closed,
+            State::Open { .. } => BreakerState::Open,
+            State::HalfOpen => BreakerState::HalfOpen,
+        };
+        metrics::global().set_breaker_state(public.gauge_value());
+    }
+
+    fn route(&self) -> Route {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        match *state {
+            State::Closed { .. } => Route::Primary { probe: false },
+            State::Open { until } if Instant::now() >= until => {
+                self.set_state(&mut state, State::HalfOpen);
+                tracing::info!("Circuit breaker half-open; probing primary anchor provider");
+                Route::Primary { probe: true }
+            }
+            State::Open { .. } | State::HalfOpen => {
+                if self.fallback.is_some() {
+                    Route::Fallback
+                } else {
+                    Route::Rejected
+                }
+            }
+        }
+    }
+
+    fn record_primary_result(&self, probe: bool, result: &Result<ChainTxRef, AnchorError>) {
+        let temporary = matches!(
+            result,
+            Err(AnchorError::Network(_)) | Err(AnchorError::Provider(_))
+        );
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+        if !temporary {
+            // Any answer from the provider, even a rejection, means it is reachable
+            if !matches!(*state, State::Closed { failures: 0 }) {
+                if probe {
+                    tracing::info!("Primary anchor provider recovered; circuit breaker closed");
+                }
+                self.set_state(&mut state, State::Closed { failures: 0 });
+            }
+            return;
+        }
+
+        let failures = match *state {
+            State::Closed { failures } if !probe => failures + 1,
+            _ => self.failure_threshold,
+        };
+        if failures >= self.failure_threshold {
+            tracing::warn!(
+                failures,
+                probe_in = ?self.probe_interval,
+                "Circuit breaker open; primary anchor provider failing"
+            );
+            self.set_state(
+                &mut state,
+                State::Open {
+                    until: Instant::now() + self.probe_interval,
+                },
+            );
+        } else {
+            *state = State::Closed { failures };
+        }
+    }
+
+    async fn anchor_fallback(
+        &self,
+        fallback: &Arc<dyn AnchorProvider + Send + Sync>,
+        evidence: &EvidenceRecord,
+    ) -> Result<ChainTxRef, AnchorError> {
+        let tx = fallback.anchor(evidence).await?;
+        metrics::global().record_failover();
+        self.fallback_networks
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(tx.network.clone());
+        Ok(tx)
+    }
+}
+
+#[async_trait]
+impl AnchorProvider for CircuitBreakerProvider {
+    async fn anchor(&self, evidence: &EvidenceRecord) -> Result<ChainTxRef, AnchorError> {
+        match self.route() {
+            Route::Primary { probe } => {
+                let result = self.primary.anchor(evidence).await;
+                self.record_primary_result(probe, &result);
+                match (&result, &self.fallback) {
+                    (
+                        Err(AnchorError::Network(_)) | Err(AnchorError::Provider(_)),
+                        Some(fallback),
+                    ) => self.anchor_fallback(fallback, evidence).await,
+                    _ => result,
+                }
+            }
+            Route::Fallback => match &self.fallback {
+                Some(fallback) => self.anchor_fallback(fallback, evidence).await,
+                None => Err(AnchorError::Network("circuit breaker open".to_string())),
+            },
+            Route::Rejected => Err(AnchorError::Network("circuit breaker open".to_string())),
+        }
+    }
+
+    async fn confirm(&self, tx: &ChainTxRef) -> Result<ChainTxRef, AnchorError> {
+        let anchored_by_fallback = self
+            .fallback_networks
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .contains(&tx.network);
+        match &self.fallback {
+            Some(fallback) if anchored_by_fallback => fallback.confirm(tx).await,
+            _ => self.primary.confirm(tx).await,
+        }
+    }
+
+    /// Splits `txs` between primary and fallback so each backend still gets
+    /// one batch
+    async fn confirm_many(&self, txs: &[ChainTxRef]) -> Vec<Result<ChainTxRef, AnchorError>> {
+        let Some(fallback) = &self.fallback else {
+            return self.primary.confirm_many(txs).await;
+        };
+        let (by_fallback, by_primary): (Vec<_>, Vec<_>) = {
+            let fallback_networks = self
+                .fallback_networks
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            (0..txs.len()).partition(|&i| fallback_networks.contains(&txs[i].network))
+        };
+
+        let mut results: Vec<Option<Result<ChainTxRef, AnchorError>>> =
+            (0..txs.len()).map(|_| None).collect();
+        for (provider, indices) in [(&self.primary, by_primary), (fallback, by_fallback)] {
+            if indices.is_empty() {
+                continue;
+            }
+            let batch: Vec<ChainTxRef> = indices.iter().map(|&i| txs[i].clone()).collect();
+            for (i, result) in indices.into_iter().zip(provider.confirm_many(&batch).await) {
+                results[i] = Some(result);
+            }
+        }
+        results
+            .into_iter()
+            .map(|result| {
+                result.unwrap_or_else(|| {
+                    Err(AnchorError::Provider("no confirmation result".to_string()))
+                })
+            })
+            .collect()
+    }
+
+    fn is_available(&self) -> bool {
+        if self.fallback.is_some() {
+            return true;
+        }
+        match *self.state.lock().unwrap_or_else(|e| e.into_inner()) {
+            State::Closed { .. } => true,
+            State::Open { until } => Instant::now() >= until,
+            State::HalfOpen => false,
+        }
+    }
+
+    fn network(&self) -> Option<&str> {
+        // With a fallback, anchors may land on either provider's network
+        match self.fallback {
+            Some(_) => None,
+            None => self.primary.network(),
+        }
+    }
+
+    async fn validate_network(&self) -> Result<(), AnchorError> {
+        self.primary.validate_network().await
+    }
+
+    /// The primary's wallet: it is the one expected to pay for anchors
+    async fn check_sufficient_balance(
+        &self,
+        min: u128,
+    ) -> Result<Option<BalanceCheck>, AnchorError> {
+        self.primary.check_sufficient_balance(min).await
+    }
+}
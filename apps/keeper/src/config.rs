@@ -6,6 +6,22 @@ pub struct KeeperConfig {
     pub job_poll_interval: Duration,
     pub confirmation_poll_interval: Duration,
     pub http_port: u16,
+    /// Attempts before a job is dead-lettered
+    pub max_attempts: i64,
+    /// Jobs anchored in parallel per tenant
+    pub concurrency: usize,
+    /// Age after which an `in_progress` job is requeued by the reaper
+    pub stale_after: Duration,
+    /// Age after which an unconfirmed tx unknown to the chain is re-anchored
+    pub confirm_timeout: Duration,
+    /// Unconfirmed txs checked per confirmation poll
+    pub confirm_batch_size: i64,
+    /// Reuse a confirmed anchor for jobs repeating an already-anchored digest
+    pub dedupe_digests: bool,
+    /// Consecutive anchor failures before the circuit breaker opens
+    pub breaker_threshold: u32,
+    /// How long the breaker stays open before probing the provider again
+    pub breaker_probe_interval: Duration,
     pub provider_config: ProviderConfig,
 }
 
@@ -47,6 +63,14 @@ impl Default for KeeperConfig {
             job_poll_interval: Duration::from_secs(5),
             confirmation_poll_interval: Duration::from_secs(30),
             http_port: 8081,
+            max_attempts: crate::DEFAULT_MAX_ATTEMPTS,
+            concurrency: 1,
+            stale_after: crate::DEFAULT_STALE_AFTER,
+            confirm_timeout: crate::DEFAULT_CONFIRM_TIMEOUT,
+            confirm_batch_size: crate::DEFAULT_CONFIRM_BATCH_SIZE,
+            dedupe_digests: false,
+            breaker_threshold: crate::circuit_breaker::DEFAULT_FAILURE_THRESHOLD,
+            breaker_probe_interval: crate::circuit_breaker::DEFAULT_PROBE_INTERVAL,
             provider_config: ProviderConfig::Stub,
         }
     }
@@ -81,6 +105,58 @@ impl KeeperConfig {
             }
         }
 
+        if let Ok(max_attempts) = std::env::var("KEEPER_MAX_ATTEMPTS") {
+            if let Ok(n) = max_attempts.parse::<i64>() {
+                config.max_attempts = n;
+            }
+        }
+
+        if let Ok(concurrency) = std::env::var("KEEPER_CONCURRENCY") {
+            if let Ok(n) = concurrency.parse::<usize>() {
+                config.concurrency = n;
+            }
+        }
+
+        if let Ok(stale_ms) = std::env::var("KEEPER_STALE_AFTER_MS") {
+            if let Ok(ms) = stale_ms.parse::<u64>() {
+                if ms > 0 {
+                    config.stale_after = Duration::from_millis(ms);
+                }
+            }
+        }
+
+        if let Ok(timeout_ms) = std::env::var("KEEPER_CONFIRM_TIMEOUT_MS") {
+            if let Ok(ms) = timeout_ms.parse::<u64>() {
+                if ms > 0 {
+                    config.confirm_timeout = Duration::from_millis(ms);
+                }
+            }
+        }
+
+        if let Ok(batch_size) = std::env::var("KEEPER_CONFIRM_BATCH_SIZE") {
+            if let Ok(n) = batch_size.parse::<i64>() {
+                if n > 0 {
+                    config.confirm_batch_size = n;
+                }
+            }
+        }
+
+        if let Ok(dedupe) = std::env::var("KEEPER_DEDUPE_DIGESTS") {
+            config.dedupe_digests = dedupe == "true" || dedupe == "1";
+        }
+
+        if let Ok(threshold) = std::env::var("KEEPER_BREAKER_THRESHOLD") {
+            if let Ok(n) = threshold.parse::<u32>() {
+                config.breaker_threshold = n;
+            }
+        }
+
+        if let Ok(probe_ms) = std::env::var("KEEPER_BREAKER_PROBE_MS") {
+            if let Ok(ms) = probe_ms.parse::<u64>() {
+                config.breaker_probe_interval = Duration::from_millis(ms);
+            }
+        }
+
         // Provider configuration
         config.provider_config = match std::env::var("KEEPER_PROVIDER").as_deref() {
             Ok("etherlink") => {
@@ -4,9 +4,51 @@ use phoenix_evidence::anchor::{AnchorError, AnchorProvider};
 use phoenix_evidence::model::{ChainTxRef, DigestAlgo, EvidenceDigest, EvidenceRecord};
 use rand::RngExt;
 use sqlx::{Pool, Row, Sqlite};
+use std::sync::Arc;
+use tokio::sync::{watch, Semaphore};
+use tokio::task::JoinSet;
 
+pub mod balance;
 pub mod batch_anchor;
+pub mod circuit_breaker;
 pub mod config;
+pub mod metrics;
+pub mod providers;
+pub mod tenants;
+
+/// Attempts before a temporarily failing job is dead-lettered (`KEEPER_MAX_ATTEMPTS`)
+pub const DEFAULT_MAX_ATTEMPTS: i64 = 10;
+
+/// Status of jobs that exhausted their attempts; only a manual replay requeues them
+pub const DEAD_LETTER_STATUS: &str = "dead_letter";
+
+/// Age after which an `in_progress` job is presumed abandoned (`KEEPER_STALE_AFTER_MS`)
+pub const DEFAULT_STALE_AFTER: std::time::Duration = std::time::Duration::from_secs(600);
+
+/// Age after which an unconfirmed tx the chain reports as unknown is abandoned
+/// and its job re-anchored (`KEEPER_CONFIRM_TIMEOUT_MS`)
+pub const DEFAULT_CONFIRM_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(600);
+
+/// Unconfirmed tx refs checked per confirmation poll (`KEEPER_CONFIRM_BATCH_SIZE`)
+pub const DEFAULT_CONFIRM_BATCH_SIZE: i64 = 100;
+
+/// Reclaims after which a job that keeps getting stranded `in_progress` is
+/// failed instead of requeued, so a job that crashes the keeper can't loop
+pub const MAX_RECLAIMS: i64 = 3;
+
+/// How often the reaper looks for abandoned jobs
+pub const REAPER_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
+const BACKOFF_BASE_MS: i64 = 5_000;
+const BACKOFF_CAP_MS: i64 = 300_000;
+
+/// Retry delay after `attempts` attempts, before jitter: 5s doubling, capped at 5m
+pub fn backoff_ms(attempts: i64) -> i64 {
+    let exp = attempts.clamp(0, 20) as u32;
+    BACKOFF_BASE_MS
+        .saturating_mul(2i64.pow(exp))
+        .min(BACKOFF_CAP_MS)
+}
 
 /// Initialize database schema for the keeper
 pub async fn ensure_schema(pool: &Pool<Sqlite>) -> Result<(), sqlx::Error> {
@@ -21,13 +63,27 @@ pub async fn ensure_schema(pool: &Pool<Sqlite>) -> Result<(), sqlx::Error> {
             last_error TEXT,
             created_ms INTEGER NOT NULL,
             updated_ms INTEGER NOT NULL,
-            next_attempt_ms INTEGER NOT NULL DEFAULT 0
+            next_attempt_ms INTEGER NOT NULL DEFAULT 0,
+            priority INTEGER NOT NULL DEFAULT 0,
+            reclaim_count INTEGER NOT NULL DEFAULT 0
         )
         "#,
     )
     .execute(pool)
     .await?;
 
+    // Migrate tables created before job priority and reclaim counting existed
+    add_column(
+        pool,
+        "ALTER TABLE outbox_jobs ADD COLUMN priority INTEGER NOT NULL DEFAULT 0",
+    )
+    .await?;
+    add_column(
+        pool,
+        "ALTER TABLE outbox_jobs ADD COLUMN reclaim_count INTEGER NOT NULL DEFAULT 0",
+    )
+    .await?;
+
     // Create outbox_tx_refs table
     sqlx::query(
         r#"
@@ -45,9 +101,148 @@ pub async fn ensure_schema(pool: &Pool<Sqlite>) -> Result<(), sqlx::Error> {
     .execute(pool)
     .await?;
 
+    sqlx::query(
+        "CREATE INDEX IF NOT EXISTS idx_outbox_tx_refs_unconfirmed ON outbox_tx_refs(confirmed, timestamp)",
+    )
+    .execute(pool)
+    .await?;
+
+    // Transactions that never landed; their jobs were requeued for re-anchoring
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS outbox_abandoned_txs (
+            job_id TEXT NOT NULL,
+            network TEXT NOT NULL,
+            chain TEXT NOT NULL,
+            tx_id TEXT NOT NULL,
+            reason TEXT,
+            abandoned_ms INTEGER NOT NULL,
+            PRIMARY KEY (job_id, network, chain, tx_id)
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
     Ok(())
 }
 
+/// Run an `ALTER TABLE ... ADD COLUMN`, treating an existing column as done
+pub(crate) async fn add_column(pool: &Pool<Sqlite>, statement: &str) -> Result<(), sqlx::Error> {
+    match sqlx::query(statement).execute(pool).await {
+        Ok(_) => Ok(()),
+        Err(e) if e.to_string().contains("duplicate column name") => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Requeue jobs left `in_progress` for longer than `stale_after`, e.g. by a
+/// keeper that crashed mid-job. Each reclaim bumps the job's `reclaim_count`;
+/// the attempt the stranded claim used still counts. A job already reclaimed
+/// [`MAX_RECLAIMS`] times is marked failed instead. Returns the number of
+/// jobs requeued.
+///
+/// `stale_after` must comfortably exceed the slowest anchor call, or a job
+/// still being worked on is reclaimed and anchored twice.
+pub async fn reclaim_stale_jobs(
+    pool: &Pool<Sqlite>,
+    stale_after: std::time::Duration,
+) -> Result<u64, sqlx::Error> {
+    let now_ms = Utc::now().timestamp_millis();
+    let cutoff_ms = now_ms - stale_after.as_millis() as i64;
+    let failed = sqlx::query(
+        "UPDATE outbox_jobs SET status='failed', last_error=?1, updated_ms=?2 WHERE status='in_progress' AND updated_ms < ?3 AND reclaim_count >= ?4",
+    )
+    .bind(format!(
+        "abandoned in_progress after {} reclaims",
+        MAX_RECLAIMS
+    ))
+    .bind(now_ms)
+    .bind(cutoff_ms)
+    .bind(MAX_RECLAIMS)
+    .execute(pool)
+    .await?;
+    let result = sqlx::query(
+        "UPDATE outbox_jobs SET status='queued', reclaim_count=reclaim_count+1, updated_ms=?1, next_attempt_ms=?1 WHERE status='in_progress' AND updated_ms < ?2",
+    )
+    .bind(now_ms)
+    .bind(cutoff_ms)
+    .execute(pool)
+    .await?;
+
+    if failed.rows_affected() > 0 {
+        tracing::error!(
+            failed = failed.rows_affected(),
+            max_reclaims = MAX_RECLAIMS,
+            "Failed jobs repeatedly stranded in_progress"
+        );
+    }
+    Ok(result.rows_affected())
+}
+
+/// Run [`reclaim_stale_jobs`] every `interval` until `shutdown` is set
+pub async fn run_reaper_loop(
+    pool: &Pool<Sqlite>,
+    stale_after: std::time::Duration,
+    interval: std::time::Duration,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    loop {
+        if *shutdown.borrow() {
+            break;
+        }
+        match reclaim_stale_jobs(pool, stale_after).await {
+            Ok(0) => {}
+            Ok(reclaimed) => {
+                tracing::warn!(reclaimed, "Requeued jobs stuck in_progress");
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to reclaim stale jobs");
+            }
+        }
+        if idle(interval, &mut shutdown).await {
+            break;
+        }
+    }
+}
+
+/// A job parked in `dead_letter` after exhausting its attempts
+#[derive(Debug, Clone)]
+pub struct DeadLetterJob {
+    pub id: String,
+    pub payload_sha256: String,
+    pub attempts: i64,
+    pub last_error: Option<String>,
+    pub created_ms: i64,
+    pub updated_ms: i64,
+}
+
+/// Dead-lettered jobs, most recently failed first
+pub async fn fetch_dead_letters(
+    pool: &Pool<Sqlite>,
+    limit: i64,
+) -> Result<Vec<DeadLetterJob>, sqlx::Error> {
+    let rows = sqlx::query(
+        "SELECT id, payload_sha256, attempts, last_error, created_ms, updated_ms FROM outbox_jobs WHERE status = ?1 ORDER BY updated_ms DESC LIMIT ?2",
+    )
+    .bind(DEAD_LETTER_STATUS)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| DeadLetterJob {
+            id: row.get(0),
+            payload_sha256: row.get(1),
+            attempts: row.get(2),
+            last_error: row.get(3),
+            created_ms: row.get(4),
+            updated_ms: row.get(5),
+        })
+        .collect())
+}
+
 #[derive(Debug, Clone)]
 pub struct EvidenceJob {
     pub id: String,
@@ -76,6 +271,17 @@ pub trait JobProvider {
     async fn mark_failed(&mut self, id: &str, reason: &str) -> Result<(), JobError>;
 }
 
+/// What [`JobProviderExt::mark_failed_or_backoff`] did with a job
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BackoffOutcome {
+    /// Queued again for a later attempt
+    Requeued,
+    /// Out of attempts and moved to `dead_letter`
+    DeadLettered,
+    /// Failed permanently
+    Failed,
+}
+
 #[async_trait]
 pub trait JobProviderExt: JobProvider {
     async fn mark_tx_and_done(&mut self, id: &str, tx: &ChainTxRef) -> Result<(), JobError>;
@@ -84,79 +290,358 @@ pub trait JobProviderExt: JobProvider {
         id: &str,
         reason: &str,
         temporary: bool,
+    ) -> Result<BackoffOutcome, JobError>;
+
+    /// Store every backend's tx ref and mark the job done. `partial_failure`
+    /// describes backends that failed; providers must keep it as the job's
+    /// `last_error` so a partly anchored job is distinguishable from a clean one.
+    async fn mark_txs_and_done(
+        &mut self,
+        id: &str,
+        txs: &[ChainTxRef],
+        partial_failure: Option<&str>,
     ) -> Result<(), JobError>;
+
+    /// Hand a claimed job back to the queue without counting the attempt,
+    /// e.g. when the keeper shuts down mid-job. Providers without durable
+    /// claims have nothing to release.
+    async fn release(&mut self, _id: &str) -> Result<(), JobError> {
+        Ok(())
+    }
+
+    /// Confirmed tx refs of another job carrying the same digest, at most one
+    /// per network and chain. Non-empty only when the provider dedupes; the
+    /// job is then marked done with these refs instead of anchored again.
+    async fn find_confirmed_anchor(
+        &mut self,
+        _payload_sha256: &str,
+        _job_id: &str,
+    ) -> Result<Vec<ChainTxRef>, JobError> {
+        Ok(Vec::new())
+    }
+}
+
+/// Resolves once `shutdown` is set to true. If every sender is dropped
+/// without signalling, it never resolves and the loop runs on.
+pub(crate) async fn shutdown_signalled(shutdown: &mut watch::Receiver<bool>) {
+    if shutdown.wait_for(|stop| *stop).await.is_err() {
+        std::future::pending::<()>().await;
+    }
+}
+
+/// Sleep for `poll`; returns true if shutdown was signalled meanwhile
+pub(crate) async fn idle(poll: std::time::Duration, shutdown: &mut watch::Receiver<bool>) -> bool {
+    tokio::select! {
+        _ = tokio::time::sleep(poll) => false,
+        _ = shutdown_signalled(shutdown) => true,
+    }
+}
+
+/// An anchor backend as held by the job and confirmation loops
+pub type BoxedAnchor = Box<dyn AnchorProvider + Send + Sync>;
+
+/// Jobs are claimed while at least one backend can take them
+fn any_available(anchors: &[BoxedAnchor]) -> bool {
+    anchors.iter().any(|anchor| anchor.is_available())
 }
 
-pub async fn run_job_loop<J: JobProvider + JobProviderExt, A: AnchorProvider + ?Sized>(
+/// Claim jobs and anchor each on every backend in `anchors` until
+/// `shutdown` is set to true.
+///
+/// A job in flight when shutdown is signalled is abandoned and released
+/// back to `queued`, so it is not stranded as `in_progress`.
+pub async fn run_job_loop<J: JobProvider + JobProviderExt + Send>(
     provider: &mut J,
-    anchor: &A,
+    anchors: &[BoxedAnchor],
     poll: std::time::Duration,
+    mut shutdown: watch::Receiver<bool>,
 ) {
     loop {
+        if *shutdown.borrow() {
+            break;
+        }
+        if !any_available(anchors) {
+            if idle(poll, &mut shutdown).await {
+                break;
+            }
+            continue;
+        }
         match provider.fetch_next().await {
-            Ok(Some(job)) => {
-                let ev = EvidenceRecord {
-                    id: job.id.clone(),
-                    created_at: Utc::now(),
-                    digest: EvidenceDigest {
-                        algo: DigestAlgo::Sha256,
-                        hex: job.payload_sha256.clone(),
-                    },
-                    payload_mime: None,
-                    metadata: serde_json::json!({}),
-                };
-                match anchor.anchor(&ev).await {
-                    Ok(txref) => {
-                        let _ = provider.mark_tx_and_done(&job.id, &txref).await;
-                    }
-                    Err(e) => {
-                        let temporary =
-                            matches!(e, AnchorError::Network(_) | AnchorError::Provider(_));
-                        let _ = provider
-                            .mark_failed_or_backoff(&job.id, &e.to_string(), temporary)
-                            .await;
-                    }
+            Ok(Some(job)) => process_or_release(provider, anchors, job, &mut shutdown).await,
+            Ok(None) => {
+                if idle(poll, &mut shutdown).await {
+                    break;
                 }
             }
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to fetch next job");
+                if idle(poll, &mut shutdown).await {
+                    break;
+                }
+            }
+        }
+    }
+    tracing::info!("Job loop stopped");
+}
+
+/// Like [`run_job_loop`], but anchors up to `concurrency` jobs at once.
+///
+/// Jobs are still claimed one at a time through `fetch_next`, which moves
+/// each row to `in_progress` before returning it, so no job is handed to two
+/// workers. Each worker records its outcome through its own provider clone.
+/// On shutdown, no new jobs are claimed and in-flight jobs are released.
+pub async fn run_job_loop_concurrent<J>(
+    mut provider: J,
+    anchors: Arc<Vec<BoxedAnchor>>,
+    poll: std::time::Duration,
+    concurrency: usize,
+    mut shutdown: watch::Receiver<bool>,
+) where
+    J: JobProvider + JobProviderExt + Clone + Send + 'static,
+{
+    let permits = Arc::new(Semaphore::new(concurrency.max(1)));
+    let mut workers = JoinSet::new();
+    loop {
+        // Wait for a free worker before claiming, so claimed jobs never queue
+        let permit = tokio::select! {
+            permit = permits.clone().acquire_owned() => match permit {
+                Ok(permit) => permit,
+                Err(_) => break,
+            },
+            _ = shutdown_signalled(&mut shutdown) => break,
+        };
+        while let Some(finished) = workers.try_join_next() {
+            if let Err(e) = finished {
+                tracing::error!(error = %e, "Job worker panicked");
+            }
+        }
+        if *shutdown.borrow() {
+            break;
+        }
+        if !any_available(&anchors) {
+            drop(permit);
+            if idle(poll, &mut shutdown).await {
+                break;
+            }
+            continue;
+        }
+
+        match provider.fetch_next().await {
+            Ok(Some(job)) => {
+                let mut worker_provider = provider.clone();
+                let anchors = anchors.clone();
+                let mut worker_shutdown = shutdown.clone();
+                workers.spawn(async move {
+                    process_or_release(
+                        &mut worker_provider,
+                        anchors.as_slice(),
+                        job,
+                        &mut worker_shutdown,
+                    )
+                    .await;
+                    drop(permit);
+                });
+            }
             Ok(None) => {
-                tokio::time::sleep(poll).await;
+                drop(permit);
+                if idle(poll, &mut shutdown).await {
+                    break;
+                }
             }
             Err(e) => {
+                drop(permit);
                 tracing::error!(error = %e, "Failed to fetch next job");
-                tokio::time::sleep(poll).await;
+                if idle(poll, &mut shutdown).await {
+                    break;
+                }
+            }
+        }
+    }
+    // Workers see the same signal and release their jobs promptly
+    while let Some(finished) = workers.join_next().await {
+        if let Err(e) = finished {
+            tracing::error!(error = %e, "Job worker panicked");
+        }
+    }
+    tracing::info!("Job loop stopped");
+}
+
+/// Run [`process_job`], unless shutdown is signalled first: then the
+/// anchor call is dropped and the claim released for the next start
+async fn process_or_release<J: JobProviderExt + Send>(
+    provider: &mut J,
+    anchors: &[BoxedAnchor],
+    job: EvidenceJob,
+    shutdown: &mut watch::Receiver<bool>,
+) {
+    let id = job.id.clone();
+    let finished = tokio::select! {
+        _ = process_job(provider, anchors, job) => true,
+        _ = shutdown_signalled(shutdown) => false,
+    };
+    if !finished {
+        match provider.release(&id).await {
+            Ok(()) => tracing::info!(job_id = %id, "Released in-progress job on shutdown"),
+            Err(e) => tracing::error!(job_id = %id, error = %e, "Failed to release job"),
+        }
+    }
+}
+
+/// Anchor one claimed job on every backend and record the outcome.
+///
+/// The job is done once any backend anchors it; failures on the others are
+/// kept in `last_error`. If every backend fails, the job backs off when any
+/// failure was temporary and fails permanently otherwise.
+async fn process_job<J: JobProviderExt + Send>(
+    provider: &mut J,
+    anchors: &[BoxedAnchor],
+    job: EvidenceJob,
+) {
+    match provider
+        .find_confirmed_anchor(&job.payload_sha256, &job.id)
+        .await
+    {
+        Ok(existing) if !existing.is_empty() => {
+            tracing::info!(
+                job_id = %job.id,
+                digest = %job.payload_sha256,
+                tx_id = %existing[0].tx_id,
+                "Digest already anchored; reusing confirmed tx ref"
+            );
+            if provider
+                .mark_txs_and_done(&job.id, &existing, None)
+                .await
+                .is_ok()
+            {
+                metrics::global().record_processed();
             }
+            return;
+        }
+        Ok(_) => {}
+        Err(e) => {
+            tracing::warn!(job_id = %job.id, error = %e, "Dedupe lookup failed; anchoring anyway");
         }
     }
+
+    let ev = EvidenceRecord {
+        id: job.id.clone(),
+        created_at: Utc::now(),
+        // The API only accepts SHA-256 digests into the outbox
+        digest: EvidenceDigest {
+            algo: DigestAlgo::Sha256,
+            hex: job.payload_sha256.clone(),
+        },
+        payload_mime: None,
+        metadata: serde_json::json!({}),
+    };
+
+    let mut txs = Vec::new();
+    let mut failures = Vec::new();
+    let mut temporary = false;
+    for (index, anchor) in anchors.iter().enumerate() {
+        let started = std::time::Instant::now();
+        let result = anchor.anchor(&ev).await;
+        metrics::global().observe_anchor_latency(started.elapsed());
+        match result {
+            Ok(txref) => txs.push(txref),
+            Err(e) => {
+                temporary |= matches!(e, AnchorError::Network(_) | AnchorError::Provider(_));
+                // Name the backend only when there is more than one
+                if anchors.len() == 1 {
+                    failures.push(e.to_string());
+                } else {
+                    let label = anchor
+                        .network()
+                        .map(str::to_string)
+                        .unwrap_or_else(|| format!("anchor {}", index));
+                    failures.push(format!("{}: {}", label, e));
+                }
+            }
+        }
+    }
+    let reason = if anchors.is_empty() {
+        "no anchor providers configured".to_string()
+    } else {
+        failures.join("; ")
+    };
+
+    if !txs.is_empty() {
+        let partial_failure = (!failures.is_empty()).then_some(reason.as_str());
+        if let Some(partial_failure) = partial_failure {
+            tracing::warn!(
+                job_id = %job.id,
+                anchored = txs.len(),
+                error = %partial_failure,
+                "Job anchored on some backends only"
+            );
+        }
+        if provider
+            .mark_txs_and_done(&job.id, &txs, partial_failure)
+            .await
+            .is_ok()
+        {
+            metrics::global().record_processed();
+        }
+        return;
+    }
+
+    match provider
+        .mark_failed_or_backoff(&job.id, &reason, temporary)
+        .await
+    {
+        Ok(BackoffOutcome::Requeued) => metrics::global().record_retry(),
+        Ok(BackoffOutcome::DeadLettered) => metrics::global().record_dead_letter(),
+        Ok(BackoffOutcome::Failed) => metrics::global().record_failed(),
+        Err(e) => tracing::warn!(job_id = %job.id, error = %e, "Failed to record job failure"),
+    }
+}
+
+/// The backend that confirms `tx`: the one declaring its network, else the
+/// first that declares none. A lone backend confirms everything.
+fn anchor_for<'a>(anchors: &'a [BoxedAnchor], tx: &ChainTxRef) -> Option<&'a BoxedAnchor> {
+    if anchors.len() == 1 {
+        return anchors.first();
+    }
+    anchors
+        .iter()
+        .find(|anchor| anchor.network() == Some(tx.network.as_str()))
+        .or_else(|| anchors.iter().find(|anchor| anchor.network().is_none()))
 }
 
-pub async fn run_confirmation_loop<A: AnchorProvider + ?Sized>(
+/// Poll unconfirmed transactions until `shutdown` is set to true, checking
+/// them with the backend that anchored each. Each poll checks at most
+/// `batch_size` of them, oldest first, handing every backend its share in one
+/// `confirm_many` call so backends that can batch status lookups do.
+///
+/// A tx unconfirmed for longer than `confirm_timeout` that its backend
+/// reports as unknown (`AnchorError::NotFound`) was dropped before landing:
+/// it is moved to `outbox_abandoned_txs` and its job requeued so it is
+/// anchored again. A tx the backend reports as reverted
+/// (`AnchorError::Reverted`) is moved there too, and its job marked failed
+/// unless it is anchored on another chain as well.
+pub async fn run_confirmation_loop(
     pool: &Pool<Sqlite>,
-    anchor: &A,
+    anchors: &[BoxedAnchor],
     poll: std::time::Duration,
+    confirm_timeout: std::time::Duration,
+    batch_size: i64,
+    mut shutdown: watch::Receiver<bool>,
 ) {
     loop {
-        match fetch_unconfirmed_tx_refs(pool).await {
+        if *shutdown.borrow() {
+            break;
+        }
+        match fetch_unconfirmed_tx_refs(pool, batch_size).await {
             Ok(tx_refs) => {
-                for tx_ref in tx_refs {
-                    match anchor.confirm(&tx_ref).await {
-                        Ok(updated_tx) => {
-                            if updated_tx.confirmed != tx_ref.confirmed {
-                                let _ = update_tx_ref_confirmation(pool, &updated_tx).await;
-                                if updated_tx.confirmed {
-                                    tracing::info!(
-                                        tx_id = %updated_tx.tx_id,
-                                        network = %updated_tx.network,
-                                    );
-                                }
-                            }
-                        }
-                        Err(e) => {
-                            tracing::warn!(
-                                tx_id = %tx_ref.tx_id,
-                                error = %e,
-                                "Failed to check confirmation status"
-                            );
-                        }
+                for (anchor, batch) in group_by_anchor(anchors, tx_refs) {
+                    if *shutdown.borrow() {
+                        break;
+                    }
+                    let txs: Vec<ChainTxRef> =
+                        batch.iter().map(|(_, tx_ref)| tx_ref.clone()).collect();
+                    let results = anchor.confirm_many(&txs).await;
+                    for ((job_id, tx_ref), result) in batch.iter().zip(results) {
+                        record_confirmation(pool, job_id, tx_ref, result, confirm_timeout).await;
                     }
                 }
             }
@@ -164,14 +649,167 @@ pub async fn run_confirmation_loop<A: AnchorProvider + ?Sized>(
                 tracing::error!(error = %e, "Failed to fetch unconfirmed tx refs");
             }
         }
-        tokio::time::sleep(poll).await;
+        if idle(poll, &mut shutdown).await {
+            break;
+        }
+    }
+    tracing::info!("Confirmation loop stopped");
+}
+
+/// Split `(job_id, tx)` pairs by the backend that confirms each, keeping
+/// their order; txs no backend handles are skipped
+fn group_by_anchor(
+    anchors: &[BoxedAnchor],
+    tx_refs: Vec<(String, ChainTxRef)>,
+) -> Vec<(&BoxedAnchor, Vec<(String, ChainTxRef)>)> {
+    let mut groups: Vec<(&BoxedAnchor, Vec<(String, ChainTxRef)>)> = Vec::new();
+    for (job_id, tx_ref) in tx_refs {
+        let Some(anchor) = anchor_for(anchors, &tx_ref) else {
+            tracing::debug!(
+                tx_id = %tx_ref.tx_id,
+                network = %tx_ref.network,
+                "No anchor backend for network; skipping confirmation"
+            );
+            continue;
+        };
+        match groups
+            .iter_mut()
+            .find(|(grouped, _)| std::ptr::eq(*grouped, anchor))
+        {
+            Some((_, batch)) => batch.push((job_id, tx_ref)),
+            None => groups.push((anchor, vec![(job_id, tx_ref)])),
+        }
     }
+    groups
 }
 
-async fn fetch_unconfirmed_tx_refs(pool: &Pool<Sqlite>) -> Result<Vec<ChainTxRef>, sqlx::Error> {
+/// Store the outcome of checking `tx_ref`, abandoning it if it never landed
+async fn record_confirmation(
+    pool: &Pool<Sqlite>,
+    job_id: &str,
+    tx_ref: &ChainTxRef,
+    result: Result<ChainTxRef, AnchorError>,
+    confirm_timeout: std::time::Duration,
+) {
+    match result {
+        Ok(updated_tx) => {
+            if updated_tx.confirmed != tx_ref.confirmed {
+                let _ = update_tx_ref_confirmation(pool, &updated_tx).await;
+                if updated_tx.confirmed {
+                    metrics::global().record_confirmation();
+                    tracing::info!(
+                        tx_id = %updated_tx.tx_id,
+                        network = %updated_tx.network,
+                    );
+                }
+            }
+        }
+        Err(AnchorError::NotFound(reason)) if is_older_than(tx_ref, confirm_timeout) => {
+            match abandon_tx_ref(pool, job_id, tx_ref, &reason, true).await {
+                Ok(()) => tracing::warn!(
+                    job_id = %job_id,
+                    tx_id = %tx_ref.tx_id,
+                    network = %tx_ref.network,
+                    reason = %reason,
+                    "Transaction never landed; job requeued for re-anchoring"
+                ),
+                Err(e) => tracing::error!(
+                    job_id = %job_id,
+                    tx_id = %tx_ref.tx_id,
+                    error = %e,
+                    "Failed to requeue job with abandoned transaction"
+                ),
+            }
+        }
+        Err(AnchorError::Reverted(reason)) => {
+            match abandon_tx_ref(pool, job_id, tx_ref, &reason, false).await {
+                Ok(()) => tracing::error!(
+                    job_id = %job_id,
+                    tx_id = %tx_ref.tx_id,
+                    network = %tx_ref.network,
+                    reason = %reason,
+                    "Transaction reverted; anchor abandoned"
+                ),
+                Err(e) => tracing::error!(
+                    job_id = %job_id,
+                    tx_id = %tx_ref.tx_id,
+                    error = %e,
+                    "Failed to record reverted transaction"
+                ),
+            }
+        }
+        Err(e) => {
+            tracing::warn!(
+                tx_id = %tx_ref.tx_id,
+                error = %e,
+                "Failed to check confirmation status"
+            );
+        }
+    }
+}
+
+/// Whether `tx` was anchored more than `timeout` ago (false if unknown)
+fn is_older_than(tx: &ChainTxRef, timeout: std::time::Duration) -> bool {
+    tx.timestamp
+        .and_then(|anchored_at| (Utc::now() - anchored_at).to_std().ok())
+        .is_some_and(|age| age > timeout)
+}
+
+/// Record `tx` as abandoned, in one transaction with either requeueing its
+/// job (`requeue`) or failing it if no other tx ref is left to carry it
+async fn abandon_tx_ref(
+    pool: &Pool<Sqlite>,
+    job_id: &str,
+    tx: &ChainTxRef,
+    reason: &str,
+    requeue: bool,
+) -> Result<(), sqlx::Error> {
+    let now_ms = Utc::now().timestamp_millis();
+    let mut t = pool.begin().await?;
+    sqlx::query(
+        "INSERT OR REPLACE INTO outbox_abandoned_txs (job_id, network, chain, tx_id, reason, abandoned_ms) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+    )
+    .bind(job_id)
+    .bind(&tx.network)
+    .bind(&tx.chain)
+    .bind(&tx.tx_id)
+    .bind(reason)
+    .bind(now_ms)
+    .execute(&mut *t)
+    .await?;
+    sqlx::query(
+        "DELETE FROM outbox_tx_refs WHERE job_id = ?1 AND network = ?2 AND chain = ?3 AND tx_id = ?4",
+    )
+    .bind(job_id)
+    .bind(&tx.network)
+    .bind(&tx.chain)
+    .bind(&tx.tx_id)
+    .execute(&mut *t)
+    .await?;
+    let update = if requeue {
+        "UPDATE outbox_jobs SET status='queued', last_error=?1, updated_ms=?2, next_attempt_ms=?2 WHERE id=?3"
+    } else {
+        "UPDATE outbox_jobs SET status = CASE WHEN EXISTS (SELECT 1 FROM outbox_tx_refs WHERE job_id = ?3) THEN status ELSE 'failed' END, last_error=?1, updated_ms=?2 WHERE id=?3"
+    };
+    sqlx::query(update)
+        .bind(format!("transaction {} abandoned: {}", tx.tx_id, reason))
+        .bind(now_ms)
+        .bind(job_id)
+        .execute(&mut *t)
+        .await?;
+    t.commit().await
+}
+
+/// Up to `limit` unconfirmed tx refs, oldest first, with the id of the job
+/// that anchored each
+pub async fn fetch_unconfirmed_tx_refs(
+    pool: &Pool<Sqlite>,
+    limit: i64,
+) -> Result<Vec<(String, ChainTxRef)>, sqlx::Error> {
     let rows = sqlx::query(
-        "SELECT job_id, network, chain, tx_id, confirmed, timestamp FROM outbox_tx_refs WHERE confirmed = 0"
+        "SELECT job_id, network, chain, tx_id, confirmed, timestamp FROM outbox_tx_refs WHERE confirmed = 0 ORDER BY timestamp ASC, job_id ASC, network ASC, chain ASC, tx_id ASC LIMIT ?1"
     )
+    .bind(limit.max(1))
     .fetch_all(pool)
     .await?;
 
@@ -183,13 +821,16 @@ async fn fetch_unconfirmed_tx_refs(pool: &Pool<Sqlite>) -> Result<Vec<ChainTxRef
             Utc.timestamp_millis_opt(ts * 1000).single()
         });
 
-        tx_refs.push(ChainTxRef {
-            network: row.get("network"),
-            chain: row.get("chain"),
-            tx_id: row.get("tx_id"),
-            confirmed: row.get::<i32, _>("confirmed") != 0,
-            timestamp,
-        });
+        tx_refs.push((
+            row.get("job_id"),
+            ChainTxRef {
+                network: row.get("network"),
+                chain: row.get("chain"),
+                tx_id: row.get("tx_id"),
+                confirmed: row.get::<i32, _>("confirmed") != 0,
+                timestamp,
+            },
+        ));
     }
 
     Ok(tx_refs)
@@ -212,13 +853,65 @@ async fn update_tx_ref_confirmation(
     Ok(())
 }
 
+/// Current time in epoch milliseconds; swapped out in tests
+pub type Clock = Arc<dyn Fn() -> i64 + Send + Sync>;
+
+/// Extra milliseconds added to each retry delay so failed jobs don't retry in
+/// lockstep; swapped out in tests
+pub type Jitter = Arc<dyn Fn() -> i64 + Send + Sync>;
+
+#[derive(Clone)]
 pub struct SqliteJobProvider {
     pool: Pool<Sqlite>,
+    max_attempts: i64,
+    dedupe_digests: bool,
+    clock: Clock,
+    jitter: Jitter,
 }
 
 impl SqliteJobProvider {
     pub fn new(pool: Pool<Sqlite>) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            dedupe_digests: false,
+            clock: Arc::new(|| chrono::Utc::now().timestamp_millis()),
+            jitter: Arc::new(|| rand::rng().random_range(0..1000)),
+        }
+    }
+
+    /// Dead-letter temporarily failing jobs after `max_attempts` attempts
+    pub fn with_max_attempts(mut self, max_attempts: i64) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Reuse another job's confirmed anchor for a digest instead of anchoring
+    /// it again (off by default: some workflows want independent anchors)
+    pub fn with_dedupe_digests(mut self, dedupe_digests: bool) -> Self {
+        self.dedupe_digests = dedupe_digests;
+        self
+    }
+
+    /// Read "now" from `clock` instead of the wall clock
+    pub fn with_clock(mut self, clock: impl Fn() -> i64 + Send + Sync + 'static) -> Self {
+        self.clock = Arc::new(clock);
+        self
+    }
+
+    /// Draw retry jitter from `jitter` instead of `0..1000` ms at random
+    pub fn with_jitter(mut self, jitter: impl Fn() -> i64 + Send + Sync + 'static) -> Self {
+        self.jitter = Arc::new(jitter);
+        self
+    }
+
+    fn now_ms(&self) -> i64 {
+        (self.clock)()
+    }
+
+    /// When a job that just failed its `attempts`-th attempt may run again
+    fn next_attempt_ms(&self, now_ms: i64, attempts: i64) -> i64 {
+        now_ms + backoff_ms(attempts) + (self.jitter)()
     }
 }
 
@@ -226,9 +919,9 @@ impl SqliteJobProvider {
 impl JobProvider for SqliteJobProvider {
     async fn fetch_next(&mut self) -> Result<Option<EvidenceJob>, JobError> {
         let mut tx = self.pool.begin().await?;
-        let now_ms = chrono::Utc::now().timestamp_millis();
+        let now_ms = self.now_ms();
         if let Some(row) = sqlx::query(
-            "SELECT id, payload_sha256, created_ms FROM outbox_jobs WHERE status='queued' AND next_attempt_ms <= ?1 ORDER BY created_ms ASC LIMIT 1",
+            "SELECT id, payload_sha256, created_ms FROM outbox_jobs WHERE status='queued' AND next_attempt_ms <= ?1 ORDER BY priority DESC, created_ms ASC LIMIT 1",
         )
         .bind(now_ms)
         .fetch_optional(&mut *tx)
@@ -256,7 +949,7 @@ impl JobProvider for SqliteJobProvider {
     }
 
     async fn mark_done(&mut self, id: &str) -> Result<(), JobError> {
-        let now_ms = chrono::Utc::now().timestamp_millis();
+        let now_ms = self.now_ms();
         sqlx::query("UPDATE outbox_jobs SET status='done', updated_ms=?1 WHERE id=?2")
             .bind(now_ms)
             .bind(id)
@@ -266,7 +959,7 @@ impl JobProvider for SqliteJobProvider {
     }
 
     async fn mark_failed(&mut self, id: &str, reason: &str) -> Result<(), JobError> {
-        let now_ms = chrono::Utc::now().timestamp_millis();
+        let now_ms = self.now_ms();
         sqlx::query(
             "UPDATE outbox_jobs SET status='failed', last_error=?1, updated_ms=?2, next_attempt_ms=?2 WHERE id=?3",
         )
@@ -282,24 +975,39 @@ impl JobProvider for SqliteJobProvider {
 #[async_trait]
 impl JobProviderExt for SqliteJobProvider {
     async fn mark_tx_and_done(&mut self, id: &str, tx: &ChainTxRef) -> Result<(), JobError> {
+        self.mark_txs_and_done(id, std::slice::from_ref(tx), None)
+            .await
+    }
+
+    async fn mark_txs_and_done(
+        &mut self,
+        id: &str,
+        txs: &[ChainTxRef],
+        partial_failure: Option<&str>,
+    ) -> Result<(), JobError> {
         let mut t = self.pool.begin().await?;
+        for tx in txs {
+            sqlx::query(
+                "INSERT OR REPLACE INTO outbox_tx_refs (job_id, network, chain, tx_id, confirmed, timestamp) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            )
+            .bind(id)
+            .bind(&tx.network)
+            .bind(&tx.chain)
+            .bind(&tx.tx_id)
+            .bind(if tx.confirmed { 1 } else { 0 })
+            .bind(tx.timestamp.map(|dt| dt.timestamp()))
+            .execute(&mut *t)
+            .await?;
+        }
+        let now_ms = self.now_ms();
         sqlx::query(
-            "INSERT OR REPLACE INTO outbox_tx_refs (job_id, network, chain, tx_id, confirmed, timestamp) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            "UPDATE outbox_jobs SET status='done', last_error=COALESCE(?1, last_error), updated_ms=?2 WHERE id=?3",
         )
+        .bind(partial_failure)
+        .bind(now_ms)
         .bind(id)
-        .bind(&tx.network)
-        .bind(&tx.chain)
-        .bind(&tx.tx_id)
-        .bind(if tx.confirmed { 1 } else { 0 })
-        .bind(tx.timestamp.map(|dt| dt.timestamp()))
         .execute(&mut *t)
         .await?;
-        let now_ms = chrono::Utc::now().timestamp_millis();
-        sqlx::query("UPDATE outbox_jobs SET status='done', updated_ms=?1 WHERE id=?2")
-            .bind(now_ms)
-            .bind(id)
-            .execute(&mut *t)
-            .await?;
         t.commit().await?;
         Ok(())
     }
@@ -309,20 +1017,33 @@ impl JobProviderExt for SqliteJobProvider {
         id: &str,
         reason: &str,
         temporary: bool,
-    ) -> Result<(), JobError> {
-        let now_ms = chrono::Utc::now().timestamp_millis();
+    ) -> Result<BackoffOutcome, JobError> {
+        let now_ms = self.now_ms();
         if temporary {
             let rec = sqlx::query("SELECT attempts FROM outbox_jobs WHERE id=?1")
                 .bind(id)
                 .fetch_one(&self.pool)
                 .await?;
             let attempts: i64 = rec.get(0);
-            let base: i64 = 5000; // 5s
-            let cap: i64 = 300000; // 5m
-            let exp: u32 = attempts.clamp(0, 20) as u32;
-            let backoff = (base.saturating_mul(2i64.pow(exp))).min(cap);
-            let jitter = rand::rng().random_range(0..1000);
-            let next = now_ms + backoff + jitter;
+            if attempts >= self.max_attempts {
+                sqlx::query(
+                    "UPDATE outbox_jobs SET status=?1, last_error=?2, updated_ms=?3 WHERE id=?4",
+                )
+                .bind(DEAD_LETTER_STATUS)
+                .bind(reason)
+                .bind(now_ms)
+                .bind(id)
+                .execute(&self.pool)
+                .await?;
+                tracing::warn!(
+                    job_id = %id,
+                    attempts,
+                    error = %reason,
+                    "Job exhausted its attempts and was dead-lettered"
+                );
+                return Ok(BackoffOutcome::DeadLettered);
+            }
+            let next = self.next_attempt_ms(now_ms, attempts);
             sqlx::query(
                 "UPDATE outbox_jobs SET status='queued', last_error=?1, updated_ms=?2, next_attempt_ms=?3 WHERE id=?4",
             )
@@ -332,7 +1053,7 @@ impl JobProviderExt for SqliteJobProvider {
             .bind(id)
             .execute(&self.pool)
             .await?;
-            return Ok(());
+            return Ok(BackoffOutcome::Requeued);
         }
         sqlx::query(
             "UPDATE outbox_jobs SET status='failed', last_error=?1, updated_ms=?2, next_attempt_ms=?2 WHERE id=?3",
@@ -342,6 +1063,57 @@ impl JobProviderExt for SqliteJobProvider {
         .bind(id)
         .execute(&self.pool)
         .await?;
+        Ok(BackoffOutcome::Failed)
+    }
+
+    async fn find_confirmed_anchor(
+        &mut self,
+        payload_sha256: &str,
+        job_id: &str,
+    ) -> Result<Vec<ChainTxRef>, JobError> {
+        if !self.dedupe_digests {
+            return Ok(Vec::new());
+        }
+        let rows = sqlx::query(
+            "SELECT r.network, r.chain, r.tx_id, r.timestamp FROM outbox_tx_refs r JOIN outbox_jobs j ON j.id = r.job_id WHERE j.payload_sha256 = ?1 AND j.id != ?2 AND r.confirmed = 1 ORDER BY r.timestamp ASC, r.tx_id ASC",
+        )
+        .bind(payload_sha256)
+        .bind(job_id)
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut txs: Vec<ChainTxRef> = Vec::new();
+        for row in rows {
+            let network: String = row.get("network");
+            let chain: String = row.get("chain");
+            if txs
+                .iter()
+                .any(|tx| tx.network == network && tx.chain == chain)
+            {
+                continue;
+            }
+            txs.push(ChainTxRef {
+                network,
+                chain,
+                tx_id: row.get("tx_id"),
+                confirmed: true,
+                timestamp: row
+                    .get::<Option<i64>, _>("timestamp")
+                    .and_then(|ts| Utc.timestamp_opt(ts, 0).single()),
+            });
+        }
+        Ok(txs)
+    }
+
+    async fn release(&mut self, id: &str) -> Result<(), JobError> {
+        let now_ms = self.now_ms();
+        sqlx::query(
+            "UPDATE outbox_jobs SET status='queued', attempts=MAX(attempts-1, 0), updated_ms=?1, next_attempt_ms=?1 WHERE id=?2 AND status='in_progress'",
+        )
+        .bind(now_ms)
+        .bind(id)
+        .execute(&self.pool)
+        .await?;
         Ok(())
     }
 }
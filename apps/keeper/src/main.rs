@@ -1,62 +1,22 @@
-use anchor_etherlink::{EtherlinkProvider, EtherlinkProviderStub};
-use axum::{routing::get, Router};
+use axum::routing::get;
 use phoenix_evidence::anchor::AnchorProvider;
-use phoenix_keeper::{ensure_schema, run_confirmation_loop, run_job_loop, SqliteJobProvider};
-use sqlx::sqlite::SqlitePoolOptions;
+use phoenix_keeper::balance::{
+    min_balance_from_env, run_balance_monitor, BalanceGuard, DEFAULT_BALANCE_CHECK_INTERVAL,
+};
+use phoenix_keeper::circuit_breaker::CircuitBreakerProvider;
+use phoenix_keeper::config::KeeperConfig;
+use phoenix_keeper::providers::{
+    providers_from_env, validate_network_from_env, validate_providers,
+};
+use phoenix_keeper::tenants::{
+    run_tenant, tenants_from_urls, TenantSettings, TENANT_RETRY_INTERVAL,
+};
+use phoenix_keeper::BoxedAnchor;
+use std::sync::Arc;
 use std::time::Duration;
 use tokio::signal;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-/// Creates the appropriate Etherlink provider based on environment configuration
-fn create_etherlink_provider() -> Box<dyn AnchorProvider + Send + Sync> {
-    let use_stub = match std::env::var("KEEPER_USE_STUB") {
-        Ok(value) => {
-            match value.trim().to_lowercase().as_str() {
-                "true" | "1" | "yes" | "on" => true,
-                "false" | "0" | "no" | "off" => false,
-                unrecognized_value => {
-                    // Emit warning for unrecognized values
-                    tracing::warn!("Invalid KEEPER_USE_STUB value '{}'. Expected true/false/1/0/yes/no/on/off. Using real provider for safety.", unrecognized_value);
-                    false // Default to false (real provider) for unrecognized values
-                }
-            }
-        }
-        Err(_) => false, // Default to false (real provider) if env var is missing or unparsable
-    };
-
-    if use_stub {
-        tracing::info!("Using EtherlinkProviderStub for development/testing");
-        Box::new(EtherlinkProviderStub)
-    } else {
-        tracing::info!("Using real EtherlinkProvider for production");
-
-        let endpoint = std::env::var("ETHERLINK_ENDPOINT")
-            .unwrap_or_else(|_| "https://node.etherlink.com".to_string());
-        let network = std::env::var("ETHERLINK_NETWORK").unwrap_or_else(|_| "mainnet".to_string());
-        let private_key = std::env::var("ETHERLINK_PRIVATE_KEY").ok();
-
-        match EtherlinkProvider::new(endpoint.clone(), network.clone(), private_key) {
-            Ok(provider) => {
-                tracing::info!(
-                    endpoint = %endpoint,
-                    network = %network,
-                    "Successfully created EtherlinkProvider"
-                );
-                Box::new(provider)
-            }
-            Err(error) => {
-                tracing::error!(
-                    endpoint = %endpoint,
-                    network = %network,
-                    error = %error,
-                    "Failed to create EtherlinkProvider"
-                );
-                std::process::exit(1);
-            }
-        }
-    }
-}
-
 #[tokio::main]
 async fn main() {
     tracing_subscriber::registry()
@@ -66,13 +26,51 @@ async fn main() {
         .with(tracing_subscriber::fmt::layer())
         .init();
 
-    // HTTP health endpoint
-    let app = Router::new().route("/health", get(|| async { "OK" }));
+    let config = KeeperConfig::from_env();
+
+    // One tenant per comma-separated database URL
+    let tenants = tenants_from_urls(&config.database_url);
+    if tenants.is_empty() {
+        tracing::error!("KEEPER_DB_URL lists no databases");
+        std::process::exit(1);
+    }
+
+    // Anchor backends named by KEEPER_ANCHOR_PROVIDER; fail fast on a bad spec
+    let providers = match providers_from_env() {
+        Ok(providers) => providers,
+        Err(error) => {
+            tracing::error!(error = %error, "Failed to create anchor providers");
+            std::process::exit(1);
+        }
+    };
+    // Refuse to anchor to a misspelled network or an endpoint serving another chain
+    if validate_network_from_env() {
+        if let Err(error) = validate_providers(&providers).await {
+            tracing::error!(error = %error, "Anchor provider network validation failed");
+            std::process::exit(1);
+        }
+    }
+
+    // HTTP health, Prometheus metrics and per-tenant counters
+    let tenant_metrics: Vec<_> = tenants
+        .iter()
+        .map(|t| (t.id.clone(), t.metrics.clone()))
+        .collect();
+    let app = phoenix_keeper::metrics::router().route(
+        "/tenants",
+        get(move || async move {
+            let snapshot: serde_json::Map<String, serde_json::Value> = tenant_metrics
+                .iter()
+                .map(|(id, metrics)| (id.clone(), serde_json::json!(metrics.snapshot())))
+                .collect();
+            axum::Json(snapshot)
+        }),
+    );
+    let addr = format!("0.0.0.0:{}", config.http_port);
     let http = tokio::spawn(async move {
-        let addr = "0.0.0.0:8081";
         tracing::info!(%addr, "keeper http starting");
 
-        let listener = match tokio::net::TcpListener::bind(addr).await {
+        let listener = match tokio::net::TcpListener::bind(&addr).await {
             Ok(listener) => listener,
             Err(bind_error) => {
                 tracing::error!(address=%addr, error=%bind_error, "Failed to bind HTTP server");
@@ -86,57 +84,85 @@ async fn main() {
         }
     });
 
-    // Job runner
-    let runner = tokio::spawn(async move {
-        let poll_interval = std::env::var("KEEPER_POLL_MS")
+    // Flipped to true on Ctrl+C; every tenant loop watches it
+    let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+
+    // Job runners, one independent task per tenant
+    let mut runner = tokio::spawn(async move {
+        let settings = TenantSettings {
+            job_poll_interval: config.job_poll_interval,
+            confirmation_poll_interval: config.confirmation_poll_interval,
+            max_attempts: config.max_attempts,
+            retry_interval: TENANT_RETRY_INTERVAL,
+            concurrency: config.concurrency,
+            stale_after: config.stale_after,
+            confirm_timeout: config.confirm_timeout,
+            confirm_batch_size: config.confirm_batch_size,
+            dedupe_digests: config.dedupe_digests,
+            batch_poll_interval: Duration::from_secs(30),
+        };
+
+        let pause_on_low_balance = std::env::var("KEEPER_PAUSE_ON_LOW_BALANCE")
+            .map(|v| v == "true" || v == "1")
+            .unwrap_or(false);
+        let balance_check_interval = std::env::var("KEEPER_BALANCE_CHECK_MS")
             .ok()
             .and_then(|v| v.parse::<u64>().ok())
+            .filter(|ms| *ms > 0)
             .map(Duration::from_millis)
-            .unwrap_or_else(|| Duration::from_secs(5));
+            .unwrap_or(DEFAULT_BALANCE_CHECK_INTERVAL);
 
-        let db_url = std::env::var("KEEPER_DB_URL")
-            .unwrap_or_else(|_| "sqlite://blockchain_outbox.sqlite3".to_string());
-        match SqlitePoolOptions::new()
-            .max_connections(5)
-            .connect(&db_url)
-            .await
-        {
-            Ok(pool) => {
-                if let Err(schema_error) = ensure_schema(&pool).await {
-                    tracing::error!(error=%schema_error, "schema init failed");
-                    tracing::error!("Exiting due to schema initialization failure");
+        // Shared by all tenants, so an RPC outage trips one breaker per backend
+        // and a short wallet pauses that backend everywhere
+        let mut guards = Vec::new();
+        for provider in providers {
+            let minimum = match min_balance_from_env(provider.network()) {
+                Ok(minimum) => minimum,
+                Err(error) => {
+                    tracing::error!(%error, "Invalid minimum wallet balance");
                     std::process::exit(1);
                 }
-
-                let mut job_provider = SqliteJobProvider::new(pool.clone());
-                let anchor = create_etherlink_provider();
-
-                // Start job processing loop
-                let job_anchor = anchor;
-                let job_handle = tokio::spawn(async move {
-                    run_job_loop(&mut job_provider, job_anchor.as_ref(), poll_interval).await;
-                });
-
-                // Start confirmation polling loop
-                let confirm_interval = Duration::from_secs(30); // Check confirmations every 30s
-                let confirm_anchor = create_etherlink_provider();
-                let confirm_handle = tokio::spawn(async move {
-                    run_confirmation_loop(&pool, confirm_anchor.as_ref(), confirm_interval).await;
-                });
-
-                // Wait for either loop to complete (they shouldn't)
-                tokio::select! {
-                    _ = job_handle => {
-                        tracing::warn!("Job loop exited unexpectedly");
-                    }
-                    _ = confirm_handle => {
-                        tracing::warn!("Confirmation loop exited unexpectedly");
-                    }
-                }
-            }
-            Err(_) => {
-                tracing::error!("db connect failed; keeper idle");
-                tokio::time::sleep(Duration::from_secs(10)).await;
+            };
+            let breaker = CircuitBreakerProvider::new(Arc::from(provider))
+                .with_failure_threshold(config.breaker_threshold)
+                .with_probe_interval(config.breaker_probe_interval);
+            guards.push(Arc::new(
+                BalanceGuard::new(Arc::new(breaker), minimum)
+                    .with_pause_when_short(pause_on_low_balance),
+            ));
+        }
+        for guard in &guards {
+            guard.check().await;
+        }
+        // Merkle batches go through the first configured backend
+        let batch_provider: Arc<dyn AnchorProvider + Send + Sync> = guards[0].clone();
+        let anchors: Arc<Vec<BoxedAnchor>> = Arc::new(
+            guards
+                .iter()
+                .map(|guard| -> BoxedAnchor { Box::new(guard.clone()) })
+                .collect(),
+        );
+        tokio::spawn(run_balance_monitor(
+            guards,
+            balance_check_interval,
+            shutdown_rx.clone(),
+        ));
+        let handles: Vec<_> = tenants
+            .into_iter()
+            .map(|tenant| {
+                tokio::spawn(run_tenant(
+                    tenant,
+                    anchors.clone(),
+                    batch_provider.clone(),
+                    settings,
+                    shutdown_rx.clone(),
+                ))
+            })
+            .collect();
+        for handle in handles {
+            // Tenants retry internally; a handle resolves on shutdown or panic
+            if let Err(e) = handle.await {
+                tracing::error!(error = %e, "Tenant task aborted");
             }
         }
     });
@@ -146,7 +172,14 @@ async fn main() {
         _ = signal::ctrl_c() => {
             tracing::info!("shutdown signal received");
         }
-        _ = http => {}
-        _ = runner => {}
+        _ = http => return,
+        _ = &mut runner => return,
+    }
+
+    // Stop claiming jobs and release in-flight ones before exiting
+    let _ = shutdown_tx.send(true);
+    match tokio::time::timeout(Duration::from_secs(30), runner).await {
+        Ok(_) => tracing::info!("keeper stopped cleanly"),
+        Err(_) => tracing::warn!("keeper loops did not stop within 30s; exiting anyway"),
     }
 }
@@ -0,0 +1,229 @@
+//! Prometheus metrics for the keeper, served as text on `GET /metrics`.
+//!
+//! A handful of counters and one histogram don't warrant a metrics crate;
+//! the registry below renders the text exposition format directly.
+
+use axum::{http::header, response::IntoResponse, routing::get, Router};
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::LazyLock;
+use std::time::Duration;
+
+/// Upper bounds (seconds) of the anchor latency histogram buckets
+const LATENCY_BUCKETS: [f64; 9] = [0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0];
+
+static METRICS: LazyLock<KeeperMetrics> = LazyLock::new(KeeperMetrics::default);
+
+/// Process-wide metrics updated by the job and confirmation loops
+pub fn global() -> &'static KeeperMetrics {
+    &METRICS
+}
+
+#[derive(Debug, Default)]
+pub struct KeeperMetrics {
+    jobs_processed: AtomicU64,
+    jobs_failed: AtomicU64,
+    job_retries: AtomicU64,
+    jobs_dead_lettered: AtomicU64,
+    confirmations: AtomicU64,
+    failovers: AtomicU64,
+    breaker_state: AtomicU64,
+    low_balance_warnings: AtomicU64,
+    anchor_latency: Histogram,
+}
+
+impl KeeperMetrics {
+    /// A job was anchored and marked done
+    pub fn record_processed(&self) {
+        self.jobs_processed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// A job failed permanently
+    pub fn record_failed(&self) {
+        self.jobs_failed.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// A job hit a temporary error and was scheduled for retry
+    pub fn record_retry(&self) {
+        self.job_retries.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// A job ran out of attempts and was dead-lettered
+    pub fn record_dead_letter(&self) {
+        self.jobs_dead_lettered.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// A transaction was observed as confirmed on chain
+    pub fn record_confirmation(&self) {
+        self.confirmations.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// A job was anchored through the fallback provider
+    pub fn record_failover(&self) {
+        self.failovers.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Circuit breaker state: 0 closed, 1 open, 2 half-open
+    pub fn set_breaker_state(&self, state: u64) {
+        self.breaker_state.store(state, Ordering::Relaxed);
+    }
+
+    /// A balance check found an anchoring wallet below its minimum
+    pub fn record_low_balance(&self) {
+        self.low_balance_warnings.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Time spent in one `AnchorProvider::anchor` call, success or not
+    pub fn observe_anchor_latency(&self, elapsed: Duration) {
+        self.anchor_latency.observe(elapsed.as_secs_f64());
+    }
+
+    pub fn jobs_processed(&self) -> u64 {
+        self.jobs_processed.load(Ordering::Relaxed)
+    }
+
+    pub fn jobs_failed(&self) -> u64 {
+        self.jobs_failed.load(Ordering::Relaxed)
+    }
+
+    pub fn job_retries(&self) -> u64 {
+        self.job_retries.load(Ordering::Relaxed)
+    }
+
+    pub fn jobs_dead_lettered(&self) -> u64 {
+        self.jobs_dead_lettered.load(Ordering::Relaxed)
+    }
+
+    pub fn confirmations(&self) -> u64 {
+        self.confirmations.load(Ordering::Relaxed)
+    }
+
+    pub fn failovers(&self) -> u64 {
+        self.failovers.load(Ordering::Relaxed)
+    }
+
+    pub fn breaker_state(&self) -> u64 {
+        self.breaker_state.load(Ordering::Relaxed)
+    }
+
+    pub fn low_balance_warnings(&self) -> u64 {
+        self.low_balance_warnings.load(Ordering::Relaxed)
+    }
+
+    /// Render in the Prometheus text exposition format (version 0.0.4)
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        write_counter(
+            &mut out,
+            "keeper_jobs_processed_total",
+            "Jobs anchored and marked done",
+            self.jobs_processed(),
+        );
+        write_counter(
+            &mut out,
+            "keeper_jobs_failed_total",
+            "Jobs that failed permanently",
+            self.jobs_failed(),
+        );
+        write_counter(
+            &mut out,
+            "keeper_job_retries_total",
+            "Temporary anchor failures scheduled for retry",
+            self.job_retries(),
+        );
+        write_counter(
+            &mut out,
+            "keeper_jobs_dead_lettered_total",
+            "Jobs moved to dead_letter after exhausting their attempts",
+            self.jobs_dead_lettered(),
+        );
+        write_counter(
+            &mut out,
+            "keeper_confirmations_total",
+            "Transactions observed as confirmed",
+            self.confirmations(),
+        );
+        write_counter(
+            &mut out,
+            "keeper_anchor_failovers_total",
+            "Jobs anchored through the fallback provider",
+            self.failovers(),
+        );
+        write_counter(
+            &mut out,
+            "keeper_low_balance_warnings_total",
+            "Balance checks that found an anchoring wallet below its minimum",
+            self.low_balance_warnings(),
+        );
+        let _ = writeln!(
+            out,
+            "# HELP keeper_circuit_breaker_state Anchor circuit breaker (0 closed, 1 open, 2 half-open)"
+        );
+        let _ = writeln!(out, "# TYPE keeper_circuit_breaker_state gauge");
+        let _ = writeln!(out, "keeper_circuit_breaker_state {}", self.breaker_state());
+        self.anchor_latency.render(
+            &mut out,
+            "keeper_anchor_latency_seconds",
+            "Latency of anchor provider calls",
+        );
+        out
+    }
+}
+
+fn write_counter(out: &mut String, name: &str, help: &str, value: u64) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} counter");
+    let _ = writeln!(out, "{name} {value}");
+}
+
+/// Fixed-bucket histogram; the sum is kept in microseconds to stay atomic
+#[derive(Debug, Default)]
+struct Histogram {
+    buckets: [AtomicU64; LATENCY_BUCKETS.len()],
+    count: AtomicU64,
+    sum_micros: AtomicU64,
+}
+
+impl Histogram {
+    fn observe(&self, seconds: f64) {
+        for (bound, bucket) in LATENCY_BUCKETS.iter().zip(&self.buckets) {
+            if seconds <= *bound {
+                bucket.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.sum_micros
+            .fetch_add((seconds * 1_000_000.0) as u64, Ordering::Relaxed);
+    }
+
+    fn render(&self, out: &mut String, name: &str, help: &str) {
+        let _ = writeln!(out, "# HELP {name} {help}");
+        let _ = writeln!(out, "# TYPE {name} histogram");
+        for (bound, bucket) in LATENCY_BUCKETS.iter().zip(&self.buckets) {
+            let _ = writeln!(
+                out,
+                "{name}_bucket{{le=\"{bound}\"}} {}",
+                bucket.load(Ordering::Relaxed)
+            );
+        }
+        let count = self.count.load(Ordering::Relaxed);
+        let _ = writeln!(out, "{name}_bucket{{le=\"+Inf\"}} {count}");
+        let sum = self.sum_micros.load(Ordering::Relaxed) as f64 / 1_000_000.0;
+        let _ = writeln!(out, "{name}_sum {sum}");
+        let _ = writeln!(out, "{name}_count {count}");
+    }
+}
+
+/// `/health` and `/metrics` for the keeper's HTTP port
+pub fn router() -> Router {
+    Router::new()
+        .route("/health", get(|| async { "OK" }))
+        .route("/metrics", get(metrics_handler))
+}
+
+async fn metrics_handler() -> impl IntoResponse {
+    (
+        [(header::CONTENT_TYPE, "text/plain; version=0.0.4")],
+        global().render(),
+    )
+}
@@ -0,0 +1,77 @@
+//! Anchor providers selected by name.
+//!
+//! `KEEPER_ANCHOR_PROVIDER` names one provider spec, or a comma list for
+//! multi-chain anchoring (`etherlink,solana`). Each provider reads its own
+//! settings from its env prefix (`ETHERLINK_*`, `SOLANA_*`). The specs and
+//! the factory live in the `anchor-providers` crate, shared with the API's
+//! inline anchoring, so adding a backend means adding a spec there rather
+//! than editing `main.rs`.
+//!
+//! Without `KEEPER_ANCHOR_PROVIDER` the older `KEEPER_PROVIDER` /
+//! `KEEPER_USE_STUB` pair is translated to specs.
+
+pub use anchor_providers::{
+    parse_spec_list, provider_from_spec, validate_providers, ProviderError, ProviderKind,
+    KNOWN_SPECS,
+};
+use phoenix_evidence::anchor::AnchorProvider;
+
+/// Provider list from `KEEPER_ANCHOR_PROVIDER`, falling back to the legacy
+/// `KEEPER_PROVIDER` / `KEEPER_USE_STUB` variables. An unrecognised
+/// `KEEPER_PROVIDER` is an error rather than a silent real provider.
+pub fn spec_list_from_env() -> Result<String, ProviderError> {
+    if let Ok(list) = std::env::var("KEEPER_ANCHOR_PROVIDER") {
+        return Ok(list);
+    }
+    let suffix = if use_stub() { "-stub" } else { "" };
+    let chains: &[&str] = match std::env::var("KEEPER_PROVIDER").as_deref() {
+        Ok("stub") => return Ok("etherlink-stub".to_string()),
+        Ok("etherlink") | Err(_) => &["etherlink"],
+        Ok("solana") => &["solana"],
+        Ok("multi") => &["etherlink", "solana"],
+        Ok(other) => return Err(ProviderError::Unknown(other.to_string())),
+    };
+    Ok(chains
+        .iter()
+        .map(|chain| format!("{}{}", chain, suffix))
+        .collect::<Vec<_>>()
+        .join(","))
+}
+
+/// Create every provider named by the environment, in order
+pub fn providers_from_env() -> Result<Vec<Box<dyn AnchorProvider + Send + Sync>>, ProviderError> {
+    parse_spec_list(&spec_list_from_env()?)?
+        .into_iter()
+        .map(ProviderKind::build)
+        .collect()
+}
+
+/// Whether startup validation is on (`KEEPER_VALIDATE_NETWORK`, default true;
+/// turn it off to start without reaching the RPC endpoints)
+pub fn validate_network_from_env() -> bool {
+    match std::env::var("KEEPER_VALIDATE_NETWORK") {
+        Ok(value) => !matches!(
+            value.trim().to_lowercase().as_str(),
+            "false" | "0" | "no" | "off"
+        ),
+        Err(_) => true,
+    }
+}
+
+/// Whether `KEEPER_USE_STUB` selects the stub providers
+fn use_stub() -> bool {
+    match std::env::var("KEEPER_USE_STUB") {
+        Ok(value) => {
+            match value.trim().to_lowercase().as_str() {
+                "true" | "1" | "yes" | "on" => true,
+                "false" | "0" | "no" | "off" => false,
+                unrecognized_value => {
+                    // Emit warning for unrecognized values
+                    tracing::warn!("Invalid KEEPER_USE_STUB value '{}'. Expected true/false/1/0/yes/no/on/off. Using real provider for safety.", unrecognized_value);
+                    false // Default to false (real provider) for unrecognized values
+                }
+            }
+        }
+        Err(_) => false, // Default to false (real provider) if env var is missing or unparsable
+    }
+}
@@ -0,0 +1,364 @@
+//! Multi-tenant keeper: one job, confirmation, reaper and batch loop per
+//! tenant database.
+//!
+//! Each tenant runs in its own task and reconnects on its own schedule, so a
+//! tenant whose database is unreachable or corrupt never stalls the others.
+
+use crate::batch_anchor::{run_batch_loop, BatchAnchor, BatchConfig};
+use crate::{
+    ensure_schema, run_confirmation_loop, run_job_loop, run_job_loop_concurrent, run_reaper_loop,
+    shutdown_signalled, BackoffOutcome, BoxedAnchor, EvidenceJob, JobError, JobProvider,
+    JobProviderExt, SqliteJobProvider, REAPER_INTERVAL,
+};
+use async_trait::async_trait;
+use phoenix_evidence::anchor::AnchorProvider;
+use phoenix_evidence::model::ChainTxRef;
+use serde::Serialize;
+use sqlx::sqlite::SqlitePoolOptions;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+
+/// Delay before a tenant retries a failed connection or restarts its loops
+pub const TENANT_RETRY_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Counters for one tenant's job processing
+#[derive(Debug, Default)]
+pub struct TenantMetrics {
+    /// Jobs anchored and marked done
+    jobs_anchored: AtomicU64,
+    /// Jobs failed permanently
+    jobs_failed: AtomicU64,
+    /// Temporary failures scheduled for retry
+    jobs_retried: AtomicU64,
+    /// Jobs moved to `dead_letter` after exhausting their attempts
+    jobs_dead_lettered: AtomicU64,
+    fetch_errors: AtomicU64,
+    connect_failures: AtomicU64,
+}
+
+/// Point-in-time copy of [`TenantMetrics`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct TenantMetricsSnapshot {
+    pub jobs_anchored: u64,
+    pub jobs_failed: u64,
+    pub jobs_retried: u64,
+    pub jobs_dead_lettered: u64,
+    pub fetch_errors: u64,
+    pub connect_failures: u64,
+}
+
+impl TenantMetrics {
+    pub fn snapshot(&self) -> TenantMetricsSnapshot {
+        TenantMetricsSnapshot {
+            jobs_anchored: self.jobs_anchored.load(Ordering::Relaxed),
+            jobs_failed: self.jobs_failed.load(Ordering::Relaxed),
+            jobs_retried: self.jobs_retried.load(Ordering::Relaxed),
+            jobs_dead_lettered: self.jobs_dead_lettered.load(Ordering::Relaxed),
+            fetch_errors: self.fetch_errors.load(Ordering::Relaxed),
+            connect_failures: self.connect_failures.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// A tenant database served by this keeper
+#[derive(Debug, Clone)]
+pub struct Tenant {
+    pub id: String,
+    pub db_url: String,
+    pub metrics: Arc<TenantMetrics>,
+}
+
+impl Tenant {
+    pub fn new(id: impl Into<String>, db_url: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            db_url: db_url.into(),
+            metrics: Arc::new(TenantMetrics::default()),
+        }
+    }
+}
+
+/// Per-loop settings shared by every tenant
+#[derive(Debug, Clone, Copy)]
+pub struct TenantSettings {
+    pub job_poll_interval: Duration,
+    pub confirmation_poll_interval: Duration,
+    pub max_attempts: i64,
+    pub retry_interval: Duration,
+    /// Jobs anchored in parallel; 1 keeps the sequential loop
+    pub concurrency: usize,
+    /// `in_progress` jobs older than this are requeued by the reaper
+    pub stale_after: Duration,
+    /// Unconfirmed txs older than this that the chain reports as unknown are
+    /// re-anchored
+    pub confirm_timeout: Duration,
+    /// Unconfirmed txs checked per confirmation poll
+    pub confirm_batch_size: i64,
+    /// Reuse a confirmed anchor for jobs repeating an already-anchored digest
+    pub dedupe_digests: bool,
+    /// How often Merkle batches are flushed and their transactions confirmed
+    pub batch_poll_interval: Duration,
+}
+
+/// Build tenants from a comma-separated `KEEPER_DB_URL`.
+///
+/// Tenants are named after their database file stem (`sqlite://data/acme.sqlite3`
+/// → `acme`), falling back to `tenant-<n>` when there is no usable stem or the
+/// name is already taken.
+pub fn tenants_from_urls(urls: &str) -> Vec<Tenant> {
+    let mut tenants: Vec<Tenant> = Vec::new();
+    for (index, url) in urls
+        .split(',')
+        .map(str::trim)
+        .filter(|u| !u.is_empty())
+        .enumerate()
+    {
+        let id = tenant_id_from_url(url)
+            .filter(|id| tenants.iter().all(|t| &t.id != id))
+            .unwrap_or_else(|| format!("tenant-{}", index));
+        tenants.push(Tenant::new(id, url));
+    }
+    tenants
+}
+
+fn tenant_id_from_url(url: &str) -> Option<String> {
+    let path = url.strip_prefix("sqlite:")?.trim_start_matches('/');
+    let path = path.strip_prefix("file:").unwrap_or(path);
+    let file = path.split('?').next()?.rsplit('/').next()?;
+    let stem = file.split('.').next()?;
+    (!stem.is_empty() && stem != ":memory:").then(|| stem.to_string())
+}
+
+/// Run one tenant's job, confirmation, reaper and batch loops until
+/// `shutdown` is set. Merkle batches are anchored and confirmed through
+/// `batch_provider`.
+///
+/// Connection and schema failures, and loops that exit (e.g. on panic), are
+/// logged and retried after `settings.retry_interval`; they never propagate.
+/// On shutdown every loop is allowed to stop cleanly before this returns.
+pub async fn run_tenant(
+    tenant: Tenant,
+    anchors: Arc<Vec<BoxedAnchor>>,
+    batch_provider: Arc<dyn AnchorProvider + Send + Sync>,
+    settings: TenantSettings,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    while !*shutdown.borrow() {
+        let pool = match SqlitePoolOptions::new()
+            .max_connections(5)
+            .connect(&tenant.db_url)
+            .await
+        {
+            Ok(pool) => pool,
+            Err(e) => {
+                tenant
+                    .metrics
+                    .connect_failures
+                    .fetch_add(1, Ordering::Relaxed);
+                tracing::error!(tenant = %tenant.id, error = %e, "Tenant db connect failed");
+                retry_or_shutdown(settings.retry_interval, &mut shutdown).await;
+                continue;
+            }
+        };
+        let schema = match ensure_schema(&pool).await {
+            Ok(()) => BatchAnchor::ensure_schema(&pool).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = schema {
+            tenant
+                .metrics
+                .connect_failures
+                .fetch_add(1, Ordering::Relaxed);
+            tracing::error!(tenant = %tenant.id, error = %e, "Tenant schema init failed");
+            retry_or_shutdown(settings.retry_interval, &mut shutdown).await;
+            continue;
+        }
+        tracing::info!(tenant = %tenant.id, "Tenant keeper started");
+
+        let mut job_provider = MeteredJobProvider {
+            inner: SqliteJobProvider::new(pool.clone())
+                .with_max_attempts(settings.max_attempts)
+                .with_dedupe_digests(settings.dedupe_digests),
+            metrics: tenant.metrics.clone(),
+        };
+        let job_anchors = anchors.clone();
+        let job_shutdown = shutdown.clone();
+        let mut job_handle = tokio::spawn(async move {
+            if settings.concurrency > 1 {
+                run_job_loop_concurrent(
+                    job_provider,
+                    job_anchors,
+                    settings.job_poll_interval,
+                    settings.concurrency,
+                    job_shutdown,
+                )
+                .await;
+            } else {
+                run_job_loop(
+                    &mut job_provider,
+                    job_anchors.as_slice(),
+                    settings.job_poll_interval,
+                    job_shutdown,
+                )
+                .await;
+            }
+        });
+        let reaper_pool = pool.clone();
+        let reaper_shutdown = shutdown.clone();
+        let mut reaper_handle = tokio::spawn(async move {
+            run_reaper_loop(
+                &reaper_pool,
+                settings.stale_after,
+                REAPER_INTERVAL.min(settings.stale_after),
+                reaper_shutdown,
+            )
+            .await;
+        });
+        let batch_anchor = Arc::new(BatchAnchor::new(
+            pool.clone(),
+            batch_provider.clone(),
+            BatchConfig::default(),
+        ));
+        let mut batch_handle = tokio::spawn(run_batch_loop(
+            batch_anchor,
+            settings.batch_poll_interval,
+            shutdown.clone(),
+        ));
+        let confirm_anchors = anchors.clone();
+        let confirm_shutdown = shutdown.clone();
+        let mut confirm_handle = tokio::spawn(async move {
+            run_confirmation_loop(
+                &pool,
+                confirm_anchors.as_slice(),
+                settings.confirmation_poll_interval,
+                settings.confirm_timeout,
+                settings.confirm_batch_size,
+                confirm_shutdown,
+            )
+            .await;
+        });
+
+        tokio::select! {
+            _ = &mut job_handle => {
+                tracing::warn!(tenant = %tenant.id, "Tenant job loop exited unexpectedly");
+            }
+            _ = &mut confirm_handle => {
+                tracing::warn!(tenant = %tenant.id, "Tenant confirmation loop exited unexpectedly");
+            }
+            _ = &mut reaper_handle => {
+                tracing::warn!(tenant = %tenant.id, "Tenant reaper loop exited unexpectedly");
+            }
+            _ = &mut batch_handle => {
+                tracing::warn!(tenant = %tenant.id, "Tenant batch loop exited unexpectedly");
+            }
+            _ = shutdown_signalled(&mut shutdown) => {
+                // Every loop sees the signal; let them release claimed jobs
+                let _ = tokio::join!(
+                    &mut job_handle,
+                    &mut confirm_handle,
+                    &mut reaper_handle,
+                    &mut batch_handle
+                );
+                tracing::info!(tenant = %tenant.id, "Tenant keeper stopped");
+                return;
+            }
+        }
+        // Restart all loops together on a fresh pool
+        job_handle.abort();
+        confirm_handle.abort();
+        reaper_handle.abort();
+        batch_handle.abort();
+        retry_or_shutdown(settings.retry_interval, &mut shutdown).await;
+    }
+}
+
+/// Wait out a tenant retry, waking early on shutdown
+async fn retry_or_shutdown(retry_interval: Duration, shutdown: &mut watch::Receiver<bool>) {
+    tokio::select! {
+        _ = tokio::time::sleep(retry_interval) => {}
+        _ = shutdown_signalled(shutdown) => {}
+    }
+}
+
+/// Job provider that records outcomes in a tenant's metrics
+#[derive(Clone)]
+struct MeteredJobProvider<J> {
+    inner: J,
+    metrics: Arc<TenantMetrics>,
+}
+
+#[async_trait]
+impl<J: JobProvider + Send> JobProvider for MeteredJobProvider<J> {
+    async fn fetch_next(&mut self) -> Result<Option<EvidenceJob>, JobError> {
+        let result = self.inner.fetch_next().await;
+        if result.is_err() {
+            self.metrics.fetch_errors.fetch_add(1, Ordering::Relaxed);
+        }
+        result
+    }
+
+    async fn mark_done(&mut self, id: &str) -> Result<(), JobError> {
+        self.inner.mark_done(id).await
+    }
+
+    async fn mark_failed(&mut self, id: &str, reason: &str) -> Result<(), JobError> {
+        self.inner.mark_failed(id, reason).await
+    }
+}
+
+#[async_trait]
+impl<J: JobProviderExt + Send> JobProviderExt for MeteredJobProvider<J> {
+    async fn mark_tx_and_done(&mut self, id: &str, tx: &ChainTxRef) -> Result<(), JobError> {
+        self.inner.mark_tx_and_done(id, tx).await?;
+        self.metrics.jobs_anchored.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    async fn mark_txs_and_done(
+        &mut self,
+        id: &str,
+        txs: &[ChainTxRef],
+        partial_failure: Option<&str>,
+    ) -> Result<(), JobError> {
+        self.inner
+            .mark_txs_and_done(id, txs, partial_failure)
+            .await?;
+        self.metrics.jobs_anchored.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    async fn mark_failed_or_backoff(
+        &mut self,
+        id: &str,
+        reason: &str,
+        temporary: bool,
+    ) -> Result<BackoffOutcome, JobError> {
+        let outcome = self
+            .inner
+            .mark_failed_or_backoff(id, reason, temporary)
+            .await?;
+        let counter = match outcome {
+            BackoffOutcome::Requeued => &self.metrics.jobs_retried,
+            BackoffOutcome::DeadLettered => &self.metrics.jobs_dead_lettered,
+            BackoffOutcome::Failed => &self.metrics.jobs_failed,
+        };
+        counter.fetch_add(1, Ordering::Relaxed);
+        Ok(outcome)
+    }
+
+    async fn release(&mut self, id: &str) -> Result<(), JobError> {
+        self.inner.release(id).await
+    }
+
+    async fn find_confirmed_anchor(
+        &mut self,
+        payload_sha256: &str,
+        job_id: &str,
+    ) -> Result<Vec<ChainTxRef>, JobError> {
+        self.inner
+            .find_confirmed_anchor(payload_sha256, job_id)
+            .await
+    }
+}
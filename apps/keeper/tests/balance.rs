@@ -0,0 +1,149 @@
+//! Anchoring wallet balance checks: warn below the minimum, optionally pause
+
+use phoenix_evidence::{
+    anchor::{AnchorError, AnchorProvider, BalanceCheck},
+    model::{ChainTxRef, EvidenceRecord},
+};
+use phoenix_keeper::balance::{
+    min_balance_from_env, run_balance_monitor, BalanceGuard, DEFAULT_SOLANA_MIN_BALANCE_LAMPORTS,
+};
+use phoenix_keeper::metrics;
+use serial_test::serial;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+
+/// Provider whose wallet holds `balance` lamports; a balance of `u64::MAX`
+/// makes the balance RPC fail
+struct WalletAnchor {
+    balance: AtomicU64,
+    checks: AtomicUsize,
+}
+
+impl WalletAnchor {
+    fn new(balance: u64) -> Arc<Self> {
+        Arc::new(Self {
+            balance: AtomicU64::new(balance),
+            checks: AtomicUsize::new(0),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl AnchorProvider for WalletAnchor {
+    async fn anchor(&self, _evidence: &EvidenceRecord) -> Result<ChainTxRef, AnchorError> {
+        Err(AnchorError::Provider("not used".to_string()))
+    }
+
+    async fn confirm(&self, tx: &ChainTxRef) -> Result<ChainTxRef, AnchorError> {
+        Ok(tx.clone())
+    }
+
+    fn network(&self) -> Option<&str> {
+        Some("solana")
+    }
+
+    async fn check_sufficient_balance(
+        &self,
+        min: u128,
+    ) -> Result<Option<BalanceCheck>, AnchorError> {
+        self.checks.fetch_add(1, Ordering::SeqCst);
+        match self.balance.load(Ordering::SeqCst) {
+            u64::MAX => Err(AnchorError::Network("getBalance failed".to_string())),
+            balance => Ok(Some(BalanceCheck {
+                balance: balance.into(),
+                minimum: min,
+            })),
+        }
+    }
+}
+
+#[tokio::test]
+#[serial]
+async fn test_balance_above_minimum_is_not_short() {
+    let wallet = WalletAnchor::new(5_000_000);
+    let guard = BalanceGuard::new(wallet.clone(), 1_000_000).with_pause_when_short(true);
+    let warnings = metrics::global().low_balance_warnings();
+
+    let check = guard.check().await.unwrap();
+    assert_eq!(check.balance, 5_000_000);
+    assert!(check.is_sufficient());
+    assert!(!guard.is_short());
+    assert!(guard.is_available());
+    assert_eq!(metrics::global().low_balance_warnings(), warnings);
+}
+
+#[tokio::test]
+#[serial]
+async fn test_balance_below_minimum_warns_and_pauses() {
+    let wallet = WalletAnchor::new(400);
+    let pausing = BalanceGuard::new(wallet.clone(), 1_000_000).with_pause_when_short(true);
+    let warning_only = BalanceGuard::new(wallet.clone(), 1_000_000);
+    let warnings = metrics::global().low_balance_warnings();
+
+    assert!(!pausing.check().await.unwrap().is_sufficient());
+    assert!(pausing.is_short());
+    assert!(!pausing.is_available(), "a short wallet pauses the backend");
+    assert!(metrics::global()
+        .render()
+        .contains("keeper_low_balance_warnings_total"));
+
+    // Without pausing the backend keeps taking jobs; both checks warned
+    warning_only.check().await;
+    assert!(warning_only.is_short());
+    assert!(warning_only.is_available());
+    assert_eq!(metrics::global().low_balance_warnings(), warnings + 2);
+
+    // A failed check keeps the last verdict; a top-up resumes anchoring
+    wallet.balance.store(u64::MAX, Ordering::SeqCst);
+    assert!(pausing.check().await.is_none());
+    assert!(!pausing.is_available());
+    wallet.balance.store(2_000_000, Ordering::SeqCst);
+    assert!(pausing.check().await.unwrap().is_sufficient());
+    assert!(pausing.is_available());
+}
+
+#[tokio::test]
+async fn test_balance_monitor_rechecks_until_shutdown() {
+    let wallet = WalletAnchor::new(5_000_000);
+    let guard = Arc::new(BalanceGuard::new(wallet.clone(), 1_000_000));
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+
+    let monitor = tokio::spawn(run_balance_monitor(
+        vec![guard.clone()],
+        Duration::from_millis(20),
+        shutdown_rx,
+    ));
+    tokio::time::sleep(Duration::from_millis(110)).await;
+    assert!(wallet.checks.load(Ordering::SeqCst) >= 2);
+
+    wallet.balance.store(10, Ordering::SeqCst);
+    tokio::time::sleep(Duration::from_millis(60)).await;
+    assert!(guard.is_short());
+
+    shutdown_tx.send(true).unwrap();
+    tokio::time::timeout(Duration::from_secs(1), monitor)
+        .await
+        .expect("monitor stops on shutdown")
+        .unwrap();
+}
+
+#[test]
+#[serial]
+fn test_min_balance_from_env() {
+    std::env::remove_var("SOLANA_MIN_BALANCE_LAMPORTS");
+    assert_eq!(
+        min_balance_from_env(Some("solana")),
+        Ok(DEFAULT_SOLANA_MIN_BALANCE_LAMPORTS)
+    );
+    assert_eq!(min_balance_from_env(None), Ok(0));
+
+    std::env::set_var("SOLANA_MIN_BALANCE_LAMPORTS", "2500");
+    assert_eq!(min_balance_from_env(Some("solana")), Ok(2500));
+    std::env::set_var("SOLANA_MIN_BALANCE_LAMPORTS", "lots");
+    assert!(min_balance_from_env(Some("solana"))
+        .unwrap_err()
+        .contains("SOLANA_MIN_BALANCE_LAMPORTS"));
+    std::env::remove_var("SOLANA_MIN_BALANCE_LAMPORTS");
+}
@@ -2,15 +2,20 @@
 //!
 //! Covers: schema creation, add-and-flush, batch-size trigger,
 //! proof retrieval, proof verification, statistics, empty-flush
-//! no-op, and timeout-triggered flushing.
+//! no-op, timeout-triggered flushing, proof bundle export, and withholding
+//! proofs until the batch transaction confirms.
 
 use async_trait::async_trait;
 use chrono::Utc;
 use phoenix_evidence::anchor::{AnchorError, AnchorProvider};
 use phoenix_evidence::model::{ChainTxRef, EvidenceRecord};
-use phoenix_keeper::batch_anchor::{BatchAnchor, BatchConfig, BatchStats};
+use phoenix_keeper::batch_anchor::{
+    verify_proof_bundle, BatchAnchor, BatchConfig, BatchError, BatchStats, ProofBundle,
+    MERKLE_CONSTRUCTION_VERSION, PROOF_BUNDLE_VERSION,
+};
 use serial_test::serial;
 use sqlx::{sqlite::SqlitePoolOptions, Pool, Row, Sqlite};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::SystemTime;
 
@@ -95,6 +100,32 @@ impl AnchorProvider for FailingAnchor {
     }
 }
 
+/// Anchor provider whose transactions start unconfirmed and only confirm once
+/// `finalized` is set, to exercise proof withholding.
+#[derive(Default)]
+struct SlowFinalityAnchor {
+    finalized: AtomicBool,
+}
+
+#[async_trait]
+impl AnchorProvider for SlowFinalityAnchor {
+    async fn anchor(&self, evidence: &EvidenceRecord) -> Result<ChainTxRef, AnchorError> {
+        Ok(ChainTxRef {
+            network: "test".to_string(),
+            chain: "mock".to_string(),
+            tx_id: format!("slow-tx-{}", &evidence.digest.hex[..8]),
+            confirmed: false,
+            timestamp: Some(Utc::now()),
+        })
+    }
+
+    async fn confirm(&self, tx: &ChainTxRef) -> Result<ChainTxRef, AnchorError> {
+        let mut checked = tx.clone();
+        checked.confirmed = self.finalized.load(Ordering::SeqCst);
+        Ok(checked)
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Shared helper: valid 64-char hex SHA-256 digests for test payloads
 // ---------------------------------------------------------------------------
@@ -186,6 +217,7 @@ async fn test_add_to_batch_and_flush() {
         max_batch_size: 50,
         max_batch_age_seconds: 3600,
         min_batch_size: 1,
+        ..BatchConfig::default()
     };
     let anchor = Arc::new(MockAnchor);
     let ba = BatchAnchor::new(pool.clone(), anchor, config);
@@ -222,6 +254,7 @@ async fn test_flush_anchors_all_pending_items() {
         max_batch_size: 100,
         max_batch_age_seconds: 3600,
         min_batch_size: 1,
+        ..BatchConfig::default()
     };
     let anchor = Arc::new(MockAnchor);
     let ba = BatchAnchor::new(pool.clone(), anchor, config);
@@ -264,6 +297,7 @@ async fn test_batch_size_triggers_automatic_anchor() {
         max_batch_size,
         max_batch_age_seconds: 3600,
         min_batch_size: 1,
+        ..BatchConfig::default()
     };
     let anchor = Arc::new(MockAnchor);
     let ba = BatchAnchor::new(pool.clone(), anchor, config);
@@ -359,6 +393,7 @@ async fn test_proof_retrieval_leaf_indices_in_multi_item_batch() {
         max_batch_size: 100,
         max_batch_age_seconds: 3600,
         min_batch_size: 1,
+        ..BatchConfig::default()
     };
     let anchor = Arc::new(MockAnchor);
     let ba = BatchAnchor::new(pool.clone(), anchor, config);
@@ -407,7 +442,9 @@ async fn test_proof_verification_single_item() {
     ba.flush().await.unwrap();
 
     let (proof, _) = ba.get_proof(job_id).await.unwrap().unwrap();
-    let valid = proof.verify(&proof.root).unwrap();
+    let valid = proof
+        .verify(&proof.root, MERKLE_CONSTRUCTION_VERSION)
+        .unwrap();
     assert!(
         valid,
         "single-item MerkleProof must verify against its root"
@@ -425,6 +462,7 @@ async fn test_proof_verification_multi_item_batch() {
         max_batch_size: 100,
         max_batch_age_seconds: 3600,
         min_batch_size: 1,
+        ..BatchConfig::default()
     };
     let anchor = Arc::new(MockAnchor);
     let ba = BatchAnchor::new(pool.clone(), anchor, config);
@@ -443,7 +481,9 @@ async fn test_proof_verification_multi_item_batch() {
     let mut roots: Vec<String> = Vec::new();
     for (job_id, _) in &items {
         let (proof, _) = ba.get_proof(job_id).await.unwrap().unwrap();
-        let valid = proof.verify(&proof.root).unwrap();
+        let valid = proof
+            .verify(&proof.root, MERKLE_CONSTRUCTION_VERSION)
+            .unwrap();
         assert!(
             valid,
             "MerkleProof for {} must verify against its root",
@@ -489,13 +529,17 @@ async fn test_proof_verification_fails_with_wrong_root() {
         // If the root had no 'a's, flip '0' to '1' instead.
         let alt_root = proof.root.replace('0', "1");
         if alt_root != proof.root {
-            let valid = proof.verify(&alt_root).unwrap();
+            let valid = proof
+                .verify(&alt_root, MERKLE_CONSTRUCTION_VERSION)
+                .unwrap();
             assert!(!valid, "proof must not verify against a tampered root");
         }
         // If the root somehow has neither 'a' nor '0' we cannot reliably
         // construct a differing valid-hex root, so skip the assertion.
     } else {
-        let valid = proof.verify(&wrong_root).unwrap();
+        let valid = proof
+            .verify(&wrong_root, MERKLE_CONSTRUCTION_VERSION)
+            .unwrap();
         assert!(!valid, "proof must not verify against a tampered root");
     }
 }
@@ -516,6 +560,7 @@ async fn test_get_stats_after_flush() {
         max_batch_size: 100,
         max_batch_age_seconds: 3600,
         min_batch_size: 1,
+        ..BatchConfig::default()
     };
     let anchor = Arc::new(MockAnchor);
     let ba = BatchAnchor::new(pool.clone(), anchor, config);
@@ -557,6 +602,7 @@ async fn test_get_stats_accumulates_across_multiple_flushes() {
         max_batch_size: 100,
         max_batch_age_seconds: 3600,
         min_batch_size: 1,
+        ..BatchConfig::default()
     };
     let anchor = Arc::new(MockAnchor);
     let ba = BatchAnchor::new(pool.clone(), anchor, config);
@@ -653,6 +699,7 @@ async fn test_check_timeout_triggers_flush_when_age_zero_threshold() {
         max_batch_size: 100,
         max_batch_age_seconds: 0,
         min_batch_size: 1,
+        ..BatchConfig::default()
     };
     let anchor = Arc::new(MockAnchor);
     let ba = BatchAnchor::new(pool.clone(), anchor, config);
@@ -690,6 +737,7 @@ async fn test_check_timeout_empty_batch_returns_false() {
         max_batch_size: 100,
         max_batch_age_seconds: 0,
         min_batch_size: 1,
+        ..BatchConfig::default()
     };
     let anchor = Arc::new(MockAnchor);
     let ba = BatchAnchor::new(pool.clone(), anchor, config);
@@ -713,6 +761,7 @@ async fn test_check_timeout_respects_min_batch_size() {
         max_batch_size: 100,
         max_batch_age_seconds: 0, // age threshold always satisfied
         min_batch_size: 3,        // but we only add 1 item
+        ..BatchConfig::default()
     };
     let anchor = Arc::new(MockAnchor);
     let ba = BatchAnchor::new(pool.clone(), anchor, config);
@@ -754,6 +803,7 @@ async fn test_flush_with_failing_anchor_survives_gracefully() {
         max_batch_size: 100,
         max_batch_age_seconds: 3600,
         min_batch_size: 1,
+        ..BatchConfig::default()
     };
     let anchor = Arc::new(FailingAnchor);
     let ba = BatchAnchor::new(pool.clone(), anchor, config);
@@ -818,3 +868,132 @@ async fn test_get_proof_returns_none_for_unknown_job() {
         "get_proof must return None for unknown job"
     );
 }
+
+// ---------------------------------------------------------------------------
+// Test 11: Proof bundle export
+// ---------------------------------------------------------------------------
+
+/// `export_proof_bundle` produces a self-contained bundle that verifies offline.
+#[tokio::test]
+#[serial]
+async fn test_export_proof_bundle_verifies_offline() {
+    let pool = make_pool().await;
+    setup_schema(&pool).await;
+
+    let config = BatchConfig::default();
+    let anchor = Arc::new(MockAnchor);
+    let ba = BatchAnchor::new(pool.clone(), anchor, config);
+
+    for i in 0..3 {
+        let job_id = format!("bundle-job-{}", i);
+        let digest = test_digest(40 + i);
+        insert_outbox_job(&pool, &job_id, &digest).await;
+        ba.add_to_batch(&job_id, &digest).await.unwrap();
+    }
+    ba.flush().await.unwrap();
+
+    let bundle = ba.export_proof_bundle("bundle-job-1").await.unwrap();
+    assert_eq!(bundle.version, PROOF_BUNDLE_VERSION);
+    assert_eq!(bundle.job_id, "bundle-job-1");
+    assert_eq!(bundle.network, "test");
+    assert_eq!(bundle.chain, "mock");
+    assert_eq!(bundle.proof.leaf_hash, test_digest(41));
+    assert_eq!(bundle.merkle_root, bundle.proof.root);
+    assert_eq!(bundle.construction_version, MERKLE_CONSTRUCTION_VERSION);
+
+    // Round-trip through JSON as an external verifier would receive it
+    let json = serde_json::to_string(&bundle).unwrap();
+    let parsed: ProofBundle = serde_json::from_str(&json).unwrap();
+    assert!(verify_proof_bundle(&parsed).unwrap());
+}
+
+/// `export_proof_bundle` errors for a job that was never anchored.
+#[tokio::test]
+#[serial]
+async fn test_export_proof_bundle_unknown_job() {
+    let pool = make_pool().await;
+    setup_schema(&pool).await;
+
+    let ba = BatchAnchor::new(pool.clone(), Arc::new(MockAnchor), BatchConfig::default());
+
+    let result = ba.export_proof_bundle("nonexistent-job-id").await;
+    assert!(matches!(result, Err(BatchError::ProofNotFound(_))));
+}
+
+// ---------------------------------------------------------------------------
+// Test 12: Proofs withheld until the batch transaction confirms
+// ---------------------------------------------------------------------------
+
+/// With `require_confirmed`, proofs stay pending until
+/// `confirm_pending_batches` sees the batch transaction confirmed.
+#[tokio::test]
+#[serial]
+async fn test_proof_withheld_until_batch_confirmed() {
+    let pool = make_pool().await;
+    setup_schema(&pool).await;
+
+    let anchor = Arc::new(SlowFinalityAnchor::default());
+    let config = BatchConfig {
+        require_confirmed: true,
+        ..BatchConfig::default()
+    };
+    let ba = BatchAnchor::new(pool.clone(), anchor.clone(), config);
+
+    let job_id = "finality-job-0";
+    let digest = test_digest(50);
+    insert_outbox_job(&pool, job_id, &digest).await;
+    ba.add_to_batch(job_id, &digest).await.unwrap();
+    ba.flush().await.unwrap();
+
+    // Anchored but unconfirmed: both proof paths withhold
+    assert!(matches!(
+        ba.get_proof(job_id).await,
+        Err(BatchError::PendingConfirmation(_))
+    ));
+    assert!(matches!(
+        ba.export_proof_bundle(job_id).await,
+        Err(BatchError::PendingConfirmation(_))
+    ));
+
+    // Still not final on chain: the confirmation pass changes nothing
+    assert_eq!(ba.confirm_pending_batches().await.unwrap(), 0);
+    assert!(matches!(
+        ba.get_proof(job_id).await,
+        Err(BatchError::PendingConfirmation(_))
+    ));
+
+    anchor.finalized.store(true, Ordering::SeqCst);
+    assert_eq!(ba.confirm_pending_batches().await.unwrap(), 1);
+
+    let (_, tx_ref) = ba.get_proof(job_id).await.unwrap().unwrap();
+    assert!(tx_ref.confirmed);
+    let bundle = ba.export_proof_bundle(job_id).await.unwrap();
+    assert!(bundle.tx_ref.confirmed);
+    assert!(verify_proof_bundle(&bundle).unwrap());
+
+    // Already-confirmed batches are not re-checked
+    assert_eq!(ba.confirm_pending_batches().await.unwrap(), 0);
+}
+
+/// Without `require_confirmed`, an unconfirmed batch still yields its proof.
+#[tokio::test]
+#[serial]
+async fn test_proof_returned_unconfirmed_by_default() {
+    let pool = make_pool().await;
+    setup_schema(&pool).await;
+
+    let ba = BatchAnchor::new(
+        pool.clone(),
+        Arc::new(SlowFinalityAnchor::default()),
+        BatchConfig::default(),
+    );
+
+    let job_id = "finality-job-default";
+    let digest = test_digest(51);
+    insert_outbox_job(&pool, job_id, &digest).await;
+    ba.add_to_batch(job_id, &digest).await.unwrap();
+    ba.flush().await.unwrap();
+
+    let (_, tx_ref) = ba.get_proof(job_id).await.unwrap().unwrap();
+    assert!(!tx_ref.confirmed);
+}
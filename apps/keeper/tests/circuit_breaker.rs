@@ -0,0 +1,217 @@
+//! Circuit breaker: opens on repeated failures, pauses or fails over, and
+//! closes again after a successful probe
+
+use chrono::Utc;
+use phoenix_evidence::{
+    anchor::{AnchorError, AnchorProvider},
+    model::{ChainTxRef, DigestAlgo, EvidenceDigest, EvidenceRecord},
+};
+use phoenix_keeper::circuit_breaker::{BreakerState, CircuitBreakerProvider};
+use phoenix_keeper::{ensure_schema, run_job_loop, BoxedAnchor, SqliteJobProvider};
+use sqlx::{sqlite::SqlitePoolOptions, Pool, Sqlite};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+
+/// Provider that fails with a network error while `down` is set
+struct SwitchableAnchor {
+    network: &'static str,
+    down: AtomicBool,
+    anchor_calls: AtomicUsize,
+    confirm_calls: AtomicUsize,
+}
+
+impl SwitchableAnchor {
+    fn new(network: &'static str, down: bool) -> Arc<Self> {
+        Arc::new(Self {
+            network,
+            down: AtomicBool::new(down),
+            anchor_calls: AtomicUsize::new(0),
+            confirm_calls: AtomicUsize::new(0),
+        })
+    }
+
+    fn calls(&self) -> usize {
+        self.anchor_calls.load(Ordering::SeqCst)
+    }
+}
+
+#[async_trait::async_trait]
+impl AnchorProvider for SwitchableAnchor {
+    async fn anchor(&self, evidence: &EvidenceRecord) -> Result<ChainTxRef, AnchorError> {
+        self.anchor_calls.fetch_add(1, Ordering::SeqCst);
+        if self.down.load(Ordering::SeqCst) {
+            return Err(AnchorError::Network("rpc unreachable".to_string()));
+        }
+        Ok(ChainTxRef {
+            network: self.network.to_string(),
+            chain: "testnet".to_string(),
+            tx_id: format!("{}-{}", self.network, evidence.id),
+            confirmed: false,
+            timestamp: Some(Utc::now()),
+        })
+    }
+
+    async fn confirm(&self, tx: &ChainTxRef) -> Result<ChainTxRef, AnchorError> {
+        self.confirm_calls.fetch_add(1, Ordering::SeqCst);
+        Ok(tx.clone())
+    }
+}
+
+fn evidence(id: &str) -> EvidenceRecord {
+    EvidenceRecord {
+        id: id.to_string(),
+        created_at: Utc::now(),
+        digest: EvidenceDigest {
+            algo: DigestAlgo::Sha256,
+            hex: "abcd1234".to_string(),
+        },
+        payload_mime: None,
+        metadata: serde_json::json!({}),
+    }
+}
+
+async fn setup_pool(jobs: usize) -> Pool<Sqlite> {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    ensure_schema(&pool).await.unwrap();
+    let now = Utc::now().timestamp_millis();
+    for i in 0..jobs {
+        sqlx::query(
+            "INSERT INTO outbox_jobs (id, payload_sha256, status, attempts, created_ms, updated_ms, next_attempt_ms)
+             VALUES (?1, 'abcd1234', 'queued', 0, ?2, ?2, 0)",
+        )
+        .bind(format!("job-{}", i))
+        .bind(now + i as i64)
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+    pool
+}
+
+async fn count_status(pool: &Pool<Sqlite>, status: &str) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM outbox_jobs WHERE status = ?1")
+        .bind(status)
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_repeated_failures_open_breaker() {
+    let primary = SwitchableAnchor::new("primary", true);
+    let breaker = CircuitBreakerProvider::new(primary.clone())
+        .with_failure_threshold(3)
+        .with_probe_interval(Duration::from_secs(60));
+
+    for i in 0..2 {
+        assert!(breaker
+            .anchor(&evidence(&format!("ev-{}", i)))
+            .await
+            .is_err());
+        assert_eq!(breaker.state(), BreakerState::Closed);
+    }
+    assert!(breaker.anchor(&evidence("ev-2")).await.is_err());
+    assert_eq!(breaker.state(), BreakerState::Open);
+    assert!(!breaker.is_available());
+
+    // While open, calls are rejected without touching the primary
+    assert!(breaker.anchor(&evidence("ev-3")).await.is_err());
+    assert_eq!(primary.calls(), 3);
+}
+
+#[tokio::test]
+async fn test_open_breaker_pauses_job_loop_until_probe_succeeds() {
+    let pool = setup_pool(5).await;
+    let primary = SwitchableAnchor::new("primary", true);
+    let breaker = Arc::new(
+        CircuitBreakerProvider::new(primary.clone())
+            .with_failure_threshold(2)
+            .with_probe_interval(Duration::from_millis(300)),
+    );
+
+    let loop_pool = pool.clone();
+    let loop_breaker = breaker.clone();
+    let runner = tokio::spawn(async move {
+        let mut provider = SqliteJobProvider::new(loop_pool);
+        run_job_loop(
+            &mut provider,
+            &[Box::new(loop_breaker) as BoxedAnchor],
+            Duration::from_millis(10),
+            watch::channel(false).1,
+        )
+        .await;
+    });
+
+    // Two failures open the breaker; the loop then stops claiming jobs
+    while breaker.state() != BreakerState::Open {
+        tokio::time::sleep(Duration::from_millis(5)).await;
+    }
+    tokio::time::sleep(Duration::from_millis(150)).await;
+    assert_eq!(primary.calls(), 2);
+    let attempts: i64 = sqlx::query_scalar("SELECT SUM(attempts) FROM outbox_jobs")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(attempts, 2, "no jobs claimed while the breaker is open");
+
+    // Primary recovers; the probe succeeds and the backlog drains
+    primary.down.store(false, Ordering::SeqCst);
+    for _ in 0..200 {
+        if count_status(&pool, "done").await == 3 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    runner.abort();
+    assert_eq!(breaker.state(), BreakerState::Closed);
+    // The two failed jobs are in backoff; the other three were anchored
+    assert_eq!(count_status(&pool, "done").await, 3);
+}
+
+#[tokio::test]
+async fn test_open_breaker_fails_over_to_fallback() {
+    let primary = SwitchableAnchor::new("primary", true);
+    let fallback = SwitchableAnchor::new("fallback", false);
+    let breaker = CircuitBreakerProvider::new(primary.clone())
+        .with_fallback(fallback.clone())
+        .with_failure_threshold(2)
+        .with_probe_interval(Duration::from_millis(100));
+
+    // Failing primary calls are retried on the fallback straight away
+    let tx = breaker.anchor(&evidence("ev-0")).await.unwrap();
+    assert_eq!(tx.network, "fallback");
+    breaker.anchor(&evidence("ev-1")).await.unwrap();
+    assert_eq!(breaker.state(), BreakerState::Open);
+    assert!(breaker.is_available(), "fallback keeps the loop running");
+
+    // Open: the primary is skipped entirely
+    let tx = breaker.anchor(&evidence("ev-2")).await.unwrap();
+    assert_eq!(tx.network, "fallback");
+    assert_eq!(primary.calls(), 2);
+    assert_eq!(fallback.calls(), 3);
+
+    // Fallback transactions are confirmed by the fallback
+    breaker.confirm(&tx).await.unwrap();
+    assert_eq!(fallback.confirm_calls.load(Ordering::SeqCst), 1);
+    assert_eq!(primary.confirm_calls.load(Ordering::SeqCst), 0);
+
+    // After the probe interval a successful probe closes the breaker
+    primary.down.store(false, Ordering::SeqCst);
+    tokio::time::sleep(Duration::from_millis(120)).await;
+    let primary_tx = breaker.anchor(&evidence("ev-3")).await.unwrap();
+    assert_eq!(primary_tx.network, "primary");
+    assert_eq!(breaker.state(), BreakerState::Closed);
+
+    // A mixed batch is split between the providers, results in input order
+    let results = breaker.confirm_many(&[tx, primary_tx]).await;
+    let networks: Vec<String> = results.into_iter().map(|r| r.unwrap().network).collect();
+    assert_eq!(networks, ["fallback", "primary"]);
+    assert_eq!(fallback.confirm_calls.load(Ordering::SeqCst), 2);
+    assert_eq!(primary.confirm_calls.load(Ordering::SeqCst), 1);
+}
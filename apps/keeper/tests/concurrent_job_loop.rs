@@ -0,0 +1,124 @@
+//! Bounded-concurrency job loop: throughput and single claim per job
+
+use chrono::Utc;
+use phoenix_evidence::{
+    anchor::{AnchorError, AnchorProvider},
+    model::{ChainTxRef, EvidenceRecord},
+};
+use phoenix_keeper::{ensure_schema, run_job_loop_concurrent, BoxedAnchor, SqliteJobProvider};
+use sqlx::{sqlite::SqlitePoolOptions, Pool, Sqlite};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tempfile::NamedTempFile;
+use tokio::sync::watch;
+
+const JOBS: usize = 20;
+const CONCURRENCY: usize = 5;
+const ANCHOR_DELAY: Duration = Duration::from_millis(100);
+
+/// Anchor that takes a fixed time per job and tracks how many run at once
+#[derive(Default)]
+struct SlowAnchor {
+    in_flight: AtomicUsize,
+    max_in_flight: AtomicUsize,
+    anchored: Mutex<Vec<String>>,
+}
+
+#[async_trait::async_trait]
+impl AnchorProvider for SlowAnchor {
+    async fn anchor(&self, evidence: &EvidenceRecord) -> Result<ChainTxRef, AnchorError> {
+        let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        self.max_in_flight.fetch_max(now, Ordering::SeqCst);
+        tokio::time::sleep(ANCHOR_DELAY).await;
+        self.in_flight.fetch_sub(1, Ordering::SeqCst);
+        self.anchored.lock().unwrap().push(evidence.id.clone());
+
+        Ok(ChainTxRef {
+            network: "mocknet".to_string(),
+            chain: "mockchain".to_string(),
+            tx_id: format!("mocktx-{}", evidence.id),
+            confirmed: false,
+            timestamp: Some(Utc::now()),
+        })
+    }
+
+    async fn confirm(&self, tx: &ChainTxRef) -> Result<ChainTxRef, AnchorError> {
+        Ok(tx.clone())
+    }
+}
+
+async fn count_done(pool: &Pool<Sqlite>) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM outbox_jobs WHERE status = 'done'")
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_concurrent_loop_anchors_jobs_in_parallel() {
+    // File-backed so concurrent workers get SQLite's busy timeout
+    let temp_db = NamedTempFile::new().unwrap();
+    let pool = SqlitePoolOptions::new()
+        .max_connections(CONCURRENCY as u32 + 1)
+        .connect(&format!("sqlite://{}", temp_db.path().display()))
+        .await
+        .unwrap();
+    ensure_schema(&pool).await.unwrap();
+
+    let now = Utc::now().timestamp_millis();
+    for i in 0..JOBS {
+        sqlx::query(
+            "INSERT INTO outbox_jobs (id, payload_sha256, status, attempts, created_ms, updated_ms, next_attempt_ms)
+             VALUES (?1, 'abcd1234', 'queued', 0, ?2, ?2, 0)",
+        )
+        .bind(format!("job-{:02}", i))
+        .bind(now + i as i64)
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+
+    let anchor = Arc::new(SlowAnchor::default());
+    let started = Instant::now();
+    let runner = tokio::spawn(run_job_loop_concurrent(
+        SqliteJobProvider::new(pool.clone()),
+        Arc::new(vec![Box::new(anchor.clone()) as BoxedAnchor]),
+        Duration::from_millis(10),
+        CONCURRENCY,
+        watch::channel(false).1,
+    ));
+
+    while count_done(&pool).await < JOBS as i64 {
+        assert!(
+            started.elapsed() < Duration::from_secs(10),
+            "jobs not drained in time"
+        );
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    let elapsed = started.elapsed();
+    runner.abort();
+
+    // 20 jobs / 5 workers = 4 rounds of 100ms; sequential would take 2s
+    let ideal = ANCHOR_DELAY * (JOBS / CONCURRENCY) as u32;
+    assert!(
+        elapsed >= ideal,
+        "finished in {:?}, faster than {:?}",
+        elapsed,
+        ideal
+    );
+    assert!(
+        elapsed < ideal * 2 + Duration::from_millis(300),
+        "took {:?}, expected roughly {:?}",
+        elapsed,
+        ideal
+    );
+    assert_eq!(anchor.max_in_flight.load(Ordering::SeqCst), CONCURRENCY);
+
+    // Every job anchored exactly once
+    let mut anchored = anchor.anchored.lock().unwrap().clone();
+    anchored.sort();
+    anchored.dedup();
+    assert_eq!(anchored.len(), JOBS);
+    assert_eq!(anchor.anchored.lock().unwrap().len(), JOBS);
+}
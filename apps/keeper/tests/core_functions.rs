@@ -2,13 +2,16 @@ use chrono::Utc;
 use phoenix_evidence::anchor::{AnchorError, AnchorProvider};
 use phoenix_evidence::model::{ChainTxRef, EvidenceRecord};
 use phoenix_keeper::{
-    run_confirmation_loop, run_job_loop, EvidenceJob, JobError, JobProvider, JobProviderExt,
-    SqliteJobProvider,
+    backoff_ms, ensure_schema, fetch_dead_letters, fetch_unconfirmed_tx_refs, reclaim_stale_jobs,
+    run_confirmation_loop, run_job_loop, BackoffOutcome, BoxedAnchor, EvidenceJob, JobError,
+    JobProvider, JobProviderExt, SqliteJobProvider, DEAD_LETTER_STATUS, MAX_RECLAIMS,
 };
 use serial_test::serial;
+use std::sync::atomic::{AtomicI64, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use tempfile::NamedTempFile;
+use tokio::sync::watch;
 use tokio::time::timeout;
 
 // Mock implementations for testing
@@ -71,14 +74,27 @@ impl JobProviderExt for MockJobProvider {
         Ok(())
     }
 
-    async fn mark_failed_or_backoff(
+    async fn mark_txs_and_done(
         &mut self,
         _id: &str,
-        _reason: &str,
-        _temporary: bool,
+        _txs: &[ChainTxRef],
+        _partial_failure: Option<&str>,
     ) -> Result<(), JobError> {
         Ok(())
     }
+
+    async fn mark_failed_or_backoff(
+        &mut self,
+        _id: &str,
+        _reason: &str,
+        temporary: bool,
+    ) -> Result<BackoffOutcome, JobError> {
+        Ok(if temporary {
+            BackoffOutcome::Requeued
+        } else {
+            BackoffOutcome::Failed
+        })
+    }
 }
 
 #[derive(Clone)]
@@ -150,7 +166,12 @@ async fn test_run_job_loop_success() {
     // Run for a short duration to test one iteration
     let result = timeout(
         Duration::from_millis(100),
-        run_job_loop(&mut provider, &anchor, Duration::from_millis(10)),
+        run_job_loop(
+            &mut provider,
+            &[Box::new(anchor) as BoxedAnchor],
+            Duration::from_millis(10),
+            watch::channel(false).1,
+        ),
     )
     .await;
 
@@ -179,7 +200,12 @@ async fn test_run_job_loop_anchor_failure() {
     // Run for a short duration
     let result = timeout(
         Duration::from_millis(100),
-        run_job_loop(&mut provider, &anchor, Duration::from_millis(10)),
+        run_job_loop(
+            &mut provider,
+            &[Box::new(anchor) as BoxedAnchor],
+            Duration::from_millis(10),
+            watch::channel(false).1,
+        ),
     )
     .await;
 
@@ -201,7 +227,12 @@ async fn test_run_job_loop_provider_failure() {
     // Run for a short duration
     let result = timeout(
         Duration::from_millis(100),
-        run_job_loop(&mut provider, &anchor, Duration::from_millis(10)),
+        run_job_loop(
+            &mut provider,
+            &[Box::new(anchor) as BoxedAnchor],
+            Duration::from_millis(10),
+            watch::channel(false).1,
+        ),
     )
     .await;
 
@@ -262,7 +293,14 @@ async fn test_run_confirmation_loop_success() {
     // Run confirmation loop for a short duration
     let result = timeout(
         Duration::from_millis(100),
-        run_confirmation_loop(&pool, &anchor, Duration::from_millis(10)),
+        run_confirmation_loop(
+            &pool,
+            &[Box::new(anchor) as BoxedAnchor],
+            Duration::from_millis(10),
+            Duration::from_secs(600),
+            100,
+            watch::channel(false).1,
+        ),
     )
     .await;
 
@@ -324,7 +362,14 @@ async fn test_run_confirmation_loop_anchor_failure() {
     // Run confirmation loop for a short duration
     let result = timeout(
         Duration::from_millis(100),
-        run_confirmation_loop(&pool, &anchor, Duration::from_millis(10)),
+        run_confirmation_loop(
+            &pool,
+            &[Box::new(anchor) as BoxedAnchor],
+            Duration::from_millis(10),
+            Duration::from_secs(600),
+            100,
+            watch::channel(false).1,
+        ),
     )
     .await;
 
@@ -360,7 +405,8 @@ async fn test_sqlite_job_provider() {
             last_error TEXT,
             created_ms INTEGER NOT NULL,
             updated_ms INTEGER NOT NULL,
-            next_attempt_ms INTEGER NOT NULL DEFAULT 0
+            next_attempt_ms INTEGER NOT NULL DEFAULT 0,
+            priority INTEGER NOT NULL DEFAULT 0
         )",
     )
     .execute(&pool)
@@ -438,6 +484,357 @@ async fn test_sqlite_job_provider() {
         .unwrap();
 }
 
+#[test]
+fn test_backoff_ms_doubles_up_to_cap() {
+    assert_eq!(backoff_ms(0), 5_000);
+    assert_eq!(backoff_ms(1), 10_000);
+    assert_eq!(backoff_ms(3), 40_000);
+    assert_eq!(backoff_ms(6), 300_000);
+    assert_eq!(backoff_ms(50), 300_000);
+    assert_eq!(backoff_ms(-1), 5_000);
+}
+
+/// In-memory pool with one queued job `backoff-job` created at `now`
+async fn pool_with_queued_job(now: i64) -> sqlx::SqlitePool {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    ensure_schema(&pool).await.unwrap();
+    sqlx::query(
+        "INSERT INTO outbox_jobs (id, payload_sha256, status, attempts, created_ms, updated_ms, next_attempt_ms)
+         VALUES ('backoff-job', 'abcd1234', 'queued', 0, ?1, ?1, 0)",
+    )
+    .bind(now)
+    .execute(&pool)
+    .await
+    .unwrap();
+    pool
+}
+
+async fn next_attempt_ms(pool: &sqlx::SqlitePool) -> i64 {
+    sqlx::query_scalar("SELECT next_attempt_ms FROM outbox_jobs WHERE id='backoff-job'")
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_backoff_schedule_doubles_per_attempt_up_to_cap() {
+    let start = 1_700_000_000_000;
+    let pool = pool_with_queued_job(start).await;
+    let clock = Arc::new(AtomicI64::new(start));
+    let mut provider = SqliteJobProvider::new(pool.clone())
+        .with_max_attempts(20)
+        .with_clock({
+            let clock = clock.clone();
+            move || clock.load(Ordering::SeqCst)
+        })
+        .with_jitter(|| 250);
+
+    let mut delays = Vec::new();
+    for _ in 0..8 {
+        // Not runnable a millisecond early, runnable exactly on time
+        let now = clock.load(Ordering::SeqCst);
+        provider.fetch_next().await.unwrap().unwrap();
+        provider
+            .mark_failed_or_backoff("backoff-job", "rpc timeout", true)
+            .await
+            .unwrap();
+        let next = next_attempt_ms(&pool).await;
+        delays.push(next - now);
+
+        clock.store(next - 1, Ordering::SeqCst);
+        assert!(provider.fetch_next().await.unwrap().is_none());
+        clock.store(next, Ordering::SeqCst);
+    }
+
+    assert_eq!(
+        delays,
+        [10_000, 20_000, 40_000, 80_000, 160_000, 300_000, 300_000, 300_000]
+            .map(|backoff| backoff + 250)
+    );
+}
+
+#[tokio::test]
+async fn test_backoff_capped_at_high_attempt_counts() {
+    let now = 1_700_000_000_000;
+    let pool = pool_with_queued_job(now).await;
+    sqlx::query("UPDATE outbox_jobs SET status='in_progress', attempts=62 WHERE id='backoff-job'")
+        .execute(&pool)
+        .await
+        .unwrap();
+    let mut provider = SqliteJobProvider::new(pool.clone())
+        .with_max_attempts(100)
+        .with_clock(move || now)
+        .with_jitter(|| 999);
+
+    provider
+        .mark_failed_or_backoff("backoff-job", "rpc timeout", true)
+        .await
+        .unwrap();
+
+    assert_eq!(next_attempt_ms(&pool).await, now + 300_000 + 999);
+}
+
+#[tokio::test]
+async fn test_job_dead_lettered_after_max_attempts() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    ensure_schema(&pool).await.unwrap();
+
+    let now = Utc::now().timestamp_millis();
+    sqlx::query(
+        "INSERT INTO outbox_jobs (id, payload_sha256, status, attempts, created_ms, updated_ms, next_attempt_ms)
+         VALUES ('dl-job', 'abcd1234', 'queued', 0, ?1, ?1, 0)",
+    )
+    .bind(now)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let mut provider = SqliteJobProvider::new(pool.clone()).with_max_attempts(2);
+
+    // First failure backs off and requeues
+    provider.fetch_next().await.unwrap().unwrap();
+    let outcome = provider
+        .mark_failed_or_backoff("dl-job", "rpc timeout", true)
+        .await
+        .unwrap();
+    assert_eq!(outcome, BackoffOutcome::Requeued);
+    let (status, next_attempt_ms): (String, i64) =
+        sqlx::query_as("SELECT status, next_attempt_ms FROM outbox_jobs WHERE id='dl-job'")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(status, "queued");
+    assert!(next_attempt_ms >= now + backoff_ms(1));
+    assert!(fetch_dead_letters(&pool, 10).await.unwrap().is_empty());
+
+    // Second failure exhausts the cap
+    sqlx::query("UPDATE outbox_jobs SET next_attempt_ms=0 WHERE id='dl-job'")
+        .execute(&pool)
+        .await
+        .unwrap();
+    provider.fetch_next().await.unwrap().unwrap();
+    let outcome = provider
+        .mark_failed_or_backoff("dl-job", "rpc timeout again", true)
+        .await
+        .unwrap();
+    assert_eq!(outcome, BackoffOutcome::DeadLettered);
+
+    let status: String = sqlx::query_scalar("SELECT status FROM outbox_jobs WHERE id='dl-job'")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(status, DEAD_LETTER_STATUS);
+    assert!(provider.fetch_next().await.unwrap().is_none());
+
+    let dead = fetch_dead_letters(&pool, 10).await.unwrap();
+    assert_eq!(dead.len(), 1);
+    assert_eq!(dead[0].id, "dl-job");
+    assert_eq!(dead[0].attempts, 2);
+    assert_eq!(dead[0].last_error.as_deref(), Some("rpc timeout again"));
+}
+
+#[tokio::test]
+async fn test_fetch_next_prefers_higher_priority() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    ensure_schema(&pool).await.unwrap();
+
+    // The routine job is older, but the high-priority one must go first
+    let now = Utc::now().timestamp_millis();
+    for (id, priority, created_ms) in [("routine", 0, now - 1_000), ("legal", 5, now)] {
+        sqlx::query(
+            "INSERT INTO outbox_jobs (id, payload_sha256, status, attempts, created_ms, updated_ms, next_attempt_ms, priority)
+             VALUES (?1, 'abcd1234', 'queued', 0, ?2, ?2, 0, ?3)",
+        )
+        .bind(id)
+        .bind(created_ms)
+        .bind(priority)
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+
+    let mut provider = SqliteJobProvider::new(pool);
+    assert_eq!(provider.fetch_next().await.unwrap().unwrap().id, "legal");
+    assert_eq!(provider.fetch_next().await.unwrap().unwrap().id, "routine");
+    assert!(provider.fetch_next().await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_reclaim_stale_jobs_requeues_abandoned_claims() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    ensure_schema(&pool).await.unwrap();
+
+    // "stranded" was claimed an hour ago by a keeper that never finished it
+    let now = Utc::now().timestamp_millis();
+    for (id, status, updated_ms) in [
+        ("stranded", "in_progress", now - 3_600_000),
+        ("working", "in_progress", now),
+        ("waiting", "queued", now - 3_600_000),
+    ] {
+        sqlx::query(
+            "INSERT INTO outbox_jobs (id, payload_sha256, status, attempts, created_ms, updated_ms, next_attempt_ms)
+             VALUES (?1, 'abcd1234', ?2, 1, ?3, ?3, 0)",
+        )
+        .bind(id)
+        .bind(status)
+        .bind(updated_ms)
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+
+    let reclaimed = reclaim_stale_jobs(&pool, Duration::from_secs(600))
+        .await
+        .unwrap();
+    assert_eq!(reclaimed, 1);
+
+    let rows: Vec<(String, String, i64)> =
+        sqlx::query_as("SELECT id, status, reclaim_count FROM outbox_jobs ORDER BY id")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert_eq!(
+        rows,
+        vec![
+            ("stranded".to_string(), "queued".to_string(), 1),
+            ("waiting".to_string(), "queued".to_string(), 0),
+            ("working".to_string(), "in_progress".to_string(), 0),
+        ]
+    );
+
+    // The reclaimed job is claimable again; the live claim is left alone
+    let mut provider = SqliteJobProvider::new(pool);
+    let mut claimed = vec![
+        provider.fetch_next().await.unwrap().unwrap().id,
+        provider.fetch_next().await.unwrap().unwrap().id,
+    ];
+    claimed.sort();
+    assert_eq!(claimed, vec!["stranded", "waiting"]);
+    assert!(provider.fetch_next().await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_reclaim_stale_jobs_fails_jobs_past_the_reclaim_cap() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    ensure_schema(&pool).await.unwrap();
+
+    let stale = Utc::now().timestamp_millis() - 3_600_000;
+    for (id, reclaim_count) in [("poison", MAX_RECLAIMS), ("unlucky", MAX_RECLAIMS - 1)] {
+        sqlx::query(
+            "INSERT INTO outbox_jobs (id, payload_sha256, status, attempts, created_ms, updated_ms, next_attempt_ms, reclaim_count)
+             VALUES (?1, 'abcd1234', 'in_progress', 1, ?2, ?2, 0, ?3)",
+        )
+        .bind(id)
+        .bind(stale)
+        .bind(reclaim_count)
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+
+    let reclaimed = reclaim_stale_jobs(&pool, Duration::from_secs(600))
+        .await
+        .unwrap();
+    assert_eq!(reclaimed, 1);
+
+    let rows: Vec<(String, String, i64, Option<String>)> =
+        sqlx::query_as("SELECT id, status, reclaim_count, last_error FROM outbox_jobs ORDER BY id")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert_eq!(rows[0].0, "poison");
+    assert_eq!(rows[0].1, "failed");
+    assert_eq!(rows[0].2, MAX_RECLAIMS);
+    assert!(rows[0].3.as_deref().unwrap().contains("reclaims"));
+    assert_eq!(rows[1].0, "unlucky");
+    assert_eq!(rows[1].1, "queued");
+    assert_eq!(rows[1].2, MAX_RECLAIMS);
+}
+
+#[tokio::test]
+async fn test_ensure_schema_propagates_migration_errors() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    ensure_schema(&pool).await.unwrap();
+    // Re-running over existing columns is fine
+    ensure_schema(&pool).await.unwrap();
+
+    // Columns can't be added to a view, and that failure must surface
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    sqlx::query("CREATE VIEW outbox_jobs AS SELECT 1 AS id")
+        .execute(&pool)
+        .await
+        .unwrap();
+    let error = ensure_schema(&pool).await.unwrap_err();
+    assert!(!error.to_string().contains("duplicate column name"));
+}
+
+#[tokio::test]
+async fn test_fetch_unconfirmed_tx_refs_returns_oldest_batch() {
+    let pool = sqlx::sqlite::SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    ensure_schema(&pool).await.unwrap();
+
+    // 250 unconfirmed refs inserted newest first, plus one confirmed one
+    let now = Utc::now().timestamp();
+    for i in 0..250 {
+        sqlx::query(
+            "INSERT INTO outbox_tx_refs (job_id, network, chain, tx_id, confirmed, timestamp)
+             VALUES (?1, 'solana', 'devnet', ?2, 0, ?3)",
+        )
+        .bind(format!("job-{:03}", i))
+        .bind(format!("sig-{:03}", i))
+        .bind(now - i)
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+    sqlx::query(
+        "INSERT INTO outbox_tx_refs (job_id, network, chain, tx_id, confirmed, timestamp)
+         VALUES ('done', 'solana', 'devnet', 'sig-done', 1, ?1)",
+    )
+    .bind(now - 10_000)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let batch = fetch_unconfirmed_tx_refs(&pool, 100).await.unwrap();
+    assert_eq!(batch.len(), 100);
+    assert!(batch.iter().all(|(_, tx)| !tx.confirmed));
+    assert_eq!(batch[0].0, "job-249");
+    assert_eq!(batch[99].0, "job-150");
+}
+
 #[test]
 fn test_job_error_from_sqlx() {
     let sqlx_err = sqlx::Error::PoolClosed;
@@ -3,7 +3,8 @@ use async_trait::async_trait;
 use phoenix_evidence::anchor::{AnchorError, AnchorProvider};
 use phoenix_evidence::model::{ChainTxRef, EvidenceRecord};
 use phoenix_keeper::{
-    ensure_schema, EvidenceJob, JobError, JobProvider, JobProviderExt, SqliteJobProvider,
+    ensure_schema, BackoffOutcome, EvidenceJob, JobError, JobProvider, JobProviderExt,
+    SqliteJobProvider,
 };
 use sqlx::{sqlite::SqlitePoolOptions, Row};
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -206,12 +207,29 @@ impl JobProviderExt for TestSqliteJobProvider {
         Ok(())
     }
 
+    async fn mark_txs_and_done(
+        &mut self,
+        id: &str,
+        txs: &[ChainTxRef],
+        partial_failure: Option<&str>,
+    ) -> Result<(), JobError> {
+        for tx_ref in txs {
+            self.mark_tx_and_done(id, tx_ref).await?;
+        }
+        sqlx::query("UPDATE outbox_jobs SET last_error=?1 WHERE id=?2")
+            .bind(partial_failure)
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
     async fn mark_failed_or_backoff(
         &mut self,
         id: &str,
         reason: &str,
         temporary: bool,
-    ) -> Result<(), JobError> {
+    ) -> Result<BackoffOutcome, JobError> {
         let now_ms = chrono::Utc::now().timestamp_millis();
         if temporary {
             // Use a much shorter backoff for testing (100ms instead of 5s)
@@ -225,7 +243,7 @@ impl JobProviderExt for TestSqliteJobProvider {
             .bind(id)
             .execute(&self.pool)
             .await?;
-            return Ok(());
+            return Ok(BackoffOutcome::Requeued);
         }
         sqlx::query(
             "UPDATE outbox_jobs SET status='failed', last_error=?1, updated_ms=?2, next_attempt_ms=?2 WHERE id=?3",
@@ -235,7 +253,7 @@ impl JobProviderExt for TestSqliteJobProvider {
         .bind(id)
         .execute(&self.pool)
         .await?;
-        Ok(())
+        Ok(BackoffOutcome::Failed)
     }
 }
 
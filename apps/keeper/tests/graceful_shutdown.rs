@@ -0,0 +1,195 @@
+//! Graceful shutdown: loops stop promptly and release claimed jobs
+
+use chrono::Utc;
+use phoenix_evidence::{
+    anchor::{AnchorError, AnchorProvider},
+    model::{ChainTxRef, EvidenceRecord},
+};
+use phoenix_keeper::{
+    ensure_schema, run_confirmation_loop, run_job_loop, run_job_loop_concurrent, BoxedAnchor,
+    SqliteJobProvider,
+};
+use sqlx::{sqlite::SqlitePoolOptions, Pool, Sqlite};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tempfile::NamedTempFile;
+use tokio::sync::watch;
+use tokio::time::timeout;
+
+/// Upper bound for a loop to return once shutdown is signalled
+const STOP_WITHIN: Duration = Duration::from_secs(1);
+
+/// Anchor that never answers, so a claimed job stays in flight
+#[derive(Default)]
+struct HangingAnchor {
+    started: AtomicUsize,
+}
+
+#[async_trait::async_trait]
+impl AnchorProvider for HangingAnchor {
+    async fn anchor(&self, _evidence: &EvidenceRecord) -> Result<ChainTxRef, AnchorError> {
+        self.started.fetch_add(1, Ordering::SeqCst);
+        std::future::pending().await
+    }
+
+    async fn confirm(&self, tx: &ChainTxRef) -> Result<ChainTxRef, AnchorError> {
+        Ok(tx.clone())
+    }
+}
+
+async fn file_pool(temp_db: &NamedTempFile, jobs: usize) -> Pool<Sqlite> {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(4)
+        .connect(&format!("sqlite://{}", temp_db.path().display()))
+        .await
+        .unwrap();
+    ensure_schema(&pool).await.unwrap();
+    let now = Utc::now().timestamp_millis();
+    for i in 0..jobs {
+        sqlx::query(
+            "INSERT INTO outbox_jobs (id, payload_sha256, status, attempts, created_ms, updated_ms, next_attempt_ms)
+             VALUES (?1, 'abcd1234', 'queued', 0, ?2, ?2, 0)",
+        )
+        .bind(format!("job-{}", i))
+        .bind(now + i as i64)
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+    pool
+}
+
+async fn wait_until_started(anchor: &HangingAnchor, n: usize) {
+    timeout(STOP_WITHIN, async {
+        while anchor.started.load(Ordering::SeqCst) < n {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+    })
+    .await
+    .expect("jobs were not claimed in time");
+}
+
+async fn job_states(pool: &Pool<Sqlite>) -> Vec<(String, i64)> {
+    sqlx::query_as("SELECT status, attempts FROM outbox_jobs ORDER BY id")
+        .fetch_all(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_idle_job_loop_stops_on_shutdown() {
+    let temp_db = NamedTempFile::new().unwrap();
+    let pool = file_pool(&temp_db, 0).await;
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+
+    let runner = tokio::spawn(async move {
+        let mut provider = SqliteJobProvider::new(pool);
+        // Poll far longer than the test waits: shutdown must cut the sleep short
+        run_job_loop(
+            &mut provider,
+            &[Box::new(HangingAnchor::default()) as BoxedAnchor],
+            Duration::from_secs(60),
+            shutdown_rx,
+        )
+        .await;
+    });
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    shutdown_tx.send(true).unwrap();
+    timeout(STOP_WITHIN, runner)
+        .await
+        .expect("job loop did not stop in time")
+        .unwrap();
+}
+
+#[tokio::test]
+async fn test_shutdown_releases_in_flight_job() {
+    let temp_db = NamedTempFile::new().unwrap();
+    let pool = file_pool(&temp_db, 2).await;
+    let anchor = Arc::new(HangingAnchor::default());
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+
+    let loop_pool = pool.clone();
+    let loop_anchor = anchor.clone();
+    let runner = tokio::spawn(async move {
+        let mut provider = SqliteJobProvider::new(loop_pool);
+        run_job_loop(
+            &mut provider,
+            &[Box::new(loop_anchor) as BoxedAnchor],
+            Duration::from_millis(10),
+            shutdown_rx,
+        )
+        .await;
+    });
+    wait_until_started(&anchor, 1).await;
+    assert_eq!(job_states(&pool).await[0], ("in_progress".to_string(), 1));
+
+    shutdown_tx.send(true).unwrap();
+    timeout(STOP_WITHIN, runner)
+        .await
+        .expect("job loop did not stop in time")
+        .unwrap();
+
+    // The abandoned claim is back in the queue and does not count as an attempt
+    assert_eq!(
+        job_states(&pool).await,
+        vec![("queued".to_string(), 0), ("queued".to_string(), 0)]
+    );
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_shutdown_releases_concurrent_workers() {
+    let temp_db = NamedTempFile::new().unwrap();
+    let pool = file_pool(&temp_db, 5).await;
+    let anchor = Arc::new(HangingAnchor::default());
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+
+    let runner = tokio::spawn(run_job_loop_concurrent(
+        SqliteJobProvider::new(pool.clone()),
+        Arc::new(vec![Box::new(anchor.clone()) as BoxedAnchor]),
+        Duration::from_millis(10),
+        3,
+        shutdown_rx,
+    ));
+    wait_until_started(&anchor, 3).await;
+
+    shutdown_tx.send(true).unwrap();
+    timeout(STOP_WITHIN, runner)
+        .await
+        .expect("concurrent job loop did not stop in time")
+        .unwrap();
+
+    let states = job_states(&pool).await;
+    assert!(
+        states.iter().all(|s| *s == ("queued".to_string(), 0)),
+        "all claims released: {:?}",
+        states
+    );
+}
+
+#[tokio::test]
+async fn test_confirmation_loop_stops_on_shutdown() {
+    let temp_db = NamedTempFile::new().unwrap();
+    let pool = file_pool(&temp_db, 0).await;
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+
+    let runner = tokio::spawn(async move {
+        run_confirmation_loop(
+            &pool,
+            &[Box::new(HangingAnchor::default()) as BoxedAnchor],
+            Duration::from_secs(60),
+            Duration::from_secs(600),
+            100,
+            shutdown_rx,
+        )
+        .await;
+    });
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    shutdown_tx.send(true).unwrap();
+    timeout(STOP_WITHIN, runner)
+        .await
+        .expect("confirmation loop did not stop in time")
+        .unwrap();
+}
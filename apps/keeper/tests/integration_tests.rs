@@ -9,13 +9,15 @@ use phoenix_evidence::{
     model::{ChainTxRef, DigestAlgo, EvidenceDigest, EvidenceRecord},
 };
 use phoenix_keeper::{
-    run_confirmation_loop, run_job_loop, JobProvider, JobProviderExt, SqliteJobProvider,
+    ensure_schema, run_confirmation_loop, run_job_loop, BoxedAnchor, JobProvider, JobProviderExt,
+    SqliteJobProvider,
 };
 use serde_json::json;
 use sqlx::{sqlite::SqlitePoolOptions, Row};
 use std::collections::HashSet;
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
+use tokio::sync::watch;
 
 /// Mock anchor provider for testing
 #[derive(Clone)]
@@ -102,7 +104,8 @@ async fn setup_test_db() -> sqlx::Pool<sqlx::Sqlite> {
             last_error TEXT,
             created_ms INTEGER NOT NULL,
             updated_ms INTEGER NOT NULL,
-            next_attempt_ms INTEGER NOT NULL DEFAULT 0
+            next_attempt_ms INTEGER NOT NULL DEFAULT 0,
+            priority INTEGER NOT NULL DEFAULT 0
         );
         "#,
     )
@@ -307,7 +310,14 @@ async fn test_confirmation_loop() {
     // Run confirmation loop
     let result = tokio::time::timeout(
         Duration::from_millis(100),
-        run_confirmation_loop(&pool, &anchor, Duration::from_millis(10)),
+        run_confirmation_loop(
+            &pool,
+            &[Box::new(anchor) as BoxedAnchor],
+            Duration::from_millis(10),
+            Duration::from_secs(600),
+            100,
+            watch::channel(false).1,
+        ),
     )
     .await;
 
@@ -324,6 +334,259 @@ async fn test_confirmation_loop() {
     assert!(confirmed);
 }
 
+/// Anchor whose chain has no record of any transaction (all were dropped)
+struct DroppedTxAnchor;
+
+#[async_trait::async_trait]
+impl AnchorProvider for DroppedTxAnchor {
+    async fn anchor(&self, _evidence: &EvidenceRecord) -> Result<ChainTxRef, AnchorError> {
+        Err(AnchorError::Invalid("not used".to_string()))
+    }
+
+    async fn confirm(&self, tx: &ChainTxRef) -> Result<ChainTxRef, AnchorError> {
+        Err(AnchorError::NotFound(format!("{} is unknown", tx.tx_id)))
+    }
+}
+
+/// Test that a tx the chain never saw is abandoned and its job re-queued once
+/// it outlives the confirmation timeout
+#[tokio::test]
+async fn test_confirmation_loop_requeues_job_for_dropped_tx() {
+    let pool = setup_test_db().await;
+    ensure_schema(&pool).await.unwrap();
+    let now = Utc::now();
+
+    for (job_id, anchored_at) in [
+        ("dropped-old", now - chrono::Duration::minutes(30)),
+        ("dropped-recent", now),
+    ] {
+        sqlx::query(
+            "INSERT INTO outbox_jobs (id, payload_sha256, status, attempts, created_ms, updated_ms, next_attempt_ms) VALUES (?1, 'hash', 'done', 1, ?2, ?2, 0)"
+        )
+        .bind(job_id)
+        .bind(anchored_at.timestamp_millis())
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO outbox_tx_refs (job_id, network, chain, tx_id, confirmed, timestamp) VALUES (?1, 'mocknet', 'mockchain', ?2, 0, ?3)"
+        )
+        .bind(job_id)
+        .bind(format!("sig-{}", job_id))
+        .bind(anchored_at.timestamp())
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+
+    let result = tokio::time::timeout(
+        Duration::from_millis(100),
+        run_confirmation_loop(
+            &pool,
+            &[Box::new(DroppedTxAnchor) as BoxedAnchor],
+            Duration::from_millis(10),
+            Duration::from_secs(600),
+            100,
+            watch::channel(false).1,
+        ),
+    )
+    .await;
+    assert!(result.is_err()); // timeout is expected
+
+    let status = |job_id: &'static str| {
+        let pool = pool.clone();
+        async move {
+            sqlx::query_scalar::<_, String>("SELECT status FROM outbox_jobs WHERE id = ?1")
+                .bind(job_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap()
+        }
+    };
+    assert_eq!(status("dropped-old").await, "queued");
+    assert_eq!(status("dropped-recent").await, "done");
+
+    let abandoned: Vec<String> =
+        sqlx::query_scalar("SELECT tx_id FROM outbox_abandoned_txs ORDER BY tx_id")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert_eq!(abandoned, vec!["sig-dropped-old".to_string()]);
+
+    let remaining: Vec<String> =
+        sqlx::query_scalar("SELECT tx_id FROM outbox_tx_refs ORDER BY tx_id")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert_eq!(remaining, vec!["sig-dropped-recent".to_string()]);
+}
+
+/// Anchor that reports `rev-*` transactions as reverted and confirms the rest
+struct RevertingAnchor;
+
+#[async_trait::async_trait]
+impl AnchorProvider for RevertingAnchor {
+    async fn anchor(&self, _evidence: &EvidenceRecord) -> Result<ChainTxRef, AnchorError> {
+        Err(AnchorError::Invalid("not used".to_string()))
+    }
+
+    async fn confirm(&self, tx: &ChainTxRef) -> Result<ChainTxRef, AnchorError> {
+        if tx.tx_id.starts_with("rev-") {
+            return Err(AnchorError::Reverted(format!("{} reverted", tx.tx_id)));
+        }
+        let mut confirmed = tx.clone();
+        confirmed.confirmed = true;
+        Ok(confirmed)
+    }
+}
+
+/// Test that a reverted tx is abandoned at once, failing its job unless
+/// another tx still anchors it
+#[tokio::test]
+async fn test_confirmation_loop_fails_job_for_reverted_tx() {
+    let pool = setup_test_db().await;
+    ensure_schema(&pool).await.unwrap();
+    let now = Utc::now();
+
+    for (job_id, tx_ids) in [
+        ("reverted-only", &["rev-only"][..]),
+        ("reverted-multi", &["rev-multi", "ok-multi"][..]),
+    ] {
+        sqlx::query(
+            "INSERT INTO outbox_jobs (id, payload_sha256, status, attempts, created_ms, updated_ms, next_attempt_ms) VALUES (?1, 'hash', 'done', 1, ?2, ?2, 0)"
+        )
+        .bind(job_id)
+        .bind(now.timestamp_millis())
+        .execute(&pool)
+        .await
+        .unwrap();
+        // One tx per chain, as a multi-chain job records them
+        for (chain, tx_id) in tx_ids.iter().enumerate() {
+            sqlx::query(
+                "INSERT INTO outbox_tx_refs (job_id, network, chain, tx_id, confirmed, timestamp) VALUES (?1, 'mocknet', ?2, ?3, 0, ?4)"
+            )
+            .bind(job_id)
+            .bind(format!("chain-{}", chain))
+            .bind(tx_id)
+            .bind(now.timestamp())
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+    }
+
+    let _ = tokio::time::timeout(
+        Duration::from_millis(100),
+        run_confirmation_loop(
+            &pool,
+            &[Box::new(RevertingAnchor) as BoxedAnchor],
+            Duration::from_millis(10),
+            Duration::from_secs(600),
+            100,
+            watch::channel(false).1,
+        ),
+    )
+    .await;
+
+    let job = |job_id: &'static str| {
+        let pool = pool.clone();
+        async move {
+            sqlx::query_as::<_, (String, Option<String>)>(
+                "SELECT status, last_error FROM outbox_jobs WHERE id = ?1",
+            )
+            .bind(job_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap()
+        }
+    };
+    let (status, last_error) = job("reverted-only").await;
+    assert_eq!(status, "failed");
+    assert!(last_error.unwrap().contains("rev-only reverted"));
+    assert_eq!(job("reverted-multi").await.0, "done");
+
+    let abandoned: Vec<String> =
+        sqlx::query_scalar("SELECT tx_id FROM outbox_abandoned_txs ORDER BY tx_id")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+    assert_eq!(abandoned, ["rev-multi", "rev-only"]);
+}
+
+/// Test that with dedupe on, a job repeating a confirmed digest reuses the
+/// existing anchor instead of writing a new one
+#[tokio::test]
+async fn test_dedupe_reuses_confirmed_anchor_for_same_digest() {
+    let pool = setup_test_db().await;
+    let mut provider = SqliteJobProvider::new(pool.clone()).with_dedupe_digests(true);
+    let anchor = MockAnchorProvider::default();
+    let anchors = [Box::new(anchor.clone()) as BoxedAnchor];
+
+    let insert_job = |id: &'static str| {
+        let pool = pool.clone();
+        async move {
+            sqlx::query(
+                "INSERT INTO outbox_jobs (id, payload_sha256, status, attempts, created_ms, updated_ms, next_attempt_ms) VALUES (?1, 'shared-digest', 'queued', 0, ?2, ?2, 0)"
+            )
+            .bind(id)
+            .bind(Utc::now().timestamp_millis())
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+    };
+
+    // First submission is anchored and confirmed
+    insert_job("dup-first").await;
+    let _ = tokio::time::timeout(
+        Duration::from_millis(100),
+        run_job_loop(
+            &mut provider,
+            &anchors,
+            Duration::from_millis(10),
+            watch::channel(false).1,
+        ),
+    )
+    .await;
+    let _ = tokio::time::timeout(
+        Duration::from_millis(100),
+        run_confirmation_loop(
+            &pool,
+            &anchors,
+            Duration::from_millis(10),
+            Duration::from_secs(600),
+            100,
+            watch::channel(false).1,
+        ),
+    )
+    .await;
+    assert_eq!(anchor.get_anchored_count(), 1);
+
+    // The duplicate is marked done with the same, already confirmed, tx
+    insert_job("dup-second").await;
+    let _ = tokio::time::timeout(
+        Duration::from_millis(100),
+        run_job_loop(
+            &mut provider,
+            &anchors,
+            Duration::from_millis(10),
+            watch::channel(false).1,
+        ),
+    )
+    .await;
+    assert_eq!(anchor.get_anchored_count(), 1);
+
+    let (status, tx_id, confirmed): (String, String, bool) = sqlx::query_as(
+        "SELECT j.status, r.tx_id, r.confirmed FROM outbox_jobs j JOIN outbox_tx_refs r ON r.job_id = j.id WHERE j.id = 'dup-second'",
+    )
+    .fetch_one(&pool)
+    .await
+    .unwrap();
+    assert_eq!(status, "done");
+    assert_eq!(tx_id, "mocktx-dup-first");
+    assert!(confirmed);
+}
+
 /// Test job processing with different anchor behaviors
 #[tokio::test]
 async fn test_job_processing_with_different_anchor_behaviors() {
@@ -592,7 +855,12 @@ async fn test_job_processing_with_timeout() {
     // Test job processing with timeout
     let result = tokio::time::timeout(
         Duration::from_millis(100),
-        run_job_loop(&mut provider, &anchor, Duration::from_millis(10)),
+        run_job_loop(
+            &mut provider,
+            &[Box::new(anchor) as BoxedAnchor],
+            Duration::from_millis(10),
+            watch::channel(false).1,
+        ),
     )
     .await;
 
@@ -629,7 +897,12 @@ async fn test_job_processing_with_provider_failures() {
     // Test job processing with provider failure
     let result = tokio::time::timeout(
         Duration::from_millis(100),
-        run_job_loop(&mut provider, &anchor, Duration::from_millis(10)),
+        run_job_loop(
+            &mut provider,
+            &[Box::new(anchor) as BoxedAnchor],
+            Duration::from_millis(10),
+            watch::channel(false).1,
+        ),
     )
     .await;
 
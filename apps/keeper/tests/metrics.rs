@@ -0,0 +1,158 @@
+//! Prometheus `/metrics` reflects job and confirmation loop activity
+
+use axum::body::{to_bytes, Body};
+use axum::http::{Request, StatusCode};
+use chrono::Utc;
+use phoenix_evidence::{
+    anchor::{AnchorError, AnchorProvider},
+    model::{ChainTxRef, EvidenceRecord},
+};
+use phoenix_keeper::{
+    ensure_schema, metrics, run_confirmation_loop, run_job_loop, BoxedAnchor, SqliteJobProvider,
+};
+use sqlx::{sqlite::SqlitePoolOptions, Pool, Sqlite};
+use std::time::Duration;
+use tokio::sync::watch;
+use tower::ServiceExt;
+
+/// Anchors everything except ids starting with "bad", which fail permanently,
+/// and "flaky", which fail temporarily
+struct MockAnchorProvider;
+
+#[async_trait::async_trait]
+impl AnchorProvider for MockAnchorProvider {
+    async fn anchor(&self, evidence: &EvidenceRecord) -> Result<ChainTxRef, AnchorError> {
+        if evidence.id.starts_with("bad") {
+            return Err(AnchorError::Invalid("rejected".to_string()));
+        }
+        if evidence.id.starts_with("flaky") {
+            return Err(AnchorError::Network("rpc timeout".to_string()));
+        }
+        Ok(ChainTxRef {
+            network: "mocknet".to_string(),
+            chain: "mockchain".to_string(),
+            tx_id: format!("mocktx-{}", evidence.id),
+            confirmed: false,
+            timestamp: Some(Utc::now()),
+        })
+    }
+
+    async fn confirm(&self, tx: &ChainTxRef) -> Result<ChainTxRef, AnchorError> {
+        let mut confirmed = tx.clone();
+        confirmed.confirmed = true;
+        Ok(confirmed)
+    }
+}
+
+async fn scrape() -> String {
+    let response = metrics::router()
+        .oneshot(Request::get("/metrics").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    String::from_utf8(body.to_vec()).unwrap()
+}
+
+/// Value of an unlabelled sample line, e.g. `keeper_jobs_processed_total 2`
+fn sample(text: &str, name: &str) -> f64 {
+    text.lines()
+        .find_map(|line| line.strip_prefix(name)?.strip_prefix(' '))
+        .unwrap_or_else(|| panic!("{} missing from scrape", name))
+        .parse()
+        .unwrap()
+}
+
+async fn count(pool: &Pool<Sqlite>, sql: &str) -> i64 {
+    sqlx::query_scalar(sql).fetch_one(pool).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_metrics_endpoint_counts_processed_jobs() {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    ensure_schema(&pool).await.unwrap();
+    let now = Utc::now().timestamp_millis();
+    for id in ["good-1", "good-2", "bad-1", "flaky-1"] {
+        sqlx::query(
+            "INSERT INTO outbox_jobs (id, payload_sha256, status, attempts, created_ms, updated_ms, next_attempt_ms)
+             VALUES (?1, 'abcd1234', 'queued', 0, ?2, ?2, 0)",
+        )
+        .bind(id)
+        .bind(now)
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+
+    let before = scrape().await;
+    assert!(before.contains("# TYPE keeper_jobs_processed_total counter"));
+    assert!(before.contains("# TYPE keeper_anchor_latency_seconds histogram"));
+
+    let job_pool = pool.clone();
+    let jobs = tokio::spawn(async move {
+        // One attempt, so the temporary failure dead-letters instead of retrying
+        let mut provider = SqliteJobProvider::new(job_pool).with_max_attempts(1);
+        run_job_loop(
+            &mut provider,
+            &[Box::new(MockAnchorProvider) as BoxedAnchor],
+            Duration::from_millis(10),
+            watch::channel(false).1,
+        )
+        .await;
+    });
+    let confirm_pool = pool.clone();
+    let confirmations = tokio::spawn(async move {
+        run_confirmation_loop(
+            &confirm_pool,
+            &[Box::new(MockAnchorProvider) as BoxedAnchor],
+            Duration::from_millis(10),
+            Duration::from_secs(600),
+            100,
+            watch::channel(false).1,
+        )
+        .await;
+    });
+
+    for _ in 0..200 {
+        let done = count(
+            &pool,
+            "SELECT COUNT(*) FROM outbox_jobs WHERE status = 'done'",
+        )
+        .await;
+        let failed = count(
+            &pool,
+            "SELECT COUNT(*) FROM outbox_jobs WHERE status = 'failed'",
+        )
+        .await;
+        let confirmed = count(
+            &pool,
+            "SELECT COUNT(*) FROM outbox_tx_refs WHERE confirmed = 1",
+        )
+        .await;
+        let dead = count(
+            &pool,
+            "SELECT COUNT(*) FROM outbox_jobs WHERE status = 'dead_letter'",
+        )
+        .await;
+        if done == 2 && failed == 1 && dead == 1 && confirmed == 2 {
+            break;
+        }
+        tokio::time::sleep(Duration::from_millis(10)).await;
+    }
+    jobs.abort();
+    confirmations.abort();
+
+    let after = scrape().await;
+    let delta = |name: &str| sample(&after, name) - sample(&before, name);
+    assert_eq!(delta("keeper_jobs_processed_total"), 2.0);
+    assert_eq!(delta("keeper_jobs_failed_total"), 1.0);
+    assert_eq!(delta("keeper_job_retries_total"), 0.0);
+    assert_eq!(delta("keeper_jobs_dead_lettered_total"), 1.0);
+    assert_eq!(delta("keeper_confirmations_total"), 2.0);
+    assert_eq!(delta("keeper_anchor_latency_seconds_count"), 4.0);
+    assert!(after.contains("keeper_anchor_latency_seconds_bucket{le=\"+Inf\"}"));
+}
@@ -0,0 +1,270 @@
+//! Multi-chain anchoring: every job goes to every backend
+
+use chrono::Utc;
+use phoenix_evidence::{
+    anchor::{AnchorError, AnchorProvider},
+    model::{ChainTxRef, EvidenceRecord},
+};
+use phoenix_keeper::{
+    ensure_schema, run_confirmation_loop, run_job_loop, BoxedAnchor, SqliteJobProvider,
+};
+use sqlx::{sqlite::SqlitePoolOptions, Pool, Sqlite};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::watch;
+use tokio::time::timeout;
+
+/// Backend on a fixed network that either anchors or fails every call
+struct ChainAnchor {
+    network: &'static str,
+    failure: Option<fn() -> AnchorError>,
+    confirms: AtomicUsize,
+    /// Size of each `confirm_many` batch
+    batches: Mutex<Vec<usize>>,
+}
+
+impl ChainAnchor {
+    fn ok(network: &'static str) -> Arc<Self> {
+        Arc::new(Self {
+            network,
+            failure: None,
+            confirms: AtomicUsize::new(0),
+            batches: Mutex::new(Vec::new()),
+        })
+    }
+
+    fn failing(network: &'static str, failure: fn() -> AnchorError) -> Arc<Self> {
+        Arc::new(Self {
+            network,
+            failure: Some(failure),
+            confirms: AtomicUsize::new(0),
+            batches: Mutex::new(Vec::new()),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl AnchorProvider for ChainAnchor {
+    async fn anchor(&self, evidence: &EvidenceRecord) -> Result<ChainTxRef, AnchorError> {
+        if let Some(failure) = self.failure {
+            return Err(failure());
+        }
+        Ok(ChainTxRef {
+            network: self.network.to_string(),
+            chain: "testnet".to_string(),
+            tx_id: format!("{}-{}", self.network, evidence.id),
+            confirmed: false,
+            timestamp: Some(Utc::now()),
+        })
+    }
+
+    async fn confirm(&self, tx: &ChainTxRef) -> Result<ChainTxRef, AnchorError> {
+        assert_eq!(
+            tx.network, self.network,
+            "confirmation routed to wrong backend"
+        );
+        self.confirms.fetch_add(1, Ordering::SeqCst);
+        let mut confirmed = tx.clone();
+        confirmed.confirmed = true;
+        Ok(confirmed)
+    }
+
+    async fn confirm_many(&self, txs: &[ChainTxRef]) -> Vec<Result<ChainTxRef, AnchorError>> {
+        self.batches.lock().unwrap().push(txs.len());
+        let mut results = Vec::new();
+        for tx in txs {
+            results.push(self.confirm(tx).await);
+        }
+        results
+    }
+
+    fn network(&self) -> Option<&str> {
+        Some(self.network)
+    }
+}
+
+async fn setup_pool() -> Pool<Sqlite> {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(1)
+        .connect("sqlite::memory:")
+        .await
+        .unwrap();
+    ensure_schema(&pool).await.unwrap();
+    let now = Utc::now().timestamp_millis();
+    sqlx::query(
+        "INSERT INTO outbox_jobs (id, payload_sha256, status, attempts, created_ms, updated_ms, next_attempt_ms)
+         VALUES ('job-1', 'abcd1234', 'queued', 0, ?1, ?1, 0)",
+    )
+    .bind(now)
+    .execute(&pool)
+    .await
+    .unwrap();
+    pool
+}
+
+/// Run the job loop briefly, long enough to process the queued job
+async fn run_jobs(pool: &Pool<Sqlite>, anchors: &[BoxedAnchor]) {
+    let mut provider = SqliteJobProvider::new(pool.clone());
+    let _ = timeout(
+        Duration::from_millis(100),
+        run_job_loop(
+            &mut provider,
+            anchors,
+            Duration::from_millis(10),
+            watch::channel(false).1,
+        ),
+    )
+    .await;
+}
+
+async fn job_row(pool: &Pool<Sqlite>) -> (String, Option<String>) {
+    sqlx::query_as("SELECT status, last_error FROM outbox_jobs WHERE id = 'job-1'")
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+async fn tx_networks(pool: &Pool<Sqlite>) -> Vec<String> {
+    sqlx::query_scalar("SELECT network FROM outbox_tx_refs WHERE job_id = 'job-1' ORDER BY network")
+        .fetch_all(pool)
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_partial_success_stores_one_tx_ref_and_marks_done() {
+    let pool = setup_pool().await;
+    let anchors: Vec<BoxedAnchor> = vec![
+        Box::new(ChainAnchor::failing("etherlink", || {
+            AnchorError::Network("rpc unreachable".to_string())
+        })),
+        Box::new(ChainAnchor::ok("solana")),
+    ];
+
+    run_jobs(&pool, &anchors).await;
+
+    assert_eq!(tx_networks(&pool).await, vec!["solana"]);
+    let (status, last_error) = job_row(&pool).await;
+    assert_eq!(status, "done");
+    let last_error = last_error.expect("partial failure recorded");
+    assert!(last_error.contains("etherlink"), "{}", last_error);
+    assert!(last_error.contains("rpc unreachable"), "{}", last_error);
+}
+
+#[tokio::test]
+async fn test_every_backend_stores_its_tx_ref() {
+    let pool = setup_pool().await;
+    let anchors: Vec<BoxedAnchor> = vec![
+        Box::new(ChainAnchor::ok("etherlink")),
+        Box::new(ChainAnchor::ok("solana")),
+    ];
+
+    run_jobs(&pool, &anchors).await;
+
+    assert_eq!(tx_networks(&pool).await, vec!["etherlink", "solana"]);
+    assert_eq!(job_row(&pool).await, ("done".to_string(), None));
+}
+
+#[tokio::test]
+async fn test_all_backends_failing_retries_if_any_failure_is_temporary() {
+    let pool = setup_pool().await;
+    let anchors: Vec<BoxedAnchor> = vec![
+        Box::new(ChainAnchor::failing("etherlink", || {
+            AnchorError::Invalid("rejected".to_string())
+        })),
+        Box::new(ChainAnchor::failing("solana", || {
+            AnchorError::Network("rpc unreachable".to_string())
+        })),
+    ];
+
+    run_jobs(&pool, &anchors).await;
+
+    assert!(tx_networks(&pool).await.is_empty());
+    let (status, last_error) = job_row(&pool).await;
+    assert_eq!(status, "queued", "backed off for retry");
+    let last_error = last_error.unwrap();
+    assert!(last_error.contains("etherlink: invalid state: rejected"));
+    assert!(last_error.contains("solana: network error: rpc unreachable"));
+}
+
+#[tokio::test]
+async fn test_confirmations_routed_by_network() {
+    let pool = setup_pool().await;
+    let etherlink = ChainAnchor::ok("etherlink");
+    let solana = ChainAnchor::ok("solana");
+    let anchors: Vec<BoxedAnchor> = vec![Box::new(etherlink.clone()), Box::new(solana.clone())];
+
+    run_jobs(&pool, &anchors).await;
+    let _ = timeout(
+        Duration::from_millis(100),
+        run_confirmation_loop(
+            &pool,
+            &anchors,
+            Duration::from_millis(10),
+            Duration::from_secs(600),
+            100,
+            watch::channel(false).1,
+        ),
+    )
+    .await;
+
+    assert_eq!(etherlink.confirms.load(Ordering::SeqCst), 1);
+    assert_eq!(solana.confirms.load(Ordering::SeqCst), 1);
+    let unconfirmed: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM outbox_tx_refs WHERE confirmed = 0")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(unconfirmed, 0);
+}
+
+#[tokio::test]
+async fn test_confirmations_batched_per_backend() {
+    let pool = setup_pool().await;
+    let now = Utc::now().timestamp();
+    for (job_id, network, tx_id) in [
+        ("job-1", "solana", "sol-1"),
+        ("job-1", "etherlink", "eth-1"),
+        ("job-2", "solana", "sol-2"),
+        ("job-3", "solana", "sol-3"),
+    ] {
+        sqlx::query(
+            "INSERT INTO outbox_tx_refs (job_id, network, chain, tx_id, confirmed, timestamp)
+             VALUES (?1, ?2, 'testnet', ?3, 0, ?4)",
+        )
+        .bind(job_id)
+        .bind(network)
+        .bind(tx_id)
+        .bind(now)
+        .execute(&pool)
+        .await
+        .unwrap();
+    }
+    let etherlink = ChainAnchor::ok("etherlink");
+    let solana = ChainAnchor::ok("solana");
+    let anchors: Vec<BoxedAnchor> = vec![Box::new(etherlink.clone()), Box::new(solana.clone())];
+
+    let _ = timeout(
+        Duration::from_millis(100),
+        run_confirmation_loop(
+            &pool,
+            &anchors,
+            Duration::from_millis(10),
+            Duration::from_secs(600),
+            100,
+            watch::channel(false).1,
+        ),
+    )
+    .await;
+
+    // One call per backend covering all of its txs; nothing left after that
+    assert_eq!(*solana.batches.lock().unwrap(), [3]);
+    assert_eq!(*etherlink.batches.lock().unwrap(), [1]);
+    let unconfirmed: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM outbox_tx_refs WHERE confirmed = 0")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+    assert_eq!(unconfirmed, 0);
+}
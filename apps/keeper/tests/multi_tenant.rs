@@ -0,0 +1,298 @@
+//! Multi-tenant keeper: each tenant database is processed independently
+
+use chrono::Utc;
+use phoenix_evidence::{
+    anchor::{AnchorError, AnchorProvider},
+    model::{ChainTxRef, EvidenceRecord},
+};
+use phoenix_keeper::batch_anchor::BatchAnchor;
+use phoenix_keeper::tenants::{run_tenant, tenants_from_urls, Tenant, TenantSettings};
+use phoenix_keeper::{ensure_schema, BoxedAnchor};
+use sqlx::{sqlite::SqlitePoolOptions, Pool, Sqlite};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+
+struct MockAnchorProvider;
+
+#[async_trait::async_trait]
+impl AnchorProvider for MockAnchorProvider {
+    async fn anchor(&self, evidence: &EvidenceRecord) -> Result<ChainTxRef, AnchorError> {
+        Ok(ChainTxRef {
+            network: "mocknet".to_string(),
+            chain: "mockchain".to_string(),
+            tx_id: format!("mocktx-{}", evidence.id),
+            confirmed: false,
+            timestamp: Some(Utc::now()),
+        })
+    }
+
+    async fn confirm(&self, tx: &ChainTxRef) -> Result<ChainTxRef, AnchorError> {
+        Ok(tx.clone())
+    }
+}
+
+fn memory_url(name: &str) -> String {
+    let nanos = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_nanos();
+    format!("sqlite:file:{}_{}?mode=memory&cache=shared", name, nanos)
+}
+
+/// Open (and keep alive) a tenant's shared in-memory database
+async fn open_tenant_db(url: &str) -> Pool<Sqlite> {
+    let pool = SqlitePoolOptions::new()
+        .max_connections(2)
+        .connect(url)
+        .await
+        .unwrap();
+    ensure_schema(&pool).await.unwrap();
+    pool
+}
+
+async fn insert_job(pool: &Pool<Sqlite>, id: &str) {
+    let now = Utc::now().timestamp_millis();
+    sqlx::query(
+        "INSERT INTO outbox_jobs (id, payload_sha256, status, attempts, created_ms, updated_ms, next_attempt_ms)
+         VALUES (?1, 'abcd1234', 'queued', 0, ?2, ?2, 0)",
+    )
+    .bind(id)
+    .bind(now)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+async fn count_done(pool: &Pool<Sqlite>) -> i64 {
+    sqlx::query_scalar("SELECT COUNT(*) FROM outbox_jobs WHERE status = 'done'")
+        .fetch_one(pool)
+        .await
+        .unwrap()
+}
+
+/// Short polls and intervals so tenant loops react within a test
+fn fast_settings() -> TenantSettings {
+    TenantSettings {
+        job_poll_interval: Duration::from_millis(20),
+        confirmation_poll_interval: Duration::from_millis(50),
+        max_attempts: 3,
+        retry_interval: Duration::from_millis(50),
+        concurrency: 1,
+        stale_after: Duration::from_secs(600),
+        confirm_timeout: Duration::from_secs(600),
+        confirm_batch_size: 100,
+        dedupe_digests: false,
+        batch_poll_interval: Duration::from_millis(50),
+    }
+}
+
+/// Poll until `condition` holds, failing the test after a few seconds
+async fn wait_for<F, Fut>(mut condition: F)
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = bool>,
+{
+    for _ in 0..100 {
+        if condition().await {
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+    panic!("condition not met in time");
+}
+
+#[test]
+fn test_tenants_from_urls() {
+    let tenants = tenants_from_urls(
+        "sqlite://data/acme.sqlite3, sqlite://other/acme.sqlite3,sqlite::memory:,,sqlite:file:globex?mode=memory",
+    );
+    let ids: Vec<&str> = tenants.iter().map(|t| t.id.as_str()).collect();
+    assert_eq!(ids, vec!["acme", "tenant-1", "tenant-2", "globex"]);
+    assert_eq!(tenants[1].db_url, "sqlite://other/acme.sqlite3");
+
+    let single = tenants_from_urls("sqlite://blockchain_outbox.sqlite3");
+    assert_eq!(single.len(), 1);
+    assert_eq!(single[0].id, "blockchain_outbox");
+}
+
+#[tokio::test]
+async fn test_tenants_processed_independently() {
+    let url_a = memory_url("tenant_a");
+    let url_b = memory_url("tenant_b");
+    let pool_a = open_tenant_db(&url_a).await;
+    let pool_b = open_tenant_db(&url_b).await;
+    insert_job(&pool_a, "a-1").await;
+    insert_job(&pool_a, "a-2").await;
+    insert_job(&pool_b, "b-1").await;
+
+    let tenant_a = Tenant::new("a", url_a);
+    let tenant_b = Tenant::new("b", url_b);
+    let unreachable = Tenant::new("c", "sqlite:///nonexistent-phoenix-tenant-dir/c.sqlite3");
+    let (metrics_a, metrics_b, metrics_c) = (
+        tenant_a.metrics.clone(),
+        tenant_b.metrics.clone(),
+        unreachable.metrics.clone(),
+    );
+
+    let settings = fast_settings();
+    let anchors: Arc<Vec<BoxedAnchor>> = Arc::new(vec![Box::new(MockAnchorProvider)]);
+    let handles: Vec<_> = [tenant_a, tenant_b, unreachable]
+        .into_iter()
+        .map(|tenant| {
+            tokio::spawn(run_tenant(
+                tenant,
+                anchors.clone(),
+                Arc::new(MockAnchorProvider),
+                settings,
+                watch::channel(false).1,
+            ))
+        })
+        .collect();
+
+    // Both reachable tenants drain their own queues despite tenant c
+    wait_for(|| async { count_done(&pool_a).await == 2 && count_done(&pool_b).await == 1 }).await;
+    assert_eq!(metrics_a.snapshot().jobs_anchored, 2);
+    assert_eq!(metrics_b.snapshot().jobs_anchored, 1);
+    wait_for(|| async { metrics_c.snapshot().connect_failures > 0 }).await;
+
+    // Break tenant b's database; tenant a keeps processing
+    sqlx::query("DROP TABLE outbox_jobs")
+        .execute(&pool_b)
+        .await
+        .unwrap();
+    insert_job(&pool_a, "a-3").await;
+    wait_for(|| async { count_done(&pool_a).await == 3 }).await;
+    wait_for(|| async { metrics_b.snapshot().fetch_errors > 0 }).await;
+    assert_eq!(metrics_a.snapshot().fetch_errors, 0);
+    assert_eq!(metrics_a.snapshot().jobs_anchored, 3);
+
+    for handle in handles {
+        handle.abort();
+    }
+}
+
+/// Fails `retry-*` jobs with a network error and every other job permanently
+struct FailingAnchor;
+
+#[async_trait::async_trait]
+impl AnchorProvider for FailingAnchor {
+    async fn anchor(&self, evidence: &EvidenceRecord) -> Result<ChainTxRef, AnchorError> {
+        if evidence.id.starts_with("retry-") {
+            Err(AnchorError::Network("rpc unavailable".to_string()))
+        } else {
+            Err(AnchorError::PayloadTooLarge {
+                size: 2048,
+                limit: 1024,
+            })
+        }
+    }
+
+    async fn confirm(&self, tx: &ChainTxRef) -> Result<ChainTxRef, AnchorError> {
+        Ok(tx.clone())
+    }
+}
+
+#[tokio::test]
+async fn test_tenant_metrics_separate_retries_from_failures() {
+    let url = memory_url("tenant_failures");
+    let pool = open_tenant_db(&url).await;
+    insert_job(&pool, "retry-1").await;
+    insert_job(&pool, "too-large-1").await;
+
+    let tenant = Tenant::new("failures", url);
+    let metrics = tenant.metrics.clone();
+    let settings = fast_settings();
+    let anchors: Arc<Vec<BoxedAnchor>> = Arc::new(vec![Box::new(FailingAnchor)]);
+    let handle = tokio::spawn(run_tenant(
+        tenant,
+        anchors,
+        Arc::new(FailingAnchor),
+        settings,
+        watch::channel(false).1,
+    ));
+
+    // Skip the backoff so the flaky job uses up its attempts quickly
+    wait_for(|| async {
+        sqlx::query("UPDATE outbox_jobs SET next_attempt_ms = 0 WHERE status = 'queued'")
+            .execute(&pool)
+            .await
+            .unwrap();
+        metrics.snapshot().jobs_dead_lettered == 1
+    })
+    .await;
+    let status: String = sqlx::query_scalar("SELECT status FROM outbox_jobs WHERE id = 'retry-1'")
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+    assert_eq!(status, "dead_letter");
+
+    // Two requeues, then the last attempt dead-letters rather than retries
+    let snapshot = metrics.snapshot();
+    assert_eq!(snapshot.jobs_retried, 2);
+    assert_eq!(snapshot.jobs_dead_lettered, 1);
+    assert_eq!(snapshot.jobs_failed, 1);
+    assert_eq!(snapshot.jobs_anchored, 0);
+
+    handle.abort();
+}
+
+/// Reports every transaction it is asked about as confirmed
+struct ConfirmingAnchor;
+
+#[async_trait::async_trait]
+impl AnchorProvider for ConfirmingAnchor {
+    async fn anchor(&self, evidence: &EvidenceRecord) -> Result<ChainTxRef, AnchorError> {
+        MockAnchorProvider.anchor(evidence).await
+    }
+
+    async fn confirm(&self, tx: &ChainTxRef) -> Result<ChainTxRef, AnchorError> {
+        Ok(ChainTxRef {
+            confirmed: true,
+            ..tx.clone()
+        })
+    }
+}
+
+#[tokio::test]
+async fn test_tenant_confirms_anchored_batches() {
+    let url = memory_url("tenant_batches");
+    let pool = open_tenant_db(&url).await;
+    BatchAnchor::ensure_schema(&pool).await.unwrap();
+    sqlx::query(
+        "INSERT INTO merkle_batches (id, merkle_root, item_count, created_at, anchored_at, tx_network, tx_chain, tx_id, tx_confirmed)
+         VALUES ('batch-1', 'root', 2, 0, 0, 'mocknet', 'mockchain', 'mocktx-batch-1', 0)",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let settings = TenantSettings {
+        batch_poll_interval: Duration::from_millis(20),
+        ..fast_settings()
+    };
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    let handle = tokio::spawn(run_tenant(
+        Tenant::new("batches", url),
+        Arc::new(vec![Box::new(ConfirmingAnchor) as BoxedAnchor]),
+        Arc::new(ConfirmingAnchor),
+        settings,
+        shutdown_rx,
+    ));
+
+    wait_for(|| async {
+        sqlx::query_scalar::<_, i64>("SELECT tx_confirmed FROM merkle_batches WHERE id = 'batch-1'")
+            .fetch_one(&pool)
+            .await
+            .unwrap()
+            == 1
+    })
+    .await;
+
+    // The batch loop stops with the others on shutdown
+    shutdown_tx.send(true).unwrap();
+    tokio::time::timeout(Duration::from_secs(5), handle)
+        .await
+        .expect("tenant stopped")
+        .unwrap();
+}
@@ -0,0 +1,203 @@
+//! Anchor provider selection by spec string
+
+use phoenix_keeper::providers::{
+    parse_spec_list, provider_from_spec, providers_from_env, spec_list_from_env,
+    validate_providers, ProviderError, ProviderKind, KNOWN_SPECS,
+};
+use serde_json::{json, Value};
+use serial_test::serial;
+
+/// Run `f` with the provider selection variables set as given, then clear them
+fn with_env<T>(vars: &[(&str, &str)], f: impl FnOnce() -> T) -> T {
+    const NAMES: [&str; 6] = [
+        "KEEPER_ANCHOR_PROVIDER",
+        "KEEPER_PROVIDER",
+        "KEEPER_USE_STUB",
+        "SOLANA_KEYPAIR",
+        "ETHERLINK_ENDPOINT",
+        "ETHERLINK_NETWORK",
+    ];
+    for name in NAMES {
+        std::env::remove_var(name);
+    }
+    for (name, value) in vars {
+        std::env::set_var(name, value);
+    }
+    let result = f();
+    for name in NAMES {
+        std::env::remove_var(name);
+    }
+    result
+}
+
+#[test]
+fn test_every_known_spec_parses() {
+    for spec in KNOWN_SPECS {
+        let kind: ProviderKind = spec.parse().unwrap();
+        assert_eq!(kind.as_str(), spec);
+    }
+    assert_eq!(
+        " Solana-Stub ".parse::<ProviderKind>(),
+        Ok(ProviderKind::SolanaStub)
+    );
+}
+
+#[test]
+#[serial]
+fn test_factory_returns_provider_for_each_spec() {
+    for (spec, network) in [
+        ("etherlink-stub", "etherlink"),
+        ("solana-stub", "solana"),
+        ("etherlink", "etherlink"),
+    ] {
+        let provider = provider_from_spec(spec).unwrap();
+        assert_eq!(provider.network(), Some(network), "{}", spec);
+    }
+
+    // The real Solana provider needs its keypair
+    with_env(&[], || {
+        assert!(matches!(
+            provider_from_spec("solana"),
+            Err(ProviderError::Config { spec, .. }) if spec == "solana"
+        ));
+    });
+    let keypair = format!("{:?}", [7u8; 32]);
+    with_env(&[("SOLANA_KEYPAIR", &keypair)], || {
+        let provider = provider_from_spec("solana").unwrap();
+        assert_eq!(provider.network(), Some("solana"));
+    });
+}
+
+#[test]
+fn test_factory_rejects_unknown_spec() {
+    let error = provider_from_spec("polygon").err().unwrap();
+    assert_eq!(error, ProviderError::Unknown("polygon".to_string()));
+    assert!(error.to_string().contains("etherlink-stub"));
+}
+
+#[test]
+fn test_spec_list_parsing() {
+    assert_eq!(
+        parse_spec_list("etherlink-stub, solana-stub").unwrap(),
+        vec![ProviderKind::EtherlinkStub, ProviderKind::SolanaStub]
+    );
+    assert_eq!(parse_spec_list(" , "), Err(ProviderError::Empty));
+    assert_eq!(
+        parse_spec_list("solana,solana"),
+        Err(ProviderError::Duplicate("solana".to_string()))
+    );
+    assert!(matches!(
+        parse_spec_list("etherlink,mainet"),
+        Err(ProviderError::Unknown(_))
+    ));
+}
+
+#[test]
+#[serial]
+fn test_env_selection_prefers_anchor_provider_over_legacy_vars() {
+    with_env(
+        &[
+            ("KEEPER_ANCHOR_PROVIDER", "solana-stub,etherlink-stub"),
+            ("KEEPER_PROVIDER", "solana"),
+        ],
+        || {
+            let providers = providers_from_env().unwrap();
+            let networks: Vec<_> = providers.iter().map(|p| p.network()).collect();
+            assert_eq!(networks, vec![Some("solana"), Some("etherlink")]);
+        },
+    );
+
+    // Legacy variables still select providers
+    with_env(
+        &[("KEEPER_PROVIDER", "multi"), ("KEEPER_USE_STUB", "true")],
+        || {
+            assert_eq!(
+                spec_list_from_env(),
+                Ok("etherlink-stub,solana-stub".to_string())
+            )
+        },
+    );
+    with_env(&[], || {
+        assert_eq!(spec_list_from_env(), Ok("etherlink".to_string()))
+    });
+    with_env(&[("KEEPER_PROVIDER", "stub")], || {
+        assert_eq!(spec_list_from_env(), Ok("etherlink-stub".to_string()))
+    });
+
+    // A typo fails instead of falling back to the real EtherLink provider
+    with_env(&[("KEEPER_PROVIDER", "solona")], || {
+        assert_eq!(
+            spec_list_from_env(),
+            Err(ProviderError::Unknown("solona".to_string()))
+        );
+        assert!(providers_from_env().is_err());
+    });
+}
+
+/// JSON-RPC node answering `eth_chainId` with `chain_id`
+async fn spawn_chain_id_rpc(chain_id: &'static str) -> String {
+    let app = axum::Router::new().route(
+        "/",
+        axum::routing::post(move |axum::Json(request): axum::Json<Value>| async move {
+            axum::Json(match request["method"].as_str() {
+                Some("eth_chainId") => json!({ "jsonrpc": "2.0", "id": 1, "result": chain_id }),
+                _ => json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "error": { "code": -32601, "message": "Method not found" }
+                }),
+            })
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    url
+}
+
+#[tokio::test]
+#[serial]
+async fn test_startup_validation_rejects_mismatched_chain_id() {
+    // Configured for mainnet, but the node is Ghostnet (chain id 128123)
+    let url = spawn_chain_id_rpc("0x1f47b").await;
+    let providers = with_env(
+        &[
+            ("KEEPER_ANCHOR_PROVIDER", "etherlink,solana-stub"),
+            ("ETHERLINK_ENDPOINT", &url),
+            ("ETHERLINK_NETWORK", "mainnet"),
+        ],
+        || providers_from_env().unwrap(),
+    );
+    match validate_providers(&providers).await {
+        Err(ProviderError::Validation { network, message }) => {
+            assert_eq!(network, "etherlink");
+            assert!(message.contains("chain id 128123"), "{}", message);
+        }
+        other => panic!("expected a validation error, got {:?}", other),
+    }
+
+    // The same node passes for the network it actually serves
+    let providers = with_env(
+        &[
+            ("KEEPER_ANCHOR_PROVIDER", "etherlink"),
+            ("ETHERLINK_ENDPOINT", &url),
+            ("ETHERLINK_NETWORK", "ghostnet"),
+        ],
+        || providers_from_env().unwrap(),
+    );
+    validate_providers(&providers).await.unwrap();
+
+    // A misspelled network fails without a working endpoint
+    let providers = with_env(
+        &[
+            ("KEEPER_ANCHOR_PROVIDER", "etherlink"),
+            ("ETHERLINK_ENDPOINT", "http://127.0.0.1:9"),
+            ("ETHERLINK_NETWORK", "mainet"),
+        ],
+        || providers_from_env().unwrap(),
+    );
+    let error = validate_providers(&providers).await.unwrap_err();
+    assert!(error
+        .to_string()
+        .contains("unknown Etherlink network 'mainet'"));
+}
@@ -0,0 +1,145 @@
+//! Configurable detection-class map.
+//!
+//! The Python detector emits raw `class_id`s together with its own
+//! `class_name`/`is_drone` guesses. This map lets the desktop app relabel
+//! classes and decide which ones count as threats, independently of the
+//! model's label file.
+
+use crate::Detection;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use tracing::debug;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DetectionClass {
+    pub name: String,
+    pub is_threat: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DetectionClassMap {
+    /// `class_id` → configured label and threat status
+    pub classes: BTreeMap<i32, DetectionClass>,
+}
+
+impl Default for DetectionClassMap {
+    /// Mirrors the detector's default label file (`["drone", "not_drone"]`)
+    fn default() -> Self {
+        let mut classes = BTreeMap::new();
+        classes.insert(
+            0,
+            DetectionClass {
+                name: "drone".to_string(),
+                is_threat: true,
+            },
+        );
+        classes.insert(
+            1,
+            DetectionClass {
+                name: "not_drone".to_string(),
+                is_threat: false,
+            },
+        );
+        Self { classes }
+    }
+}
+
+impl DetectionClassMap {
+    pub fn get(&self, class_id: i32) -> Option<&DetectionClass> {
+        self.classes.get(&class_id)
+    }
+
+    /// Whether a class is configured as a threat (unknown classes are not)
+    pub fn is_threat(&self, class_id: i32) -> bool {
+        self.get(class_id).is_some_and(|class| class.is_threat)
+    }
+
+    /// Overwrite the detection's label and `is_drone` flag from the map.
+    ///
+    /// Returns `false` (leaving the detection untouched) for unmapped classes.
+    pub fn apply(&self, detection: &mut Detection) -> bool {
+        let Some(class) = self.get(detection.class_id) else {
+            return false;
+        };
+
+        if detection.class_name != class.name {
+            debug!(
+                class_id = detection.class_id,
+                detector_name = %detection.class_name,
+                configured_name = %class.name,
+                "Relabelling detection class"
+            );
+            detection.class_name = class.name.clone();
+        }
+        detection.is_drone = class.is_threat;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detection(class_id: i32, class_name: &str, is_drone: bool) -> Detection {
+        Detection {
+            class_id,
+            class_name: class_name.to_string(),
+            confidence: 0.9,
+            bbox: vec![0.0, 0.0, 10.0, 10.0],
+            drone_score: 0.5,
+            track_id: None,
+            is_drone,
+        }
+    }
+
+    #[test]
+    fn test_default_map_matches_detector_labels() {
+        let map = DetectionClassMap::default();
+        assert!(map.is_threat(0));
+        assert!(!map.is_threat(1));
+        assert!(!map.is_threat(42));
+    }
+
+    #[test]
+    fn test_configured_map_relabels_and_flips_threat() {
+        let mut map = DetectionClassMap::default();
+        map.classes.insert(
+            1,
+            DetectionClass {
+                name: "bird".to_string(),
+                is_threat: true,
+            },
+        );
+
+        let mut det = detection(1, "not_drone", false);
+        assert!(map.apply(&mut det));
+        assert_eq!(det.class_name, "bird");
+        assert!(det.is_drone);
+
+        map.classes.get_mut(&0).unwrap().is_threat = false;
+        let mut det = detection(0, "drone", true);
+        assert!(map.apply(&mut det));
+        assert_eq!(det.class_name, "drone");
+        assert!(!det.is_drone);
+    }
+
+    #[test]
+    fn test_unmapped_class_is_left_untouched() {
+        let map = DetectionClassMap::default();
+        let mut det = detection(7, "balloon", true);
+        assert!(!map.apply(&mut det));
+        assert_eq!(det.class_name, "balloon");
+        assert!(det.is_drone);
+    }
+
+    #[test]
+    fn test_map_roundtrips_through_json() {
+        let map = DetectionClassMap::default();
+        let json = serde_json::to_value(&map).unwrap();
+        assert_eq!(json["classes"]["0"]["isThreat"], true);
+        let back: DetectionClassMap = serde_json::from_value(json).unwrap();
+        assert_eq!(back, map);
+    }
+}
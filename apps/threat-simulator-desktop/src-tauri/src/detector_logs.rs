@@ -0,0 +1,209 @@
+//! Captured output of the Python detector process.
+//!
+//! `start_detector` pipes the child's stdout and stderr; reader threads copy
+//! each line into a bounded ring buffer so the UI can show the tail via
+//! `get_detector_logs`, and a detector that dies during startup reports its
+//! stderr instead of failing silently.
+
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader, Read};
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// Lines kept across both streams
+pub const DEFAULT_CAPACITY: usize = 500;
+
+/// A detector that exits within this window failed to start
+const STARTUP_WINDOW: Duration = Duration::from_secs(1);
+
+/// How long to wait for the readers to drain a dead process's pipes
+const DRAIN_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// Stderr lines included in a startup failure message
+const STARTUP_ERROR_LINES: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogStream {
+    Stdout,
+    Stderr,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LogLine {
+    pub stream: LogStream,
+    pub line: String,
+}
+
+/// Ring buffer of the most recent detector output lines
+pub struct DetectorLogs {
+    lines: Mutex<VecDeque<LogLine>>,
+    capacity: usize,
+}
+
+impl Default for DetectorLogs {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl DetectorLogs {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            lines: Mutex::new(VecDeque::with_capacity(capacity)),
+            capacity: capacity.max(1),
+        }
+    }
+
+    pub fn push(&self, stream: LogStream, line: String) {
+        if let Ok(mut lines) = self.lines.lock() {
+            if lines.len() == self.capacity {
+                lines.pop_front();
+            }
+            lines.push_back(LogLine { stream, line });
+        }
+    }
+
+    /// The last `count` lines, oldest first
+    pub fn tail(&self, count: usize) -> Vec<LogLine> {
+        self.lines
+            .lock()
+            .map(|lines| {
+                let skip = lines.len().saturating_sub(count);
+                lines.iter().skip(skip).cloned().collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// The last `count` stderr lines joined with newlines
+    pub fn stderr_tail(&self, count: usize) -> String {
+        let lines = self.lines.lock().map(|l| l.clone()).unwrap_or_default();
+        let stderr: Vec<&str> = lines
+            .iter()
+            .filter(|l| l.stream == LogStream::Stderr)
+            .map(|l| l.line.as_str())
+            .collect();
+        stderr[stderr.len().saturating_sub(count)..].join("\n")
+    }
+
+    pub fn clear(&self) {
+        if let Ok(mut lines) = self.lines.lock() {
+            lines.clear();
+        }
+    }
+}
+
+fn spawn_reader(
+    source: impl Read + Send + 'static,
+    stream: LogStream,
+    logs: Arc<DetectorLogs>,
+) -> JoinHandle<()> {
+    std::thread::spawn(move || {
+        for line in BufReader::new(source).lines() {
+            match line {
+                Ok(line) => logs.push(stream, line),
+                Err(_) => break,
+            }
+        }
+    })
+}
+
+/// Spawn `command` with its output captured into `logs`, which is cleared
+/// first.
+///
+/// If the process exits within the first second the captured stderr is
+/// returned as the error.
+pub fn spawn_captured(mut command: Command, logs: &Arc<DetectorLogs>) -> Result<Child, String> {
+    logs.clear();
+    let mut child = command
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to start detector: {}", e))?;
+
+    let mut readers = Vec::new();
+    if let Some(stdout) = child.stdout.take() {
+        readers.push(spawn_reader(stdout, LogStream::Stdout, logs.clone()));
+    }
+    if let Some(stderr) = child.stderr.take() {
+        readers.push(spawn_reader(stderr, LogStream::Stderr, logs.clone()));
+    }
+
+    let started = Instant::now();
+    while started.elapsed() < STARTUP_WINDOW {
+        match child.try_wait() {
+            Ok(Some(status)) => {
+                // Let the readers reach EOF so the last lines are captured
+                let drain_started = Instant::now();
+                while !readers.iter().all(|r| r.is_finished())
+                    && drain_started.elapsed() < DRAIN_TIMEOUT
+                {
+                    std::thread::sleep(Duration::from_millis(10));
+                }
+                let stderr = logs.stderr_tail(STARTUP_ERROR_LINES);
+                return Err(if stderr.is_empty() {
+                    format!("Detector exited during startup ({})", status)
+                } else {
+                    format!("Detector exited during startup ({}):\n{}", status, stderr)
+                });
+            }
+            Ok(None) => std::thread::sleep(Duration::from_millis(50)),
+            Err(e) => return Err(format!("Failed to check detector process: {}", e)),
+        }
+    }
+    Ok(child)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ring_buffer_keeps_last_lines() {
+        let logs = DetectorLogs::new(3);
+        for i in 0..5 {
+            logs.push(LogStream::Stdout, format!("line {}", i));
+        }
+        let tail = logs.tail(10);
+        assert_eq!(tail.len(), 3);
+        assert_eq!(tail[0].line, "line 2");
+        assert_eq!(logs.tail(1)[0].line, "line 4");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_early_exit_returns_captured_stderr() {
+        let logs = Arc::new(DetectorLogs::default());
+        let mut command = Command::new("sh");
+        command.args([
+            "-c",
+            "echo starting; echo 'ModuleNotFoundError: No module named cv2' >&2; exit 3",
+        ]);
+
+        let error = spawn_captured(command, &logs).unwrap_err();
+        assert!(error.contains("exited during startup"), "{}", error);
+        assert!(error.contains("No module named cv2"), "{}", error);
+        assert!(!error.contains("starting"), "{}", error);
+        assert!(logs.tail(10).contains(&LogLine {
+            stream: LogStream::Stdout,
+            line: "starting".to_string(),
+        }));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_running_process_is_returned() {
+        let logs = Arc::new(DetectorLogs::default());
+        let mut command = Command::new("sh");
+        command.args(["-c", "echo ready; sleep 5"]);
+
+        let mut child = spawn_captured(command, &logs).unwrap();
+        assert_eq!(logs.tail(1)[0].line, "ready");
+        let _ = child.kill();
+        let _ = child.wait();
+    }
+}
@@ -0,0 +1,134 @@
+//! Graceful shutdown of the Python detector process.
+//!
+//! The detector is asked to exit (SIGTERM on Unix, CTRL_BREAK on Windows) and
+//! given `DetectorConfig::shutdown_grace_ms` to do so; `try_wait` is polled so
+//! a detector that exits promptly is reaped immediately. Only after the grace
+//! period is it killed.
+
+use std::process::{Child, Command};
+use std::time::{Duration, Instant};
+
+/// Default wait between the shutdown signal and a forced kill
+pub const DEFAULT_SHUTDOWN_GRACE_MS: u64 = 2_000;
+
+/// How often to check whether the detector has exited
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// `CREATE_NEW_PROCESS_GROUP`, so CTRL_BREAK reaches only the detector
+#[cfg(windows)]
+const CREATE_NEW_PROCESS_GROUP: u32 = 0x0000_0200;
+
+#[cfg(windows)]
+const CTRL_BREAK_EVENT: u32 = 1;
+
+#[cfg(windows)]
+extern "system" {
+    fn GenerateConsoleCtrlEvent(ctrl_event: u32, process_group_id: u32) -> i32;
+}
+
+/// How the detector went away
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shutdown {
+    /// It had already exited
+    AlreadyExited,
+    /// It exited on the shutdown signal within the grace period
+    Graceful,
+    /// It outlived the grace period and was killed
+    Killed,
+}
+
+/// Prepare the detector command so it can be signalled gracefully later
+pub fn configure_command(command: &mut Command) {
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        command.creation_flags(CREATE_NEW_PROCESS_GROUP);
+    }
+    #[cfg(not(windows))]
+    let _ = command;
+}
+
+/// Ask the process to exit without forcing it
+fn request_exit(child: &mut Child) -> bool {
+    #[cfg(unix)]
+    {
+        // SAFETY: kill(2) with a pid we spawned and still own (not yet reaped)
+        unsafe { libc::kill(child.id() as i32, libc::SIGTERM) == 0 }
+    }
+    #[cfg(windows)]
+    {
+        // SAFETY: plain Win32 call; the detector leads its own process group
+        unsafe { GenerateConsoleCtrlEvent(CTRL_BREAK_EVENT, child.id()) != 0 }
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        let _ = child;
+        false
+    }
+}
+
+/// Signal the process, wait up to `grace` for it to exit, then kill it
+pub fn terminate(child: &mut Child, grace: Duration) -> Shutdown {
+    if let Ok(Some(_)) = child.try_wait() {
+        return Shutdown::AlreadyExited;
+    }
+
+    if request_exit(child) {
+        let started = Instant::now();
+        loop {
+            match child.try_wait() {
+                Ok(Some(_)) => return Shutdown::Graceful,
+                Ok(None) if started.elapsed() < grace => std::thread::sleep(POLL_INTERVAL),
+                _ => break,
+            }
+        }
+    }
+
+    let _ = child.kill();
+    let _ = child.wait();
+    Shutdown::Killed
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    fn spawn_sh(script: &str) -> Child {
+        let child = Command::new("sh").args(["-c", script]).spawn().unwrap();
+        // Give the shell time to install its trap
+        std::thread::sleep(Duration::from_millis(200));
+        child
+    }
+
+    #[test]
+    fn test_cooperating_process_exits_before_grace_period() {
+        let mut child = spawn_sh("trap 'exit 0' TERM; while true; do sleep 0.05; done");
+
+        let started = Instant::now();
+        let outcome = terminate(&mut child, Duration::from_secs(5));
+
+        assert_eq!(outcome, Shutdown::Graceful);
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_process_ignoring_sigterm_is_killed() {
+        let mut child = spawn_sh("trap '' TERM; while true; do sleep 0.05; done");
+
+        let outcome = terminate(&mut child, Duration::from_millis(300));
+
+        assert_eq!(outcome, Shutdown::Killed);
+        assert!(child.try_wait().unwrap().is_some());
+    }
+
+    #[test]
+    fn test_exited_process_is_not_signalled() {
+        let mut child = Command::new("true").spawn().unwrap();
+        child.wait().unwrap();
+
+        assert_eq!(
+            terminate(&mut child, Duration::from_secs(1)),
+            Shutdown::AlreadyExited
+        );
+    }
+}
@@ -0,0 +1,406 @@
+//! Submitting game sessions and evidence events to the Phoenix API.
+//!
+//! Each record is serialized to JSON, hashed with
+//! `phoenix_evidence::hash::sha256_hex` and POSTed to `/evidence` with the
+//! JSON as metadata. Ids are derived from the digest, so submitting the same
+//! record twice is answered with a 409 and treated as already recorded.
+//!
+//! When the API cannot be reached (or fails with a 5xx) the request is kept
+//! in the local outbox and its id returned as usual; a 4xx is returned as an
+//! error because retrying would not help. A background task
+//! ([`EvidenceService::flush_outbox`], every [`FLUSH_INTERVAL`]) re-sends
+//! queued requests once the API answers again.
+
+use crate::outbox::LocalOutbox;
+use crate::GameSession;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::Duration;
+use tracing::{info, warn};
+
+/// API used when neither the settings nor `PHOENIX_API_URL` name one
+pub const DEFAULT_API_URL: &str = "http://127.0.0.1:8080";
+
+/// How often the background task retries the local outbox
+pub const FLUSH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Queued requests re-sent per flush
+const FLUSH_BATCH: i64 = 50;
+
+/// Evidence source recorded by the API for simulator submissions
+const EVIDENCE_SOURCE: &str = "simulator";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EvidenceSettings {
+    /// Base URL of the Phoenix API (`POST {api_url}/evidence`)
+    pub api_url: String,
+    /// Per-request timeout
+    pub timeout_ms: u64,
+}
+
+impl Default for EvidenceSettings {
+    fn default() -> Self {
+        Self {
+            api_url: std::env::var("PHOENIX_API_URL")
+                .ok()
+                .filter(|url| !url.trim().is_empty())
+                .unwrap_or_else(|| DEFAULT_API_URL.to_string()),
+            timeout_ms: 5_000,
+        }
+    }
+}
+
+/// A record ready to POST to `/evidence`
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvidenceSubmission {
+    pub id: String,
+    pub digest_hex: String,
+    /// The hashed JSON, sent as the job's metadata
+    pub payload: serde_json::Value,
+}
+
+impl EvidenceSubmission {
+    /// Hash `payload_json` (the exact bytes the digest covers) and derive the id
+    fn new(id_prefix: &str, payload_json: &str, payload: serde_json::Value) -> Self {
+        let digest_hex = phoenix_evidence::hash::sha256_hex(payload_json.as_bytes());
+        Self {
+            id: format!("{}-{}", id_prefix, &digest_hex[..12]),
+            digest_hex,
+            payload,
+        }
+    }
+
+    /// `/evidence` request body
+    pub fn request_body(&self) -> serde_json::Value {
+        serde_json::json!({
+            "id": self.id,
+            "digest_hex": self.digest_hex,
+            "payload_mime": "application/json",
+            "metadata": self.payload,
+            "source": EVIDENCE_SOURCE,
+        })
+    }
+}
+
+/// Evidence for a finished game session
+pub fn session_submission(session: &GameSession) -> Result<EvidenceSubmission, String> {
+    let serialize_error = |e: serde_json::Error| format!("Failed to serialize session: {}", e);
+    let session_json = serde_json::to_string(session).map_err(serialize_error)?;
+    let payload = serde_json::to_value(session).map_err(serialize_error)?;
+    Ok(EvidenceSubmission::new(
+        &format!("sim-{}", session.session_id),
+        &session_json,
+        payload,
+    ))
+}
+
+/// Evidence for a single in-game event
+pub fn event_submission(
+    session_id: &str,
+    event_type: &str,
+    event_data: &serde_json::Value,
+    timestamp: &str,
+) -> Result<EvidenceSubmission, String> {
+    let payload = serde_json::json!({
+        "session_id": session_id,
+        "event_type": event_type,
+        "event_data": event_data,
+        "timestamp": timestamp,
+    });
+    let payload_json = serde_json::to_string(&payload)
+        .map_err(|e| format!("Failed to serialize evidence payload: {}", e))?;
+    Ok(EvidenceSubmission::new(
+        &format!("ev-{}", session_id),
+        &payload_json,
+        payload,
+    ))
+}
+
+/// Why a submission did not reach the API
+enum SubmitError {
+    /// Worth retrying later (network failure, timeout, 5xx)
+    Unavailable(String),
+    /// The API refused the request
+    Rejected(String),
+}
+
+/// API client plus the local outbox for offline submissions
+pub struct EvidenceService {
+    settings: Mutex<EvidenceSettings>,
+    client: reqwest::Client,
+    outbox: LocalOutbox,
+}
+
+impl EvidenceService {
+    pub fn new(settings: EvidenceSettings, outbox: LocalOutbox) -> Self {
+        Self {
+            settings: Mutex::new(settings),
+            client: reqwest::Client::new(),
+            outbox,
+        }
+    }
+
+    pub fn settings(&self) -> Result<EvidenceSettings, String> {
+        self.settings
+            .lock()
+            .map(|s| s.clone())
+            .map_err(|e| e.to_string())
+    }
+
+    pub fn set_settings(&self, settings: EvidenceSettings) -> Result<(), String> {
+        *self.settings.lock().map_err(|e| e.to_string())? = settings;
+        Ok(())
+    }
+
+    pub fn outbox(&self) -> &LocalOutbox {
+        &self.outbox
+    }
+
+    /// Submit to the API, falling back to the local outbox when it is
+    /// unavailable. Returns the evidence id either way.
+    pub async fn persist(&self, submission: &EvidenceSubmission) -> Result<String, String> {
+        let body = submission.request_body();
+        match self.submit(&body).await {
+            Ok(()) => {
+                info!(evidence_id = %submission.id, "Evidence submitted to API");
+                Ok(submission.id.clone())
+            }
+            Err(SubmitError::Rejected(e)) => Err(e),
+            Err(SubmitError::Unavailable(e)) => {
+                warn!(
+                    evidence_id = %submission.id,
+                    error = %e,
+                    "API unavailable, queuing evidence locally"
+                );
+                self.outbox
+                    .enqueue(&submission.id, &submission.digest_hex, &body, &e)
+                    .await
+                    .map_err(|e| format!("Failed to queue evidence locally: {}", e))?;
+                Ok(submission.id.clone())
+            }
+        }
+    }
+
+    /// Re-send queued requests, oldest first. Stops at the first one the API
+    /// is unavailable for, leaving it and the rest queued for the next flush.
+    /// Returns how many were delivered.
+    pub async fn flush_outbox(&self) -> Result<usize, String> {
+        let due = self
+            .outbox
+            .fetch_due(FLUSH_BATCH)
+            .await
+            .map_err(|e| format!("Failed to read local outbox: {}", e))?;
+        let mut sent = 0;
+        for job in due {
+            let result = match self.submit(&job.body).await {
+                Ok(()) => {
+                    sent += 1;
+                    self.outbox.mark_done(&job.id).await
+                }
+                Err(SubmitError::Rejected(e)) => {
+                    warn!(evidence_id = %job.id, error = %e, "API rejected queued evidence");
+                    self.outbox.mark_failed(&job.id, &e).await
+                }
+                Err(SubmitError::Unavailable(e)) => {
+                    self.outbox
+                        .mark_retry(&job.id, job.attempts, &e)
+                        .await
+                        .map_err(|e| format!("Failed to update local outbox: {}", e))?;
+                    break;
+                }
+            };
+            result.map_err(|e| format!("Failed to update local outbox: {}", e))?;
+        }
+        if sent > 0 {
+            info!(sent, "Flushed queued evidence to API");
+        }
+        Ok(sent)
+    }
+
+    async fn submit(&self, body: &serde_json::Value) -> Result<(), SubmitError> {
+        let settings = self
+            .settings()
+            .map_err(|e| SubmitError::Unavailable(e.to_string()))?;
+        let url = format!("{}/evidence", settings.api_url.trim_end_matches('/'));
+        let response = self
+            .client
+            .post(&url)
+            .timeout(Duration::from_millis(settings.timeout_ms))
+            .json(body)
+            .send()
+            .await
+            .map_err(|e| SubmitError::Unavailable(format!("POST {} failed: {}", url, e)))?;
+
+        let status = response.status();
+        if status.is_success() || status == reqwest::StatusCode::CONFLICT {
+            // 409: already recorded under this digest-derived id
+            return Ok(());
+        }
+        let detail = response.text().await.unwrap_or_default();
+        let message = format!("API returned {}: {}", status, detail);
+        if status.is_server_error() {
+            Err(SubmitError::Unavailable(message))
+        } else {
+            Err(SubmitError::Rejected(message))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{extract::State, http::StatusCode, routing::post, Json, Router};
+    use sqlx::sqlite::SqlitePoolOptions;
+    use std::sync::{
+        atomic::{AtomicU16, Ordering},
+        Arc,
+    };
+
+    /// Mock `/evidence` endpoint answering with a settable status
+    #[derive(Clone, Default)]
+    struct MockApi {
+        status: Arc<AtomicU16>,
+        received: Arc<Mutex<Vec<serde_json::Value>>>,
+    }
+
+    async fn mock_api(status: u16) -> (String, MockApi) {
+        let api = MockApi::default();
+        api.status.store(status, Ordering::SeqCst);
+        let app = Router::new()
+            .route(
+                "/evidence",
+                post(
+                    |State(api): State<MockApi>, Json(body): Json<serde_json::Value>| async move {
+                        api.received.lock().unwrap().push(body);
+                        StatusCode::from_u16(api.status.load(Ordering::SeqCst)).unwrap()
+                    },
+                ),
+            )
+            .with_state(api.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (url, api)
+    }
+
+    /// A URL nothing is listening on
+    async fn offline_url() -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        format!("http://{}", listener.local_addr().unwrap())
+    }
+
+    async fn service(api_url: String) -> EvidenceService {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        let outbox = LocalOutbox::with_pool(pool).await.unwrap();
+        EvidenceService::new(
+            EvidenceSettings {
+                api_url,
+                timeout_ms: 1_000,
+            },
+            outbox,
+        )
+    }
+
+    fn session() -> GameSession {
+        GameSession {
+            session_id: "session-1".to_string(),
+            start_time: 1_700_000_000,
+            score: 1200,
+            threats_neutralized: 7,
+            level: 3,
+        }
+    }
+
+    #[test]
+    fn test_session_serializes_camel_case() {
+        let submission = session_submission(&session()).unwrap();
+        assert_eq!(submission.payload["sessionId"], "session-1");
+        assert_eq!(submission.payload["threatsNeutralized"], 7);
+        assert_eq!(submission.payload["startTime"], 1_700_000_000);
+    }
+
+    #[test]
+    fn test_session_digest_is_sha256_of_json() {
+        let submission = session_submission(&session()).unwrap();
+        let json = serde_json::to_string(&session()).unwrap();
+        assert_eq!(
+            submission.digest_hex,
+            phoenix_evidence::hash::sha256_hex(json.as_bytes())
+        );
+        assert_eq!(submission.digest_hex.len(), 64);
+        assert_eq!(
+            submission.id,
+            format!("sim-session-1-{}", &submission.digest_hex[..12])
+        );
+
+        // Deterministic: the same session always maps to the same evidence
+        assert_eq!(session_submission(&session()).unwrap(), submission);
+
+        let mut changed = session();
+        changed.score += 1;
+        assert_ne!(
+            session_submission(&changed).unwrap().digest_hex,
+            submission.digest_hex
+        );
+    }
+
+    #[test]
+    fn test_request_body_matches_api_shape() {
+        let submission = event_submission(
+            "session-1",
+            "threat_neutralized",
+            &serde_json::json!({ "threat_id": "t-9" }),
+            "2026-01-01T00:00:00Z",
+        )
+        .unwrap();
+        let body = submission.request_body();
+        assert_eq!(body["id"], submission.id);
+        assert_eq!(body["digest_hex"], submission.digest_hex);
+        assert_eq!(body["source"], "simulator");
+        assert_eq!(body["metadata"]["event_data"]["threat_id"], "t-9");
+        assert!(submission.id.starts_with("ev-session-1-"));
+    }
+
+    #[tokio::test]
+    async fn test_offline_evidence_is_flushed_on_reconnect() {
+        let evidence = service(offline_url().await).await;
+        let submission = session_submission(&session()).unwrap();
+
+        let id = evidence.persist(&submission).await.unwrap();
+        assert_eq!(id, submission.id);
+        assert_eq!(evidence.outbox().pending_count().await.unwrap(), 1);
+
+        let (url, api) = mock_api(201).await;
+        evidence
+            .set_settings(EvidenceSettings {
+                api_url: url,
+                timeout_ms: 1_000,
+            })
+            .unwrap();
+        assert_eq!(evidence.flush_outbox().await.unwrap(), 1);
+        assert_eq!(evidence.outbox().pending_count().await.unwrap(), 0);
+
+        let received = api.received.lock().unwrap();
+        assert_eq!(received.len(), 1);
+        assert_eq!(received[0], submission.request_body());
+    }
+
+    #[tokio::test]
+    async fn test_failed_flush_leaves_evidence_queued() {
+        let (url, api) = mock_api(503).await;
+        let evidence = service(url).await;
+        let submission = session_submission(&session()).unwrap();
+
+        evidence.persist(&submission).await.unwrap();
+        assert_eq!(evidence.outbox().pending_count().await.unwrap(), 1);
+
+        assert_eq!(evidence.flush_outbox().await.unwrap(), 0);
+        assert_eq!(evidence.outbox().pending_count().await.unwrap(), 1);
+        assert_eq!(api.received.lock().unwrap().len(), 2);
+    }
+}
@@ -1,10 +1,19 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
+mod detection_classes;
+mod detector_logs;
+mod detector_process;
+mod evidence;
+mod outbox;
+
+use detection_classes::DetectionClassMap;
+use detector_logs::{DetectorLogs, LogLine};
+use evidence::{EvidenceService, EvidenceSettings};
 use serde::{Deserialize, Serialize};
 use std::process::Child;
-use std::sync::Mutex;
-use tauri::{AppHandle, Emitter, State};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager, State};
 use tracing::{debug, error, info, warn};
 use tracing_subscriber::fmt;
 
@@ -23,6 +32,8 @@ struct AppState {
     current_session: Mutex<Option<GameSession>>,
     detector_process: Mutex<Option<Child>>,
     detector_config: Mutex<DetectorConfig>,
+    detection_classes: Mutex<DetectionClassMap>,
+    detector_logs: Arc<DetectorLogs>,
 }
 
 // Detection types matching Python detector output
@@ -77,6 +88,13 @@ pub struct DetectorConfig {
     pub source: String, // "mock", "usb", "picamera", "file:<path>"
     pub headless: bool,
     pub stream_enabled: bool,
+    /// Wait between asking the detector to exit and killing it
+    #[serde(default = "default_shutdown_grace_ms")]
+    pub shutdown_grace_ms: u64,
+}
+
+fn default_shutdown_grace_ms() -> u64 {
+    detector_process::DEFAULT_SHUTDOWN_GRACE_MS
 }
 
 impl Default for DetectorConfig {
@@ -89,42 +107,11 @@ impl Default for DetectorConfig {
             source: "mock".to_string(),
             headless: true,
             stream_enabled: true,
+            shutdown_grace_ms: detector_process::DEFAULT_SHUTDOWN_GRACE_MS,
         }
     }
 }
 
-/// Persist session data with tamper-evident hashing via phoenix-evidence crate.
-///
-/// Produces a deterministic evidence ID from a SHA-256 digest of the canonical
-/// JSON session payload.  The digest can later be submitted to a blockchain
-/// anchor (Solana / EtherLink) for immutable timestamping — that step is
-/// handled by the keeper service when an API endpoint is available.
-fn save_session_to_persistence(session: &GameSession) -> Result<String, String> {
-    // 1. Canonical JSON serialisation (deterministic key order via serde)
-    let session_json = serde_json::to_string(session)
-        .map_err(|e| format!("Failed to serialize session: {}", e))?;
-
-    debug!("Persisting session data: {}", session_json);
-
-    // 2. Compute tamper-evident SHA-256 digest using phoenix-evidence crate
-    let digest_hex = phoenix_evidence::hash::sha256_hex(session_json.as_bytes());
-
-    // 3. Build a structured EvidenceRecord for downstream consumers
-    let evidence_id = format!("sim-{}-{}", session.session_id, &digest_hex[..12]);
-
-    info!(
-        session_id = %session.session_id,
-        score = session.score,
-        threats = session.threats_neutralized,
-        level = session.level,
-        evidence_id = %evidence_id,
-        digest = %digest_hex,
-        "Session persisted with tamper-evident hash"
-    );
-
-    Ok(evidence_id)
-}
-
 // Tauri commands that can be called from the frontend
 
 // Input struct for end_game_session to handle camelCase from frontend
@@ -172,94 +159,125 @@ fn start_game_session(state: State<'_, AppState>) -> Result<GameSession, String>
     Ok(session)
 }
 
+/// Record the final stats and submit the session to the evidence chain.
+///
+/// The session is only cleared once it has been submitted to the API or
+/// queued in the local outbox.
 #[tauri::command]
-fn end_game_session(state: State<'_, AppState>, input: EndGameSessionInput) -> Result<(), String> {
+async fn end_game_session(
+    state: State<'_, AppState>,
+    evidence: State<'_, EvidenceService>,
+    input: EndGameSessionInput,
+) -> Result<(), String> {
     debug!(
         final_score = input.final_score,
         threats_neutralized = input.threats_neutralized,
         "Ending game session"
     );
 
-    let mut current = match state.current_session.lock() {
-        Ok(guard) => guard,
-        Err(e) => {
+    // Update under the lock, but don't hold it across the API call
+    let session = {
+        let mut current = state.current_session.lock().map_err(|e| {
             error!("Failed to acquire session lock (mutex poisoned): {}", e);
-            return Err(format!(
-                "Failed to acquire session lock (mutex poisoned): {}",
-                e
-            ));
-        }
-    };
-
-    if let Some(session) = current.as_mut() {
-        // Update session with final stats
+            format!("Failed to acquire session lock (mutex poisoned): {}", e)
+        })?;
+        let Some(session) = current.as_mut() else {
+            error!("No active session to end");
+            return Err("No active session to end".to_string());
+        };
         session.score = input.final_score;
         session.threats_neutralized = input.threats_neutralized;
+        session.clone()
+    };
 
-        info!(
-            session_id = %session.session_id,
-            duration_secs = chrono::Utc::now().timestamp() - session.start_time,
-            final_score = input.final_score,
-            threats_neutralized = input.threats_neutralized,
-            level = session.level,
-            "Game session ending, persisting data"
-        );
-
-        // Persist session to database/evidence chain
-        match save_session_to_persistence(session) {
-            Ok(evidence_id) => {
-                info!(
-                    session_id = %session.session_id,
-                    evidence_id = %evidence_id,
-                    "Session persisted successfully, clearing current session"
-                );
-                // Only clear session if persistence succeeded
+    info!(
+        session_id = %session.session_id,
+        duration_secs = chrono::Utc::now().timestamp() - session.start_time,
+        final_score = input.final_score,
+        threats_neutralized = input.threats_neutralized,
+        level = session.level,
+        "Game session ending, persisting data"
+    );
+
+    let submission = evidence::session_submission(&session)?;
+    match evidence.persist(&submission).await {
+        Ok(evidence_id) => {
+            info!(
+                session_id = %session.session_id,
+                evidence_id = %evidence_id,
+                digest = %submission.digest_hex,
+                "Session persisted successfully, clearing current session"
+            );
+            let mut current = state.current_session.lock().map_err(|e| e.to_string())?;
+            // A new session may have started meanwhile; leave it alone
+            if current
+                .as_ref()
+                .is_some_and(|c| c.session_id == session.session_id)
+            {
                 *current = None;
-                Ok(())
-            }
-            Err(e) => {
-                error!(
-                    session_id = %session.session_id,
-                    error = %e,
-                    "Failed to persist session data"
-                );
-                Err(format!("Failed to persist session: {}", e))
             }
+            Ok(())
+        }
+        Err(e) => {
+            error!(
+                session_id = %session.session_id,
+                error = %e,
+                "Failed to persist session data"
+            );
+            Err(format!("Failed to persist session: {}", e))
         }
-    } else {
-        error!("No active session to end");
-        Err("No active session to end".to_string())
     }
 }
 
 #[tauri::command]
-async fn save_evidence(payload: EvidencePayload) -> Result<String, String> {
-    // Build a canonical JSON blob from the evidence payload for hashing
-    let canonical = serde_json::json!({
-        "session_id": payload.session_id,
-        "event_type": payload.event_type,
-        "event_data": payload.event_data,
-        "timestamp": chrono::Utc::now().to_rfc3339(),
-    });
-
-    let canonical_bytes = serde_json::to_vec(&canonical)
-        .map_err(|e| format!("Failed to serialize evidence payload: {}", e))?;
-
-    // Compute SHA-256 digest via phoenix-evidence crate
-    let digest_hex = phoenix_evidence::hash::sha256_hex(&canonical_bytes);
-    let evidence_id = format!("ev-{}-{}", payload.session_id, &digest_hex[..12]);
+async fn save_evidence(
+    evidence: State<'_, EvidenceService>,
+    payload: EvidencePayload,
+) -> Result<String, String> {
+    let submission = evidence::event_submission(
+        &payload.session_id,
+        &payload.event_type,
+        &payload.event_data,
+        &chrono::Utc::now().to_rfc3339(),
+    )?;
+    let evidence_id = evidence.persist(&submission).await?;
 
     info!(
         session_id = %payload.session_id,
         event_type = %payload.event_type,
         evidence_id = %evidence_id,
-        digest = %digest_hex,
+        digest = %submission.digest_hex,
         "Evidence saved with tamper-evident hash"
     );
 
     Ok(evidence_id)
 }
 
+/// Get the evidence API settings
+#[tauri::command]
+fn get_evidence_settings(evidence: State<'_, EvidenceService>) -> Result<EvidenceSettings, String> {
+    evidence.settings()
+}
+
+/// Point evidence submission at a different API
+#[tauri::command]
+fn set_evidence_settings(
+    evidence: State<'_, EvidenceService>,
+    settings: EvidenceSettings,
+) -> Result<(), String> {
+    evidence.set_settings(settings)
+}
+
+/// Number of evidence submissions waiting in the local outbox
+#[tauri::command]
+async fn get_pending_evidence_count(evidence: State<'_, EvidenceService>) -> Result<i64, String> {
+    evidence
+        .outbox()
+        .pending_count()
+        .await
+        .map_err(|e| format!("Failed to read local outbox: {}", e))
+}
+
 #[tauri::command]
 fn get_system_info() -> Result<serde_json::Value, String> {
     Ok(serde_json::json!({
@@ -356,16 +374,14 @@ fn start_detector(
     args.push("--webhook".to_string());
     args.push(format!("http://127.0.0.1:{}/detection", webhook_port));
 
-    // Spawn the process
-    let child = std::process::Command::new(&config.python_path)
-        .args(&args)
-        .stdout(std::process::Stdio::piped())
-        .stderr(std::process::Stdio::piped())
-        .spawn()
-        .map_err(|e| {
-            error!("Failed to spawn detector process: {}", e);
-            format!("Failed to start detector: {}", e)
-        })?;
+    // Spawn the process, capturing its output and failing if it dies on startup
+    let mut command = std::process::Command::new(&config.python_path);
+    command.args(&args);
+    detector_process::configure_command(&mut command);
+    let child = detector_logs::spawn_captured(command, &state.detector_logs).map_err(|e| {
+        error!("Failed to start detector process: {}", e);
+        e
+    })?;
 
     info!(pid = child.id(), "Detector process started");
     *detector = Some(child);
@@ -393,37 +409,23 @@ fn stop_detector(state: State<'_, AppState>, app_handle: AppHandle) -> Result<()
     if let Some(ref mut child) = *detector {
         info!(pid = child.id(), "Stopping detector process");
 
-        // Try graceful shutdown first
-        #[cfg(unix)]
-        {
-            use std::os::unix::process::CommandExt;
-            // Send SIGTERM
-            unsafe {
-                libc::kill(child.id() as i32, libc::SIGTERM);
+        let grace_ms = state
+            .detector_config
+            .lock()
+            .map(|config| config.shutdown_grace_ms)
+            .unwrap_or(detector_process::DEFAULT_SHUTDOWN_GRACE_MS);
+        match detector_process::terminate(child, std::time::Duration::from_millis(grace_ms)) {
+            detector_process::Shutdown::AlreadyExited => {
+                info!("Detector process had already exited");
             }
-        }
-
-        #[cfg(windows)]
-        {
-            // On Windows, just kill the process
-            let _ = child.kill();
-        }
-
-        // Wait a bit for graceful shutdown
-        std::thread::sleep(std::time::Duration::from_millis(500));
-
-        // Force kill if still running
-        match child.try_wait() {
-            Ok(Some(_)) => {
+            detector_process::Shutdown::Graceful => {
                 info!("Detector process terminated gracefully");
             }
-            Ok(None) => {
-                warn!("Detector process did not terminate, killing forcefully");
-                let _ = child.kill();
-                let _ = child.wait();
-            }
-            Err(e) => {
-                warn!("Error checking detector process: {}", e);
+            detector_process::Shutdown::Killed => {
+                warn!(
+                    grace_ms,
+                    "Detector process did not terminate, killed forcefully"
+                );
             }
         }
 
@@ -436,6 +438,12 @@ fn stop_detector(state: State<'_, AppState>, app_handle: AppHandle) -> Result<()
     }
 }
 
+/// Get the last `lines` lines of detector stdout/stderr, oldest first
+#[tauri::command]
+fn get_detector_logs(state: State<'_, AppState>, lines: usize) -> Vec<LogLine> {
+    state.detector_logs.tail(lines)
+}
+
 /// Get the current detector status
 #[tauri::command]
 fn get_detector_status(state: State<'_, AppState>) -> Result<DetectorStatus, String> {
@@ -490,10 +498,44 @@ fn set_detector_config(state: State<'_, AppState>, config: DetectorConfig) -> Re
     Ok(())
 }
 
+/// Get the detection class map
+#[tauri::command]
+fn get_detection_classes(state: State<'_, AppState>) -> Result<DetectionClassMap, String> {
+    let classes = state.detection_classes.lock().map_err(|e| e.to_string())?;
+    Ok(classes.clone())
+}
+
+/// Replace the detection class map
+#[tauri::command]
+fn set_detection_classes(
+    state: State<'_, AppState>,
+    classes: DetectionClassMap,
+) -> Result<(), String> {
+    let mut stored = state.detection_classes.lock().map_err(|e| e.to_string())?;
+    *stored = classes;
+    Ok(())
+}
+
 /// Receive a detection event from the Python detector (webhook endpoint)
 /// This is called by the detector's WebhookAlertHandler
 #[tauri::command]
-fn receive_detection(app_handle: AppHandle, event: DetectionEvent) -> Result<(), String> {
+fn receive_detection(
+    state: State<'_, AppState>,
+    app_handle: AppHandle,
+    mut event: DetectionEvent,
+) -> Result<(), String> {
+    // Relabel and recompute threat status from the configured class map
+    {
+        let classes = state.detection_classes.lock().map_err(|e| e.to_string())?;
+        if !classes.apply(&mut event.detection) {
+            warn!(
+                class_id = event.detection.class_id,
+                class = %event.detection.class_name,
+                "Detection class not in configured class map"
+            );
+        }
+    }
+
     debug!(
         event = %event.event,
         frame = event.frame_number,
@@ -550,23 +592,59 @@ fn main() {
 
     tauri::Builder::default()
         .plugin(tauri_plugin_shell::init())
+        .setup(|app| {
+            let data_dir = app.path().app_data_dir()?;
+            std::fs::create_dir_all(&data_dir)?;
+            let outbox = tauri::async_runtime::block_on(outbox::LocalOutbox::open(
+                &data_dir.join("evidence_outbox.sqlite3"),
+            ))?;
+            app.manage(EvidenceService::new(EvidenceSettings::default(), outbox));
+
+            // Re-send evidence queued while the API was unreachable
+            let app_handle = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                let mut interval = tokio::time::interval(evidence::FLUSH_INTERVAL);
+                loop {
+                    interval.tick().await;
+                    let evidence = app_handle.state::<EvidenceService>();
+                    match evidence.flush_outbox().await {
+                        Ok(0) => {}
+                        Ok(_) => {
+                            if let Ok(pending) = evidence.outbox().pending_count().await {
+                                let _ = app_handle.emit("evidence-pending", pending);
+                            }
+                        }
+                        Err(e) => warn!("Evidence outbox flush failed: {}", e),
+                    }
+                }
+            });
+            Ok(())
+        })
         .manage(AppState {
             current_session: Mutex::new(None),
             detector_process: Mutex::new(None),
             detector_config: Mutex::new(DetectorConfig::default()),
+            detection_classes: Mutex::new(DetectionClassMap::default()),
+            detector_logs: Arc::new(DetectorLogs::default()),
         })
         .invoke_handler(tauri::generate_handler![
             // Game session commands
             start_game_session,
             end_game_session,
             save_evidence,
+            get_evidence_settings,
+            set_evidence_settings,
+            get_pending_evidence_count,
             get_system_info,
             // Detector management commands
             start_detector,
             stop_detector,
             get_detector_status,
+            get_detector_logs,
             get_detector_config,
             set_detector_config,
+            get_detection_classes,
+            set_detection_classes,
             receive_detection,
             trigger_test_detection,
         ])
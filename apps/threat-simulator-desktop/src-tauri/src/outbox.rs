@@ -0,0 +1,224 @@
+//! Local SQLite outbox for evidence the API could not take yet.
+//!
+//! Mirrors the keeper's `outbox_jobs` table so queued rows read the same way
+//! on both sides, plus the JSON `body` to POST to `/evidence` once the API is
+//! reachable again. Rows move `queued` → `done` when the API accepts them, or
+//! `failed` when it rejects them outright; unavailable APIs back a row off
+//! via `next_attempt_ms` and leave it queued.
+
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePoolOptions};
+use sqlx::Row;
+use sqlx::{Pool, Sqlite};
+use std::path::Path;
+
+/// First retry delay after a failed flush, doubled per attempt
+const BACKOFF_BASE_MS: i64 = 5_000;
+/// Longest delay between flush attempts for one row
+const BACKOFF_CAP_MS: i64 = 300_000;
+
+/// A queued `/evidence` request
+#[derive(Debug, Clone)]
+pub struct QueuedEvidence {
+    pub id: String,
+    pub body: serde_json::Value,
+    pub attempts: i64,
+}
+
+#[derive(Clone)]
+pub struct LocalOutbox {
+    pool: Pool<Sqlite>,
+}
+
+impl LocalOutbox {
+    /// Open (creating if needed) the outbox database at `path`
+    pub async fn open(path: &Path) -> Result<Self, sqlx::Error> {
+        let options = SqliteConnectOptions::new()
+            .filename(path)
+            .create_if_missing(true);
+        let pool = SqlitePoolOptions::new()
+            .max_connections(2)
+            .connect_with(options)
+            .await?;
+        Self::with_pool(pool).await
+    }
+
+    /// Use an existing pool (tests use an in-memory database)
+    pub async fn with_pool(pool: Pool<Sqlite>) -> Result<Self, sqlx::Error> {
+        sqlx::query(
+            r#"
+            CREATE TABLE IF NOT EXISTS outbox_jobs (
+                id TEXT PRIMARY KEY,
+                payload_sha256 TEXT NOT NULL,
+                status TEXT NOT NULL DEFAULT 'queued',
+                attempts INTEGER NOT NULL DEFAULT 0,
+                last_error TEXT,
+                created_ms INTEGER NOT NULL,
+                updated_ms INTEGER NOT NULL,
+                next_attempt_ms INTEGER NOT NULL DEFAULT 0,
+                body TEXT NOT NULL
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await?;
+        Ok(Self { pool })
+    }
+
+    /// Queue a `/evidence` request body; re-queuing the same id is a no-op
+    pub async fn enqueue(
+        &self,
+        id: &str,
+        digest_hex: &str,
+        body: &serde_json::Value,
+        last_error: &str,
+    ) -> Result<(), sqlx::Error> {
+        let now = chrono::Utc::now().timestamp_millis();
+        sqlx::query(
+            "INSERT OR IGNORE INTO outbox_jobs (id, payload_sha256, status, attempts, last_error, created_ms, updated_ms, next_attempt_ms, body) VALUES (?1, ?2, 'queued', 0, ?3, ?4, ?4, 0, ?5)",
+        )
+        .bind(id)
+        .bind(digest_hex)
+        .bind(last_error)
+        .bind(now)
+        .bind(body.to_string())
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    /// Number of rows still waiting to reach the API
+    pub async fn pending_count(&self) -> Result<i64, sqlx::Error> {
+        sqlx::query_scalar("SELECT COUNT(*) FROM outbox_jobs WHERE status = 'queued'")
+            .fetch_one(&self.pool)
+            .await
+    }
+
+    /// Queued rows whose next attempt is due, oldest first
+    pub async fn fetch_due(&self, limit: i64) -> Result<Vec<QueuedEvidence>, sqlx::Error> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let rows = sqlx::query(
+            "SELECT id, body, attempts FROM outbox_jobs WHERE status = 'queued' AND next_attempt_ms <= ?1 ORDER BY created_ms LIMIT ?2",
+        )
+        .bind(now)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|row| QueuedEvidence {
+                id: row.get("id"),
+                body: serde_json::from_str(&row.get::<String, _>("body"))
+                    .unwrap_or(serde_json::Value::Null),
+                attempts: row.get("attempts"),
+            })
+            .collect())
+    }
+
+    /// The API accepted the row
+    pub async fn mark_done(&self, id: &str) -> Result<(), sqlx::Error> {
+        self.set_status(id, "done", None).await
+    }
+
+    /// The API rejected the row; retrying would not help
+    pub async fn mark_failed(&self, id: &str, error: &str) -> Result<(), sqlx::Error> {
+        self.set_status(id, "failed", Some(error)).await
+    }
+
+    /// The API was unavailable; keep the row queued and back off
+    pub async fn mark_retry(
+        &self,
+        id: &str,
+        attempts: i64,
+        error: &str,
+    ) -> Result<(), sqlx::Error> {
+        let now = chrono::Utc::now().timestamp_millis();
+        let exp = attempts.clamp(0, 16) as u32;
+        let delay = BACKOFF_BASE_MS
+            .saturating_mul(2i64.pow(exp))
+            .min(BACKOFF_CAP_MS);
+        sqlx::query(
+            "UPDATE outbox_jobs SET attempts = attempts + 1, last_error = ?2, updated_ms = ?3, next_attempt_ms = ?4 WHERE id = ?1",
+        )
+        .bind(id)
+        .bind(error)
+        .bind(now)
+        .bind(now + delay)
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn set_status(
+        &self,
+        id: &str,
+        status: &str,
+        error: Option<&str>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            "UPDATE outbox_jobs SET status = ?2, attempts = attempts + 1, last_error = ?3, updated_ms = ?4 WHERE id = ?1",
+        )
+        .bind(id)
+        .bind(status)
+        .bind(error)
+        .bind(chrono::Utc::now().timestamp_millis())
+        .execute(&self.pool)
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::sqlite::SqlitePoolOptions;
+
+    async fn memory_outbox() -> LocalOutbox {
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        LocalOutbox::with_pool(pool).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_enqueue_is_pending_until_done() {
+        let outbox = memory_outbox().await;
+        let body = serde_json::json!({ "id": "ev-1", "digest_hex": "ab".repeat(32) });
+
+        outbox
+            .enqueue("ev-1", &"ab".repeat(32), &body, "offline")
+            .await
+            .unwrap();
+        // Re-queuing the same evidence does not duplicate it
+        outbox
+            .enqueue("ev-1", &"ab".repeat(32), &body, "offline")
+            .await
+            .unwrap();
+        assert_eq!(outbox.pending_count().await.unwrap(), 1);
+
+        let due = outbox.fetch_due(10).await.unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].body, body);
+
+        outbox.mark_done("ev-1").await.unwrap();
+        assert_eq!(outbox.pending_count().await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_retry_keeps_row_queued_but_not_due() {
+        let outbox = memory_outbox().await;
+        let body = serde_json::json!({ "id": "ev-2" });
+        outbox
+            .enqueue("ev-2", &"cd".repeat(32), &body, "offline")
+            .await
+            .unwrap();
+
+        outbox
+            .mark_retry("ev-2", 0, "API returned 503")
+            .await
+            .unwrap();
+        assert_eq!(outbox.pending_count().await.unwrap(), 1);
+        assert!(outbox.fetch_due(10).await.unwrap().is_empty());
+    }
+}
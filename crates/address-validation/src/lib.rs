@@ -1,3 +1,8 @@
+use async_trait::async_trait;
+use bech32::{
+    primitives::decode::{CheckedHrpstring, UncheckedHrpstring, UncheckedHrpstringError},
+    Bech32, Bech32m, Fe32,
+};
 use serde::{Deserialize, Serialize};
 use sha3::{Digest, Keccak256};
 use thiserror::Error;
@@ -14,6 +19,8 @@ pub enum AddressError {
     InvalidChecksum,
     #[error("base58 decode error: {0}")]
     Base58Error(String),
+    #[error("name resolution failed: {0}")]
+    ResolutionFailed(String),
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -31,26 +38,84 @@ pub struct EvmAddressInfo {
     pub normalized_address: String,
     pub checksum_valid: bool,
     pub validation_reason: String,
+    /// Name the address was resolved from (e.g. `vitalik.eth`), if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Address returned by the resolver for `name`, before normalization
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved_address: Option<String>,
+}
+
+/// Chain families with address validation support
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Chain {
+    Evm,
+    Solana,
+    Bitcoin,
+    Cosmos,
+}
+
+impl Chain {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Chain::Evm => "evm",
+            Chain::Solana => "solana",
+            Chain::Bitcoin => "bitcoin",
+            Chain::Cosmos => "cosmos",
+        }
+    }
+}
+
+impl std::str::FromStr for Chain {
+    type Err = AddressError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "ethereum" | "etherlink" | "evm" => Ok(Chain::Evm),
+            "solana" => Ok(Chain::Solana),
+            "bitcoin" | "btc" => Ok(Chain::Bitcoin),
+            "cosmos" | "cosmoshub" => Ok(Chain::Cosmos),
+            _ => Err(AddressError::InvalidPrefix(format!(
+                "Unsupported chain: {}",
+                s
+            ))),
+        }
+    }
+}
+
+impl std::fmt::Display for Chain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 pub fn get_address_metadata(chain: &str) -> Result<AddressMetadata, AddressError> {
-    match chain.to_lowercase().as_str() {
-        "ethereum" | "etherlink" | "evm" => Ok(AddressMetadata {
+    match chain.parse::<Chain>()? {
+        Chain::Evm => Ok(AddressMetadata {
             chain: "evm".to_string(),
             address_format: "0x-prefixed hex (42 chars, 20 bytes). EIP-55 checksum recommended."
                 .to_string(),
             address_example: "0x742d35Cc6634C0532925a3b844Bc454e4438f44e".to_string(),
         }),
-        "solana" => Ok(AddressMetadata {
+        Chain::Solana => Ok(AddressMetadata {
             chain: "solana".to_string(),
             address_format: "Base58 encoded; decodes to exactly 32 bytes (length varies)."
                 .to_string(),
             address_example: "4Nd1mY3iQz9dKqG2m9X3pQxvGXn3a6TT5p7H1cDJ5b5P".to_string(),
         }),
-        _ => Err(AddressError::InvalidPrefix(format!(
-            "Unsupported chain: {}",
-            chain
-        ))),
+        Chain::Bitcoin => Ok(AddressMetadata {
+            chain: "bitcoin".to_string(),
+            address_format: "Base58Check (P2PKH 1..., P2SH 3...) or Bech32/Bech32m SegWit (bc1q..., bc1p...); testnet uses m/n, 2 and tb1."
+                .to_string(),
+            address_example: "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4".to_string(),
+        }),
+        Chain::Cosmos => Ok(AddressMetadata {
+            chain: "cosmos".to_string(),
+            address_format: "Bech32 with a chain-specific prefix (cosmos1..., osmo1...); decodes to a 20-byte account address."
+                .to_string(),
+            address_example: "cosmos1hsk6jryyqjfhp5dhc55tc9jtckygx0eph6dd02".to_string(),
+        }),
     }
 }
 
@@ -95,26 +160,55 @@ pub fn to_eip55_checksum(address: &str) -> Result<String, AddressError> {
     validate_evm_address(address, false)?;
 
     let hex_part = &address[2..].to_lowercase();
-    let hash = Keccak256::digest(hex_part.as_bytes());
+    Ok(apply_checksum(hex_part, hex_part))
+}
+
+/// EIP-1191 checksum: EIP-55 with `"{chain_id}0x"` prefixed to the hashed
+/// preimage, so the checksum differs per chain (e.g. RSK mainnet is 30)
+pub fn to_eip1191_checksum(address: &str, chain_id: u64) -> Result<String, AddressError> {
+    validate_evm_address(address, false)?;
+
+    let hex_part = &address[2..].to_lowercase();
+    let preimage = format!("{}0x{}", chain_id, hex_part);
+    Ok(apply_checksum(hex_part, &preimage))
+}
+
+/// Validate an EVM address, checking an EIP-1191 checksum for `chain_id`
+/// instead of EIP-55 when `require_checksum` is set
+pub fn validate_evm_address_with_chain_id(
+    address: &str,
+    chain_id: u64,
+    require_checksum: bool,
+) -> Result<(), AddressError> {
+    validate_evm_address(address, false)?;
+
+    if require_checksum && address != to_eip1191_checksum(address, chain_id)? {
+        return Err(AddressError::InvalidChecksum);
+    }
+
+    Ok(())
+}
+
+/// Uppercase each letter of `hex_part` whose nibble in keccak256(`preimage`)
+/// is 8 or above
+fn apply_checksum(hex_part: &str, preimage: &str) -> String {
+    let hash = Keccak256::digest(preimage.as_bytes());
     let hash_hex = hex::encode(hash);
 
     let mut result = String::with_capacity(42);
     result.push_str("0x");
 
-    for (i, c) in hex_part.chars().enumerate() {
+    for (c, hash_char) in hex_part.chars().zip(hash_hex.chars()) {
         if c.is_ascii_digit() {
             result.push(c);
+        } else if hash_char >= '8' {
+            result.push(c.to_ascii_uppercase());
         } else {
-            let hash_char = hash_hex.chars().nth(i).unwrap();
-            if hash_char >= '8' {
-                result.push(c.to_ascii_uppercase());
-            } else {
-                result.push(c);
-            }
+            result.push(c);
         }
     }
 
-    Ok(result)
+    result
 }
 
 pub fn get_evm_address_info(address: &str, require_checksum: bool) -> EvmAddressInfo {
@@ -132,6 +226,8 @@ pub fn get_evm_address_info(address: &str, require_checksum: bool) -> EvmAddress
                 normalized_address: normalized,
                 checksum_valid,
                 validation_reason: String::new(),
+                name: None,
+                resolved_address: None,
             }
         }
         Err(e) => EvmAddressInfo {
@@ -141,7 +237,67 @@ pub fn get_evm_address_info(address: &str, require_checksum: bool) -> EvmAddress
             normalized_address: String::new(),
             checksum_valid: false,
             validation_reason: e.to_string(),
+            name: None,
+            resolved_address: None,
+        },
+    }
+}
+
+/// Resolves human-readable names (e.g. ENS `vitalik.eth`) to EVM addresses
+#[async_trait]
+pub trait NameResolver {
+    async fn resolve(&self, name: &str) -> Result<String, AddressError>;
+}
+
+/// Resolver for callers without name resolution: every name is an error
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopNameResolver;
+
+#[async_trait]
+impl NameResolver for NoopNameResolver {
+    async fn resolve(&self, name: &str) -> Result<String, AddressError> {
+        Err(AddressError::ResolutionFailed(format!(
+            "no name resolver configured for {}",
+            name
+        )))
+    }
+}
+
+/// Whether `input` looks like an ENS name rather than a hex address
+pub fn is_ens_name(input: &str) -> bool {
+    input.len() > 4 && input.to_ascii_lowercase().ends_with(".eth")
+}
+
+/// Like [`get_evm_address_info`], but resolves `.eth` names through
+/// `resolver` first and reports both the name and the resolved address.
+pub async fn get_evm_address_info_with_resolver(
+    input: &str,
+    require_checksum: bool,
+    resolver: &(dyn NameResolver + Sync),
+) -> EvmAddressInfo {
+    if !is_ens_name(input) {
+        return get_evm_address_info(input, require_checksum);
+    }
+
+    match resolver.resolve(input).await {
+        Ok(resolved) => EvmAddressInfo {
+            name: Some(input.to_string()),
+            resolved_address: Some(resolved.clone()),
+            ..get_evm_address_info(&resolved, require_checksum)
         },
+        Err(e) => {
+            let metadata = get_address_metadata("evm").unwrap();
+            EvmAddressInfo {
+                chain: "evm".to_string(),
+                address_format: metadata.address_format,
+                address_example: metadata.address_example,
+                normalized_address: String::new(),
+                checksum_valid: false,
+                validation_reason: e.to_string(),
+                name: Some(input.to_string()),
+                resolved_address: None,
+            }
+        }
     }
 }
 
@@ -160,6 +316,236 @@ pub fn validate_solana_address(address: &str) -> Result<(), AddressError> {
     Ok(())
 }
 
+/// Decode a Solana address and re-encode it in canonical base58.
+///
+/// Surrounding whitespace is ignored, so a result that differs from the input
+/// means the input was not in canonical form and callers may want to warn.
+/// Base58 keeps leading zero bytes as leading `1`s, so a missing or extra `1`
+/// changes the decoded length and is rejected rather than silently repaired.
+pub fn normalize_solana_address(address: &str) -> Result<String, AddressError> {
+    let trimmed = address.trim();
+    validate_solana_address(trimmed)?;
+    let decoded = bs58::decode(trimmed)
+        .into_vec()
+        .map_err(|e| AddressError::Base58Error(e.to_string()))?;
+    Ok(bs58::encode(decoded).into_string())
+}
+
+/// Base58Check version bytes for P2PKH and P2SH (mainnet and testnet)
+const BITCOIN_BASE58_VERSIONS: [u8; 4] = [0x00, 0x05, 0x6f, 0xc4];
+
+/// Human-readable parts for mainnet, testnet and regtest SegWit addresses
+const BITCOIN_SEGWIT_HRPS: [&str; 3] = ["bc", "tb", "bcrt"];
+
+/// Validate a Bitcoin address: Base58Check P2PKH/P2SH or a Bech32 (v0) /
+/// Bech32m (v1+) SegWit address.
+pub fn validate_bitcoin_address(address: &str) -> Result<(), AddressError> {
+    // Base58 addresses are mixed case, which bech32 never parses; anything
+    // with a Bitcoin hrp is reported against the bech32 rules
+    let lower = address.to_ascii_lowercase();
+    let has_bitcoin_hrp = BITCOIN_SEGWIT_HRPS
+        .iter()
+        .any(|hrp| lower.starts_with(&format!("{}1", hrp)));
+    if has_bitcoin_hrp || UncheckedHrpstring::new(address).is_ok() {
+        validate_bitcoin_segwit_address(address)
+    } else {
+        validate_bitcoin_base58_address(address)
+    }
+}
+
+fn validate_bitcoin_base58_address(address: &str) -> Result<(), AddressError> {
+    let decoded = bs58::decode(address)
+        .with_check(None)
+        .into_vec()
+        .map_err(|e| match e {
+            bs58::decode::Error::InvalidChecksum { .. } => AddressError::InvalidChecksum,
+            e => AddressError::Base58Error(e.to_string()),
+        })?;
+
+    // Version byte plus a 20-byte hash
+    if decoded.len() != 21 {
+        return Err(AddressError::InvalidLength {
+            expected: 21,
+            actual: decoded.len(),
+        });
+    }
+    if !BITCOIN_BASE58_VERSIONS.contains(&decoded[0]) {
+        return Err(AddressError::InvalidPrefix(format!(
+            "unknown version byte 0x{:02x}",
+            decoded[0]
+        )));
+    }
+
+    Ok(())
+}
+
+fn validate_bitcoin_segwit_address(address: &str) -> Result<(), AddressError> {
+    let unchecked = UncheckedHrpstring::new(address).map_err(bech32_parse_error)?;
+
+    let hrp = unchecked.hrp().to_lowercase();
+    if !BITCOIN_SEGWIT_HRPS.contains(&hrp.as_str()) {
+        return Err(AddressError::InvalidPrefix(format!(
+            "unknown human-readable part: {}",
+            hrp
+        )));
+    }
+
+    let version = unchecked
+        .witness_version()
+        .ok_or(AddressError::InvalidLength {
+            expected: 1,
+            actual: 0,
+        })?;
+    if version.to_u8() > 16 {
+        return Err(AddressError::InvalidPrefix(format!(
+            "invalid witness version {}",
+            version.to_u8()
+        )));
+    }
+
+    // BIP-173 Bech32 for v0, BIP-350 Bech32m for v1 and later
+    let mut checked: CheckedHrpstring = if version == Fe32::Q {
+        unchecked
+            .validate_checksum::<Bech32>()
+            .map_err(|_| AddressError::InvalidChecksum)?;
+        unchecked.remove_checksum::<Bech32>()
+    } else {
+        unchecked
+            .validate_checksum::<Bech32m>()
+            .map_err(|_| AddressError::InvalidChecksum)?;
+        unchecked.remove_checksum::<Bech32m>()
+    };
+    checked.remove_witness_version();
+    checked
+        .validate_segwit_padding()
+        .map_err(|e| AddressError::InvalidCharacters(e.to_string()))?;
+
+    let program_len = checked.byte_iter().len();
+    let expected = match version.to_u8() {
+        // P2WPKH or P2WSH
+        0 if program_len == 20 || program_len == 32 => None,
+        0 => Some(if program_len < 20 { 20 } else { 32 }),
+        // P2TR
+        1 if program_len == 32 => None,
+        1 => Some(32),
+        // Future versions: any BIP-141 program length
+        _ if (2..=40).contains(&program_len) => None,
+        _ => Some(if program_len < 2 { 2 } else { 40 }),
+    };
+    match expected {
+        Some(expected) => Err(AddressError::InvalidLength {
+            expected,
+            actual: program_len,
+        }),
+        None => Ok(()),
+    }
+}
+
+fn bech32_parse_error(e: UncheckedHrpstringError) -> AddressError {
+    match e {
+        UncheckedHrpstringError::Hrp(e) => AddressError::InvalidPrefix(e.to_string()),
+        e => AddressError::InvalidCharacters(e.to_string()),
+    }
+}
+
+/// Default human-readable prefix for Cosmos Hub account addresses
+pub const COSMOS_HUB_HRP: &str = "cosmos";
+
+/// Validate a Cosmos SDK account address: Bech32 with the expected
+/// human-readable prefix (e.g. `cosmos`, `osmo`) over a 20-byte payload.
+pub fn validate_cosmos_address(address: &str, hrp: &str) -> Result<(), AddressError> {
+    let unchecked = UncheckedHrpstring::new(address).map_err(bech32_parse_error)?;
+
+    let actual_hrp = unchecked.hrp().to_lowercase();
+    if actual_hrp != hrp.to_lowercase() {
+        return Err(AddressError::InvalidPrefix(format!(
+            "expected {}, got {}",
+            hrp, actual_hrp
+        )));
+    }
+
+    unchecked
+        .validate_checksum::<Bech32>()
+        .map_err(|_| AddressError::InvalidChecksum)?;
+    let checked = unchecked.remove_checksum::<Bech32>();
+
+    let payload_len = checked.byte_iter().len();
+    if payload_len != 20 {
+        return Err(AddressError::InvalidLength {
+            expected: 20,
+            actual: payload_len,
+        });
+    }
+
+    Ok(())
+}
+
+/// Outcome of validating one entry in a batch
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddressValidationResult {
+    pub chain: Chain,
+    /// The address as supplied
+    pub input: String,
+    pub valid: bool,
+    /// Canonical form (EIP-55 for EVM, lowercase for bech32); `None` if invalid
+    pub normalized_address: Option<String>,
+    /// Why validation failed; `None` if valid
+    pub error: Option<String>,
+}
+
+/// Validate a single address for `chain`, returning its canonical form.
+///
+/// Cosmos addresses are checked against the Cosmos Hub prefix; use
+/// [`validate_cosmos_address`] directly for other zones.
+pub fn normalize_address(chain: Chain, address: &str) -> Result<String, AddressError> {
+    match chain {
+        Chain::Evm => {
+            validate_evm_address(address, false)?;
+            to_eip55_checksum(address)
+        }
+        Chain::Solana => normalize_solana_address(address),
+        Chain::Bitcoin => {
+            validate_bitcoin_address(address)?;
+            // Bech32 is case-insensitive with lowercase canonical; Base58 is case-sensitive
+            if UncheckedHrpstring::new(address).is_ok() {
+                Ok(address.to_lowercase())
+            } else {
+                Ok(address.to_string())
+            }
+        }
+        Chain::Cosmos => {
+            validate_cosmos_address(address, COSMOS_HUB_HRP)?;
+            Ok(address.to_lowercase())
+        }
+    }
+}
+
+/// Validate a list of addresses, reporting every entry instead of stopping
+/// at the first failure. Results are in input order.
+pub fn validate_addresses(inputs: &[(Chain, &str)]) -> Vec<AddressValidationResult> {
+    inputs
+        .iter()
+        .map(
+            |&(chain, address)| match normalize_address(chain, address) {
+                Ok(normalized) => AddressValidationResult {
+                    chain,
+                    input: address.to_string(),
+                    valid: true,
+                    normalized_address: Some(normalized),
+                    error: None,
+                },
+                Err(e) => AddressValidationResult {
+                    chain,
+                    input: address.to_string(),
+                    valid: false,
+                    normalized_address: None,
+                    error: Some(e.to_string()),
+                },
+            },
+        )
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -315,9 +315,26 @@ fn metadata_solana_chain() {
     assert!(!meta.address_example.is_empty());
 }
 
+#[test]
+fn metadata_bitcoin_chain() {
+    let meta = get_address_metadata("bitcoin").unwrap();
+    assert_eq!(meta.chain, "bitcoin");
+    assert!(meta.address_format.contains("Bech32"));
+    assert!(validate_bitcoin_address(&meta.address_example).is_ok());
+}
+
+#[test]
+fn metadata_cosmos_chain() {
+    for name in ["cosmos", "cosmoshub", "CosmosHub"] {
+        let meta = get_address_metadata(name).unwrap();
+        assert_eq!(meta.chain, "cosmos");
+        assert!(validate_cosmos_address(&meta.address_example, COSMOS_HUB_HRP).is_ok());
+    }
+}
+
 #[test]
 fn metadata_unsupported_chain_returns_err() {
-    let result = get_address_metadata("bitcoin");
+    let result = get_address_metadata("dogecoin");
     assert!(result.is_err(), "unsupported chain must return an error");
     assert!(
         matches!(result.unwrap_err(), AddressError::InvalidPrefix(_)),
@@ -389,3 +406,407 @@ fn evm_address_info_invalid_address() {
         "invalid address must populate validation_reason with the error message"
     );
 }
+
+// ---------------------------------------------------------------------------
+// Bitcoin Base58Check addresses
+// ---------------------------------------------------------------------------
+
+#[test]
+fn bitcoin_valid_base58_addresses() {
+    for address in [
+        // Mainnet P2PKH (genesis coinbase) and P2SH
+        "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa",
+        "3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy",
+        // Testnet P2PKH and P2SH
+        "mipcBbFg9gMiCh81Kj8tqqdgoZub1ZJRfn",
+        "2MzQwSSnBHWHqSAqtTVQ6v47XtaisrJa1Vc",
+    ] {
+        assert!(
+            validate_bitcoin_address(address).is_ok(),
+            "{} should be valid",
+            address
+        );
+    }
+}
+
+#[test]
+fn bitcoin_base58_wrong_checksum() {
+    // Last character of the genesis address altered
+    assert!(matches!(
+        validate_bitcoin_address("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNb"),
+        Err(AddressError::InvalidChecksum)
+    ));
+}
+
+// ---------------------------------------------------------------------------
+// Bitcoin SegWit addresses (BIP-173 / BIP-350 vectors)
+// ---------------------------------------------------------------------------
+
+#[test]
+fn bitcoin_valid_segwit_v0_addresses() {
+    for address in [
+        // Mainnet P2WPKH, lower and upper case
+        "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+        "BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4",
+        // Testnet P2WSH
+        "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7",
+    ] {
+        assert!(
+            validate_bitcoin_address(address).is_ok(),
+            "{} should be valid",
+            address
+        );
+    }
+}
+
+#[test]
+fn bitcoin_valid_taproot_addresses() {
+    for address in [
+        "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0",
+        "tb1pqqqqp399et2xygdj5xreqhjjvcmzhxw4aywxecjdzew6hylgvsesf3hn0c",
+    ] {
+        assert!(
+            validate_bitcoin_address(address).is_ok(),
+            "{} should be valid",
+            address
+        );
+    }
+}
+
+#[test]
+fn bitcoin_segwit_wrong_checksum() {
+    // v0 address with its last character altered
+    assert!(matches!(
+        validate_bitcoin_address("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t5"),
+        Err(AddressError::InvalidChecksum)
+    ));
+    // v0 address carrying a Bech32m checksum
+    assert!(matches!(
+        validate_bitcoin_address("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kemeawh"),
+        Err(AddressError::InvalidChecksum)
+    ));
+    // v1 address carrying a Bech32 checksum
+    assert!(matches!(
+        validate_bitcoin_address("bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqh2y7hd"),
+        Err(AddressError::InvalidChecksum)
+    ));
+}
+
+#[test]
+fn bitcoin_segwit_wrong_hrp() {
+    // Valid Bech32m checksum, but "tc" is not a Bitcoin network
+    let result =
+        validate_bitcoin_address("tc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vq5zuyut");
+    assert!(matches!(result, Err(AddressError::InvalidPrefix(_))));
+}
+
+#[test]
+fn bitcoin_segwit_v0_wrong_program_length() {
+    // 16-byte v0 program (BIP-173 invalid vector)
+    assert!(matches!(
+        validate_bitcoin_address("BC1QR508D6QEJXTDG4Y5R3ZARVARYV98GJ9P"),
+        Err(AddressError::InvalidLength { actual: 16, .. })
+    ));
+}
+
+#[test]
+fn bitcoin_segwit_invalid_character() {
+    // 'o' is not in the bech32 alphabet
+    assert!(matches!(
+        validate_bitcoin_address("bc1p38j9r5y49hruaue7wxjce0updqjuyyx0kh56v8s25huc6995vvpql3jow4"),
+        Err(AddressError::InvalidCharacters(_))
+    ));
+}
+
+// ---------------------------------------------------------------------------
+// Chain parsing
+// ---------------------------------------------------------------------------
+
+#[test]
+fn chain_from_str_aliases() {
+    assert_eq!("etherlink".parse::<Chain>().unwrap(), Chain::Evm);
+    assert_eq!("Solana".parse::<Chain>().unwrap(), Chain::Solana);
+    assert_eq!("btc".parse::<Chain>().unwrap(), Chain::Bitcoin);
+    assert_eq!("cosmos".parse::<Chain>().unwrap(), Chain::Cosmos);
+    assert_eq!("cosmoshub".parse::<Chain>().unwrap(), Chain::Cosmos);
+    assert!(matches!(
+        "dogecoin".parse::<Chain>(),
+        Err(AddressError::InvalidPrefix(_))
+    ));
+}
+
+// ---------------------------------------------------------------------------
+// Cosmos addresses
+// ---------------------------------------------------------------------------
+
+#[test]
+fn cosmos_valid_hub_and_osmosis_addresses() {
+    assert!(
+        validate_cosmos_address("cosmos1hsk6jryyqjfhp5dhc55tc9jtckygx0eph6dd02", "cosmos").is_ok()
+    );
+    // Same key under the Osmosis prefix
+    assert!(validate_cosmos_address("osmo1hsk6jryyqjfhp5dhc55tc9jtckygx0eplp7aec", "osmo").is_ok());
+}
+
+#[test]
+fn cosmos_wrong_hrp_is_invalid_prefix() {
+    assert!(matches!(
+        validate_cosmos_address("osmo1hsk6jryyqjfhp5dhc55tc9jtckygx0eplp7aec", "cosmos"),
+        Err(AddressError::InvalidPrefix(_))
+    ));
+}
+
+#[test]
+fn cosmos_wrong_checksum() {
+    assert!(matches!(
+        validate_cosmos_address("cosmos1hsk6jryyqjfhp5dhc55tc9jtckygx0eph6dd03", "cosmos"),
+        Err(AddressError::InvalidChecksum)
+    ));
+}
+
+#[test]
+fn cosmos_non_account_payload_length() {
+    // 32-byte payload (module/contract style), not a 20-byte account
+    assert!(matches!(
+        validate_cosmos_address(
+            "cosmos1zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zyg3zygs5u086e",
+            "cosmos"
+        ),
+        Err(AddressError::InvalidLength {
+            expected: 20,
+            actual: 32
+        })
+    ));
+}
+
+// ---------------------------------------------------------------------------
+// Batch validation
+// ---------------------------------------------------------------------------
+
+#[test]
+fn batch_reports_every_entry_in_order() {
+    let inputs = [
+        (Chain::Evm, "0x742d35cc6634c0532925a3b844bc454e4438f44e"),
+        (Chain::Evm, "0xdeadbeef"),
+        (
+            Chain::Solana,
+            "4Nd1mY3iQz9dKqG2m9X3pQxvGXn3a6TT5p7H1cDJ5b5P",
+        ),
+        (Chain::Solana, "0OIl"),
+        (Chain::Bitcoin, "BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4"),
+        (Chain::Bitcoin, "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNb"),
+        (Chain::Bitcoin, "3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy"),
+    ];
+    let results = validate_addresses(&inputs);
+
+    assert_eq!(results.len(), inputs.len());
+    let flags: Vec<bool> = results.iter().map(|r| r.valid).collect();
+    assert_eq!(flags, [true, false, true, false, true, false, true]);
+    for (result, (chain, input)) in results.iter().zip(inputs) {
+        assert_eq!(result.chain, chain);
+        assert_eq!(result.input, input);
+        assert_eq!(result.error.is_none(), result.valid);
+        assert_eq!(result.normalized_address.is_some(), result.valid);
+    }
+}
+
+#[test]
+fn batch_normalizes_valid_addresses() {
+    let results = validate_addresses(&[
+        (Chain::Evm, "0x742d35cc6634c0532925a3b844bc454e4438f44e"),
+        (Chain::Bitcoin, "BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4"),
+    ]);
+    assert_eq!(
+        results[0].normalized_address.as_deref(),
+        Some("0x742d35Cc6634C0532925a3b844Bc454e4438f44e")
+    );
+    assert_eq!(
+        results[1].normalized_address.as_deref(),
+        Some("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4")
+    );
+}
+
+#[test]
+fn batch_error_reason_matches_single_validation() {
+    let results = validate_addresses(&[(Chain::Evm, "0xdeadbeef")]);
+    let expected = validate_evm_address("0xdeadbeef", false)
+        .unwrap_err()
+        .to_string();
+    assert_eq!(results[0].error.as_deref(), Some(expected.as_str()));
+}
+
+#[test]
+fn batch_of_nothing_is_empty() {
+    assert!(validate_addresses(&[]).is_empty());
+}
+
+// ---------------------------------------------------------------------------
+// Name resolution
+// ---------------------------------------------------------------------------
+
+/// Resolver that knows a single name
+struct MockResolver;
+
+#[async_trait::async_trait]
+impl NameResolver for MockResolver {
+    async fn resolve(&self, name: &str) -> Result<String, AddressError> {
+        match name {
+            "vitalik.eth" => Ok("0xd8da6bf26964af9d7eed9e03e53415d37aa96045".to_string()),
+            "broken.eth" => Ok("0xnot-an-address".to_string()),
+            _ => Err(AddressError::ResolutionFailed(format!(
+                "{} not found",
+                name
+            ))),
+        }
+    }
+}
+
+#[tokio::test]
+async fn resolver_resolves_ens_name_before_validating() {
+    let info = get_evm_address_info_with_resolver("vitalik.eth", false, &MockResolver).await;
+
+    assert_eq!(info.name.as_deref(), Some("vitalik.eth"));
+    assert_eq!(
+        info.resolved_address.as_deref(),
+        Some("0xd8da6bf26964af9d7eed9e03e53415d37aa96045")
+    );
+    assert_eq!(
+        info.normalized_address,
+        "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045"
+    );
+    assert!(info.validation_reason.is_empty());
+}
+
+#[tokio::test]
+async fn resolver_reports_unknown_name() {
+    let info = get_evm_address_info_with_resolver("nobody.eth", false, &MockResolver).await;
+
+    assert_eq!(info.name.as_deref(), Some("nobody.eth"));
+    assert!(info.resolved_address.is_none());
+    assert!(info.normalized_address.is_empty());
+    assert!(info.validation_reason.contains("not found"));
+}
+
+#[tokio::test]
+async fn resolver_output_is_still_validated() {
+    let info = get_evm_address_info_with_resolver("broken.eth", false, &MockResolver).await;
+
+    assert_eq!(info.resolved_address.as_deref(), Some("0xnot-an-address"));
+    assert!(info.normalized_address.is_empty());
+    assert!(!info.validation_reason.is_empty());
+}
+
+#[tokio::test]
+async fn resolver_leaves_hex_addresses_alone() {
+    let addr = "0x742d35Cc6634C0532925a3b844Bc454e4438f44e";
+    let info = get_evm_address_info_with_resolver(addr, true, &MockResolver).await;
+
+    assert!(info.name.is_none());
+    assert!(info.resolved_address.is_none());
+    assert_eq!(info.normalized_address, addr);
+}
+
+#[tokio::test]
+async fn noop_resolver_rejects_names() {
+    let info = get_evm_address_info_with_resolver("vitalik.eth", false, &NoopNameResolver).await;
+
+    assert!(info.normalized_address.is_empty());
+    assert!(info.validation_reason.contains("no name resolver"));
+}
+
+// ---------------------------------------------------------------------------
+// Solana normalization
+// ---------------------------------------------------------------------------
+
+#[test]
+fn solana_normalize_round_trips_canonical_address() {
+    let addr = "4Nd1mY3iQz9dKqG2m9X3pQxvGXn3a6TT5p7H1cDJ5b5P";
+    assert_eq!(normalize_solana_address(addr).unwrap(), addr);
+}
+
+#[test]
+fn solana_normalize_flags_non_canonical_input() {
+    let addr = "4Nd1mY3iQz9dKqG2m9X3pQxvGXn3a6TT5p7H1cDJ5b5P";
+    let padded = format!("  {}\n", addr);
+    let normalized = normalize_solana_address(&padded).unwrap();
+    assert_eq!(normalized, addr);
+    assert_ne!(
+        normalized, padded,
+        "caller can detect the input was not canonical"
+    );
+}
+
+#[test]
+fn solana_normalize_rejects_leading_zero_ambiguity() {
+    // A key whose first byte is zero encodes with a leading '1'
+    let mut key = [0x5au8; 32];
+    key[0] = 0;
+    let canonical = bs58::encode(key).into_string();
+    assert!(canonical.starts_with('1'));
+    assert_eq!(normalize_solana_address(&canonical).unwrap(), canonical);
+
+    // Dropping or adding a leading '1' changes the decoded key length
+    assert!(matches!(
+        normalize_solana_address(&canonical[1..]),
+        Err(AddressError::InvalidLength {
+            expected: 32,
+            actual: 31
+        })
+    ));
+    assert!(matches!(
+        normalize_solana_address(&format!("1{}", canonical)),
+        Err(AddressError::InvalidLength {
+            expected: 32,
+            actual: 33
+        })
+    ));
+}
+
+// ---------------------------------------------------------------------------
+// EIP-1191 chain-id checksums (vectors from the EIP)
+// ---------------------------------------------------------------------------
+
+const EIP1191_RSK_MAINNET: [&str; 5] = [
+    "0x27b1FdB04752BBc536007A920D24ACB045561c26",
+    "0x3599689E6292B81B2D85451025146515070129Bb",
+    "0x42712D45473476B98452f434E72461577d686318",
+    "0x52908400098527886E0F7030069857D2E4169ee7",
+    "0x5aaEB6053f3e94c9b9a09f33669435E7ef1bEAeD",
+];
+
+const EIP1191_RSK_TESTNET: [&str; 5] = [
+    "0x27B1FdB04752BbC536007a920D24acB045561C26",
+    "0x3599689e6292b81b2D85451025146515070129Bb",
+    "0x42712D45473476B98452F434E72461577D686318",
+    "0x52908400098527886E0F7030069857D2e4169EE7",
+    "0x5aAeb6053F3e94c9b9A09F33669435E7EF1BEaEd",
+];
+
+#[test]
+fn eip1191_checksum_matches_spec_vectors() {
+    for (chain_id, vectors) in [(30, EIP1191_RSK_MAINNET), (31, EIP1191_RSK_TESTNET)] {
+        for expected in vectors {
+            let lower = expected.to_lowercase();
+            assert_eq!(
+                to_eip1191_checksum(&lower, chain_id).unwrap(),
+                expected,
+                "chain {}",
+                chain_id
+            );
+            assert!(validate_evm_address_with_chain_id(expected, chain_id, true).is_ok());
+        }
+    }
+}
+
+#[test]
+fn eip1191_checksum_is_chain_specific() {
+    // A chain 30 checksum does not validate on chain 31
+    assert!(matches!(
+        validate_evm_address_with_chain_id(EIP1191_RSK_MAINNET[0], 31, true),
+        Err(AddressError::InvalidChecksum)
+    ));
+    // Nor is it valid EIP-55
+    assert!(validate_evm_address(EIP1191_RSK_MAINNET[0], true).is_err());
+    // Without checksum enforcement only the format matters
+    assert!(validate_evm_address_with_chain_id(EIP1191_RSK_MAINNET[0], 31, false).is_ok());
+}
@@ -1,12 +1,66 @@
 use async_trait::async_trait;
 use chrono::Utc;
-use phoenix_evidence::anchor::{AnchorError, AnchorProvider};
+use phoenix_evidence::anchor::{AnchorError, AnchorProvider, BalanceCheck};
 use phoenix_evidence::model::{ChainTxRef, EvidenceRecord};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use std::sync::Arc;
 use std::time::Duration;
 
+pub mod transaction;
+
+use transaction::{Eip1559Fees, Eip1559Transaction, GasOverrides, NonceTracker};
+
+/// Largest transaction payload Etherlink nodes admit to the mempool
+/// (go-ethereum's `txMaxSize`, 4 slots of 32 KiB)
+pub const MAX_TRANSACTION_DATA_SIZE: usize = 4 * 32 * 1024;
+
+/// Default blocks that must be built on top of a transaction's block before
+/// `confirm` reports it confirmed
+pub const DEFAULT_CONFIRMATION_DEPTH: u64 = 2;
+
+/// Recipient of memo transactions (the data is the payload, not a call)
+pub const MEMO_RECIPIENT: &str = "0x0000000000000000000000000000000000000000";
+
+/// Networks `ETHERLINK_NETWORK` may name, with the EIP-155 chain id their
+/// nodes report from `eth_chainId` (`testnet` is an alias for Ghostnet)
+pub const KNOWN_NETWORKS: [(&str, u64); 3] = [
+    ("mainnet", 42793),
+    ("ghostnet", 128123),
+    ("testnet", 128123),
+];
+
+/// Names accepted for `ETHERLINK_NETWORK`
+pub fn known_networks() -> Vec<&'static str> {
+    KNOWN_NETWORKS.iter().map(|(name, _)| *name).collect()
+}
+
+/// Chain id of a known network
+pub fn chain_id_for(network: &str) -> Option<u64> {
+    KNOWN_NETWORKS
+        .iter()
+        .find(|(name, _)| *name == network)
+        .map(|(_, chain_id)| *chain_id)
+}
+
+/// Env var overriding [`DEFAULT_CONFIRMATION_DEPTH`]
+pub const ETHERLINK_CONFIRMATION_DEPTH_ENV: &str = "ETHERLINK_CONFIRMATION_DEPTH";
+
+/// Confirmation depth from `ETHERLINK_CONFIRMATION_DEPTH`, or the default
+/// when unset
+pub fn confirmation_depth_from_env() -> Result<u64, AnchorError> {
+    match std::env::var(ETHERLINK_CONFIRMATION_DEPTH_ENV) {
+        Ok(value) if !value.trim().is_empty() => value.trim().parse::<u64>().map_err(|e| {
+            AnchorError::Invalid(format!(
+                "invalid {} '{}': {}",
+                ETHERLINK_CONFIRMATION_DEPTH_ENV, value, e
+            ))
+        }),
+        _ => Ok(DEFAULT_CONFIRMATION_DEPTH),
+    }
+}
+
 #[derive(Clone)]
 pub struct EtherlinkProviderStub;
 
@@ -29,6 +83,10 @@ impl AnchorProvider for EtherlinkProviderStub {
         confirmed_tx.confirmed = true;
         Ok(confirmed_tx)
     }
+
+    fn network(&self) -> Option<&str> {
+        Some("etherlink")
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -38,6 +96,15 @@ pub struct EtherlinkProvider {
     pub network: String,
     #[allow(dead_code)]
     pub private_key: Option<String>,
+    /// Blocks needed on top of a receipt's block before `confirm` reports
+    /// the transaction confirmed (0 = included is enough)
+    pub confirmation_depth: u64,
+    /// Account anchors are sent from; fees and nonces are only prepared
+    /// when set
+    pub sender: Option<String>,
+    /// Manual gas limit / fee values replacing the estimates
+    pub gas_overrides: GasOverrides,
+    nonces: Arc<NonceTracker>,
 }
 
 #[derive(Debug, Serialize)]
@@ -54,10 +121,20 @@ pub struct JsonRpcResponse {
     pub jsonrpc: String,
     #[allow(dead_code)]
     pub id: u64,
+    /// `Some(Value::Null)` for an explicit `"result": null` (e.g. no receipt
+    /// yet), `None` only when the field is absent
+    #[serde(default, deserialize_with = "present_value")]
     pub result: Option<Value>,
     pub error: Option<JsonRpcError>,
 }
 
+fn present_value<'de, D>(deserializer: D) -> Result<Option<Value>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Value::deserialize(deserializer).map(Some)
+}
+
 #[derive(Debug, Deserialize)]
 pub struct JsonRpcError {
     pub code: i32,
@@ -92,9 +169,133 @@ impl EtherlinkProvider {
             endpoint,
             network,
             private_key,
+            confirmation_depth: DEFAULT_CONFIRMATION_DEPTH,
+            sender: None,
+            gas_overrides: GasOverrides::default(),
+            nonces: Arc::new(NonceTracker::default()),
         })
     }
 
+    /// Set the confirmation depth (default [`DEFAULT_CONFIRMATION_DEPTH`])
+    pub fn with_confirmation_depth(mut self, depth: u64) -> Self {
+        self.confirmation_depth = depth;
+        self
+    }
+
+    /// Set the sending account (`0x`-prefixed address)
+    pub fn with_sender(mut self, sender: impl Into<String>) -> Self {
+        self.sender = Some(sender.into());
+        self
+    }
+
+    /// Replace estimated gas values with manual ones
+    pub fn with_gas_overrides(mut self, overrides: GasOverrides) -> Self {
+        self.gas_overrides = overrides;
+        self
+    }
+
+    /// Build the EIP-1559 transaction carrying `data` (`0x`-prefixed
+    /// calldata) from the configured sender
+    ///
+    /// The gas limit comes from `eth_estimateGas` and the fees from
+    /// `eth_feeHistory` unless overridden. The nonce is only a preview from
+    /// `eth_getTransactionCount(pending)`: nothing is reserved until
+    /// [`send_prepared`](Self::send_prepared) broadcasts the transaction.
+    pub async fn prepare_transaction(&self, data: &str) -> Result<Eip1559Transaction, AnchorError> {
+        let from = self
+            .sender
+            .clone()
+            .ok_or_else(|| AnchorError::Invalid("Etherlink sender address not set".to_string()))?;
+        let overrides = &self.gas_overrides;
+
+        let gas_limit = match overrides.gas_limit {
+            Some(limit) => limit,
+            None => {
+                let call = json!({ "from": from, "to": MEMO_RECIPIENT, "data": data });
+                let estimate = self.rpc_call("eth_estimateGas", json!([call])).await?;
+                let estimate = estimate.as_str().and_then(parse_quantity).ok_or_else(|| {
+                    AnchorError::Provider(format!("Invalid gas estimate: {}", estimate))
+                })?;
+                transaction::gas_limit_with_headroom(estimate)
+            }
+        };
+
+        let fees = match (
+            overrides.max_fee_per_gas,
+            overrides.max_priority_fee_per_gas,
+        ) {
+            (Some(max_fee_per_gas), Some(max_priority_fee_per_gas)) => Eip1559Fees {
+                max_fee_per_gas,
+                max_priority_fee_per_gas,
+            },
+            _ => {
+                let history = self
+                    .rpc_call(
+                        "eth_feeHistory",
+                        json!([
+                            transaction::quantity(transaction::FEE_HISTORY_BLOCKS),
+                            "latest",
+                            [transaction::PRIORITY_FEE_PERCENTILE]
+                        ]),
+                    )
+                    .await?;
+                Eip1559Fees::from_fee_history(&history)?.with_overrides(overrides)
+            }
+        };
+
+        let nonce = self.nonces.peek(self.pending_nonce(&from).await?);
+
+        Ok(Eip1559Transaction {
+            from,
+            to: MEMO_RECIPIENT.to_string(),
+            data: data.to_string(),
+            nonce,
+            gas_limit,
+            max_fee_per_gas: fees.max_fee_per_gas,
+            max_priority_fee_per_gas: fees.max_priority_fee_per_gas,
+        })
+    }
+
+    /// Broadcast a prepared transaction through `send` (sign and
+    /// `eth_sendRawTransaction`), returning its hash
+    ///
+    /// The nonce is reserved just before `send` and replaces the previewed
+    /// one; it is given back if `send` fails. Clones of this provider share
+    /// the reservations.
+    pub async fn send_prepared<F, Fut>(
+        &self,
+        mut tx: Eip1559Transaction,
+        send: F,
+    ) -> Result<String, AnchorError>
+    where
+        F: FnOnce(Eip1559Transaction) -> Fut,
+        Fut: std::future::Future<Output = Result<String, AnchorError>>,
+    {
+        let reservation = self.nonces.reserve(self.pending_nonce(&tx.from).await?);
+        tx.nonce = reservation.nonce();
+        match send(tx).await {
+            Ok(tx_hash) => {
+                reservation.sent();
+                Ok(tx_hash)
+            }
+            Err(e) => {
+                reservation.release();
+                Err(e)
+            }
+        }
+    }
+
+    /// The node's `eth_getTransactionCount(pending)` for `from`
+    async fn pending_nonce(&self, from: &str) -> Result<u64, AnchorError> {
+        let pending = self
+            .rpc_call("eth_getTransactionCount", json!([from, "pending"]))
+            .await?;
+        pending
+            .as_str()
+            .and_then(parse_quantity)
+            .ok_or_else(|| AnchorError::Provider(format!("Invalid transaction count: {}", pending)))
+    }
+
     async fn rpc_call(&self, method: &str, params: Value) -> Result<Value, AnchorError> {
         let request = JsonRpcRequest {
             jsonrpc: "2.0".to_string(),
@@ -136,14 +337,27 @@ impl EtherlinkProvider {
     }
 
     async fn send_memo_transaction(&self, memo_data: &str) -> Result<String, AnchorError> {
-        // Create a simple transaction with memo data
-        // In a real implementation, you'd sign this with the private key
-        let _tx_data = json!({
-            "to": "0x0000000000000000000000000000000000000000", // null address for memo
-            "data": format!("0x{}", hex::encode(memo_data.as_bytes())),
-            "gas": "0x5208", // 21000 gas
-            "gasPrice": "0x3b9aca00" // 1 gwei
-        });
+        if memo_data.len() > MAX_TRANSACTION_DATA_SIZE {
+            return Err(AnchorError::PayloadTooLarge {
+                size: memo_data.len(),
+                limit: MAX_TRANSACTION_DATA_SIZE,
+            });
+        }
+
+        // Fees are real once a sender is configured; signing and
+        // eth_sendRawTransaction are still to come, so the hash is simulated
+        // and no nonce is reserved (see send_prepared)
+        if self.sender.is_some() {
+            let data = format!("0x{}", hex::encode(memo_data.as_bytes()));
+            let tx = self.prepare_transaction(&data).await?;
+            tracing::debug!(
+                nonce = tx.nonce,
+                gas_limit = tx.gas_limit,
+                max_fee_per_gas = %tx.max_fee_per_gas,
+                max_priority_fee_per_gas = %tx.max_priority_fee_per_gas,
+                "Prepared Etherlink memo transaction"
+            );
+        }
 
         // Create a memo transaction with the provided data
         // In production, you'd call eth_sendTransaction or eth_sendRawTransaction
@@ -178,6 +392,44 @@ impl EtherlinkProvider {
 
         Ok(Some(receipt))
     }
+
+    /// Chain id the endpoint reports (`eth_chainId`)
+    pub async fn chain_id(&self) -> Result<u64, AnchorError> {
+        let result = self.rpc_call("eth_chainId", json!([])).await?;
+        result
+            .as_str()
+            .and_then(parse_quantity)
+            .ok_or_else(|| AnchorError::Provider(format!("Invalid chain id: {}", result)))
+    }
+
+    /// Balance of the sending account in wei (`eth_getBalance`), if a
+    /// sender is configured
+    pub async fn balance(&self) -> Result<Option<u128>, AnchorError> {
+        let Some(sender) = &self.sender else {
+            return Ok(None);
+        };
+        let result = self
+            .rpc_call("eth_getBalance", json!([sender, "latest"]))
+            .await?;
+        result
+            .as_str()
+            .and_then(transaction::parse_quantity)
+            .map(Some)
+            .ok_or_else(|| AnchorError::Provider(format!("Invalid balance: {}", result)))
+    }
+
+    async fn block_number(&self) -> Result<u64, AnchorError> {
+        let result = self.rpc_call("eth_blockNumber", json!([])).await?;
+        result
+            .as_str()
+            .and_then(parse_quantity)
+            .ok_or_else(|| AnchorError::Provider(format!("Invalid block number: {}", result)))
+    }
+}
+
+/// Parse a JSON-RPC hex quantity (`0x1a`) that fits a u64
+fn parse_quantity(value: &str) -> Option<u64> {
+    transaction::parse_quantity(value).and_then(|v| u64::try_from(v).ok())
 }
 
 #[async_trait]
@@ -197,26 +449,95 @@ impl AnchorProvider for EtherlinkProvider {
         })
     }
 
+    /// Confirmed once the receipt reports success (`status == 0x1`) and at
+    /// least `confirmation_depth` blocks have been built on top of it. No
+    /// receipt yet means pending; a reverted receipt (`status == 0x0`) is
+    /// `AnchorError::Reverted`.
     async fn confirm(&self, tx: &ChainTxRef) -> Result<ChainTxRef, AnchorError> {
-        let receipt = self.get_transaction_receipt(&tx.tx_id).await?;
-
         let mut confirmed_tx = tx.clone();
+        confirmed_tx.confirmed = false;
+
+        let Some(receipt) = self.get_transaction_receipt(&tx.tx_id).await? else {
+            return Ok(confirmed_tx);
+        };
+        let Some(receipt_block) = receipt.block_number.as_deref().and_then(parse_quantity) else {
+            // Not mined yet
+            return Ok(confirmed_tx);
+        };
 
-        if let Some(receipt) = receipt {
-            // Check if transaction is confirmed (has block number and successful status)
-            let is_confirmed =
-                receipt.block_number.is_some() && receipt.status.as_deref() == Some("0x1");
-
-            confirmed_tx.confirmed = is_confirmed;
-            if is_confirmed {
-                tracing::info!(
-                    tx_id = %tx.tx_id,
-                    block_number = ?receipt.block_number,
-                    "Transaction confirmed on Etherlink"
-                );
+        match receipt.status.as_deref() {
+            Some("0x1") => {}
+            Some("0x0") => {
+                return Err(AnchorError::Reverted(format!(
+                    "transaction {} reverted in block {}",
+                    tx.tx_id, receipt_block
+                )));
             }
+            other => {
+                return Err(AnchorError::Provider(format!(
+                    "unexpected receipt status {:?} for {}",
+                    other, tx.tx_id
+                )));
+            }
+        }
+
+        let depth = if self.confirmation_depth == 0 {
+            0
+        } else {
+            self.block_number().await?.saturating_sub(receipt_block)
+        };
+        confirmed_tx.confirmed = depth >= self.confirmation_depth;
+        if confirmed_tx.confirmed {
+            tracing::info!(
+                tx_id = %tx.tx_id,
+                block_number = receipt_block,
+                depth,
+                "Transaction confirmed on Etherlink"
+            );
+        } else {
+            tracing::debug!(
+                tx_id = %tx.tx_id,
+                block_number = receipt_block,
+                depth,
+                required = self.confirmation_depth,
+                "Transaction not yet at required depth"
+            );
         }
 
         Ok(confirmed_tx)
     }
+
+    fn network(&self) -> Option<&str> {
+        Some("etherlink")
+    }
+
+    /// The network must be one of [`KNOWN_NETWORKS`] and the endpoint must
+    /// report its chain id
+    async fn validate_network(&self) -> Result<(), AnchorError> {
+        let expected = chain_id_for(&self.network).ok_or_else(|| {
+            AnchorError::Invalid(format!(
+                "unknown Etherlink network '{}' (expected one of: {})",
+                self.network,
+                known_networks().join(", ")
+            ))
+        })?;
+        let reported = self.chain_id().await?;
+        if reported != expected {
+            return Err(AnchorError::Invalid(format!(
+                "Etherlink endpoint {} reports chain id {}, but network '{}' is chain id {}",
+                self.endpoint, reported, self.network, expected
+            )));
+        }
+        Ok(())
+    }
+
+    async fn check_sufficient_balance(
+        &self,
+        min: u128,
+    ) -> Result<Option<BalanceCheck>, AnchorError> {
+        Ok(self.balance().await?.map(|balance| BalanceCheck {
+            balance,
+            minimum: min,
+        }))
+    }
 }
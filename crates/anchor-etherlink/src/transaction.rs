@@ -0,0 +1,430 @@
+//! EIP-1559 transaction parameters for memo anchoring
+//!
+//! Fees follow the usual wallet heuristic over `eth_feeHistory`: the tip is
+//! the median of recent blocks' [`PRIORITY_FEE_PERCENTILE`] rewards, and the
+//! fee cap is twice the next block's base fee plus that tip, so the
+//! transaction stays includable through a few blocks of rising base fees.
+//! Gas limits are the node's `eth_estimateGas` plus [`GAS_LIMIT_HEADROOM_PCT`].
+//!
+//! Nonces come from [`NonceTracker`], which hands out one nonce per
+//! in-flight transaction so concurrent anchors never reuse one. A nonce is
+//! only reserved for a broadcast and is given back if the send fails.
+
+use phoenix_evidence::anchor::AnchorError;
+use serde_json::{json, Value};
+use std::collections::BTreeSet;
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+/// Blocks of fee history sampled per estimate
+pub const FEE_HISTORY_BLOCKS: u64 = 10;
+
+/// Reward percentile requested from `eth_feeHistory`
+pub const PRIORITY_FEE_PERCENTILE: u64 = 50;
+
+/// Extra gas added on top of `eth_estimateGas`, in percent
+pub const GAS_LIMIT_HEADROOM_PCT: u64 = 20;
+
+/// How long after the last broadcast the node's pending count may lag the
+/// local counter before [`NonceTracker`] trusts the node again
+pub const NONCE_RESYNC_AFTER: Duration = Duration::from_secs(30);
+
+/// Manual values that replace the estimated ones
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GasOverrides {
+    /// Skip `eth_estimateGas` and use this limit
+    pub gas_limit: Option<u64>,
+    /// Fee cap in wei per gas
+    pub max_fee_per_gas: Option<u128>,
+    /// Tip in wei per gas
+    pub max_priority_fee_per_gas: Option<u128>,
+}
+
+impl GasOverrides {
+    /// Read from `ETHERLINK_GAS_LIMIT`, `ETHERLINK_MAX_FEE_PER_GAS` and
+    /// `ETHERLINK_MAX_PRIORITY_FEE_PER_GAS` (decimal; wei for the fees)
+    pub fn from_env() -> Result<Self, AnchorError> {
+        fn read<T: std::str::FromStr>(name: &str) -> Result<Option<T>, AnchorError>
+        where
+            T::Err: std::fmt::Display,
+        {
+            match std::env::var(name) {
+                Ok(value) if !value.trim().is_empty() => {
+                    value.trim().parse::<T>().map(Some).map_err(|e| {
+                        AnchorError::Invalid(format!("invalid {} '{}': {}", name, value, e))
+                    })
+                }
+                _ => Ok(None),
+            }
+        }
+        Ok(Self {
+            gas_limit: read("ETHERLINK_GAS_LIMIT")?,
+            max_fee_per_gas: read("ETHERLINK_MAX_FEE_PER_GAS")?,
+            max_priority_fee_per_gas: read("ETHERLINK_MAX_PRIORITY_FEE_PER_GAS")?,
+        })
+    }
+}
+
+/// Fee cap and tip for a type-2 transaction, in wei per gas
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Eip1559Fees {
+    pub max_fee_per_gas: u128,
+    pub max_priority_fee_per_gas: u128,
+}
+
+impl Eip1559Fees {
+    /// Fees from an `eth_feeHistory` result
+    pub fn from_fee_history(history: &Value) -> Result<Self, AnchorError> {
+        let invalid = |what: &str| AnchorError::Provider(format!("Invalid fee history: {}", what));
+
+        // One entry per sampled block plus the next block's base fee
+        let next_base_fee = history
+            .get("baseFeePerGas")
+            .and_then(Value::as_array)
+            .and_then(|fees| fees.last())
+            .and_then(Value::as_str)
+            .and_then(parse_quantity)
+            .ok_or_else(|| invalid("missing baseFeePerGas"))?;
+
+        let mut rewards = history
+            .get("reward")
+            .and_then(Value::as_array)
+            .map(|blocks| {
+                blocks
+                    .iter()
+                    .filter_map(|block| block.get(0).and_then(Value::as_str))
+                    .map(|reward| parse_quantity(reward).ok_or_else(|| invalid("bad reward")))
+                    .collect::<Result<Vec<u128>, AnchorError>>()
+            })
+            .transpose()?
+            .unwrap_or_default();
+        rewards.sort_unstable();
+        let tip = rewards.get(rewards.len() / 2).copied().unwrap_or(0);
+
+        Ok(Self {
+            max_fee_per_gas: next_base_fee.saturating_mul(2).saturating_add(tip),
+            max_priority_fee_per_gas: tip,
+        })
+    }
+
+    /// Apply manual fee overrides; an overridden tip alone keeps the
+    /// estimated base-fee headroom under the cap
+    pub fn with_overrides(self, overrides: &GasOverrides) -> Self {
+        let tip = overrides
+            .max_priority_fee_per_gas
+            .unwrap_or(self.max_priority_fee_per_gas);
+        let max_fee = overrides.max_fee_per_gas.unwrap_or_else(|| {
+            self.max_fee_per_gas
+                .saturating_sub(self.max_priority_fee_per_gas)
+                .saturating_add(tip)
+        });
+        Self {
+            max_fee_per_gas: max_fee,
+            max_priority_fee_per_gas: tip,
+        }
+    }
+}
+
+/// Gas limit for an `eth_estimateGas` result, with headroom
+pub fn gas_limit_with_headroom(estimate: u64) -> u64 {
+    estimate.saturating_add(estimate.saturating_mul(GAS_LIMIT_HEADROOM_PCT) / 100)
+}
+
+/// Parse a JSON-RPC hex quantity (`0x1a`)
+pub fn parse_quantity(value: &str) -> Option<u128> {
+    u128::from_str_radix(value.strip_prefix("0x")?, 16).ok()
+}
+
+/// Format a JSON-RPC hex quantity
+pub fn quantity(value: impl Into<u128>) -> String {
+    format!("0x{:x}", value.into())
+}
+
+/// A type-2 (EIP-1559) transaction ready to be signed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Eip1559Transaction {
+    pub from: String,
+    pub to: String,
+    /// `0x`-prefixed calldata
+    pub data: String,
+    pub nonce: u64,
+    pub gas_limit: u64,
+    pub max_fee_per_gas: u128,
+    pub max_priority_fee_per_gas: u128,
+}
+
+impl Eip1559Transaction {
+    /// JSON-RPC transaction object (as taken by `eth_sendTransaction` or a
+    /// signer)
+    pub fn to_json(&self) -> Value {
+        json!({
+            "type": "0x2",
+            "from": self.from,
+            "to": self.to,
+            "data": self.data,
+            "nonce": quantity(self.nonce),
+            "gas": quantity(self.gas_limit),
+            "maxFeePerGas": quantity(self.max_fee_per_gas),
+            "maxPriorityFeePerGas": quantity(self.max_priority_fee_per_gas),
+        })
+    }
+}
+
+/// Next nonce for one sender, shared by every in-flight transaction
+///
+/// Each reservation takes the larger of the node's pending count and one
+/// past the last nonce handed out, so back-to-back or concurrent broadcasts
+/// get sequential nonces even before the node has seen the earlier ones, and
+/// transactions sent by other tools are skipped over. A failed send releases
+/// its nonce; once nothing is in flight and the node's pending count has
+/// stayed below the local counter for [`NONCE_RESYNC_AFTER`], the missing
+/// nonces never reached the node and the tracker starts over from its count.
+#[derive(Debug, Default)]
+pub struct NonceTracker {
+    state: Mutex<NonceState>,
+}
+
+#[derive(Debug, Default)]
+struct NonceState {
+    /// One past the last nonce handed out
+    next: Option<u64>,
+    /// Reserved nonces whose send hasn't finished
+    in_flight: BTreeSet<u64>,
+    /// When the last send went out
+    last_sent: Option<Instant>,
+}
+
+impl NonceTracker {
+    /// Reserve a nonce for a broadcast given the node's
+    /// `eth_getTransactionCount(pending)`
+    pub fn reserve(&self, pending: u64) -> NonceReservation<'_> {
+        self.reserve_at(pending, Instant::now())
+    }
+
+    fn reserve_at(&self, pending: u64, now: Instant) -> NonceReservation<'_> {
+        let mut state = self.lock();
+        let behind = state.next.is_some_and(|next| next > pending);
+        let settled = state.in_flight.is_empty()
+            && state
+                .last_sent
+                .is_none_or(|sent| now.saturating_duration_since(sent) >= NONCE_RESYNC_AFTER);
+        if behind && settled {
+            tracing::warn!(
+                local = ?state.next,
+                pending,
+                "Node's pending nonce stayed below the local counter; resyncing"
+            );
+            state.next = None;
+        }
+
+        let nonce = state.next.map_or(pending, |next| next.max(pending));
+        state.next = Some(nonce + 1);
+        state.in_flight.insert(nonce);
+        NonceReservation {
+            tracker: self,
+            nonce,
+            settled: false,
+        }
+    }
+
+    /// The nonce the next reservation would get, without reserving it
+    pub fn peek(&self, pending: u64) -> u64 {
+        self.lock().next.map_or(pending, |next| next.max(pending))
+    }
+
+    /// Forget local state so the next reservation trusts the node (e.g.
+    /// after a transaction was dropped and its nonce must be reused)
+    pub fn reset(&self) {
+        *self.lock() = NonceState::default();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, NonceState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn sent_at(&self, nonce: u64, now: Instant) {
+        let mut state = self.lock();
+        state.in_flight.remove(&nonce);
+        state.last_sent = Some(now);
+    }
+
+    fn release(&self, nonce: u64) {
+        let mut state = self.lock();
+        state.in_flight.remove(&nonce);
+        // Hand it out again unless a later nonce is still being sent; a gap
+        // left behind is resynced once the node's count stays below it
+        if !state.in_flight.iter().any(|&other| other > nonce) {
+            state.next = state.next.map(|next| next.min(nonce));
+        }
+    }
+}
+
+/// A nonce reserved for one broadcast
+///
+/// Settle it with [`sent`](Self::sent) or [`release`](Self::release). Dropped
+/// unsettled (e.g. the send was cancelled), it counts as sent, since the
+/// transaction may have reached the node.
+#[derive(Debug)]
+pub struct NonceReservation<'a> {
+    tracker: &'a NonceTracker,
+    nonce: u64,
+    settled: bool,
+}
+
+impl NonceReservation<'_> {
+    pub fn nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    /// The node accepted the transaction
+    pub fn sent(self) {
+        self.sent_at(Instant::now());
+    }
+
+    fn sent_at(mut self, now: Instant) {
+        self.settled = true;
+        self.tracker.sent_at(self.nonce, now);
+    }
+
+    /// The send failed; the nonce can be handed out again
+    pub fn release(mut self) {
+        self.settled = true;
+        self.tracker.release(self.nonce);
+    }
+}
+
+impl Drop for NonceReservation<'_> {
+    fn drop(&mut self) {
+        if !self.settled {
+            self.tracker.sent_at(self.nonce, Instant::now());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fees_from_fee_history() {
+        let history = json!({
+            "oldestBlock": "0x10",
+            "baseFeePerGas": ["0x3b9aca00", "0x3b9aca00", "0x77359400"],
+            "reward": [["0x5f5e100"], ["0x2faf080"]],
+        });
+        let fees = Eip1559Fees::from_fee_history(&history).unwrap();
+        // Median of 0.1 and 0.05 gwei (upper middle), next base fee 2 gwei
+        assert_eq!(fees.max_priority_fee_per_gas, 100_000_000);
+        assert_eq!(fees.max_fee_per_gas, 2 * 2_000_000_000 + 100_000_000);
+
+        let no_rewards = json!({ "baseFeePerGas": ["0x1", "0x2"] });
+        let fees = Eip1559Fees::from_fee_history(&no_rewards).unwrap();
+        assert_eq!(fees.max_priority_fee_per_gas, 0);
+        assert_eq!(fees.max_fee_per_gas, 4);
+
+        assert!(Eip1559Fees::from_fee_history(&json!({})).is_err());
+    }
+
+    #[test]
+    fn test_fee_overrides() {
+        let estimated = Eip1559Fees {
+            max_fee_per_gas: 4_100,
+            max_priority_fee_per_gas: 100,
+        };
+        let tip_only = GasOverrides {
+            max_priority_fee_per_gas: Some(500),
+            ..GasOverrides::default()
+        };
+        assert_eq!(
+            estimated.with_overrides(&tip_only),
+            Eip1559Fees {
+                max_fee_per_gas: 4_500,
+                max_priority_fee_per_gas: 500,
+            }
+        );
+        let cap_only = GasOverrides {
+            max_fee_per_gas: Some(9_000),
+            ..GasOverrides::default()
+        };
+        assert_eq!(estimated.with_overrides(&cap_only).max_fee_per_gas, 9_000);
+        assert_eq!(
+            estimated.with_overrides(&GasOverrides::default()),
+            estimated
+        );
+
+        // A hand-built max fee below its tip leaves no base-fee headroom
+        let inverted = Eip1559Fees {
+            max_fee_per_gas: 50,
+            max_priority_fee_per_gas: 100,
+        };
+        assert_eq!(
+            inverted.with_overrides(&tip_only),
+            Eip1559Fees {
+                max_fee_per_gas: 500,
+                max_priority_fee_per_gas: 500,
+            }
+        );
+    }
+
+    #[test]
+    fn test_nonce_tracker_sequences_and_catches_up() {
+        let tracker = NonceTracker::default();
+        tracker.reserve(7).sent();
+        // Node hasn't seen nonce 7 yet
+        let second = tracker.reserve(7);
+        assert_eq!(second.nonce(), 8);
+        second.sent();
+        // Another tool sent transactions meanwhile
+        assert_eq!(tracker.peek(12), 12);
+        let third = tracker.reserve(12);
+        assert_eq!(third.nonce(), 12);
+        third.sent();
+        tracker.reset();
+        assert_eq!(tracker.reserve(10).nonce(), 10);
+    }
+
+    #[test]
+    fn test_nonce_tracker_releases_failed_sends() {
+        let tracker = NonceTracker::default();
+        let first = tracker.reserve(7);
+        let second = tracker.reserve(7);
+        assert_eq!((first.nonce(), second.nonce()), (7, 8));
+
+        // 8 failed while 7 is still in flight: 8 is reused
+        second.release();
+        assert_eq!(tracker.peek(7), 8);
+        first.sent();
+        let retry = tracker.reserve(7);
+        assert_eq!(retry.nonce(), 8);
+
+        // A cancelled send keeps its nonce, as it may have been broadcast
+        drop(retry);
+        assert_eq!(tracker.peek(7), 9);
+    }
+
+    #[test]
+    fn test_nonce_tracker_resyncs_when_node_stays_behind() {
+        let tracker = NonceTracker::default();
+        let start = Instant::now();
+        tracker.reserve_at(7, start).sent_at(start);
+        tracker.reserve_at(7, start).sent_at(start);
+
+        // Shortly after sending, a lagging count is trusted less than ours
+        let soon = start + Duration::from_secs(1);
+        let reservation = tracker.reserve_at(7, soon);
+        assert_eq!(reservation.nonce(), 9);
+        reservation.release();
+
+        // Still behind long after: nonces 7 and 8 never reached the node
+        let later = start + NONCE_RESYNC_AFTER;
+        assert_eq!(tracker.reserve_at(7, later).nonce(), 7);
+    }
+
+    #[test]
+    fn test_gas_limit_headroom_and_quantities() {
+        assert_eq!(gas_limit_with_headroom(21_000), 25_200);
+        assert_eq!(quantity(255u64), "0xff");
+        assert_eq!(parse_quantity("0xff"), Some(255));
+        assert_eq!(parse_quantity("ff"), None);
+    }
+}
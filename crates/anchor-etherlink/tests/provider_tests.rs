@@ -1,8 +1,85 @@
-use anchor_etherlink::{EtherlinkProvider, EtherlinkProviderStub};
+use anchor_etherlink::transaction::GasOverrides;
+use anchor_etherlink::{
+    EtherlinkProvider, EtherlinkProviderStub, DEFAULT_CONFIRMATION_DEPTH,
+    MAX_TRANSACTION_DATA_SIZE, MEMO_RECIPIENT,
+};
 use chrono::Utc;
-use phoenix_evidence::anchor::AnchorProvider;
+use phoenix_evidence::anchor::{AnchorError, AnchorProvider};
 use phoenix_evidence::model::{ChainTxRef, DigestAlgo, EvidenceDigest, EvidenceRecord};
-use serde_json::json;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// JSON-RPC `result` per method served by a mock node; tests can change them
+/// between calls
+type RpcResults = Arc<Mutex<HashMap<&'static str, Value>>>;
+
+/// Minimal JSON-RPC node answering each call with the result set for its
+/// method (or a -32601 error), recording each request body it served
+async fn spawn_mock_rpc(results: RpcResults) -> (String, Arc<Mutex<Vec<Value>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let requests = Arc::new(Mutex::new(Vec::new()));
+    let recorded = requests.clone();
+
+    tokio::spawn(async move {
+        loop {
+            let (mut socket, _) = match listener.accept().await {
+                Ok(conn) => conn,
+                Err(_) => return,
+            };
+            // Read headers plus Content-Length bytes of body before replying
+            let mut buf = Vec::new();
+            let mut chunk = [0u8; 1024];
+            let body = loop {
+                let n = socket.read(&mut chunk).await.unwrap_or(0);
+                if n == 0 {
+                    break String::new();
+                }
+                buf.extend_from_slice(&chunk[..n]);
+                let text = String::from_utf8_lossy(&buf).to_string();
+                if let Some(header_end) = text.find("\r\n\r\n") {
+                    let content_length = text[..header_end]
+                        .lines()
+                        .find_map(|l| {
+                            l.to_ascii_lowercase()
+                                .strip_prefix("content-length:")
+                                .map(|v| v.trim().parse::<usize>().unwrap_or(0))
+                        })
+                        .unwrap_or(0);
+                    if buf.len() >= header_end + 4 + content_length {
+                        break text[header_end + 4..].to_string();
+                    }
+                }
+            };
+
+            let request: Value = serde_json::from_str(&body).unwrap_or(Value::Null);
+            let method = request["method"].as_str().unwrap_or_default().to_string();
+            recorded.lock().unwrap().push(request);
+            let reply = match results.lock().unwrap().get(method.as_str()) {
+                Some(result) => json!({"jsonrpc": "2.0", "id": 1, "result": result}),
+                None => json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "error": {"code": -32601, "message": "Method not found"}
+                }),
+            }
+            .to_string();
+
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                reply.len(),
+                reply
+            );
+            let _ = socket.write_all(response.as_bytes()).await;
+            let _ = socket.shutdown().await;
+        }
+    });
+
+    (url, requests)
+}
 
 #[tokio::test]
 async fn test_etherlink_provider_stub_anchor() {
@@ -208,4 +285,360 @@ fn test_json_rpc_error_response() {
     let error = response.error.unwrap();
     assert_eq!(error.code, -32601);
     assert_eq!(error.message, "Method not found");
+
+    // An explicit null result is kept, unlike a missing one
+    let response: JsonRpcResponse =
+        serde_json::from_str(r#"{"jsonrpc": "2.0", "id": 1, "result": null}"#).unwrap();
+    assert_eq!(response.result, Some(serde_json::Value::Null));
+}
+
+#[tokio::test]
+async fn test_etherlink_provider_rejects_oversized_payload() {
+    // Nothing listens here: the size check must fail before any RPC
+    let provider = EtherlinkProvider::new(
+        "http://127.0.0.1:9".to_string(),
+        "testnet".to_string(),
+        None,
+    )
+    .unwrap();
+
+    let evidence = EvidenceRecord {
+        id: "oversized".to_string(),
+        created_at: Utc::now(),
+        digest: EvidenceDigest {
+            algo: DigestAlgo::Sha256,
+            hex: "ab".repeat(MAX_TRANSACTION_DATA_SIZE),
+        },
+        payload_mime: None,
+        metadata: json!({}),
+    };
+
+    let result = provider.anchor(&evidence).await;
+    match result {
+        Err(AnchorError::PayloadTooLarge { size, limit }) => {
+            assert_eq!(limit, MAX_TRANSACTION_DATA_SIZE);
+            assert_eq!(size, "evidence:".len() + 2 * MAX_TRANSACTION_DATA_SIZE);
+        }
+        other => panic!("expected PayloadTooLarge, got {:?}", other),
+    }
+}
+
+fn etherlink_tx_ref() -> ChainTxRef {
+    ChainTxRef {
+        network: "etherlink".to_string(),
+        chain: "testnet".to_string(),
+        tx_id: "0xabc".to_string(),
+        confirmed: false,
+        timestamp: Some(Utc::now()),
+    }
+}
+
+fn receipt(block: &str, status: &str) -> Value {
+    json!({"transactionHash": "0xabc", "blockNumber": block, "status": status})
+}
+
+#[tokio::test]
+async fn test_etherlink_provider_confirm_pending_until_deep_enough() {
+    let results: RpcResults = Arc::new(Mutex::new(HashMap::from([
+        ("eth_getTransactionReceipt", Value::Null),
+        ("eth_blockNumber", json!("0x10")),
+    ])));
+    let (url, requests) = spawn_mock_rpc(results.clone()).await;
+    let provider = EtherlinkProvider::new(url, "testnet".to_string(), None).unwrap();
+    assert_eq!(provider.confirmation_depth, DEFAULT_CONFIRMATION_DEPTH);
+    let provider = provider.with_confirmation_depth(2);
+
+    // No receipt yet: pending
+    let tx = provider.confirm(&etherlink_tx_ref()).await.unwrap();
+    assert!(!tx.confirmed);
+    assert_eq!(requests.lock().unwrap()[0]["params"], json!(["0xabc"]));
+
+    // Mined in block 0x0f with the head at 0x10: one block deep, not enough
+    results
+        .lock()
+        .unwrap()
+        .insert("eth_getTransactionReceipt", receipt("0xf", "0x1"));
+    assert!(
+        !provider
+            .confirm(&etherlink_tx_ref())
+            .await
+            .unwrap()
+            .confirmed
+    );
+
+    // Head at 0x11: two blocks deep
+    results
+        .lock()
+        .unwrap()
+        .insert("eth_blockNumber", json!("0x11"));
+    assert!(
+        provider
+            .confirm(&etherlink_tx_ref())
+            .await
+            .unwrap()
+            .confirmed
+    );
+}
+
+#[tokio::test]
+async fn test_etherlink_provider_confirm_depth_zero_skips_block_number() {
+    let results: RpcResults = Arc::new(Mutex::new(HashMap::from([(
+        "eth_getTransactionReceipt",
+        receipt("0xf", "0x1"),
+    )])));
+    let (url, requests) = spawn_mock_rpc(results).await;
+    let provider = EtherlinkProvider::new(url, "testnet".to_string(), None)
+        .unwrap()
+        .with_confirmation_depth(0);
+
+    assert!(
+        provider
+            .confirm(&etherlink_tx_ref())
+            .await
+            .unwrap()
+            .confirmed
+    );
+    assert_eq!(requests.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_etherlink_provider_confirm_reports_revert() {
+    let results: RpcResults = Arc::new(Mutex::new(HashMap::from([
+        ("eth_getTransactionReceipt", receipt("0xf", "0x0")),
+        ("eth_blockNumber", json!("0x20")),
+    ])));
+    let (url, _) = spawn_mock_rpc(results).await;
+    let provider = EtherlinkProvider::new(url, "testnet".to_string(), None).unwrap();
+
+    match provider.confirm(&etherlink_tx_ref()).await {
+        Err(AnchorError::Reverted(reason)) => {
+            assert!(reason.contains("0xabc"));
+            assert!(reason.contains("block 15"));
+        }
+        other => panic!("expected Reverted, got {:?}", other),
+    }
+}
+
+const SENDER: &str = "0x1111111111111111111111111111111111111111";
+
+fn gas_rpc_results() -> RpcResults {
+    Arc::new(Mutex::new(HashMap::from([
+        ("eth_estimateGas", json!("0x5208")),
+        (
+            "eth_feeHistory",
+            json!({
+                "oldestBlock": "0x10",
+                "baseFeePerGas": ["0x3b9aca00", "0x3b9aca00", "0x3b9aca00"],
+                "reward": [["0x5f5e100"], ["0x5f5e100"]]
+            }),
+        ),
+        ("eth_getTransactionCount", json!("0x7")),
+    ])))
+}
+
+#[tokio::test]
+async fn test_etherlink_provider_prepares_eip1559_transaction() {
+    let (url, requests) = spawn_mock_rpc(gas_rpc_results()).await;
+    let provider = EtherlinkProvider::new(url, "testnet".to_string(), None)
+        .unwrap()
+        .with_sender(SENDER);
+
+    let tx = provider.prepare_transaction("0xabcd").await.unwrap();
+    assert_eq!(tx.from, SENDER);
+    assert_eq!(tx.to, MEMO_RECIPIENT);
+    assert_eq!(tx.nonce, 7);
+    // 21000 plus 20% headroom
+    assert_eq!(tx.gas_limit, 25_200);
+    // Tip 0.1 gwei, cap twice the 1 gwei base fee plus the tip
+    assert_eq!(tx.max_priority_fee_per_gas, 100_000_000);
+    assert_eq!(tx.max_fee_per_gas, 2_100_000_000);
+    assert_eq!(tx.to_json()["maxFeePerGas"], "0x7d2b7500");
+    assert_eq!(tx.to_json()["nonce"], "0x7");
+
+    let requests = requests.lock().unwrap();
+    let methods: Vec<&str> = requests
+        .iter()
+        .map(|r| r["method"].as_str().unwrap())
+        .collect();
+    assert_eq!(
+        methods,
+        [
+            "eth_estimateGas",
+            "eth_feeHistory",
+            "eth_getTransactionCount"
+        ]
+    );
+    assert_eq!(requests[0]["params"][0]["data"], "0xabcd");
+    assert_eq!(requests[2]["params"], json!([SENDER, "pending"]));
+}
+
+#[tokio::test]
+async fn test_etherlink_provider_reserves_nonces_only_for_broadcasts() {
+    let (url, requests) = spawn_mock_rpc(gas_rpc_results()).await;
+    let provider = EtherlinkProvider::new(url, "testnet".to_string(), None)
+        .unwrap()
+        .with_sender(SENDER);
+
+    // Preparing and (simulated) anchoring reserve nothing
+    let first = provider.prepare_transaction("0x01").await.unwrap();
+    let second = provider.clone().prepare_transaction("0x02").await.unwrap();
+    assert_eq!((first.nonce, second.nonce), (7, 7));
+    let evidence = EvidenceRecord {
+        id: "nonce".to_string(),
+        created_at: Utc::now(),
+        digest: EvidenceDigest {
+            algo: DigestAlgo::Sha256,
+            hex: "ab".repeat(32),
+        },
+        payload_mime: None,
+        metadata: json!({}),
+    };
+    provider.anchor(&evidence).await.unwrap();
+    assert_eq!(provider.prepare_transaction("0x03").await.unwrap().nonce, 7);
+    assert!(requests
+        .lock()
+        .unwrap()
+        .iter()
+        .any(|r| r["method"] == "eth_estimateGas"
+            && r["params"][0]["data"] == format!("0x{}", hex_memo(&evidence))));
+
+    // Back-to-back broadcasts get sequential nonces while the node still
+    // reports 7 pending
+    let sent = |tx: anchor_etherlink::transaction::Eip1559Transaction| async move {
+        Ok(format!("0x{:064x}", tx.nonce))
+    };
+    let hash = provider.send_prepared(first, sent).await.unwrap();
+    assert!(hash.ends_with('7'));
+    let hash = provider.clone().send_prepared(second, sent).await.unwrap();
+    assert!(hash.ends_with('8'));
+
+    // A failed send gives its nonce back
+    let tx = provider.prepare_transaction("0x04").await.unwrap();
+    assert_eq!(tx.nonce, 9);
+    let failed = provider
+        .send_prepared(tx.clone(), |_| async {
+            Err(AnchorError::Network("connection reset".to_string()))
+        })
+        .await;
+    assert!(matches!(failed, Err(AnchorError::Network(_))));
+    assert_eq!(
+        provider.send_prepared(tx, sent).await.unwrap(),
+        format!("0x{:064x}", 9)
+    );
+}
+
+fn hex_memo(evidence: &EvidenceRecord) -> String {
+    format!("evidence:{}", evidence.digest.hex)
+        .bytes()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_etherlink_provider_gas_overrides_skip_estimates() {
+    let (url, requests) = spawn_mock_rpc(gas_rpc_results()).await;
+    let provider = EtherlinkProvider::new(url, "testnet".to_string(), None)
+        .unwrap()
+        .with_sender(SENDER)
+        .with_gas_overrides(GasOverrides {
+            gas_limit: Some(50_000),
+            max_fee_per_gas: Some(5_000_000_000),
+            max_priority_fee_per_gas: Some(2_000_000_000),
+        });
+
+    let tx = provider.prepare_transaction("0xabcd").await.unwrap();
+    assert_eq!(tx.gas_limit, 50_000);
+    assert_eq!(tx.max_fee_per_gas, 5_000_000_000);
+    assert_eq!(tx.max_priority_fee_per_gas, 2_000_000_000);
+    assert_eq!(tx.nonce, 7);
+    assert_eq!(requests.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_etherlink_provider_prepare_requires_sender() {
+    let provider = EtherlinkProvider::new(
+        "http://127.0.0.1:9".to_string(),
+        "testnet".to_string(),
+        None,
+    )
+    .unwrap();
+    assert!(matches!(
+        provider.prepare_transaction("0x").await,
+        Err(AnchorError::Invalid(_))
+    ));
+}
+
+#[tokio::test]
+async fn test_etherlink_provider_validates_chain_id() {
+    let results: RpcResults = Arc::new(Mutex::new(HashMap::from([(
+        "eth_chainId",
+        json!("0xa729"),
+    )])));
+    let (url, _requests) = spawn_mock_rpc(results.clone()).await;
+
+    // 0xa729 = 42793, Etherlink mainnet
+    let mainnet = EtherlinkProvider::new(url.clone(), "mainnet".to_string(), None).unwrap();
+    mainnet.validate_network().await.unwrap();
+
+    // A Ghostnet config pointed at a mainnet node fails
+    let ghostnet = EtherlinkProvider::new(url, "ghostnet".to_string(), None).unwrap();
+    match ghostnet.validate_network().await {
+        Err(AnchorError::Invalid(message)) => {
+            assert!(message.contains("chain id 42793"), "{}", message);
+            assert!(message.contains("128123"), "{}", message);
+        }
+        other => panic!("expected a chain id mismatch, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_etherlink_provider_rejects_unknown_network_before_rpc() {
+    let results: RpcResults = Arc::new(Mutex::new(HashMap::new()));
+    let (url, requests) = spawn_mock_rpc(results).await;
+    let provider = EtherlinkProvider::new(url, "mainet".to_string(), None).unwrap();
+
+    match provider.validate_network().await {
+        Err(AnchorError::Invalid(message)) => {
+            assert!(message.contains("unknown Etherlink network 'mainet'"));
+            assert!(message.contains("mainnet, ghostnet, testnet"));
+        }
+        other => panic!("expected an unknown network error, got {:?}", other),
+    }
+    assert!(requests.lock().unwrap().is_empty());
+    assert_eq!(anchor_etherlink::chain_id_for("testnet"), Some(128123));
+}
+
+#[tokio::test]
+async fn test_etherlink_provider_checks_sender_balance() {
+    // 0x2386f26fc10000 = 10^16 wei (0.01 XTZ)
+    let results: RpcResults = Arc::new(Mutex::new(HashMap::from([(
+        "eth_getBalance",
+        json!("0x2386f26fc10000"),
+    )])));
+    let (url, requests) = spawn_mock_rpc(results).await;
+    let provider = EtherlinkProvider::new(url, "testnet".to_string(), None).unwrap();
+
+    // No sender, no wallet to check
+    assert_eq!(provider.check_sufficient_balance(1).await.unwrap(), None);
+    assert!(requests.lock().unwrap().is_empty());
+
+    let provider = provider.with_sender("0x00000000000000000000000000000000000000aa");
+    let check = provider
+        .check_sufficient_balance(10_000_000_000_000_000)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(check.balance, 10_000_000_000_000_000);
+    assert!(check.is_sufficient());
+    assert_eq!(
+        requests.lock().unwrap()[0]["params"],
+        json!(["0x00000000000000000000000000000000000000aa", "latest"])
+    );
+
+    let check = provider
+        .check_sufficient_balance(10_000_000_000_000_001)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(!check.is_sufficient());
 }
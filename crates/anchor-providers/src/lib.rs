@@ -0,0 +1,164 @@
+//! Anchor providers selected by spec string.
+//!
+//! A spec names one backend (`etherlink`, `solana`, or their `-stub`
+//! variants). Each provider reads its own settings from its env prefix
+//! (`ETHERLINK_*`, `SOLANA_*`), so the keeper and the API's inline anchoring
+//! build providers the same way.
+
+use anchor_etherlink::{transaction::GasOverrides, EtherlinkProvider, EtherlinkProviderStub};
+use anchor_solana::{PriorityFee, SolanaProvider, SolanaProviderStub};
+use phoenix_evidence::anchor::AnchorProvider;
+use std::str::FromStr;
+
+/// Specs accepted by [`provider_from_spec`]
+pub const KNOWN_SPECS: [&str; 4] = ["etherlink", "etherlink-stub", "solana", "solana-stub"];
+
+#[derive(Debug, thiserror::Error, PartialEq, Eq)]
+pub enum ProviderError {
+    #[error("unknown anchor provider '{0}' (expected one of: {known})", known = KNOWN_SPECS.join(", "))]
+    Unknown(String),
+    #[error("no anchor provider configured")]
+    Empty,
+    #[error("anchor provider '{0}' listed twice")]
+    Duplicate(String),
+    #[error("failed to create {spec} provider: {message}")]
+    Config { spec: String, message: String },
+    #[error("{network} provider failed network validation: {message}")]
+    Validation { network: String, message: String },
+}
+
+/// A provider named by a spec string
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProviderKind {
+    Etherlink,
+    EtherlinkStub,
+    Solana,
+    SolanaStub,
+}
+
+impl ProviderKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ProviderKind::Etherlink => "etherlink",
+            ProviderKind::EtherlinkStub => "etherlink-stub",
+            ProviderKind::Solana => "solana",
+            ProviderKind::SolanaStub => "solana-stub",
+        }
+    }
+
+    /// Create the provider, reading its settings from the environment
+    pub fn build(self) -> Result<Box<dyn AnchorProvider + Send + Sync>, ProviderError> {
+        let config_error = |message: String| ProviderError::Config {
+            spec: self.as_str().to_string(),
+            message,
+        };
+        match self {
+            ProviderKind::EtherlinkStub => {
+                tracing::info!("Using EtherlinkProviderStub for development/testing");
+                Ok(Box::new(EtherlinkProviderStub))
+            }
+            ProviderKind::SolanaStub => {
+                tracing::info!("Using SolanaProviderStub for development/testing");
+                Ok(Box::new(SolanaProviderStub))
+            }
+            ProviderKind::Etherlink => {
+                let endpoint = std::env::var("ETHERLINK_ENDPOINT")
+                    .unwrap_or_else(|_| "https://node.etherlink.com".to_string());
+                let network =
+                    std::env::var("ETHERLINK_NETWORK").unwrap_or_else(|_| "mainnet".to_string());
+                let private_key = std::env::var("ETHERLINK_PRIVATE_KEY").ok();
+                let confirmation_depth = anchor_etherlink::confirmation_depth_from_env()
+                    .map_err(|e| config_error(e.to_string()))?;
+                let gas_overrides =
+                    GasOverrides::from_env().map_err(|e| config_error(e.to_string()))?;
+                let mut provider =
+                    EtherlinkProvider::new(endpoint.clone(), network.clone(), private_key)
+                        .map_err(config_error)?
+                        .with_confirmation_depth(confirmation_depth)
+                        .with_gas_overrides(gas_overrides);
+                if let Ok(sender) = std::env::var("ETHERLINK_SENDER_ADDRESS") {
+                    provider = provider.with_sender(sender);
+                }
+                tracing::info!(
+                    endpoint = %endpoint,
+                    network = %network,
+                    confirmation_depth,
+                    "Successfully created EtherlinkProvider"
+                );
+                Ok(Box::new(provider))
+            }
+            ProviderKind::Solana => {
+                let endpoint = std::env::var("SOLANA_ENDPOINT")
+                    .unwrap_or_else(|_| "https://api.devnet.solana.com".to_string());
+                let network =
+                    std::env::var("SOLANA_NETWORK").unwrap_or_else(|_| "devnet".to_string());
+                let provider = PriorityFee::from_env()
+                    .and_then(|priority_fee| {
+                        SolanaProvider::with_keypair_from_env(endpoint.clone(), network.clone())
+                            .map(|provider| provider.with_priority_fee(priority_fee))
+                    })
+                    .map_err(|e| config_error(e.to_string()))?;
+                tracing::info!(
+                    endpoint = %endpoint,
+                    network = %network,
+                    priority_fee = ?provider.priority_fee,
+                    "Successfully created SolanaProvider"
+                );
+                Ok(Box::new(provider))
+            }
+        }
+    }
+}
+
+impl FromStr for ProviderKind {
+    type Err = ProviderError;
+
+    fn from_str(spec: &str) -> Result<Self, Self::Err> {
+        match spec.trim().to_lowercase().as_str() {
+            "etherlink" => Ok(ProviderKind::Etherlink),
+            "etherlink-stub" => Ok(ProviderKind::EtherlinkStub),
+            "solana" => Ok(ProviderKind::Solana),
+            "solana-stub" => Ok(ProviderKind::SolanaStub),
+            _ => Err(ProviderError::Unknown(spec.trim().to_string())),
+        }
+    }
+}
+
+/// Create the provider named by `spec` (one of [`KNOWN_SPECS`])
+pub fn provider_from_spec(
+    spec: &str,
+) -> Result<Box<dyn AnchorProvider + Send + Sync>, ProviderError> {
+    spec.parse::<ProviderKind>()?.build()
+}
+
+/// Parse a comma-separated provider list, rejecting duplicates
+pub fn parse_spec_list(list: &str) -> Result<Vec<ProviderKind>, ProviderError> {
+    let mut kinds: Vec<ProviderKind> = Vec::new();
+    for spec in list.split(',').map(str::trim).filter(|s| !s.is_empty()) {
+        let kind = spec.parse::<ProviderKind>()?;
+        if kinds.contains(&kind) {
+            return Err(ProviderError::Duplicate(kind.as_str().to_string()));
+        }
+        kinds.push(kind);
+    }
+    if kinds.is_empty() {
+        return Err(ProviderError::Empty);
+    }
+    Ok(kinds)
+}
+
+/// Check each provider's network name and probe its endpoint (chain id or
+/// genesis hash), stopping at the first that fails
+pub async fn validate_providers(
+    providers: &[Box<dyn AnchorProvider + Send + Sync>],
+) -> Result<(), ProviderError> {
+    for provider in providers {
+        if let Err(e) = provider.validate_network().await {
+            return Err(ProviderError::Validation {
+                network: provider.network().unwrap_or("anchor").to_string(),
+                message: e.to_string(),
+            });
+        }
+    }
+    Ok(())
+}
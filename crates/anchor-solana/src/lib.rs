@@ -1,12 +1,49 @@
 use async_trait::async_trait;
+use base64::Engine;
 use chrono::Utc;
-use phoenix_evidence::anchor::{AnchorError, AnchorProvider};
+use ed25519_dalek::SigningKey;
+use phoenix_evidence::anchor::{AnchorError, AnchorProvider, BalanceCheck};
 use phoenix_evidence::model::{ChainTxRef, EvidenceRecord};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use std::sync::Arc;
 use std::time::Duration;
 
+pub mod transaction;
+
+/// Environment variable holding the fee-payer keypair (JSON byte array or base58)
+pub const SOLANA_KEYPAIR_ENV: &str = "SOLANA_KEYPAIR";
+
+/// Fixed priority fee, or the adaptive floor (micro-lamports per compute unit)
+pub const SOLANA_PRIORITY_FEE_ENV: &str = "SOLANA_PRIORITY_FEE_MICROLAMPORTS";
+
+/// Cap for adaptive priority fees; setting it turns sampling on
+pub const SOLANA_PRIORITY_FEE_MAX_ENV: &str = "SOLANA_PRIORITY_FEE_MAX_MICROLAMPORTS";
+
+/// Networks `SOLANA_NETWORK` may name, with the genesis hash their nodes
+/// report from `getGenesisHash` (`None`: a local validator, any genesis)
+pub const KNOWN_NETWORKS: [(&str, Option<&str>); 4] = [
+    (
+        "mainnet-beta",
+        Some("5eykt4UsFv8P8NJdTREpY1vzqKqZKvdpKuc147dw2N9d"),
+    ),
+    (
+        "devnet",
+        Some("EtWTRABZaYq6iMfeYKouRu166VU2xqa1wcaWoxPkrZBG"),
+    ),
+    (
+        "testnet",
+        Some("4uhcVJyU9pJkvQyS88uRDiswHXSCkY3zQawwpjk2NsNY"),
+    ),
+    ("localnet", None),
+];
+
+/// Names accepted for `SOLANA_NETWORK`
+pub fn known_networks() -> Vec<&'static str> {
+    KNOWN_NETWORKS.iter().map(|(name, _)| *name).collect()
+}
+
 #[derive(Clone)]
 pub struct SolanaProviderStub;
 
@@ -27,6 +64,10 @@ impl AnchorProvider for SolanaProviderStub {
         t.confirmed = true;
         Ok(t)
     }
+
+    fn network(&self) -> Option<&str> {
+        Some("solana")
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -34,6 +75,175 @@ pub struct SolanaProvider {
     pub client: Client,
     pub endpoint: String,
     pub network: String,
+    /// Fee payer used to sign memo transactions (None = cannot anchor)
+    signer: Option<Arc<SigningKey>>,
+    /// Minimum confirmation level `confirm` treats as confirmed
+    pub commitment: Commitment,
+    /// All RPC endpoints in failover order (`endpoint` is the first)
+    pub endpoints: Vec<String>,
+    /// Extra passes over `endpoints` after the first one fails
+    pub max_retries: u32,
+    /// Base delay for exponential backoff between attempts
+    pub retry_backoff: Duration,
+    /// Age after which `confirm` sets `searchTransactionHistory`
+    ///
+    /// Recently submitted transactions are in the RPC node's status cache, so
+    /// searching the full ledger only pays off once a tx has been pending a while.
+    pub search_history_after: Duration,
+    /// Endpoints that must agree before `confirm` reports a tx confirmed
+    ///
+    /// `None` asks a single endpoint with failover. With a quorum set, every
+    /// endpoint in `endpoints` is queried once and a lagging or forked node
+    /// cannot confirm a tx on its own. An old tx is reported dropped only
+    /// when every endpoint answers without knowing it.
+    pub confirmation_quorum: Option<usize>,
+    /// Compute unit price paid on anchoring transactions
+    pub priority_fee: PriorityFee,
+}
+
+/// Priority fee for anchoring transactions, in micro-lamports per compute unit
+///
+/// Without one, a memo transaction can sit unconfirmed on a congested
+/// mainnet-beta. Devnet rarely needs it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PriorityFee {
+    /// No `SetComputeUnitPrice` instruction
+    #[default]
+    None,
+    /// Always pay this price
+    Fixed(u64),
+    /// Pay the [`ADAPTIVE_FEE_PERCENTILE`] of fees recently paid for the
+    /// payer's account (`getRecentPrioritizationFees`), clamped to
+    /// `min..=max`; `min` is paid if sampling fails
+    Adaptive { min: u64, max: u64 },
+}
+
+/// Percentile of recent prioritization fees an adaptive fee pays
+pub const ADAPTIVE_FEE_PERCENTILE: usize = 75;
+
+impl PriorityFee {
+    /// Read from `SOLANA_PRIORITY_FEE_MICROLAMPORTS` and
+    /// `SOLANA_PRIORITY_FEE_MAX_MICROLAMPORTS`
+    ///
+    /// A max makes the fee adaptive, with the plain value as its floor.
+    pub fn from_env() -> Result<Self, AnchorError> {
+        let read = |name: &str| -> Result<Option<u64>, AnchorError> {
+            match std::env::var(name) {
+                Ok(value) if !value.trim().is_empty() => {
+                    value.trim().parse::<u64>().map(Some).map_err(|e| {
+                        AnchorError::Invalid(format!("invalid {} '{}': {}", name, value, e))
+                    })
+                }
+                _ => Ok(None),
+            }
+        };
+        let fee = read(SOLANA_PRIORITY_FEE_ENV)?;
+        match (fee, read(SOLANA_PRIORITY_FEE_MAX_ENV)?) {
+            (min, Some(max)) => {
+                let min = min.unwrap_or(0);
+                if min > max {
+                    return Err(AnchorError::Invalid(format!(
+                        "{} ({}) exceeds {} ({})",
+                        SOLANA_PRIORITY_FEE_ENV, min, SOLANA_PRIORITY_FEE_MAX_ENV, max
+                    )));
+                }
+                Ok(PriorityFee::Adaptive { min, max })
+            }
+            (Some(fee), None) => Ok(PriorityFee::Fixed(fee)),
+            (None, None) => Ok(PriorityFee::None),
+        }
+    }
+}
+
+/// The [`ADAPTIVE_FEE_PERCENTILE`] of sampled fees, clamped to `min..=max`
+pub fn adaptive_priority_fee(mut samples: Vec<u64>, min: u64, max: u64) -> u64 {
+    if samples.is_empty() {
+        return min;
+    }
+    samples.sort_unstable();
+    let index = (samples.len() * ADAPTIVE_FEE_PERCENTILE)
+        .div_ceil(100)
+        .max(1)
+        - 1;
+    samples[index].clamp(min, max)
+}
+
+/// Most signatures `getSignatureStatuses` accepts in one call
+pub const MAX_SIGNATURES_PER_STATUS_CALL: usize = 256;
+
+/// Default age after which confirmation checks search full transaction history
+pub const DEFAULT_SEARCH_HISTORY_AFTER: Duration = Duration::from_secs(60);
+
+/// Upper bound on a single backoff delay
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(5);
+
+/// Outcome of one RPC attempt against a single endpoint
+enum RpcAttemptError {
+    /// Network failure, 429 or 5xx: try the next endpoint
+    Retryable(AnchorError),
+    /// Anything else (RPC error, other 4xx): give up immediately
+    Fatal(AnchorError),
+}
+
+/// Solana commitment levels, ordered from weakest to strongest
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Commitment {
+    Processed,
+    Confirmed,
+    #[default]
+    Finalized,
+}
+
+impl Commitment {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Commitment::Processed => "processed",
+            Commitment::Confirmed => "confirmed",
+            Commitment::Finalized => "finalized",
+        }
+    }
+
+    /// Parse a `confirmationStatus` string as reported by `getSignatureStatuses`
+    pub fn from_status(status: &str) -> Option<Self> {
+        match status {
+            "processed" => Some(Commitment::Processed),
+            "confirmed" => Some(Commitment::Confirmed),
+            "finalized" => Some(Commitment::Finalized),
+            _ => None,
+        }
+    }
+}
+
+impl std::str::FromStr for Commitment {
+    type Err = AnchorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_status(&s.trim().to_lowercase())
+            .ok_or_else(|| AnchorError::Invalid(format!("unknown commitment level: {}", s)))
+    }
+}
+
+/// How far along a transaction is, as reported by the cluster
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatureConfirmation {
+    pub slot: u64,
+    /// Raw `confirmationStatus` string (None if the node did not report one)
+    pub status: Option<String>,
+    /// Whether the transaction failed on-chain
+    pub failed: bool,
+}
+
+impl SignatureConfirmation {
+    /// True when the tx succeeded and its status meets or exceeds `commitment`
+    pub fn meets(&self, commitment: Commitment) -> bool {
+        !self.failed
+            && self
+                .status
+                .as_deref()
+                .and_then(Commitment::from_status)
+                .is_some_and(|reported| reported >= commitment)
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -63,6 +273,7 @@ pub struct SolanaRpcError {
 }
 
 #[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct TransactionStatus {
     slot: u64,
     #[allow(dead_code)]
@@ -80,11 +291,113 @@ impl SolanaProvider {
 
         Self {
             client,
+            endpoints: vec![endpoint.clone()],
             endpoint,
             network,
+            signer: None,
+            commitment: Commitment::default(),
+            max_retries: 2,
+            retry_backoff: Duration::from_millis(250),
+            search_history_after: DEFAULT_SEARCH_HISTORY_AFTER,
+            confirmation_quorum: None,
+            priority_fee: PriorityFee::None,
         }
     }
 
+    /// Create a provider that fails over across several RPC endpoints, in order
+    pub fn with_endpoints(endpoints: Vec<String>, network: String) -> Result<Self, AnchorError> {
+        let primary = endpoints
+            .first()
+            .cloned()
+            .ok_or_else(|| AnchorError::Invalid("at least one RPC endpoint is required".into()))?;
+        let mut provider = Self::new(primary, network);
+        provider.endpoints = endpoints;
+        Ok(provider)
+    }
+
+    /// Configure how many extra passes over the endpoints to make, and the base backoff
+    pub fn with_retry(mut self, max_retries: u32, retry_backoff: Duration) -> Self {
+        self.max_retries = max_retries;
+        self.retry_backoff = retry_backoff;
+        self
+    }
+
+    /// Set how old a transaction must be before `confirm` searches full history
+    pub fn with_search_history_after(mut self, search_history_after: Duration) -> Self {
+        self.search_history_after = search_history_after;
+        self
+    }
+
+    /// Require `quorum` of the configured endpoints to agree before confirming
+    pub fn with_confirmation_quorum(mut self, quorum: usize) -> Result<Self, AnchorError> {
+        if quorum == 0 || quorum > self.endpoints.len() {
+            return Err(AnchorError::Invalid(format!(
+                "confirmation quorum must be between 1 and {} (the number of endpoints)",
+                self.endpoints.len()
+            )));
+        }
+        self.confirmation_quorum = Some(quorum);
+        Ok(self)
+    }
+
+    /// Whether a confirmation check for `tx` should search full ledger history.
+    ///
+    /// The keeper re-checks unconfirmed refs on every confirmation pass, so the
+    /// tx's age since anchoring tracks how long it has been pending. Refs with
+    /// no timestamp are treated as old.
+    pub fn should_search_history(&self, tx: &ChainTxRef) -> bool {
+        match tx.timestamp {
+            Some(anchored_at) => (Utc::now() - anchored_at)
+                .to_std()
+                .map(|age| age >= self.search_history_after)
+                .unwrap_or(false),
+            None => true,
+        }
+    }
+
+    /// Set the priority fee paid on anchoring transactions (default: none)
+    pub fn with_priority_fee(mut self, priority_fee: PriorityFee) -> Self {
+        self.priority_fee = priority_fee;
+        self
+    }
+
+    /// Set the commitment level `confirm` waits for (default: finalized)
+    pub fn with_commitment(mut self, commitment: Commitment) -> Self {
+        self.commitment = commitment;
+        self
+    }
+
+    /// Create a provider that signs memo transactions with the given keypair.
+    ///
+    /// Accepts a 64-byte Solana keypair (secret || public) or a 32-byte seed.
+    pub fn with_keypair(
+        endpoint: String,
+        network: String,
+        keypair_bytes: &[u8],
+    ) -> Result<Self, AnchorError> {
+        let signer = transaction::signing_key_from_bytes(keypair_bytes)?;
+        let mut provider = Self::new(endpoint, network);
+        provider.signer = Some(Arc::new(signer));
+        Ok(provider)
+    }
+
+    /// Create a provider whose keypair is read from `SOLANA_KEYPAIR`
+    pub fn with_keypair_from_env(endpoint: String, network: String) -> Result<Self, AnchorError> {
+        let secret = std::env::var(SOLANA_KEYPAIR_ENV)
+            .map_err(|_| AnchorError::Invalid(format!("{} is not set", SOLANA_KEYPAIR_ENV)))?;
+        let keypair_bytes = transaction::keypair_bytes_from_secret(&secret)?;
+        Self::with_keypair(endpoint, network, &keypair_bytes)
+    }
+
+    /// Base58 public key of the fee payer, if a keypair is configured
+    pub fn payer_pubkey(&self) -> Option<String> {
+        self.signer
+            .as_ref()
+            .map(|signer| bs58::encode(signer.verifying_key().as_bytes()).into_string())
+    }
+
+    /// Issue a JSON-RPC call, rotating through `endpoints` on network errors,
+    /// HTTP 429 and 5xx with exponential backoff between attempts.
     async fn rpc_call(&self, method: &str, params: Value) -> Result<Value, AnchorError> {
         let request = SolanaRpcRequest {
             jsonrpc: "2.0".to_string(),
@@ -93,86 +406,520 @@ impl SolanaProvider {
             params,
         };
 
+        let total_attempts = self.endpoints.len() * (self.max_retries as usize + 1);
+        let mut last_error = AnchorError::Network("no RPC endpoints configured".to_string());
+
+        for attempt in 0..total_attempts {
+            if attempt > 0 {
+                let factor = 2u32.saturating_pow((attempt - 1) as u32);
+                let delay = self
+                    .retry_backoff
+                    .saturating_mul(factor)
+                    .min(MAX_RETRY_BACKOFF);
+                tokio::time::sleep(delay).await;
+            }
+
+            let endpoint = &self.endpoints[attempt % self.endpoints.len()];
+            match self.rpc_attempt(endpoint, &request).await {
+                Ok(result) => return Ok(result),
+                Err(RpcAttemptError::Fatal(e)) => return Err(e),
+                Err(RpcAttemptError::Retryable(e)) => {
+                    tracing::warn!(
+                        endpoint = %endpoint,
+                        method = %method,
+                        attempt = attempt + 1,
+                        error = %e,
+                        "Solana RPC attempt failed, failing over"
+                    );
+                    last_error = e;
+                }
+            }
+        }
+
+        Err(last_error)
+    }
+
+    async fn rpc_attempt(
+        &self,
+        endpoint: &str,
+        request: &SolanaRpcRequest,
+    ) -> Result<Value, RpcAttemptError> {
         let response = self
             .client
-            .post(&self.endpoint)
-            .json(&request)
+            .post(endpoint)
+            .json(request)
             .send()
             .await
-            .map_err(|e| AnchorError::Network(format!("HTTP request failed: {}", e)))?;
-
-        if !response.status().is_success() {
-            return Err(AnchorError::Network(format!(
-                "HTTP error: {}",
-                response.status()
-            )));
+            .map_err(|e| {
+                RpcAttemptError::Retryable(AnchorError::Network(format!(
+                    "HTTP request failed: {}",
+                    e
+                )))
+            })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error = AnchorError::Network(format!("HTTP error: {}", status));
+            return Err(
+                if status == reqwest::StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+                    RpcAttemptError::Retryable(error)
+                } else {
+                    RpcAttemptError::Fatal(error)
+                },
+            );
         }
 
-        let rpc_response: SolanaRpcResponse = response
-            .json()
-            .await
-            .map_err(|e| AnchorError::Network(format!("Failed to parse JSON: {}", e)))?;
+        let rpc_response: SolanaRpcResponse = response.json().await.map_err(|e| {
+            RpcAttemptError::Retryable(AnchorError::Network(format!("Failed to parse JSON: {}", e)))
+        })?;
 
         if let Some(error) = rpc_response.error {
-            return Err(AnchorError::Provider(format!(
+            return Err(RpcAttemptError::Fatal(AnchorError::Provider(format!(
                 "RPC error {}: {}",
                 error.code, error.message
-            )));
+            ))));
         }
 
-        rpc_response
-            .result
-            .ok_or_else(|| AnchorError::Provider("RPC response missing result field".to_string()))
+        rpc_response.result.ok_or_else(|| {
+            RpcAttemptError::Fatal(AnchorError::Provider(
+                "RPC response missing result field".to_string(),
+            ))
+        })
+    }
+
+    /// Genesis hash the RPC endpoint reports (`getGenesisHash`)
+    pub async fn genesis_hash(&self) -> Result<String, AnchorError> {
+        let result = self.rpc_call("getGenesisHash", json!([])).await?;
+        result
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| AnchorError::Provider(format!("Invalid genesis hash: {}", result)))
+    }
+
+    /// Balance of the fee payer in lamports (`getBalance`), if a keypair is
+    /// configured
+    pub async fn balance(&self) -> Result<Option<u64>, AnchorError> {
+        let Some(payer) = self.payer_pubkey() else {
+            return Ok(None);
+        };
+        let result = self
+            .rpc_call(
+                "getBalance",
+                json!([payer, {"commitment": self.commitment.as_str()}]),
+            )
+            .await?;
+        result
+            .get("value")
+            .and_then(Value::as_u64)
+            .map(Some)
+            .ok_or_else(|| {
+                AnchorError::Provider(format!("Invalid getBalance response: {}", result))
+            })
+    }
+
+    async fn get_latest_blockhash(&self) -> Result<String, AnchorError> {
+        let result = self
+            .rpc_call("getLatestBlockhash", json!([{"commitment": "finalized"}]))
+            .await?;
+
+        result
+            .get("value")
+            .and_then(|v| v.get("blockhash"))
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+            .ok_or_else(|| AnchorError::Provider("Invalid getLatestBlockhash response".to_string()))
+    }
+
+    /// Compute unit price for the next anchoring transaction, if any
+    ///
+    /// Adaptive fees sample `getRecentPrioritizationFees` for the payer's
+    /// account (which every anchoring tx write-locks); a failed sample falls
+    /// back to the floor rather than blocking anchoring.
+    pub async fn current_priority_fee(&self) -> Option<u64> {
+        match self.priority_fee {
+            PriorityFee::None => None,
+            PriorityFee::Fixed(fee) => Some(fee),
+            PriorityFee::Adaptive { min, max } => {
+                let accounts: Vec<String> = self.payer_pubkey().into_iter().collect();
+                match self
+                    .rpc_call("getRecentPrioritizationFees", json!([accounts]))
+                    .await
+                {
+                    Ok(result) => {
+                        let samples = result
+                            .as_array()
+                            .map(|entries| {
+                                entries
+                                    .iter()
+                                    .filter_map(|e| e.get("prioritizationFee")?.as_u64())
+                                    .collect()
+                            })
+                            .unwrap_or_default();
+                        let fee = adaptive_priority_fee(samples, min, max);
+                        tracing::debug!(fee, "Sampled Solana priority fee");
+                        Some(fee)
+                    }
+                    Err(error) => {
+                        tracing::warn!(
+                            error = %error,
+                            fee = min,
+                            "Failed to sample priority fees, using the floor"
+                        );
+                        Some(min)
+                    }
+                }
+            }
+        }
     }
 
     async fn send_memo_transaction(&self, memo_data: &str) -> Result<String, AnchorError> {
-        // Create a memo transaction
-        // In a real implementation, you'd create and sign a proper Solana transaction
-        // For now, return a deterministic fake signature
-        // sha256_hex already returns a hex string, so we use it directly as the signature
-        let signature = phoenix_evidence::hash::sha256_hex(memo_data.as_bytes());
-
-        tracing::info!(
-            signature = %signature,
-            memo_data = %memo_data,
-            "Anchored evidence to Solana (simulated)"
-        );
+        // The RPC only reports an oversized tx as a generic error, so catch it first
+        let with_priority_fee = self.priority_fee != PriorityFee::None;
+        transaction::check_memo_size(memo_data, with_priority_fee)?;
 
-        Ok(signature)
+        let signer = self.signer.as_ref().ok_or_else(|| {
+            AnchorError::Invalid(
+                "No Solana keypair configured; use SolanaProvider::with_keypair".to_string(),
+            )
+        })?;
+
+        // One retry if the blockhash expires between fetch and submission
+        let mut retried = false;
+        loop {
+            let blockhash = self.get_latest_blockhash().await?;
+            let priority_fee = self.current_priority_fee().await;
+            let tx =
+                transaction::build_memo_transaction(signer, &blockhash, memo_data, priority_fee)?;
+            let encoded = base64::engine::general_purpose::STANDARD.encode(&tx.bytes);
+
+            match self
+                .rpc_call(
+                    "sendTransaction",
+                    json!([encoded, {"encoding": "base64", "preflightCommitment": "confirmed"}]),
+                )
+                .await
+            {
+                Ok(result) => {
+                    let signature = result.as_str().unwrap_or(&tx.signature).to_string();
+                    tracing::info!(
+                        signature = %signature,
+                        memo_data = %memo_data,
+                        priority_fee = ?priority_fee,
+                        "Anchored evidence to Solana"
+                    );
+                    return Ok(signature);
+                }
+                Err(AnchorError::Provider(message))
+                    if is_blockhash_expired(&message) && !retried =>
+                {
+                    tracing::warn!(error = %message, "Blockhash expired, retrying with a fresh one");
+                    retried = true;
+                }
+                Err(AnchorError::Provider(message)) if is_insufficient_funds(&message) => {
+                    return Err(AnchorError::Provider(format!(
+                        "insufficient funds in fee payer account: {}",
+                        message
+                    )));
+                }
+                Err(e) => return Err(e),
+            }
+        }
     }
 
     async fn get_signature_status(
         &self,
         signature: &str,
+        search_history: bool,
     ) -> Result<Option<TransactionStatus>, AnchorError> {
         let result = self
             .rpc_call(
                 "getSignatureStatuses",
-                json!([[signature], {"searchTransactionHistory": true}]),
+                signature_statuses_params(signature, search_history),
             )
             .await?;
 
-        let statuses = result
-            .get("value")
-            .and_then(|v| v.as_array())
-            .ok_or_else(|| AnchorError::Provider("Invalid response format".to_string()))?;
+        parse_signature_status(&result)
+    }
 
-        if statuses.is_empty() {
-            return Ok(None);
+    /// Statuses for `signatures` from one `getSignatureStatuses` call, in
+    /// input order (at most [`MAX_SIGNATURES_PER_STATUS_CALL`])
+    async fn get_signature_statuses(
+        &self,
+        signatures: &[&str],
+        search_history: bool,
+    ) -> Result<Vec<Option<TransactionStatus>>, AnchorError> {
+        let result = self
+            .rpc_call(
+                "getSignatureStatuses",
+                json!([signatures, {"searchTransactionHistory": search_history}]),
+            )
+            .await?;
+
+        parse_signature_statuses(&result, signatures.len())
+    }
+
+    /// Slot and confirmation status for a signature (None if unknown to the cluster).
+    ///
+    /// `ChainTxRef` has no room for chain-specific detail, so callers that
+    /// want to show progress can use this alongside `confirm`. Set
+    /// `search_history` to look beyond the node's recent status cache.
+    pub async fn signature_confirmation(
+        &self,
+        signature: &str,
+        search_history: bool,
+    ) -> Result<Option<SignatureConfirmation>, AnchorError> {
+        Ok(self
+            .get_signature_status(signature, search_history)
+            .await?
+            .map(SignatureConfirmation::from))
+    }
+
+    /// Ask every configured endpoint, once each, whether `signature` has
+    /// reached the configured commitment.
+    ///
+    /// Endpoints that error count as dissenting. Fails only if no endpoint
+    /// answered at all.
+    pub async fn quorum_confirmation(
+        &self,
+        signature: &str,
+        search_history: bool,
+    ) -> Result<QuorumConfirmation, AnchorError> {
+        let request = SolanaRpcRequest {
+            jsonrpc: "2.0".to_string(),
+            id: 1,
+            method: "getSignatureStatuses".to_string(),
+            params: signature_statuses_params(signature, search_history),
+        };
+
+        let mut outcome = QuorumConfirmation::default();
+        let mut last_error = None;
+        let mut answered = 0;
+
+        for endpoint in &self.endpoints {
+            let status = match self.rpc_attempt(endpoint, &request).await {
+                Ok(result) => parse_signature_status(&result),
+                Err(RpcAttemptError::Retryable(e)) | Err(RpcAttemptError::Fatal(e)) => Err(e),
+            };
+
+            match status {
+                Ok(status) => {
+                    answered += 1;
+                    if status.is_none() {
+                        outcome.unknown.push(endpoint.clone());
+                    }
+                    let confirmation = status.map(SignatureConfirmation::from);
+                    if confirmation.is_some_and(|c| c.meets(self.commitment)) {
+                        outcome.confirming.push(endpoint.clone());
+                    } else {
+                        outcome.dissenting.push(endpoint.clone());
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        endpoint = %endpoint,
+                        signature = %signature,
+                        error = %e,
+                        "Solana RPC endpoint failed during quorum confirmation"
+                    );
+                    outcome.dissenting.push(endpoint.clone());
+                    last_error = Some(e);
+                }
+            }
         }
 
-        let status_value = &statuses[0];
-        if status_value.is_null() {
-            return Ok(None);
+        match last_error {
+            Some(e) if answered == 0 => Err(e),
+            _ => Ok(outcome),
+        }
+    }
+
+    /// `tx` updated with the status the cluster reported for it
+    fn apply_confirmation(
+        &self,
+        tx: &ChainTxRef,
+        confirmation: Option<SignatureConfirmation>,
+    ) -> Result<ChainTxRef, AnchorError> {
+        if confirmation.is_none() {
+            if let Some(dropped) = self.dropped_error(tx) {
+                return Err(dropped);
+            }
+        }
+
+        let mut confirmed_tx = tx.clone();
+
+        if let Some(confirmation) = confirmation {
+            // Confirmed once the tx succeeded and reached the configured commitment
+            let is_confirmed = confirmation.meets(self.commitment);
+
+            confirmed_tx.confirmed = is_confirmed;
+            if is_confirmed {
+                tracing::info!(
+                    signature = %tx.tx_id,
+                    slot = %confirmation.slot,
+                    status = ?confirmation.status,
+                    commitment = %self.commitment.as_str(),
+                    "Transaction confirmed on Solana"
+                );
+            } else {
+                tracing::debug!(
+                    signature = %tx.tx_id,
+                    slot = %confirmation.slot,
+                    status = ?confirmation.status,
+                    failed = confirmation.failed,
+                    "Transaction not yet at required commitment"
+                );
+            }
+        }
+
+        Ok(confirmed_tx)
+    }
+
+    /// `NotFound` for a tx the cluster doesn't know, if it is old enough to
+    /// have searched the full ledger for: it was dropped before landing (its
+    /// blockhash has long expired)
+    fn dropped_error(&self, tx: &ChainTxRef) -> Option<AnchorError> {
+        (self.should_search_history(tx) && tx.timestamp.is_some()).then(|| {
+            AnchorError::NotFound(format!("signature {} is unknown to the cluster", tx.tx_id))
+        })
+    }
+
+    async fn confirm_with_quorum(
+        &self,
+        tx: &ChainTxRef,
+        quorum: usize,
+    ) -> Result<ChainTxRef, AnchorError> {
+        let outcome = self
+            .quorum_confirmation(&tx.tx_id, self.should_search_history(tx))
+            .await?;
+
+        // Dropped only if every endpoint answered and none knows the tx
+        if outcome.unknown.len() == self.endpoints.len() {
+            if let Some(dropped) = self.dropped_error(tx) {
+                return Err(dropped);
+            }
+        }
+        let is_confirmed = outcome.reached(quorum);
+
+        // Endpoints on the losing side of the vote are lagging, forked or broken
+        let disagreeing = if is_confirmed {
+            &outcome.dissenting
+        } else {
+            &outcome.confirming
+        };
+        for endpoint in disagreeing {
+            tracing::warn!(
+                endpoint = %endpoint,
+                signature = %tx.tx_id,
+                confirmed = is_confirmed,
+                confirming = outcome.confirming.len(),
+                quorum,
+                "Solana RPC endpoint disagrees with confirmation quorum"
+            );
+        }
+
+        if is_confirmed {
+            tracing::info!(
+                signature = %tx.tx_id,
+                confirming = outcome.confirming.len(),
+                quorum,
+                commitment = %self.commitment.as_str(),
+                "Transaction confirmed on Solana by quorum"
+            );
         }
 
-        let status: TransactionStatus = serde_json::from_value(status_value.clone())
-            .map_err(|e| AnchorError::Provider(format!("Failed to parse status: {}", e)))?;
+        let mut confirmed_tx = tx.clone();
+        confirmed_tx.confirmed = is_confirmed;
+        Ok(confirmed_tx)
+    }
+}
+
+/// Per-endpoint tally from [`SolanaProvider::quorum_confirmation`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QuorumConfirmation {
+    /// Endpoints reporting the tx at or beyond the required commitment
+    pub confirming: Vec<String>,
+    /// Endpoints reporting a lower status, no status, or an error
+    pub dissenting: Vec<String>,
+    /// Dissenting endpoints that answered with no status for the tx at all
+    pub unknown: Vec<String>,
+}
 
-        Ok(Some(status))
+impl QuorumConfirmation {
+    /// Whether at least `quorum` endpoints confirmed
+    pub fn reached(&self, quorum: usize) -> bool {
+        self.confirming.len() >= quorum
     }
 }
 
+impl From<TransactionStatus> for SignatureConfirmation {
+    fn from(status: TransactionStatus) -> Self {
+        Self {
+            slot: status.slot,
+            status: status.confirmation_status,
+            failed: status.err.is_some(),
+        }
+    }
+}
+
+fn signature_statuses_params(signature: &str, search_history: bool) -> Value {
+    json!([[signature], {"searchTransactionHistory": search_history}])
+}
+
+/// First entry of a `getSignatureStatuses` result (None if the cluster has no status)
+fn parse_signature_status(result: &Value) -> Result<Option<TransactionStatus>, AnchorError> {
+    let statuses = result
+        .get("value")
+        .and_then(|v| v.as_array())
+        .ok_or_else(|| AnchorError::Provider("Invalid response format".to_string()))?;
+
+    match statuses.first() {
+        None | Some(Value::Null) => Ok(None),
+        Some(status_value) => serde_json::from_value(status_value.clone())
+            .map(Some)
+            .map_err(|e| AnchorError::Provider(format!("Failed to parse status: {}", e))),
+    }
+}
+
+/// Every entry of a `getSignatureStatuses` result, which must hold `expected`
+fn parse_signature_statuses(
+    result: &Value,
+    expected: usize,
+) -> Result<Vec<Option<TransactionStatus>>, AnchorError> {
+    let statuses = result
+        .get("value")
+        .and_then(|v| v.as_array())
+        .ok_or_else(|| AnchorError::Provider("Invalid response format".to_string()))?;
+    if statuses.len() != expected {
+        return Err(AnchorError::Provider(format!(
+            "expected {} signature statuses, got {}",
+            expected,
+            statuses.len()
+        )));
+    }
+
+    statuses
+        .iter()
+        .map(|status_value| match status_value {
+            Value::Null => Ok(None),
+            status_value => serde_json::from_value(status_value.clone())
+                .map(Some)
+                .map_err(|e| AnchorError::Provider(format!("Failed to parse status: {}", e))),
+        })
+        .collect()
+}
+
+fn is_blockhash_expired(message: &str) -> bool {
+    let message = message.to_lowercase();
+    message.contains("blockhash not found") || message.contains("block height exceeded")
+}
+
+fn is_insufficient_funds(message: &str) -> bool {
+    let message = message.to_lowercase();
+    message.contains("insufficient funds")
+        || message.contains("insufficient lamports")
+        || message.contains("no record of a prior credit")
+}
+
 #[async_trait]
 impl AnchorProvider for SolanaProvider {
     async fn anchor(&self, evidence: &EvidenceRecord) -> Result<ChainTxRef, AnchorError> {
@@ -191,26 +938,90 @@ impl AnchorProvider for SolanaProvider {
     }
 
     async fn confirm(&self, tx: &ChainTxRef) -> Result<ChainTxRef, AnchorError> {
-        let status = self.get_signature_status(&tx.tx_id).await?;
+        if let Some(quorum) = self.confirmation_quorum {
+            return self.confirm_with_quorum(tx, quorum).await;
+        }
 
-        let mut confirmed_tx = tx.clone();
+        let search_history = self.should_search_history(tx);
+        let confirmation = self
+            .signature_confirmation(&tx.tx_id, search_history)
+            .await?;
+        self.apply_confirmation(tx, confirmation)
+    }
 
-        if let Some(status) = status {
-            // Transaction is confirmed if it has no error and is finalized
-            let is_confirmed =
-                status.err.is_none() && status.confirmation_status.as_deref() == Some("finalized");
+    /// Looks up to [`MAX_SIGNATURES_PER_STATUS_CALL`] signatures per
+    /// `getSignatureStatuses` call. With a confirmation quorum each tx is
+    /// still checked on its own.
+    async fn confirm_many(&self, txs: &[ChainTxRef]) -> Vec<Result<ChainTxRef, AnchorError>> {
+        if self.confirmation_quorum.is_some() {
+            let mut results = Vec::with_capacity(txs.len());
+            for tx in txs {
+                results.push(self.confirm(tx).await);
+            }
+            return results;
+        }
 
-            confirmed_tx.confirmed = is_confirmed;
-            if is_confirmed {
-                tracing::info!(
-                    signature = %tx.tx_id,
-                    slot = %status.slot,
-                    "Transaction confirmed on Solana"
-                );
+        let mut results = Vec::with_capacity(txs.len());
+        for chunk in txs.chunks(MAX_SIGNATURES_PER_STATUS_CALL) {
+            // One flag per call: search history if any tx in the chunk needs it
+            let search_history = chunk.iter().any(|tx| self.should_search_history(tx));
+            let signatures: Vec<&str> = chunk.iter().map(|tx| tx.tx_id.as_str()).collect();
+            match self
+                .get_signature_statuses(&signatures, search_history)
+                .await
+            {
+                Ok(statuses) => results.extend(chunk.iter().zip(statuses).map(|(tx, status)| {
+                    self.apply_confirmation(tx, status.map(SignatureConfirmation::from))
+                })),
+                Err(e) => results.extend(chunk.iter().map(|_| Err(e.clone()))),
             }
         }
+        results
+    }
 
-        Ok(confirmed_tx)
+    fn network(&self) -> Option<&str> {
+        Some("solana")
+    }
+
+    /// The network must be one of [`KNOWN_NETWORKS`], the endpoint must pass
+    /// `getHealth` and, except on `localnet`, report the network's genesis
+    /// hash
+    async fn validate_network(&self) -> Result<(), AnchorError> {
+        let expected = KNOWN_NETWORKS
+            .iter()
+            .find(|(name, _)| *name == self.network)
+            .map(|(_, genesis)| *genesis)
+            .ok_or_else(|| {
+                AnchorError::Invalid(format!(
+                    "unknown Solana network '{}' (expected one of: {})",
+                    self.network,
+                    known_networks().join(", ")
+                ))
+            })?;
+
+        // Unhealthy nodes answer getHealth with an RPC error
+        self.rpc_call("getHealth", json!([])).await?;
+
+        if let Some(expected) = expected {
+            let reported = self.genesis_hash().await?;
+            if reported != expected {
+                return Err(AnchorError::Invalid(format!(
+                    "Solana endpoint {} reports genesis hash {}, but network '{}' has genesis hash {}",
+                    self.endpoint, reported, self.network, expected
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    async fn check_sufficient_balance(
+        &self,
+        min: u128,
+    ) -> Result<Option<BalanceCheck>, AnchorError> {
+        Ok(self.balance().await?.map(|balance| BalanceCheck {
+            balance: balance.into(),
+            minimum: min,
+        }))
     }
 }
 
@@ -450,4 +1261,123 @@ mod tests {
         assert_eq!(error.code, 429);
         assert_eq!(error.message, "Too Many Requests");
     }
+
+    // ------------------------------------------------------------------
+    // 7. Keypair-backed provider and send error classification
+    // ------------------------------------------------------------------
+    #[test]
+    fn with_keypair_exposes_payer_pubkey() {
+        let signer = SigningKey::from_bytes(&[5u8; 32]);
+        let provider = SolanaProvider::with_keypair(
+            "https://api.devnet.solana.com".to_string(),
+            "devnet".to_string(),
+            &signer.to_keypair_bytes(),
+        )
+        .expect("valid keypair");
+
+        assert_eq!(
+            provider.payer_pubkey(),
+            Some(bs58::encode(signer.verifying_key().as_bytes()).into_string())
+        );
+        assert!(
+            SolanaProvider::new("http://x".to_string(), "devnet".to_string())
+                .payer_pubkey()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn with_keypair_rejects_wrong_length() {
+        let result = SolanaProvider::with_keypair(
+            "https://api.devnet.solana.com".to_string(),
+            "devnet".to_string(),
+            &[1u8; 48],
+        );
+        assert!(matches!(result, Err(AnchorError::Invalid(_))));
+    }
+
+    #[tokio::test]
+    async fn anchor_without_keypair_is_invalid() {
+        let provider = SolanaProvider::new("http://127.0.0.1:1".to_string(), "devnet".to_string());
+        let result = provider.anchor(&make_evidence("abcd")).await;
+        assert!(matches!(result, Err(AnchorError::Invalid(_))));
+    }
+
+    #[test]
+    fn classifies_send_errors() {
+        assert!(is_blockhash_expired(
+            "RPC error -32002: Transaction simulation failed: Blockhash not found"
+        ));
+        assert!(!is_blockhash_expired(
+            "RPC error -32002: insufficient funds"
+        ));
+        assert!(is_insufficient_funds(
+            "RPC error -32002: Attempt to debit an account but found no record of a prior credit."
+        ));
+        assert!(is_insufficient_funds(
+            "custom program error: insufficient lamports"
+        ));
+        assert!(!is_insufficient_funds("RPC error -32602: Invalid params"));
+    }
+
+    // ------------------------------------------------------------------
+    // 8. Commitment levels
+    // ------------------------------------------------------------------
+    #[test]
+    fn provider_defaults_to_finalized_commitment() {
+        let provider = SolanaProvider::new("http://x".to_string(), "devnet".to_string());
+        assert_eq!(provider.commitment, Commitment::Finalized);
+
+        let provider = provider.with_commitment(Commitment::Confirmed);
+        assert_eq!(provider.commitment, Commitment::Confirmed);
+    }
+
+    #[test]
+    fn confirmation_meets_or_exceeds_commitment() {
+        let at = |status: &str| SignatureConfirmation {
+            slot: 10,
+            status: Some(status.to_string()),
+            failed: false,
+        };
+
+        assert!(at("finalized").meets(Commitment::Confirmed));
+        assert!(at("confirmed").meets(Commitment::Confirmed));
+        assert!(at("confirmed").meets(Commitment::Processed));
+        assert!(!at("processed").meets(Commitment::Confirmed));
+        assert!(!at("confirmed").meets(Commitment::Finalized));
+
+        let failed = SignatureConfirmation {
+            failed: true,
+            ..at("finalized")
+        };
+        assert!(!failed.meets(Commitment::Processed));
+
+        let unknown = SignatureConfirmation {
+            status: None,
+            ..at("finalized")
+        };
+        assert!(!unknown.meets(Commitment::Processed));
+    }
+
+    #[test]
+    fn commitment_parses_from_str() {
+        assert_eq!(
+            "Confirmed".parse::<Commitment>().unwrap(),
+            Commitment::Confirmed
+        );
+        assert!("rooted".parse::<Commitment>().is_err());
+    }
+
+    #[test]
+    fn transaction_status_reads_camel_case_fields() {
+        let status: TransactionStatus = serde_json::from_value(json!({
+            "slot": 72,
+            "confirmations": null,
+            "err": null,
+            "confirmationStatus": "confirmed"
+        }))
+        .unwrap();
+        assert_eq!(status.slot, 72);
+        assert_eq!(status.confirmation_status.as_deref(), Some("confirmed"));
+    }
 }
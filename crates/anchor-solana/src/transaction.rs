@@ -0,0 +1,363 @@
+//! Minimal Solana legacy transaction encoding for SPL Memo instructions
+//!
+//! Only what the provider needs to anchor a memo: a single fee-payer signer,
+//! the Memo program (plus the Compute Budget program when a priority fee is
+//! set) as the only other accounts, and one instruction carrying the memo
+//! bytes, preceded by `SetComputeUnitPrice` if paying a priority fee. Layout
+//! follows the Solana wire format:
+//!
+//! ```text
+//! transaction = shortvec<signature[64]> || message
+//! message     = header[3] || shortvec<pubkey[32]> || blockhash[32] || shortvec<instruction>
+//! instruction = program_id_index[1] || shortvec<u8 account_index> || shortvec<u8 data>
+//! ```
+
+use ed25519_dalek::{Signer, SigningKey};
+use phoenix_evidence::anchor::AnchorError;
+
+/// SPL Memo program (v2)
+pub const MEMO_PROGRAM_ID: &str = "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr";
+
+/// Compute Budget program, which sets the transaction's priority fee
+pub const COMPUTE_BUDGET_PROGRAM_ID: &str = "ComputeBudget111111111111111111111111111111";
+
+/// `ComputeBudgetInstruction::SetComputeUnitPrice` discriminant
+const SET_COMPUTE_UNIT_PRICE: u8 = 3;
+
+/// Bytes a `SetComputeUnitPrice` instruction adds: the program key, then
+/// program index, empty account list and 9-byte data with its length prefix
+const PRIORITY_FEE_OVERHEAD: usize = 32 + 1 + 1 + 1 + 9;
+
+/// Largest serialized transaction a Solana node accepts (IPv6 MTU minus headers)
+pub const MAX_TRANSACTION_SIZE: usize = 1232;
+
+/// A signed transaction ready for `sendTransaction`
+#[derive(Debug, Clone)]
+pub struct SignedTransaction {
+    /// Base58-encoded fee-payer signature (the transaction id)
+    pub signature: String,
+    /// Serialized wire bytes
+    pub bytes: Vec<u8>,
+}
+
+/// Append a Solana "shortvec" (compact-u16) length prefix
+pub fn encode_shortvec_len(out: &mut Vec<u8>, len: usize) {
+    let mut rem = len;
+    loop {
+        let mut byte = (rem & 0x7f) as u8;
+        rem >>= 7;
+        if rem == 0 {
+            out.push(byte);
+            break;
+        }
+        byte |= 0x80;
+        out.push(byte);
+    }
+}
+
+/// Decode a base58 32-byte value (pubkey or blockhash)
+pub fn decode_32(value: &str, what: &str) -> Result<[u8; 32], AnchorError> {
+    let bytes = bs58::decode(value)
+        .into_vec()
+        .map_err(|e| AnchorError::Invalid(format!("invalid {} '{}': {}", what, value, e)))?;
+    bytes.try_into().map_err(|b: Vec<u8>| {
+        AnchorError::Invalid(format!(
+            "invalid {} '{}': expected 32 bytes, got {}",
+            what,
+            value,
+            b.len()
+        ))
+    })
+}
+
+/// `SetComputeUnitPrice` instruction data: discriminant then the price in
+/// micro-lamports per compute unit, little-endian
+pub fn set_compute_unit_price_data(micro_lamports: u64) -> [u8; 9] {
+    let mut data = [0u8; 9];
+    data[0] = SET_COMPUTE_UNIT_PRICE;
+    data[1..].copy_from_slice(&micro_lamports.to_le_bytes());
+    data
+}
+
+/// Serialize the message for a single memo instruction paid by `payer`,
+/// optionally preceded by a `SetComputeUnitPrice` priority fee
+pub fn memo_message(
+    payer: &[u8; 32],
+    recent_blockhash: &[u8; 32],
+    memo: &[u8],
+    priority_fee_microlamports: Option<u64>,
+) -> Vec<u8> {
+    let memo_program =
+        decode_32(MEMO_PROGRAM_ID, "program id").expect("MEMO_PROGRAM_ID is a valid pubkey");
+
+    let mut msg = Vec::with_capacity(3 + 1 + 96 + 32 + 20 + memo.len());
+    let read_only_programs = if priority_fee_microlamports.is_some() {
+        2
+    } else {
+        1
+    };
+    // Header: 1 required signature, 0 read-only signed, programs read-only unsigned
+    msg.extend_from_slice(&[1, 0, read_only_programs]);
+    // Account keys: [payer, memo program, compute budget program?]
+    encode_shortvec_len(&mut msg, 1 + read_only_programs as usize);
+    msg.extend_from_slice(payer);
+    msg.extend_from_slice(&memo_program);
+    if priority_fee_microlamports.is_some() {
+        let compute_budget = decode_32(COMPUTE_BUDGET_PROGRAM_ID, "program id")
+            .expect("COMPUTE_BUDGET_PROGRAM_ID is a valid pubkey");
+        msg.extend_from_slice(&compute_budget);
+    }
+    msg.extend_from_slice(recent_blockhash);
+    // Instructions, none taking accounts: [set compute unit price?, memo]
+    encode_shortvec_len(&mut msg, read_only_programs as usize);
+    if let Some(micro_lamports) = priority_fee_microlamports {
+        let data = set_compute_unit_price_data(micro_lamports);
+        msg.push(2); // program id index
+        encode_shortvec_len(&mut msg, 0);
+        encode_shortvec_len(&mut msg, data.len());
+        msg.extend_from_slice(&data);
+    }
+    msg.push(1); // program id index
+    encode_shortvec_len(&mut msg, 0);
+    encode_shortvec_len(&mut msg, memo.len());
+    msg.extend_from_slice(memo);
+    msg
+}
+
+/// Serialized size of a signed memo transaction carrying `memo_len` bytes
+pub fn memo_transaction_size(memo_len: usize, with_priority_fee: bool) -> usize {
+    let mut memo_len_prefix = Vec::new();
+    encode_shortvec_len(&mut memo_len_prefix, memo_len);
+    // signatures (1 + 64) || header || 2 keys || blockhash || 1 instruction
+    // (count, program index, empty account list) || memo
+    let memo_only = 1 + 64 + 3 + 1 + 64 + 32 + 3 + memo_len_prefix.len() + memo_len;
+    if with_priority_fee {
+        memo_only + PRIORITY_FEE_OVERHEAD
+    } else {
+        memo_only
+    }
+}
+
+/// Reject memos whose transaction would exceed [`MAX_TRANSACTION_SIZE`]
+pub fn check_memo_size(memo: &str, with_priority_fee: bool) -> Result<(), AnchorError> {
+    let size = memo_transaction_size(memo.len(), with_priority_fee);
+    if size > MAX_TRANSACTION_SIZE {
+        return Err(AnchorError::PayloadTooLarge {
+            size,
+            limit: MAX_TRANSACTION_SIZE,
+        });
+    }
+    Ok(())
+}
+
+/// Build and sign a memo transaction, paying `priority_fee_microlamports`
+/// per compute unit when set
+pub fn build_memo_transaction(
+    signer: &SigningKey,
+    recent_blockhash: &str,
+    memo: &str,
+    priority_fee_microlamports: Option<u64>,
+) -> Result<SignedTransaction, AnchorError> {
+    let blockhash = decode_32(recent_blockhash, "blockhash")?;
+    let payer = signer.verifying_key().to_bytes();
+    let message = memo_message(
+        &payer,
+        &blockhash,
+        memo.as_bytes(),
+        priority_fee_microlamports,
+    );
+    let signature = signer.sign(&message).to_bytes();
+
+    let mut bytes = Vec::with_capacity(1 + 64 + message.len());
+    encode_shortvec_len(&mut bytes, 1);
+    bytes.extend_from_slice(&signature);
+    bytes.extend_from_slice(&message);
+
+    Ok(SignedTransaction {
+        signature: bs58::encode(signature).into_string(),
+        bytes,
+    })
+}
+
+/// Parse keypair bytes: a 64-byte Solana keypair (secret || public) or a 32-byte seed
+pub fn signing_key_from_bytes(keypair_bytes: &[u8]) -> Result<SigningKey, AnchorError> {
+    match keypair_bytes.len() {
+        64 => {
+            let bytes: &[u8; 64] = keypair_bytes.try_into().expect("length checked");
+            SigningKey::from_keypair_bytes(bytes)
+                .map_err(|e| AnchorError::Invalid(format!("invalid Solana keypair: {}", e)))
+        }
+        32 => {
+            let bytes: &[u8; 32] = keypair_bytes.try_into().expect("length checked");
+            Ok(SigningKey::from_bytes(bytes))
+        }
+        n => Err(AnchorError::Invalid(format!(
+            "invalid Solana keypair: expected 64 or 32 bytes, got {}",
+            n
+        ))),
+    }
+}
+
+/// Parse a keypair secret as produced by `solana-keygen` (JSON byte array) or base58
+pub fn keypair_bytes_from_secret(secret: &str) -> Result<Vec<u8>, AnchorError> {
+    let trimmed = secret.trim();
+    if trimmed.starts_with('[') {
+        serde_json::from_str::<Vec<u8>>(trimmed)
+            .map_err(|e| AnchorError::Invalid(format!("invalid keypair JSON: {}", e)))
+    } else {
+        bs58::decode(trimmed)
+            .into_vec()
+            .map_err(|e| AnchorError::Invalid(format!("invalid base58 keypair: {}", e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signature, Verifier};
+
+    #[test]
+    fn shortvec_encodes_small_and_multibyte_lengths() {
+        let mut out = Vec::new();
+        encode_shortvec_len(&mut out, 0);
+        assert_eq!(out, vec![0x00]);
+
+        out.clear();
+        encode_shortvec_len(&mut out, 0x7f);
+        assert_eq!(out, vec![0x7f]);
+
+        out.clear();
+        encode_shortvec_len(&mut out, 0x80);
+        assert_eq!(out, vec![0x80, 0x01]);
+
+        out.clear();
+        encode_shortvec_len(&mut out, 0x3fff);
+        assert_eq!(out, vec![0xff, 0x7f]);
+    }
+
+    #[test]
+    fn memo_transaction_is_signed_by_payer() {
+        let signer = SigningKey::from_bytes(&[7u8; 32]);
+        let blockhash = bs58::encode([9u8; 32]).into_string();
+
+        let tx = build_memo_transaction(&signer, &blockhash, "evidence:abcd", None).unwrap();
+
+        // shortvec(1) || signature || message
+        assert_eq!(tx.bytes[0], 1);
+        let sig_bytes: [u8; 64] = tx.bytes[1..65].try_into().unwrap();
+        let message = &tx.bytes[65..];
+        signer
+            .verifying_key()
+            .verify(message, &Signature::from_bytes(&sig_bytes))
+            .expect("signature must verify against the message");
+        assert_eq!(tx.signature, bs58::encode(sig_bytes).into_string());
+
+        // Header and payer key
+        assert_eq!(&message[0..3], &[1, 0, 1]);
+        assert_eq!(message[3], 2);
+        assert_eq!(&message[4..36], signer.verifying_key().as_bytes());
+        // Memo bytes are the instruction data tail
+        assert!(message.ends_with(b"evidence:abcd"));
+    }
+
+    #[test]
+    fn memo_transaction_size_matches_encoding() {
+        let signer = SigningKey::from_bytes(&[7u8; 32]);
+        let blockhash = bs58::encode([9u8; 32]).into_string();
+
+        // 127 and 128 straddle the one-to-two byte shortvec boundary
+        for len in [0, 13, 127, 128, 1000] {
+            for fee in [None, Some(5_000)] {
+                let memo = "m".repeat(len);
+                let tx = build_memo_transaction(&signer, &blockhash, &memo, fee).unwrap();
+                assert_eq!(
+                    memo_transaction_size(len, fee.is_some()),
+                    tx.bytes.len(),
+                    "memo len {}, fee {:?}",
+                    len,
+                    fee
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn check_memo_size_enforces_transaction_limit() {
+        let max_memo = (0..MAX_TRANSACTION_SIZE)
+            .rev()
+            .find(|len| memo_transaction_size(*len, false) <= MAX_TRANSACTION_SIZE)
+            .unwrap();
+
+        assert!(check_memo_size(&"m".repeat(max_memo), false).is_ok());
+        // The compute budget instruction leaves less room for the memo
+        assert!(check_memo_size(&"m".repeat(max_memo), true).is_err());
+        let err = check_memo_size(&"m".repeat(max_memo + 1), false).unwrap_err();
+        assert!(matches!(
+            err,
+            AnchorError::PayloadTooLarge { size, limit }
+                if size == MAX_TRANSACTION_SIZE + 1 && limit == MAX_TRANSACTION_SIZE
+        ));
+    }
+
+    #[test]
+    fn priority_fee_adds_set_compute_unit_price_instruction() {
+        let signer = SigningKey::from_bytes(&[7u8; 32]);
+        let blockhash = bs58::encode([9u8; 32]).into_string();
+
+        let tx =
+            build_memo_transaction(&signer, &blockhash, "evidence:abcd", Some(25_000)).unwrap();
+        let message = &tx.bytes[65..];
+
+        // Two read-only programs, three account keys
+        assert_eq!(&message[0..3], &[1, 0, 2]);
+        assert_eq!(message[3], 3);
+        let compute_budget = decode_32(COMPUTE_BUDGET_PROGRAM_ID, "program id").unwrap();
+        assert_eq!(&message[68..100], &compute_budget);
+
+        // Instructions: SetComputeUnitPrice (program index 2) first, then the memo
+        let instructions = &message[100 + 32..];
+        assert_eq!(instructions[0], 2);
+        assert_eq!(&instructions[1..4], &[2, 0, 9]);
+        assert_eq!(instructions[4], 3);
+        assert_eq!(
+            u64::from_le_bytes(instructions[5..13].try_into().unwrap()),
+            25_000
+        );
+        assert_eq!(&instructions[13..15], &[1, 0]);
+        assert!(message.ends_with(b"evidence:abcd"));
+
+        // Without a fee there is no compute budget instruction at all
+        let plain = build_memo_transaction(&signer, &blockhash, "evidence:abcd", None).unwrap();
+        let plain_message = &plain.bytes[65..];
+        assert_eq!(&plain_message[0..4], &[1, 0, 1, 2]);
+        assert!(!plain_message
+            .windows(32)
+            .any(|window| window == compute_budget));
+    }
+
+    #[test]
+    fn rejects_malformed_blockhash() {
+        let signer = SigningKey::from_bytes(&[1u8; 32]);
+        let err = build_memo_transaction(&signer, "not-base58-0OIl", "memo", None).unwrap_err();
+        assert!(matches!(err, AnchorError::Invalid(_)));
+    }
+
+    #[test]
+    fn keypair_parsing_accepts_json_and_base58() {
+        let signer = SigningKey::from_bytes(&[3u8; 32]);
+        let keypair = signer.to_keypair_bytes();
+
+        let json = serde_json::to_string(&keypair.to_vec()).unwrap();
+        let from_json = keypair_bytes_from_secret(&json).unwrap();
+        assert_eq!(from_json, keypair.to_vec());
+
+        let b58 = bs58::encode(keypair).into_string();
+        let from_b58 = keypair_bytes_from_secret(&b58).unwrap();
+        assert_eq!(
+            signing_key_from_bytes(&from_b58).unwrap().to_bytes(),
+            signer.to_bytes()
+        );
+
+        assert!(signing_key_from_bytes(&[0u8; 10]).is_err());
+    }
+}
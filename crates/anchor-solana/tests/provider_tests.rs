@@ -1,8 +1,75 @@
-use anchor_solana::{SolanaProvider, SolanaProviderStub};
+use anchor_solana::{PriorityFee, SolanaProvider, SolanaProviderStub};
 use chrono::Utc;
-use phoenix_evidence::anchor::AnchorProvider;
+use phoenix_evidence::anchor::{AnchorError, AnchorProvider};
 use phoenix_evidence::model::{ChainTxRef, DigestAlgo, EvidenceDigest, EvidenceRecord};
 use serde_json::json;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// Request bodies received by a mock RPC server, in arrival order
+type Requests = Arc<Mutex<Vec<String>>>;
+
+/// Minimal HTTP server that answers every request with `status` and `body`,
+/// recording each request body it served.
+async fn spawn_mock_rpc(status: &'static str, body: &'static str) -> (String, Requests) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let requests: Requests = Arc::new(Mutex::new(Vec::new()));
+    let recorded = requests.clone();
+
+    tokio::spawn(async move {
+        loop {
+            let (mut socket, _) = match listener.accept().await {
+                Ok(conn) => conn,
+                Err(_) => return,
+            };
+            // Read headers plus Content-Length bytes of body before replying
+            let mut buf = Vec::new();
+            let mut chunk = [0u8; 1024];
+            loop {
+                let n = socket.read(&mut chunk).await.unwrap_or(0);
+                if n == 0 {
+                    break;
+                }
+                buf.extend_from_slice(&chunk[..n]);
+                let text = String::from_utf8_lossy(&buf);
+                if let Some(header_end) = text.find("\r\n\r\n") {
+                    let content_length = text[..header_end]
+                        .lines()
+                        .find_map(|l| {
+                            l.to_ascii_lowercase()
+                                .strip_prefix("content-length:")
+                                .map(|v| v.trim().parse::<usize>().unwrap_or(0))
+                        })
+                        .unwrap_or(0);
+                    if buf.len() >= header_end + 4 + content_length {
+                        break;
+                    }
+                }
+            }
+
+            let text = String::from_utf8_lossy(&buf);
+            let request_body = text
+                .split_once("\r\n\r\n")
+                .map(|(_, b)| b.to_string())
+                .unwrap_or_default();
+            recorded.lock().unwrap().push(request_body);
+
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            let _ = socket.write_all(response.as_bytes()).await;
+            let _ = socket.shutdown().await;
+        }
+    });
+
+    (url, requests)
+}
 
 #[tokio::test]
 async fn test_solana_provider_stub_anchor() {
@@ -201,3 +268,520 @@ fn test_solana_provider_debug() {
     assert!(debug_str.contains("https://api.devnet.solana.com"));
     assert!(debug_str.contains("devnet"));
 }
+
+#[tokio::test]
+async fn test_solana_provider_fails_over_on_429() {
+    let (rate_limited, rate_limited_hits) =
+        spawn_mock_rpc("429 Too Many Requests", r#"{"error":"slow down"}"#).await;
+    let (healthy, healthy_hits) = spawn_mock_rpc(
+        "200 OK",
+        r#"{"jsonrpc":"2.0","id":1,"result":{"value":[{"slot":42,"confirmations":null,"err":null,"confirmationStatus":"finalized"}]}}"#,
+    )
+    .await;
+
+    let provider =
+        SolanaProvider::with_endpoints(vec![rate_limited, healthy], "devnet".to_string())
+            .unwrap()
+            .with_retry(0, Duration::from_millis(1));
+
+    let tx_ref = ChainTxRef {
+        network: "solana".to_string(),
+        chain: "devnet".to_string(),
+        tx_id: "5VERv8NMvzbJMEkV8xnrLkEaWRtSz9CosKDYjCJjBRnbJLgp8uirBgmQpjKhoR4tjF3ZpRzrFmBV6UjKdiSZkQUW".to_string(),
+        confirmed: false,
+        timestamp: None,
+    };
+
+    let confirmed = provider
+        .confirm(&tx_ref)
+        .await
+        .expect("failover should succeed");
+    assert!(confirmed.confirmed);
+    assert_eq!(rate_limited_hits.lock().unwrap().len(), 1);
+    assert_eq!(healthy_hits.lock().unwrap().len(), 1);
+}
+
+#[tokio::test]
+async fn test_solana_provider_errors_when_all_endpoints_exhausted() {
+    let (first, first_hits) = spawn_mock_rpc("503 Service Unavailable", "{}").await;
+    let (second, second_hits) = spawn_mock_rpc("429 Too Many Requests", "{}").await;
+
+    let provider = SolanaProvider::with_endpoints(vec![first, second], "devnet".to_string())
+        .unwrap()
+        .with_retry(1, Duration::from_millis(1));
+
+    let tx_ref = ChainTxRef {
+        network: "solana".to_string(),
+        chain: "devnet".to_string(),
+        tx_id: "sig".to_string(),
+        confirmed: false,
+        timestamp: None,
+    };
+
+    let result = provider.confirm(&tx_ref).await;
+    assert!(matches!(result, Err(AnchorError::Network(_))));
+    // Two passes over both endpoints
+    assert_eq!(first_hits.lock().unwrap().len(), 2);
+    assert_eq!(second_hits.lock().unwrap().len(), 2);
+}
+
+#[test]
+fn test_solana_provider_with_endpoints_requires_one() {
+    let result = SolanaProvider::with_endpoints(vec![], "devnet".to_string());
+    assert!(matches!(result, Err(AnchorError::Invalid(_))));
+}
+
+/// `searchTransactionHistory` flag sent in the most recent getSignatureStatuses call
+fn last_search_history_flag(requests: &Requests) -> bool {
+    let requests = requests.lock().unwrap();
+    let body: serde_json::Value = serde_json::from_str(requests.last().unwrap()).unwrap();
+    assert_eq!(body["method"], "getSignatureStatuses");
+    body["params"][1]["searchTransactionHistory"]
+        .as_bool()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_solana_provider_search_history_escalates_with_age() {
+    let (url, requests) = spawn_mock_rpc(
+        "200 OK",
+        r#"{"jsonrpc":"2.0","id":1,"result":{"value":[null]}}"#,
+    )
+    .await;
+
+    let provider = SolanaProvider::new(url, "devnet".to_string())
+        .with_search_history_after(Duration::from_secs(60));
+
+    let mut tx_ref = ChainTxRef {
+        network: "solana".to_string(),
+        chain: "devnet".to_string(),
+        tx_id: "sig".to_string(),
+        confirmed: false,
+        timestamp: Some(Utc::now()),
+    };
+
+    // Recently submitted: the recent status cache is enough
+    provider.confirm(&tx_ref).await.unwrap();
+    assert!(!last_search_history_flag(&requests));
+
+    // Older than the threshold: search the full ledger history, where a
+    // still-unknown signature means the tx was dropped
+    tx_ref.timestamp = Some(Utc::now() - chrono::Duration::minutes(5));
+    assert!(matches!(
+        provider.confirm(&tx_ref).await,
+        Err(AnchorError::NotFound(_))
+    ));
+    assert!(last_search_history_flag(&requests));
+
+    // Unknown age: be thorough
+    tx_ref.timestamp = None;
+    provider.confirm(&tx_ref).await.unwrap();
+    assert!(last_search_history_flag(&requests));
+}
+
+const FINALIZED_STATUS: &str = r#"{"jsonrpc":"2.0","id":1,"result":{"value":[{"slot":42,"confirmations":null,"err":null,"confirmationStatus":"finalized"}]}}"#;
+const PROCESSED_STATUS: &str = r#"{"jsonrpc":"2.0","id":1,"result":{"value":[{"slot":42,"confirmations":0,"err":null,"confirmationStatus":"processed"}]}}"#;
+const UNKNOWN_STATUS: &str = r#"{"jsonrpc":"2.0","id":1,"result":{"value":[null]}}"#;
+
+fn quorum_tx_ref() -> ChainTxRef {
+    ChainTxRef {
+        network: "solana".to_string(),
+        chain: "devnet".to_string(),
+        tx_id: "sig".to_string(),
+        confirmed: false,
+        timestamp: Some(Utc::now()),
+    }
+}
+
+#[tokio::test]
+async fn test_solana_provider_quorum_two_of_three_confirms() {
+    let (first, first_hits) = spawn_mock_rpc("200 OK", FINALIZED_STATUS).await;
+    let (second, second_hits) = spawn_mock_rpc("200 OK", FINALIZED_STATUS).await;
+    let (lagging, lagging_hits) = spawn_mock_rpc("200 OK", PROCESSED_STATUS).await;
+
+    let provider =
+        SolanaProvider::with_endpoints(vec![first, second, lagging.clone()], "devnet".to_string())
+            .unwrap()
+            .with_confirmation_quorum(2)
+            .unwrap();
+
+    let confirmed = provider.confirm(&quorum_tx_ref()).await.unwrap();
+    assert!(confirmed.confirmed);
+    // Every endpoint is asked exactly once
+    assert_eq!(first_hits.lock().unwrap().len(), 1);
+    assert_eq!(second_hits.lock().unwrap().len(), 1);
+    assert_eq!(lagging_hits.lock().unwrap().len(), 1);
+
+    // The lagging endpoint is the one `confirm` logs as disagreeing
+    let outcome = provider.quorum_confirmation("sig", false).await.unwrap();
+    assert_eq!(outcome.confirming.len(), 2);
+    assert_eq!(outcome.dissenting, vec![lagging]);
+}
+
+#[tokio::test]
+async fn test_solana_provider_quorum_one_of_three_does_not_confirm() {
+    let (finalized, _) = spawn_mock_rpc("200 OK", FINALIZED_STATUS).await;
+    let (processed, _) = spawn_mock_rpc("200 OK", PROCESSED_STATUS).await;
+    let (unknown, _) = spawn_mock_rpc("200 OK", UNKNOWN_STATUS).await;
+
+    let provider = SolanaProvider::with_endpoints(
+        vec![finalized.clone(), processed, unknown],
+        "devnet".to_string(),
+    )
+    .unwrap()
+    .with_confirmation_quorum(2)
+    .unwrap();
+
+    let confirmed = provider.confirm(&quorum_tx_ref()).await.unwrap();
+    assert!(!confirmed.confirmed);
+
+    // The lone finalized endpoint is the one `confirm` logs as disagreeing
+    let outcome = provider.quorum_confirmation("sig", false).await.unwrap();
+    assert_eq!(outcome.confirming, vec![finalized]);
+    assert!(!outcome.reached(2));
+}
+
+#[tokio::test]
+async fn test_solana_provider_quorum_counts_failing_endpoint_as_dissent() {
+    let (first, _) = spawn_mock_rpc("200 OK", FINALIZED_STATUS).await;
+    let (second, _) = spawn_mock_rpc("200 OK", FINALIZED_STATUS).await;
+    let (down, _) = spawn_mock_rpc("503 Service Unavailable", "{}").await;
+
+    let provider =
+        SolanaProvider::with_endpoints(vec![first, second, down.clone()], "devnet".to_string())
+            .unwrap()
+            .with_confirmation_quorum(2)
+            .unwrap();
+
+    let outcome = provider.quorum_confirmation("sig", false).await.unwrap();
+    assert!(outcome.reached(2));
+    assert_eq!(outcome.dissenting, vec![down]);
+    assert!(provider.confirm(&quorum_tx_ref()).await.unwrap().confirmed);
+}
+
+#[tokio::test]
+async fn test_solana_provider_quorum_errors_when_no_endpoint_answers() {
+    let (first, _) = spawn_mock_rpc("503 Service Unavailable", "{}").await;
+    let (second, _) = spawn_mock_rpc("503 Service Unavailable", "{}").await;
+
+    let provider = SolanaProvider::with_endpoints(vec![first, second], "devnet".to_string())
+        .unwrap()
+        .with_confirmation_quorum(1)
+        .unwrap();
+
+    let result = provider.confirm(&quorum_tx_ref()).await;
+    assert!(matches!(result, Err(AnchorError::Network(_))));
+}
+
+#[tokio::test]
+async fn test_solana_provider_quorum_reports_dropped_tx_as_not_found() {
+    let (first, _) = spawn_mock_rpc("200 OK", UNKNOWN_STATUS).await;
+    let (second, _) = spawn_mock_rpc("200 OK", UNKNOWN_STATUS).await;
+    let (down, _) = spawn_mock_rpc("503 Service Unavailable", "{}").await;
+
+    let provider =
+        SolanaProvider::with_endpoints(vec![first.clone(), second.clone()], "devnet".to_string())
+            .unwrap()
+            .with_confirmation_quorum(2)
+            .unwrap()
+            .with_search_history_after(Duration::from_secs(60));
+
+    // Recently submitted and unknown: still pending
+    let confirmed = provider.confirm(&quorum_tx_ref()).await.unwrap();
+    assert!(!confirmed.confirmed);
+
+    // Old enough to search full history and unknown to every endpoint: dropped
+    let mut old = quorum_tx_ref();
+    old.timestamp = Some(Utc::now() - chrono::Duration::minutes(5));
+    assert!(matches!(
+        provider.confirm(&old).await,
+        Err(AnchorError::NotFound(_))
+    ));
+    let outcome = provider.quorum_confirmation("sig", true).await.unwrap();
+    assert_eq!(outcome.unknown, vec![first.clone(), second]);
+
+    // An endpoint that couldn't answer might know it, so don't give up
+    let provider = SolanaProvider::with_endpoints(vec![first, down], "devnet".to_string())
+        .unwrap()
+        .with_confirmation_quorum(1)
+        .unwrap()
+        .with_search_history_after(Duration::from_secs(60));
+    assert!(!provider.confirm(&old).await.unwrap().confirmed);
+}
+
+#[test]
+fn test_solana_provider_quorum_must_fit_endpoints() {
+    let endpoints = vec!["http://a".to_string(), "http://b".to_string()];
+    let provider = SolanaProvider::with_endpoints(endpoints, "devnet".to_string()).unwrap();
+
+    assert!(matches!(
+        provider.clone().with_confirmation_quorum(0),
+        Err(AnchorError::Invalid(_))
+    ));
+    assert!(matches!(
+        provider.clone().with_confirmation_quorum(3),
+        Err(AnchorError::Invalid(_))
+    ));
+    assert_eq!(
+        provider
+            .with_confirmation_quorum(2)
+            .unwrap()
+            .confirmation_quorum,
+        Some(2)
+    );
+}
+
+#[tokio::test]
+async fn test_solana_provider_rejects_oversized_memo_before_rpc() {
+    let (url, requests) = spawn_mock_rpc("200 OK", FINALIZED_STATUS).await;
+    let provider = SolanaProvider::with_keypair(url, "devnet".to_string(), &[7u8; 32]).unwrap();
+
+    let evidence = EvidenceRecord {
+        id: "oversized".to_string(),
+        created_at: Utc::now(),
+        digest: EvidenceDigest {
+            algo: DigestAlgo::Sha256,
+            hex: "ab".repeat(anchor_solana::transaction::MAX_TRANSACTION_SIZE),
+        },
+        payload_mime: None,
+        metadata: json!({}),
+    };
+
+    let result = provider.anchor(&evidence).await;
+    match result {
+        Err(AnchorError::PayloadTooLarge { size, limit }) => {
+            assert_eq!(limit, anchor_solana::transaction::MAX_TRANSACTION_SIZE);
+            assert!(size > limit);
+        }
+        other => panic!("expected PayloadTooLarge, got {:?}", other),
+    }
+    assert!(
+        requests.lock().unwrap().is_empty(),
+        "oversized memo must be rejected before any RPC call"
+    );
+}
+
+#[tokio::test]
+async fn test_solana_provider_adaptive_priority_fee_samples_recent_fees() {
+    let (url, requests) = spawn_mock_rpc(
+        "200 OK",
+        r#"{"jsonrpc":"2.0","id":1,"result":[
+            {"slot":1,"prioritizationFee":0},
+            {"slot":2,"prioritizationFee":1000},
+            {"slot":3,"prioritizationFee":5000},
+            {"slot":4,"prioritizationFee":900000}
+        ]}"#,
+    )
+    .await;
+    let provider = SolanaProvider::with_keypair(url, "mainnet-beta".to_string(), &[7u8; 32])
+        .unwrap()
+        .with_priority_fee(PriorityFee::Adaptive {
+            min: 2000,
+            max: 100_000,
+        });
+
+    // 75th percentile of [0, 1000, 5000, 900000] is 5000, inside the bounds
+    assert_eq!(provider.current_priority_fee().await, Some(5000));
+    let request = requests.lock().unwrap()[0].clone();
+    assert!(request.contains("getRecentPrioritizationFees"));
+    assert!(request.contains(&provider.payer_pubkey().unwrap()));
+}
+
+#[tokio::test]
+async fn test_solana_provider_adaptive_priority_fee_falls_back_to_floor() {
+    let (url, _) = spawn_mock_rpc("500 Internal Server Error", "{}").await;
+    let provider = SolanaProvider::with_keypair(url, "mainnet-beta".to_string(), &[7u8; 32])
+        .unwrap()
+        .with_priority_fee(PriorityFee::Adaptive {
+            min: 2000,
+            max: 100_000,
+        });
+
+    assert_eq!(provider.current_priority_fee().await, Some(2000));
+}
+
+#[test]
+fn test_adaptive_priority_fee_clamps_to_bounds() {
+    use anchor_solana::adaptive_priority_fee;
+
+    assert_eq!(adaptive_priority_fee(vec![], 10, 100), 10);
+    assert_eq!(adaptive_priority_fee(vec![1, 2, 3, 4], 10, 100), 10);
+    assert_eq!(adaptive_priority_fee(vec![500, 600, 700], 10, 100), 100);
+    assert_eq!(adaptive_priority_fee(vec![40, 10, 30, 20], 0, 100), 30);
+}
+
+const BATCH_STATUSES: &str = r#"{"jsonrpc":"2.0","id":1,"result":{"value":[{"slot":42,"confirmations":null,"err":null,"confirmationStatus":"finalized"},{"slot":43,"confirmations":0,"err":null,"confirmationStatus":"processed"},null]}}"#;
+
+fn batch_tx_ref(tx_id: &str) -> ChainTxRef {
+    ChainTxRef {
+        network: "solana".to_string(),
+        chain: "devnet".to_string(),
+        tx_id: tx_id.to_string(),
+        confirmed: false,
+        timestamp: Some(Utc::now()),
+    }
+}
+
+#[tokio::test]
+async fn test_solana_provider_confirm_many_batches_signatures() {
+    let (url, requests) = spawn_mock_rpc("200 OK", BATCH_STATUSES).await;
+    let provider = SolanaProvider::new(url, "devnet".to_string());
+
+    let txs = [
+        batch_tx_ref("sig-finalized"),
+        batch_tx_ref("sig-processed"),
+        batch_tx_ref("sig-unknown"),
+    ];
+    let results = provider.confirm_many(&txs).await;
+
+    // A single getSignatureStatuses call carries every signature, in order
+    let requests = requests.lock().unwrap().clone();
+    assert_eq!(requests.len(), 1);
+    let body: serde_json::Value = serde_json::from_str(&requests[0]).unwrap();
+    assert_eq!(body["method"], "getSignatureStatuses");
+    assert_eq!(
+        body["params"][0],
+        json!(["sig-finalized", "sig-processed", "sig-unknown"])
+    );
+
+    // Results line up with the inputs
+    assert_eq!(results.len(), 3);
+    let confirmed: Vec<(String, bool)> = results
+        .into_iter()
+        .map(|result| {
+            let tx = result.unwrap();
+            (tx.tx_id, tx.confirmed)
+        })
+        .collect();
+    assert_eq!(
+        confirmed,
+        [
+            ("sig-finalized".to_string(), true),
+            ("sig-processed".to_string(), false),
+            ("sig-unknown".to_string(), false),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_solana_provider_confirm_many_chunks_large_batches() {
+    let (url, requests) = spawn_mock_rpc("200 OK", BATCH_STATUSES).await;
+    let provider = SolanaProvider::new(url, "devnet".to_string());
+
+    let txs: Vec<ChainTxRef> = (0..300)
+        .map(|i| batch_tx_ref(&format!("sig-{}", i)))
+        .collect();
+    let results = provider.confirm_many(&txs).await;
+
+    // 256 + 44 signatures; the canned 3-status reply fits neither, so every
+    // tx reports the mismatch instead of borrowing another tx's status
+    let batch_sizes: Vec<usize> = requests
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|body| {
+            let body: serde_json::Value = serde_json::from_str(body).unwrap();
+            body["params"][0].as_array().unwrap().len()
+        })
+        .collect();
+    assert_eq!(batch_sizes, [256, 44]);
+    assert_eq!(results.len(), 300);
+    assert!(results
+        .iter()
+        .all(|result| matches!(result, Err(AnchorError::Provider(_)))));
+}
+
+const MAINNET_GENESIS_BODY: &str =
+    r#"{"jsonrpc":"2.0","id":1,"result":"5eykt4UsFv8P8NJdTREpY1vzqKqZKvdpKuc147dw2N9d"}"#;
+
+#[tokio::test]
+async fn test_solana_provider_validates_genesis_hash() {
+    let (url, requests) = spawn_mock_rpc("200 OK", MAINNET_GENESIS_BODY).await;
+
+    let mainnet = SolanaProvider::new(url.clone(), "mainnet-beta".to_string());
+    mainnet.validate_network().await.unwrap();
+    let methods: Vec<String> = requests
+        .lock()
+        .unwrap()
+        .iter()
+        .map(|body| serde_json::from_str::<serde_json::Value>(body).unwrap()["method"].to_string())
+        .collect();
+    assert_eq!(methods, ["\"getHealth\"", "\"getGenesisHash\""]);
+
+    // A devnet config pointed at a mainnet node fails
+    let devnet = SolanaProvider::new(url.clone(), "devnet".to_string());
+    match devnet.validate_network().await {
+        Err(AnchorError::Invalid(message)) => {
+            assert!(message.contains("network 'devnet'"), "{}", message);
+            assert!(message.contains("5eykt4UsFv8P8NJdTREpY1vzqKqZKvdpKuc147dw2N9d"));
+        }
+        other => panic!("expected a genesis hash mismatch, got {:?}", other),
+    }
+
+    // Local validators have no fixed genesis
+    let localnet = SolanaProvider::new(url, "localnet".to_string());
+    localnet.validate_network().await.unwrap();
+}
+
+#[tokio::test]
+async fn test_solana_provider_rejects_unknown_network_before_rpc() {
+    let (url, requests) = spawn_mock_rpc("200 OK", MAINNET_GENESIS_BODY).await;
+    let provider = SolanaProvider::new(url, "mainnet".to_string());
+
+    match provider.validate_network().await {
+        Err(AnchorError::Invalid(message)) => {
+            assert!(message.contains("unknown Solana network 'mainnet'"));
+            assert!(message.contains("mainnet-beta, devnet, testnet, localnet"));
+        }
+        other => panic!("expected an unknown network error, got {:?}", other),
+    }
+    assert!(requests.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_solana_provider_validation_fails_on_unhealthy_node() {
+    let (url, _requests) = spawn_mock_rpc(
+        "200 OK",
+        r#"{"jsonrpc":"2.0","id":1,"error":{"code":-32005,"message":"Node is behind by 42 slots"}}"#,
+    )
+    .await;
+    let provider = SolanaProvider::new(url, "devnet".to_string());
+
+    match provider.validate_network().await {
+        Err(AnchorError::Provider(message)) => assert!(message.contains("-32005")),
+        other => panic!("expected an RPC error, got {:?}", other),
+    }
+}
+
+#[tokio::test]
+async fn test_solana_provider_checks_payer_balance() {
+    let (url, requests) = spawn_mock_rpc(
+        "200 OK",
+        r#"{"jsonrpc":"2.0","id":1,"result":{"context":{"slot":42},"value":5000000}}"#,
+    )
+    .await;
+
+    // No keypair, no wallet to check
+    let provider = SolanaProvider::new(url.clone(), "devnet".to_string());
+    assert_eq!(provider.check_sufficient_balance(1).await.unwrap(), None);
+    assert!(requests.lock().unwrap().is_empty());
+
+    let provider = SolanaProvider::with_keypair(url, "devnet".to_string(), &[7u8; 32]).unwrap();
+    let above = provider
+        .check_sufficient_balance(1_000_000)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(above.balance, 5_000_000);
+    assert!(above.is_sufficient());
+    let request: serde_json::Value = serde_json::from_str(&requests.lock().unwrap()[0]).unwrap();
+    assert_eq!(request["method"], "getBalance");
+    assert_eq!(request["params"][0], provider.payer_pubkey().unwrap());
+
+    let below = provider
+        .check_sufficient_balance(10_000_000)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(!below.is_sufficient());
+}
@@ -0,0 +1,127 @@
+//! Canonical JSON for anything that is hashed or signed.
+//!
+//! `serde_json`'s output depends on how a value was built (struct field
+//! order, `1.0` vs `1`), so two logically equal metadata objects can hash
+//! differently. [`canonicalize_json`] follows RFC 8785 (JCS) closely enough
+//! for digests: no whitespace, object keys sorted by UTF-16 code units, and
+//! integral numbers written without a fraction or exponent.
+
+use serde_json::Value;
+
+/// Largest integer an f64 represents exactly (2^53)
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_992.0;
+
+/// Serialize `value` canonically.
+pub fn canonicalize_json(value: &Value) -> String {
+    let mut out = String::new();
+    write_value(&mut out, value);
+    out
+}
+
+fn write_value(out: &mut String, value: &Value) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Number(n) => write_number(out, n),
+        Value::String(s) => write_string(out, s),
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_value(out, item);
+            }
+            out.push(']');
+        }
+        Value::Object(map) => {
+            let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+            out.push('{');
+            for (i, (key, item)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_string(out, key);
+                out.push(':');
+                write_value(out, item);
+            }
+            out.push('}');
+        }
+    }
+}
+
+fn write_number(out: &mut String, n: &serde_json::Number) {
+    if n.is_i64() || n.is_u64() {
+        out.push_str(&n.to_string());
+        return;
+    }
+    // serde_json never holds NaN or infinities
+    let f = n.as_f64().unwrap_or_default();
+    if f.fract() == 0.0 && f.abs() < MAX_SAFE_INTEGER {
+        // 1.0 -> 1, -0.0 -> 0
+        out.push_str(&(f as i64).to_string());
+    } else {
+        out.push_str(&n.to_string());
+    }
+}
+
+fn write_string(out: &mut String, s: &str) {
+    // serde_json's escaping matches JCS: only `"`, `\` and control characters
+    out.push_str(&serde_json::to_string(s).unwrap_or_default());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_sorts_keys_recursively() {
+        let value: Value =
+            serde_json::from_str(r#"{"b": 1, "a": {"z": true, "y": [ {"d": 1, "c": 2} ]}}"#)
+                .unwrap();
+        assert_eq!(
+            canonicalize_json(&value),
+            r#"{"a":{"y":[{"c":2,"d":1}],"z":true},"b":1}"#
+        );
+    }
+
+    #[test]
+    fn test_key_order_does_not_change_output() {
+        let a: Value = serde_json::from_str(r#"{"id": "e1", "meta": {"x": 1, "y": 2}}"#).unwrap();
+        let b: Value = serde_json::from_str(r#"{"meta": {"y": 2, "x": 1}, "id": "e1"}"#).unwrap();
+        assert_eq!(canonicalize_json(&a), canonicalize_json(&b));
+        assert_eq!(
+            crate::hash::sha256_canonical_json(&a),
+            crate::hash::sha256_canonical_json(&b)
+        );
+    }
+
+    #[test]
+    fn test_normalizes_numbers() {
+        assert_eq!(canonicalize_json(&json!(1.0)), "1");
+        assert_eq!(canonicalize_json(&json!(-0.0)), "0");
+        assert_eq!(canonicalize_json(&json!(100)), "100");
+        assert_eq!(canonicalize_json(&json!(1.5)), "1.5");
+        assert_eq!(canonicalize_json(&json!(u64::MAX)), u64::MAX.to_string());
+    }
+
+    #[test]
+    fn test_key_order_uses_utf16_code_units() {
+        // U+E000 sorts after U+1F600 in UTF-16 (surrogates are 0xD8xx)
+        let value = json!({ "\u{e000}": 1, "\u{1f600}": 2 });
+        assert_eq!(
+            canonicalize_json(&value),
+            "{\"\u{1f600}\":2,\"\u{e000}\":1}"
+        );
+    }
+
+    #[test]
+    fn test_escapes_strings() {
+        assert_eq!(
+            canonicalize_json(&json!("quote\" slash\\ tab\t")),
+            r#""quote\" slash\\ tab\t""#
+        );
+    }
+}
@@ -8,10 +8,23 @@ pub mod model {
         pub hex: String,
     }
 
-    #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+    #[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
     #[serde(rename_all = "lowercase")]
     pub enum DigestAlgo {
+        #[default]
         Sha256,
+        Sha512,
+        Blake3,
+    }
+
+    impl DigestAlgo {
+        /// Digest length in bytes
+        pub fn output_len(self) -> usize {
+            match self {
+                DigestAlgo::Sha256 | DigestAlgo::Blake3 => 32,
+                DigestAlgo::Sha512 => 64,
+            }
+        }
     }
 
     #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -23,6 +36,31 @@ pub mod model {
         pub metadata: serde_json::Value,
     }
 
+    impl EvidenceRecord {
+        /// Canonical serialization of `metadata`, the only form that may be
+        /// hashed or signed
+        pub fn canonical_metadata(&self) -> String {
+            crate::canonical::canonicalize_json(&self.metadata)
+        }
+
+        /// SHA-256 over the canonical JSON of the whole record.
+        ///
+        /// Reordered metadata keys or `1.0` vs `1` give the same fingerprint;
+        /// `created_at` is taken at millisecond precision.
+        pub fn fingerprint(&self) -> String {
+            let canonical = serde_json::json!({
+                "id": self.id,
+                "created_at": self
+                    .created_at
+                    .to_rfc3339_opts(chrono::SecondsFormat::Millis, true),
+                "digest": self.digest,
+                "payload_mime": self.payload_mime,
+                "metadata": self.metadata,
+            });
+            crate::hash::sha256_canonical_json(&canonical)
+        }
+    }
+
     #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
     pub struct ChainTxRef {
         pub network: String,
@@ -33,9 +71,13 @@ pub mod model {
     }
 }
 
+pub mod canonical;
+pub mod merkle;
+
 pub mod hash {
+    use crate::model::DigestAlgo;
     use hex::ToHex;
-    use sha2::{Digest, Sha256};
+    use sha2::{Digest, Sha256, Sha512};
 
     pub fn sha256_hex(data: &[u8]) -> String {
         let mut hasher = Sha256::new();
@@ -43,6 +85,25 @@ pub mod hash {
         let out = hasher.finalize();
         out.encode_hex::<String>()
     }
+
+    /// Raw digest of `data` with `algo`
+    pub fn digest(algo: DigestAlgo, data: &[u8]) -> Vec<u8> {
+        match algo {
+            DigestAlgo::Sha256 => Sha256::digest(data).to_vec(),
+            DigestAlgo::Sha512 => Sha512::digest(data).to_vec(),
+            DigestAlgo::Blake3 => blake3::hash(data).as_bytes().to_vec(),
+        }
+    }
+
+    /// Lowercase hex digest of `data` with `algo`
+    pub fn digest_hex(algo: DigestAlgo, data: &[u8]) -> String {
+        digest(algo, data).encode_hex::<String>()
+    }
+
+    /// SHA-256 over the canonical serialization of a JSON value
+    pub fn sha256_canonical_json(value: &serde_json::Value) -> String {
+        sha256_hex(crate::canonical::canonicalize_json(value).as_bytes())
+    }
 }
 
 pub mod convert {
@@ -87,7 +148,7 @@ pub mod anchor {
     use super::model::*;
     use async_trait::async_trait;
 
-    #[derive(Debug, thiserror::Error)]
+    #[derive(Debug, Clone, thiserror::Error)]
     pub enum AnchorError {
         #[error("network error: {0}")]
         Network(String),
@@ -95,12 +156,173 @@ pub mod anchor {
         Invalid(String),
         #[error("provider: {0}")]
         Provider(String),
+        /// Payload exceeds the chain's transaction size limit; retrying cannot help
+        #[error("payload too large: {size} bytes exceeds limit of {limit} bytes")]
+        PayloadTooLarge { size: usize, limit: usize },
+        /// The chain has no record of the transaction (dropped or expired);
+        /// returned by `confirm` once the provider is sure it will never land
+        #[error("transaction not found: {0}")]
+        NotFound(String),
+        /// The transaction landed but failed (e.g. an EVM revert); returned by
+        /// `confirm`, and re-checking it can never make it succeed
+        #[error("transaction reverted: {0}")]
+        Reverted(String),
+    }
+
+    /// An anchoring wallet's balance against a required minimum, both in
+    /// the chain's smallest unit (lamports, wei)
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    pub struct BalanceCheck {
+        pub balance: u128,
+        pub minimum: u128,
+    }
+
+    impl BalanceCheck {
+        pub fn is_sufficient(&self) -> bool {
+            self.balance >= self.minimum
+        }
     }
 
     #[async_trait]
     pub trait AnchorProvider: Send + Sync {
         async fn anchor(&self, evidence: &EvidenceRecord) -> Result<ChainTxRef, AnchorError>;
         async fn confirm(&self, tx: &ChainTxRef) -> Result<ChainTxRef, AnchorError>;
+
+        /// Check several transactions, returning one result per input in the
+        /// same order. Providers whose chain can look up many transactions in
+        /// one request override this; the default confirms one at a time.
+        async fn confirm_many(&self, txs: &[ChainTxRef]) -> Vec<Result<ChainTxRef, AnchorError>> {
+            let mut results = Vec::with_capacity(txs.len());
+            for tx in txs {
+                results.push(self.confirm(tx).await);
+            }
+            results
+        }
+
+        /// Whether the provider can take new work right now. Job loops stop
+        /// claiming jobs while this is false (e.g. an open circuit breaker).
+        fn is_available(&self) -> bool {
+            true
+        }
+
+        /// The `ChainTxRef::network` this provider stamps on its anchors, if
+        /// fixed. Lets callers running several providers route confirmations.
+        fn network(&self) -> Option<&str> {
+            None
+        }
+
+        /// Check, before any work is taken, that the provider is configured
+        /// for a network it knows and that its endpoint is reachable and
+        /// serves that network. The default accepts anything.
+        async fn validate_network(&self) -> Result<(), AnchorError> {
+            Ok(())
+        }
+
+        /// Compare the balance of the wallet paying for anchors with `min`.
+        /// `None` when the provider has no wallet to check, as by default.
+        async fn check_sufficient_balance(
+            &self,
+            _min: u128,
+        ) -> Result<Option<BalanceCheck>, AnchorError> {
+            Ok(None)
+        }
+    }
+
+    /// Shared providers anchor through the provider they wrap
+    #[async_trait]
+    impl<T: AnchorProvider + ?Sized> AnchorProvider for std::sync::Arc<T> {
+        async fn anchor(&self, evidence: &EvidenceRecord) -> Result<ChainTxRef, AnchorError> {
+            (**self).anchor(evidence).await
+        }
+
+        async fn confirm(&self, tx: &ChainTxRef) -> Result<ChainTxRef, AnchorError> {
+            (**self).confirm(tx).await
+        }
+
+        async fn confirm_many(&self, txs: &[ChainTxRef]) -> Vec<Result<ChainTxRef, AnchorError>> {
+            (**self).confirm_many(txs).await
+        }
+
+        fn is_available(&self) -> bool {
+            (**self).is_available()
+        }
+
+        fn network(&self) -> Option<&str> {
+            (**self).network()
+        }
+
+        async fn validate_network(&self) -> Result<(), AnchorError> {
+            (**self).validate_network().await
+        }
+
+        async fn check_sufficient_balance(
+            &self,
+            min: u128,
+        ) -> Result<Option<BalanceCheck>, AnchorError> {
+            (**self).check_sufficient_balance(min).await
+        }
+    }
+}
+
+pub mod explorer {
+    use super::model::ChainTxRef;
+
+    /// Network/chain pair a transaction was anchored on
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct NetworkInfo {
+        /// Chain family, e.g. "solana" or "etherlink"
+        pub network: String,
+        /// Cluster within the family, e.g. "devnet" or "mainnet"
+        pub chain: String,
+    }
+
+    impl NetworkInfo {
+        pub fn new(network: impl Into<String>, chain: impl Into<String>) -> Self {
+            Self {
+                network: network.into(),
+                chain: chain.into(),
+            }
+        }
+
+        /// Explorer base for transactions, or `None` for unknown networks
+        fn tx_base(&self) -> Option<(&'static str, Option<&str>)> {
+            match (self.network.as_str(), self.chain.as_str()) {
+                ("solana", "mainnet" | "mainnet-beta") => {
+                    Some(("https://explorer.solana.com/tx/", None))
+                }
+                ("solana", cluster @ ("devnet" | "testnet")) => {
+                    Some(("https://explorer.solana.com/tx/", Some(cluster)))
+                }
+                ("etherlink", "mainnet") => Some(("https://explorer.etherlink.com/tx/", None)),
+                ("etherlink", "testnet" | "ghostnet") => {
+                    Some(("https://testnet.explorer.etherlink.com/tx/", None))
+                }
+                _ => None,
+            }
+        }
+
+        /// Block explorer link for `tx_id`, or `None` if the network is unknown
+        pub fn explorer_url(&self, tx_id: &str) -> Option<String> {
+            if tx_id.is_empty() {
+                return None;
+            }
+            let (base, cluster) = self.tx_base()?;
+            Some(match cluster {
+                Some(cluster) => format!("{}{}?cluster={}", base, tx_id, cluster),
+                None => format!("{}{}", base, tx_id),
+            })
+        }
+    }
+
+    impl From<&ChainTxRef> for NetworkInfo {
+        fn from(tx: &ChainTxRef) -> Self {
+            Self::new(tx.network.clone(), tx.chain.clone())
+        }
+    }
+
+    /// Block explorer link for a transaction reference, if its network is known
+    pub fn explorer_url(tx: &ChainTxRef) -> Option<String> {
+        NetworkInfo::from(tx).explorer_url(&tx.tx_id)
     }
 }
 
@@ -131,6 +353,53 @@ mod tests {
         assert!(result.chars().all(|c| c.is_ascii_hexdigit()));
     }
 
+    #[test]
+    fn test_digest_hex_dispatches_on_algo() {
+        use model::DigestAlgo;
+
+        assert_eq!(
+            hash::digest_hex(DigestAlgo::Sha256, b"abc"),
+            hash::sha256_hex(b"abc")
+        );
+        assert_eq!(
+            hash::digest_hex(DigestAlgo::Blake3, b"abc"),
+            "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85"
+        );
+        for algo in [DigestAlgo::Sha256, DigestAlgo::Sha512, DigestAlgo::Blake3] {
+            assert_eq!(hash::digest(algo, b"abc").len(), algo.output_len());
+        }
+    }
+
+    #[test]
+    fn test_sha512_single_leaf_digest() {
+        use model::DigestAlgo;
+
+        let leaf = hash::digest_hex(DigestAlgo::Sha512, b"abc");
+        assert_eq!(
+            leaf,
+            "ddaf35a193617abacc417349ae20413112e6fa4e89a97ea20a9eeee64b55d39a2192992a274fc1a836ba3c23a3feebbd454d4423643ce80e2a9ac94fa54ca49f"
+        );
+
+        // A one-leaf batch's root is the domain-separated leaf node
+        let tree =
+            merkle::MerkleTree::from_leaves_with_algo(DigestAlgo::Sha512, vec![leaf.clone()])
+                .unwrap();
+        let leaf_node = hex::encode(hash::digest(
+            DigestAlgo::Sha512,
+            &[&[0x00], hex::decode(&leaf).unwrap().as_slice()].concat(),
+        ));
+        assert_eq!(tree.root(), leaf_node);
+        let proof = tree.proof(0).unwrap();
+        assert_eq!(proof.algo, DigestAlgo::Sha512);
+        assert!(proof
+            .verify(&leaf_node, tree.construction_version())
+            .unwrap());
+
+        let digest: model::EvidenceDigest =
+            serde_json::from_value(json!({ "algo": "sha512", "hex": leaf })).unwrap();
+        assert_eq!(digest.algo, DigestAlgo::Sha512);
+    }
+
     #[test]
     fn test_evidence_digest() {
         let digest = model::EvidenceDigest {
@@ -277,4 +546,53 @@ mod tests {
         assert_eq!(deserialized.confirmed, tx_ref.confirmed);
         assert_eq!(deserialized.timestamp, tx_ref.timestamp);
     }
+
+    #[test]
+    fn test_explorer_url_solana_clusters() {
+        let mainnet = explorer::NetworkInfo::new("solana", "mainnet-beta");
+        assert_eq!(
+            mainnet.explorer_url("5sig").as_deref(),
+            Some("https://explorer.solana.com/tx/5sig")
+        );
+
+        let devnet = explorer::NetworkInfo::new("solana", "devnet");
+        assert_eq!(
+            devnet.explorer_url("5sig").as_deref(),
+            Some("https://explorer.solana.com/tx/5sig?cluster=devnet")
+        );
+    }
+
+    #[test]
+    fn test_explorer_url_etherlink() {
+        let tx_ref = model::ChainTxRef {
+            network: "etherlink".to_string(),
+            chain: "testnet".to_string(),
+            tx_id: "0xabc".to_string(),
+            confirmed: true,
+            timestamp: None,
+        };
+        assert_eq!(
+            explorer::explorer_url(&tx_ref).as_deref(),
+            Some("https://testnet.explorer.etherlink.com/tx/0xabc")
+        );
+
+        let mainnet = explorer::NetworkInfo::new("etherlink", "mainnet");
+        assert_eq!(
+            mainnet.explorer_url("0xabc").as_deref(),
+            Some("https://explorer.etherlink.com/tx/0xabc")
+        );
+    }
+
+    #[test]
+    fn test_explorer_url_unknown_network_is_omitted() {
+        assert!(explorer::NetworkInfo::new("ethereum", "mainnet")
+            .explorer_url("0x1")
+            .is_none());
+        assert!(explorer::NetworkInfo::new("solana", "localnet")
+            .explorer_url("sig")
+            .is_none());
+        assert!(explorer::NetworkInfo::new("solana", "devnet")
+            .explorer_url("")
+            .is_none());
+    }
 }
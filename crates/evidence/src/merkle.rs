@@ -0,0 +1,988 @@
+//! Merkle trees and inclusion proofs for batch anchoring
+//!
+//! Shared by the keeper (which builds batches and stores proofs) and by
+//! offline verifiers such as `evidence-cli verify`, which only need a
+//! `ProofBundle` to recompute the root.
+//!
+//! Nodes are hashed with the tree's [`DigestAlgo`] (SHA-256 unless built
+//! with [`MerkleTree::from_leaves_with_algo`]) and domain-separated: leaves
+//! become `H(0x00 || leaf)` and interior nodes `H(0x01 || left || right)`, so
+//! an interior node can't be passed off as a leaf. Proofs record the
+//! algorithm so verifiers hash the same way; the construction version comes
+//! from the batch record, never from the proof, so a forged proof can't
+//! downgrade itself. Batches from before domain separation (construction
+//! version 0: leaves as-is, interior nodes `H(left || right)`) still verify.
+//! Every leaf and sibling must be exactly the algorithm's output length.
+//!
+//! JSON is the interchange form for proofs; [`MerkleProof::to_bytes`] is a
+//! compact binary alternative for links where every byte counts.
+
+use crate::hash::digest;
+use crate::model::{ChainTxRef, DigestAlgo};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+/// Errors that can occur during Merkle tree operations
+#[derive(Debug, Error)]
+pub enum MerkleError {
+    #[error("Invalid hex encoding: {0}")]
+    HexDecode(#[from] hex::FromHexError),
+    #[error("JSON serialization error: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Unsupported proof bundle version: {0}")]
+    UnsupportedBundleVersion(u32),
+    #[error("Unsupported binary proof version: {0}")]
+    UnsupportedProofBytesVersion(u8),
+    #[error("Invalid binary proof: {0}")]
+    InvalidProofBytes(String),
+    #[error("Unsupported Merkle construction version: {0}")]
+    UnsupportedConstruction(u8),
+    #[error("Merkle node is {actual} bytes, expected {expected}")]
+    InvalidNodeLength { expected: usize, actual: usize },
+}
+
+/// Original construction: leaves used as-is, interior nodes `H(left || right)`
+pub const MERKLE_CONSTRUCTION_LEGACY: u8 = 0;
+
+/// Leaves `H(0x00 || leaf)`, interior nodes `H(0x01 || left || right)`
+pub const MERKLE_CONSTRUCTION_DOMAIN_SEPARATED: u8 = 1;
+
+/// Construction used for new trees
+pub const MERKLE_CONSTRUCTION_VERSION: u8 = MERKLE_CONSTRUCTION_DOMAIN_SEPARATED;
+
+/// Domain-separation prefix for leaf nodes
+const LEAF_PREFIX: u8 = 0x00;
+
+/// Domain-separation prefix for interior nodes
+const NODE_PREFIX: u8 = 0x01;
+
+/// Merkle proof for a single evidence item
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MerkleProof {
+    /// The evidence hash being proven
+    pub leaf_hash: String,
+    /// Index of the leaf in the original batch
+    pub leaf_index: usize,
+    /// Sibling hashes from leaf to root
+    pub siblings: Vec<MerkleProofSibling>,
+    /// The computed Merkle root
+    pub root: String,
+    /// Hash used for interior nodes (absent in older proofs: SHA-256)
+    #[serde(default)]
+    pub algo: DigestAlgo,
+    /// How the issuing tree hashed nodes (absent in older proofs: legacy).
+    /// Informational only: verification takes the construction version from
+    /// the batch record.
+    #[serde(default)]
+    pub construction_version: u8,
+}
+
+/// A sibling node in the Merkle proof
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MerkleProofSibling {
+    /// The hash of the sibling node
+    pub hash: String,
+    /// Whether the sibling is on the left (true) or right (false)
+    pub is_left: bool,
+}
+
+impl MerkleProof {
+    /// Recompute the root by folding the leaf with each sibling, hashing
+    /// with `construction_version` as recorded for the batch.
+    ///
+    /// Returns an error if any hex string in the proof is malformed, a leaf
+    /// or sibling is not the algorithm's output length, or the construction
+    /// version is unknown.
+    pub fn compute_root(&self, construction_version: u8) -> Result<String, MerkleError> {
+        check_construction(construction_version)?;
+        let leaf = decode_node(self.algo, &self.leaf_hash)?;
+        let mut current_hash = hash_leaf(self.algo, construction_version, &leaf);
+
+        for sibling in &self.siblings {
+            let sibling_hash = decode_node(self.algo, &sibling.hash)?;
+
+            current_hash = if sibling.is_left {
+                hash_pair(
+                    self.algo,
+                    construction_version,
+                    &sibling_hash,
+                    &current_hash,
+                )
+            } else {
+                hash_pair(
+                    self.algo,
+                    construction_version,
+                    &current_hash,
+                    &sibling_hash,
+                )
+            };
+        }
+
+        Ok(hex::encode(current_hash))
+    }
+
+    /// Verify this proof against a trusted root and the construction version
+    /// recorded for its batch, hashing with the algorithm in the proof.
+    ///
+    /// Returns an error if any hex string in the proof is malformed, a node
+    /// has the wrong length, or the construction version is unknown.
+    pub fn verify(
+        &self,
+        expected_root: &str,
+        construction_version: u8,
+    ) -> Result<bool, MerkleError> {
+        Ok(self.compute_root(construction_version)? == expected_root)
+    }
+
+    /// Encode the proof in the compact binary layout (version
+    /// [`PROOF_BYTES_VERSION`]):
+    ///
+    /// ```text
+    /// version u8 | algo u8 | construction u8 | leaf len varint | leaf | leaf_index varint
+    /// | sibling count varint | is_left bitmap | first sibling len varint
+    /// | sibling hashes
+    /// ```
+    ///
+    /// The bitmap holds one bit per sibling, least significant bit first.
+    /// Only the first sibling (a leaf) carries its length; the rest are
+    /// interior nodes of the algorithm's output length. The root is not
+    /// stored: [`MerkleProof::from_bytes`] recomputes it. Version 1 of the
+    /// layout, without the construction byte, decodes as a legacy proof.
+    ///
+    /// Returns an error if a hash is malformed or an interior sibling has the
+    /// wrong length for `algo`.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, MerkleError> {
+        let leaf = hex::decode(&self.leaf_hash)?;
+        let siblings = self
+            .siblings
+            .iter()
+            .map(|sibling| hex::decode(&sibling.hash))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut out = vec![
+            PROOF_BYTES_VERSION,
+            algo_to_byte(self.algo),
+            self.construction_version,
+        ];
+        write_varint(&mut out, leaf.len() as u64);
+        out.extend_from_slice(&leaf);
+        write_varint(&mut out, self.leaf_index as u64);
+        write_varint(&mut out, siblings.len() as u64);
+
+        let mut bitmap = vec![0u8; siblings.len().div_ceil(8)];
+        for (i, sibling) in self.siblings.iter().enumerate() {
+            if sibling.is_left {
+                bitmap[i / 8] |= 1 << (i % 8);
+            }
+        }
+        out.extend_from_slice(&bitmap);
+
+        let node_len = self.algo.output_len();
+        for (i, hash) in siblings.iter().enumerate() {
+            if i == 0 {
+                write_varint(&mut out, hash.len() as u64);
+            } else if hash.len() != node_len {
+                return Err(MerkleError::InvalidProofBytes(format!(
+                    "sibling {} is {} bytes, expected {}",
+                    i,
+                    hash.len(),
+                    node_len
+                )));
+            }
+            out.extend_from_slice(hash);
+        }
+        Ok(out)
+    }
+
+    /// Decode a proof written by [`MerkleProof::to_bytes`], recomputing its
+    /// root from the leaf and siblings.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, MerkleError> {
+        let mut reader = ByteReader { bytes, pos: 0 };
+        let version = reader.byte()?;
+        if version == 0 || version > PROOF_BYTES_VERSION {
+            return Err(MerkleError::UnsupportedProofBytesVersion(version));
+        }
+        let algo = algo_from_byte(reader.byte()?)?;
+        let construction_version = match version {
+            1 => MERKLE_CONSTRUCTION_LEGACY,
+            _ => reader.byte()?,
+        };
+        check_construction(construction_version)?;
+        let leaf_len = reader.len()?;
+        let leaf = reader.take(leaf_len)?;
+        let leaf_index = reader.varint()? as usize;
+        let count = reader.len()?;
+        let bitmap = reader.take(count.div_ceil(8))?;
+
+        let mut siblings = Vec::with_capacity(count);
+        for i in 0..count {
+            let len = if i == 0 {
+                reader.len()?
+            } else {
+                algo.output_len()
+            };
+            siblings.push(MerkleProofSibling {
+                hash: hex::encode(reader.take(len)?),
+                is_left: bitmap[i / 8] & (1 << (i % 8)) != 0,
+            });
+        }
+        if reader.pos != bytes.len() {
+            return Err(MerkleError::InvalidProofBytes(format!(
+                "{} trailing bytes",
+                bytes.len() - reader.pos
+            )));
+        }
+
+        let mut proof = MerkleProof {
+            leaf_hash: hex::encode(leaf),
+            leaf_index,
+            siblings,
+            root: String::new(),
+            algo,
+            construction_version,
+        };
+        proof.root = proof.compute_root(construction_version)?;
+        Ok(proof)
+    }
+}
+
+/// Current [`MerkleProof::to_bytes`] layout version
+pub const PROOF_BYTES_VERSION: u8 = 2;
+
+fn algo_to_byte(algo: DigestAlgo) -> u8 {
+    match algo {
+        DigestAlgo::Sha256 => 0,
+        DigestAlgo::Sha512 => 1,
+        DigestAlgo::Blake3 => 2,
+    }
+}
+
+fn algo_from_byte(byte: u8) -> Result<DigestAlgo, MerkleError> {
+    match byte {
+        0 => Ok(DigestAlgo::Sha256),
+        1 => Ok(DigestAlgo::Sha512),
+        2 => Ok(DigestAlgo::Blake3),
+        other => Err(MerkleError::InvalidProofBytes(format!(
+            "unknown digest algorithm {}",
+            other
+        ))),
+    }
+}
+
+/// Unsigned LEB128
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+/// Cursor over a binary proof, failing cleanly on truncated input
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], MerkleError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|end| *end <= self.bytes.len())
+            .ok_or_else(|| MerkleError::InvalidProofBytes("truncated".to_string()))?;
+        let slice = &self.bytes[self.pos..end];
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn byte(&mut self) -> Result<u8, MerkleError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn varint(&mut self) -> Result<u64, MerkleError> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(MerkleError::InvalidProofBytes(
+            "varint too long".to_string(),
+        ))
+    }
+
+    /// A varint length, bounded by the bytes left so garbage can't allocate
+    fn len(&mut self) -> Result<usize, MerkleError> {
+        let len = self.varint()?;
+        if len > (self.bytes.len() - self.pos) as u64 * 8 {
+            return Err(MerkleError::InvalidProofBytes("truncated".to_string()));
+        }
+        Ok(len as usize)
+    }
+}
+
+/// Current `ProofBundle` schema version
+pub const PROOF_BUNDLE_VERSION: u32 = 1;
+
+/// Self-contained proof that an evidence hash was anchored in a batch.
+///
+/// Carries everything an external verifier needs to confirm inclusion offline
+/// (leaf, siblings, root) and then look up the anchoring transaction on-chain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProofBundle {
+    /// Schema version, bumped on incompatible changes
+    pub version: u32,
+    /// Outbox job the proof belongs to
+    pub job_id: String,
+    /// Chain family the root was anchored on (e.g. "solana")
+    pub network: String,
+    /// Cluster within the network (e.g. "devnet")
+    pub chain: String,
+    /// Merkle root recorded for the batch
+    pub merkle_root: String,
+    /// How the batch's nodes were hashed, from the batch record (absent in
+    /// older bundles: legacy)
+    #[serde(default)]
+    pub construction_version: u8,
+    /// Inclusion proof for the job's evidence hash
+    pub proof: MerkleProof,
+    /// Transaction that anchored `merkle_root`
+    pub tx_ref: ChainTxRef,
+}
+
+/// Verify a proof bundle offline by recomputing the root from leaf + siblings,
+/// using the bundle's batch-level construction version.
+///
+/// Returns `Ok(false)` if the recomputed root differs from the embedded
+/// `merkle_root`; on-chain lookup of `tx_ref` is left to the caller.
+pub fn verify_proof_bundle(bundle: &ProofBundle) -> Result<bool, MerkleError> {
+    if bundle.version != PROOF_BUNDLE_VERSION {
+        return Err(MerkleError::UnsupportedBundleVersion(bundle.version));
+    }
+    bundle
+        .proof
+        .verify(&bundle.merkle_root, bundle.construction_version)
+}
+
+fn check_construction(construction_version: u8) -> Result<(), MerkleError> {
+    match construction_version {
+        MERKLE_CONSTRUCTION_LEGACY | MERKLE_CONSTRUCTION_DOMAIN_SEPARATED => Ok(()),
+        other => Err(MerkleError::UnsupportedConstruction(other)),
+    }
+}
+
+/// Decode a leaf or sibling hash, rejecting any that isn't `algo`'s output
+/// length. Without this a legacy-mode leaf of `0x01 || left` would hash to the
+/// same root as a domain-separated interior node.
+fn decode_node(algo: DigestAlgo, hash: &str) -> Result<Vec<u8>, MerkleError> {
+    let bytes = hex::decode(hash)?;
+    if bytes.len() != algo.output_len() {
+        return Err(MerkleError::InvalidNodeLength {
+            expected: algo.output_len(),
+            actual: bytes.len(),
+        });
+    }
+    Ok(bytes)
+}
+
+/// The bottom-level node for `leaf`: `H(0x00 || leaf)`, or the leaf itself
+/// in the legacy construction
+fn hash_leaf(algo: DigestAlgo, construction_version: u8, leaf: &[u8]) -> Vec<u8> {
+    match construction_version {
+        MERKLE_CONSTRUCTION_LEGACY => leaf.to_vec(),
+        _ => digest(algo, &[&[LEAF_PREFIX], leaf].concat()),
+    }
+}
+
+/// `H(0x01 || left || right)`, or `H(left || right)` in the legacy construction
+fn hash_pair(algo: DigestAlgo, construction_version: u8, left: &[u8], right: &[u8]) -> Vec<u8> {
+    match construction_version {
+        MERKLE_CONSTRUCTION_LEGACY => digest(algo, &[left, right].concat()),
+        _ => digest(algo, &[&[NODE_PREFIX], left, right].concat()),
+    }
+}
+
+/// Merkle tree for batch anchoring
+#[derive(Debug)]
+pub struct MerkleTree {
+    /// Leaf hashes as given
+    leaves: Vec<Vec<u8>>,
+    /// All levels of the tree (leaf nodes at 0, root at end)
+    levels: Vec<Vec<Vec<u8>>>,
+    /// Hash used for nodes
+    algo: DigestAlgo,
+    /// How nodes are hashed
+    construction_version: u8,
+}
+
+impl MerkleTree {
+    /// Build a SHA-256 Merkle tree from leaf hashes.
+    ///
+    /// Returns an error if any input hash is not valid 32-byte hex.
+    pub fn from_leaves(leaf_hashes: Vec<String>) -> Result<Self, MerkleError> {
+        Self::from_leaves_with_algo(DigestAlgo::Sha256, leaf_hashes)
+    }
+
+    /// Build a Merkle tree whose interior nodes are hashed with `algo`,
+    /// normally the algorithm that produced the leaves.
+    ///
+    /// Returns an error if any input hash is not valid hex of `algo`'s
+    /// output length.
+    pub fn from_leaves_with_algo(
+        algo: DigestAlgo,
+        leaf_hashes: Vec<String>,
+    ) -> Result<Self, MerkleError> {
+        Self::from_leaves_with_construction(algo, MERKLE_CONSTRUCTION_VERSION, leaf_hashes)
+    }
+
+    /// Build a Merkle tree with an explicit construction version, e.g.
+    /// [`MERKLE_CONSTRUCTION_LEGACY`] to reproduce a root anchored before
+    /// domain separation.
+    ///
+    /// Returns an error if any input hash is not valid hex of `algo`'s
+    /// output length or the construction version is unknown.
+    pub fn from_leaves_with_construction(
+        algo: DigestAlgo,
+        construction_version: u8,
+        leaf_hashes: Vec<String>,
+    ) -> Result<Self, MerkleError> {
+        check_construction(construction_version)?;
+        let leaves: Vec<Vec<u8>> = leaf_hashes
+            .iter()
+            .map(|leaf| decode_node(algo, leaf))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut current_level: Vec<Vec<u8>> = leaves
+            .iter()
+            .map(|leaf| hash_leaf(algo, construction_version, leaf))
+            .collect();
+        let mut levels = vec![current_level.clone()];
+
+        // Build tree bottom-up
+        while current_level.len() > 1 {
+            let mut next_level = Vec::new();
+
+            for chunk in current_level.chunks(2) {
+                // Odd number of nodes - duplicate the last one
+                let right = chunk.get(1).unwrap_or(&chunk[0]);
+                next_level.push(hash_pair(algo, construction_version, &chunk[0], right));
+            }
+
+            levels.push(next_level.clone());
+            current_level = next_level;
+        }
+
+        Ok(Self {
+            leaves,
+            levels,
+            algo,
+            construction_version,
+        })
+    }
+
+    /// Hash used for nodes
+    pub fn algo(&self) -> DigestAlgo {
+        self.algo
+    }
+
+    /// How nodes are hashed
+    pub fn construction_version(&self) -> u8 {
+        self.construction_version
+    }
+
+    /// Get the Merkle root hash
+    pub fn root(&self) -> String {
+        if let Some(top_level) = self.levels.last() {
+            if let Some(root) = top_level.first() {
+                return hex::encode(root);
+            }
+        }
+        String::new()
+    }
+
+    /// Generate a proof for a specific leaf index
+    pub fn proof(&self, index: usize) -> Option<MerkleProof> {
+        if index >= self.leaves.len() {
+            return None;
+        }
+
+        let mut siblings = Vec::new();
+        let mut current_index = index;
+
+        for level in &self.levels[..self.levels.len().saturating_sub(1)] {
+            let sibling_index = if current_index.is_multiple_of(2) {
+                current_index + 1
+            } else {
+                current_index - 1
+            };
+
+            if sibling_index < level.len() {
+                siblings.push(MerkleProofSibling {
+                    hash: hex::encode(&level[sibling_index]),
+                    is_left: current_index % 2 == 1,
+                });
+            } else {
+                // Odd number of nodes - sibling is self
+                siblings.push(MerkleProofSibling {
+                    hash: hex::encode(&level[current_index]),
+                    is_left: current_index % 2 == 1,
+                });
+            }
+
+            current_index /= 2;
+        }
+
+        Some(MerkleProof {
+            leaf_hash: hex::encode(&self.leaves[index]),
+            leaf_index: index,
+            siblings,
+            root: self.root(),
+            algo: self.algo,
+            construction_version: self.construction_version,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merkle_tree_single_leaf() {
+        let tree = MerkleTree::from_leaves(sha256_leaves(1)).unwrap();
+        assert!(!tree.root().is_empty());
+    }
+
+    #[test]
+    fn test_merkle_tree_multiple_leaves() {
+        let tree = MerkleTree::from_leaves(sha256_leaves(4)).unwrap();
+
+        // Verify each proof
+        for i in 0..4 {
+            let proof = tree.proof(i).unwrap();
+            assert!(proof
+                .verify(&tree.root(), tree.construction_version())
+                .unwrap());
+        }
+    }
+
+    #[test]
+    fn test_merkle_proof_verification() {
+        let tree = MerkleTree::from_leaves(sha256_leaves(2)).unwrap();
+        let version = tree.construction_version();
+
+        let proof0 = tree.proof(0).unwrap();
+        let proof1 = tree.proof(1).unwrap();
+
+        assert!(proof0.verify(&tree.root(), version).unwrap());
+        assert!(proof1.verify(&tree.root(), version).unwrap());
+
+        // Wrong root should fail (but return Ok(false), not an error for valid hex)
+        assert!(!proof0
+            .verify(&tree.root().replace("a", "b"), version)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_blake3_batch_builds_and_verifies() {
+        use crate::hash::digest_hex;
+
+        let leaves: Vec<String> = ["a", "b", "c", "d", "e"]
+            .iter()
+            .map(|item| digest_hex(DigestAlgo::Blake3, item.as_bytes()))
+            .collect();
+        let tree = MerkleTree::from_leaves_with_algo(DigestAlgo::Blake3, leaves.clone()).unwrap();
+
+        // Siblings are hashed with BLAKE3, so the root differs from SHA-256's
+        let sha_root = MerkleTree::from_leaves(leaves).unwrap().root();
+        assert_ne!(tree.root(), sha_root);
+
+        for i in 0..5 {
+            let proof = tree.proof(i).unwrap();
+            assert_eq!(proof.algo, DigestAlgo::Blake3);
+            assert!(proof
+                .verify(&tree.root(), MERKLE_CONSTRUCTION_VERSION)
+                .unwrap());
+
+            // The recorded algorithm survives serialization
+            let json = serde_json::to_string(&proof).unwrap();
+            assert!(json.contains(r#""algo":"blake3""#));
+            let parsed: MerkleProof = serde_json::from_str(&json).unwrap();
+            assert!(parsed
+                .verify(&tree.root(), MERKLE_CONSTRUCTION_VERSION)
+                .unwrap());
+        }
+    }
+
+    #[test]
+    fn test_proof_without_algo_defaults_to_sha256() {
+        let tree = MerkleTree::from_leaves(sha256_leaves(2)).unwrap();
+        let mut json = serde_json::to_value(tree.proof(1).unwrap()).unwrap();
+        json.as_object_mut().unwrap().remove("algo");
+
+        let proof: MerkleProof = serde_json::from_value(json).unwrap();
+        assert_eq!(proof.algo, DigestAlgo::Sha256);
+        assert!(proof
+            .verify(&tree.root(), MERKLE_CONSTRUCTION_VERSION)
+            .unwrap());
+    }
+
+    fn sha256_leaves(count: usize) -> Vec<String> {
+        use crate::hash::digest_hex;
+
+        (0..count)
+            .map(|i| digest_hex(DigestAlgo::Sha256, format!("evidence-{}", i).as_bytes()))
+            .collect()
+    }
+
+    fn assert_same_proof(decoded: &MerkleProof, proof: &MerkleProof) {
+        assert_eq!(decoded.leaf_hash, proof.leaf_hash);
+        assert_eq!(decoded.leaf_index, proof.leaf_index);
+        assert_eq!(decoded.root, proof.root);
+        assert_eq!(decoded.algo, proof.algo);
+        assert_eq!(decoded.construction_version, proof.construction_version);
+        assert_eq!(decoded.siblings.len(), proof.siblings.len());
+        for (a, b) in decoded.siblings.iter().zip(&proof.siblings) {
+            assert_eq!(a.hash, b.hash);
+            assert_eq!(a.is_left, b.is_left);
+        }
+    }
+
+    #[test]
+    fn test_proof_bytes_roundtrip_single_leaf() {
+        let tree = MerkleTree::from_leaves(sha256_leaves(1)).unwrap();
+        let proof = tree.proof(0).unwrap();
+
+        let bytes = proof.to_bytes().unwrap();
+        assert_eq!(bytes[0], PROOF_BYTES_VERSION);
+        let decoded = MerkleProof::from_bytes(&bytes).unwrap();
+        assert!(decoded.siblings.is_empty());
+        assert_same_proof(&decoded, &proof);
+    }
+
+    #[test]
+    fn test_proof_bytes_roundtrip_multi_level() {
+        // Odd width, so some proofs pair a node with itself
+        let tree = MerkleTree::from_leaves(sha256_leaves(13)).unwrap();
+        for i in 0..13 {
+            let proof = tree.proof(i).unwrap();
+            let decoded = MerkleProof::from_bytes(&proof.to_bytes().unwrap()).unwrap();
+            assert_same_proof(&decoded, &proof);
+        }
+
+        // Non-default algorithms survive too
+        let leaves = ["a", "b", "c"]
+            .iter()
+            .map(|item| crate::hash::digest_hex(DigestAlgo::Blake3, item.as_bytes()))
+            .collect();
+        let tree = MerkleTree::from_leaves_with_algo(DigestAlgo::Blake3, leaves).unwrap();
+        let proof = tree.proof(2).unwrap();
+        let decoded = MerkleProof::from_bytes(&proof.to_bytes().unwrap()).unwrap();
+        assert_same_proof(&decoded, &proof);
+    }
+
+    #[test]
+    fn test_decoded_proof_verifies_against_root() {
+        let tree = MerkleTree::from_leaves(sha256_leaves(1000)).unwrap();
+        let proof = tree.proof(777).unwrap();
+        let bytes = proof.to_bytes().unwrap();
+
+        // 10 levels: well under half the JSON
+        let json = serde_json::to_vec(&proof).unwrap();
+        assert!(
+            bytes.len() * 2 < json.len(),
+            "{} vs {}",
+            bytes.len(),
+            json.len()
+        );
+
+        let decoded = MerkleProof::from_bytes(&bytes).unwrap();
+        assert!(decoded
+            .verify(&tree.root(), MERKLE_CONSTRUCTION_VERSION)
+            .unwrap());
+
+        // A flipped bit in a sibling no longer reaches the root
+        let mut tampered = bytes.clone();
+        let last = tampered.len() - 1;
+        tampered[last] ^= 1;
+        let decoded = MerkleProof::from_bytes(&tampered).unwrap();
+        assert!(!decoded
+            .verify(&tree.root(), MERKLE_CONSTRUCTION_VERSION)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_proof_bytes_rejects_malformed_input() {
+        let tree = MerkleTree::from_leaves(sha256_leaves(4)).unwrap();
+        let bytes = tree.proof(1).unwrap().to_bytes().unwrap();
+
+        assert!(matches!(
+            MerkleProof::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(MerkleError::InvalidProofBytes(_))
+        ));
+        assert!(matches!(
+            MerkleProof::from_bytes(&[bytes.as_slice(), &[0]].concat()),
+            Err(MerkleError::InvalidProofBytes(_))
+        ));
+        let mut future = bytes;
+        future[0] = PROOF_BYTES_VERSION + 1;
+        assert!(matches!(
+            MerkleProof::from_bytes(&future),
+            Err(MerkleError::UnsupportedProofBytesVersion(_))
+        ));
+        assert!(MerkleProof::from_bytes(&[]).is_err());
+    }
+
+    #[test]
+    fn test_domain_separation_changes_roots() {
+        let leaves = sha256_leaves(5);
+        let tree = MerkleTree::from_leaves(leaves.clone()).unwrap();
+        let legacy = MerkleTree::from_leaves_with_construction(
+            DigestAlgo::Sha256,
+            MERKLE_CONSTRUCTION_LEGACY,
+            leaves,
+        )
+        .unwrap();
+
+        assert_eq!(tree.construction_version(), MERKLE_CONSTRUCTION_VERSION);
+        assert_ne!(tree.root(), legacy.root());
+
+        // Each scheme verifies its own proofs, not the other's root
+        for i in 0..5 {
+            let proof = tree.proof(i).unwrap();
+            assert_eq!(
+                proof.construction_version,
+                MERKLE_CONSTRUCTION_DOMAIN_SEPARATED
+            );
+            assert!(proof
+                .verify(&tree.root(), MERKLE_CONSTRUCTION_DOMAIN_SEPARATED)
+                .unwrap());
+            assert!(!proof
+                .verify(&legacy.root(), MERKLE_CONSTRUCTION_DOMAIN_SEPARATED)
+                .unwrap());
+
+            let legacy_proof = legacy.proof(i).unwrap();
+            assert!(legacy_proof
+                .verify(&legacy.root(), MERKLE_CONSTRUCTION_LEGACY)
+                .unwrap());
+            assert!(!legacy_proof
+                .verify(&tree.root(), MERKLE_CONSTRUCTION_LEGACY)
+                .unwrap());
+        }
+    }
+
+    #[test]
+    fn test_bundle_without_construction_version_verifies_as_legacy() {
+        let legacy = MerkleTree::from_leaves_with_construction(
+            DigestAlgo::Sha256,
+            MERKLE_CONSTRUCTION_LEGACY,
+            sha256_leaves(3),
+        )
+        .unwrap();
+        let mut json = serde_json::to_value(bundle_for(&legacy, 2)).unwrap();
+        json.as_object_mut().unwrap().remove("construction_version");
+        json["proof"]
+            .as_object_mut()
+            .unwrap()
+            .remove("construction_version");
+
+        let bundle: ProofBundle = serde_json::from_value(json).unwrap();
+        assert_eq!(bundle.construction_version, MERKLE_CONSTRUCTION_LEGACY);
+        assert_eq!(
+            bundle.proof.construction_version,
+            MERKLE_CONSTRUCTION_LEGACY
+        );
+        assert!(verify_proof_bundle(&bundle).unwrap());
+
+        assert!(matches!(
+            bundle.proof.verify(&legacy.root(), 9),
+            Err(MerkleError::UnsupportedConstruction(9))
+        ));
+    }
+
+    /// Present the interior node over leaves 0 and 1 as a leaf, proven with
+    /// the siblings above it
+    fn internal_node_as_leaf(tree: &MerkleTree) -> MerkleProof {
+        let genuine = tree.proof(0).unwrap();
+        MerkleProof {
+            leaf_hash: hex::encode(&tree.levels[1][0]),
+            leaf_index: 0,
+            siblings: genuine.siblings[1..].to_vec(),
+            root: tree.root(),
+            algo: tree.algo,
+            construction_version: tree.construction_version,
+        }
+    }
+
+    #[test]
+    fn test_internal_node_cannot_pass_as_leaf() {
+        let leaves = sha256_leaves(4);
+
+        // Without domain separation the forged proof is accepted
+        let legacy = MerkleTree::from_leaves_with_construction(
+            DigestAlgo::Sha256,
+            MERKLE_CONSTRUCTION_LEGACY,
+            leaves.clone(),
+        )
+        .unwrap();
+        assert!(internal_node_as_leaf(&legacy)
+            .verify(&legacy.root(), MERKLE_CONSTRUCTION_LEGACY)
+            .unwrap());
+
+        let tree = MerkleTree::from_leaves(leaves).unwrap();
+        let forged = internal_node_as_leaf(&tree);
+        assert!(!forged
+            .verify(&tree.root(), MERKLE_CONSTRUCTION_DOMAIN_SEPARATED)
+            .unwrap());
+
+        // The proof's own construction version is ignored
+        let mut downgraded = forged;
+        downgraded.construction_version = MERKLE_CONSTRUCTION_LEGACY;
+        assert!(!downgraded
+            .verify(&tree.root(), MERKLE_CONSTRUCTION_DOMAIN_SEPARATED)
+            .unwrap());
+    }
+
+    #[test]
+    fn test_prefixed_leaf_cannot_downgrade_to_legacy() {
+        let tree = MerkleTree::from_leaves(sha256_leaves(2)).unwrap();
+        let (left, right) = (&tree.levels[0][0], &tree.levels[0][1]);
+
+        // In the legacy scheme H(0x01 || left || right) is the root of the
+        // 33-byte leaf 0x01 || left with sibling right
+        let forged = MerkleProof {
+            leaf_hash: hex::encode([&[NODE_PREFIX], left.as_slice()].concat()),
+            leaf_index: 0,
+            siblings: vec![MerkleProofSibling {
+                hash: hex::encode(right),
+                is_left: false,
+            }],
+            root: tree.root(),
+            algo: DigestAlgo::Sha256,
+            construction_version: MERKLE_CONSTRUCTION_LEGACY,
+        };
+        assert!(matches!(
+            forged.verify(&tree.root(), MERKLE_CONSTRUCTION_LEGACY),
+            Err(MerkleError::InvalidNodeLength {
+                expected: 32,
+                actual: 33
+            })
+        ));
+
+        // Nor can a bundle claim the legacy scheme for that root
+        let mut bundle = bundle_for(&tree, 0);
+        bundle.construction_version = MERKLE_CONSTRUCTION_LEGACY;
+        bundle.proof = forged;
+        assert!(verify_proof_bundle(&bundle).is_err());
+    }
+
+    #[test]
+    fn test_nodes_must_match_algo_output_length() {
+        assert!(matches!(
+            MerkleTree::from_leaves(vec!["aa".to_string()]),
+            Err(MerkleError::InvalidNodeLength {
+                expected: 32,
+                actual: 1
+            })
+        ));
+
+        let tree = MerkleTree::from_leaves(sha256_leaves(2)).unwrap();
+        let mut proof = tree.proof(0).unwrap();
+        proof.siblings[0].hash.push_str("00");
+        assert!(matches!(
+            proof.verify(&tree.root(), MERKLE_CONSTRUCTION_VERSION),
+            Err(MerkleError::InvalidNodeLength {
+                expected: 32,
+                actual: 33
+            })
+        ));
+    }
+
+    #[test]
+    fn test_proof_bytes_carry_construction_version() {
+        let legacy = MerkleTree::from_leaves_with_construction(
+            DigestAlgo::Sha256,
+            MERKLE_CONSTRUCTION_LEGACY,
+            sha256_leaves(6),
+        )
+        .unwrap();
+        let proof = legacy.proof(4).unwrap();
+        let bytes = proof.to_bytes().unwrap();
+        let decoded = MerkleProof::from_bytes(&bytes).unwrap();
+        assert_same_proof(&decoded, &proof);
+
+        // Layout version 1 had no construction byte and only legacy trees
+        let v1 = [&[1u8, bytes[1]][..], &bytes[3..]].concat();
+        let decoded = MerkleProof::from_bytes(&v1).unwrap();
+        assert_same_proof(&decoded, &proof);
+    }
+
+    #[test]
+    fn test_merkle_tree_invalid_hex() {
+        // Invalid hex should return an error
+        let result = MerkleTree::from_leaves(vec!["not_valid_hex!".to_string()]);
+        assert!(result.is_err());
+    }
+
+    fn bundle_for(tree: &MerkleTree, index: usize) -> ProofBundle {
+        ProofBundle {
+            version: PROOF_BUNDLE_VERSION,
+            job_id: format!("job-{}", index),
+            network: "solana".to_string(),
+            chain: "devnet".to_string(),
+            merkle_root: tree.root(),
+            construction_version: tree.construction_version(),
+            proof: tree.proof(index).unwrap(),
+            tx_ref: ChainTxRef {
+                network: "solana".to_string(),
+                chain: "devnet".to_string(),
+                tx_id: "sig".to_string(),
+                confirmed: true,
+                timestamp: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_verify_proof_bundle_roundtrip() {
+        let tree = MerkleTree::from_leaves(sha256_leaves(3)).unwrap();
+
+        let bundle = bundle_for(&tree, 2);
+        let json = serde_json::to_string(&bundle).unwrap();
+        let parsed: ProofBundle = serde_json::from_str(&json).unwrap();
+        assert!(verify_proof_bundle(&parsed).unwrap());
+
+        // Tampered leaf no longer reaches the embedded root
+        let mut tampered = parsed.clone();
+        tampered.proof.leaf_hash = sha256_leaves(4)[3].clone();
+        assert!(!verify_proof_bundle(&tampered).unwrap());
+
+        let mut future = parsed;
+        future.version = PROOF_BUNDLE_VERSION + 1;
+        assert!(matches!(
+            verify_proof_bundle(&future),
+            Err(MerkleError::UnsupportedBundleVersion(_))
+        ));
+    }
+
+    #[test]
+    fn test_merkle_proof_verify_invalid_hex() {
+        let tree = MerkleTree::from_leaves(sha256_leaves(2)).unwrap();
+        let proof = tree.proof(0).unwrap();
+
+        // Invalid hex in expected_root should return an error
+        // Note: The expected_root is compared as hex string, so this tests
+        // that invalid sibling hashes would be caught
+        let mut bad_proof = proof.clone();
+        bad_proof.siblings = vec![MerkleProofSibling {
+            hash: "not_valid_hex!".to_string(),
+            is_left: false,
+        }];
+        assert!(bad_proof
+            .verify(&tree.root(), MERKLE_CONSTRUCTION_VERSION)
+            .is_err());
+    }
+}
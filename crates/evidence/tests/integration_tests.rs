@@ -393,3 +393,39 @@ fn test_anchor_error_serialization() {
     let error_string = format!("{}", provider_error);
     assert!(error_string.contains("test provider error"));
 }
+
+/// Reordered metadata keys must not change what gets hashed
+#[test]
+fn test_reordered_metadata_has_identical_canonical_form_and_fingerprint() {
+    let created_at = Utc::now();
+    let record = |metadata_json: &str| EvidenceRecord {
+        id: "canonical-test".to_string(),
+        created_at,
+        digest: EvidenceDigest {
+            algo: DigestAlgo::Sha256,
+            hex: hash::sha256_hex(b"payload"),
+        },
+        payload_mime: Some("application/json".to_string()),
+        metadata: serde_json::from_str(metadata_json).unwrap(),
+    };
+
+    let a =
+        record(r#"{"sensor": "rf-7", "reading": {"dbm": -40.0, "band": "2.4GHz"}, "count": 3}"#);
+    let b =
+        record(r#"{"count": 3.0, "reading": {"band": "2.4GHz", "dbm": -40}, "sensor": "rf-7"}"#);
+
+    assert_eq!(a.canonical_metadata(), b.canonical_metadata());
+    assert_eq!(
+        a.canonical_metadata(),
+        r#"{"count":3,"reading":{"band":"2.4GHz","dbm":-40},"sensor":"rf-7"}"#
+    );
+    assert_eq!(
+        hash::sha256_canonical_json(&a.metadata),
+        hash::sha256_canonical_json(&b.metadata)
+    );
+    assert_eq!(a.fingerprint(), b.fingerprint());
+
+    // Any change to the metadata itself changes the fingerprint
+    let c = record(r#"{"count": 4, "reading": {"band": "2.4GHz", "dbm": -40}, "sensor": "rf-7"}"#);
+    assert_ne!(a.fingerprint(), c.fingerprint());
+}
@@ -0,0 +1,137 @@
+//! Exact USDC amounts
+//!
+//! Prices and payments are micropayments ($0.001 is a viable price), so they
+//! never pass through floats. [`UsdcAmount`] holds a decimal at USDC's six
+//! places, keeps the scale it was written with (`"1.00"` stays `"1.00"`) and
+//! serializes as a string.
+
+use crate::types::{format_units, PaymentToken};
+use crate::X402Error;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// Non-negative USDC amount with at most six decimal places
+///
+/// Compares by value, so `"0.10"` equals `"0.1"`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UsdcAmount(Decimal);
+
+impl UsdcAmount {
+    pub const ZERO: Self = Self(Decimal::ZERO);
+
+    /// Amount of `micro` millionths of a USDC
+    pub fn from_micro(micro: u128) -> Self {
+        format_units(micro, PaymentToken::Usdc.decimals())
+            .parse()
+            .expect("formatted micro-USDC is a valid amount")
+    }
+
+    /// Amount in millionths of a USDC
+    pub fn to_micro(&self) -> u128 {
+        let scaled = self.0.mantissa() as u128;
+        scaled * 10u128.pow(PaymentToken::Usdc.decimals() - self.0.scale())
+    }
+
+    /// `self - other`, or `None` if that would be negative
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        (self >= other).then(|| Self(self.0 - other.0))
+    }
+}
+
+impl FromStr for UsdcAmount {
+    type Err = X402Error;
+
+    /// Parse a plain decimal such as `"0.001"`; signs, exponents and more
+    /// than six decimal places are rejected
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || X402Error::InvalidAmount(format!("{:?} is not a USDC amount", s));
+        let trimmed = s.trim();
+        if trimmed.is_empty() || !trimmed.chars().all(|c| c.is_ascii_digit() || c == '.') {
+            return Err(invalid());
+        }
+        let value = Decimal::from_str_exact(trimmed).map_err(|_| invalid())?;
+        if value.scale() > PaymentToken::Usdc.decimals() {
+            return Err(X402Error::InvalidAmount(format!(
+                "{} has more than {} decimal places",
+                trimmed,
+                PaymentToken::Usdc.decimals()
+            )));
+        }
+        Ok(Self(value))
+    }
+}
+
+impl fmt::Display for UsdcAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl PartialEq<str> for UsdcAmount {
+    fn eq(&self, other: &str) -> bool {
+        other.parse::<Self>().is_ok_and(|other| *self == other)
+    }
+}
+
+impl PartialEq<&str> for UsdcAmount {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl Serialize for UsdcAmount {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for UsdcAmount {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usdc(s: &str) -> UsdcAmount {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_round_trip() {
+        assert_eq!(usdc("0.001").to_string(), "0.001");
+        assert_eq!(usdc("1.00").to_string(), "1.00");
+        assert_eq!(usdc(" 0.05 ").to_string(), "0.05");
+        assert_eq!(usdc("0.001").to_micro(), 1_000);
+        assert_eq!(usdc("3").to_micro(), 3_000_000);
+        assert_eq!(UsdcAmount::from_micro(70_000).to_string(), "0.07");
+        assert_eq!(UsdcAmount::from_micro(1), usdc("0.000001"));
+
+        let json = serde_json::to_string(&usdc("0.001")).unwrap();
+        assert_eq!(json, "\"0.001\"");
+        assert_eq!(serde_json::from_str::<UsdcAmount>(&json).unwrap(), "0.001");
+    }
+
+    #[test]
+    fn test_rejects_invalid_amounts() {
+        for bad in ["", "abc", "-0.01", "+1", "1e-3", "0.0000001", "1.2.3", "."] {
+            assert!(bad.parse::<UsdcAmount>().is_err(), "{:?} parsed", bad);
+        }
+        assert!(serde_json::from_str::<UsdcAmount>("\"0.0000001\"").is_err());
+    }
+
+    #[test]
+    fn test_compares_by_value() {
+        assert!(usdc("0.05") >= usdc("0.050"));
+        assert!(usdc("0.051") >= usdc("0.05"));
+        assert!(usdc("0.049999") < usdc("0.05"));
+        assert_eq!(usdc("0.10"), "0.1");
+        assert_eq!(usdc("0.07").checked_sub(&usdc("0.05")), Some(usdc("0.02")));
+        assert_eq!(usdc("0.01").checked_sub(&usdc("0.05")), None);
+    }
+}
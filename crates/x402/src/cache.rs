@@ -0,0 +1,208 @@
+//! Bounded, TTL'd cache of verified payments
+//!
+//! Lets [`crate::X402Facilitator`] answer repeated verifications of the same
+//! payment without another facilitator round-trip. The cache only saves work:
+//! one-time redemption is still enforced by the [`crate::ReplayStore`].
+
+use crate::PaymentVerification;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// What a cached verification was checked against. A lookup only hits when
+/// the new request asks the same question of the same transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct VerificationKey {
+    pub signature: String,
+    pub token: String,
+    pub memo: String,
+    pub min_amount: String,
+}
+
+#[derive(Debug)]
+struct Entry {
+    key: VerificationKey,
+    verification: PaymentVerification,
+    stored_at: Instant,
+    last_used: u64,
+}
+
+#[derive(Debug, Default)]
+struct Entries {
+    by_signature: HashMap<String, Entry>,
+    /// Monotonic use counter; the entry with the lowest `last_used` is evicted
+    tick: u64,
+}
+
+/// Least-recently-used cache of successful payment verifications
+#[derive(Debug)]
+pub(crate) struct VerificationCache {
+    entries: Mutex<Entries>,
+    capacity: usize,
+    ttl: Duration,
+}
+
+impl VerificationCache {
+    /// A cache of up to `capacity` entries, each served for `ttl`. A zero
+    /// capacity or TTL disables caching.
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        Self {
+            entries: Mutex::default(),
+            capacity,
+            ttl,
+        }
+    }
+
+    fn enabled(&self) -> bool {
+        self.capacity > 0 && !self.ttl.is_zero()
+    }
+
+    /// The cached verification for `key`, if stored within the TTL
+    pub fn get(&self, key: &VerificationKey) -> Option<PaymentVerification> {
+        if !self.enabled() {
+            return None;
+        }
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.tick += 1;
+        let tick = entries.tick;
+        let entry = entries.by_signature.get_mut(&key.signature)?;
+        if entry.stored_at.elapsed() >= self.ttl {
+            entries.by_signature.remove(&key.signature);
+            return None;
+        }
+        if entry.key != *key {
+            return None;
+        }
+        entry.last_used = tick;
+        Some(entry.verification.clone())
+    }
+
+    /// Cache a successful verification; failed ones are never stored, so a
+    /// payment that confirms later is re-checked
+    pub fn insert(&self, key: VerificationKey, verification: &PaymentVerification) {
+        if !self.enabled() || !verification.valid {
+            return;
+        }
+        let mut entries = self.entries.lock().unwrap_or_else(|e| e.into_inner());
+        entries.tick += 1;
+        let tick = entries.tick;
+
+        if !entries.by_signature.contains_key(&key.signature)
+            && entries.by_signature.len() >= self.capacity
+        {
+            let ttl = self.ttl;
+            entries
+                .by_signature
+                .retain(|_, entry| entry.stored_at.elapsed() < ttl);
+            if entries.by_signature.len() >= self.capacity {
+                let oldest = entries
+                    .by_signature
+                    .iter()
+                    .min_by_key(|(_, entry)| entry.last_used)
+                    .map(|(signature, _)| signature.clone());
+                if let Some(oldest) = oldest {
+                    entries.by_signature.remove(&oldest);
+                }
+            }
+        }
+
+        entries.by_signature.insert(
+            key.signature.clone(),
+            Entry {
+                key,
+                verification: verification.clone(),
+                stored_at: Instant::now(),
+                last_used: tick,
+            },
+        );
+    }
+
+    /// Number of entries currently held, expired or not
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.entries
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .by_signature
+            .len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(signature: &str) -> VerificationKey {
+        VerificationKey {
+            signature: signature.to_string(),
+            token: "USDC".to_string(),
+            memo: "evidence:evt-001".to_string(),
+            min_amount: "0.05".to_string(),
+        }
+    }
+
+    fn verified(signature: &str, valid: bool) -> PaymentVerification {
+        PaymentVerification {
+            valid,
+            tx_signature: signature.to_string(),
+            amount_usdc: "0.05".parse().unwrap(),
+            recipient: None,
+            overpaid_usdc: None,
+            underpaid: None,
+            block: Some(1),
+            confirmed_at: None,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_hit_requires_matching_request() {
+        let cache = VerificationCache::new(8, Duration::from_secs(60));
+        cache.insert(key("sig-1"), &verified("sig-1", true));
+
+        assert!(cache.get(&key("sig-1")).is_some());
+        let other_memo = VerificationKey {
+            memo: "evidence:evt-002".to_string(),
+            ..key("sig-1")
+        };
+        assert!(cache.get(&other_memo).is_none());
+        assert!(cache.get(&key("sig-2")).is_none());
+    }
+
+    #[test]
+    fn test_failed_verifications_are_not_cached() {
+        let cache = VerificationCache::new(8, Duration::from_secs(60));
+        cache.insert(key("sig-1"), &verified("sig-1", false));
+        assert!(cache.get(&key("sig-1")).is_none());
+    }
+
+    #[test]
+    fn test_entries_expire_after_ttl() {
+        let cache = VerificationCache::new(8, Duration::from_millis(20));
+        cache.insert(key("sig-1"), &verified("sig-1", true));
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(cache.get(&key("sig-1")).is_none());
+    }
+
+    #[test]
+    fn test_least_recently_used_entry_is_evicted() {
+        let cache = VerificationCache::new(2, Duration::from_secs(60));
+        cache.insert(key("sig-1"), &verified("sig-1", true));
+        cache.insert(key("sig-2"), &verified("sig-2", true));
+        // Touch sig-1 so sig-2 is the least recently used
+        assert!(cache.get(&key("sig-1")).is_some());
+
+        cache.insert(key("sig-3"), &verified("sig-3", true));
+        assert_eq!(cache.len(), 2);
+        assert!(cache.get(&key("sig-1")).is_some());
+        assert!(cache.get(&key("sig-2")).is_none());
+        assert!(cache.get(&key("sig-3")).is_some());
+    }
+
+    #[test]
+    fn test_zero_capacity_disables_cache() {
+        let cache = VerificationCache::new(0, Duration::from_secs(60));
+        cache.insert(key("sig-1"), &verified("sig-1", true));
+        assert!(cache.get(&key("sig-1")).is_none());
+    }
+}
@@ -1,7 +1,105 @@
 //! Configuration for x402 payment integration
 
+use crate::types::{format_units, parse_units, BulkPricing, PaymentToken, PriceTier};
+use crate::UsdcAmount;
 use serde::{Deserialize, Serialize};
 
+/// Default lifetime of a payment quote (5 minutes)
+pub const DEFAULT_QUOTE_TTL_SECS: u64 = 300;
+
+fn default_quote_ttl_secs() -> u64 {
+    DEFAULT_QUOTE_TTL_SECS
+}
+
+/// Default number of verified payments kept in the facilitator's cache
+pub const DEFAULT_VERIFY_CACHE_SIZE: usize = 1024;
+
+/// Default seconds a verified payment is served from the cache
+pub const DEFAULT_VERIFY_CACHE_TTL_SECS: u64 = 60;
+
+fn default_verify_cache_size() -> usize {
+    DEFAULT_VERIFY_CACHE_SIZE
+}
+
+fn default_verify_cache_ttl_secs() -> u64 {
+    DEFAULT_VERIFY_CACHE_TTL_SECS
+}
+
+/// Default per-request timeout for facilitator calls
+pub const DEFAULT_FACILITATOR_TIMEOUT_SECS: u64 = 10;
+
+fn default_facilitator_timeout_secs() -> u64 {
+    DEFAULT_FACILITATOR_TIMEOUT_SECS
+}
+
+/// Default extra attempts after a facilitator 5xx or connection failure
+pub const DEFAULT_FACILITATOR_RETRIES: u32 = 2;
+
+fn default_facilitator_retries() -> u32 {
+    DEFAULT_FACILITATOR_RETRIES
+}
+
+/// Default seconds a token price from the price oracle is reused
+pub const DEFAULT_PRICE_ORACLE_TTL_SECS: u64 = 60;
+
+fn default_price_oracle_ttl_secs() -> u64 {
+    DEFAULT_PRICE_ORACLE_TTL_SECS
+}
+
+/// Requests per minute allowed on the x402 endpoints
+///
+/// Clients presenting a bearer token are limited per token (hashed) with the
+/// `api_key_*` quotas; everyone else is limited per IP.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimits {
+    /// Premium verifications per minute per IP
+    pub verify_per_min: u32,
+    /// Status checks per minute per IP
+    pub status_per_min: u32,
+    /// Premium verifications per minute per API key
+    pub api_key_verify_per_min: u32,
+    /// Status checks per minute per API key
+    pub api_key_status_per_min: u32,
+}
+
+impl RateLimits {
+    /// Defaults overridden by `X402_RATE_LIMIT_*_PER_MIN`; zero or unparseable
+    /// values keep the default
+    pub fn from_env() -> Self {
+        let defaults = Self::default();
+        let per_minute = |var: &str, default: u32| {
+            std::env::var(var)
+                .ok()
+                .and_then(|v| v.trim().parse::<u32>().ok())
+                .filter(|n| *n > 0)
+                .unwrap_or(default)
+        };
+        Self {
+            verify_per_min: per_minute("X402_RATE_LIMIT_VERIFY_PER_MIN", defaults.verify_per_min),
+            status_per_min: per_minute("X402_RATE_LIMIT_STATUS_PER_MIN", defaults.status_per_min),
+            api_key_verify_per_min: per_minute(
+                "X402_RATE_LIMIT_API_KEY_VERIFY_PER_MIN",
+                defaults.api_key_verify_per_min,
+            ),
+            api_key_status_per_min: per_minute(
+                "X402_RATE_LIMIT_API_KEY_STATUS_PER_MIN",
+                defaults.api_key_status_per_min,
+            ),
+        }
+    }
+}
+
+impl Default for RateLimits {
+    fn default() -> Self {
+        Self {
+            verify_per_min: 10,
+            status_per_min: 60,
+            api_key_verify_per_min: 60,
+            api_key_status_per_min: 300,
+        }
+    }
+}
+
 /// Configuration for x402 payment processing
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct X402Config {
@@ -22,6 +120,51 @@ pub struct X402Config {
 
     /// Minimum payment amount in USDC (prevents dust attacks)
     pub min_payment_usdc: String,
+
+    /// Base fee, per-item rate and volume discounts for the bulk tier
+    #[serde(default)]
+    pub bulk_pricing: BulkPricing,
+
+    /// Price of one SOL in USDC, used to convert prices for native SOL
+    /// payments. SOL is not accepted while this is unset.
+    #[serde(default)]
+    pub sol_price_usdc: Option<String>,
+
+    /// Simple price endpoint quoting live SOL prices (see
+    /// [`crate::HttpPriceOracle`]). When set, its price replaces
+    /// `sol_price_usdc`, which remains the fallback if the endpoint fails.
+    #[serde(default)]
+    pub price_oracle_url: Option<String>,
+
+    /// Seconds a fetched token price is reused
+    #[serde(default = "default_price_oracle_ttl_secs")]
+    pub price_oracle_ttl_secs: u64,
+
+    /// Seconds a payment quote stays valid; payments confirmed on chain after
+    /// the quote's `expires_at` are rejected
+    #[serde(default = "default_quote_ttl_secs")]
+    pub quote_ttl_secs: u64,
+
+    /// Maximum verified payments cached by the facilitator client (0 disables)
+    #[serde(default = "default_verify_cache_size")]
+    pub verify_cache_size: usize,
+
+    /// Seconds a verified payment is served from the cache (0 disables)
+    #[serde(default = "default_verify_cache_ttl_secs")]
+    pub verify_cache_ttl_secs: u64,
+
+    /// Seconds to wait for the facilitator before answering 504
+    #[serde(default = "default_facilitator_timeout_secs")]
+    pub facilitator_timeout_secs: u64,
+
+    /// Extra attempts after the facilitator answers 5xx or can't be reached
+    /// (timeouts are not retried)
+    #[serde(default = "default_facilitator_retries")]
+    pub facilitator_retries: u32,
+
+    /// Per-IP and per-API-key request quotas for the x402 endpoints
+    #[serde(default)]
+    pub rate_limits: RateLimits,
 }
 
 impl X402Config {
@@ -41,9 +184,220 @@ impl X402Config {
             network: std::env::var("SOLANA_NETWORK").unwrap_or_else(|_| "devnet".to_string()),
             min_payment_usdc: std::env::var("X402_MIN_PAYMENT")
                 .unwrap_or_else(|_| "0.001".to_string()),
+            bulk_pricing: match std::env::var("X402_BULK_PRICING") {
+                Ok(json) => serde_json::from_str(&json).map_err(|e| {
+                    crate::X402Error::ConfigError(format!("Invalid X402_BULK_PRICING: {}", e))
+                })?,
+                Err(_) => BulkPricing::default(),
+            },
+            sol_price_usdc: match std::env::var("X402_SOL_PRICE_USDC") {
+                Ok(price) => match parse_units(&price, PaymentToken::Usdc.decimals()) {
+                    Some(micro) if micro > 0 => Some(price.trim().to_string()),
+                    _ => {
+                        return Err(crate::X402Error::ConfigError(format!(
+                            "Invalid X402_SOL_PRICE_USDC: {}",
+                            price
+                        )))
+                    }
+                },
+                Err(_) => None,
+            },
+            price_oracle_url: std::env::var("X402_PRICE_ORACLE_URL")
+                .ok()
+                .filter(|url| !url.trim().is_empty()),
+            price_oracle_ttl_secs: match std::env::var("X402_PRICE_ORACLE_TTL_SECS") {
+                Ok(ttl) => ttl.trim().parse().map_err(|_| {
+                    crate::X402Error::ConfigError(format!(
+                        "Invalid X402_PRICE_ORACLE_TTL_SECS: {}",
+                        ttl
+                    ))
+                })?,
+                Err(_) => DEFAULT_PRICE_ORACLE_TTL_SECS,
+            },
+            quote_ttl_secs: match std::env::var("X402_QUOTE_TTL_SECS") {
+                Ok(ttl) => ttl
+                    .trim()
+                    .parse()
+                    .ok()
+                    .filter(|ttl| *ttl > 0)
+                    .ok_or_else(|| {
+                        crate::X402Error::ConfigError(format!(
+                            "Invalid X402_QUOTE_TTL_SECS: {}",
+                            ttl
+                        ))
+                    })?,
+                Err(_) => DEFAULT_QUOTE_TTL_SECS,
+            },
+            verify_cache_size: match std::env::var("X402_VERIFY_CACHE_SIZE") {
+                Ok(size) => size.trim().parse().map_err(|_| {
+                    crate::X402Error::ConfigError(format!(
+                        "Invalid X402_VERIFY_CACHE_SIZE: {}",
+                        size
+                    ))
+                })?,
+                Err(_) => DEFAULT_VERIFY_CACHE_SIZE,
+            },
+            verify_cache_ttl_secs: match std::env::var("X402_VERIFY_CACHE_TTL_SECS") {
+                Ok(ttl) => ttl.trim().parse().map_err(|_| {
+                    crate::X402Error::ConfigError(format!(
+                        "Invalid X402_VERIFY_CACHE_TTL_SECS: {}",
+                        ttl
+                    ))
+                })?,
+                Err(_) => DEFAULT_VERIFY_CACHE_TTL_SECS,
+            },
+            facilitator_timeout_secs: match std::env::var("X402_FACILITATOR_TIMEOUT_SECS") {
+                Ok(secs) => secs
+                    .trim()
+                    .parse()
+                    .ok()
+                    .filter(|secs| *secs > 0)
+                    .ok_or_else(|| {
+                        crate::X402Error::ConfigError(format!(
+                            "Invalid X402_FACILITATOR_TIMEOUT_SECS: {}",
+                            secs
+                        ))
+                    })?,
+                Err(_) => DEFAULT_FACILITATOR_TIMEOUT_SECS,
+            },
+            facilitator_retries: match std::env::var("X402_FACILITATOR_RETRIES") {
+                Ok(retries) => retries.trim().parse().map_err(|_| {
+                    crate::X402Error::ConfigError(format!(
+                        "Invalid X402_FACILITATOR_RETRIES: {}",
+                        retries
+                    ))
+                })?,
+                Err(_) => DEFAULT_FACILITATOR_RETRIES,
+            },
+            rate_limits: RateLimits::from_env(),
         })
     }
 
+    /// Tokens this configuration accepts: SOL only with a configured price
+    pub fn supported_tokens(&self) -> Vec<PaymentToken> {
+        PaymentToken::ALL
+            .into_iter()
+            .filter(|token| *token != PaymentToken::Sol || self.sol_price_micro_usdc().is_some())
+            .collect()
+    }
+
+    /// Amount of `token` that pays a USDC price of `price_usdc`.
+    ///
+    /// USDC and USDT pay 1:1. SOL is converted at `sol_price_usdc` and rounded
+    /// up to the next lamport, so the payment never falls short.
+    pub fn required_amount(
+        &self,
+        token: PaymentToken,
+        price_usdc: &str,
+    ) -> Result<String, crate::X402Error> {
+        match token {
+            PaymentToken::Usdc | PaymentToken::Usdt => Ok(price_usdc.to_string()),
+            PaymentToken::Sol => {
+                let sol_price = self.sol_price_micro_usdc().ok_or_else(|| {
+                    crate::X402Error::UnsupportedToken(
+                        "SOL (no SOL price configured; set X402_SOL_PRICE_USDC)".to_string(),
+                    )
+                })?;
+                let micro_usdc = parse_units(price_usdc, PaymentToken::Usdc.decimals())
+                    .ok_or_else(|| {
+                        crate::X402Error::ConfigError(format!("Invalid price: {}", price_usdc))
+                    })?;
+                let lamports_per_sol = 10u128.pow(PaymentToken::Sol.decimals());
+                let lamports = (micro_usdc * lamports_per_sol).div_ceil(sol_price);
+                Ok(format_units(lamports, PaymentToken::Sol.decimals()))
+            }
+        }
+    }
+
+    /// USDC value of `amount` of `token`, or `None` if it cannot be priced.
+    ///
+    /// USDC and USDT amounts are taken as written.
+    pub fn to_usdc(&self, token: PaymentToken, amount: &str) -> Option<UsdcAmount> {
+        match token {
+            PaymentToken::Usdc | PaymentToken::Usdt => amount.parse().ok(),
+            PaymentToken::Sol => {
+                let lamports = parse_units(amount, PaymentToken::Sol.decimals())?;
+                let micro_usdc = self.micro_usdc_value(token, lamports, false)?;
+                Some(UsdcAmount::from_micro(micro_usdc))
+            }
+        }
+    }
+
+    /// Overpayment and shortfall, in USDC, of paying `paid` where `required`
+    /// was due (both in `token` units).
+    ///
+    /// Returns `(overpaid_usdc, underpaid)`; at most one is set, and neither
+    /// for an exact payment. An unparseable `paid` counts as nothing paid.
+    /// For SOL the overpayment is rounded down and the shortfall up, so a
+    /// shortfall is never reported as zero.
+    pub fn payment_difference(
+        &self,
+        token: PaymentToken,
+        required: &str,
+        paid: &str,
+    ) -> (Option<String>, Option<String>) {
+        if matches!(token, PaymentToken::Usdc | PaymentToken::Usdt) {
+            let required: UsdcAmount = required.parse().unwrap_or_default();
+            let paid: UsdcAmount = paid.parse().unwrap_or_default();
+            let usdc = |amount: UsdcAmount| UsdcAmount::from_micro(amount.to_micro()).to_string();
+            return if paid >= required {
+                let over = paid
+                    .checked_sub(&required)
+                    .filter(|d| *d > UsdcAmount::ZERO);
+                (over.map(usdc), None)
+            } else {
+                (None, required.checked_sub(&paid).map(usdc))
+            };
+        }
+        let required = parse_units(required, token.decimals()).unwrap_or(0);
+        let paid = parse_units(paid, token.decimals()).unwrap_or(0);
+        let usdc = |units, round_up| {
+            self.micro_usdc_value(token, units, round_up)
+                .map(|micro| format_units(micro, PaymentToken::Usdc.decimals()))
+        };
+        match paid.cmp(&required) {
+            std::cmp::Ordering::Greater => (usdc(paid - required, false), None),
+            std::cmp::Ordering::Less => (None, usdc(required - paid, true)),
+            std::cmp::Ordering::Equal => (None, None),
+        }
+    }
+
+    /// Value in micro-USDC of `units` of `token`
+    fn micro_usdc_value(&self, token: PaymentToken, units: u128, round_up: bool) -> Option<u128> {
+        match token {
+            PaymentToken::Usdc | PaymentToken::Usdt => Some(units),
+            PaymentToken::Sol => {
+                let value = units.checked_mul(self.sol_price_micro_usdc()?)?;
+                let lamports_per_sol = 10u128.pow(PaymentToken::Sol.decimals());
+                Some(if round_up {
+                    value.div_ceil(lamports_per_sol)
+                } else {
+                    value / lamports_per_sol
+                })
+            }
+        }
+    }
+
+    /// Configured SOL price in micro-USDC, if set and valid
+    fn sol_price_micro_usdc(&self) -> Option<u128> {
+        self.sol_price_usdc
+            .as_deref()
+            .and_then(|price| parse_units(price, PaymentToken::Usdc.decimals()))
+            .filter(|micro| *micro > 0)
+    }
+
+    /// Price in USDC for a request of `count` evidence items at `tier`.
+    ///
+    /// Only the bulk tier scales with `count`; other tiers are per request.
+    pub fn price_usdc(&self, tier: PriceTier, count: u32) -> UsdcAmount {
+        match tier {
+            PriceTier::Bulk => {
+                UsdcAmount::from_micro(u128::from(self.bulk_pricing.price_micro_usdc(count)))
+            }
+            _ => tier.price(),
+        }
+    }
+
     /// Create a devnet configuration for testing
     pub fn devnet(wallet_address: &str) -> Self {
         Self {
@@ -53,6 +407,16 @@ impl X402Config {
             enabled: true,
             network: "devnet".to_string(),
             min_payment_usdc: "0.001".to_string(),
+            bulk_pricing: BulkPricing::default(),
+            sol_price_usdc: None,
+            price_oracle_url: None,
+            price_oracle_ttl_secs: DEFAULT_PRICE_ORACLE_TTL_SECS,
+            quote_ttl_secs: DEFAULT_QUOTE_TTL_SECS,
+            verify_cache_size: DEFAULT_VERIFY_CACHE_SIZE,
+            verify_cache_ttl_secs: DEFAULT_VERIFY_CACHE_TTL_SECS,
+            facilitator_timeout_secs: DEFAULT_FACILITATOR_TIMEOUT_SECS,
+            facilitator_retries: DEFAULT_FACILITATOR_RETRIES,
+            rate_limits: RateLimits::default(),
         }
     }
 
@@ -65,6 +429,16 @@ impl X402Config {
             enabled: true,
             network: "mainnet-beta".to_string(),
             min_payment_usdc: "0.001".to_string(),
+            bulk_pricing: BulkPricing::default(),
+            sol_price_usdc: None,
+            price_oracle_url: None,
+            price_oracle_ttl_secs: DEFAULT_PRICE_ORACLE_TTL_SECS,
+            quote_ttl_secs: DEFAULT_QUOTE_TTL_SECS,
+            verify_cache_size: DEFAULT_VERIFY_CACHE_SIZE,
+            verify_cache_ttl_secs: DEFAULT_VERIFY_CACHE_TTL_SECS,
+            facilitator_timeout_secs: DEFAULT_FACILITATOR_TIMEOUT_SECS,
+            facilitator_retries: DEFAULT_FACILITATOR_RETRIES,
+            rate_limits: RateLimits::default(),
         }
     }
 }
@@ -78,6 +452,16 @@ impl Default for X402Config {
             enabled: false,
             network: "devnet".to_string(),
             min_payment_usdc: "0.001".to_string(),
+            bulk_pricing: BulkPricing::default(),
+            sol_price_usdc: None,
+            price_oracle_url: None,
+            price_oracle_ttl_secs: DEFAULT_PRICE_ORACLE_TTL_SECS,
+            quote_ttl_secs: DEFAULT_QUOTE_TTL_SECS,
+            verify_cache_size: DEFAULT_VERIFY_CACHE_SIZE,
+            verify_cache_ttl_secs: DEFAULT_VERIFY_CACHE_TTL_SECS,
+            facilitator_timeout_secs: DEFAULT_FACILITATOR_TIMEOUT_SECS,
+            facilitator_retries: DEFAULT_FACILITATOR_RETRIES,
+            rate_limits: RateLimits::default(),
         }
     }
 }
@@ -104,10 +488,95 @@ mod tests {
         assert!(config.solana_rpc_url.contains("mainnet"));
     }
 
+    #[test]
+    fn test_price_usdc_scales_only_bulk() {
+        let config = X402Config::devnet("PhxRvk789");
+        assert_eq!(config.price_usdc(PriceTier::Basic, 500), "0.01");
+        assert_eq!(config.price_usdc(PriceTier::Bulk, 1), "0.055");
+        assert_eq!(config.price_usdc(PriceTier::Bulk, 10), "0.10");
+    }
+
+    #[test]
+    fn test_sol_requires_configured_price() {
+        let mut config = X402Config::devnet("PhxRvk789");
+        assert!(!config.supported_tokens().contains(&PaymentToken::Sol));
+        assert!(matches!(
+            config.required_amount(PaymentToken::Sol, "0.01"),
+            Err(crate::X402Error::UnsupportedToken(_))
+        ));
+
+        config.sol_price_usdc = Some("150".to_string());
+        assert_eq!(config.supported_tokens(), PaymentToken::ALL.to_vec());
+    }
+
+    #[test]
+    fn test_required_amount_converts_sol() {
+        let config = X402Config {
+            sol_price_usdc: Some("150".to_string()),
+            ..X402Config::devnet("PhxRvk789")
+        };
+        assert_eq!(
+            config.required_amount(PaymentToken::Usdt, "0.05").unwrap(),
+            "0.05"
+        );
+        assert_eq!(
+            config.required_amount(PaymentToken::Sol, "0.03").unwrap(),
+            "0.0002"
+        );
+        // 0.01 / 150 SOL is not a whole lamport: round up
+        assert_eq!(
+            config.required_amount(PaymentToken::Sol, "0.01").unwrap(),
+            "0.000066667"
+        );
+        assert_eq!(config.to_usdc(PaymentToken::Sol, "0.0002").unwrap(), "0.03");
+    }
+
+    #[test]
+    fn test_payment_difference() {
+        let config = X402Config {
+            sol_price_usdc: Some("100".to_string()),
+            ..X402Config::devnet("PhxRvk789")
+        };
+        let usdc = PaymentToken::Usdc;
+        assert_eq!(
+            config.payment_difference(usdc, "0.05", "0.05"),
+            (None, None)
+        );
+        assert_eq!(
+            config.payment_difference(usdc, "0.05", "0.050"),
+            (None, None)
+        );
+        assert_eq!(
+            config.payment_difference(usdc, "0.05", "0.08"),
+            (Some("0.03".to_string()), None)
+        );
+        assert_eq!(
+            config.payment_difference(usdc, "0.05", "0.045"),
+            (None, Some("0.005".to_string()))
+        );
+        assert_eq!(
+            config.payment_difference(usdc, "0.05", "garbage"),
+            (None, Some("0.05".to_string()))
+        );
+
+        // One lamport short at $100/SOL is a fraction of a micro-USDC: round up
+        assert_eq!(
+            config.payment_difference(PaymentToken::Sol, "0.0001", "0.000099999"),
+            (None, Some("0.000001".to_string()))
+        );
+        assert_eq!(
+            config.payment_difference(PaymentToken::Sol, "0.0001", "0.0002"),
+            (Some("0.01".to_string()), None)
+        );
+    }
+
     #[test]
     fn test_default_config() {
         let config = X402Config::default();
+        assert_eq!(config.quote_ttl_secs, 300);
         assert!(!config.enabled);
         assert!(config.wallet_address.is_empty());
+        assert_eq!(config.rate_limits.verify_per_min, 10);
+        assert!(config.rate_limits.api_key_verify_per_min > config.rate_limits.verify_per_min);
     }
 }
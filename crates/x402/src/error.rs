@@ -29,6 +29,10 @@ pub enum X402Error {
     #[error("facilitator network error: {0}")]
     NetworkError(String),
 
+    /// The facilitator did not answer in time; the payment may still be good
+    #[error("facilitator timed out: {0}")]
+    FacilitatorTimeout(String),
+
     /// Configuration error
     #[error("configuration error: {0}")]
     ConfigError(String),
@@ -36,6 +40,14 @@ pub enum X402Error {
     /// The payment token/currency is not supported
     #[error("unsupported token: {0}")]
     UnsupportedToken(String),
+
+    /// The request being paid for is malformed
+    #[error("invalid request: {0}")]
+    InvalidRequest(String),
+
+    /// A USDC amount is malformed or more precise than USDC allows
+    #[error("invalid amount: {0}")]
+    InvalidAmount(String),
 }
 
 impl X402Error {
@@ -53,7 +65,28 @@ impl X402Error {
     pub fn is_client_error(&self) -> bool {
         matches!(
             self,
-            X402Error::InvalidProof(_) | X402Error::UnsupportedToken(_)
+            X402Error::InvalidProof(_)
+                | X402Error::UnsupportedToken(_)
+                | X402Error::InvalidRequest(_)
+                | X402Error::InvalidAmount(_)
         )
     }
+
+    /// Returns true if the facilitator did not answer in time (a 504 rather
+    /// than a 502: the payment itself was not judged)
+    pub fn is_timeout(&self) -> bool {
+        matches!(self, X402Error::FacilitatorTimeout(_))
+    }
+}
+
+/// Errors from a [`crate::replay::ReplayStore`]
+#[derive(Debug, Error)]
+pub enum ReplayError {
+    /// The payment signature was already redeemed
+    #[error("payment already used: {0}")]
+    AlreadyUsed(String),
+
+    /// The store could not be read or written
+    #[error("replay store error: {0}")]
+    Store(String),
 }
@@ -1,15 +1,31 @@
 //! x402 Facilitator client for payment verification
 
-use crate::{PaymentProof, PaymentVerification, X402Config, X402Error};
+use crate::cache::{VerificationCache, VerificationKey};
+use crate::oracle::{HttpPriceOracle, PriceOracle};
+use crate::replay::{InMemoryReplayStore, PaymentReceipt, ReplayStore};
+use crate::types::format_units;
+use crate::{
+    PaymentDetails, PaymentProof, PaymentToken, PaymentVerification, QuoteSigner, ReplayError,
+    UsdcAmount, X402Config, X402Error,
+};
+use chrono::{DateTime, FixedOffset};
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use std::time::Duration;
 
 /// Client for interacting with x402 facilitator service
+///
+/// Clones share one cache of verified payments (see [`X402Config::verify_cache_size`]),
+/// one [`ReplayStore`] of redeemed ones and one [`PriceOracle`], if any.
 #[derive(Debug, Clone)]
 pub struct X402Facilitator {
     client: Client,
     config: X402Config,
+    cache: Arc<VerificationCache>,
+    replay: Arc<dyn ReplayStore>,
+    oracle: Option<Arc<dyn PriceOracle>>,
+    quotes: QuoteSigner,
 }
 
 #[derive(Debug, Serialize)]
@@ -19,75 +35,339 @@ struct VerifyPaymentRequest {
     expected_memo: String,
     min_amount: String,
     token: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    mint: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 struct FacilitatorResponse {
     valid: bool,
     amount: Option<String>,
+    /// Destination wallet of the transfer found on chain
+    #[serde(default)]
+    recipient: Option<String>,
     block: Option<u64>,
     confirmed_at: Option<String>,
     error: Option<String>,
 }
 
+/// Base delay before retrying a failed facilitator call, doubled per attempt
+const FACILITATOR_RETRY_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Idle pooled connections to the facilitator are kept this long
+const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// TCP keep-alive interval for facilitator connections
+const TCP_KEEPALIVE: Duration = Duration::from_secs(60);
+
+/// Outcome of one `/verify` attempt
+enum VerifyAttemptError {
+    /// Connection failure or 5xx: worth another attempt
+    Retryable(X402Error),
+    /// Timeout, other status or bad body: give up
+    Fatal(X402Error),
+}
+
 impl X402Facilitator {
     /// Create a new facilitator client with the given configuration
+    ///
+    /// The HTTP client times out after `facilitator_timeout_secs` and keeps
+    /// connections alive for reuse across verifications.
     pub fn new(config: X402Config) -> Self {
         let client = Client::builder()
-            .timeout(Duration::from_secs(30))
+            .timeout(Duration::from_secs(config.facilitator_timeout_secs))
+            .pool_idle_timeout(POOL_IDLE_TIMEOUT)
+            .tcp_keepalive(TCP_KEEPALIVE)
             .build()
             .expect("Failed to create HTTP client");
+        Self::with_client(config, client)
+    }
+
+    /// Create a facilitator client sending requests through `client`, e.g.
+    /// one shared with the rest of the application or with its own timeout
+    pub fn with_client(config: X402Config, client: Client) -> Self {
+        let cache = Arc::new(VerificationCache::new(
+            config.verify_cache_size,
+            Duration::from_secs(config.verify_cache_ttl_secs),
+        ));
+        let oracle = config.price_oracle_url.as_ref().map(|url| {
+            Arc::new(HttpPriceOracle::new(
+                url.clone(),
+                Duration::from_secs(config.price_oracle_ttl_secs),
+            )) as Arc<dyn PriceOracle>
+        });
+
+        Self {
+            client,
+            config,
+            cache,
+            replay: Arc::new(InMemoryReplayStore::new()),
+            oracle,
+            quotes: QuoteSigner::from_env(),
+        }
+    }
+
+    /// Price SOL with `oracle` instead of the configured one, if any
+    pub fn with_price_oracle(mut self, oracle: Arc<dyn PriceOracle>) -> Self {
+        self.oracle = Some(oracle);
+        self
+    }
+
+    /// Configuration to quote and settle payments with right now: the
+    /// configured one with `sol_price_usdc` from the price oracle.
+    ///
+    /// If the oracle fails the configured `sol_price_usdc` stands, so SOL is
+    /// still accepted at the static price, or not at all if none is set.
+    pub async fn pricing_config(&self) -> X402Config {
+        let mut config = self.config.clone();
+        if let Some(oracle) = &self.oracle {
+            match oracle.price_micro_usd(PaymentToken::Sol).await {
+                Ok(price) => {
+                    config.sol_price_usdc =
+                        Some(format_units(price, PaymentToken::Usdc.decimals()));
+                }
+                Err(e) => tracing::warn!("SOL price unavailable, using configured price: {}", e),
+            }
+        }
+        config
+    }
+
+    /// Sign quotes with `signer` instead of the one from `X402_QUOTE_SECRET`
+    pub fn with_quote_signer(mut self, signer: QuoteSigner) -> Self {
+        self.quotes = signer;
+        self
+    }
+
+    /// Sign `quote`'s memo and expiry so a proof echoing them is accepted
+    pub fn sign_quote(&self, quote: PaymentDetails) -> PaymentDetails {
+        quote.signed(&self.quotes)
+    }
+
+    /// Keep redeemed payments in `store` instead of the default in-memory one
+    pub fn with_replay_store(mut self, store: Arc<dyn ReplayStore>) -> Self {
+        self.replay = store;
+        self
+    }
+
+    /// Whether a payment signature was already redeemed
+    pub async fn is_payment_used(&self, signature: &str) -> Result<bool, ReplayError> {
+        self.replay.is_used(signature).await
+    }
 
-        Self { client, config }
+    /// Redeem a verified payment; [`ReplayError::AlreadyUsed`] if it already was
+    pub async fn record_payment(&self, receipt: &PaymentReceipt) -> Result<(), ReplayError> {
+        self.replay.record(receipt).await
     }
 
     /// Verify a payment proof against the facilitator
+    ///
+    /// The proof must echo the `expires_at` and `quote_signature` of a quote
+    /// from [`Self::sign_quote`] for `expected_memo`; otherwise it is an
+    /// [`X402Error::InvalidProof`]. A payment whose block time (or, when the
+    /// facilitator reports none, the current time) is after that expiry is
+    /// rejected with [`X402Error::PaymentExpired`].
+    ///
+    /// Successful verifications are cached briefly, so asking again about the
+    /// same payment skips the facilitator. This does not make a payment
+    /// redeemable twice: callers must still [`Self::record_payment`] it
+    /// before serving anything.
+    ///
+    /// `min_amount` is the price in USDC. USDC and USDT payments must be SPL
+    /// transfers of that amount in the token's mint; SOL payments must be
+    /// native transfers worth it at the SOL price from [`Self::pricing_config`].
+    /// Payments in any other token, naming the wrong mint, or claiming a USDC
+    /// or USDT amount finer than six decimal places are rejected before the
+    /// facilitator is contacted.
     pub async fn verify_payment(
         &self,
         proof: &PaymentProof,
         expected_memo: &str,
-        min_amount: &str,
+        min_amount: &UsdcAmount,
+    ) -> Result<PaymentVerification, X402Error> {
+        let quote_expiry = self.quote_expiry(proof, expected_memo)?;
+        self.check_payment(proof, expected_memo, min_amount, Some(quote_expiry))
+            .await
+    }
+
+    /// Look up a payment again after it was redeemed, e.g. to reconcile its
+    /// receipt. Like [`Self::verify_payment`] but without the quote checks:
+    /// the quote was checked when the payment was accepted.
+    pub async fn recheck_payment(
+        &self,
+        proof: &PaymentProof,
+        expected_memo: &str,
+        min_amount: &UsdcAmount,
     ) -> Result<PaymentVerification, X402Error> {
+        self.check_payment(proof, expected_memo, min_amount, None)
+            .await
+    }
+
+    /// The server-signed expiry of the quote `proof` pays
+    fn quote_expiry(
+        &self,
+        proof: &PaymentProof,
+        expected_memo: &str,
+    ) -> Result<DateTime<FixedOffset>, X402Error> {
+        let (Some(expires_at), Some(signature)) = (&proof.expires_at, &proof.quote_signature)
+        else {
+            return Err(X402Error::InvalidProof(
+                "payment proof must echo the quote's expires_at and quote_signature".to_string(),
+            ));
+        };
+        if !self.quotes.verify(expected_memo, expires_at, signature) {
+            return Err(X402Error::InvalidProof(
+                "quote_signature does not match the quote's memo and expiry".to_string(),
+            ));
+        }
+        DateTime::parse_from_rfc3339(expires_at).map_err(|e| {
+            X402Error::InvalidProof(format!("invalid quote expiry '{}': {}", expires_at, e))
+        })
+    }
+
+    /// Verify a payment, checking it against `quote_expiry` when given
+    async fn check_payment(
+        &self,
+        proof: &PaymentProof,
+        expected_memo: &str,
+        min_amount: &UsdcAmount,
+        quote_expiry: Option<DateTime<FixedOffset>>,
+    ) -> Result<PaymentVerification, X402Error> {
+        let token: PaymentToken = proof.token.parse()?;
+        if matches!(token, PaymentToken::Usdc | PaymentToken::Usdt) {
+            proof.amount.parse::<UsdcAmount>()?;
+        }
+        let mint = token.mint(&self.config.network);
+        if let Some(claimed) = proof.mint.as_deref() {
+            if Some(claimed) != mint {
+                return Err(X402Error::InvalidProof(format!(
+                    "mint {} is not the {} mint on {}",
+                    claimed, token, self.config.network
+                )));
+            }
+        }
+        let pricing = match token {
+            PaymentToken::Sol => self.pricing_config().await,
+            _ => self.config.clone(),
+        };
+        let required = pricing.required_amount(token, &min_amount.to_string())?;
+
         // For devnet/testing, simulate verification
         if self.config.network == "devnet" {
-            return self.simulate_verification(proof, expected_memo, min_amount);
+            check_quote_expiry(quote_expiry, None)?;
+            return self.simulate_verification(&pricing, proof, token, expected_memo, &required);
+        }
+
+        let cache_key = VerificationKey {
+            signature: proof.signature.clone(),
+            token: token.symbol().to_string(),
+            memo: expected_memo.to_string(),
+            min_amount: required.clone(),
+        };
+        if let Some(cached) = self.cache.get(&cache_key) {
+            check_quote_expiry(quote_expiry, cached.confirmed_at.as_deref())?;
+            return Ok(cached);
         }
 
         let request = VerifyPaymentRequest {
             signature: proof.signature.clone(),
             expected_recipient: self.config.wallet_address.clone(),
             expected_memo: expected_memo.to_string(),
-            min_amount: min_amount.to_string(),
-            token: proof.token.clone(),
+            min_amount: required,
+            token: token.symbol().to_string(),
+            mint: mint.map(str::to_string),
         };
 
+        let result = self.post_verify(&request).await?;
+
+        // A rejected payment is reported as such whatever its block time
+        if result.valid {
+            check_quote_expiry(quote_expiry, result.confirmed_at.as_deref())?;
+        }
+
+        // Settle against the amount the facilitator saw on chain, not the claim
+        let amount = result.amount.unwrap_or_else(|| proof.amount.clone());
+        let (overpaid_usdc, underpaid) =
+            pricing.payment_difference(token, &request.min_amount, &amount);
+        let error = match &underpaid {
+            Some(shortfall) if result.valid => Some(underpaid_message(&amount, token, shortfall)),
+            _ => result.error,
+        };
+        let verification = PaymentVerification {
+            valid: result.valid && underpaid.is_none(),
+            tx_signature: proof.signature.clone(),
+            amount_usdc: pricing.to_usdc(token, &amount).unwrap_or_default(),
+            recipient: result.recipient,
+            overpaid_usdc,
+            underpaid,
+            block: result.block,
+            confirmed_at: result.confirmed_at,
+            error,
+        };
+        self.cache.insert(cache_key, &verification);
+        Ok(verification)
+    }
+
+    /// POST `request` to the facilitator's `/verify`, retrying connection
+    /// failures and 5xx up to `facilitator_retries` times with backoff
+    async fn post_verify(
+        &self,
+        request: &VerifyPaymentRequest,
+    ) -> Result<FacilitatorResponse, X402Error> {
+        let mut attempt = 0;
+        loop {
+            match self.verify_attempt(request).await {
+                Ok(result) => return Ok(result),
+                Err(VerifyAttemptError::Fatal(e)) => return Err(e),
+                Err(VerifyAttemptError::Retryable(e)) => {
+                    if attempt >= self.config.facilitator_retries {
+                        return Err(e);
+                    }
+                    attempt += 1;
+                    tracing::warn!(attempt, error = %e, "Facilitator call failed, retrying");
+                    let factor = 2u32.saturating_pow(attempt - 1);
+                    tokio::time::sleep(FACILITATOR_RETRY_BACKOFF.saturating_mul(factor)).await;
+                }
+            }
+        }
+    }
+
+    async fn verify_attempt(
+        &self,
+        request: &VerifyPaymentRequest,
+    ) -> Result<FacilitatorResponse, VerifyAttemptError> {
         let response = self
             .client
             .post(format!("{}/verify", self.config.facilitator_url))
-            .json(&request)
+            .json(request)
             .send()
             .await
-            .map_err(|e| X402Error::NetworkError(format!("Facilitator request failed: {}", e)))?;
-
-        if !response.status().is_success() {
-            return Err(X402Error::NetworkError(format!(
-                "Facilitator returned error: {}",
-                response.status()
-            )));
+            .map_err(|e| {
+                if e.is_timeout() {
+                    VerifyAttemptError::Fatal(X402Error::FacilitatorTimeout(e.to_string()))
+                } else {
+                    VerifyAttemptError::Retryable(X402Error::NetworkError(format!(
+                        "Facilitator request failed: {}",
+                        e
+                    )))
+                }
+            })?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let error = X402Error::NetworkError(format!("Facilitator returned error: {}", status));
+            return Err(if status.is_server_error() {
+                VerifyAttemptError::Retryable(error)
+            } else {
+                VerifyAttemptError::Fatal(error)
+            });
         }
 
-        let result: FacilitatorResponse = response
-            .json()
-            .await
-            .map_err(|e| X402Error::NetworkError(format!("Failed to parse response: {}", e)))?;
-
-        Ok(PaymentVerification {
-            valid: result.valid,
-            tx_signature: proof.signature.clone(),
-            amount_usdc: result.amount.unwrap_or_else(|| proof.amount.clone()),
-            block: result.block,
-            confirmed_at: result.confirmed_at,
-            error: result.error,
+        response.json().await.map_err(|e| {
+            VerifyAttemptError::Fatal(if e.is_timeout() {
+                X402Error::FacilitatorTimeout(e.to_string())
+            } else {
+                X402Error::NetworkError(format!("Failed to parse response: {}", e))
+            })
         })
     }
 
@@ -139,7 +419,10 @@ impl X402Facilitator {
             return Ok(PaymentVerification {
                 valid: false,
                 tx_signature: proof.signature.clone(),
-                amount_usdc: proof.amount.clone(),
+                amount_usdc: proof.amount.parse().unwrap_or_default(),
+                recipient: None,
+                overpaid_usdc: None,
+                underpaid: None,
                 block: None,
                 confirmed_at: None,
                 error: Some("Transaction not found".to_string()),
@@ -160,7 +443,10 @@ impl X402Facilitator {
         Ok(PaymentVerification {
             valid: is_valid,
             tx_signature: proof.signature.clone(),
-            amount_usdc: proof.amount.clone(),
+            amount_usdc: proof.amount.parse().unwrap_or_default(),
+            recipient: None,
+            overpaid_usdc: None,
+            underpaid: None,
             block: slot,
             confirmed_at: block_time.map(|t| {
                 chrono::DateTime::from_timestamp(t, 0)
@@ -176,18 +462,27 @@ impl X402Facilitator {
     }
 
     /// Simulate payment verification for testing (devnet)
+    ///
+    /// `min_amount` is already in `token` units, priced by `pricing`.
     fn simulate_verification(
         &self,
+        pricing: &X402Config,
         proof: &PaymentProof,
+        token: PaymentToken,
         expected_memo: &str,
         min_amount: &str,
     ) -> Result<PaymentVerification, X402Error> {
+        let amount_usdc = pricing.to_usdc(token, &proof.amount).unwrap_or_default();
+
         // Basic validation for testing
         if proof.memo != expected_memo {
             return Ok(PaymentVerification {
                 valid: false,
                 tx_signature: proof.signature.clone(),
-                amount_usdc: proof.amount.clone(),
+                amount_usdc,
+                recipient: None,
+                overpaid_usdc: None,
+                underpaid: None,
                 block: None,
                 confirmed_at: None,
                 error: Some(format!(
@@ -197,21 +492,19 @@ impl X402Facilitator {
             });
         }
 
-        // Parse amounts for comparison
-        let proof_amount: f64 = proof.amount.parse().unwrap_or(0.0);
-        let min: f64 = min_amount.parse().unwrap_or(0.0);
-
-        if proof_amount < min {
+        let (overpaid_usdc, underpaid) =
+            pricing.payment_difference(token, min_amount, &proof.amount);
+        if let Some(shortfall) = underpaid {
             return Ok(PaymentVerification {
                 valid: false,
                 tx_signature: proof.signature.clone(),
-                amount_usdc: proof.amount.clone(),
+                amount_usdc,
+                recipient: None,
+                overpaid_usdc: None,
+                error: Some(underpaid_message(&proof.amount, token, &shortfall)),
+                underpaid: Some(shortfall),
                 block: None,
                 confirmed_at: None,
-                error: Some(format!(
-                    "Insufficient payment: {} < {}",
-                    proof.amount, min_amount
-                )),
             });
         }
 
@@ -219,7 +512,10 @@ impl X402Facilitator {
         Ok(PaymentVerification {
             valid: true,
             tx_signature: proof.signature.clone(),
-            amount_usdc: proof.amount.clone(),
+            amount_usdc,
+            recipient: Some(self.config.wallet_address.clone()),
+            overpaid_usdc,
+            underpaid: None,
             block: Some(999999),
             confirmed_at: Some(chrono::Utc::now().to_rfc3339()),
             error: None,
@@ -242,10 +538,64 @@ impl X402Facilitator {
     }
 }
 
+/// Reject a payment whose block time falls after the quote it pays expired.
+///
+/// Without a block time the payment is judged as confirmed now; a block time
+/// that cannot be parsed fails verification. No `expires_at` (a re-check of
+/// a redeemed payment) skips the check.
+fn check_quote_expiry(
+    expires_at: Option<DateTime<FixedOffset>>,
+    confirmed_at: Option<&str>,
+) -> Result<(), X402Error> {
+    let Some(expires_at) = expires_at else {
+        return Ok(());
+    };
+    let confirmed = match confirmed_at {
+        Some(confirmed_at) => DateTime::parse_from_rfc3339(confirmed_at).map_err(|e| {
+            X402Error::VerificationFailed(format!(
+                "unparseable block time '{}': {}",
+                confirmed_at, e
+            ))
+        })?,
+        None => chrono::Utc::now().fixed_offset(),
+    };
+    if confirmed > expires_at {
+        return Err(X402Error::PaymentExpired(format!(
+            "expired quote: payment confirmed at {} but the quote expired at {}",
+            confirmed.to_rfc3339(),
+            expires_at.to_rfc3339()
+        )));
+    }
+    Ok(())
+}
+
+/// Error for a payment that fell short; keeps the "Insufficient payment"
+/// prefix clients already match on
+fn underpaid_message(paid: &str, token: PaymentToken, shortfall_usdc: &str) -> String {
+    format!(
+        "Insufficient payment: paid {} {}, short by {} USDC",
+        paid, token, shortfall_usdc
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn usdc(amount: &str) -> UsdcAmount {
+        amount.parse().unwrap()
+    }
+
+    /// `proof` echoing a quote for `evidence:evt-001` signed by `facilitator`
+    fn quoted(facilitator: &X402Facilitator, proof: &PaymentProof) -> PaymentProof {
+        let expires_at = (chrono::Utc::now() + chrono::Duration::minutes(5)).to_rfc3339();
+        PaymentProof {
+            quote_signature: Some(facilitator.quotes.sign("evidence:evt-001", &expires_at)),
+            expires_at: Some(expires_at),
+            ..proof.clone()
+        }
+    }
+
     #[test]
     fn test_facilitator_creation() {
         let config = X402Config::devnet("PhxRvk123");
@@ -264,13 +614,20 @@ mod tests {
             signature: "test-sig-123".to_string(),
             amount: "0.01".to_string(),
             token: "USDC".to_string(),
+            mint: None,
             sender: "sender123".to_string(),
             memo: "evidence:evt-001".to_string(),
+            expires_at: None,
+            quote_signature: None,
             timestamp: chrono::Utc::now().to_rfc3339(),
         };
 
         let result = facilitator
-            .verify_payment(&proof, "evidence:evt-001", "0.01")
+            .verify_payment(
+                &quoted(&facilitator, &proof),
+                "evidence:evt-001",
+                &usdc("0.01"),
+            )
             .await
             .unwrap();
 
@@ -287,13 +644,20 @@ mod tests {
             signature: "test-sig-123".to_string(),
             amount: "0.01".to_string(),
             token: "USDC".to_string(),
+            mint: None,
             sender: "sender123".to_string(),
             memo: "evidence:wrong-id".to_string(),
+            expires_at: None,
+            quote_signature: None,
             timestamp: chrono::Utc::now().to_rfc3339(),
         };
 
         let result = facilitator
-            .verify_payment(&proof, "evidence:evt-001", "0.01")
+            .verify_payment(
+                &quoted(&facilitator, &proof),
+                "evidence:evt-001",
+                &usdc("0.01"),
+            )
             .await
             .unwrap();
 
@@ -310,17 +674,213 @@ mod tests {
             signature: "test-sig-123".to_string(),
             amount: "0.001".to_string(),
             token: "USDC".to_string(),
+            mint: None,
             sender: "sender123".to_string(),
             memo: "evidence:evt-001".to_string(),
+            expires_at: None,
+            quote_signature: None,
             timestamp: chrono::Utc::now().to_rfc3339(),
         };
 
         let result = facilitator
-            .verify_payment(&proof, "evidence:evt-001", "0.01")
+            .verify_payment(
+                &quoted(&facilitator, &proof),
+                "evidence:evt-001",
+                &usdc("0.01"),
+            )
             .await
             .unwrap();
 
         assert!(!result.valid);
         assert!(result.error.unwrap().contains("Insufficient"));
     }
+
+    fn usdc_proof(amount: &str) -> PaymentProof {
+        PaymentProof {
+            signature: "test-sig-123".to_string(),
+            amount: amount.to_string(),
+            token: "USDC".to_string(),
+            mint: None,
+            sender: "sender123".to_string(),
+            memo: "evidence:evt-001".to_string(),
+            expires_at: None,
+            quote_signature: None,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_exact_payment_has_no_difference() {
+        let facilitator = X402Facilitator::new(X402Config::devnet("PhxRvk123"));
+
+        let result = facilitator
+            .verify_payment(
+                &quoted(&facilitator, &usdc_proof("0.05")),
+                "evidence:evt-001",
+                &usdc("0.05"),
+            )
+            .await
+            .unwrap();
+
+        assert!(result.valid);
+        assert_eq!(result.overpaid_usdc, None);
+        assert_eq!(result.underpaid, None);
+    }
+
+    #[tokio::test]
+    async fn test_overpayment_is_accepted_and_reported() {
+        let facilitator = X402Facilitator::new(X402Config::devnet("PhxRvk123"));
+
+        let result = facilitator
+            .verify_payment(
+                &quoted(&facilitator, &usdc_proof("0.10")),
+                "evidence:evt-001",
+                &usdc("0.05"),
+            )
+            .await
+            .unwrap();
+
+        assert!(result.valid);
+        assert_eq!(result.amount_usdc, "0.10");
+        assert_eq!(result.overpaid_usdc.as_deref(), Some("0.05"));
+        assert_eq!(result.underpaid, None);
+    }
+
+    #[tokio::test]
+    async fn test_underpayment_reports_shortfall() {
+        let facilitator = X402Facilitator::new(X402Config::devnet("PhxRvk123"));
+
+        let result = facilitator
+            .verify_payment(
+                &quoted(&facilitator, &usdc_proof("0.045")),
+                "evidence:evt-001",
+                &usdc("0.05"),
+            )
+            .await
+            .unwrap();
+
+        assert!(!result.valid);
+        assert_eq!(result.overpaid_usdc, None);
+        assert_eq!(result.underpaid.as_deref(), Some("0.005"));
+        assert!(result.error.unwrap().contains("short by 0.005 USDC"));
+    }
+
+    #[tokio::test]
+    async fn test_simulate_verification_sol_at_configured_price() {
+        let mut config = X402Config::devnet("PhxRvk123");
+        config.sol_price_usdc = Some("100".to_string());
+        let facilitator = X402Facilitator::new(config);
+
+        let proof = PaymentProof {
+            signature: "test-sig-sol".to_string(),
+            amount: "0.0001".to_string(),
+            token: "SOL".to_string(),
+            mint: None,
+            sender: "sender123".to_string(),
+            memo: "evidence:evt-001".to_string(),
+            expires_at: None,
+            quote_signature: None,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+        };
+
+        let result = facilitator
+            .verify_payment(
+                &quoted(&facilitator, &proof),
+                "evidence:evt-001",
+                &usdc("0.01"),
+            )
+            .await
+            .unwrap();
+        assert!(result.valid);
+        assert_eq!(result.amount_usdc, "0.01");
+
+        let short = facilitator
+            .verify_payment(
+                &quoted(&facilitator, &proof),
+                "evidence:evt-001",
+                &usdc("0.02"),
+            )
+            .await
+            .unwrap();
+        assert!(!short.valid);
+    }
+
+    #[tokio::test]
+    async fn test_proof_without_quote_expiry_is_rejected() {
+        let facilitator = X402Facilitator::new(X402Config::devnet("PhxRvk123"));
+        let quoted = quoted(&facilitator, &usdc_proof("0.05"));
+
+        let missing_expiry = PaymentProof {
+            expires_at: None,
+            ..quoted.clone()
+        };
+        let err = facilitator
+            .verify_payment(&missing_expiry, "evidence:evt-001", &usdc("0.05"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, X402Error::InvalidProof(_)), "{}", err);
+
+        let unsigned = PaymentProof {
+            quote_signature: None,
+            ..quoted.clone()
+        };
+        let err = facilitator
+            .verify_payment(&unsigned, "evidence:evt-001", &usdc("0.05"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, X402Error::InvalidProof(_)), "{}", err);
+
+        // A client pushing the expiry out breaks the signature
+        let extended = PaymentProof {
+            expires_at: Some("2099-01-01T00:00:00Z".to_string()),
+            ..quoted
+        };
+        let err = facilitator
+            .verify_payment(&extended, "evidence:evt-001", &usdc("0.05"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, X402Error::InvalidProof(_)), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_unparseable_quote_expiry_is_rejected() {
+        let facilitator = X402Facilitator::new(X402Config::devnet("PhxRvk123"));
+        let proof = PaymentProof {
+            quote_signature: Some(facilitator.quotes.sign("evidence:evt-001", "soon")),
+            expires_at: Some("soon".to_string()),
+            ..usdc_proof("0.05")
+        };
+
+        let err = facilitator
+            .verify_payment(&proof, "evidence:evt-001", &usdc("0.05"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, X402Error::InvalidProof(_)), "{}", err);
+    }
+
+    #[test]
+    fn test_unparseable_block_time_fails_expiry_check() {
+        let expires_at = DateTime::parse_from_rfc3339("2026-01-01T00:05:00Z").unwrap();
+
+        assert!(matches!(
+            check_quote_expiry(Some(expires_at), Some("block 1234")),
+            Err(X402Error::VerificationFailed(_))
+        ));
+        assert!(check_quote_expiry(Some(expires_at), Some("2026-01-01T00:04:00Z")).is_ok());
+        assert!(matches!(
+            check_quote_expiry(Some(expires_at), Some("2026-01-01T00:06:00Z")),
+            Err(X402Error::PaymentExpired(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_recheck_skips_quote() {
+        let facilitator = X402Facilitator::new(X402Config::devnet("PhxRvk123"));
+
+        let result = facilitator
+            .recheck_payment(&usdc_proof("0.05"), "evidence:evt-001", &usdc("0.05"))
+            .await
+            .unwrap();
+        assert!(result.valid);
+    }
 }
@@ -0,0 +1,288 @@
+//! Tower layer gating any route behind an x402 payment
+//!
+//! [`X402Layer`] asks a price resolver what each request costs. Free requests
+//! (`None`) go straight to the inner service. Priced requests without an
+//! `X-PAYMENT` header get 402 Payment Required with [`PaymentDetails`]; with
+//! one, the payment is verified against the resolved price and memo, redeemed
+//! through the facilitator's [`crate::ReplayStore`], and the request is
+//! served with the [`PaymentVerification`] in its extensions.
+//!
+//! ```rust,ignore
+//! let app = Router::new()
+//!     .route("/reports/{id}", get(report))
+//!     .layer(X402Layer::new(config, |parts: &Parts| {
+//!         Some(RoutePrice::for_tier(format!("report:{}", parts.uri.path()), PriceTier::Basic))
+//!     }));
+//! ```
+//!
+//! Failures answer the way the API's premium verification does: 400 for a
+//! malformed proof or unsupported token, 402 (with a fresh quote) for an
+//! expired, short or invalid payment, 409 for a redeemed signature, 502 when
+//! the facilitator can't be reached and 504 when it doesn't answer in time.
+
+use crate::middleware::extract_payment_proof;
+use crate::{
+    PaymentDetails, PaymentProof, PaymentReceipt, PaymentVerification, PriceTier, ReplayError,
+    UsdcAmount, X402Config, X402Error, X402Facilitator,
+};
+use axum::extract::Request;
+use axum::http::request::Parts;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use serde_json::json;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+
+/// What a priced request costs
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoutePrice {
+    /// Memo the payment must carry, tying it to this resource
+    pub memo: String,
+    pub tier: PriceTier,
+    /// Price in USDC
+    pub price_usdc: UsdcAmount,
+}
+
+impl RoutePrice {
+    pub fn new(memo: impl Into<String>, tier: PriceTier, price_usdc: UsdcAmount) -> Self {
+        Self {
+            memo: memo.into(),
+            tier,
+            price_usdc,
+        }
+    }
+
+    /// Priced at the tier's list price
+    pub fn for_tier(memo: impl Into<String>, tier: PriceTier) -> Self {
+        Self::new(memo, tier, tier.price())
+    }
+}
+
+type PriceResolver = Arc<dyn Fn(&Parts) -> Option<RoutePrice> + Send + Sync>;
+
+/// Layer requiring an x402 payment for every request its resolver prices
+#[derive(Clone)]
+pub struct X402Layer {
+    config: X402Config,
+    facilitator: X402Facilitator,
+    resolver: PriceResolver,
+}
+
+impl X402Layer {
+    /// Gate requests priced by `resolver` (`None` = free), verifying payments
+    /// with a facilitator built from `config`
+    pub fn new<F>(config: X402Config, resolver: F) -> Self
+    where
+        F: Fn(&Parts) -> Option<RoutePrice> + Send + Sync + 'static,
+    {
+        Self {
+            facilitator: X402Facilitator::new(config.clone()),
+            config,
+            resolver: Arc::new(resolver),
+        }
+    }
+
+    /// Verify and redeem through `facilitator` instead, sharing its cache
+    /// and replay store with other users of it
+    pub fn with_facilitator(mut self, facilitator: X402Facilitator) -> Self {
+        self.facilitator = facilitator;
+        self
+    }
+}
+
+impl<S> Layer<S> for X402Layer {
+    type Service = X402Service<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        X402Service {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+/// Service produced by [`X402Layer`]
+#[derive(Clone)]
+pub struct X402Service<S> {
+    inner: S,
+    layer: X402Layer,
+}
+
+impl<S> Service<Request> for X402Service<S>
+where
+    S: Service<Request, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request) -> Self::Future {
+        // Serve with the instance that was polled ready
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let layer = self.layer.clone();
+
+        Box::pin(async move {
+            let (mut parts, body) = request.into_parts();
+            let Some(price) = (layer.resolver)(&parts) else {
+                return inner.call(Request::from_parts(parts, body)).await;
+            };
+            match layer.redeem(&parts, &price).await {
+                Ok(verification) => {
+                    parts.extensions.insert(verification);
+                    inner.call(Request::from_parts(parts, body)).await
+                }
+                Err(response) => Ok(response),
+            }
+        })
+    }
+}
+
+impl X402Layer {
+    /// Quote for `price`, as returned with every 402
+    async fn payment_details(&self, price: &RoutePrice) -> PaymentDetails {
+        let mut details = PaymentDetails::for_evidence(
+            "",
+            price.tier,
+            &self.config.wallet_address,
+            &self.config.facilitator_url,
+        );
+        details.memo = price.memo.clone();
+        details.price = price.price_usdc;
+        let details = details
+            .with_token_quotes(&self.facilitator.pricing_config().await)
+            .with_expiry(self.config.quote_ttl_secs);
+        self.facilitator.sign_quote(details)
+    }
+
+    async fn payment_required(&self, price: &RoutePrice, body: serde_json::Value) -> Response {
+        let mut body = body;
+        body["payment_details"] = json!(self.payment_details(price).await);
+        (StatusCode::PAYMENT_REQUIRED, Json(body)).into_response()
+    }
+
+    /// Verify and redeem the request's payment, or the response refusing it
+    async fn redeem(
+        &self,
+        parts: &Parts,
+        price: &RoutePrice,
+    ) -> Result<PaymentVerification, Response> {
+        let proof: PaymentProof = match extract_payment_proof(&parts.headers) {
+            Ok(Some(proof)) => proof,
+            Ok(None) => {
+                return Err((
+                    StatusCode::PAYMENT_REQUIRED,
+                    Json(self.payment_details(price).await),
+                )
+                    .into_response())
+            }
+            Err(e) => return Err(bad_request("Invalid payment proof", &e)),
+        };
+
+        match self.facilitator.is_payment_used(&proof.signature).await {
+            Ok(false) => {}
+            Ok(true) => return Err(already_used(&proof.signature)),
+            Err(e) => return Err(store_error(&e)),
+        }
+
+        let verification = match self
+            .facilitator
+            .verify_payment(&proof, &price.memo, &price.price_usdc)
+            .await
+        {
+            Ok(verification) => verification,
+            Err(e @ X402Error::PaymentExpired(_)) => {
+                return Err(self
+                    .payment_required(
+                        price,
+                        json!({ "error": "Payment quote expired", "details": e.to_string() }),
+                    )
+                    .await)
+            }
+            Err(e) if e.is_client_error() => {
+                return Err(bad_request("Payment not accepted", &e));
+            }
+            Err(e) => {
+                return Err((
+                    gateway_status(&e),
+                    Json(json!({
+                        "error": "Payment verification failed",
+                        "details": e.to_string()
+                    })),
+                )
+                    .into_response())
+            }
+        };
+        if !verification.valid {
+            return Err(self
+                .payment_required(
+                    price,
+                    json!({ "error": "Payment verification failed", "verification": verification }),
+                )
+                .await);
+        }
+
+        let receipt = PaymentReceipt {
+            tx_signature: proof.signature.clone(),
+            evidence_id: price.memo.clone(),
+            tier: price.tier,
+            token: proof.token.trim().to_ascii_uppercase(),
+            token_amount: proof.amount.clone(),
+            amount_usdc: verification.amount_usdc.to_string(),
+            overpaid_usdc: verification.overpaid_usdc.clone(),
+            sender_wallet: Some(proof.sender.clone()),
+        };
+        match self.facilitator.record_payment(&receipt).await {
+            Ok(()) => Ok(verification),
+            Err(ReplayError::AlreadyUsed(_)) => Err(already_used(&proof.signature)),
+            Err(e) => Err(store_error(&e)),
+        }
+    }
+}
+
+fn bad_request(error: &str, e: &X402Error) -> Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(json!({ "error": error, "details": e.to_string() })),
+    )
+        .into_response()
+}
+
+/// 504 when the facilitator timed out, 502 for any other failure reaching it
+fn gateway_status(e: &X402Error) -> StatusCode {
+    if e.is_timeout() {
+        StatusCode::GATEWAY_TIMEOUT
+    } else {
+        StatusCode::BAD_GATEWAY
+    }
+}
+
+fn already_used(signature: &str) -> Response {
+    (
+        StatusCode::CONFLICT,
+        Json(json!({
+            "error": "Payment already used",
+            "tx_signature": signature,
+            "hint": "This payment signature has already been redeemed"
+        })),
+    )
+        .into_response()
+}
+
+fn store_error(e: &ReplayError) -> Response {
+    tracing::error!("x402 replay store failed: {}", e);
+    (
+        StatusCode::INTERNAL_SERVER_ERROR,
+        Json(json!({ "error": "Failed to record payment" })),
+    )
+        .into_response()
+}
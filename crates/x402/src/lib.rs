@@ -18,18 +18,31 @@
 //! - AI agent-native (autonomous payments)
 //! - Settlement via Solana (400ms finality)
 
+pub mod amount;
 pub mod attestation;
+mod cache;
 pub mod config;
 pub mod error;
 pub mod facilitator;
+pub mod layer;
 pub mod middleware;
+pub mod oracle;
+pub mod quote;
+pub mod replay;
 pub mod types;
 
-pub use attestation::AttestationSigner;
-pub use config::X402Config;
-pub use error::X402Error;
+pub use amount::UsdcAmount;
+pub use attestation::{verify_attestation, AttestationSigner};
+pub use config::{RateLimits, X402Config};
+pub use error::{ReplayError, X402Error};
 pub use facilitator::X402Facilitator;
+pub use layer::{RoutePrice, X402Layer};
+pub use oracle::{FixedRateOracle, HttpPriceOracle, PriceOracle};
+pub use quote::QuoteSigner;
+pub use replay::{InMemoryReplayStore, PaymentReceipt, ReplayStore};
 pub use types::{
-    AttestationInfo, EvidenceDigestInfo, PaymentDetails, PaymentProof, PaymentVerification,
-    PriceTier, VerifyEvidenceRequest, VerifyEvidenceResponse,
+    AttestationInfo, BulkDiscount, BulkEvidenceResult, BulkPricing, EvidenceDigestInfo,
+    PaymentDetails, PaymentProof, PaymentToken, PaymentVerification, PriceTier,
+    VerifyEvidenceBulkRequest, VerifyEvidenceRequest, VerifyEvidenceResponse,
+    MAX_BULK_EVIDENCE_IDS, SUPPORTED_CHAINS,
 };
@@ -39,6 +39,9 @@ mod tests {
     use super::*;
     use axum::http::HeaderMap;
 
+    const SIGNATURE: &str =
+        "5ivC3aygpPjezqAEQ9VmCJnhv8J7nq9JMGd8xLKQvJn8pBvJy6wEToqcJTKwVFZuu35DsDJDVcLc2kR1jZkb93om";
+
     #[test]
     fn test_extract_no_payment() {
         let headers = HeaderMap::new();
@@ -49,11 +52,14 @@ mod tests {
     #[test]
     fn test_extract_valid_payment() {
         let proof = PaymentProof {
-            signature: "sig123".to_string(),
+            signature: SIGNATURE.to_string(),
             amount: "0.01".to_string(),
             token: "USDC".to_string(),
-            sender: "sender".to_string(),
+            mint: None,
+            sender: "gsGBZpMXkp6VsXpe6t81fa2SAnKKkeVBZ8mucAAy7qb".to_string(),
             memo: "test".to_string(),
+            expires_at: None,
+            quote_signature: None,
             timestamp: "2025-01-01T00:00:00Z".to_string(),
         };
 
@@ -69,7 +75,7 @@ mod tests {
         assert!(result.is_some());
 
         let extracted = result.unwrap();
-        assert_eq!(extracted.signature, "sig123");
+        assert_eq!(extracted.signature, SIGNATURE);
         assert_eq!(extracted.amount, "0.01");
     }
 }
@@ -0,0 +1,197 @@
+//! Live token prices for quoting non-USDC payments
+//!
+//! Tier prices are in USDC, treated as USD. A [`PriceOracle`] supplies what
+//! each payment token is worth so SOL quotes follow the market instead of the
+//! static `X402_SOL_PRICE_USDC`. USDC and USDT stay 1:1.
+//!
+//! [`X402Facilitator::with_price_oracle`](crate::X402Facilitator::with_price_oracle)
+//! applies an oracle to both quoting and verification, so a payment is
+//! checked against the rate it was quoted at (while the rate is cached).
+
+use crate::types::{format_units, parse_units, PaymentToken};
+use crate::{X402Config, X402Error};
+use async_trait::async_trait;
+use reqwest::Client;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// One USD in micro-USD
+const ONE_USD_MICRO: u128 = 1_000_000;
+
+/// Source of token prices in USD
+#[async_trait]
+pub trait PriceOracle: Debug + Send + Sync {
+    /// Price of one whole `token` in micro-USD
+    async fn price_micro_usd(&self, token: PaymentToken) -> Result<u128, X402Error>;
+
+    /// Amount of `token` worth `amount_usd` (a decimal string), rounded up to
+    /// the token's smallest unit so the payment never falls short
+    async fn usd_to_token(
+        &self,
+        amount_usd: &str,
+        token: PaymentToken,
+    ) -> Result<String, X402Error> {
+        let price = self.price_micro_usd(token).await?;
+        X402Config {
+            sol_price_usdc: Some(format_units(price, PaymentToken::Usdc.decimals())),
+            ..X402Config::default()
+        }
+        .required_amount(token, amount_usd)
+    }
+}
+
+/// Fixed exchange rates, for tests and operators pinning a price
+#[derive(Debug, Clone, Default)]
+pub struct FixedRateOracle {
+    rates: HashMap<PaymentToken, u128>,
+}
+
+impl FixedRateOracle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Price one whole `token` at `usd` (a decimal string, e.g. `"150.25"`)
+    pub fn with_rate(mut self, token: PaymentToken, usd: &str) -> Self {
+        if let Some(micro) = parse_units(usd, PaymentToken::Usdc.decimals()) {
+            self.rates.insert(token, micro);
+        }
+        self
+    }
+}
+
+#[async_trait]
+impl PriceOracle for FixedRateOracle {
+    async fn price_micro_usd(&self, token: PaymentToken) -> Result<u128, X402Error> {
+        match token {
+            PaymentToken::Usdc | PaymentToken::Usdt => Ok(ONE_USD_MICRO),
+            _ => self
+                .rates
+                .get(&token)
+                .copied()
+                .filter(|price| *price > 0)
+                .ok_or_else(|| X402Error::UnsupportedToken(format!("{} (no rate set)", token))),
+        }
+    }
+}
+
+/// Oracle reading a CoinGecko-style simple price endpoint
+///
+/// Requests `{url}?ids=solana&vs_currencies=usd` and reads
+/// `{"solana": {"usd": 142.17}}`. Prices are cached for the TTL; a failed
+/// fetch is an error rather than a stale price.
+#[derive(Debug)]
+pub struct HttpPriceOracle {
+    client: Client,
+    url: String,
+    ttl: Duration,
+    cache: Mutex<HashMap<PaymentToken, (u128, Instant)>>,
+}
+
+impl HttpPriceOracle {
+    pub fn new(url: impl Into<String>, ttl: Duration) -> Self {
+        Self {
+            client: Client::builder()
+                .timeout(Duration::from_secs(10))
+                .build()
+                .expect("Failed to create HTTP client"),
+            url: url.into(),
+            ttl,
+            cache: Mutex::default(),
+        }
+    }
+
+    /// Id of `token` at the price endpoint
+    fn coin_id(token: PaymentToken) -> &'static str {
+        match token {
+            PaymentToken::Usdc => "usd-coin",
+            PaymentToken::Usdt => "tether",
+            PaymentToken::Sol => "solana",
+        }
+    }
+
+    fn cached(&self, token: PaymentToken) -> Option<u128> {
+        let cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        cache
+            .get(&token)
+            .filter(|(_, fetched_at)| fetched_at.elapsed() < self.ttl)
+            .map(|(price, _)| *price)
+    }
+
+    async fn fetch(&self, token: PaymentToken) -> Result<u128, X402Error> {
+        let id = Self::coin_id(token);
+        let url =
+            reqwest::Url::parse_with_params(&self.url, [("ids", id), ("vs_currencies", "usd")])
+                .map_err(|e| X402Error::ConfigError(format!("Invalid price oracle URL: {}", e)))?;
+        let response =
+            self.client.get(url).send().await.map_err(|e| {
+                X402Error::NetworkError(format!("Price oracle request failed: {}", e))
+            })?;
+        if !response.status().is_success() {
+            return Err(X402Error::NetworkError(format!(
+                "Price oracle returned error: {}",
+                response.status()
+            )));
+        }
+        let body: serde_json::Value = response.json().await.map_err(|e| {
+            X402Error::NetworkError(format!("Failed to parse price oracle response: {}", e))
+        })?;
+        let usd = body[id]["usd"]
+            .as_f64()
+            .filter(|usd| usd.is_finite() && *usd > 0.0)
+            .ok_or_else(|| {
+                X402Error::NetworkError(format!("Price oracle has no USD price for {}", id))
+            })?;
+        let micro = (usd * ONE_USD_MICRO as f64).round() as u128;
+        tracing::debug!(%token, price_usd = %format_units(micro, PaymentToken::Usdc.decimals()), "fetched token price");
+        Ok(micro)
+    }
+}
+
+#[async_trait]
+impl PriceOracle for HttpPriceOracle {
+    async fn price_micro_usd(&self, token: PaymentToken) -> Result<u128, X402Error> {
+        if matches!(token, PaymentToken::Usdc | PaymentToken::Usdt) {
+            return Ok(ONE_USD_MICRO);
+        }
+        if let Some(price) = self.cached(token) {
+            return Ok(price);
+        }
+        let price = self.fetch(token).await?;
+        self.cache
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(token, (price, Instant::now()));
+        Ok(price)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_fixed_rate_oracle_converts_usd() {
+        let oracle = FixedRateOracle::new().with_rate(PaymentToken::Sol, "200");
+        assert_eq!(
+            oracle
+                .usd_to_token("1.00", PaymentToken::Sol)
+                .await
+                .unwrap(),
+            "0.005"
+        );
+        assert_eq!(
+            oracle
+                .usd_to_token("0.05", PaymentToken::Usdt)
+                .await
+                .unwrap(),
+            "0.05"
+        );
+        assert!(FixedRateOracle::new()
+            .usd_to_token("1.00", PaymentToken::Sol)
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,91 @@
+//! Server-signed payment quotes
+//!
+//! A 402 quote carries `expires_at` and a `quote_signature`: the
+//! HMAC-SHA256 of `<memo>\n<expires_at>` under `X402_QUOTE_SECRET`. Payers
+//! echo both in their [`PaymentProof`](crate::PaymentProof), so the expiry a
+//! payment is judged against is the one this server issued, not whatever the
+//! client chose to send.
+
+use hmac::{Hmac, Mac};
+use rand_core::RngCore;
+use sha2::Sha256;
+use std::sync::Arc;
+
+/// Signs and checks quote expiries
+#[derive(Clone)]
+pub struct QuoteSigner {
+    secret: Arc<[u8]>,
+}
+
+impl QuoteSigner {
+    pub fn new(secret: impl AsRef<[u8]>) -> Self {
+        Self {
+            secret: Arc::from(secret.as_ref()),
+        }
+    }
+
+    /// Random secret: quotes only verify against this process
+    pub fn ephemeral() -> Self {
+        let mut secret = [0u8; 32];
+        rand_core::OsRng.fill_bytes(&mut secret);
+        Self::new(secret)
+    }
+
+    /// Signer keyed by `X402_QUOTE_SECRET`, or an ephemeral one if that is
+    /// unset (then a quote must be paid against the instance that issued it)
+    pub fn from_env() -> Self {
+        match std::env::var("X402_QUOTE_SECRET") {
+            Ok(secret) if !secret.trim().is_empty() => Self::new(secret.trim()),
+            _ => Self::ephemeral(),
+        }
+    }
+
+    /// Hex signature over a quote's memo and expiry
+    pub fn sign(&self, memo: &str, expires_at: &str) -> String {
+        hex::encode(self.mac(memo, expires_at).finalize().into_bytes())
+    }
+
+    /// Whether `signature` is this server's signature for `memo` and `expires_at`
+    pub fn verify(&self, memo: &str, expires_at: &str, signature: &str) -> bool {
+        let Ok(signature) = hex::decode(signature) else {
+            return false;
+        };
+        self.mac(memo, expires_at).verify_slice(&signature).is_ok()
+    }
+
+    fn mac(&self, memo: &str, expires_at: &str) -> Hmac<Sha256> {
+        let mut mac =
+            Hmac::<Sha256>::new_from_slice(&self.secret).expect("HMAC accepts any key length");
+        mac.update(memo.as_bytes());
+        mac.update(b"\n");
+        mac.update(expires_at.as_bytes());
+        mac
+    }
+}
+
+impl std::fmt::Debug for QuoteSigner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("QuoteSigner").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signature_binds_memo_and_expiry() {
+        let signer = QuoteSigner::new("quote-secret");
+        let signature = signer.sign("evidence:ev-1", "2026-01-01T00:05:00Z");
+
+        assert!(signer.verify("evidence:ev-1", "2026-01-01T00:05:00Z", &signature));
+        assert!(!signer.verify("evidence:ev-1", "2099-01-01T00:00:00Z", &signature));
+        assert!(!signer.verify("evidence:ev-2", "2026-01-01T00:05:00Z", &signature));
+        assert!(!signer.verify("evidence:ev-1", "2026-01-01T00:05:00Z", "not-hex"));
+        assert!(!QuoteSigner::new("other-secret").verify(
+            "evidence:ev-1",
+            "2026-01-01T00:05:00Z",
+            &signature
+        ));
+    }
+}
@@ -0,0 +1,89 @@
+//! Replay protection for redeemed payments
+//!
+//! A payment signature buys one request. [`ReplayStore`] is where redeemed
+//! signatures are remembered: [`crate::X402Facilitator`] consults it through
+//! [`crate::X402Facilitator::is_payment_used`] and
+//! [`crate::X402Facilitator::record_payment`], so services pick the storage
+//! (the API keeps receipts in SQLite) without the crate depending on it.
+//!
+//! [`InMemoryReplayStore`] is the default. It forgets everything on restart,
+//! so a deployment serving paid requests should provide a durable store.
+
+use crate::error::ReplayError;
+use crate::PriceTier;
+use async_trait::async_trait;
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::Mutex;
+
+/// A verified payment being redeemed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaymentReceipt {
+    /// Payment transaction signature; redeemable once
+    pub tx_signature: String,
+    /// Evidence the payment was for
+    pub evidence_id: String,
+    pub tier: PriceTier,
+    /// Token paid in, as its symbol (`USDC`, `USDT` or `SOL`)
+    pub token: String,
+    /// Amount paid, in `token`
+    pub token_amount: String,
+    /// Exact amount paid, in USDC
+    pub amount_usdc: String,
+    /// Part of the amount beyond the price, if any
+    pub overpaid_usdc: Option<String>,
+    pub sender_wallet: Option<String>,
+}
+
+/// Remembers which payment signatures have been redeemed
+#[async_trait]
+pub trait ReplayStore: Debug + Send + Sync {
+    /// Whether `signature` was already redeemed
+    async fn is_used(&self, signature: &str) -> Result<bool, ReplayError>;
+
+    /// Redeem a payment. Must fail with [`ReplayError::AlreadyUsed`] when
+    /// its signature was already recorded, atomically with the insert, so
+    /// two concurrent redemptions can't both succeed.
+    async fn record(&self, receipt: &PaymentReceipt) -> Result<(), ReplayError>;
+}
+
+/// Process-local [`ReplayStore`], for tests and single-instance development
+#[derive(Debug, Default)]
+pub struct InMemoryReplayStore {
+    receipts: Mutex<HashMap<String, PaymentReceipt>>,
+}
+
+impl InMemoryReplayStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The recorded receipt for `signature`, if any
+    pub fn get(&self, signature: &str) -> Option<PaymentReceipt> {
+        self.receipts
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(signature)
+            .cloned()
+    }
+}
+
+#[async_trait]
+impl ReplayStore for InMemoryReplayStore {
+    async fn is_used(&self, signature: &str) -> Result<bool, ReplayError> {
+        Ok(self
+            .receipts
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .contains_key(signature))
+    }
+
+    async fn record(&self, receipt: &PaymentReceipt) -> Result<(), ReplayError> {
+        let mut receipts = self.receipts.lock().unwrap_or_else(|e| e.into_inner());
+        if receipts.contains_key(&receipt.tx_signature) {
+            return Err(ReplayError::AlreadyUsed(receipt.tx_signature.clone()));
+        }
+        receipts.insert(receipt.tx_signature.clone(), receipt.clone());
+        Ok(())
+    }
+}
@@ -1,9 +1,12 @@
 //! Core types for x402 payment protocol
 
+use crate::UsdcAmount;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// Supported price tiers for evidence verification
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 #[serde(rename_all = "snake_case")]
 pub enum PriceTier {
     /// Basic single-chain verification ($0.01 USDC)
@@ -28,6 +31,13 @@ impl PriceTier {
         }
     }
 
+    /// Price in USDC as an exact amount
+    pub fn price(&self) -> UsdcAmount {
+        self.price_usdc()
+            .parse()
+            .expect("tier prices are valid USDC amounts")
+    }
+
     /// Get a human-readable description of this tier
     pub fn description(&self) -> &'static str {
         match self {
@@ -39,11 +49,197 @@ impl PriceTier {
     }
 }
 
+/// Format a micro-USDC amount as a decimal string, keeping at least two places
+pub fn format_micro_usdc(micro: u64) -> String {
+    format_units(u128::from(micro), PaymentToken::Usdc.decimals())
+}
+
+/// Format an amount in a token's smallest units (e.g. lamports) as a decimal
+/// string, keeping at least two places
+pub fn format_units(units: u128, decimals: u32) -> String {
+    let scale = 10u128.pow(decimals);
+    let fraction = format!("{:0width$}", units % scale, width = decimals as usize);
+    format!("{}.{:0<2}", units / scale, fraction.trim_end_matches('0'))
+}
+
+/// Parse a decimal amount string into a token's smallest units.
+///
+/// Returns `None` for malformed amounts or more fractional digits than the
+/// token has decimals.
+pub fn parse_units(amount: &str, decimals: u32) -> Option<u128> {
+    let (whole, fraction) = amount.trim().split_once('.').unwrap_or((amount.trim(), ""));
+    if (whole.is_empty() && fraction.is_empty())
+        || fraction.len() > decimals as usize
+        || !whole
+            .chars()
+            .chain(fraction.chars())
+            .all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+    let whole: u128 = if whole.is_empty() {
+        0
+    } else {
+        whole.parse().ok()?
+    };
+    let fraction: u128 = format!("{:0<width$}", fraction, width = decimals as usize)
+        .parse()
+        .ok()?;
+    whole
+        .checked_mul(10u128.pow(decimals))?
+        .checked_add(fraction)
+}
+
+/// USDC mint on Solana mainnet-beta
+pub const USDC_MINT_MAINNET: &str = "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v";
+
+/// USDC mint on Solana devnet (Circle's devnet faucet token)
+pub const USDC_MINT_DEVNET: &str = "4zMMC9srt5Ri5X14GAgXhaHii3GnPAEERYPJgZJDncDU";
+
+/// USDT mint on Solana mainnet-beta (Tether has no devnet mint)
+pub const USDT_MINT: &str = "Es9vMFrzaCERmJfrF4H2FYD4KCoNkY11McCe8BenwNYB";
+
+/// Tokens accepted for x402 payments
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum PaymentToken {
+    /// USD Coin (SPL token); tier prices are quoted in USDC
+    Usdc,
+    /// Tether USD (SPL token), accepted 1:1 with USDC
+    Usdt,
+    /// Native SOL, converted at the configured SOL/USDC price
+    Sol,
+}
+
+impl PaymentToken {
+    /// Every accepted token, in the order advertised to clients
+    pub const ALL: [PaymentToken; 3] = [PaymentToken::Usdc, PaymentToken::Usdt, PaymentToken::Sol];
+
+    /// Ticker symbol used in payment proofs and 402 responses
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            PaymentToken::Usdc => "USDC",
+            PaymentToken::Usdt => "USDT",
+            PaymentToken::Sol => "SOL",
+        }
+    }
+
+    /// Decimal places of the token's smallest unit
+    pub fn decimals(&self) -> u32 {
+        match self {
+            PaymentToken::Usdc | PaymentToken::Usdt => 6,
+            PaymentToken::Sol => 9,
+        }
+    }
+
+    /// SPL mint address on `network`, or `None` for native SOL transfers
+    pub fn mint(&self, network: &str) -> Option<&'static str> {
+        match self {
+            PaymentToken::Usdc if network.starts_with("mainnet") => Some(USDC_MINT_MAINNET),
+            PaymentToken::Usdc => Some(USDC_MINT_DEVNET),
+            PaymentToken::Usdt => Some(USDT_MINT),
+            PaymentToken::Sol => None,
+        }
+    }
+}
+
+impl std::fmt::Display for PaymentToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.symbol())
+    }
+}
+
+impl std::str::FromStr for PaymentToken {
+    type Err = crate::X402Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PaymentToken::ALL
+            .into_iter()
+            .find(|token| token.symbol().eq_ignore_ascii_case(s.trim()))
+            .ok_or_else(|| {
+                crate::X402Error::UnsupportedToken(format!(
+                    "{} (supported: USDC, USDT, SOL)",
+                    s.trim()
+                ))
+            })
+    }
+}
+
+/// Volume discount on the per-item part of a bulk order
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BulkDiscount {
+    /// Minimum item count for the discount to apply
+    pub min_items: u32,
+    /// Discount in basis points (1000 = 10%)
+    pub discount_bps: u32,
+}
+
+/// Bulk verification pricing: a base fee plus a per-item rate with volume discounts
+///
+/// `price = base + per_item * count * (10000 - discount_bps) / 10000`, where
+/// `discount_bps` comes from the highest threshold `count` reaches. Amounts are
+/// in micro-USDC so clients can reproduce the charge exactly.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BulkPricing {
+    /// Flat fee per bulk request
+    pub base_micro_usdc: u64,
+    /// Rate per evidence item before discounts
+    pub per_item_micro_usdc: u64,
+    /// Volume discount thresholds
+    pub discounts: Vec<BulkDiscount>,
+}
+
+impl Default for BulkPricing {
+    fn default() -> Self {
+        Self {
+            base_micro_usdc: 50_000,    // $0.05
+            per_item_micro_usdc: 5_000, // $0.005
+            discounts: vec![
+                BulkDiscount {
+                    min_items: 100,
+                    discount_bps: 1_000,
+                },
+                BulkDiscount {
+                    min_items: 1_000,
+                    discount_bps: 2_500,
+                },
+            ],
+        }
+    }
+}
+
+impl BulkPricing {
+    /// Discount in basis points for an order of `count` items
+    pub fn discount_bps(&self, count: u32) -> u32 {
+        self.discounts
+            .iter()
+            .filter(|d| count >= d.min_items)
+            .map(|d| d.discount_bps.min(10_000))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Total price in micro-USDC for `count` items
+    pub fn price_micro_usdc(&self, count: u32) -> u64 {
+        let per_items = u128::from(self.per_item_micro_usdc) * u128::from(count);
+        let discounted = per_items * u128::from(10_000 - self.discount_bps(count)) / 10_000;
+        self.base_micro_usdc
+            .saturating_add(u64::try_from(discounted).unwrap_or(u64::MAX))
+    }
+
+    /// Total price in USDC as a string (for precision)
+    pub fn price_usdc(&self, count: u32) -> String {
+        format_micro_usdc(self.price_micro_usdc(count))
+    }
+}
+
 /// Payment details returned in a 402 response
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct PaymentDetails {
-    /// Price amount (as string for precision)
-    pub price: String,
+    /// Price in USDC (serialized as a decimal string for precision)
+    #[cfg_attr(feature = "openapi", schema(value_type = String, example = "0.05"))]
+    pub price: UsdcAmount,
 
     /// Currency (e.g., "USDC", "USDT", "SOL")
     pub currency: String,
@@ -60,12 +256,27 @@ pub struct PaymentDetails {
     /// List of supported tokens for payment
     pub supported_tokens: Vec<String>,
 
-    /// Payment expiration timestamp (ISO 8601)
+    /// Quote expiration timestamp (RFC 3339). Payments confirmed on chain
+    /// after this are rejected; echo it in [`PaymentProof::expires_at`].
     #[serde(skip_serializing_if = "Option::is_none")]
     pub expires_at: Option<String>,
 
+    /// Server signature over `memo` and `expires_at`; echo it in
+    /// [`PaymentProof::quote_signature`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quote_signature: Option<String>,
+
     /// Price tier for this request
     pub tier: PriceTier,
+
+    /// Price in each supported token, keyed by symbol (SOL converted at the
+    /// configured price; omitted when SOL has no price)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub token_prices: BTreeMap<String, String>,
+
+    /// SPL mint for each supported SPL token, keyed by symbol
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub token_mints: BTreeMap<String, String>,
 }
 
 impl PaymentDetails {
@@ -77,18 +288,71 @@ impl PaymentDetails {
         facilitator: &str,
     ) -> Self {
         Self {
-            price: tier.price_usdc().to_string(),
+            price: tier.price(),
             currency: "USDC".to_string(),
             recipient: recipient.to_string(),
             memo: format!("evidence:{}", evidence_id),
             facilitator: facilitator.to_string(),
-            supported_tokens: vec!["USDC".to_string(), "USDT".to_string(), "SOL".to_string()],
+            supported_tokens: PaymentToken::ALL
+                .iter()
+                .map(|token| token.symbol().to_string())
+                .collect(),
             expires_at: None,
+            quote_signature: None,
             tier,
+            token_prices: BTreeMap::new(),
+            token_mints: BTreeMap::new(),
         }
+        .with_expiry(crate::config::DEFAULT_QUOTE_TTL_SECS)
+    }
+
+    /// Expire the quote `ttl_secs` from now
+    pub fn with_expiry(mut self, ttl_secs: u64) -> Self {
+        let ttl = chrono::Duration::seconds(i64::try_from(ttl_secs).unwrap_or(i64::MAX));
+        let expires_at = chrono::Utc::now()
+            .checked_add_signed(ttl)
+            .unwrap_or(chrono::DateTime::<chrono::Utc>::MAX_UTC);
+        self.expires_at = Some(expires_at.to_rfc3339_opts(chrono::SecondsFormat::Secs, true));
+        self.quote_signature = None;
+        self
+    }
+
+    /// Sign the quote's memo and expiry; call after both are final
+    pub fn signed(mut self, signer: &crate::QuoteSigner) -> Self {
+        self.quote_signature = self
+            .expires_at
+            .as_deref()
+            .map(|expires_at| signer.sign(&self.memo, expires_at));
+        self
+    }
+
+    /// Fill in per-token prices and mints for `price` on `config`'s network.
+    ///
+    /// Call after `price` is final. Tokens the config cannot price (SOL
+    /// without a configured price) are left out of `supported_tokens`.
+    pub fn with_token_quotes(mut self, config: &crate::X402Config) -> Self {
+        self.token_prices.clear();
+        self.token_mints.clear();
+        let mut supported = Vec::new();
+        for token in config.supported_tokens() {
+            let Ok(price) = config.required_amount(token, &self.price.to_string()) else {
+                continue;
+            };
+            supported.push(token.symbol().to_string());
+            self.token_prices.insert(token.symbol().to_string(), price);
+            if let Some(mint) = token.mint(&config.network) {
+                self.token_mints
+                    .insert(token.symbol().to_string(), mint.to_string());
+            }
+        }
+        self.supported_tokens = supported;
+        self
     }
 }
 
+/// Length in bytes of a Solana transaction signature
+const SIGNATURE_LEN: usize = 64;
+
 /// Payment proof submitted by the client in the X-PAYMENT header
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PaymentProof {
@@ -98,21 +362,44 @@ pub struct PaymentProof {
     /// Payment amount
     pub amount: String,
 
-    /// Token used for payment
+    /// Token used for payment ("USDC", "USDT" or "SOL")
     pub token: String,
 
+    /// SPL mint of the transferred token; absent for native SOL. When set it
+    /// must match the expected mint for `token`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mint: Option<String>,
+
     /// Sender wallet address
     pub sender: String,
 
     /// Memo from the transaction
     pub memo: String,
 
+    /// Expiry of the quote being paid, echoed from
+    /// [`PaymentDetails::expires_at`]; required
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<String>,
+
+    /// Signature of the quote being paid, echoed from
+    /// [`PaymentDetails::quote_signature`]; required
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quote_signature: Option<String>,
+
     /// Timestamp of the payment
     pub timestamp: String,
 }
 
 impl PaymentProof {
-    /// Decode a payment proof from base64-encoded X-PAYMENT header
+    /// Echo `quote`'s expiry and signature, as payers must
+    pub fn for_quote(mut self, quote: &PaymentDetails) -> Self {
+        self.expires_at = quote.expires_at.clone();
+        self.quote_signature = quote.quote_signature.clone();
+        self
+    }
+
+    /// Decode a payment proof from base64-encoded X-PAYMENT header and
+    /// [`validate`](Self::validate) it
     pub fn from_header(header_value: &str) -> Result<Self, crate::X402Error> {
         let decoded =
             base64::Engine::decode(&base64::engine::general_purpose::STANDARD, header_value)
@@ -123,8 +410,30 @@ impl PaymentProof {
         let json_str = String::from_utf8(decoded)
             .map_err(|e| crate::X402Error::InvalidProof(format!("UTF-8 decode error: {}", e)))?;
 
-        serde_json::from_str(&json_str)
-            .map_err(|e| crate::X402Error::InvalidProof(format!("JSON parse error: {}", e)))
+        let proof: Self = serde_json::from_str(&json_str)
+            .map_err(|e| crate::X402Error::InvalidProof(format!("JSON parse error: {}", e)))?;
+        proof.validate()?;
+        Ok(proof)
+    }
+
+    /// Check the proof could name a real payment: the signature must be a
+    /// 64-byte base58 Solana transaction signature and the sender a Solana
+    /// address. Malformed proofs are rejected before any replay check or
+    /// facilitator call.
+    pub fn validate(&self) -> Result<(), crate::X402Error> {
+        let signature = bs58::decode(&self.signature).into_vec().map_err(|e| {
+            crate::X402Error::InvalidProof(format!("signature is not base58: {}", e))
+        })?;
+        if signature.len() != SIGNATURE_LEN {
+            return Err(crate::X402Error::InvalidProof(format!(
+                "signature must be {} bytes, got {}",
+                SIGNATURE_LEN,
+                signature.len()
+            )));
+        }
+        address_validation::validate_solana_address(&self.sender).map_err(|e| {
+            crate::X402Error::InvalidProof(format!("sender is not a Solana address: {}", e))
+        })
     }
 
     /// Encode this payment proof for the X-PAYMENT header
@@ -148,8 +457,21 @@ pub struct PaymentVerification {
     /// Transaction signature on Solana
     pub tx_signature: String,
 
-    /// Amount paid in USDC
-    pub amount_usdc: String,
+    /// Amount paid in USDC (SOL payments converted at the configured price)
+    pub amount_usdc: UsdcAmount,
+
+    /// Wallet the transfer paid, when the facilitator reports it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub recipient: Option<String>,
+
+    /// USDC paid beyond the price; `None` unless the payment was over
+    #[serde(default)]
+    pub overpaid_usdc: Option<String>,
+
+    /// USDC still owed when the payment fell short of the price; such a
+    /// payment is never `valid`
+    #[serde(default)]
+    pub underpaid: Option<String>,
 
     /// Block height of the transaction
     pub block: Option<u64>,
@@ -162,8 +484,12 @@ pub struct PaymentVerification {
     pub error: Option<String>,
 }
 
+/// Chains a [`VerifyEvidenceRequest`] may name
+pub const SUPPORTED_CHAINS: [&str; 2] = ["solana", "etherlink"];
+
 /// Request to verify evidence with premium features
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct VerifyEvidenceRequest {
     /// Evidence ID to verify
     pub evidence_id: String,
@@ -175,10 +501,114 @@ pub struct VerifyEvidenceRequest {
     /// Requested price tier
     #[serde(default)]
     pub tier: PriceTier,
+
+    /// Number of evidence items covered (bulk tier pricing; defaults to 1)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub count: Option<u32>,
+}
+
+impl VerifyEvidenceRequest {
+    /// Check the request is well-formed, so a malformed one is refused
+    /// before the caller is asked to pay for it
+    pub fn validate(&self) -> Result<(), crate::X402Error> {
+        if self.evidence_id.trim().is_empty() {
+            return Err(crate::X402Error::InvalidRequest(
+                "evidence_id must not be empty".to_string(),
+            ));
+        }
+        validate_chain(self.chain.as_deref())?;
+        if self.count == Some(0) {
+            return Err(crate::X402Error::InvalidRequest(
+                "count must be at least 1".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn validate_chain(chain: Option<&str>) -> Result<(), crate::X402Error> {
+    match chain {
+        Some(chain) if !SUPPORTED_CHAINS.contains(&chain) => {
+            Err(crate::X402Error::InvalidRequest(format!(
+                "unknown chain '{}' (expected one of: {})",
+                chain,
+                SUPPORTED_CHAINS.join(", ")
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Most evidence items one bulk verification may cover
+pub const MAX_BULK_EVIDENCE_IDS: usize = 500;
+
+/// Request to verify several evidence records under one bulk-tier payment,
+/// priced by [`crate::X402Config::price_usdc`] for the number of ids
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct VerifyEvidenceBulkRequest {
+    /// Evidence IDs to verify (1 to [`MAX_BULK_EVIDENCE_IDS`], no duplicates)
+    pub evidence_ids: Vec<String>,
+
+    /// Specific chain to verify (optional, defaults to primary)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chain: Option<String>,
+}
+
+impl VerifyEvidenceBulkRequest {
+    /// Check the request is well-formed and within the batch cap, before
+    /// the caller is asked to pay for it
+    pub fn validate(&self) -> Result<(), crate::X402Error> {
+        let invalid = |message: String| Err(crate::X402Error::InvalidRequest(message));
+        if self.evidence_ids.is_empty() {
+            return invalid("evidence_ids must not be empty".to_string());
+        }
+        if self.evidence_ids.len() > MAX_BULK_EVIDENCE_IDS {
+            return invalid(format!(
+                "{} evidence_ids exceeds the limit of {} per request",
+                self.evidence_ids.len(),
+                MAX_BULK_EVIDENCE_IDS
+            ));
+        }
+        let mut seen = std::collections::HashSet::new();
+        for id in &self.evidence_ids {
+            if id.trim().is_empty() {
+                return invalid("evidence_ids must not contain empty ids".to_string());
+            }
+            if !seen.insert(id.as_str()) {
+                return invalid(format!("duplicate evidence_id '{}'", id));
+            }
+        }
+        validate_chain(self.chain.as_deref())
+    }
+
+    /// Number of evidence items billed
+    pub fn count(&self) -> u32 {
+        u32::try_from(self.evidence_ids.len()).unwrap_or(u32::MAX)
+    }
+}
+
+/// Outcome for one evidence record of a bulk verification
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct BulkEvidenceResult {
+    pub evidence_id: String,
+
+    /// Whether the evidence was found and verified
+    pub verified: bool,
+
+    /// Verification details when `verified`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verification: Option<VerifyEvidenceResponse>,
+
+    /// Why the evidence could not be verified
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
 }
 
 /// Response from premium evidence verification
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct VerifyEvidenceResponse {
     /// Whether the evidence was verified
     pub verified: bool,
@@ -199,6 +629,7 @@ pub struct VerifyEvidenceResponse {
 
 /// Evidence digest information
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct EvidenceDigestInfo {
     /// Hash algorithm used
     pub algo: String,
@@ -209,6 +640,7 @@ pub struct EvidenceDigestInfo {
 
 /// Legal attestation information
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct AttestationInfo {
     /// Entity that signed the attestation
     pub signed_by: String,
@@ -216,14 +648,25 @@ pub struct AttestationInfo {
     /// Digital signature
     pub signature: String,
 
-    /// Attestation expiration
+    /// Attestation expiration (RFC 3339); covered by the signature
     pub valid_until: String,
+
+    /// Hex-encoded Ed25519 public key that produced `signature`
+    pub public_key: String,
+
+    /// Short identifier of `public_key`, for lookup in a published key list
+    pub key_id: String,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Well-formed transaction signature (64 bytes, base58)
+    const SIGNATURE: &str =
+        "5ivC3aygpPjezqAEQ9VmCJnhv8J7nq9JMGd8xLKQvJn8pBvJy6wEToqcJTKwVFZuu35DsDJDVcLc2kR1jZkb93om";
+    const SENDER: &str = "gsGBZpMXkp6VsXpe6t81fa2SAnKKkeVBZ8mucAAy7qb";
+
     #[test]
     fn test_price_tier_prices() {
         assert_eq!(PriceTier::Basic.price_usdc(), "0.01");
@@ -232,6 +675,155 @@ mod tests {
         assert_eq!(PriceTier::Bulk.price_usdc(), "0.005");
     }
 
+    #[test]
+    fn test_bulk_price_scales_with_count() {
+        let pricing = BulkPricing::default();
+
+        assert_eq!(pricing.price_micro_usdc(0), 50_000);
+        assert_eq!(pricing.price_micro_usdc(1), 55_000);
+        assert_eq!(pricing.price_micro_usdc(10), 100_000);
+        assert!(pricing.price_micro_usdc(50) > pricing.price_micro_usdc(49));
+        assert_eq!(pricing.price_usdc(10), "0.10");
+        assert_eq!(pricing.price_usdc(1), "0.055");
+    }
+
+    #[test]
+    fn test_bulk_volume_discounts_apply_at_thresholds() {
+        let pricing = BulkPricing::default();
+
+        assert_eq!(pricing.discount_bps(99), 0);
+        assert_eq!(pricing.discount_bps(100), 1_000);
+        assert_eq!(pricing.discount_bps(999), 1_000);
+        assert_eq!(pricing.discount_bps(1_000), 2_500);
+
+        // 99 items at full rate, 100 items at 10% off
+        assert_eq!(pricing.price_micro_usdc(99), 50_000 + 99 * 5_000);
+        assert_eq!(pricing.price_micro_usdc(100), 50_000 + 450_000);
+        assert_eq!(pricing.price_usdc(1_000), "3.80");
+    }
+
+    #[test]
+    fn test_bulk_pricing_custom_thresholds() {
+        let pricing = BulkPricing {
+            base_micro_usdc: 0,
+            per_item_micro_usdc: 10_000,
+            discounts: vec![BulkDiscount {
+                min_items: 5,
+                discount_bps: 5_000,
+            }],
+        };
+
+        assert_eq!(pricing.price_usdc(4), "0.04");
+        assert_eq!(pricing.price_usdc(5), "0.025");
+    }
+
+    #[test]
+    fn test_format_micro_usdc() {
+        assert_eq!(format_micro_usdc(0), "0.00");
+        assert_eq!(format_micro_usdc(10_000), "0.01");
+        assert_eq!(format_micro_usdc(5_000), "0.005");
+        assert_eq!(format_micro_usdc(1_000_000), "1.00");
+        assert_eq!(format_micro_usdc(12_345_678), "12.345678");
+    }
+
+    #[test]
+    fn test_parse_and_format_units() {
+        assert_eq!(parse_units("0.01", 6), Some(10_000));
+        assert_eq!(parse_units("1", 9), Some(1_000_000_000));
+        assert_eq!(parse_units(".5", 6), Some(500_000));
+        assert_eq!(parse_units("0.0000001", 6), None);
+        assert_eq!(parse_units("-1", 6), None);
+        assert_eq!(parse_units("abc", 6), None);
+        assert_eq!(parse_units("", 6), None);
+        assert_eq!(format_units(200_000, 9), "0.0002");
+        assert_eq!(format_units(1_500_000_000, 9), "1.50");
+    }
+
+    #[test]
+    fn test_payment_token_parse() {
+        assert_eq!("usdc".parse::<PaymentToken>().unwrap(), PaymentToken::Usdc);
+        assert_eq!("USDT".parse::<PaymentToken>().unwrap(), PaymentToken::Usdt);
+        assert_eq!("SOL".parse::<PaymentToken>().unwrap(), PaymentToken::Sol);
+        let err = "BONK".parse::<PaymentToken>().unwrap_err();
+        assert!(matches!(err, crate::X402Error::UnsupportedToken(_)));
+        assert!(err.to_string().contains("BONK"));
+    }
+
+    #[test]
+    fn test_payment_token_mints() {
+        assert_eq!(
+            PaymentToken::Usdc.mint("mainnet-beta"),
+            Some(USDC_MINT_MAINNET)
+        );
+        assert_eq!(PaymentToken::Usdc.mint("devnet"), Some(USDC_MINT_DEVNET));
+        assert_eq!(PaymentToken::Usdt.mint("mainnet-beta"), Some(USDT_MINT));
+        assert_eq!(PaymentToken::Sol.mint("mainnet-beta"), None);
+    }
+
+    #[test]
+    fn test_verify_request_validation() {
+        let request =
+            |evidence_id: &str, chain: Option<&str>, count: Option<u32>| VerifyEvidenceRequest {
+                evidence_id: evidence_id.to_string(),
+                chain: chain.map(str::to_string),
+                tier: PriceTier::Basic,
+                count,
+            };
+
+        assert!(request("ev-1", None, None).validate().is_ok());
+        assert!(request("ev-1", Some("etherlink"), Some(3))
+            .validate()
+            .is_ok());
+
+        for invalid in [
+            request("", None, None),
+            request("  ", None, None),
+            request("ev-1", Some("bitcoin"), None),
+            request("ev-1", Some(""), None),
+            request("ev-1", None, Some(0)),
+        ] {
+            let err = invalid.validate().unwrap_err();
+            assert!(matches!(err, crate::X402Error::InvalidRequest(_)));
+            assert!(err.is_client_error());
+        }
+        assert!(request("ev-1", Some("bitcoin"), None)
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("solana, etherlink"));
+    }
+
+    #[test]
+    fn test_bulk_request_validation() {
+        let request = |ids: &[&str], chain: Option<&str>| VerifyEvidenceBulkRequest {
+            evidence_ids: ids.iter().map(|id| id.to_string()).collect(),
+            chain: chain.map(str::to_string),
+        };
+        let full: Vec<String> = (0..MAX_BULK_EVIDENCE_IDS)
+            .map(|i| format!("ev-{}", i))
+            .collect();
+        let full: Vec<&str> = full.iter().map(String::as_str).collect();
+
+        assert!(request(&["ev-1", "ev-2"], Some("solana"))
+            .validate()
+            .is_ok());
+        assert_eq!(request(&full, None).count(), 500);
+        assert!(request(&full, None).validate().is_ok());
+
+        let mut over = full.clone();
+        over.push("ev-extra");
+        for invalid in [
+            request(&[], None),
+            request(&over, None),
+            request(&["ev-1", " "], None),
+            request(&["ev-1", "ev-1"], None),
+            request(&["ev-1"], Some("bitcoin")),
+        ] {
+            let err = invalid.validate().unwrap_err();
+            assert!(matches!(err, crate::X402Error::InvalidRequest(_)));
+        }
+    }
+
     #[test]
     fn test_payment_details_for_evidence() {
         let details = PaymentDetails::for_evidence(
@@ -251,11 +843,14 @@ mod tests {
     #[test]
     fn test_payment_proof_roundtrip() {
         let proof = PaymentProof {
-            signature: "5xKj789abc".to_string(),
+            signature: SIGNATURE.to_string(),
             amount: "0.01".to_string(),
             token: "USDC".to_string(),
-            sender: "sender123".to_string(),
+            sender: SENDER.to_string(),
+            mint: None,
             memo: "evidence:evt-001".to_string(),
+            expires_at: None,
+            quote_signature: None,
             timestamp: "2025-11-28T10:00:00Z".to_string(),
         };
 
@@ -266,4 +861,41 @@ mod tests {
         assert_eq!(decoded.amount, proof.amount);
         assert_eq!(decoded.memo, proof.memo);
     }
+
+    #[test]
+    fn test_payment_proof_rejects_malformed_signature_and_sender() {
+        let valid = PaymentProof {
+            signature: SIGNATURE.to_string(),
+            amount: "0.01".to_string(),
+            token: "USDC".to_string(),
+            sender: SENDER.to_string(),
+            mint: None,
+            memo: "evidence:evt-001".to_string(),
+            expires_at: None,
+            quote_signature: None,
+            timestamp: "2025-11-28T10:00:00Z".to_string(),
+        };
+        assert!(valid.validate().is_ok());
+
+        for signature in ["5xKj789abc", "sig-0OIl", "", &SIGNATURE[..40]] {
+            let proof = PaymentProof {
+                signature: signature.to_string(),
+                ..valid.clone()
+            };
+            let err = PaymentProof::from_header(&proof.to_header().unwrap()).unwrap_err();
+            assert!(
+                matches!(err, crate::X402Error::InvalidProof(_)),
+                "{:?}",
+                err
+            );
+            assert!(err.to_string().contains("signature"), "{}", err);
+        }
+
+        let proof = PaymentProof {
+            sender: "sender-wallet".to_string(),
+            ..valid
+        };
+        let err = proof.validate().unwrap_err();
+        assert!(err.to_string().contains("sender"), "{}", err);
+    }
 }
@@ -0,0 +1,118 @@
+//! Facilitator HTTP behaviour: timeouts surface as their own error and
+//! transient 5xx answers are retried.
+
+use axum::{extract::State, http::StatusCode, routing::post, Json, Router};
+use phoenix_x402::types::USDC_MINT_MAINNET;
+use phoenix_x402::*;
+use serde_json::json;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Start a mock facilitator whose `/verify` waits `delay`, then answers 503
+/// to the first `failures` calls and a confirmed payment after that
+async fn mock_facilitator(delay: Duration, failures: usize) -> (String, Arc<AtomicUsize>) {
+    let hits = Arc::new(AtomicUsize::new(0));
+    let app = Router::new()
+        .route(
+            "/verify",
+            post(move |State(hits): State<Arc<AtomicUsize>>| async move {
+                let call = hits.fetch_add(1, Ordering::SeqCst);
+                tokio::time::sleep(delay).await;
+                if call < failures {
+                    return Err(StatusCode::SERVICE_UNAVAILABLE);
+                }
+                Ok(Json(json!({
+                    "valid": true,
+                    "amount": "0.05",
+                    "block": 123456,
+                    "confirmed_at": "2025-11-28T10:00:00Z"
+                })))
+            }),
+        )
+        .with_state(hits.clone());
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    (url, hits)
+}
+
+fn config(url: &str) -> X402Config {
+    let mut config = X402Config::mainnet("PhxRvkWallet");
+    config.facilitator_url = url.to_string();
+    config
+}
+
+/// Proof paying a quote `facilitator` signed, valid until after the mock's
+/// block time
+fn proof(facilitator: &X402Facilitator) -> PaymentProof {
+    let mut quote =
+        PaymentDetails::for_evidence("evt-001", PriceTier::MultiChain, "PhxRvkWallet", "");
+    quote.expires_at = Some("2025-11-28T10:05:00Z".to_string());
+    PaymentProof {
+        signature: "sig-client".to_string(),
+        amount: "0.05".to_string(),
+        token: "USDC".to_string(),
+        mint: Some(USDC_MINT_MAINNET.to_string()),
+        sender: "sender-wallet".to_string(),
+        memo: "evidence:evt-001".to_string(),
+        expires_at: None,
+        quote_signature: None,
+        timestamp: "2025-11-28T10:00:00Z".to_string(),
+    }
+    .for_quote(&facilitator.sign_quote(quote))
+}
+
+async fn verify(facilitator: &X402Facilitator) -> Result<PaymentVerification, X402Error> {
+    facilitator
+        .verify_payment(
+            &proof(facilitator),
+            "evidence:evt-001",
+            &"0.05".parse().unwrap(),
+        )
+        .await
+}
+
+#[tokio::test]
+async fn slow_facilitator_is_a_timeout_not_an_invalid_payment() {
+    let (url, hits) = mock_facilitator(Duration::from_secs(5), 0).await;
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_millis(200))
+        .build()
+        .unwrap();
+    let facilitator = X402Facilitator::with_client(config(&url), client);
+
+    let err = verify(&facilitator).await.unwrap_err();
+    assert!(matches!(err, X402Error::FacilitatorTimeout(_)), "{:?}", err);
+    assert!(err.is_timeout());
+    assert!(!err.is_client_error());
+    // Timeouts are not retried
+    assert_eq!(hits.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn transient_server_errors_are_retried() {
+    let (url, hits) = mock_facilitator(Duration::ZERO, 2).await;
+    let facilitator = X402Facilitator::new(config(&url));
+
+    let verification = verify(&facilitator).await.unwrap();
+    assert!(verification.valid);
+    assert_eq!(hits.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn server_errors_fail_once_retries_run_out() {
+    let (url, hits) = mock_facilitator(Duration::ZERO, usize::MAX).await;
+    let mut config = config(&url);
+    config.facilitator_retries = 1;
+    let facilitator = X402Facilitator::new(config);
+
+    let err = verify(&facilitator).await.unwrap_err();
+    assert!(matches!(err, X402Error::NetworkError(_)), "{:?}", err);
+    assert!(err.to_string().contains("503"), "{}", err);
+    assert!(!err.is_timeout());
+    assert_eq!(hits.load(Ordering::SeqCst), 2);
+}
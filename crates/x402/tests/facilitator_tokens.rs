@@ -0,0 +1,462 @@
+//! Payment verification against a mocked facilitator: token paths, quote
+//! expiry and the verified-payment cache.
+//!
+//! Each test starts a local HTTP server standing in for the facilitator's
+//! `/verify` endpoint, records what the client sent, and answers with a
+//! canned response, including the payment's block time.
+
+use axum::{extract::State, routing::post, Json, Router};
+use phoenix_x402::types::{USDC_MINT_MAINNET, USDT_MINT};
+use phoenix_x402::*;
+use serde_json::{json, Value};
+use std::sync::{Arc, Mutex};
+
+/// Requests received by the mock facilitator
+type Received = Arc<Mutex<Vec<Value>>>;
+
+/// Start a mock facilitator answering every `/verify` call with `response`
+async fn mock_facilitator(response: Value) -> (String, Received) {
+    let received: Received = Arc::default();
+    let app =
+        Router::new()
+            .route(
+                "/verify",
+                post(
+                    |State((received, response)): State<(Received, Value)>,
+                     Json(body): Json<Value>| async move {
+                        received.lock().unwrap().push(body);
+                        Json(response)
+                    },
+                ),
+            )
+            .with_state((received.clone(), response));
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    (url, received)
+}
+
+/// Expiry of the quotes the proofs below pay, after the mock's block times
+const QUOTE_EXPIRES_AT: &str = "2025-11-28T10:05:00Z";
+
+/// Signer shared by the facilitators and the quotes they are shown
+fn quote_signer() -> QuoteSigner {
+    QuoteSigner::new("facilitator-tokens-quote-secret")
+}
+
+/// Mainnet facilitator client pointed at `url`, with SOL priced at $150
+fn facilitator(url: &str) -> X402Facilitator {
+    let mut config = X402Config::mainnet("PhxRvkWallet");
+    config.facilitator_url = url.to_string();
+    config.sol_price_usdc = Some("150".to_string());
+    X402Facilitator::new(config).with_quote_signer(quote_signer())
+}
+
+fn usdc(amount: &str) -> UsdcAmount {
+    amount.parse().unwrap()
+}
+
+fn proof(token: &str, amount: &str, mint: Option<&str>) -> PaymentProof {
+    PaymentProof {
+        signature: format!("sig-{}", token.to_lowercase()),
+        amount: amount.to_string(),
+        token: token.to_string(),
+        mint: mint.map(str::to_string),
+        sender: "sender-wallet".to_string(),
+        memo: "evidence:evt-001".to_string(),
+        expires_at: Some(QUOTE_EXPIRES_AT.to_string()),
+        quote_signature: Some(quote_signer().sign("evidence:evt-001", QUOTE_EXPIRES_AT)),
+        timestamp: "2025-11-28T10:00:00Z".to_string(),
+    }
+}
+
+fn confirmed(amount: &str) -> Value {
+    json!({
+        "valid": true,
+        "amount": amount,
+        "block": 123456,
+        "confirmed_at": "2025-11-28T10:00:01Z"
+    })
+}
+
+#[tokio::test]
+async fn usdc_payment_checks_usdc_mint_transfer() {
+    let (url, received) = mock_facilitator(confirmed("0.05")).await;
+
+    let verification = facilitator(&url)
+        .verify_payment(
+            &proof("USDC", "0.05", Some(USDC_MINT_MAINNET)),
+            "evidence:evt-001",
+            &usdc("0.05"),
+        )
+        .await
+        .unwrap();
+
+    assert!(verification.valid);
+    assert_eq!(verification.amount_usdc, "0.05");
+    let sent = received.lock().unwrap()[0].clone();
+    assert_eq!(sent["token"], "USDC");
+    assert_eq!(sent["mint"], USDC_MINT_MAINNET);
+    assert_eq!(sent["min_amount"], "0.05");
+    assert_eq!(sent["expected_recipient"], "PhxRvkWallet");
+}
+
+#[tokio::test]
+async fn usdt_payment_checks_usdt_mint_transfer() {
+    let (url, received) = mock_facilitator(confirmed("0.05")).await;
+
+    let verification = facilitator(&url)
+        .verify_payment(
+            &proof("usdt", "0.05", None),
+            "evidence:evt-001",
+            &usdc("0.05"),
+        )
+        .await
+        .unwrap();
+
+    assert!(verification.valid);
+    assert_eq!(verification.amount_usdc, "0.05");
+    let sent = received.lock().unwrap()[0].clone();
+    assert_eq!(sent["token"], "USDT");
+    assert_eq!(sent["mint"], USDT_MINT);
+    assert_eq!(sent["min_amount"], "0.05");
+}
+
+#[tokio::test]
+async fn sol_payment_is_a_native_transfer_of_equivalent_value() {
+    let (url, received) = mock_facilitator(confirmed("0.0002")).await;
+
+    let verification = facilitator(&url)
+        .verify_payment(
+            &proof("SOL", "0.0002", None),
+            "evidence:evt-001",
+            &usdc("0.03"),
+        )
+        .await
+        .unwrap();
+
+    assert!(verification.valid);
+    // 0.0002 SOL at $150 is worth the $0.03 price
+    assert_eq!(verification.amount_usdc, "0.03");
+    let sent = received.lock().unwrap()[0].clone();
+    assert_eq!(sent["token"], "SOL");
+    assert_eq!(sent["min_amount"], "0.0002");
+    assert!(sent.get("mint").is_none(), "native SOL has no mint");
+}
+
+#[tokio::test]
+async fn facilitator_rejection_is_reported() {
+    let (url, _) = mock_facilitator(json!({
+        "valid": false,
+        "error": "transfer below min_amount"
+    }))
+    .await;
+
+    let verification = facilitator(&url)
+        .verify_payment(
+            &proof("USDT", "0.01", None),
+            "evidence:evt-001",
+            &usdc("0.05"),
+        )
+        .await
+        .unwrap();
+
+    assert!(!verification.valid);
+    assert_eq!(
+        verification.error.as_deref(),
+        Some("transfer below min_amount")
+    );
+}
+
+#[tokio::test]
+async fn unsupported_token_is_rejected_before_facilitator() {
+    let (url, received) = mock_facilitator(confirmed("0.05")).await;
+
+    let err = facilitator(&url)
+        .verify_payment(
+            &proof("BONK", "0.05", None),
+            "evidence:evt-001",
+            &usdc("0.05"),
+        )
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, X402Error::UnsupportedToken(_)));
+    assert!(err.to_string().contains("BONK"), "{}", err);
+    assert!(err.is_client_error());
+    assert!(received.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn over_precise_usdc_amount_is_rejected_before_facilitator() {
+    let (url, received) = mock_facilitator(confirmed("0.05")).await;
+
+    let err = facilitator(&url)
+        .verify_payment(
+            &proof("USDC", "0.0500001", Some(USDC_MINT_MAINNET)),
+            "evidence:evt-001",
+            &usdc("0.05"),
+        )
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, X402Error::InvalidAmount(_)));
+    assert!(err.is_client_error());
+    assert!(received.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn sol_without_configured_price_is_rejected() {
+    let (url, received) = mock_facilitator(confirmed("0.0002")).await;
+    let mut config = X402Config::mainnet("PhxRvkWallet");
+    config.facilitator_url = url;
+
+    let err = X402Facilitator::new(config)
+        .with_quote_signer(quote_signer())
+        .verify_payment(
+            &proof("SOL", "0.0002", None),
+            "evidence:evt-001",
+            &usdc("0.03"),
+        )
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, X402Error::UnsupportedToken(_)));
+    assert!(received.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn mismatched_mint_is_rejected() {
+    let (url, received) = mock_facilitator(confirmed("0.05")).await;
+
+    // A USDC claim carrying the USDT mint, and a SOL claim carrying any mint
+    for bad in [
+        proof("USDC", "0.05", Some(USDT_MINT)),
+        proof("SOL", "0.0002", Some(USDC_MINT_MAINNET)),
+    ] {
+        let err = facilitator(&url)
+            .verify_payment(&bad, "evidence:evt-001", &usdc("0.03"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, X402Error::InvalidProof(_)), "{}", err);
+    }
+    assert!(received.lock().unwrap().is_empty());
+}
+
+#[test]
+fn payment_details_quote_every_supported_token() {
+    let mut config = X402Config::mainnet("PhxRvkWallet");
+    config.sol_price_usdc = Some("150".to_string());
+
+    let details = PaymentDetails::for_evidence(
+        "evt-001",
+        PriceTier::MultiChain,
+        "PhxRvkWallet",
+        "https://x402.org/facilitator",
+    )
+    .with_token_quotes(&config);
+
+    assert_eq!(details.supported_tokens, vec!["USDC", "USDT", "SOL"]);
+    assert_eq!(details.token_prices["USDC"], "0.05");
+    assert_eq!(details.token_prices["USDT"], "0.05");
+    assert_eq!(details.token_prices["SOL"], "0.000333334");
+    assert_eq!(details.token_mints["USDC"], USDC_MINT_MAINNET);
+    assert_eq!(details.token_mints["USDT"], USDT_MINT);
+    assert!(!details.token_mints.contains_key("SOL"));
+
+    // Without a SOL price, SOL is neither quoted nor advertised
+    let unpriced = PaymentDetails::for_evidence(
+        "evt-001",
+        PriceTier::MultiChain,
+        "PhxRvkWallet",
+        "https://x402.org/facilitator",
+    )
+    .with_token_quotes(&X402Config::mainnet("PhxRvkWallet"));
+    assert_eq!(unpriced.supported_tokens, vec!["USDC", "USDT"]);
+}
+
+fn confirmed_at(block_time: &str) -> Value {
+    json!({
+        "valid": true,
+        "amount": "0.05",
+        "block": 123456,
+        "confirmed_at": block_time
+    })
+}
+
+fn quoted_proof(expires_at: &str) -> PaymentProof {
+    PaymentProof {
+        expires_at: Some(expires_at.to_string()),
+        quote_signature: Some(quote_signer().sign("evidence:evt-001", expires_at)),
+        ..proof("USDC", "0.05", None)
+    }
+}
+
+#[tokio::test]
+async fn payment_inside_quote_window_is_accepted() {
+    let (url, _) = mock_facilitator(confirmed_at("2025-11-28T10:04:59Z")).await;
+
+    let verification = facilitator(&url)
+        .verify_payment(
+            &quoted_proof("2025-11-28T10:05:00Z"),
+            "evidence:evt-001",
+            &usdc("0.05"),
+        )
+        .await
+        .unwrap();
+
+    assert!(verification.valid);
+}
+
+#[tokio::test]
+async fn payment_after_quote_expiry_is_rejected() {
+    let (url, _) = mock_facilitator(confirmed_at("2025-11-28T10:05:01Z")).await;
+
+    let err = facilitator(&url)
+        .verify_payment(
+            &quoted_proof("2025-11-28T10:05:00Z"),
+            "evidence:evt-001",
+            &usdc("0.05"),
+        )
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, X402Error::PaymentExpired(_)), "{}", err);
+    assert!(err.is_payment_required());
+    assert!(err.to_string().contains("expired quote"), "{}", err);
+}
+
+#[tokio::test]
+async fn malformed_quote_expiry_is_rejected() {
+    let (url, received) = mock_facilitator(confirmed_at("2025-11-28T10:00:00Z")).await;
+
+    let err = facilitator(&url)
+        .verify_payment(&quoted_proof("tomorrow"), "evidence:evt-001", &usdc("0.05"))
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, X402Error::InvalidProof(_)), "{}", err);
+    assert!(received.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn proof_without_signed_quote_expiry_is_rejected() {
+    let (url, received) = mock_facilitator(confirmed("0.05")).await;
+    let facilitator = facilitator(&url);
+
+    let missing_expiry = PaymentProof {
+        expires_at: None,
+        ..proof("USDC", "0.05", None)
+    };
+    // Signed by another server, or for another quote
+    let foreign = PaymentProof {
+        quote_signature: Some(
+            QuoteSigner::new("someone-else").sign("evidence:evt-001", QUOTE_EXPIRES_AT),
+        ),
+        ..proof("USDC", "0.05", None)
+    };
+    let extended = PaymentProof {
+        expires_at: Some("2099-01-01T00:00:00Z".to_string()),
+        ..proof("USDC", "0.05", None)
+    };
+
+    for bad in [missing_expiry, foreign, extended] {
+        let err = facilitator
+            .verify_payment(&bad, "evidence:evt-001", &usdc("0.05"))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, X402Error::InvalidProof(_)), "{}", err);
+    }
+    assert!(received.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn unparseable_block_time_is_rejected() {
+    let (url, _) = mock_facilitator(confirmed_at("block 123456")).await;
+
+    let err = facilitator(&url)
+        .verify_payment(
+            &quoted_proof(QUOTE_EXPIRES_AT),
+            "evidence:evt-001",
+            &usdc("0.05"),
+        )
+        .await
+        .unwrap_err();
+
+    assert!(matches!(err, X402Error::VerificationFailed(_)), "{}", err);
+}
+
+#[tokio::test]
+async fn repeated_verification_within_ttl_is_served_from_cache() {
+    let (url, received) = mock_facilitator(confirmed("0.05")).await;
+    let facilitator = facilitator(&url);
+    let payment = proof("USDC", "0.05", None);
+
+    let first = facilitator
+        .verify_payment(&payment, "evidence:evt-001", &usdc("0.05"))
+        .await
+        .unwrap();
+    // A clone shares the cache, as the API's per-request state clones do
+    let second = facilitator
+        .clone()
+        .verify_payment(&payment, "evidence:evt-001", &usdc("0.05"))
+        .await
+        .unwrap();
+
+    assert!(first.valid && second.valid);
+    assert_eq!(second.tx_signature, first.tx_signature);
+    assert_eq!(received.lock().unwrap().len(), 1, "second verify hit HTTP");
+
+    // A different question about the same transaction is asked afresh
+    let other_quote = PaymentProof {
+        quote_signature: Some(quote_signer().sign("evidence:evt-002", QUOTE_EXPIRES_AT)),
+        ..payment
+    };
+    facilitator
+        .verify_payment(&other_quote, "evidence:evt-002", &usdc("0.05"))
+        .await
+        .unwrap();
+    assert_eq!(received.lock().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn failed_verification_is_not_cached() {
+    let (url, received) = mock_facilitator(json!({
+        "valid": false,
+        "amount": "0.05",
+        "error": "transaction not yet confirmed"
+    }))
+    .await;
+    let facilitator = facilitator(&url);
+    let payment = proof("USDC", "0.05", None);
+
+    for _ in 0..2 {
+        let verification = facilitator
+            .verify_payment(&payment, "evidence:evt-001", &usdc("0.05"))
+            .await
+            .unwrap();
+        assert!(!verification.valid);
+    }
+    assert_eq!(received.lock().unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn disabled_cache_always_asks_facilitator() {
+    let (url, received) = mock_facilitator(confirmed("0.05")).await;
+    let mut config = X402Config::mainnet("PhxRvkWallet");
+    config.facilitator_url = url;
+    config.verify_cache_ttl_secs = 0;
+    let facilitator = X402Facilitator::new(config).with_quote_signer(quote_signer());
+    let payment = proof("USDC", "0.05", None);
+
+    for _ in 0..2 {
+        facilitator
+            .verify_payment(&payment, "evidence:evt-001", &usdc("0.05"))
+            .await
+            .unwrap();
+    }
+    assert_eq!(received.lock().unwrap().len(), 2);
+}
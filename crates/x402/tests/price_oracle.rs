@@ -0,0 +1,131 @@
+//! Live SOL quotes from a price oracle: a mocked simple price endpoint sets
+//! the rate, quotes and settlement follow it, and fetched prices are cached.
+
+use axum::{extract::Query, extract::State, routing::get, Json, Router};
+use phoenix_x402::*;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Start a mock price endpoint quoting SOL at `sol_usd`, counting requests
+async fn mock_prices(sol_usd: f64) -> (String, Arc<AtomicUsize>) {
+    let hits = Arc::new(AtomicUsize::new(0));
+    let app = Router::new()
+        .route(
+            "/simple/price",
+            get(
+                move |State(hits): State<Arc<AtomicUsize>>,
+                      Query(params): Query<HashMap<String, String>>| async move {
+                    hits.fetch_add(1, Ordering::SeqCst);
+                    assert_eq!(params["vs_currencies"], "usd");
+                    Json(json!({ params["ids"].clone(): { "usd": sol_usd } }))
+                },
+            ),
+        )
+        .with_state(hits.clone());
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/simple/price", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    (url, hits)
+}
+
+#[tokio::test]
+async fn http_oracle_prices_sol_and_caches() {
+    let (url, hits) = mock_prices(125.0).await;
+    let oracle = HttpPriceOracle::new(url, Duration::from_secs(60));
+
+    // $0.05 at $125/SOL
+    assert_eq!(
+        oracle
+            .usd_to_token("0.05", PaymentToken::Sol)
+            .await
+            .unwrap(),
+        "0.0004"
+    );
+    assert_eq!(
+        oracle
+            .usd_to_token("1.00", PaymentToken::Sol)
+            .await
+            .unwrap(),
+        "0.008"
+    );
+    assert_eq!(hits.load(Ordering::SeqCst), 1);
+
+    // Stablecoins never hit the endpoint
+    assert_eq!(
+        oracle
+            .usd_to_token("0.05", PaymentToken::Usdc)
+            .await
+            .unwrap(),
+        "0.05"
+    );
+    assert_eq!(hits.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn facilitator_quotes_and_settles_at_oracle_price() {
+    let (url, _) = mock_prices(125.0).await;
+    let mut config = X402Config::devnet("PhxRvkWallet");
+    // Stale static price, replaced by the oracle's
+    config.sol_price_usdc = Some("150".to_string());
+    config.price_oracle_url = Some(url);
+    let facilitator = X402Facilitator::new(config);
+
+    let pricing = facilitator.pricing_config().await;
+    let details = PaymentDetails::for_evidence(
+        "evt-oracle",
+        PriceTier::MultiChain,
+        "PhxRvkWallet",
+        "https://x402.org/facilitator",
+    )
+    .with_token_quotes(&pricing)
+    .with_expiry(300);
+    let details = facilitator.sign_quote(details);
+    assert_eq!(details.token_prices["SOL"], "0.0004");
+
+    let proof = PaymentProof {
+        signature: "sig-oracle-sol".to_string(),
+        amount: "0.0004".to_string(),
+        token: "SOL".to_string(),
+        mint: None,
+        sender: "sender-wallet".to_string(),
+        memo: "evidence:evt-oracle".to_string(),
+        expires_at: None,
+        quote_signature: None,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    }
+    .for_quote(&details);
+    let verification = facilitator
+        .verify_payment(&proof, "evidence:evt-oracle", &"0.05".parse().unwrap())
+        .await
+        .unwrap();
+    assert!(verification.valid);
+    assert_eq!(verification.amount_usdc, "0.05");
+}
+
+#[tokio::test]
+async fn unreachable_oracle_falls_back_to_configured_price() {
+    let mut config = X402Config::devnet("PhxRvkWallet");
+    config.sol_price_usdc = Some("150".to_string());
+    let facilitator = X402Facilitator::new(config).with_price_oracle(Arc::new(
+        HttpPriceOracle::new("http://127.0.0.1:1/price", Duration::from_secs(60)),
+    ));
+
+    let pricing = facilitator.pricing_config().await;
+    assert_eq!(pricing.sol_price_usdc.as_deref(), Some("150"));
+
+    let fixed = X402Facilitator::new(X402Config::devnet("PhxRvkWallet")).with_price_oracle(
+        Arc::new(FixedRateOracle::new().with_rate(PaymentToken::Sol, "200")),
+    );
+    let pricing = fixed.pricing_config().await;
+    assert_eq!(
+        pricing.required_amount(PaymentToken::Sol, "1.00").unwrap(),
+        "0.005"
+    );
+    assert!(pricing.supported_tokens().contains(&PaymentToken::Sol));
+}
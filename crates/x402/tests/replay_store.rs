@@ -0,0 +1,51 @@
+//! One-time redemption of payments through the facilitator's replay store
+
+use phoenix_x402::*;
+use std::sync::Arc;
+
+fn receipt(signature: &str) -> PaymentReceipt {
+    PaymentReceipt {
+        tx_signature: signature.to_string(),
+        evidence_id: "evt-001".to_string(),
+        tier: PriceTier::Basic,
+        token: "USDC".to_string(),
+        token_amount: "0.01".to_string(),
+        amount_usdc: "0.01".to_string(),
+        overpaid_usdc: None,
+        sender_wallet: Some("sender123".to_string()),
+    }
+}
+
+#[tokio::test]
+async fn test_second_redemption_is_rejected() {
+    let store = Arc::new(InMemoryReplayStore::new());
+    let facilitator =
+        X402Facilitator::new(X402Config::devnet("PhxRvk123")).with_replay_store(store.clone());
+
+    assert!(!facilitator.is_payment_used("sig-1").await.unwrap());
+    facilitator.record_payment(&receipt("sig-1")).await.unwrap();
+    assert!(facilitator.is_payment_used("sig-1").await.unwrap());
+
+    // Clones share the store, as concurrent request handlers do
+    let err = facilitator
+        .clone()
+        .record_payment(&receipt("sig-1"))
+        .await
+        .unwrap_err();
+    assert!(matches!(err, ReplayError::AlreadyUsed(ref sig) if sig == "sig-1"));
+
+    // Other payments are unaffected; the first receipt is kept
+    facilitator.record_payment(&receipt("sig-2")).await.unwrap();
+    assert_eq!(store.get("sig-1"), Some(receipt("sig-1")));
+}
+
+#[tokio::test]
+async fn test_default_store_is_per_facilitator() {
+    let config = X402Config::devnet("PhxRvk123");
+    let first = X402Facilitator::new(config.clone());
+    let second = X402Facilitator::new(config);
+
+    first.record_payment(&receipt("sig-1")).await.unwrap();
+    assert!(first.is_payment_used("sig-1").await.unwrap());
+    assert!(!second.is_payment_used("sig-1").await.unwrap());
+}
@@ -42,6 +42,8 @@ fn error_is_client_error_true_variants() {
     let cases: &[X402Error] = &[
         X402Error::InvalidProof("bad base64".to_string()),
         X402Error::UnsupportedToken("XYZ".to_string()),
+        X402Error::InvalidRequest("evidence_id must not be empty".to_string()),
+        X402Error::InvalidAmount("0.0000001 has more than 6 decimal places".to_string()),
     ];
 
     for err in cases {
@@ -65,6 +67,7 @@ fn error_neutral_variants_both_false() {
         X402Error::VerificationFailed("rpc timeout".to_string()),
         X402Error::NetworkError("connection refused".to_string()),
         X402Error::ConfigError("X402_WALLET_ADDRESS not set".to_string()),
+        X402Error::FacilitatorTimeout("operation timed out".to_string()),
     ];
 
     for err in cases {
@@ -158,7 +161,11 @@ fn payment_details_for_evidence_basic_tier() {
     assert!(details.supported_tokens.contains(&"USDC".to_string()));
     assert!(details.supported_tokens.contains(&"USDT".to_string()));
     assert!(details.supported_tokens.contains(&"SOL".to_string()));
-    assert!(details.expires_at.is_none());
+    // Quotes expire after the default five-minute TTL
+    let expires_at =
+        chrono::DateTime::parse_from_rfc3339(details.expires_at.as_deref().unwrap()).unwrap();
+    let ttl = expires_at.signed_duration_since(chrono::Utc::now());
+    assert!(ttl > chrono::Duration::seconds(290) && ttl <= chrono::Duration::seconds(300));
 }
 
 #[test]
@@ -205,55 +212,64 @@ fn payment_details_for_evidence_bulk_tier() {
 // PaymentProof encoding edge cases
 // ---------------------------------------------------------------------------
 
+/// Well-formed transaction signature (64 bytes, base58)
+const SIGNATURE: &str =
+    "4dittWHeWjDsBMhV2ryq6TyJzTTf5oDUz59tGf2xQs1GsRBHgLnh5hUhJ6k7ZZuKDyH1KhZB8FoELVczof26ZP3o";
+
 fn make_proof(signature: &str, amount: &str, memo: &str) -> PaymentProof {
     PaymentProof {
         signature: signature.to_string(),
         amount: amount.to_string(),
         token: "USDC".to_string(),
-        sender: "sender-wallet".to_string(),
+        mint: None,
+        sender: "gsGBZpMXkp6VsXpe6t81fa2SAnKKkeVBZ8mucAAy7qb".to_string(),
         memo: memo.to_string(),
+        expires_at: None,
+        quote_signature: None,
         timestamp: "2025-11-28T10:00:00Z".to_string(),
     }
 }
 
 #[test]
 fn payment_proof_roundtrip_empty_strings() {
-    let proof = make_proof("", "", "");
+    let proof = make_proof(SIGNATURE, "", "");
     let encoded = proof.to_header().unwrap();
     let decoded = PaymentProof::from_header(&encoded).unwrap();
 
-    assert_eq!(decoded.signature, "");
     assert_eq!(decoded.amount, "");
     assert_eq!(decoded.memo, "");
+
+    // An empty signature is not a payment
+    let encoded = make_proof("", "", "").to_header().unwrap();
+    assert!(matches!(
+        PaymentProof::from_header(&encoded),
+        Err(X402Error::InvalidProof(_))
+    ));
 }
 
 #[test]
 fn payment_proof_roundtrip_special_characters() {
     // Memo with colons, slashes, unicode — all must survive the base64/JSON round-trip.
-    let proof = make_proof(
-        "sig/with+special=chars",
-        "0.01",
-        "evidence:evt-2025/drone\u{1F680}",
-    );
+    let proof = make_proof(SIGNATURE, "0.01", "evidence:evt-2025/drone\u{1F680}");
 
     let encoded = proof.to_header().unwrap();
     let decoded = PaymentProof::from_header(&encoded).unwrap();
 
-    assert_eq!(decoded.signature, "sig/with+special=chars");
+    assert_eq!(decoded.signature, SIGNATURE);
     assert_eq!(decoded.memo, "evidence:evt-2025/drone\u{1F680}");
 }
 
 #[test]
 fn payment_proof_roundtrip_large_payload() {
-    // Signature that is 10 KiB of repeated 'a' — tests no size limit in encoding.
-    let large_sig = "a".repeat(10_240);
-    let proof = make_proof(&large_sig, "0.005", "evidence:bulk-batch");
+    // Memo that is 10 KiB of repeated 'a' — tests no size limit in encoding.
+    let large_memo = "a".repeat(10_240);
+    let proof = make_proof(SIGNATURE, "0.005", &large_memo);
 
     let encoded = proof.to_header().unwrap();
     let decoded = PaymentProof::from_header(&encoded).unwrap();
 
-    assert_eq!(decoded.signature, large_sig);
-    assert_eq!(decoded.memo, "evidence:bulk-batch");
+    assert_eq!(decoded.signature, SIGNATURE);
+    assert_eq!(decoded.memo, large_memo);
 }
 
 // ---------------------------------------------------------------------------
@@ -373,7 +389,7 @@ fn extract_payment_proof_malformed_header_returns_err() {
 
 #[test]
 fn extract_payment_proof_valid_header_returns_proof() {
-    let proof = make_proof("extract-sig-456", "0.05", "evidence:integ-test");
+    let proof = make_proof(SIGNATURE, "0.05", "evidence:integ-test");
     let encoded = proof.to_header().unwrap();
 
     let mut headers = HeaderMap::new();
@@ -386,7 +402,7 @@ fn extract_payment_proof_valid_header_returns_proof() {
     assert!(result.is_some());
 
     let extracted = result.unwrap();
-    assert_eq!(extracted.signature, "extract-sig-456");
+    assert_eq!(extracted.signature, SIGNATURE);
     assert_eq!(extracted.amount, "0.05");
     assert_eq!(extracted.memo, "evidence:integ-test");
     assert_eq!(extracted.token, "USDC");
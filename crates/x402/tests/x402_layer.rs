@@ -0,0 +1,152 @@
+//! `X402Layer` wrapped around a plain axum handler: 402 without payment,
+//! 200 once paid, and one redemption per payment.
+
+use axum::{routing::get, Extension, Router};
+use phoenix_x402::*;
+use reqwest::StatusCode;
+use serde_json::Value;
+
+const WALLET: &str = "PhxRvkWallet";
+const MEMO: &str = "report:42";
+
+async fn spawn_app() -> String {
+    let layer = X402Layer::new(X402Config::devnet(WALLET), |parts| {
+        (parts.uri.path() == "/report")
+            .then(|| RoutePrice::new(MEMO, PriceTier::Basic, "0.05".parse().unwrap()))
+    });
+    let app = Router::new()
+        .route(
+            "/report",
+            get(
+                |Extension(payment): Extension<PaymentVerification>| async move {
+                    format!("report paid by {}", payment.tx_signature)
+                },
+            ),
+        )
+        .route("/free", get(|| async { "free" }))
+        .layer(layer);
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move {
+        axum::serve(listener, app).await.unwrap();
+    });
+    url
+}
+
+/// Well-formed transaction signature, distinct per `seed`
+fn signature(seed: u8) -> String {
+    bs58::encode([seed; 64]).into_string()
+}
+
+/// The 402 quote for `/report`
+async fn quote(client: &reqwest::Client, url: &str) -> PaymentDetails {
+    let response = client.get(format!("{}/report", url)).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::PAYMENT_REQUIRED);
+    response.json().await.unwrap()
+}
+
+/// Proof paying `quote`, echoing its signed expiry
+fn payment_header(signature: &str, amount: &str, quote: &PaymentDetails) -> String {
+    PaymentProof {
+        signature: signature.to_string(),
+        amount: amount.to_string(),
+        token: "USDC".to_string(),
+        mint: None,
+        sender: "gsGBZpMXkp6VsXpe6t81fa2SAnKKkeVBZ8mucAAy7qb".to_string(),
+        memo: MEMO.to_string(),
+        expires_at: None,
+        quote_signature: None,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    }
+    .for_quote(quote)
+    .to_header()
+    .unwrap()
+}
+
+#[tokio::test]
+async fn test_layer_requires_payment_then_serves() {
+    let url = spawn_app().await;
+    let client = reqwest::Client::new();
+
+    let details = quote(&client, &url).await;
+    assert_eq!(details.price, "0.05");
+    assert_eq!(details.memo, MEMO);
+    assert_eq!(details.recipient, WALLET);
+    assert!(details.expires_at.is_some());
+    assert!(details.quote_signature.is_some());
+
+    // Paying without echoing the signed quote is not accepted
+    let unquoted = PaymentDetails {
+        expires_at: None,
+        quote_signature: None,
+        ..details.clone()
+    };
+    let response = client
+        .get(format!("{}/report", url))
+        .header(
+            "X-PAYMENT",
+            payment_header(&signature(1), "0.05", &unquoted),
+        )
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = client
+        .get(format!("{}/report", url))
+        .header("X-PAYMENT", payment_header(&signature(1), "0.05", &details))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.text().await.unwrap(),
+        format!("report paid by {}", signature(1))
+    );
+
+    // Unpriced routes pass through untouched
+    let response = client.get(format!("{}/free", url)).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_layer_rejects_short_replayed_and_malformed_payments() {
+    let url = spawn_app().await;
+    let client = reqwest::Client::new();
+    let details = quote(&client, &url).await;
+    let get_report = |header: String| {
+        client
+            .get(format!("{}/report", url))
+            .header("X-PAYMENT", header)
+            .send()
+    };
+
+    let response = get_report(payment_header(&signature(2), "0.01", &details))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::PAYMENT_REQUIRED);
+    let body: Value = response.json().await.unwrap();
+    assert_eq!(body["verification"]["underpaid"], "0.04");
+    assert_eq!(body["payment_details"]["price"], "0.05");
+
+    let response = get_report(payment_header(&signature(3), "0.05", &details))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = get_report(payment_header(&signature(3), "0.05", &details))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    let response = get_report("not-base64!".to_string()).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // A signature that can't be a Solana transaction never reaches the facilitator
+    let response = get_report(payment_header("sig-once", "0.05", &details))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let body: Value = response.json().await.unwrap();
+    assert!(body["details"].as_str().unwrap().contains("signature"));
+}